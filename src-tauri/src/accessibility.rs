@@ -0,0 +1,432 @@
+//! Accessibility integration
+//!
+//! Screen-reader announcements: connection state changes and finished
+//! responses are announced so VoiceOver, NVDA and Orca users know what the
+//! assistant is doing without polling the UI:
+//! - macOS: posted natively as an `NSAccessibilityAnnouncementRequested`
+//!   notification
+//! - Windows/Linux: emitted as `a11y:announce` and spoken through the
+//!   frontend's live region, which WebView2 and WebKitGTK expose as UIA /
+//!   AT-SPI live-region events
+//!
+//! Repeats of the same message in quick succession (e.g. a reconnect loop)
+//! are announced once.
+//!
+//! OS preferences: reduce motion, increase contrast and the preferred text
+//! size are polled and emitted as `accessibility:changed`, since webviews
+//! don't reliably report them through CSS media queries. Increased contrast
+//! also switches the tray icon to its high-contrast variant, and a larger
+//! text size grows the Quick Ask window so its input isn't clipped (the
+//! window itself scales its text from `textScale` like the main window).
+
+use crate::error::CommandError;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::image::Image;
+use tauri::{AppHandle, Emitter, LogicalSize, Manager};
+
+/// Identical announcements within this window are dropped
+const REPEAT_WINDOW: Duration = Duration::from_secs(5);
+
+/// How often OS accessibility preferences are checked
+const PREFS_CHECK_INTERVAL_SECS: u64 = 15;
+
+/// Quick Ask window size at the default text size (as in tauri.conf.json)
+const QUICK_INPUT_SIZE: (f64, f64) = (600.0, 460.0);
+
+/// Largest text scale the Quick Ask window grows for
+const MAX_QUICK_INPUT_SCALE: f64 = 2.0;
+
+/// Share of the screen the grown Quick Ask window may cover
+const MAX_QUICK_INPUT_SCREEN_SHARE: f64 = 0.9;
+
+/// How urgently an announcement interrupts the screen reader
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    /// Read after the current utterance
+    Polite,
+    /// Interrupt the current utterance
+    Assertive,
+}
+
+/// Payload of `a11y:announce`
+#[derive(Debug, Clone, Serialize)]
+pub struct Announcement {
+    pub message: String,
+    pub priority: Priority,
+    /// Already announced natively; the frontend should not repeat it
+    pub native: bool,
+}
+
+/// OS accessibility preferences
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct AccessibilityPrefs {
+    #[serde(rename = "reduceMotion")]
+    pub reduce_motion: bool,
+    #[serde(rename = "increaseContrast")]
+    pub increase_contrast: bool,
+    /// Preferred text size relative to the default (1.0), where the OS has one
+    #[serde(rename = "textScale")]
+    pub text_scale: Option<f64>,
+}
+
+/// Accessibility state managed by Tauri
+#[derive(Default)]
+pub struct AccessibilityState {
+    last: Mutex<Option<(String, Instant)>>,
+    prefs: Mutex<AccessibilityPrefs>,
+}
+
+/// Whether a message should be announced, given the last announcement
+fn should_announce(last: Option<&(String, Instant)>, message: &str, now: Instant) -> bool {
+    match last {
+        Some((previous, at)) => previous != message || now.duration_since(*at) >= REPEAT_WINDOW,
+        None => true,
+    }
+}
+
+/// Announce a message to screen readers
+pub fn announce(app: &AppHandle, message: impl Into<String>, priority: Priority) {
+    let message = message.into();
+    if let Some(state) = app.try_state::<AccessibilityState>() {
+        let Ok(mut last) = state.last.lock() else {
+            return;
+        };
+        let now = Instant::now();
+        if !should_announce(last.as_ref(), &message, now) {
+            return;
+        }
+        *last = Some((message.clone(), now));
+    }
+
+    let native = announce_native(app, &message, priority);
+    let _ = app.emit(
+        "a11y:announce",
+        Announcement {
+            message,
+            priority,
+            native,
+        },
+    );
+}
+
+#[cfg(target_os = "macos")]
+fn announce_native(app: &AppHandle, message: &str, priority: Priority) -> bool {
+    let message = message.to_string();
+    app.run_on_main_thread(move || macos::announce(&message, priority))
+        .is_ok()
+}
+
+#[cfg(not(target_os = "macos"))]
+fn announce_native(_app: &AppHandle, _message: &str, _priority: Priority) -> bool {
+    false
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::Priority;
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+    use objc2_foundation::{NSNumber, NSString};
+
+    /// `NSAccessibilityPriorityMedium` / `NSAccessibilityPriorityHigh`
+    const PRIORITY_MEDIUM: isize = 50;
+    const PRIORITY_HIGH: isize = 90;
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {
+        static NSAccessibilityAnnouncementRequestedNotification: &'static NSString;
+        static NSAccessibilityAnnouncementKey: &'static NSString;
+        static NSAccessibilityPriorityKey: &'static NSString;
+        fn NSAccessibilityPostNotificationWithUserInfo(
+            element: *mut AnyObject,
+            notification: &NSString,
+            user_info: *mut AnyObject,
+        );
+    }
+
+    /// Post an announcement (main thread only)
+    pub fn announce(message: &str, priority: Priority) {
+        let message = NSString::from_str(message);
+        let priority = NSNumber::new_isize(match priority {
+            Priority::Polite => PRIORITY_MEDIUM,
+            Priority::Assertive => PRIORITY_HIGH,
+        });
+        // SAFETY: called on the main thread; the AppKit statics are valid for
+        // the process lifetime and the dictionary outlives the post call
+        unsafe {
+            let app: *mut AnyObject = msg_send![class!(NSApplication), sharedApplication];
+            if app.is_null() {
+                return;
+            }
+            let info: Retained<AnyObject> = msg_send![class!(NSMutableDictionary), new];
+            let _: () =
+                msg_send![&*info, setObject: &*message, forKey: NSAccessibilityAnnouncementKey];
+            let _: () =
+                msg_send![&*info, setObject: &*priority, forKey: NSAccessibilityPriorityKey];
+            NSAccessibilityPostNotificationWithUserInfo(
+                app,
+                NSAccessibilityAnnouncementRequestedNotification,
+                Retained::as_ptr(&info) as *mut AnyObject,
+            );
+        }
+    }
+}
+
+// ============================================================================
+// OS preferences
+// ============================================================================
+
+/// Trimmed output of `gsettings get`
+#[cfg(target_os = "linux")]
+fn gsettings(schema: &str, key: &str) -> Option<String> {
+    let output = std::process::Command::new("gsettings")
+        .args(["get", schema, key])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn read_prefs() -> AccessibilityPrefs {
+    let contrast_theme = gsettings("org.gnome.desktop.interface", "gtk-theme")
+        .is_some_and(|theme| theme.contains("HighContrast"));
+    AccessibilityPrefs {
+        reduce_motion: gsettings("org.gnome.desktop.interface", "enable-animations").as_deref()
+            == Some("false"),
+        increase_contrast: contrast_theme
+            || gsettings("org.gnome.desktop.a11y.interface", "high-contrast").as_deref()
+                == Some("true"),
+        text_scale: gsettings("org.gnome.desktop.interface", "text-scaling-factor")
+            .and_then(|factor| factor.parse().ok()),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn read_prefs() -> AccessibilityPrefs {
+    let universal_access = |key: &str| {
+        std::process::Command::new("defaults")
+            .args(["read", "com.apple.universalaccess", key])
+            .output()
+            .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).trim() == "1")
+    };
+    AccessibilityPrefs {
+        reduce_motion: universal_access("reduceMotion"),
+        increase_contrast: universal_access("increaseContrast"),
+        // macOS has no system-wide text size for desktop apps
+        text_scale: None,
+    }
+}
+
+/// Parse a REG_DWORD value from `reg query` output
+#[cfg(any(target_os = "windows", test))]
+fn parse_reg_dword(output: &str, name: &str) -> Option<u32> {
+    output.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        if parts.next()? != name || parts.next()? != "REG_DWORD" {
+            return None;
+        }
+        u32::from_str_radix(parts.next()?.trim_start_matches("0x"), 16).ok()
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn read_prefs() -> AccessibilityPrefs {
+    use windows_sys::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST,
+    };
+
+    let mut animations: i32 = 1;
+    let mut high_contrast: HIGHCONTRASTW = unsafe { std::mem::zeroed() };
+    high_contrast.cbSize = std::mem::size_of::<HIGHCONTRASTW>() as u32;
+    // SAFETY: both out-params are valid, writable and sized as the actions expect
+    let (animations_ok, contrast_ok) = unsafe {
+        (
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                &mut animations as *mut i32 as *mut _,
+                0,
+            ),
+            SystemParametersInfoW(
+                SPI_GETHIGHCONTRAST,
+                high_contrast.cbSize,
+                &mut high_contrast as *mut HIGHCONTRASTW as *mut _,
+                0,
+            ),
+        )
+    };
+
+    // Settings > Accessibility > Text size (100 - 225)
+    let text_scale = std::process::Command::new("reg")
+        .args([
+            "query",
+            "HKCU\\Software\\Microsoft\\Accessibility",
+            "/v",
+            "TextScaleFactor",
+        ])
+        .output()
+        .ok()
+        .and_then(|output| {
+            parse_reg_dword(&String::from_utf8_lossy(&output.stdout), "TextScaleFactor")
+        })
+        .map(|percent| percent as f64 / 100.0);
+
+    AccessibilityPrefs {
+        reduce_motion: animations_ok != 0 && animations == 0,
+        increase_contrast: contrast_ok != 0 && high_contrast.dwFlags & HCF_HIGHCONTRASTON != 0,
+        text_scale,
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn read_prefs() -> AccessibilityPrefs {
+    AccessibilityPrefs::default()
+}
+
+/// Map RGBA pixels to a black-and-white, fully opaque or fully transparent
+/// high-contrast variant
+fn high_contrast_rgba(rgba: &[u8]) -> Vec<u8> {
+    rgba.chunks_exact(4)
+        .flat_map(|pixel| {
+            let luminance =
+                (299 * pixel[0] as u32 + 587 * pixel[1] as u32 + 114 * pixel[2] as u32) / 1000;
+            let value = if luminance >= 128 { 255 } else { 0 };
+            let alpha = if pixel[3] >= 128 { 255 } else { 0 };
+            [value, value, value, alpha]
+        })
+        .collect()
+}
+
+/// High-contrast variant of an icon
+pub fn high_contrast_icon(icon: &Image<'_>) -> Image<'static> {
+    Image::new_owned(high_contrast_rgba(icon.rgba()), icon.width(), icon.height())
+}
+
+/// Quick Ask window size for a text scale, never smaller than the default
+fn quick_input_size(text_scale: Option<f64>) -> (f64, f64) {
+    let scale = text_scale
+        .filter(|scale| scale.is_finite())
+        .unwrap_or(1.0)
+        .clamp(1.0, MAX_QUICK_INPUT_SCALE);
+    (QUICK_INPUT_SIZE.0 * scale, QUICK_INPUT_SIZE.1 * scale)
+}
+
+/// Resize the Quick Ask window for the preferred text size, keeping it on
+/// screen
+fn resize_quick_input(app: &AppHandle, text_scale: Option<f64>) {
+    let Some(window) = app.get_webview_window("quickinput") else {
+        return;
+    };
+    let (mut width, mut height) = quick_input_size(text_scale);
+    if let Ok(Some(monitor)) = window.current_monitor() {
+        let screen = monitor.size().to_logical::<f64>(monitor.scale_factor());
+        width = width.min(screen.width * MAX_QUICK_INPUT_SCREEN_SHARE);
+        height = height.min(screen.height * MAX_QUICK_INPUT_SCREEN_SHARE);
+    }
+    if let Err(e) = window.set_size(LogicalSize::new(width, height)) {
+        log::error!("[Accessibility] Failed to resize Quick Ask: {}", e);
+        return;
+    }
+    let _ = window.center();
+}
+
+/// Start polling the OS accessibility preferences
+pub fn start_accessibility_monitor(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let prefs = tokio::task::spawn_blocking(read_prefs)
+                .await
+                .unwrap_or_default();
+
+            let state = app.state::<AccessibilityState>();
+            let previous = state
+                .prefs
+                .lock()
+                .map(|mut current| std::mem::replace(&mut *current, prefs.clone()))
+                .unwrap_or_default();
+            if previous != prefs {
+                log::info!("[Accessibility] Preferences changed: {:?}", prefs);
+                if previous.increase_contrast != prefs.increase_contrast {
+                    crate::tray::set_high_contrast(&app, prefs.increase_contrast);
+                }
+                if previous.text_scale != prefs.text_scale {
+                    resize_quick_input(&app, prefs.text_scale);
+                }
+                let _ = app.emit("accessibility:changed", prefs);
+            }
+
+            tokio::time::sleep(Duration::from_secs(PREFS_CHECK_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+/// Get the current OS accessibility preferences
+#[tauri::command]
+pub async fn get_accessibility_prefs(
+    state: tauri::State<'_, AccessibilityState>,
+) -> Result<AccessibilityPrefs, CommandError> {
+    state
+        .prefs
+        .lock()
+        .map(|prefs| prefs.clone())
+        .map_err(|e| CommandError::internal(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_announcements_are_collapsed() {
+        let now = Instant::now();
+        assert!(should_announce(None, "Connected", now));
+
+        let last = ("Connection lost".to_string(), now);
+        assert!(!should_announce(
+            Some(&last),
+            "Connection lost",
+            now + Duration::from_secs(1)
+        ));
+        assert!(should_announce(Some(&last), "Connected", now));
+        assert!(should_announce(
+            Some(&last),
+            "Connection lost",
+            now + REPEAT_WINDOW
+        ));
+    }
+
+    #[test]
+    fn test_parse_reg_dword() {
+        let output = "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\Accessibility\r\n    TextScaleFactor    REG_DWORD    0x7d\r\n";
+        assert_eq!(parse_reg_dword(output, "TextScaleFactor"), Some(125));
+        assert_eq!(parse_reg_dword(output, "Other"), None);
+        assert_eq!(parse_reg_dword("", "TextScaleFactor"), None);
+    }
+
+    #[test]
+    fn test_quick_input_size() {
+        assert_eq!(quick_input_size(None), QUICK_INPUT_SIZE);
+        assert_eq!(quick_input_size(Some(0.8)), QUICK_INPUT_SIZE);
+        assert_eq!(quick_input_size(Some(1.5)), (900.0, 690.0));
+        assert_eq!(quick_input_size(Some(3.0)), (1200.0, 920.0));
+        assert_eq!(quick_input_size(Some(f64::NAN)), QUICK_INPUT_SIZE);
+    }
+
+    #[test]
+    fn test_high_contrast_rgba() {
+        let pixels = [200, 220, 240, 255, 20, 40, 60, 200, 255, 255, 255, 10];
+        assert_eq!(
+            high_contrast_rgba(&pixels),
+            vec![255, 255, 255, 255, 0, 0, 0, 255, 255, 255, 255, 0]
+        );
+    }
+}
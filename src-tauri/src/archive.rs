@@ -0,0 +1,404 @@
+//! Cold storage for old conversations
+//!
+//! Archiving moves a conversation out of the live IndexedDB database into a
+//! compressed file of its own (`archives/<file>.zip` in the app data dir),
+//! keeping the hot database small. Rows are stored exactly as the database
+//! holds them (title and contents still encrypted, plus the plaintext
+//! `searchText` the database already keeps for search), so restoring is a
+//! plain re-insert.
+//!
+//! An index of archived conversations is kept next to the files for listing;
+//! searching opens the archives on demand.
+
+use crate::error::CommandError;
+use crate::guest;
+use crate::store;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime, State};
+use tokio::sync::Mutex;
+
+/// Directory of the archive files in the app data dir
+const ARCHIVE_DIR: &str = "archives";
+
+/// Index of the archived conversations
+const INDEX_FILE: &str = "index.json";
+
+/// Name of the conversation document inside an archive file
+const ARCHIVE_ENTRY: &str = "conversation.json";
+
+/// Version of the archive file format
+const ARCHIVE_VERSION: u32 = 1;
+
+/// Length of the text around a search match
+const SNIPPET_CHARS: usize = 120;
+
+/// A message row as stored in the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedMessage {
+    pub id: String,
+    /// Lowercased plaintext kept by the database for search
+    #[serde(rename = "searchText", default)]
+    pub search_text: String,
+    /// Remaining (encrypted) columns, kept as-is
+    #[serde(flatten)]
+    pub rest: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A conversation and its messages as stored in the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationArchive {
+    /// Conversation row; `id` is required, other columns are kept as-is
+    pub conversation: serde_json::Value,
+    pub messages: Vec<ArchivedMessage>,
+}
+
+/// Archive file contents
+#[derive(Debug, Serialize, Deserialize)]
+struct ArchiveFile {
+    version: u32,
+    #[serde(rename = "archivedAt")]
+    archived_at: DateTime<Utc>,
+    #[serde(flatten)]
+    archive: ConversationArchive,
+}
+
+/// An archived conversation, as listed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    /// Conversation ID
+    pub id: String,
+    /// Title as stored (encrypted)
+    pub title: Option<String>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<String>,
+    #[serde(rename = "archivedAt")]
+    pub archived_at: DateTime<Utc>,
+    #[serde(rename = "messageCount")]
+    pub message_count: usize,
+    /// Size of the archive file in bytes
+    pub size: u64,
+    /// File name in the archive directory
+    file: String,
+}
+
+/// A message matching an archive search
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveMatch {
+    #[serde(rename = "conversationId")]
+    pub conversation_id: String,
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    /// Plaintext (lowercased) around the match
+    pub snippet: String,
+}
+
+/// Archive state managed by Tauri
+pub struct ArchiveState {
+    dir: PathBuf,
+    index: Mutex<Vec<ArchiveEntry>>,
+}
+
+impl ArchiveState {
+    /// Load the archive index from the app data dir
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let dir = store::store_path(app, ARCHIVE_DIR);
+        Self {
+            index: Mutex::new(store::load(&dir.join(INDEX_FILE))),
+            dir,
+        }
+    }
+
+    fn save_index(&self, index: &[ArchiveEntry]) -> Result<(), String> {
+        store::save(&self.dir.join(INDEX_FILE), &index)
+    }
+}
+
+/// Compress an archive file
+fn write_archive(path: &Path, file: &ArchiveFile) -> Result<u64, String> {
+    let json = serde_json::to_vec(file).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file(ARCHIVE_ENTRY, options)
+        .map_err(|e| e.to_string())?;
+    zip.write_all(&json).map_err(|e| e.to_string())?;
+    let bytes = zip.finish().map_err(|e| e.to_string())?.into_inner();
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let tmp_path = path.with_extension("zip.tmp");
+    std::fs::write(&tmp_path, &bytes).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp_path, path).map_err(|e| e.to_string())?;
+    Ok(bytes.len() as u64)
+}
+
+/// Read an archive file
+fn read_archive_file(path: &Path) -> Result<ArchiveFile, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read archive: {}", e))?;
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(bytes)).map_err(|e| e.to_string())?;
+    let mut json = String::new();
+    zip.by_name(ARCHIVE_ENTRY)
+        .map_err(|e| e.to_string())?
+        .read_to_string(&mut json)
+        .map_err(|e| e.to_string())?;
+    let file: ArchiveFile =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid archive: {}", e))?;
+    if file.version > ARCHIVE_VERSION {
+        return Err(format!(
+            "Archive version {} is newer than supported ({})",
+            file.version, ARCHIVE_VERSION
+        ));
+    }
+    Ok(file)
+}
+
+/// Text around the first occurrence of `word` in `text`
+fn snippet(text: &str, word: &str) -> String {
+    let Some(at) = text.find(word) else {
+        return text.chars().take(SNIPPET_CHARS).collect();
+    };
+    let before = text[..at]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_CHARS / 3)
+        .map_or(0, |(i, _)| i);
+    let snippet: String = text[before..].chars().take(SNIPPET_CHARS).collect();
+    if before > 0 {
+        format!("…{}", snippet)
+    } else {
+        snippet
+    }
+}
+
+/// Messages whose search text contains every word of the query
+fn search_messages(archive: &ConversationArchive, words: &[String]) -> Vec<ArchivedMessage> {
+    archive
+        .messages
+        .iter()
+        .filter(|m| words.iter().all(|w| m.search_text.contains(w.as_str())))
+        .cloned()
+        .collect()
+}
+
+/// Move a conversation into an archive file
+///
+/// The caller removes it from the database once this succeeds.
+#[tauri::command]
+pub async fn archive_conversation(
+    state: State<'_, ArchiveState>,
+    archive: ConversationArchive,
+) -> Result<ArchiveEntry, CommandError> {
+    if guest::active() {
+        return Err(CommandError::validation("Archiving is off in guest mode"));
+    }
+    let id = archive
+        .conversation
+        .get("id")
+        .and_then(|id| id.as_str())
+        .filter(|id| !id.is_empty())
+        .ok_or_else(|| CommandError::validation("Conversation has no ID"))?
+        .to_string();
+    let field = |name: &str| {
+        archive
+            .conversation
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+    let (title, updated_at) = (field("title"), field("updatedAt"));
+    let message_count = archive.messages.len();
+
+    let mut index = state.index.lock().await;
+    // Re-archiving replaces the previous file
+    let file_name = index
+        .iter()
+        .find(|e| e.id == id)
+        .map(|e| e.file.clone())
+        .unwrap_or_else(|| format!("{}.zip", uuid::Uuid::new_v4()));
+    let archived_at = Utc::now();
+    let path = state.dir.join(&file_name);
+    let file = ArchiveFile {
+        version: ARCHIVE_VERSION,
+        archived_at,
+        archive,
+    };
+    let size = tokio::task::spawn_blocking(move || write_archive(&path, &file))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    let entry = ArchiveEntry {
+        id,
+        title,
+        updated_at,
+        archived_at,
+        message_count,
+        size,
+        file: file_name,
+    };
+    index.retain(|e| e.id != entry.id);
+    index.push(entry.clone());
+    state.save_index(&index)?;
+    Ok(entry)
+}
+
+/// Archived conversations, most recently archived first
+#[tauri::command]
+pub async fn list_archives(
+    state: State<'_, ArchiveState>,
+) -> Result<Vec<ArchiveEntry>, CommandError> {
+    let mut entries = state.index.lock().await.clone();
+    entries.sort_by(|a, b| b.archived_at.cmp(&a.archived_at));
+    Ok(entries)
+}
+
+/// Read an archived conversation's rows, to restore or view it
+#[tauri::command]
+pub async fn read_archive(
+    state: State<'_, ArchiveState>,
+    id: String,
+) -> Result<ConversationArchive, CommandError> {
+    let path = {
+        let index = state.index.lock().await;
+        let entry = index
+            .iter()
+            .find(|e| e.id == id)
+            .ok_or_else(|| CommandError::validation("Archived conversation not found"))?;
+        state.dir.join(&entry.file)
+    };
+    let file = tokio::task::spawn_blocking(move || read_archive_file(&path))
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(file.archive)
+}
+
+/// Delete an archive file (after restoring it, or to discard it)
+#[tauri::command]
+pub async fn delete_archive(
+    state: State<'_, ArchiveState>,
+    id: String,
+) -> Result<(), CommandError> {
+    if guest::active() {
+        return Err(CommandError::validation("Archiving is off in guest mode"));
+    }
+    let mut index = state.index.lock().await;
+    let Some(position) = index.iter().position(|e| e.id == id) else {
+        return Err(CommandError::validation("Archived conversation not found"));
+    };
+    let entry = index.remove(position);
+    if let Err(e) = std::fs::remove_file(state.dir.join(&entry.file)) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            index.insert(position, entry);
+            return Err(format!("Failed to delete archive: {}", e).into());
+        }
+    }
+    state.save_index(&index)?;
+    Ok(())
+}
+
+/// Search the archived conversations (every word must match)
+#[tauri::command]
+pub async fn search_archives(
+    state: State<'_, ArchiveState>,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<ArchiveMatch>, CommandError> {
+    let words: Vec<String> = query
+        .to_lowercase()
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+    if words.is_empty() {
+        return Ok(Vec::new());
+    }
+    let limit = limit.unwrap_or(50);
+    let paths: Vec<(String, PathBuf)> = state
+        .index
+        .lock()
+        .await
+        .iter()
+        .map(|e| (e.id.clone(), state.dir.join(&e.file)))
+        .collect();
+
+    let matches = tokio::task::spawn_blocking(move || {
+        let mut matches = Vec::new();
+        for (conversation_id, path) in paths {
+            let file = match read_archive_file(&path) {
+                Ok(file) => file,
+                Err(e) => {
+                    log::error!("[Archive] Skipping {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            for message in search_messages(&file.archive, &words) {
+                matches.push(ArchiveMatch {
+                    conversation_id: conversation_id.clone(),
+                    snippet: snippet(&message.search_text, &words[0]),
+                    message_id: message.id,
+                });
+                if matches.len() >= limit {
+                    return matches;
+                }
+            }
+        }
+        matches
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive() -> ConversationArchive {
+        serde_json::from_value(serde_json::json!({
+            "conversation": { "id": "c1", "title": "enc:title", "updatedAt": "2026-01-01T00:00:00Z" },
+            "messages": [
+                { "id": "m1", "searchText": "how do i rotate a gateway token?", "content": "enc:1" },
+                { "id": "m2", "searchText": "use token.rotate", "content": "enc:2" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_archive_file_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("moltz-archive-{}", uuid::Uuid::new_v4()))
+            .join("a.zip");
+        let file = ArchiveFile {
+            version: ARCHIVE_VERSION,
+            archived_at: Utc::now(),
+            archive: archive(),
+        };
+        let size = write_archive(&path, &file).unwrap();
+        assert!(size > 0);
+
+        let read = read_archive_file(&path).unwrap();
+        assert_eq!(read.archive.conversation["title"], "enc:title");
+        assert_eq!(read.archive.messages.len(), 2);
+        // Encrypted columns survive untouched
+        assert_eq!(read.archive.messages[1].rest["content"], "enc:2");
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_search_messages_and_snippet() {
+        let words = vec!["token".to_string(), "gateway".to_string()];
+        let found = search_messages(&archive(), &words);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "m1");
+
+        let text = format!("{} needle {}", "a".repeat(200), "b".repeat(200));
+        let s = snippet(&text, "needle");
+        assert!(s.starts_with('…'));
+        assert!(s.contains("needle"));
+        assert_eq!(s.chars().count(), SNIPPET_CHARS + 1);
+    }
+}
@@ -0,0 +1,237 @@
+//! Connection chaos mode (development builds only)
+//!
+//! Degrades the Gateway connection on purpose so reconnection, queueing and
+//! resume logic can be exercised interactively:
+//! - Artificial latency on every inbound and outbound frame
+//! - Random frame drops (in both directions)
+//! - Forced disconnects, on demand or at a fixed interval
+//!
+//! Release builds reject the commands, so the hooks in the connection path
+//! never fire there.
+
+use crate::error::CommandError;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::Notify;
+
+/// Shortest allowed forced-disconnect interval
+const MIN_DISCONNECT_INTERVAL_SECS: u64 = 5;
+
+/// Chaos settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ChaosConfig {
+    pub enabled: bool,
+    /// Maximum latency added to each frame (uniformly 0..=latencyMs)
+    #[serde(rename = "latencyMs", default)]
+    pub latency_ms: u64,
+    /// Fraction of frames dropped (0.0 - 1.0)
+    #[serde(rename = "dropRate", default)]
+    pub drop_rate: f64,
+    /// Force a disconnect this often
+    #[serde(rename = "disconnectIntervalSecs", default)]
+    pub disconnect_interval_secs: Option<u64>,
+}
+
+impl ChaosConfig {
+    fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.drop_rate) {
+            return Err("Drop rate must be between 0 and 1".to_string());
+        }
+        if self
+            .disconnect_interval_secs
+            .is_some_and(|secs| secs < MIN_DISCONNECT_INTERVAL_SECS)
+        {
+            return Err(format!(
+                "Disconnect interval must be at least {}s",
+                MIN_DISCONNECT_INTERVAL_SECS
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// What to do with a frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameFate {
+    Deliver { delay: Duration },
+    Drop,
+}
+
+/// Decide a frame's fate from two uniform random numbers in [0, 1)
+fn frame_fate(config: &ChaosConfig, drop_roll: f64, latency_roll: f64) -> FrameFate {
+    if !config.enabled {
+        return FrameFate::Deliver {
+            delay: Duration::ZERO,
+        };
+    }
+    if drop_roll < config.drop_rate {
+        return FrameFate::Drop;
+    }
+    let delay_ms = (latency_roll * (config.latency_ms + 1) as f64) as u64;
+    FrameFate::Deliver {
+        delay: Duration::from_millis(delay_ms.min(config.latency_ms)),
+    }
+}
+
+/// Chaos state managed by Tauri
+#[derive(Default)]
+pub struct ChaosState {
+    config: Mutex<ChaosConfig>,
+    /// Wakes the connection's read loop to simulate a dropped connection
+    disconnect: Notify,
+    /// Bumped on every config change to stop the previous disconnect timer
+    generation: AtomicU64,
+}
+
+impl ChaosState {
+    fn config(&self) -> ChaosConfig {
+        self.config.lock().map(|c| c.clone()).unwrap_or_default()
+    }
+}
+
+/// Delay or drop a frame according to the chaos settings
+///
+/// Returns false when the frame should be dropped.
+pub async fn pass_frame(app: &AppHandle, direction: &str) -> bool {
+    let Some(state) = app.try_state::<ChaosState>() else {
+        return true;
+    };
+    let config = state.config();
+    if !config.enabled {
+        return true;
+    }
+    match frame_fate(&config, rand::random(), rand::random()) {
+        FrameFate::Drop => {
+            log::error!("[Chaos] Dropped {} frame", direction);
+            false
+        }
+        FrameFate::Deliver { delay } => {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            true
+        }
+    }
+}
+
+/// Wait until a forced disconnect is requested (never, without chaos state)
+pub async fn forced_disconnect(app: &AppHandle) {
+    match app.try_state::<ChaosState>() {
+        Some(state) => state.disconnect.notified().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Force the current connection to drop, as if the network failed
+fn force_disconnect(state: &ChaosState) {
+    log::error!("[Chaos] Forcing disconnect");
+    state.disconnect.notify_waiters();
+}
+
+/// Get the chaos settings
+#[tauri::command]
+pub async fn get_chaos_config(state: State<'_, ChaosState>) -> Result<ChaosConfig, CommandError> {
+    Ok(state.config())
+}
+
+/// Change the chaos settings (development builds only)
+#[tauri::command]
+pub async fn set_chaos_config(
+    app: AppHandle,
+    state: State<'_, ChaosState>,
+    config: ChaosConfig,
+) -> Result<(), CommandError> {
+    if !cfg!(debug_assertions) {
+        return Err(CommandError::validation(
+            "Chaos mode is only available in development builds",
+        ));
+    }
+    config.validate().map_err(CommandError::validation)?;
+    *state
+        .config
+        .lock()
+        .map_err(|e| CommandError::internal(e.to_string()))? = config.clone();
+    let generation = state.generation.fetch_add(1, Ordering::SeqCst) + 1;
+
+    if let (true, Some(secs)) = (config.enabled, config.disconnect_interval_secs) {
+        tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(secs)).await;
+                let state = app.state::<ChaosState>();
+                if state.generation.load(Ordering::SeqCst) != generation {
+                    break;
+                }
+                force_disconnect(&state);
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Force a disconnect now (development builds only)
+#[tauri::command]
+pub async fn chaos_disconnect(state: State<'_, ChaosState>) -> Result<(), CommandError> {
+    if !cfg!(debug_assertions) {
+        return Err(CommandError::validation(
+            "Chaos mode is only available in development builds",
+        ));
+    }
+    force_disconnect(&state);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_fate() {
+        let mut config = ChaosConfig {
+            enabled: true,
+            latency_ms: 1000,
+            drop_rate: 0.25,
+            disconnect_interval_secs: None,
+        };
+        assert_eq!(frame_fate(&config, 0.1, 0.5), FrameFate::Drop);
+        assert_eq!(
+            frame_fate(&config, 0.5, 0.5),
+            FrameFate::Deliver {
+                delay: Duration::from_millis(500)
+            }
+        );
+        // Latency is capped at the configured maximum
+        assert_eq!(
+            frame_fate(&config, 0.5, 0.99999),
+            FrameFate::Deliver {
+                delay: Duration::from_millis(1000)
+            }
+        );
+
+        config.enabled = false;
+        assert_eq!(
+            frame_fate(&config, 0.0, 0.9),
+            FrameFate::Deliver {
+                delay: Duration::ZERO
+            }
+        );
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let config = ChaosConfig {
+            enabled: true,
+            drop_rate: 1.5,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        let config = ChaosConfig {
+            enabled: true,
+            disconnect_interval_secs: Some(1),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,144 @@
+//! Timeline of a connection attempt
+//!
+//! Each attempt records its steps (DNS, TCP, TLS, WebSocket upgrade,
+//! `connect.challenge`, hello-ok) with their offset from the start of the
+//! attempt, duration and outcome. The last attempt is kept by the gateway
+//! state, returned by `get_last_connection_trace` and included in the
+//! diagnostics bundle, so a failed connection can be read step by step
+//! instead of reconstructed from the `[Gateway Protocol Error]` log lines.
+//!
+//! When tokio-tungstenite performs TLS and the upgrade in one call, they are
+//! recorded as a single `ws_upgrade` step.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// A step of the connection sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceStep {
+    Dns,
+    Tcp,
+    Tls,
+    WsUpgrade,
+    Challenge,
+    HelloOk,
+}
+
+/// One recorded step
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceEntry {
+    pub step: TraceStep,
+    /// Offset from the start of the attempt
+    #[serde(rename = "startMs")]
+    pub start_ms: u64,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: u64,
+    pub ok: bool,
+    /// Resolved addresses, peer address or error message
+    pub detail: Option<String>,
+}
+
+/// Timeline of one connection attempt
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionTrace {
+    pub url: String,
+    #[serde(rename = "startedAt")]
+    pub started_at: DateTime<Utc>,
+    /// `None` while the attempt is in progress
+    pub success: Option<bool>,
+    /// Why the attempt failed, if it did
+    pub error: Option<String>,
+    #[serde(rename = "totalMs")]
+    pub total_ms: Option<u64>,
+    pub steps: Vec<TraceEntry>,
+}
+
+/// Shared handle for recording an attempt from the tasks that perform it
+#[derive(Debug, Clone)]
+pub struct ConnectionTracer {
+    started: Instant,
+    trace: Arc<Mutex<ConnectionTrace>>,
+}
+
+impl ConnectionTracer {
+    pub fn new(url: &str) -> Self {
+        Self {
+            started: Instant::now(),
+            trace: Arc::new(Mutex::new(ConnectionTrace {
+                url: url.to_string(),
+                started_at: Utc::now(),
+                success: None,
+                error: None,
+                total_ms: None,
+                steps: Vec::new(),
+            })),
+        }
+    }
+
+    fn push(&self, step: TraceStep, started: Instant, ok: bool, detail: Option<String>) {
+        let entry = TraceEntry {
+            step,
+            start_ms: started.saturating_duration_since(self.started).as_millis() as u64,
+            duration_ms: started.elapsed().as_millis() as u64,
+            ok,
+            detail,
+        };
+        if let Ok(mut trace) = self.trace.lock() {
+            trace.steps.push(entry);
+        }
+    }
+
+    /// Record a step that began at `started` and succeeded
+    pub fn ok(&self, step: TraceStep, started: Instant, detail: Option<String>) {
+        self.push(step, started, true, detail);
+    }
+
+    /// Record a step that began at `started` and failed
+    pub fn failed(&self, step: TraceStep, started: Instant, error: impl ToString) {
+        self.push(step, started, false, Some(error.to_string()));
+    }
+
+    /// Record the outcome of the whole attempt
+    pub fn finish(&self, error: Option<String>) {
+        if let Ok(mut trace) = self.trace.lock() {
+            trace.success = Some(error.is_none());
+            trace.error = error;
+            trace.total_ms = Some(self.started.elapsed().as_millis() as u64);
+        }
+    }
+
+    /// Copy of the timeline recorded so far
+    pub fn snapshot(&self) -> Option<ConnectionTrace> {
+        self.trace.lock().ok().map(|trace| trace.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_steps_in_order() {
+        let tracer = ConnectionTracer::new("ws://localhost:18789");
+        let started = Instant::now();
+        tracer.ok(TraceStep::Dns, started, Some("127.0.0.1:18789".to_string()));
+        tracer.failed(TraceStep::Tcp, Instant::now(), "Connection refused");
+        tracer.finish(Some("Connection refused".to_string()));
+
+        let trace = tracer.snapshot().unwrap();
+        assert_eq!(trace.success, Some(false));
+        assert!(trace.total_ms.is_some());
+        assert_eq!(trace.steps.len(), 2);
+        assert_eq!(trace.steps[0].step, TraceStep::Dns);
+        assert!(trace.steps[0].ok);
+        assert!(!trace.steps[1].ok);
+        assert!(trace.steps[1].start_ms >= trace.steps[0].start_ms);
+
+        let json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(json["steps"][1]["step"], "tcp");
+        assert_eq!(json["steps"][1]["detail"], "Connection refused");
+    }
+}
@@ -0,0 +1,221 @@
+//! Bandwidth-saver mode and data usage accounting
+//!
+//! When data saver is on:
+//! - Streamed deltas are batched per run and emitted every
+//!   [`SAVER_STREAM_BATCH_MS`] instead of on every chunk
+//! - The frontend stops auto-loading remote inline images and compresses
+//!   image attachments harder (it listens for `data_saver:changed`)
+//!
+//! Bytes sent and received over the Gateway socket are counted for the
+//! connection and, where a frame names its session, per session. The counts
+//! are estimates (WebSocket framing and TLS overhead are not included) and
+//! are kept in memory for the lifetime of the app.
+
+use crate::error::CommandError;
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+/// Store file name for the data saver setting
+const DATA_SAVER_FILE: &str = "data_saver.json";
+
+/// Stream emit interval while data saver is on
+pub const SAVER_STREAM_BATCH_MS: u64 = 500;
+
+/// Persisted data saver setting
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DataSaverSettings {
+    enabled: bool,
+}
+
+/// Bytes sent and received
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct DataUsage {
+    pub sent: u64,
+    pub received: u64,
+}
+
+/// Data usage of one session
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionDataUsage {
+    #[serde(rename = "sessionKey")]
+    pub session_key: String,
+    pub sent: u64,
+    pub received: u64,
+}
+
+/// Data usage report for the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct DataUsageReport {
+    /// Everything sent/received on the connection, including pings and
+    /// frames not tied to a session
+    pub total: DataUsage,
+    /// Per-session usage, heaviest first
+    pub sessions: Vec<SessionDataUsage>,
+}
+
+#[derive(Debug, Default)]
+struct UsageCounters {
+    total: DataUsage,
+    sessions: HashMap<String, DataUsage>,
+}
+
+/// Data saver state managed by Tauri
+pub struct DataSaverState {
+    path: PathBuf,
+    enabled: AtomicBool,
+    usage: Mutex<UsageCounters>,
+}
+
+impl DataSaverState {
+    /// Load the data saver setting from the local store
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, DATA_SAVER_FILE);
+        let settings: DataSaverSettings = store::load(&path);
+        Self {
+            path,
+            enabled: AtomicBool::new(settings.enabled),
+            usage: Mutex::new(UsageCounters::default()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Persist the setting and emit `data_saver:changed`
+    pub fn set_enabled(&self, app: &AppHandle, enabled: bool) -> Result<(), String> {
+        store::save(&self.path, &DataSaverSettings { enabled })?;
+        self.enabled.store(enabled, Ordering::Relaxed);
+        let _ = app.emit("data_saver:changed", enabled);
+        Ok(())
+    }
+
+    /// Count a frame received from the Gateway
+    pub fn record_received(&self, session_key: Option<&str>, bytes: usize) {
+        if let Ok(mut usage) = self.usage.lock() {
+            usage.total.received += bytes as u64;
+            if let Some(key) = session_key {
+                usage.sessions.entry(key.to_string()).or_default().received += bytes as u64;
+            }
+        }
+    }
+
+    /// Count a frame written to the socket
+    pub fn record_sent(&self, bytes: usize) {
+        if let Ok(mut usage) = self.usage.lock() {
+            usage.total.sent += bytes as u64;
+        }
+    }
+
+    /// Attribute sent bytes to a session (already counted by `record_sent`)
+    pub fn record_session_sent(&self, session_key: &str, bytes: usize) {
+        if let Ok(mut usage) = self.usage.lock() {
+            usage
+                .sessions
+                .entry(session_key.to_string())
+                .or_default()
+                .sent += bytes as u64;
+        }
+    }
+
+    fn report(&self) -> DataUsageReport {
+        let Ok(usage) = self.usage.lock() else {
+            return DataUsageReport {
+                total: DataUsage::default(),
+                sessions: Vec::new(),
+            };
+        };
+        let mut sessions: Vec<SessionDataUsage> = usage
+            .sessions
+            .iter()
+            .map(|(key, u)| SessionDataUsage {
+                session_key: key.clone(),
+                sent: u.sent,
+                received: u.received,
+            })
+            .collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.sent + s.received));
+        DataUsageReport {
+            total: usage.total,
+            sessions,
+        }
+    }
+}
+
+/// Whether data saver is on (false before the state is managed)
+pub fn is_enabled<R: Runtime>(app: &AppHandle<R>) -> bool {
+    app.try_state::<DataSaverState>()
+        .is_some_and(|state| state.is_enabled())
+}
+
+/// Get whether data saver is on
+#[tauri::command]
+pub async fn get_data_saver(state: State<'_, DataSaverState>) -> Result<bool, CommandError> {
+    Ok(state.is_enabled())
+}
+
+/// Turn data saver on or off
+#[tauri::command]
+pub async fn set_data_saver(
+    app: AppHandle,
+    state: State<'_, DataSaverState>,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    state
+        .set_enabled(&app, enabled)
+        .map_err(CommandError::internal)
+}
+
+/// Get estimated data usage since the app started
+#[tauri::command]
+pub async fn get_data_usage(
+    state: State<'_, DataSaverState>,
+) -> Result<DataUsageReport, CommandError> {
+    Ok(state.report())
+}
+
+/// Reset the data usage counters
+#[tauri::command]
+pub async fn reset_data_usage(state: State<'_, DataSaverState>) -> Result<(), CommandError> {
+    let mut usage = state
+        .usage
+        .lock()
+        .map_err(|e| CommandError::internal(e.to_string()))?;
+    *usage = UsageCounters::default();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_report_totals_and_order() {
+        let state = DataSaverState {
+            path: std::env::temp_dir().join("moltz-data-saver-test.json"),
+            enabled: AtomicBool::new(false),
+            usage: Mutex::new(UsageCounters::default()),
+        };
+        state.record_sent(100);
+        state.record_session_sent("small", 100);
+        state.record_received(Some("small"), 50);
+        state.record_received(Some("big"), 1000);
+        state.record_received(None, 10);
+
+        let report = state.report();
+        assert_eq!(report.total.sent, 100);
+        assert_eq!(report.total.received, 1060);
+        let keys: Vec<&str> = report
+            .sessions
+            .iter()
+            .map(|s| s.session_key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["big", "small"]);
+        assert_eq!(report.sessions[1].sent, 100);
+    }
+}
@@ -0,0 +1,270 @@
+//! `moltz://` URL scheme for launcher integrations (Raycast, Alfred, ...)
+//!
+//! - `moltz://ask?q=<text>&mode=quick` opens Quick Ask and runs the query
+//!   immediately; without `mode=quick` the query runs in the main window
+//! - `&copy=1` copies the answer to the clipboard once the run completes
+//! - `moltz://open?conversation=<id>&message=<id>` brings up a conversation
+//!   (and scrolls to a message); Windows toasts use it as their protocol
+//!   activation so a click still routes after the app has exited
+//!
+//! macOS delivers URLs through `RunEvent::Opened` (the scheme is declared in
+//! Info.plist). Windows and Linux pass them as a launch argument, which is
+//! read at startup, or forwarded by the single-instance plugin when the app
+//! is already running.
+
+use crate::error::CommandError;
+use tauri::{AppHandle, Emitter, Manager, Url};
+
+/// URL scheme handled by the app
+pub const SCHEME: &str = "moltz";
+
+/// Where an `ask` link runs its query
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AskMode {
+    /// The Quick Ask window
+    Quick,
+    /// A new conversation in the main window
+    Chat,
+}
+
+/// A parsed `moltz://` link
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeepLink {
+    Ask {
+        query: String,
+        mode: AskMode,
+        /// Copy the answer to the clipboard when the run completes
+        copy: bool,
+    },
+    Open {
+        conversation: String,
+        message: Option<String>,
+    },
+}
+
+/// Payload of `quickinput:ask` / `quickinput:submit` for deep-link queries
+#[derive(Debug, Clone, serde::Serialize)]
+struct AskPayload {
+    message: String,
+    #[serde(rename = "copyAnswer")]
+    copy_answer: bool,
+}
+
+/// Payload of `deeplink:open`
+#[derive(Debug, Clone, serde::Serialize)]
+struct OpenPayload {
+    #[serde(rename = "conversationId")]
+    conversation_id: String,
+    #[serde(rename = "messageId")]
+    message_id: Option<String>,
+}
+
+/// Link that opens a conversation (conversation IDs double as session keys)
+pub fn conversation_url(conversation: &str, message: Option<&str>) -> String {
+    let mut url = Url::parse(&format!("{}://open", SCHEME)).expect("valid base URL");
+    url.query_pairs_mut()
+        .append_pair("conversation", conversation);
+    if let Some(message) = message {
+        url.query_pairs_mut().append_pair("message", message);
+    }
+    url.to_string()
+}
+
+/// Parse a `moltz://` URL; unknown actions and empty queries are ignored
+pub fn parse(url: &str) -> Option<DeepLink> {
+    let url = Url::parse(url).ok()?;
+    if url.scheme() != SCHEME {
+        return None;
+    }
+    // `moltz://ask?...` puts the action in the host, `moltz:ask?...` in the path
+    let action = url
+        .host_str()
+        .unwrap_or_else(|| url.path())
+        .trim_matches('/');
+    match action {
+        "ask" => parse_ask(&url),
+        "open" => parse_open(&url),
+        _ => None,
+    }
+}
+
+fn parse_ask(url: &Url) -> Option<DeepLink> {
+    let mut query = None;
+    let mut mode = AskMode::Chat;
+    let mut copy = false;
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "q" | "query" => query = Some(value.trim().to_string()),
+            "mode" if value == "quick" => mode = AskMode::Quick,
+            "copy" => copy = matches!(value.as_ref(), "1" | "true" | "yes"),
+            _ => {}
+        }
+    }
+    let query = query.filter(|q| !q.is_empty())?;
+    Some(DeepLink::Ask { query, mode, copy })
+}
+
+fn parse_open(url: &Url) -> Option<DeepLink> {
+    let mut conversation = None;
+    let mut message = None;
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "conversation" => conversation = Some(value.to_string()),
+            "message" => message = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    let conversation = conversation.filter(|c| !c.is_empty())?;
+    Some(DeepLink::Open {
+        conversation,
+        message: message.filter(|m| !m.is_empty()),
+    })
+}
+
+/// Act on a `moltz://` URL
+pub fn handle(app: &AppHandle, url: &str) {
+    let Some(link) = parse(url) else {
+        log::error!("[DeepLink] Ignoring unsupported URL: {}", url);
+        return;
+    };
+    match link {
+        DeepLink::Ask { query, mode, copy } => {
+            let payload = AskPayload {
+                message: query,
+                copy_answer: copy,
+            };
+            let (label, event) = match mode {
+                // Quick Ask submits on its own, then hands over to the main window
+                AskMode::Quick => ("quickinput", "quickinput:ask"),
+                AskMode::Chat => ("main", "quickinput:submit"),
+            };
+            if let Some(window) = app.get_webview_window(label) {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            let _ = app.emit_to(label, event, payload);
+        }
+        DeepLink::Open {
+            conversation,
+            message,
+        } => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.unminimize();
+                let _ = window.set_focus();
+            }
+            let payload = OpenPayload {
+                conversation_id: conversation,
+                message_id: message,
+            };
+            let _ = app.emit_to("main", "deeplink:open", payload);
+        }
+    }
+}
+
+/// The `moltz:` URL among command-line arguments, if any
+fn find_url(args: impl IntoIterator<Item = String>) -> Option<String> {
+    args.into_iter().find(|arg| arg.starts_with("moltz:"))
+}
+
+/// Handle a `moltz://` URL passed on the command line (Windows / Linux)
+///
+/// Deferred so the windows' listeners are mounted first.
+pub fn handle_launch_args(app: &AppHandle) {
+    let Some(url) = find_url(std::env::args()) else {
+        return;
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        handle(&app, &url);
+    });
+}
+
+/// Handle the arguments of a second launch forwarded by the single-instance
+/// plugin (a link or toast clicked while the app is running)
+pub fn handle_forwarded_args(app: &AppHandle, args: Vec<String>) {
+    match find_url(args) {
+        Some(url) => handle(app, &url),
+        // Launched again without a link: bring the running app forward
+        None => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+    }
+}
+
+/// Copy text (a deep-link query's answer) to the clipboard
+#[tauri::command]
+pub async fn copy_to_clipboard(text: String) -> Result<(), CommandError> {
+    tokio::task::spawn_blocking(move || {
+        arboard::Clipboard::new()
+            .and_then(|mut clipboard| clipboard.set_text(text))
+            .map_err(|e| CommandError::internal(e.to_string()))
+    })
+    .await
+    .map_err(|e| CommandError::internal(e.to_string()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ask_links() {
+        assert_eq!(
+            parse("moltz://ask?q=What%20is%20Rust%3F&mode=quick&copy=1"),
+            Some(DeepLink::Ask {
+                query: "What is Rust?".to_string(),
+                mode: AskMode::Quick,
+                copy: true,
+            })
+        );
+        assert_eq!(
+            parse("moltz:ask?q=hello+world"),
+            Some(DeepLink::Ask {
+                query: "hello world".to_string(),
+                mode: AskMode::Chat,
+                copy: false,
+            })
+        );
+        assert_eq!(parse("moltz://ask?q=%20&mode=quick"), None);
+        assert_eq!(parse("moltz://settings"), None);
+        assert_eq!(parse("https://ask?q=hi"), None);
+    }
+
+    #[test]
+    fn test_conversation_url_round_trip() {
+        let url = conversation_url("moltz:a b&c", Some("m1"));
+        assert_eq!(
+            parse(&url),
+            Some(DeepLink::Open {
+                conversation: "moltz:a b&c".to_string(),
+                message: Some("m1".to_string()),
+            })
+        );
+        assert_eq!(
+            parse("moltz://open?conversation=c1&message="),
+            Some(DeepLink::Open {
+                conversation: "c1".to_string(),
+                message: None,
+            })
+        );
+        assert_eq!(parse("moltz://open?message=m1"), None);
+    }
+
+    #[test]
+    fn test_find_url() {
+        let args = vec![
+            "moltz.exe".to_string(),
+            "moltz://open?conversation=c1".to_string(),
+        ];
+        assert_eq!(
+            find_url(args),
+            Some("moltz://open?conversation=c1".to_string())
+        );
+        assert_eq!(find_url(vec!["moltz.exe".to_string()]), None);
+    }
+}
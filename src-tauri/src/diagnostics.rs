@@ -0,0 +1,77 @@
+//! Diagnostics bundle for troubleshooting
+//!
+//! Collects a point-in-time snapshot of the connection state, health metrics
+//! (including latency percentiles) and the timeline of the last connection
+//! attempt that users can export and attach to bug reports, along with the
+//! state of supervised background tasks. Bundles are scrubbed of credentials
+//! before they leave the backend.
+
+use crate::connection_trace::ConnectionTrace;
+use crate::error::CommandError;
+use crate::gateway::GatewayState;
+use crate::guest;
+use crate::protocol::{ConnectionState, HealthSnapshot};
+use crate::redact;
+use crate::supervisor::{self, TaskStatus};
+use serde::Serialize;
+use tauri::State;
+
+/// Everything included in an exported diagnostics bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticsBundle {
+    #[serde(rename = "generatedAt")]
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+    #[serde(rename = "appVersion")]
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub connection: ConnectionState,
+    pub health: HealthSnapshot,
+    #[serde(rename = "connectionTrace")]
+    pub connection_trace: Option<ConnectionTrace>,
+    /// Supervised background tasks and their restarts
+    pub tasks: Vec<TaskStatus>,
+}
+
+/// Collect a diagnostics bundle from the current app state
+pub async fn collect(gateway: &GatewayState) -> DiagnosticsBundle {
+    DiagnosticsBundle {
+        generated_at: chrono::Utc::now(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        connection: gateway.connection_state().await,
+        health: gateway.health_snapshot().await,
+        connection_trace: gateway.last_connection_trace(),
+        tasks: supervisor::task_statuses(),
+    }
+}
+
+/// Serialize a bundle with credentials redacted
+fn scrubbed(bundle: &DiagnosticsBundle) -> Result<serde_json::Value, String> {
+    let mut value = serde_json::to_value(bundle).map_err(|e| e.to_string())?;
+    redact::redact_json(&mut value);
+    Ok(value)
+}
+
+/// Get the current diagnostics bundle
+#[tauri::command]
+pub async fn get_diagnostics(
+    gateway: State<'_, GatewayState>,
+) -> Result<serde_json::Value, CommandError> {
+    scrubbed(&collect(&gateway).await).map_err(CommandError::internal)
+}
+
+/// Write the diagnostics bundle as JSON to the given path
+#[tauri::command]
+pub async fn export_diagnostics(
+    gateway: State<'_, GatewayState>,
+    path: String,
+) -> Result<(), CommandError> {
+    guest::ensure_inactive("Exporting diagnostics")?;
+    let bundle = scrubbed(&collect(&gateway).await)?;
+    let json = serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+    tokio::fs::write(&path, json)
+        .await
+        .map_err(|e| CommandError::io(format!("Failed to write diagnostics to {}: {}", path, e)))
+}
@@ -4,12 +4,80 @@
 //! - Local port scanning (common Gateway ports)
 //! - Environment variables
 //! - Configuration files
+//! - User hosts file (`gateways.toml` in the config dir)
 //! - Tailscale network
+//!
+//! wss:// endpoints are probed accepting any certificate so the certificate
+//! details can be reported; `tls.trusted` tells whether a normal connect
+//! would succeed.
 
+use crate::error::CommandError;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::time::Duration;
 use tokio::time::timeout;
-use tokio_tungstenite::connect_async;
+use tokio_tungstenite::{connect_async, connect_async_tls_with_config, Connector, MaybeTlsStream};
+
+/// Default Gateway port, used for hosts file entries without a port
+const DEFAULT_GATEWAY_PORT: u16 = 18789;
+
+/// File name of the user-editable hosts file in the config dir
+const HOSTS_FILE: &str = "gateways.toml";
+
+/// User-editable list of extra Gateways to always probe
+///
+/// ```toml
+/// [[gateway]]
+/// name = "NAS"
+/// host = "nas.local"      # probed over ws and wss
+/// port = 18789            # optional
+///
+/// [[gateway]]
+/// url = "wss://gateway.example.com"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+struct HostsFile {
+    #[serde(default, rename = "gateway")]
+    gateways: Vec<HostEntry>,
+}
+
+/// A hosts file entry: either a full URL or a host (+ optional port)
+#[derive(Debug, Deserialize)]
+struct HostEntry {
+    name: Option<String>,
+    url: Option<String>,
+    host: Option<String>,
+    port: Option<u16>,
+}
+
+impl HostEntry {
+    /// URLs to probe for this entry
+    fn urls(&self) -> Vec<String> {
+        if let Some(url) = &self.url {
+            return vec![url.clone()];
+        }
+        match &self.host {
+            Some(host) => {
+                let port = self.port.unwrap_or(DEFAULT_GATEWAY_PORT);
+                ["ws", "wss"]
+                    .iter()
+                    .map(|protocol| format!("{}://{}:{}", protocol, host, port))
+                    .collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn source(&self) -> String {
+        let label = self
+            .name
+            .as_deref()
+            .or(self.host.as_deref())
+            .or(self.url.as_deref())
+            .unwrap_or("unnamed");
+        format!("Hosts File ({})", label)
+    }
+}
 
 /// A discovered Gateway instance
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,11 +90,28 @@ pub struct DiscoveredGateway {
     pub reachable: bool,
     /// Response time in milliseconds (if reachable)
     pub response_time_ms: Option<u64>,
+    /// TLS certificate details (wss:// endpoints only)
+    pub tls: Option<TlsInfo>,
+}
+
+/// TLS certificate details of a wss:// Gateway
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsInfo {
+    /// Certificate subject (e.g. "CN=gateway.local")
+    pub subject: String,
+    /// Certificate issuer
+    pub issuer: String,
+    /// Certificate expiry
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Whether the certificate is self-signed
+    pub self_signed: bool,
+    /// Whether the OS trusts the certificate (if not, connecting requires trusting it)
+    pub trusted: bool,
 }
 
 /// Discover Gateways using all available methods
 #[tauri::command]
-pub async fn discover_gateways() -> Result<Vec<DiscoveredGateway>, String> {
+pub async fn discover_gateways() -> Result<Vec<DiscoveredGateway>, CommandError> {
     let mut gateways = Vec::new();
 
     // Method 1: Check environment variables
@@ -48,7 +133,14 @@ pub async fn discover_gateways() -> Result<Vec<DiscoveredGateway>, String> {
         }
     }
 
-    // Method 4: Check Tailscale network
+    // Method 4: Check user hosts file
+    for hg in check_hosts_file().await {
+        if !gateways.iter().any(|g| g.url == hg.url) {
+            gateways.push(hg);
+        }
+    }
+
+    // Method 5: Check Tailscale network
     let tailscale_gateways = check_tailscale().await;
     // Filter out duplicates
     for tg in tailscale_gateways {
@@ -185,6 +277,73 @@ fn extract_url_from_config(content: &str) -> Option<String> {
     None
 }
 
+/// Path of the user hosts file (same directory as Tauri's app config dir)
+fn hosts_file_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("com.moltz.client").join(HOSTS_FILE))
+}
+
+/// Parse hosts file content
+fn parse_hosts_file(content: &str) -> Result<HostsFile, String> {
+    toml::from_str(content).map_err(|e| e.to_string())
+}
+
+/// Content of the user hosts file, if there is one
+pub(crate) fn read_hosts_file() -> Option<String> {
+    std::fs::read_to_string(hosts_file_path()?).ok()
+}
+
+/// Check that hosts file content parses
+pub(crate) fn validate_hosts_file(content: &str) -> Result<(), String> {
+    parse_hosts_file(content)
+        .map(|_| ())
+        .map_err(|e| format!("Invalid {}: {}", HOSTS_FILE, e))
+}
+
+/// Replace the user hosts file, rejecting content that doesn't parse
+pub(crate) fn write_hosts_file(content: &str) -> Result<(), String> {
+    validate_hosts_file(content)?;
+    let path = hosts_file_path().ok_or("No config directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Probe every Gateway listed in the user hosts file
+///
+/// Listed entries are always returned, reachable or not, so the user can see
+/// why a configured host is not connecting.
+async fn check_hosts_file() -> Vec<DiscoveredGateway> {
+    let Some(path) = hosts_file_path() else {
+        return Vec::new();
+    };
+    let Ok(content) = tokio::fs::read_to_string(&path).await else {
+        return Vec::new();
+    };
+    let hosts = match parse_hosts_file(&content) {
+        Ok(hosts) => hosts,
+        Err(e) => {
+            log::error!("[Discovery] Ignoring invalid {}: {}", path.display(), e);
+            return Vec::new();
+        }
+    };
+
+    let mut tasks = Vec::new();
+    for entry in &hosts.gateways {
+        for url in entry.urls() {
+            tasks.push(tokio::spawn(test_gateway(url, entry.source())));
+        }
+    }
+
+    let mut gateways = Vec::new();
+    for task in tasks {
+        if let Ok(gateway) = task.await {
+            gateways.push(gateway);
+        }
+    }
+    gateways
+}
+
 /// Check Tailscale network for Gateway instances
 async fn check_tailscale() -> Vec<DiscoveredGateway> {
     let mut gateways = Vec::new();
@@ -258,16 +417,24 @@ async fn check_tailscale() -> Vec<DiscoveredGateway> {
 }
 
 /// Test if a Gateway is reachable at the given URL
-async fn test_gateway(url: String, source: impl Into<String>) -> DiscoveredGateway {
+pub(crate) async fn test_gateway(url: String, source: impl Into<String>) -> DiscoveredGateway {
     let start = std::time::Instant::now();
 
     // Try to connect with a short timeout (1 second)
     let connect_timeout = Duration::from_secs(1);
 
-    let reachable = matches!(
-        timeout(connect_timeout, connect_async(&url)).await,
-        Ok(Ok(_))
-    );
+    let (reachable, certificate) = if url.starts_with("wss://") {
+        match timeout(connect_timeout, connect_wss_any_certificate(&url)).await {
+            Ok(Some(der)) => (true, Some(der)),
+            _ => (false, None),
+        }
+    } else {
+        let reachable = matches!(
+            timeout(connect_timeout, connect_async(&url)).await,
+            Ok(Ok(_))
+        );
+        (reachable, None)
+    };
 
     let response_time_ms = if reachable {
         Some(start.elapsed().as_millis() as u64)
@@ -275,14 +442,80 @@ async fn test_gateway(url: String, source: impl Into<String>) -> DiscoveredGatew
         None
     };
 
+    let tls = match certificate {
+        Some(der) => {
+            let trusted = matches!(
+                timeout(connect_timeout, check_certificate_trusted(&url)).await,
+                Ok(true)
+            );
+            parse_tls_info(&der, trusted)
+        }
+        None => None,
+    };
+
     DiscoveredGateway {
         url,
         source: source.into(),
         reachable,
         response_time_ms,
+        tls,
+    }
+}
+
+/// Connect to a wss:// Gateway accepting any certificate, returning the
+/// peer certificate (DER) on success
+async fn connect_wss_any_certificate(url: &str) -> Option<Vec<u8>> {
+    let connector = native_tls::TlsConnector::builder()
+        .danger_accept_invalid_certs(true)
+        .danger_accept_invalid_hostnames(true)
+        .build()
+        .ok()?;
+
+    let (ws, _) =
+        connect_async_tls_with_config(url, None, false, Some(Connector::NativeTls(connector)))
+            .await
+            .ok()?;
+
+    match ws.get_ref() {
+        MaybeTlsStream::NativeTls(tls) => tls.get_ref().peer_certificate().ok()??.to_der().ok(),
+        _ => None,
     }
 }
 
+/// Check whether a normal (verifying) TLS handshake succeeds
+async fn check_certificate_trusted(url: &str) -> bool {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return false;
+    };
+    let (Some(host), Some(port)) = (parsed.host_str(), parsed.port_or_known_default()) else {
+        return false;
+    };
+
+    let Ok(stream) = tokio::net::TcpStream::connect((host, port)).await else {
+        return false;
+    };
+    let Ok(connector) = native_tls::TlsConnector::new() else {
+        return false;
+    };
+    tokio_native_tls::TlsConnector::from(connector)
+        .connect(host, stream)
+        .await
+        .is_ok()
+}
+
+/// Extract certificate details from a DER-encoded certificate
+fn parse_tls_info(der: &[u8], trusted: bool) -> Option<TlsInfo> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+
+    Some(TlsInfo {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        expires_at: chrono::DateTime::from_timestamp(cert.validity().not_after.timestamp(), 0),
+        self_signed: cert.subject().as_raw() == cert.issuer().as_raw(),
+        trusted,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -301,6 +534,59 @@ mod tests {
         assert_eq!(url, Some("ws://localhost:18789".to_string()));
     }
 
+    #[test]
+    fn test_parse_hosts_file() {
+        let content = r#"
+[[gateway]]
+name = "NAS"
+host = "nas.local"
+
+[[gateway]]
+host = "10.0.0.5"
+port = 8789
+
+[[gateway]]
+url = "wss://gateway.example.com"
+"#;
+        let hosts = parse_hosts_file(content).unwrap();
+        assert_eq!(hosts.gateways.len(), 3);
+
+        assert_eq!(
+            hosts.gateways[0].urls(),
+            vec!["ws://nas.local:18789", "wss://nas.local:18789"]
+        );
+        assert_eq!(hosts.gateways[0].source(), "Hosts File (NAS)");
+        assert_eq!(
+            hosts.gateways[1].urls(),
+            vec!["ws://10.0.0.5:8789", "wss://10.0.0.5:8789"]
+        );
+        assert_eq!(hosts.gateways[2].urls(), vec!["wss://gateway.example.com"]);
+    }
+
+    #[test]
+    fn test_parse_tls_info_self_signed() {
+        const CERT_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIBiDCCAS2gAwIBAgIUUQywu919jrIjk0VrZp+Za9VHBG0wCgYIKoZIzj0EAwIw
+GDEWMBQGA1UEAwwNZ2F0ZXdheS5sb2NhbDAgFw0yNjEwMTYxOTEyMDBaGA8yMTI2
+MDkyMjE5MTIwMFowGDEWMBQGA1UEAwwNZ2F0ZXdheS5sb2NhbDBZMBMGByqGSM49
+AgEGCCqGSM49AwEHA0IABDh3guyNCXaJoCeqybwh7wyqadj8eYjYMt5AIeh/c1ku
+DGz0G7h5CgM/jB3YrbMvRxhKsGROwz6EGhDjmMA2LG6jUzBRMB0GA1UdDgQWBBRP
+ELofaTG1OpNT0S0A3PNzq5kTbzAfBgNVHSMEGDAWgBRPELofaTG1OpNT0S0A3PNz
+q5kTbzAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0kAMEYCIQDRY60yH3sB
+CKOi3qoAxZalPL+NemJBwwtK1qWWZ4lMowIhALdbC7RS3Kz1zr+iHAV68KYQFOMC
+P7mxnJkOwSsmjVA1
+-----END CERTIFICATE-----
+";
+        let (_, pem) = x509_parser::pem::parse_x509_pem(CERT_PEM.as_bytes()).unwrap();
+        let info = parse_tls_info(&pem.contents, false).unwrap();
+
+        assert_eq!(info.subject, "CN=gateway.local");
+        assert_eq!(info.issuer, "CN=gateway.local");
+        assert!(info.self_signed);
+        assert!(!info.trusted);
+        assert!(info.expires_at.is_some());
+    }
+
     #[test]
     fn test_extract_url_with_quotes() {
         let env = r#"GATEWAY_URL="ws://localhost:18789""#;
@@ -0,0 +1,747 @@
+//! Name resolution for Gateway connections
+//!
+//! Some ISP resolvers poison or stall lookups of Tailscale/MagicDNS names, so
+//! the resolver used to find the Gateway is selectable:
+//! - System DNS (default)
+//! - A custom DNS server, queried directly over UDP (TCP when truncated)
+//! - DNS-over-HTTPS (RFC 8484, POST of the wire format)
+//!
+//! A resolver can be set per Gateway host, overriding the default. Answers
+//! from custom and DoH resolvers are cached for their TTL (clamped), and a
+//! stale answer is used when a later lookup fails. System lookups rely on
+//! the OS cache.
+//!
+//! The DoH server's own host name is resolved with system DNS; use an IP
+//! address in its URL (e.g. `https://1.1.1.1/dns-query`) to avoid that.
+
+use crate::error::CommandError;
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Runtime, State};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Store file name for the DNS settings
+const DNS_FILE: &str = "dns.json";
+
+/// Timeout of a single query
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Bounds on how long answers are cached
+const MIN_TTL: Duration = Duration::from_secs(30);
+const MAX_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Largest DNS message read over UDP
+const MAX_UDP_RESPONSE: usize = 4096;
+
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+const CLASS_IN: u16 = 1;
+
+/// How host names are resolved
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum DnsMode {
+    #[default]
+    System,
+    /// DNS server address, optionally with a port (default 53)
+    Custom { server: String },
+    /// DNS-over-HTTPS endpoint URL
+    Doh { url: String },
+}
+
+impl DnsMode {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            DnsMode::System => Ok(()),
+            DnsMode::Custom { server } => parse_server(server).map(|_| ()),
+            DnsMode::Doh { url } => parse_doh_url(url).map(|_| ()),
+        }
+    }
+
+    /// Human-readable description, for traces and the settings pane
+    fn describe(&self) -> String {
+        match self {
+            DnsMode::System => "system DNS".to_string(),
+            DnsMode::Custom { server } => format!("DNS server {}", server),
+            DnsMode::Doh { url } => format!("DoH {}", url),
+        }
+    }
+}
+
+/// Resolver settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DnsSettings {
+    #[serde(default)]
+    pub default: DnsMode,
+    /// Per Gateway host (lowercase) overrides of the default
+    #[serde(default)]
+    pub overrides: BTreeMap<String, DnsMode>,
+}
+
+impl DnsSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        self.default.validate()?;
+        for (host, mode) in &self.overrides {
+            if host.trim().is_empty() {
+                return Err("Override host is empty".to_string());
+            }
+            mode.validate()
+                .map_err(|e| format!("Override for {}: {}", host, e))?;
+        }
+        Ok(())
+    }
+
+    /// Resolver for a host
+    fn mode_for(&self, host: &str) -> &DnsMode {
+        self.overrides
+            .get(&host.to_lowercase())
+            .unwrap_or(&self.default)
+    }
+
+    fn normalized(mut self) -> Self {
+        self.overrides = self
+            .overrides
+            .into_iter()
+            .map(|(host, mode)| (host.trim().to_lowercase(), mode))
+            .collect();
+        self
+    }
+}
+
+/// Parse a custom DNS server address (`1.1.1.1`, `1.1.1.1:5353`, `[::1]:53`)
+fn parse_server(server: &str) -> Result<SocketAddr, String> {
+    let server = server.trim();
+    if let Ok(addr) = server.parse::<SocketAddr>() {
+        return Ok(addr);
+    }
+    server
+        .trim_matches(|c| c == '[' || c == ']')
+        .parse::<IpAddr>()
+        .map(|ip| SocketAddr::new(ip, 53))
+        .map_err(|_| format!("Not an IP address: {}", server))
+}
+
+/// Parse a DoH URL into host, port and path
+fn parse_doh_url(url: &str) -> Result<(String, u16, String), String> {
+    let parsed = url::Url::parse(url.trim()).map_err(|e| format!("Invalid DoH URL: {}", e))?;
+    if parsed.scheme() != "https" {
+        return Err("DoH URL must use https://".to_string());
+    }
+    let host = parsed
+        .host_str()
+        .ok_or("DoH URL has no host")?
+        .trim_matches(|c| c == '[' || c == ']')
+        .to_string();
+    let mut path = parsed.path().to_string();
+    if let Some(query) = parsed.query() {
+        path = format!("{}?{}", path, query);
+    }
+    Ok((host, parsed.port().unwrap_or(443), path))
+}
+
+// ============================================================================
+// DNS wire format
+// ============================================================================
+
+/// Build a recursive query for `host`
+fn build_query(id: u16, host: &str, qtype: u16) -> Result<Vec<u8>, String> {
+    let name = host.trim_end_matches('.');
+    if name.is_empty() || name.len() > 253 {
+        return Err(format!("Invalid host name: {}", host));
+    }
+    let mut query = Vec::with_capacity(18 + name.len());
+    query.extend_from_slice(&id.to_be_bytes());
+    // Recursion desired, one question
+    query.extend_from_slice(&[0x01, 0x00, 0x00, 0x01, 0, 0, 0, 0, 0, 0]);
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(format!("Invalid host name: {}", host));
+        }
+        query.push(label.len() as u8);
+        query.extend_from_slice(label.as_bytes());
+    }
+    query.push(0);
+    query.extend_from_slice(&qtype.to_be_bytes());
+    query.extend_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(query)
+}
+
+/// A parsed answer
+#[derive(Debug, PartialEq)]
+enum Answer {
+    Addresses(Vec<(IpAddr, u32)>),
+    /// Truncated UDP response; retry over TCP
+    Truncated,
+}
+
+fn read_u16(buf: &[u8], at: usize) -> Result<u16, String> {
+    buf.get(at..at + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+        .ok_or_else(|| "Truncated DNS response".to_string())
+}
+
+/// Offset just past the (possibly compressed) name at `at`
+fn skip_name(buf: &[u8], mut at: usize) -> Result<usize, String> {
+    loop {
+        let len = *buf.get(at).ok_or("Truncated DNS response")?;
+        match len {
+            0 => return Ok(at + 1),
+            l if l & 0xC0 == 0xC0 => return Ok(at + 2),
+            l => at += 1 + l as usize,
+        }
+    }
+}
+
+/// Parse the address records of a response to query `id`
+fn parse_response(buf: &[u8], id: u16) -> Result<Answer, String> {
+    if read_u16(buf, 0)? != id {
+        return Err("DNS response ID mismatch".to_string());
+    }
+    let flags = read_u16(buf, 2)?;
+    if flags & 0x8000 == 0 {
+        return Err("Not a DNS response".to_string());
+    }
+    if flags & 0x0200 != 0 {
+        return Ok(Answer::Truncated);
+    }
+    match flags & 0x000F {
+        0 => {}
+        3 => return Err("Name not found (NXDOMAIN)".to_string()),
+        code => return Err(format!("DNS server error (rcode {})", code)),
+    }
+
+    let questions = read_u16(buf, 4)?;
+    let answers = read_u16(buf, 6)?;
+    let mut at = 12;
+    for _ in 0..questions {
+        at = skip_name(buf, at)? + 4;
+    }
+
+    let mut addrs = Vec::new();
+    for _ in 0..answers {
+        at = skip_name(buf, at)?;
+        let rtype = read_u16(buf, at)?;
+        let ttl = buf
+            .get(at + 4..at + 8)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+            .ok_or("Truncated DNS response")?;
+        let len = read_u16(buf, at + 8)? as usize;
+        let data = buf
+            .get(at + 10..at + 10 + len)
+            .ok_or("Truncated DNS response")?;
+        match (rtype, len) {
+            (TYPE_A, 4) => addrs.push((
+                IpAddr::V4(Ipv4Addr::new(data[0], data[1], data[2], data[3])),
+                ttl,
+            )),
+            (TYPE_AAAA, 16) => {
+                let octets: [u8; 16] = data.try_into().map_err(|_| "Bad AAAA record")?;
+                addrs.push((IpAddr::V6(Ipv6Addr::from(octets)), ttl));
+            }
+            // CNAMEs etc.: the recursive resolver includes the final records
+            _ => {}
+        }
+        at += 10 + len;
+    }
+    Ok(Answer::Addresses(addrs))
+}
+
+/// Body of an HTTP/1.1 response, which must be 200 OK
+fn parse_http_response(response: &[u8]) -> Result<Vec<u8>, String> {
+    let split = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or("Malformed HTTP response")?;
+    let head = String::from_utf8_lossy(&response[..split]);
+    let mut body = &response[split + 4..];
+
+    let mut lines = head.lines();
+    let status = lines.next().unwrap_or_default();
+    let code = status.split_whitespace().nth(1).unwrap_or_default();
+    if code != "200" {
+        return Err(format!("DoH server answered {}", status));
+    }
+    let mut chunked = false;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("transfer-encoding") && value.eq_ignore_ascii_case("chunked") {
+            chunked = true;
+        } else if name.eq_ignore_ascii_case("content-length") {
+            let len: usize = value.parse().map_err(|_| "Bad Content-Length")?;
+            body = body.get(..len).ok_or("Truncated HTTP response")?;
+        }
+    }
+    if !chunked {
+        return Ok(body.to_vec());
+    }
+
+    let mut decoded = Vec::new();
+    loop {
+        let line_end = body
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or("Malformed chunked response")?;
+        let size_text = String::from_utf8_lossy(&body[..line_end]);
+        let size_text = size_text.split(';').next().unwrap_or_default().trim();
+        let size =
+            usize::from_str_radix(size_text, 16).map_err(|_| "Malformed chunked response")?;
+        if size == 0 {
+            return Ok(decoded);
+        }
+        let chunk = body
+            .get(line_end + 2..line_end + 2 + size)
+            .ok_or("Truncated HTTP response")?;
+        decoded.extend_from_slice(chunk);
+        body = body.get(line_end + 4 + size..).unwrap_or_default();
+    }
+}
+
+// ============================================================================
+// Transports
+// ============================================================================
+
+async fn query_udp(server: SocketAddr, query: &[u8]) -> Result<Vec<u8>, String> {
+    let bind: SocketAddr = if server.is_ipv4() {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = tokio::net::UdpSocket::bind(bind)
+        .await
+        .map_err(|e| e.to_string())?;
+    socket.connect(server).await.map_err(|e| e.to_string())?;
+    socket.send(query).await.map_err(|e| e.to_string())?;
+    let mut buf = vec![0u8; MAX_UDP_RESPONSE];
+    let len = socket.recv(&mut buf).await.map_err(|e| e.to_string())?;
+    buf.truncate(len);
+    Ok(buf)
+}
+
+async fn query_tcp(server: SocketAddr, query: &[u8]) -> Result<Vec<u8>, String> {
+    let mut stream = tokio::net::TcpStream::connect(server)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut message = (query.len() as u16).to_be_bytes().to_vec();
+    message.extend_from_slice(query);
+    stream
+        .write_all(&message)
+        .await
+        .map_err(|e| e.to_string())?;
+    let len = stream.read_u16().await.map_err(|e| e.to_string())?;
+    let mut buf = vec![0u8; len as usize];
+    stream
+        .read_exact(&mut buf)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(buf)
+}
+
+async fn query_doh(url: &str, query: &[u8]) -> Result<Vec<u8>, String> {
+    let (host, port, path) = parse_doh_url(url)?;
+    let stream = tokio::net::TcpStream::connect((host.as_str(), port))
+        .await
+        .map_err(|e| format!("Cannot reach {}: {}", host, e))?;
+    let connector = native_tls::TlsConnector::new().map_err(|e| e.to_string())?;
+    let mut stream = tokio_native_tls::TlsConnector::from(connector)
+        .connect(&host, stream)
+        .await
+        .map_err(|e| format!("TLS handshake with {} failed: {}", host, e))?;
+
+    let request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/dns-message\r\nAccept: application/dns-message\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        path,
+        host,
+        query.len()
+    );
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+    stream.write_all(query).await.map_err(|e| e.to_string())?;
+    let mut response = Vec::new();
+    stream
+        .read_to_end(&mut response)
+        .await
+        .map_err(|e| e.to_string())?;
+    parse_http_response(&response)
+}
+
+/// Send a query and read the response
+async fn exchange(mode: &DnsMode, request: &[u8], id: u16) -> Result<Vec<u8>, String> {
+    match mode {
+        DnsMode::System => Err("System DNS is not queried directly".to_string()),
+        DnsMode::Custom { server } => {
+            let server = parse_server(server)?;
+            let response = query_udp(server, request).await?;
+            if parse_response(&response, id)? == Answer::Truncated {
+                query_tcp(server, request).await
+            } else {
+                Ok(response)
+            }
+        }
+        DnsMode::Doh { url } => query_doh(url, request).await,
+    }
+}
+
+/// Query one record type
+async fn query(mode: &DnsMode, host: &str, qtype: u16) -> Result<Vec<(IpAddr, u32)>, String> {
+    let id: u16 = rand::random();
+    let request = build_query(id, host, qtype)?;
+    let response = tokio::time::timeout(QUERY_TIMEOUT, exchange(mode, &request, id))
+        .await
+        .map_err(|_| format!("No answer within {}s", QUERY_TIMEOUT.as_secs()))??;
+    match parse_response(&response, id)? {
+        Answer::Addresses(addrs) => Ok(addrs),
+        Answer::Truncated => Err("Truncated DNS response".to_string()),
+    }
+}
+
+// ============================================================================
+// Resolver
+// ============================================================================
+
+/// Result of a lookup
+#[derive(Debug, Clone, Serialize)]
+pub struct Lookup {
+    pub addrs: Vec<SocketAddr>,
+    /// Resolver that answered, e.g. "DoH https://1.1.1.1/dns-query"
+    pub via: String,
+    pub cached: bool,
+}
+
+#[derive(Debug, Clone)]
+struct CachedAnswer {
+    addrs: Vec<IpAddr>,
+    expires: Instant,
+}
+
+struct ResolverInner {
+    settings: RwLock<DnsSettings>,
+    /// Keyed by resolver and host
+    cache: Mutex<HashMap<(DnsMode, String), CachedAnswer>>,
+}
+
+/// Shared resolver, cheap to clone into connection tasks
+#[derive(Clone)]
+pub struct Resolver {
+    inner: Arc<ResolverInner>,
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new(DnsSettings::default())
+    }
+}
+
+impl Resolver {
+    fn new(settings: DnsSettings) -> Self {
+        Self {
+            inner: Arc::new(ResolverInner {
+                settings: RwLock::new(settings),
+                cache: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    fn settings(&self) -> DnsSettings {
+        self.inner
+            .settings
+            .read()
+            .map(|s| s.clone())
+            .unwrap_or_default()
+    }
+
+    fn set_settings(&self, settings: DnsSettings) {
+        if let Ok(mut current) = self.inner.settings.write() {
+            *current = settings;
+        }
+        self.clear_cache();
+    }
+
+    fn clear_cache(&self) {
+        if let Ok(mut cache) = self.inner.cache.lock() {
+            cache.clear();
+        }
+    }
+
+    /// Resolve `host` with the resolver configured for it
+    pub async fn resolve(&self, host: &str, port: u16) -> Result<Lookup, String> {
+        let mode = self.settings().mode_for(host).clone();
+        self.resolve_with(&mode, host, port).await
+    }
+
+    async fn resolve_with(&self, mode: &DnsMode, host: &str, port: u16) -> Result<Lookup, String> {
+        let bare = host.trim_matches(|c| c == '[' || c == ']');
+        if let Ok(ip) = bare.parse::<IpAddr>() {
+            return Ok(Lookup {
+                addrs: vec![SocketAddr::new(ip, port)],
+                via: "IP address".to_string(),
+                cached: false,
+            });
+        }
+        let via = mode.describe();
+        if *mode == DnsMode::System {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+                .await
+                .map_err(|e| e.to_string())?
+                .collect();
+            return Ok(Lookup {
+                addrs,
+                via,
+                cached: false,
+            });
+        }
+
+        let key = (mode.clone(), host.to_lowercase());
+        let cached = self
+            .inner
+            .cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(&key).cloned());
+        let to_lookup = |addrs: &[IpAddr], cached: bool| Lookup {
+            addrs: addrs.iter().map(|ip| SocketAddr::new(*ip, port)).collect(),
+            via: via.clone(),
+            cached,
+        };
+        if let Some(answer) = &cached {
+            if answer.expires > Instant::now() {
+                return Ok(to_lookup(&answer.addrs, true));
+            }
+        }
+
+        // IPv6 only when the host has no IPv4 address
+        let mut result = query(mode, host, TYPE_A).await;
+        if matches!(&result, Ok(addrs) if addrs.is_empty()) {
+            result = query(mode, host, TYPE_AAAA).await;
+        }
+        match result {
+            Ok(records) if !records.is_empty() => {
+                let ttl = records.iter().map(|(_, ttl)| *ttl).min().unwrap_or(0);
+                let ttl = Duration::from_secs(ttl as u64).clamp(MIN_TTL, MAX_TTL);
+                let addrs: Vec<IpAddr> = records.into_iter().map(|(ip, _)| ip).collect();
+                if let Ok(mut cache) = self.inner.cache.lock() {
+                    cache.insert(
+                        key,
+                        CachedAnswer {
+                            addrs: addrs.clone(),
+                            expires: Instant::now() + ttl,
+                        },
+                    );
+                }
+                Ok(to_lookup(&addrs, false))
+            }
+            result => {
+                let error = match result {
+                    Err(e) => e,
+                    Ok(_) => format!("No addresses for {}", host),
+                };
+                // A stale answer beats no connection
+                match cached {
+                    Some(answer) => {
+                        log::error!(
+                            "[DNS] {} failed for {} ({}), using cached answer",
+                            via,
+                            host,
+                            error
+                        );
+                        Ok(to_lookup(&answer.addrs, true))
+                    }
+                    None => Err(format!("{} via {}", error, via)),
+                }
+            }
+        }
+    }
+}
+
+/// DNS state managed by Tauri
+pub struct DnsState {
+    path: PathBuf,
+    resolver: Resolver,
+}
+
+impl DnsState {
+    /// Load the DNS settings from the local store
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, DNS_FILE);
+        let settings: DnsSettings = store::load(&path);
+        Self {
+            path,
+            resolver: Resolver::new(settings),
+        }
+    }
+
+    pub fn resolver(&self) -> Resolver {
+        self.resolver.clone()
+    }
+
+    pub fn settings(&self) -> DnsSettings {
+        self.resolver.settings()
+    }
+
+    /// Validate, save and apply settings
+    pub fn apply(&self, settings: DnsSettings) -> Result<(), String> {
+        settings.validate()?;
+        let settings = settings.normalized();
+        store::save(&self.path, &settings)?;
+        self.resolver.set_settings(settings);
+        Ok(())
+    }
+}
+
+/// Current resolver settings
+#[tauri::command]
+pub async fn get_dns_settings(state: State<'_, DnsState>) -> Result<DnsSettings, CommandError> {
+    Ok(state.settings())
+}
+
+/// Replace the resolver settings (clears the cache)
+#[tauri::command]
+pub async fn set_dns_settings(
+    state: State<'_, DnsState>,
+    settings: DnsSettings,
+) -> Result<(), CommandError> {
+    settings.validate().map_err(CommandError::validation)?;
+    state.apply(settings)?;
+    Ok(())
+}
+
+/// Resolve a host with the given resolver, or the one configured for it
+#[tauri::command]
+pub async fn test_dns(
+    state: State<'_, DnsState>,
+    host: String,
+    mode: Option<DnsMode>,
+) -> Result<Lookup, CommandError> {
+    let resolver = state.resolver();
+    let result = match mode {
+        Some(mode) => {
+            mode.validate().map_err(CommandError::validation)?;
+            resolver.resolve_with(&mode, &host, 0).await
+        }
+        None => resolver.resolve(&host, 0).await,
+    };
+    result.map_err(CommandError::network)
+}
+
+/// Forget cached answers
+#[tauri::command]
+pub async fn clear_dns_cache(state: State<'_, DnsState>) -> Result<(), CommandError> {
+    state.resolver.clear_cache();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Response to `build_query(0x1234, "gw.ts.net", TYPE_A)` with a CNAME
+    /// and an A record, using name compression
+    fn response() -> Vec<u8> {
+        let mut buf = build_query(0x1234, "gw.ts.net", TYPE_A).unwrap();
+        buf[2] = 0x81; // QR + RD
+        buf[3] = 0x80; // RA
+        buf[7] = 2; // two answers
+                    // CNAME gw.ts.net -> pointer to the question name
+        buf.extend_from_slice(&[0xC0, 12, 0, 5, 0, 1, 0, 0, 0, 60, 0, 2, 0xC0, 12]);
+        // A 100.64.0.7, TTL 300
+        buf.extend_from_slice(&[0xC0, 12, 0, 1, 0, 1, 0, 0, 1, 44, 0, 4, 100, 64, 0, 7]);
+        buf
+    }
+
+    #[test]
+    fn test_build_query() {
+        let query = build_query(0xABCD, "gw.ts.net.", TYPE_AAAA).unwrap();
+        assert_eq!(&query[..4], &[0xAB, 0xCD, 0x01, 0x00]);
+        assert_eq!(&query[12..23], b"\x02gw\x02ts\x03net\x00");
+        assert_eq!(&query[23..], &[0, 28, 0, 1]);
+
+        assert!(build_query(1, "bad..name", TYPE_A).is_err());
+        assert!(build_query(1, &"a".repeat(64), TYPE_A).is_err());
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let answer = parse_response(&response(), 0x1234).unwrap();
+        assert_eq!(
+            answer,
+            Answer::Addresses(vec![("100.64.0.7".parse().unwrap(), 300)])
+        );
+        assert!(parse_response(&response(), 0x4321).is_err());
+
+        let mut nxdomain = response();
+        nxdomain[3] = 0x83;
+        assert!(parse_response(&nxdomain, 0x1234)
+            .unwrap_err()
+            .contains("NXDOMAIN"));
+
+        let mut truncated = response();
+        truncated[2] |= 0x02;
+        assert_eq!(
+            parse_response(&truncated, 0x1234).unwrap(),
+            Answer::Truncated
+        );
+
+        let full = response();
+        assert!(parse_response(&full[..full.len() - 2], 0x1234).is_err());
+    }
+
+    #[test]
+    fn test_parse_http_response() {
+        let plain = b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\n\r\nabcdef";
+        assert_eq!(parse_http_response(plain).unwrap(), b"abc");
+
+        let chunked = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n2;x=y\r\nde\r\n0\r\n\r\n";
+        assert_eq!(parse_http_response(chunked).unwrap(), b"abcde");
+
+        let error = b"HTTP/1.1 400 Bad Request\r\n\r\n";
+        assert!(parse_http_response(error).is_err());
+    }
+
+    #[test]
+    fn test_settings() {
+        let settings = DnsSettings {
+            default: DnsMode::Custom {
+                server: "1.1.1.1".to_string(),
+            },
+            overrides: [(
+                " NAS.tail1234.TS.net ".to_string(),
+                DnsMode::Doh {
+                    url: "https://1.1.1.1/dns-query".to_string(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+        assert!(settings.validate().is_ok());
+        let settings = settings.normalized();
+        assert!(matches!(
+            settings.mode_for("nas.TAIL1234.ts.net"),
+            DnsMode::Doh { .. }
+        ));
+        assert!(matches!(
+            settings.mode_for("other.host"),
+            DnsMode::Custom { .. }
+        ));
+
+        assert_eq!(
+            parse_server("[::1]:5353").unwrap(),
+            "[::1]:5353".parse().unwrap()
+        );
+        assert_eq!(parse_server("9.9.9.9").unwrap().port(), 53);
+        assert!(parse_server("dns.example.com").is_err());
+        assert_eq!(
+            parse_doh_url("https://dns.example/dns-query?ct").unwrap(),
+            ("dns.example".to_string(), 443, "/dns-query?ct".to_string())
+        );
+        assert!(parse_doh_url("http://dns.example/dns-query").is_err());
+    }
+}
@@ -0,0 +1,314 @@
+//! Drag answers out of the app as files
+//!
+//! A message or code block is written to a temp file, then an OS drag session
+//! is started from the window so it can be dropped into Finder/Explorer, an
+//! email or an IDE:
+//! - macOS: `NSView dragFile:` with the current mouse event
+//! - Windows: shell data object + `SHDoDragDrop`
+//! - Linux: a GTK drag offering a `text/uri-list`
+//!
+//! Drop targets may read the file after the drag ends, so files are kept and
+//! swept once older than [`MAX_FILE_AGE`].
+
+use crate::error::CommandError;
+use crate::guest;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::WebviewWindow;
+
+/// Temp subdirectory holding dragged files
+const DRAG_DIR: &str = "moltz-drag";
+
+/// Dragged files older than this are removed
+const MAX_FILE_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Longest file stem, in characters
+const MAX_STEM_CHARS: usize = 60;
+
+/// File extension for a code block language (messages are Markdown)
+fn extension_for(language: Option<&str>) -> &'static str {
+    match language.map(|l| l.trim().to_ascii_lowercase()).as_deref() {
+        None | Some("") | Some("markdown") | Some("md") => "md",
+        Some("rust") | Some("rs") => "rs",
+        Some("python") | Some("py") => "py",
+        Some("javascript") | Some("js") => "js",
+        Some("jsx") => "jsx",
+        Some("typescript") | Some("ts") => "ts",
+        Some("tsx") => "tsx",
+        Some("json") => "json",
+        Some("html") => "html",
+        Some("css") => "css",
+        Some("go") => "go",
+        Some("java") => "java",
+        Some("kotlin") | Some("kt") => "kt",
+        Some("swift") => "swift",
+        Some("c") => "c",
+        Some("cpp") | Some("c++") => "cpp",
+        Some("csharp") | Some("cs") | Some("c#") => "cs",
+        Some("ruby") | Some("rb") => "rb",
+        Some("php") => "php",
+        Some("bash") | Some("sh") | Some("shell") | Some("zsh") => "sh",
+        Some("powershell") | Some("ps1") => "ps1",
+        Some("sql") => "sql",
+        Some("yaml") | Some("yml") => "yaml",
+        Some("toml") => "toml",
+        Some("xml") => "xml",
+        Some("diff") | Some("patch") => "diff",
+        _ => "txt",
+    }
+}
+
+/// Turn a title into a file stem that is valid on every OS
+fn file_stem(name: Option<&str>) -> String {
+    let stem: String = name
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ' ') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .take(MAX_STEM_CHARS)
+        .collect();
+    let stem = stem.trim_matches(|c: char| c == '.' || c == '-' || c.is_whitespace());
+    if stem.is_empty() {
+        "answer".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+/// Remove dragged files past [`MAX_FILE_AGE`]
+fn sweep(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > MAX_FILE_AGE);
+        if expired {
+            let _ = std::fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+/// Temp directory holding dragged files
+pub(crate) fn temp_root() -> PathBuf {
+    std::env::temp_dir().join(DRAG_DIR)
+}
+
+/// Write the content to a fresh temp file named `<stem>.<ext>`
+///
+/// Each file gets its own directory so the dropped file keeps a clean name.
+fn materialize(
+    content: &str,
+    language: Option<&str>,
+    name: Option<&str>,
+) -> Result<PathBuf, String> {
+    let root = temp_root();
+    sweep(&root);
+    let dir = root.join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create temp folder: {}", e))?;
+    let path = dir.join(format!("{}.{}", file_stem(name), extension_for(language)));
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write temp file: {}", e))?;
+    Ok(path)
+}
+
+#[cfg(target_os = "macos")]
+fn begin_drag(window: &WebviewWindow, path: &Path) -> Result<(), String> {
+    use objc2::runtime::{AnyObject, Bool};
+    use objc2::{class, msg_send};
+    use objc2_foundation::{NSPoint, NSRect, NSSize, NSString};
+
+    let view = window.ns_view().map_err(|e| e.to_string())? as *mut AnyObject;
+    // SAFETY: runs on the main thread; the view belongs to the live window
+    // and the event is the one currently being dispatched
+    unsafe {
+        let app: *mut AnyObject = msg_send![class!(NSApplication), sharedApplication];
+        let event: *mut AnyObject = msg_send![app, currentEvent];
+        if event.is_null() {
+            return Err("No mouse event to start the drag from".to_string());
+        }
+        let in_window: NSPoint = msg_send![event, locationInWindow];
+        let location: NSPoint =
+            msg_send![view, convertPoint: in_window, fromView: std::ptr::null_mut::<AnyObject>()];
+        let rect = NSRect::new(
+            NSPoint::new(location.x - 16.0, location.y - 16.0),
+            NSSize::new(32.0, 32.0),
+        );
+        let file = NSString::from_str(&path.to_string_lossy());
+        let started: Bool = msg_send![
+            view,
+            dragFile: &*file,
+            fromRect: rect,
+            slideBack: Bool::YES,
+            event: event
+        ];
+        if started.as_bool() {
+            Ok(())
+        } else {
+            Err("The drag could not be started".to_string())
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn begin_drag(window: &WebviewWindow, path: &Path) -> Result<(), String> {
+    use std::ffi::c_void;
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::core::GUID;
+    use windows_sys::Win32::System::Ole::DROPEFFECT_COPY;
+    use windows_sys::Win32::UI::Shell::{
+        ILCreateFromPathW, ILFree, SHCreateDataObject, SHDoDragDrop,
+    };
+
+    const IID_IDATAOBJECT: GUID = GUID::from_u128(0x0000010e_0000_0000_c000_000000000046);
+
+    let hwnd = window.hwnd().map_err(|e| e.to_string())?.0 as _;
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    // SAFETY: runs on the (OLE-initialized) main thread; the PIDL and data
+    // object are released before returning
+    unsafe {
+        let pidl = ILCreateFromPathW(wide.as_ptr());
+        if pidl.is_null() {
+            return Err("Failed to resolve the temp file".to_string());
+        }
+        let items = [pidl as *const _];
+        let mut data: *mut c_void = std::ptr::null_mut();
+        let hr = SHCreateDataObject(
+            std::ptr::null(),
+            1,
+            items.as_ptr(),
+            std::ptr::null_mut(),
+            &IID_IDATAOBJECT,
+            &mut data,
+        );
+        ILFree(pidl);
+        if hr < 0 || data.is_null() {
+            return Err(format!("Failed to create the drag data (0x{:08X})", hr));
+        }
+        // Blocks until the drop; the default drop source handles the mouse
+        let mut effect = 0;
+        let hr = SHDoDragDrop(
+            hwnd,
+            data,
+            std::ptr::null_mut(),
+            DROPEFFECT_COPY,
+            &mut effect,
+        );
+
+        // IUnknown::Release is the third vtable entry
+        let vtable = *(data as *const *const [unsafe extern "system" fn(*mut c_void) -> u32; 3]);
+        ((*vtable)[2])(data);
+
+        if hr < 0 {
+            return Err(format!("The drag failed (0x{:08X})", hr));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn begin_drag(window: &WebviewWindow, path: &Path) -> Result<(), String> {
+    use gtk::prelude::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let gtk_window = window.gtk_window().map_err(|e| e.to_string())?;
+    let uri = gtk::glib::filename_to_uri(path, None).map_err(|e| e.to_string())?;
+    let targets = gtk::TargetList::new(&[gtk::TargetEntry::new(
+        "text/uri-list",
+        gtk::TargetFlags::OTHER_APP,
+        0,
+    )]);
+
+    // Serve the URI for this drag only
+    let data_get = gtk_window.connect_drag_data_get(move |_, _, data, _, _| {
+        data.set_uris(&[uri.as_str()]);
+    });
+    let handlers = Rc::new(RefCell::new(Some(data_get)));
+    let drag_end = Rc::new(RefCell::new(None));
+    let drag_end_handler = drag_end.clone();
+    *drag_end.borrow_mut() = Some(gtk_window.connect_drag_end(move |widget, _| {
+        if let Some(id) = handlers.borrow_mut().take() {
+            widget.disconnect(id);
+        }
+        if let Some(id) = drag_end_handler.borrow_mut().take() {
+            widget.disconnect(id);
+        }
+    }));
+
+    gtk_window
+        .drag_begin_with_coordinates(&targets, gtk::gdk::DragAction::COPY, 1, None, -1, -1)
+        .map(|_| ())
+        .ok_or_else(|| "The drag could not be started".to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn begin_drag(_window: &WebviewWindow, _path: &Path) -> Result<(), String> {
+    Err("Dragging files out is not supported on this platform".to_string())
+}
+
+/// Materialize a message or code block as a file and start dragging it
+///
+/// Call while the mouse button is held (from `mousedown` / `dragstart`).
+/// `language` picks the extension (Markdown when absent); `name` the file
+/// name, sanitized.
+#[tauri::command]
+pub async fn drag_out(
+    window: WebviewWindow,
+    content: String,
+    language: Option<String>,
+    name: Option<String>,
+) -> Result<String, CommandError> {
+    guest::ensure_inactive("Dragging out files")?;
+    let path = tokio::task::spawn_blocking(move || {
+        materialize(&content, language.as_deref(), name.as_deref())
+    })
+    .await
+    .map_err(|e| CommandError::internal(e.to_string()))?
+    .map_err(CommandError::io)?;
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let drag_window = window.clone();
+    let drag_path = path.clone();
+    window
+        .run_on_main_thread(move || {
+            let _ = tx.send(begin_drag(&drag_window, &drag_path));
+        })
+        .map_err(|e| CommandError::internal(e.to_string()))?;
+    rx.await
+        .map_err(|e| CommandError::internal(e.to_string()))?
+        .inspect_err(|e| {
+            log::error!("[DragOut] {}", e);
+        })?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_names() {
+        assert_eq!(extension_for(None), "md");
+        assert_eq!(extension_for(Some("TypeScript")), "ts");
+        assert_eq!(extension_for(Some("brainfuck")), "txt");
+
+        assert_eq!(file_stem(None), "answer");
+        assert_eq!(file_stem(Some("  ../etc/passwd ")), "etc-passwd");
+        assert_eq!(file_stem(Some("Q: what?")), "Q- what");
+        assert_eq!(file_stem(Some(&"x".repeat(100))).len(), MAX_STEM_CHARS);
+
+        let path = materialize("fn main() {}", Some("rust"), Some("main")).unwrap();
+        assert!(path.ends_with("main.rs"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "fn main() {}");
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
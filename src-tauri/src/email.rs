@@ -0,0 +1,198 @@
+//! Email export of conversations
+//!
+//! Opens a prefilled draft in the default mail client:
+//! - macOS: the native compose-email sharing service, with the HTML
+//!   rendering as rich text when given
+//! - Elsewhere (and as the macOS fallback): a `mailto:` link with the plain
+//!   text body, truncated to what mail clients and OS shells reliably accept
+
+use crate::error::CommandError;
+use serde::Serialize;
+use tauri::AppHandle;
+
+/// Longest body put into a `mailto:` link, in characters; Windows rejects
+/// URLs over ~2K characters once encoded
+const MAX_MAILTO_BODY_CHARS: usize = 1500;
+
+/// Appended to truncated bodies
+const TRUNCATION_NOTE: &str =
+    "\n\n[Conversation truncated — export it to a file for the full text]";
+
+/// How a draft was opened
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailDraft {
+    /// "native" or "mailto"
+    pub method: String,
+    /// Whether the body was shortened to fit a `mailto:` link
+    pub truncated: bool,
+}
+
+/// Percent-encode a `mailto:` header value (RFC 6068)
+fn encode_mailto_component(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.replace("\r\n", "\n").replace('\n', "\r\n").bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Build a `mailto:` link, truncating long bodies
+fn mailto_url(subject: &str, body: &str) -> (String, bool) {
+    let truncated = body.chars().count() > MAX_MAILTO_BODY_CHARS;
+    let body = if truncated {
+        let cut: String = body.chars().take(MAX_MAILTO_BODY_CHARS).collect();
+        format!("{}{}", cut.trim_end(), TRUNCATION_NOTE)
+    } else {
+        body.to_string()
+    };
+    let url = format!(
+        "mailto:?subject={}&body={}",
+        encode_mailto_component(subject),
+        encode_mailto_component(&body)
+    );
+    (url, truncated)
+}
+
+/// Open a URL with the OS default handler
+fn open_url(url: &str) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        // rundll32 avoids cmd.exe's handling of `&` in the URL
+        let mut command = std::process::Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = std::process::Command::new("xdg-open");
+
+    command
+        .arg(url)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open the mail client: {}", e))
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use objc2::rc::{Allocated, Retained};
+    use objc2::runtime::{AnyObject, Bool};
+    use objc2::{class, msg_send};
+    use objc2_foundation::{NSData, NSString};
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {
+        static NSSharingServiceNameComposeEmail: &'static NSString;
+    }
+
+    /// Open a compose window through the sharing service (main thread only)
+    ///
+    /// Returns false when no mail service is available.
+    pub fn compose(subject: &str, body: &str, html: Option<&str>) -> bool {
+        // SAFETY: called on the main thread; every object passed to AppKit is
+        // retained for the duration of the calls
+        unsafe {
+            let service: Option<Retained<AnyObject>> = msg_send![
+                class!(NSSharingService),
+                sharingServiceNamed: NSSharingServiceNameComposeEmail
+            ];
+            let Some(service) = service else {
+                return false;
+            };
+            let subject = NSString::from_str(subject);
+            let _: () = msg_send![&*service, setSubject: &*subject];
+
+            let rich: Option<Retained<AnyObject>> = html.and_then(|html| {
+                let data = NSData::with_bytes(html.as_bytes());
+                let allocated: Allocated<AnyObject> = msg_send![class!(NSAttributedString), alloc];
+                msg_send![
+                    allocated,
+                    initWithHTML: &*data,
+                    documentAttributes: std::ptr::null_mut::<*mut AnyObject>()
+                ]
+            });
+            let plain = NSString::from_str(body);
+            let item: &AnyObject = match &rich {
+                Some(rich) => rich,
+                None => &plain,
+            };
+            let items: Retained<AnyObject> = msg_send![class!(NSArray), arrayWithObject: item];
+
+            let can_perform: Bool = msg_send![&*service, canPerformWithItems: &*items];
+            if !can_perform.as_bool() {
+                return false;
+            }
+            let _: () = msg_send![&*service, performWithItems: &*items];
+            true
+        }
+    }
+}
+
+/// Open a prefilled email draft
+///
+/// `body` is the Markdown rendering, sent as plain text; `html` an optional
+/// rich rendering used where the platform's compose API accepts one.
+#[tauri::command]
+pub async fn compose_email(
+    app: AppHandle,
+    subject: String,
+    body: String,
+    html: Option<String>,
+) -> Result<EmailDraft, CommandError> {
+    let body = crate::plaintext::to_plaintext(&body);
+    #[cfg(target_os = "macos")]
+    {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let (native_subject, native_body) = (subject.clone(), body.clone());
+        let scheduled = app.run_on_main_thread(move || {
+            let _ = tx.send(macos::compose(
+                &native_subject,
+                &native_body,
+                html.as_deref(),
+            ));
+        });
+        if scheduled.is_ok() && rx.await.unwrap_or(false) {
+            return Ok(EmailDraft {
+                method: "native".to_string(),
+                truncated: false,
+            });
+        }
+        log::error!("[Email] Native compose unavailable, falling back to mailto");
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = (&app, html);
+
+    let (url, truncated) = mailto_url(&subject, &body);
+    tokio::task::spawn_blocking(move || open_url(&url))
+        .await
+        .map_err(|e| CommandError::internal(e.to_string()))??;
+    Ok(EmailDraft {
+        method: "mailto".to_string(),
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mailto_url_encoding_and_truncation() {
+        let (url, truncated) = mailto_url("Q&A: résumé", "line 1\nline 2 = ok?");
+        assert!(!truncated);
+        assert_eq!(
+            url,
+            "mailto:?subject=Q%26A%3A%20r%C3%A9sum%C3%A9&body=line%201%0D%0Aline%202%20%3D%20ok%3F"
+        );
+
+        let (url, truncated) = mailto_url("Long", &"é".repeat(MAX_MAILTO_BODY_CHARS + 1));
+        assert!(truncated);
+        assert!(url.ends_with(&encode_mailto_component(TRUNCATION_NOTE)));
+    }
+}
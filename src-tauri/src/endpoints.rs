@@ -0,0 +1,401 @@
+//! Home/away Gateway endpoints
+//!
+//! The same Gateway is often reachable at two URLs: a LAN address at home
+//! and a Tailscale or public address everywhere else. With both configured,
+//! the LAN URL is used whenever it is reachable:
+//! - `connect` with either URL goes through the endpoint for the current
+//!   network
+//! - A network monitor watches the local addresses used for outbound
+//!   traffic; when they change, the LAN URL is re-checked and a live
+//!   connection is moved to the other endpoint if needed
+//!
+//! The endpoint in use is emitted as `endpoints:active`.
+
+use crate::error::CommandError;
+use crate::gateway::{self, GatewayState};
+use crate::{power, store};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+/// Store file name for the endpoint settings
+const ENDPOINTS_FILE: &str = "endpoints.json";
+
+/// How often the network is checked for changes
+const NETWORK_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long the home URL gets to accept a TCP connection
+const HOME_PROBE_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Home and away URLs of the Gateway
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EndpointSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Gateway URL on the home network
+    #[serde(rename = "homeUrl", default)]
+    pub home_url: String,
+    /// Gateway URL everywhere else (Tailscale or public)
+    #[serde(rename = "awayUrl", default)]
+    pub away_url: String,
+}
+
+impl EndpointSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let home = endpoint_host(&self.home_url).ok_or("Home URL must be a ws:// or wss:// URL")?;
+        let away = endpoint_host(&self.away_url).ok_or("Away URL must be a ws:// or wss:// URL")?;
+        if home == away {
+            return Err("Home and away URLs must use different hosts".to_string());
+        }
+        Ok(())
+    }
+
+    /// Which endpoint `url` is, matched by host so a ws/wss fallback still
+    /// counts as the same endpoint
+    fn endpoint_of(&self, url: &str) -> Option<Endpoint> {
+        if !self.enabled {
+            return None;
+        }
+        let host = endpoint_host(url)?;
+        if endpoint_host(&self.home_url).as_ref() == Some(&host) {
+            Some(Endpoint::Home)
+        } else if endpoint_host(&self.away_url).as_ref() == Some(&host) {
+            Some(Endpoint::Away)
+        } else {
+            None
+        }
+    }
+
+    fn url_of(&self, endpoint: Endpoint) -> &str {
+        match endpoint {
+            Endpoint::Home => self.home_url.trim(),
+            Endpoint::Away => self.away_url.trim(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Endpoint {
+    Home,
+    Away,
+}
+
+impl Endpoint {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Home => "home",
+            Self::Away => "away",
+        }
+    }
+}
+
+/// Endpoint a connection goes through
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActiveEndpoint {
+    pub endpoint: Endpoint,
+    pub url: String,
+}
+
+/// Lowercase host of a ws:// or wss:// URL
+fn endpoint_host(url: &str) -> Option<String> {
+    let parsed = url::Url::parse(url.trim()).ok()?;
+    if !matches!(parsed.scheme(), "ws" | "wss") {
+        return None;
+    }
+    parsed.host_str().map(str::to_lowercase)
+}
+
+/// Host and port to probe for a Gateway URL
+fn probe_target(url: &str) -> Option<(String, u16)> {
+    let parsed = url::Url::parse(url.trim()).ok()?;
+    let host = parsed.host_str()?.to_string();
+    Some((host, parsed.port_or_known_default()?))
+}
+
+/// Local addresses the OS picks for outbound IPv4 and IPv6 traffic
+///
+/// Connecting a UDP socket sends nothing, it only selects a route. The
+/// addresses change when the machine joins another network.
+fn network_fingerprint() -> String {
+    let local = |bind: &str, target: &str| {
+        let socket = std::net::UdpSocket::bind(bind).ok()?;
+        socket.connect(target).ok()?;
+        socket.local_addr().ok().map(|addr| addr.ip().to_string())
+    };
+    // Documentation addresses: they take the default route and get no traffic
+    format!(
+        "{}|{}",
+        local("0.0.0.0:0", "192.0.2.1:9").unwrap_or_default(),
+        local("[::]:0", "[2001:db8::1]:9").unwrap_or_default()
+    )
+}
+
+/// Whether the home URL accepts a TCP connection from this network
+async fn home_reachable(app: &AppHandle, url: &str) -> bool {
+    let Some((host, port)) = probe_target(url) else {
+        return false;
+    };
+    let resolver = gateway::dns_resolver(app);
+    let attempt = async {
+        let lookup = resolver.resolve(&host, port).await.ok()?;
+        let addr = *lookup.addrs.first()?;
+        tokio::net::TcpStream::connect(addr).await.ok()
+    };
+    matches!(
+        tokio::time::timeout(HOME_PROBE_TIMEOUT, attempt).await,
+        Ok(Some(_))
+    )
+}
+
+/// Endpoint to use on the current network
+async fn pick(app: &AppHandle, settings: &EndpointSettings) -> Endpoint {
+    if home_reachable(app, &settings.home_url).await {
+        Endpoint::Home
+    } else {
+        Endpoint::Away
+    }
+}
+
+/// Endpoint state managed by Tauri
+pub struct EndpointState {
+    path: PathBuf,
+    settings: std::sync::RwLock<EndpointSettings>,
+    active: std::sync::Mutex<Option<ActiveEndpoint>>,
+    /// Serializes re-checks so a burst of network changes switches once
+    switching: tokio::sync::Mutex<()>,
+}
+
+impl EndpointState {
+    /// Load the endpoint settings from the local store
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, ENDPOINTS_FILE);
+        let settings = store::load(&path);
+        Self {
+            path,
+            settings: std::sync::RwLock::new(settings),
+            active: std::sync::Mutex::new(None),
+            switching: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn settings(&self) -> EndpointSettings {
+        self.settings
+            .read()
+            .map(|settings| settings.clone())
+            .unwrap_or_default()
+    }
+
+    /// Validate, save and apply settings
+    pub fn apply(&self, settings: EndpointSettings) -> Result<(), String> {
+        settings.validate()?;
+        store::save(&self.path, &settings)?;
+        if let Ok(mut current) = self.settings.write() {
+            *current = settings;
+        }
+        Ok(())
+    }
+
+    fn active(&self) -> Option<ActiveEndpoint> {
+        self.active.lock().ok().and_then(|active| active.clone())
+    }
+}
+
+/// Endpoint to connect through when asked to connect to `url`
+///
+/// Returns `None` unless `url` is one of the configured endpoints.
+pub async fn select(app: &AppHandle, url: &str) -> Option<ActiveEndpoint> {
+    let settings = app.try_state::<EndpointState>()?.settings();
+    settings.endpoint_of(url)?;
+    let endpoint = pick(app, &settings).await;
+    Some(ActiveEndpoint {
+        endpoint,
+        url: settings.url_of(endpoint).to_string(),
+    })
+}
+
+/// Record the endpoint the current connection goes through, emitting
+/// `endpoints:active` when it changes
+pub fn set_active(app: &AppHandle, active: Option<ActiveEndpoint>) {
+    let Some(state) = app.try_state::<EndpointState>() else {
+        return;
+    };
+    let Ok(mut current) = state.active.lock() else {
+        return;
+    };
+    if *current == active {
+        return;
+    }
+    if let Some(active) = &active {
+        log::info!("[Endpoints] Using the {} endpoint", active.endpoint.label());
+    }
+    *current = active.clone();
+    let _ = app.emit("endpoints:active", active);
+}
+
+/// Move a live connection to the endpoint for the current network
+async fn recheck(app: &AppHandle) {
+    let Some(state) = app.try_state::<EndpointState>() else {
+        return;
+    };
+    let _guard = state.switching.lock().await;
+    let settings = state.settings();
+    let gateway = app.state::<GatewayState>();
+    let Some(current_url) = gateway.connected_url().await else {
+        return;
+    };
+    let Some(current) = settings.endpoint_of(&current_url) else {
+        return;
+    };
+    let target = pick(app, &settings).await;
+    if target == current {
+        return;
+    }
+
+    let url = settings.url_of(target).to_string();
+    log::info!(
+        "[Endpoints] Switching from the {} to the {} endpoint",
+        current.label(),
+        target.label()
+    );
+    match gateway.switch_url(app, url.clone()).await {
+        Ok(_) => set_active(
+            app,
+            Some(ActiveEndpoint {
+                endpoint: target,
+                url,
+            }),
+        ),
+        Err(e) => log::error!(
+            "[Endpoints] Failed to switch to the {} endpoint: {}",
+            target.label(),
+            e
+        ),
+    }
+}
+
+/// Start watching for network changes
+pub fn start_network_monitor(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut last = tokio::task::spawn_blocking(network_fingerprint)
+            .await
+            .unwrap_or_default();
+        loop {
+            tokio::time::sleep(power::throttled_interval(&app, NETWORK_CHECK_INTERVAL)).await;
+
+            let current = tokio::task::spawn_blocking(network_fingerprint)
+                .await
+                .unwrap_or_default();
+            if current == last {
+                continue;
+            }
+            last = current;
+            let enabled = app
+                .try_state::<EndpointState>()
+                .is_some_and(|state| state.settings().enabled);
+            if enabled {
+                log::info!("[Endpoints] Network changed, checking the home endpoint");
+                recheck(&app).await;
+            }
+        }
+    });
+}
+
+/// Current endpoint settings
+#[tauri::command]
+pub async fn get_endpoint_settings(
+    state: State<'_, EndpointState>,
+) -> Result<EndpointSettings, CommandError> {
+    Ok(state.settings())
+}
+
+/// Replace the endpoint settings and move a live connection if needed
+#[tauri::command]
+pub async fn set_endpoint_settings(
+    app: AppHandle,
+    state: State<'_, EndpointState>,
+    settings: EndpointSettings,
+) -> Result<(), CommandError> {
+    settings.validate().map_err(CommandError::validation)?;
+    let enabled = settings.enabled;
+    state.apply(settings)?;
+    if enabled {
+        tauri::async_runtime::spawn(async move { recheck(&app).await });
+    } else {
+        set_active(&app, None);
+    }
+    Ok(())
+}
+
+/// Endpoint the current connection goes through, if it is one of the
+/// configured endpoints
+#[tauri::command]
+pub async fn get_active_endpoint(
+    state: State<'_, EndpointState>,
+) -> Result<Option<ActiveEndpoint>, CommandError> {
+    Ok(state.active())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> EndpointSettings {
+        EndpointSettings {
+            enabled: true,
+            home_url: "ws://192.168.1.20:18789".to_string(),
+            away_url: "wss://gateway.tail1234.ts.net".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(settings().validate().is_ok());
+        assert!(EndpointSettings::default().validate().is_ok());
+
+        let mut same_host = settings();
+        same_host.away_url = "wss://192.168.1.20".to_string();
+        assert!(same_host.validate().is_err());
+
+        let mut not_ws = settings();
+        not_ws.home_url = "http://192.168.1.20".to_string();
+        assert!(not_ws.validate().is_err());
+    }
+
+    #[test]
+    fn test_endpoint_of_matches_host() {
+        let settings = settings();
+        assert_eq!(
+            settings.endpoint_of("wss://192.168.1.20:18789"),
+            Some(Endpoint::Home)
+        );
+        assert_eq!(
+            settings.endpoint_of("ws://Gateway.tail1234.ts.net"),
+            Some(Endpoint::Away)
+        );
+        assert_eq!(settings.endpoint_of("ws://other.local"), None);
+
+        let disabled = EndpointSettings {
+            enabled: false,
+            ..settings
+        };
+        assert_eq!(disabled.endpoint_of("ws://192.168.1.20:18789"), None);
+    }
+
+    #[test]
+    fn test_probe_target() {
+        assert_eq!(
+            probe_target("ws://192.168.1.20:18789"),
+            Some(("192.168.1.20".to_string(), 18789))
+        );
+        assert_eq!(
+            probe_target("wss://gateway.tail1234.ts.net/ws"),
+            Some(("gateway.tail1234.ts.net".to_string(), 443))
+        );
+    }
+}
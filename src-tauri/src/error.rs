@@ -0,0 +1,181 @@
+//! Machine-readable errors shared with the frontend
+//!
+//! Commands return [`CommandError`] instead of a bare string so the frontend
+//! can branch on a stable [`ErrorCode`] (e.g. open settings on `auth`, offer
+//! a retry on `network`) rather than matching message text. Error events
+//! (`gateway:error`, `ConnectionState::Failed`) carry the same code.
+//!
+//! Serialized as `{ "code": "network", "message": "...", "retryable": true }`.
+//! The codes are part of the frontend contract (see `src/lib/errors.ts`):
+//! add new ones, never rename.
+
+use crate::protocol::GatewayError;
+use serde::{Deserialize, Serialize};
+
+/// Stable error category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// Gateway unreachable, not connected, connection lost or timed out
+    Network,
+    /// Credentials missing, rejected or expired
+    Auth,
+    /// Unexpected or malformed data from the Gateway
+    Protocol,
+    /// The Gateway reported an error for a valid request (e.g. a failed run)
+    Gateway,
+    /// Bad input, or a request that can't be served in the current state
+    Validation,
+    /// Reading or writing a user-visible file or folder failed (exports,
+    /// attachments, vault notes, ...)
+    Io,
+    /// Local failure: keychain, app data, serialization, updater, ...
+    Internal,
+}
+
+/// Error returned by commands and carried by error events
+#[derive(Debug, Clone, PartialEq, Serialize, thiserror::Error)]
+#[error("{message}")]
+pub struct CommandError {
+    pub code: ErrorCode,
+    pub message: String,
+    /// Whether trying again later can succeed without user action
+    pub retryable: bool,
+}
+
+impl CommandError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        let retryable = matches!(code, ErrorCode::Network);
+        Self {
+            code,
+            message: message.into(),
+            retryable,
+        }
+    }
+
+    pub fn network(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Network, message)
+    }
+
+    pub fn auth(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Auth, message)
+    }
+
+    pub fn protocol(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Protocol, message)
+    }
+
+    pub fn gateway(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Gateway, message)
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Validation, message)
+    }
+
+    pub fn io(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Io, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+
+    /// "Not connected to Gateway"
+    pub fn not_connected() -> Self {
+        Self::network("Not connected to Gateway")
+    }
+}
+
+impl From<GatewayError> for CommandError {
+    fn from(error: GatewayError) -> Self {
+        let code = if error.requires_reauth() {
+            ErrorCode::Auth
+        } else {
+            match &error {
+                GatewayError::Network { .. }
+                | GatewayError::Timeout { .. }
+                | GatewayError::StreamTimeout { .. }
+                | GatewayError::Closed { .. } => ErrorCode::Network,
+                GatewayError::Protocol { .. } => ErrorCode::Protocol,
+                GatewayError::Gateway { .. } => ErrorCode::Gateway,
+                GatewayError::Auth { .. } => ErrorCode::Auth,
+                GatewayError::Validation { .. } => ErrorCode::Validation,
+            }
+        };
+        Self {
+            code,
+            message: error.user_message(),
+            retryable: error.is_retryable(),
+        }
+    }
+}
+
+/// Free-form errors from local helpers (store, file system, ...) are internal
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        Self::internal(message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        Self::internal(message)
+    }
+}
+
+impl From<keyring::Error> for CommandError {
+    fn from(error: keyring::Error) -> Self {
+        match error {
+            keyring::Error::NoEntry => Self::validation(error.to_string()),
+            _ => Self::internal(error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gateway_errors_map_to_codes() {
+        let network = CommandError::from(GatewayError::Timeout {
+            timeout_secs: 30,
+            request_id: None,
+        });
+        assert_eq!(network.code, ErrorCode::Network);
+        assert!(network.retryable);
+
+        let auth = CommandError::from(GatewayError::from_gateway_response(
+            "TOKEN_EXPIRED".to_string(),
+            "expired".to_string(),
+            None,
+            None,
+        ));
+        assert_eq!(auth.code, ErrorCode::Auth);
+        assert!(!auth.retryable);
+
+        let gateway = CommandError::from(GatewayError::from_gateway_response(
+            "RATE_LIMITED".to_string(),
+            "slow down".to_string(),
+            None,
+            None,
+        ));
+        assert_eq!(gateway.code, ErrorCode::Gateway);
+        assert!(gateway.retryable);
+    }
+
+    #[test]
+    fn test_serialized_shape() {
+        let json = serde_json::to_value(CommandError::not_connected()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "code": "network",
+                "message": "Not connected to Gateway",
+                "retryable": true,
+            })
+        );
+        assert_eq!(serde_json::to_value(ErrorCode::Io).unwrap(), "io");
+    }
+}
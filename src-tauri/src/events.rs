@@ -0,0 +1,150 @@
+//! Pluggable handlers for Gateway events
+//!
+//! Events without built-in handling used to be dumped onto a generic
+//! `gateway:event`. New event types can now be handled in a structured way:
+//! - Rust side: implement [`GatewayEventHandler`] and register it with the
+//!   [`EventRegistry`]
+//! - Frontend side: call `subscribe_gateway_events` and listen on
+//!   `gateway:event:<name>` (dots become underscores, e.g.
+//!   `agent.status` -> `gateway:event:agent_status`)
+//!
+//! Events nobody handles still fall through to `gateway:event`.
+
+use crate::error::CommandError;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use tauri::{AppHandle, Emitter, State};
+
+/// A handler for one or more Gateway event types
+pub trait GatewayEventHandler: Send + Sync {
+    /// Event names handled; a trailing `*` matches a prefix (e.g. `cron.*`)
+    fn events(&self) -> &[&str];
+
+    /// Handle an event
+    fn handle(&self, app: &AppHandle, event: &str, payload: Option<&Value>);
+}
+
+/// Check whether an event name matches a handler/subscription pattern
+fn event_matches(pattern: &str, event: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => event.starts_with(prefix),
+        None => pattern == event,
+    }
+}
+
+/// Frontend event name for a forwarded Gateway event
+pub fn frontend_event_name(event: &str) -> String {
+    let sanitized: String = event
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("gateway:event:{}", sanitized)
+}
+
+/// Registry of Rust handlers and frontend subscriptions, managed by Tauri
+#[derive(Default)]
+pub struct EventRegistry {
+    handlers: RwLock<Vec<Arc<dyn GatewayEventHandler>>>,
+    subscriptions: RwLock<HashSet<String>>,
+}
+
+impl EventRegistry {
+    /// Register a Rust-side handler
+    pub fn register(&self, handler: Arc<dyn GatewayEventHandler>) {
+        if let Ok(mut handlers) = self.handlers.write() {
+            handlers.push(handler);
+        }
+    }
+
+    fn is_subscribed(&self, event: &str) -> bool {
+        self.subscriptions
+            .read()
+            .map(|subs| subs.iter().any(|pattern| event_matches(pattern, event)))
+            .unwrap_or(false)
+    }
+
+    /// Dispatch an event to matching handlers and frontend subscriptions
+    ///
+    /// Returns false when nothing handled the event.
+    pub fn dispatch(&self, app: &AppHandle, event: &str, payload: Option<&Value>) -> bool {
+        let handlers: Vec<Arc<dyn GatewayEventHandler>> = match self.handlers.read() {
+            Ok(handlers) => handlers
+                .iter()
+                .filter(|h| h.events().iter().any(|p| event_matches(p, event)))
+                .cloned()
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        for handler in &handlers {
+            handler.handle(app, event, payload);
+        }
+
+        let subscribed = self.is_subscribed(event);
+        if subscribed {
+            let _ = app.emit(
+                &frontend_event_name(event),
+                serde_json::json!({ "event": event, "payload": payload }),
+            );
+        }
+
+        !handlers.is_empty() || subscribed
+    }
+}
+
+/// Subscribe the frontend to Gateway events (exact names or `prefix.*`)
+#[tauri::command]
+pub async fn subscribe_gateway_events(
+    registry: State<'_, EventRegistry>,
+    events: Vec<String>,
+) -> Result<(), CommandError> {
+    let mut subs = registry
+        .subscriptions
+        .write()
+        .map_err(|e| CommandError::internal(e.to_string()))?;
+    subs.extend(events);
+    Ok(())
+}
+
+/// Remove frontend subscriptions for Gateway events
+#[tauri::command]
+pub async fn unsubscribe_gateway_events(
+    registry: State<'_, EventRegistry>,
+    events: Vec<String>,
+) -> Result<(), CommandError> {
+    let mut subs = registry
+        .subscriptions
+        .write()
+        .map_err(|e| CommandError::internal(e.to_string()))?;
+    for event in &events {
+        subs.remove(event);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_matches() {
+        assert!(event_matches("agent.status", "agent.status"));
+        assert!(!event_matches("agent.status", "agent.statuses"));
+        assert!(event_matches("cron.*", "cron.finished"));
+        assert!(!event_matches("cron.*", "chat"));
+    }
+
+    #[test]
+    fn test_frontend_event_name() {
+        assert_eq!(
+            frontend_event_name("agent.status"),
+            "gateway:event:agent_status"
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Watch attached files for edits
+//!
+//! When watching is enabled for an attachment, edits to the source file emit
+//! `attachment:changed` to the window that attached it, so the user can send
+//! the updated version in one click (e.g. while iterating on a review).
+//!
+//! The parent directory is watched rather than the file itself: most editors
+//! save by writing a temporary file and renaming it over the original, which
+//! would end a watch on the original inode.
+
+use crate::error::CommandError;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Saves within this window are reported once
+const CHANGE_DEBOUNCE_MS: u64 = 500;
+
+/// Payload of `attachment:changed`
+#[derive(Debug, Clone, Serialize)]
+pub struct AttachmentChanged {
+    #[serde(rename = "attachmentId")]
+    pub attachment_id: String,
+    pub path: String,
+    pub filename: String,
+}
+
+/// A watched attachment
+struct WatchedFile {
+    path: PathBuf,
+    /// Label of the window that attached the file
+    window: String,
+    /// Last seen modification time and size, to skip events without content changes
+    stamp: Option<(SystemTime, u64)>,
+    last_emitted: Option<Instant>,
+}
+
+/// File watch state managed by Tauri
+#[derive(Default)]
+pub struct FileWatchState {
+    watcher: Mutex<Option<RecommendedWatcher>>,
+    /// Watched files keyed by attachment ID
+    files: Mutex<HashMap<String, WatchedFile>>,
+}
+
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Attachments whose file changed, updating their stamps
+fn changed_attachments(
+    files: &mut HashMap<String, WatchedFile>,
+    paths: &[PathBuf],
+    now: Instant,
+) -> Vec<AttachmentChanged> {
+    let mut changed = Vec::new();
+    for (id, file) in files.iter_mut() {
+        if !paths.iter().any(|p| p == &file.path) {
+            continue;
+        }
+        let stamp = file_stamp(&file.path);
+        // Deleted (mid-rename) or unchanged
+        if stamp.is_none() || stamp == file.stamp {
+            continue;
+        }
+        file.stamp = stamp;
+
+        let debounced = file
+            .last_emitted
+            .is_some_and(|t| now.duration_since(t) < Duration::from_millis(CHANGE_DEBOUNCE_MS));
+        if debounced {
+            continue;
+        }
+        file.last_emitted = Some(now);
+        changed.push(AttachmentChanged {
+            attachment_id: id.clone(),
+            path: file.path.to_string_lossy().to_string(),
+            filename: file
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        });
+    }
+    changed
+}
+
+/// Handle a raw watcher event
+fn handle_event(app: &AppHandle, event: notify::Event) {
+    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+        return;
+    }
+    let Some(state) = app.try_state::<FileWatchState>() else {
+        return;
+    };
+    let Ok(mut files) = state.files.lock() else {
+        return;
+    };
+    for change in changed_attachments(&mut files, &event.paths, Instant::now()) {
+        if let Some(file) = files.get(&change.attachment_id) {
+            let _ = app.emit_to(file.window.as_str(), "attachment:changed", change);
+        }
+    }
+}
+
+/// Start watching an attachment's source file
+#[tauri::command]
+pub async fn watch_attachment(
+    app: AppHandle,
+    window: tauri::Window,
+    state: State<'_, FileWatchState>,
+    attachment_id: String,
+    path: String,
+) -> Result<(), CommandError> {
+    let path = std::fs::canonicalize(&path)
+        .map_err(|e| CommandError::io(format!("Cannot watch {}: {}", path, e)))?;
+    let dir = path
+        .parent()
+        .ok_or_else(|| CommandError::validation(format!("Cannot watch {}", path.display())))?
+        .to_path_buf();
+
+    let mut watcher = state.watcher.lock().map_err(|e| e.to_string())?;
+    if watcher.is_none() {
+        let handle = app.clone();
+        let created =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+                Ok(event) => handle_event(&handle, event),
+                Err(e) => log::error!("[FileWatch] Watch error: {}", e),
+            })
+            .map_err(|e| format!("Failed to start file watcher: {}", e))?;
+        *watcher = Some(created);
+    }
+
+    let mut files = state.files.lock().map_err(|e| e.to_string())?;
+    let dir_watched = files
+        .values()
+        .any(|f| f.path.parent() == Some(dir.as_path()));
+    if !dir_watched {
+        if let Some(watcher) = watcher.as_mut() {
+            watcher
+                .watch(&dir, RecursiveMode::NonRecursive)
+                .map_err(|e| CommandError::io(format!("Cannot watch {}: {}", path.display(), e)))?;
+        }
+    }
+
+    files.insert(
+        attachment_id,
+        WatchedFile {
+            stamp: file_stamp(&path),
+            path,
+            window: window.label().to_string(),
+            last_emitted: None,
+        },
+    );
+    Ok(())
+}
+
+/// Stop watching an attachment's source file
+#[tauri::command]
+pub async fn unwatch_attachment(
+    state: State<'_, FileWatchState>,
+    attachment_id: String,
+) -> Result<(), CommandError> {
+    let mut watcher = state.watcher.lock().map_err(|e| e.to_string())?;
+    let mut files = state.files.lock().map_err(|e| e.to_string())?;
+
+    let Some(removed) = files.remove(&attachment_id) else {
+        return Ok(());
+    };
+    let Some(dir) = removed.path.parent() else {
+        return Ok(());
+    };
+    let dir_still_used = files.values().any(|f| f.path.parent() == Some(dir));
+    if !dir_still_used {
+        if let Some(watcher) = watcher.as_mut() {
+            let _ = watcher.unwatch(dir);
+        }
+    }
+    if files.is_empty() {
+        // Drop the watcher thread when nothing is watched
+        *watcher = None;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_attachments_skips_unchanged_and_debounces() {
+        let dir = std::env::temp_dir().join(format!("moltz-watch-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("notes.md");
+        std::fs::write(&path, "one").unwrap();
+
+        let mut files = HashMap::new();
+        files.insert(
+            "a1".to_string(),
+            WatchedFile {
+                path: path.clone(),
+                window: "main".to_string(),
+                stamp: file_stamp(&path),
+                last_emitted: None,
+            },
+        );
+        let paths = vec![path.clone()];
+        let now = Instant::now();
+
+        // Event without a content change
+        assert!(changed_attachments(&mut files, &paths, now).is_empty());
+
+        std::fs::write(&path, "two, longer").unwrap();
+        let changed = changed_attachments(&mut files, &paths, now);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].attachment_id, "a1");
+        assert_eq!(changed[0].filename, "notes.md");
+
+        // A second save right away is debounced
+        std::fs::write(&path, "three, even longer").unwrap();
+        assert!(changed_attachments(&mut files, &paths, now).is_empty());
+
+        std::fs::write(&path, "four, the longest one").unwrap();
+        let later = now + Duration::from_millis(CHANGE_DEBOUNCE_MS + 1);
+        assert_eq!(changed_attachments(&mut files, &paths, later).len(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,426 @@
+//! Workspace folder attachments
+//!
+//! Packages the text files of a directory for the next message, either as one
+//! attachment per file or as a single zip archive. Both forms include a
+//! manifest listing what was included and what was skipped (and why).
+//!
+//! Files are selected like `git status` would: .gitignore, .git/info/exclude
+//! and the global gitignore are honored, even outside a git repository.
+//! Binary files are always skipped.
+
+use crate::error::CommandError;
+use base64::Engine;
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Default per-file size limit
+const DEFAULT_MAX_FILE_SIZE: u64 = 256 * 1024;
+
+/// Default size budget for all included files
+const DEFAULT_MAX_TOTAL_SIZE: u64 = 2 * 1024 * 1024;
+
+/// Maximum number of files included
+const MAX_FOLDER_FILES: usize = 500;
+
+/// Bytes inspected when checking whether a file is text
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// Name of the manifest file
+const MANIFEST_NAME: &str = "MANIFEST.md";
+
+/// Options for `attach_folder`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AttachFolderOptions {
+    /// Only include files with these extensions (all text files when empty)
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// Extra gitignore-style patterns to exclude
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Per-file size limit in bytes
+    #[serde(default, rename = "maxFileSize")]
+    pub max_file_size: Option<u64>,
+    /// Size budget for all files in bytes
+    #[serde(default, rename = "maxTotalSize")]
+    pub max_total_size: Option<u64>,
+    /// Include hidden files and directories
+    #[serde(default, rename = "includeHidden")]
+    pub include_hidden: bool,
+    /// Package everything into a single zip archive
+    #[serde(default)]
+    pub archive: bool,
+}
+
+/// A file included from the folder
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestEntry {
+    /// Path relative to the folder, with `/` separators
+    pub path: String,
+    pub size: u64,
+}
+
+/// A file left out, with the reason
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedFile {
+    pub path: String,
+    pub reason: String,
+}
+
+/// What was packaged from the folder
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderManifest {
+    pub root: String,
+    pub files: Vec<ManifestEntry>,
+    pub skipped: Vec<SkippedFile>,
+    #[serde(rename = "totalSize")]
+    pub total_size: u64,
+}
+
+/// An attachment ready to send (same shape as a frontend attachment)
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderFileAttachment {
+    pub id: String,
+    pub filename: String,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+    /// Base64 encoded content
+    pub data: String,
+    pub path: String,
+}
+
+/// Result of `attach_folder`
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderAttachment {
+    pub attachments: Vec<FolderFileAttachment>,
+    pub manifest: FolderManifest,
+}
+
+/// A text file read from the folder
+struct FolderFile {
+    relative: String,
+    absolute: PathBuf,
+    content: String,
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn extension_allowed(path: &Path, extensions: &[String]) -> bool {
+    if extensions.is_empty() {
+        return true;
+    }
+    let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        return false;
+    };
+    extensions
+        .iter()
+        .any(|allowed| allowed.trim_start_matches('.').eq_ignore_ascii_case(ext))
+}
+
+/// Read a file as text; None for binary or non-UTF-8 content
+fn read_text(path: &Path) -> std::io::Result<Option<String>> {
+    let mut bytes = Vec::new();
+    std::fs::File::open(path)?.read_to_end(&mut bytes)?;
+    let sniff = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+    if sniff.contains(&0) {
+        return Ok(None);
+    }
+    Ok(String::from_utf8(bytes).ok())
+}
+
+/// Walk the folder and collect the files to include
+fn collect(
+    root: &Path,
+    options: &AttachFolderOptions,
+) -> Result<(Vec<FolderFile>, FolderManifest), String> {
+    let max_file_size = options.max_file_size.unwrap_or(DEFAULT_MAX_FILE_SIZE);
+    let max_total_size = options.max_total_size.unwrap_or(DEFAULT_MAX_TOTAL_SIZE);
+
+    let mut overrides = OverrideBuilder::new(root);
+    for pattern in &options.exclude {
+        overrides
+            .add(&format!("!{}", pattern))
+            .map_err(|e| format!("Invalid exclude pattern '{}': {}", pattern, e))?;
+    }
+    let overrides = overrides.build().map_err(|e| e.to_string())?;
+
+    let walker = WalkBuilder::new(root)
+        .hidden(!options.include_hidden)
+        .require_git(false)
+        .overrides(overrides)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .sort_by_file_path(|a, b| a.cmp(b))
+        .build();
+
+    let mut files = Vec::new();
+    let mut skipped = Vec::new();
+    let mut total_size = 0u64;
+
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                log::error!("[FolderAttach] Walk error: {}", e);
+                continue;
+            }
+        };
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let path = entry.path();
+        let relative = relative_path(root, path);
+        let mut skip = |reason: &str| {
+            skipped.push(SkippedFile {
+                path: relative.clone(),
+                reason: reason.to_string(),
+            })
+        };
+
+        if !extension_allowed(path, &options.extensions) {
+            continue;
+        }
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        if size > max_file_size {
+            skip("too large");
+            continue;
+        }
+        if files.len() >= MAX_FOLDER_FILES {
+            skip("file limit reached");
+            continue;
+        }
+        if total_size + size > max_total_size {
+            skip("size budget reached");
+            continue;
+        }
+        match read_text(path) {
+            Ok(Some(content)) => {
+                total_size += size;
+                files.push(FolderFile {
+                    relative,
+                    absolute: path.to_path_buf(),
+                    content,
+                });
+            }
+            Ok(None) => skip("binary"),
+            Err(_) => skip("unreadable"),
+        }
+    }
+
+    let manifest = FolderManifest {
+        root: root.to_string_lossy().to_string(),
+        files: files
+            .iter()
+            .map(|f| ManifestEntry {
+                path: f.relative.clone(),
+                size: f.content.len() as u64,
+            })
+            .collect(),
+        skipped,
+        total_size,
+    };
+    Ok((files, manifest))
+}
+
+/// Render the manifest as Markdown
+fn render_manifest(name: &str, manifest: &FolderManifest) -> String {
+    let mut out = format!(
+        "# Folder: {}\n\n{} file(s), {} bytes\n\n## Included\n\n",
+        name,
+        manifest.files.len(),
+        manifest.total_size
+    );
+    for file in &manifest.files {
+        out.push_str(&format!("- {} ({} bytes)\n", file.path, file.size));
+    }
+    if !manifest.skipped.is_empty() {
+        out.push_str("\n## Skipped\n\n");
+        for file in &manifest.skipped {
+            out.push_str(&format!("- {} ({})\n", file.path, file.reason));
+        }
+    }
+    out
+}
+
+/// Package the files and manifest into a zip archive
+fn build_archive(name: &str, manifest: &str, files: &[FolderFile]) -> Result<Vec<u8>, String> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let entries = std::iter::once((MANIFEST_NAME, manifest)).chain(
+        files
+            .iter()
+            .map(|f| (f.relative.as_str(), f.content.as_str())),
+    );
+    for (path, content) in entries {
+        zip.start_file(format!("{}/{}", name, path), options)
+            .map_err(|e| e.to_string())?;
+        zip.write_all(content.as_bytes())
+            .map_err(|e| e.to_string())?;
+    }
+    let cursor = zip.finish().map_err(|e| e.to_string())?;
+    Ok(cursor.into_inner())
+}
+
+fn text_attachment(filename: String, content: &str, path: String) -> FolderFileAttachment {
+    FolderFileAttachment {
+        id: uuid::Uuid::new_v4().to_string(),
+        filename,
+        mime_type: "text/plain".to_string(),
+        data: base64::engine::general_purpose::STANDARD.encode(content),
+        path,
+    }
+}
+
+fn package(root: &Path, options: &AttachFolderOptions) -> Result<FolderAttachment, String> {
+    let (files, manifest) = collect(root, options)?;
+    if files.is_empty() {
+        return Err(format!("No text files to attach in {}", root.display()));
+    }
+
+    let name = root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "folder".to_string());
+    let manifest_text = render_manifest(&name, &manifest);
+
+    let attachments = if options.archive {
+        let archive = build_archive(&name, &manifest_text, &files)?;
+        vec![FolderFileAttachment {
+            id: uuid::Uuid::new_v4().to_string(),
+            filename: format!("{}.zip", name),
+            mime_type: "application/zip".to_string(),
+            data: base64::engine::general_purpose::STANDARD.encode(archive),
+            path: manifest.root.clone(),
+        }]
+    } else {
+        std::iter::once(text_attachment(
+            format!("{}-{}", name, MANIFEST_NAME),
+            &manifest_text,
+            manifest.root.clone(),
+        ))
+        .chain(files.iter().map(|f| {
+            text_attachment(
+                f.relative.clone(),
+                &f.content,
+                f.absolute.to_string_lossy().to_string(),
+            )
+        }))
+        .collect()
+    };
+
+    Ok(FolderAttachment {
+        attachments,
+        manifest,
+    })
+}
+
+/// Package a folder's text files as attachments or a single archive
+#[tauri::command]
+pub async fn attach_folder(
+    path: String,
+    options: Option<AttachFolderOptions>,
+) -> Result<FolderAttachment, CommandError> {
+    let root =
+        std::fs::canonicalize(&path).map_err(|e| CommandError::io(format!("{}: {}", path, e)))?;
+    if !root.is_dir() {
+        return Err(CommandError::validation(format!("Not a folder: {}", path)));
+    }
+    let options = options.unwrap_or_default();
+    tokio::task::spawn_blocking(move || package(&root, &options))
+        .await
+        .map_err(|e| CommandError::internal(e.to_string()))?
+        .map_err(CommandError::io)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_folder() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("moltz-folder-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::create_dir_all(dir.join("target")).unwrap();
+        std::fs::write(dir.join(".gitignore"), "target/\n*.log\n").unwrap();
+        std::fs::write(dir.join("README.md"), "# Demo\n").unwrap();
+        std::fs::write(dir.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(dir.join("src/big.rs"), "x".repeat(2048)).unwrap();
+        std::fs::write(dir.join("debug.log"), "noise").unwrap();
+        std::fs::write(dir.join("target/out.rs"), "built").unwrap();
+        std::fs::write(dir.join("logo.png"), [0x89, b'P', b'N', b'G', 0, 0]).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_collect_honors_gitignore_and_limits() {
+        let dir = temp_folder();
+        let options = AttachFolderOptions {
+            max_file_size: Some(1024),
+            ..Default::default()
+        };
+        let (_, manifest) = collect(&dir, &options).unwrap();
+
+        let included: Vec<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(included, vec!["README.md", "src/main.rs"]);
+
+        let skipped: Vec<(&str, &str)> = manifest
+            .skipped
+            .iter()
+            .map(|f| (f.path.as_str(), f.reason.as_str()))
+            .collect();
+        assert_eq!(
+            skipped,
+            vec![("logo.png", "binary"), ("src/big.rs", "too large")]
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_collect_extension_and_exclude_filters() {
+        let dir = temp_folder();
+        let options = AttachFolderOptions {
+            extensions: vec![".rs".to_string()],
+            exclude: vec!["big.rs".to_string()],
+            ..Default::default()
+        };
+        let (files, _) = collect(&dir, &options).unwrap();
+        let included: Vec<&str> = files.iter().map(|f| f.relative.as_str()).collect();
+        assert_eq!(included, vec!["src/main.rs"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_package_archive_contains_manifest() {
+        let dir = temp_folder();
+        let options = AttachFolderOptions {
+            archive: true,
+            ..Default::default()
+        };
+        let result = package(&dir, &options).unwrap();
+        assert_eq!(result.attachments.len(), 1);
+        assert_eq!(result.attachments[0].mime_type, "application/zip");
+
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&result.attachments[0].data)
+            .unwrap();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+        let name = dir.file_name().unwrap().to_string_lossy().to_string();
+        assert!(archive
+            .by_name(&format!("{}/{}", name, MANIFEST_NAME))
+            .is_ok());
+        assert!(archive.by_name(&format!("{}/src/main.rs", name)).is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,120 @@
+//! Protocol frame tap for the debug console
+//!
+//! While enabled, every text frame sent to or received from the Gateway is
+//! emitted as `debug:frame`. Frames are redacted (see [`crate::redact`]) and
+//! truncated before they leave the backend, so the inspector
+//! never holds tokens or megabyte-sized attachments.
+
+use crate::error::CommandError;
+use crate::redact;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Longest frame text emitted, in bytes
+const MAX_FRAME_CHARS: usize = 4096;
+
+/// Direction of a tapped frame
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FrameDirection {
+    Inbound,
+    Outbound,
+}
+
+/// Payload of `debug:frame`
+#[derive(Debug, Clone, Serialize)]
+pub struct TappedFrame {
+    pub direction: FrameDirection,
+    /// Milliseconds since the Unix epoch
+    pub at: i64,
+    /// Size of the original frame in bytes
+    pub size: usize,
+    /// Redacted, possibly truncated frame text
+    pub frame: String,
+    pub truncated: bool,
+}
+
+/// Frame tap state managed by Tauri
+#[derive(Default)]
+pub struct FrameTapState {
+    enabled: AtomicBool,
+}
+
+/// Redact and truncate a frame for display
+fn prepare_frame(text: &str) -> (String, bool) {
+    let redacted = match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(mut value) => {
+            redact::redact_json(&mut value);
+            value.to_string()
+        }
+        // Never emit unparseable frames verbatim: they could hold anything
+        Err(_) => format!("<{} bytes, not JSON>", text.len()),
+    };
+    if redacted.len() <= MAX_FRAME_CHARS {
+        return (redacted, false);
+    }
+    let mut cut = MAX_FRAME_CHARS;
+    while !redacted.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    (redacted[..cut].to_string(), true)
+}
+
+/// Emit a frame on `debug:frame` if the tap is enabled
+pub fn tap(app: &AppHandle, direction: FrameDirection, text: &str) {
+    let enabled = app
+        .try_state::<FrameTapState>()
+        .is_some_and(|state| state.enabled.load(Ordering::Relaxed));
+    if !enabled {
+        return;
+    }
+    let (frame, truncated) = prepare_frame(text);
+    let _ = app.emit(
+        "debug:frame",
+        TappedFrame {
+            direction,
+            at: chrono::Utc::now().timestamp_millis(),
+            size: text.len(),
+            frame,
+            truncated,
+        },
+    );
+}
+
+/// Turn the frame tap on or off
+#[tauri::command]
+pub async fn enable_frame_tap(
+    state: State<'_, FrameTapState>,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    state.enabled.store(enabled, Ordering::Relaxed);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redact::REDACTED;
+
+    #[test]
+    fn test_prepare_frame_redacts_nested_credentials() {
+        let frame = r#"{"type":"req","method":"connect","params":{"auth":{"token":"sk-secret"},"client":{"id":"moltz"}}}"#;
+        let (prepared, truncated) = prepare_frame(frame);
+        assert!(!truncated);
+        assert!(!prepared.contains("sk-secret"));
+        assert!(prepared.contains(REDACTED));
+        assert!(prepared.contains("moltz"));
+
+        let (prepared, _) = prepare_frame("Bearer sk-secret");
+        assert!(!prepared.contains("sk-secret"));
+    }
+
+    #[test]
+    fn test_prepare_frame_truncates_on_char_boundary() {
+        let frame = serde_json::json!({ "text": "é".repeat(MAX_FRAME_CHARS) }).to_string();
+        let (prepared, truncated) = prepare_frame(&frame);
+        assert!(truncated);
+        assert!(prepared.len() <= MAX_FRAME_CHARS);
+    }
+}
@@ -9,22 +9,50 @@
 
 #![allow(dead_code)]
 
+use crate::accessibility::{self, Priority};
+use crate::chaos;
+use crate::connection_trace::{ConnectionTrace, ConnectionTracer, TraceStep};
+use crate::data_saver::{self, DataSaverState};
+use crate::dns::{DnsState, Resolver};
+use crate::endpoints;
+use crate::error::CommandError;
+use crate::events::EventRegistry;
+use crate::frame_tap::{self, FrameDirection};
+use crate::incident;
+use crate::journal;
+use crate::keychain;
+use crate::message_store;
+use crate::notifications::{self, RunOutcome, RunProgress};
+use crate::offline::OfflineState;
+use crate::power;
 use crate::protocol::{
-    calculate_backoff, validate_frame, ConnectionQuality, ConnectionState, GatewayError,
-    HealthMetrics, QueuedMessage, RawGatewayError, ValidatedFrame, BACKOFF_INITIAL_MS,
-    DEFAULT_PING_INTERVAL_SECS, DEFAULT_PING_TIMEOUT_SECS, DEFAULT_REQUEST_TIMEOUT_SECS,
-    DEFAULT_STREAM_TIMEOUT_SECS, MAX_RECONNECT_ATTEMPTS, PROTOCOL_VERSION,
+    calculate_backoff, check_gateway_compat, request_retry_delay, retry_policy, stream_timeout,
+    thinking_enabled, validate_frame, ConnectionQuality, ConnectionState, GatewayError,
+    HealthMetrics, HealthSnapshot, QueuedMessage, RawGatewayError, SendOutcome, SendStatus,
+    ValidatedFrame, BACKOFF_INITIAL_MS, DEFAULT_PING_INTERVAL_SECS, DEFAULT_PING_TIMEOUT_SECS,
+    DEFAULT_REQUEST_TIMEOUT_SECS, DEFAULT_STREAM_TIMEOUT_SECS, MAX_RECONNECT_ATTEMPTS,
+    PROTOCOL_VERSION,
 };
+use crate::quota;
+use crate::redact::{self, Redacted};
+use crate::replay;
+use crate::scheduler;
+use crate::sessions::{ResolvedDefaults, SessionDefaults, SessionState};
+use crate::startup;
+use crate::supervisor::{self, Heartbeat};
+use crate::token_expiry;
+use crate::tray;
+use chrono::{DateTime, Utc};
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Emitter, State};
-use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::{mpsc, oneshot, Mutex, Notify, RwLock};
 use tokio_tungstenite::{
-    connect_async_tls_with_config, tungstenite::Message as WsMessage, Connector,
+    tungstenite::Error as WsError, tungstenite::Message as WsMessage, Connector,
 };
 
 // ============================================================================
@@ -42,23 +70,42 @@ struct GatewayStateInner {
     pending_requests: Arc<Mutex<HashMap<String, PendingRequest>>>,
     /// Stored credentials for reconnection
     stored_credentials: Mutex<Option<StoredCredentials>>,
+    /// Profile whose keychain entry holds the connection's token ("" for
+    /// the default one)
+    token_profile: Mutex<String>,
     /// Message queue for retry during reconnection
     message_queue: Mutex<VecDeque<QueuedMessage>>,
     /// Set of processed message IDs for deduplication
     processed_ids: Mutex<HashSet<String>>,
     /// Health metrics for connection quality
-    health_metrics: Mutex<HealthMetrics>,
+    /// Shared with the message handler and health monitor
+    health_metrics: Arc<Mutex<HealthMetrics>>,
     /// Flag to signal shutdown
     shutdown: AtomicBool,
     /// Current reconnection attempt number
     reconnect_attempt: AtomicU32,
-    /// Active run IDs for streaming timeout tracking
-    active_runs: Mutex<HashMap<String, Instant>>,
+    /// Reconnection loops in a row that gave up, for incident reports
+    failed_reconnect_cycles: AtomicU32,
+    /// Cuts the reconnection backoff short (`reconnect_now`, `disconnect`)
+    reconnect_wake: Notify,
+    /// Run registry: active runs keyed by run ID, shared with the message handler
+    active_runs: Arc<Mutex<HashMap<String, ActiveRun>>>,
+    /// Label of the window that last sent on each session, keyed by session key
+    /// (used to scope run events to the window that initiated them)
+    session_windows: Mutex<HashMap<String, String>>,
+    /// Stream timeout for the runs of each session, set by the last send on it
+    session_stream_timeouts: Mutex<HashMap<String, Duration>>,
+    /// IDs of the models the Gateway reports as reasoning models
+    reasoning_models: Mutex<HashSet<String>>,
+    /// Models from the last `models.list`, for the command palette
+    known_models: Mutex<Vec<ModelInfo>>,
     /// CRITICAL: Connection mutex to prevent race conditions
     /// Only one connection attempt can be in progress at a time
     connection_mutex: Mutex<()>,
     /// Unique ID for current connection session (to detect stale handlers)
     connection_session_id: Mutex<u64>,
+    /// Timeline of the most recent connection attempt
+    connection_trace: std::sync::Mutex<Option<ConnectionTracer>>,
 }
 
 impl Default for GatewayStateInner {
@@ -68,14 +115,22 @@ impl Default for GatewayStateInner {
             sender: Mutex::new(None),
             pending_requests: Arc::new(Mutex::new(HashMap::new())),
             stored_credentials: Mutex::new(None),
+            token_profile: Mutex::new(String::new()),
             message_queue: Mutex::new(VecDeque::new()),
             processed_ids: Mutex::new(HashSet::new()),
-            health_metrics: Mutex::new(HealthMetrics::default()),
+            health_metrics: Arc::new(Mutex::new(HealthMetrics::default())),
             shutdown: AtomicBool::new(false),
             reconnect_attempt: AtomicU32::new(0),
-            active_runs: Mutex::new(HashMap::new()),
+            failed_reconnect_cycles: AtomicU32::new(0),
+            reconnect_wake: Notify::new(),
+            active_runs: Arc::new(Mutex::new(HashMap::new())),
+            session_windows: Mutex::new(HashMap::new()),
+            session_stream_timeouts: Mutex::new(HashMap::new()),
+            reasoning_models: Mutex::new(HashSet::new()),
+            known_models: Mutex::new(Vec::new()),
             connection_mutex: Mutex::new(()),
             connection_session_id: Mutex::new(0),
+            connection_trace: std::sync::Mutex::new(None),
         }
     }
 }
@@ -107,6 +162,115 @@ struct PendingRequest {
     timeout: Duration,
 }
 
+/// An in-progress run tracked in the run registry
+#[derive(Debug, Clone)]
+struct ActiveRun {
+    /// Session the run belongs to (needed to abort it)
+    session_key: Option<String>,
+    /// When the first chat event for this run was received
+    started_at: Instant,
+    /// Last time a chat event was received for this run
+    last_activity: Instant,
+    /// How long the run may go without a chat event
+    stream_timeout: Duration,
+    /// Label of the window that initiated the run (None = broadcast)
+    window: Option<String>,
+    /// Streamed text not yet emitted (data saver batching)
+    pending_stream: String,
+    /// All text streamed so far, assembled into the final message
+    text: String,
+    /// Last time streamed text was emitted
+    last_stream_emit: Instant,
+}
+
+impl ActiveRun {
+    fn new(session_key: Option<String>, window: Option<String>, stream_timeout: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            session_key,
+            started_at: now,
+            last_activity: now,
+            stream_timeout,
+            window,
+            pending_stream: String::new(),
+            text: String::new(),
+            last_stream_emit: now,
+        }
+    }
+}
+
+/// Buffer a streamed delta while data saver is on
+///
+/// Returns the text to emit now: the delta itself when not batching, the
+/// buffered text once the batch interval has passed, or None to keep buffering.
+async fn batch_stream_delta(
+    app: &AppHandle,
+    active_runs: &Arc<Mutex<HashMap<String, ActiveRun>>>,
+    run_id: Option<&str>,
+    content: String,
+) -> Option<String> {
+    let Some(run_id) = run_id.filter(|_| data_saver::is_enabled(app)) else {
+        return Some(content);
+    };
+    let mut runs = active_runs.lock().await;
+    let Some(run) = runs.get_mut(run_id) else {
+        return Some(content);
+    };
+    run.pending_stream.push_str(&content);
+    if run.last_stream_emit.elapsed() < Duration::from_millis(data_saver::SAVER_STREAM_BATCH_MS) {
+        return None;
+    }
+    run.last_stream_emit = Instant::now();
+    Some(std::mem::take(&mut run.pending_stream))
+}
+
+/// Emit streamed text still buffered for a finished run
+fn flush_pending_stream(app: &AppHandle, window: Option<&str>, run: Option<ActiveRun>) {
+    if let Some(run) = run.filter(|run| !run.pending_stream.is_empty()) {
+        emit_run_event(
+            app,
+            window,
+            run.session_key.as_deref(),
+            "gateway:stream",
+            run.pending_stream,
+        );
+    }
+}
+
+/// Emit a run event to the window that initiated the run, or to every window
+/// if the origin is unknown, and buffer it for replay
+fn emit_run_event<S: Serialize + Clone>(
+    app: &AppHandle,
+    window: Option<&str>,
+    session_key: Option<&str>,
+    event: &str,
+    payload: S,
+) {
+    replay::record(app, session_key, event, &payload);
+    let _ = match window {
+        Some(label) => app.emit_to(label, event, payload),
+        None => app.emit(event, payload),
+    };
+}
+
+/// Look up the window that last sent on a session
+async fn session_window(app: &AppHandle, session_key: Option<&str>) -> Option<String> {
+    let session_key = session_key?;
+    let state = app.try_state::<GatewayState>()?;
+    let windows = state.inner.session_windows.lock().await;
+    windows.get(session_key).cloned()
+}
+
+/// Look up the stream timeout set by the last send on a session
+async fn session_stream_timeout(app: &AppHandle, session_key: Option<&str>) -> Duration {
+    let default = Duration::from_secs(DEFAULT_STREAM_TIMEOUT_SECS);
+    let (Some(session_key), Some(state)) = (session_key, app.try_state::<GatewayState>()) else {
+        return default;
+    };
+    let timeouts = state.inner.session_stream_timeouts.lock().await;
+    timeouts.get(session_key).copied().unwrap_or(default)
+}
+
 /// Outgoing message types
 enum OutgoingMessage {
     Raw(String),
@@ -158,6 +322,13 @@ pub struct ChatParams {
     pub thinking: Option<String>,
     #[serde(default)]
     pub attachments: Vec<AttachmentData>,
+    /// Seconds a run may go without a chat event before it is timed out
+    /// (defaults by whether the model reasons)
+    #[serde(default)]
+    pub stream_timeout_secs: Option<u64>,
+    /// Reuse the idempotency key of an earlier attempt (interrupted sends)
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
 }
 
 /// Stream chunk from Gateway (chat event)
@@ -307,7 +478,7 @@ struct ClientInfo {
 
 #[derive(Debug, Serialize)]
 struct AuthInfo {
-    token: String,
+    token: Redacted<String>,
 }
 
 /// Model info returned from Gateway
@@ -323,6 +494,18 @@ pub struct ModelInfo {
     pub reasoning: Option<bool>,
 }
 
+/// Agent (persona) info from Gateway
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AgentInfo {
+    pub id: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// Model the agent runs on, if the Gateway reports it
+    pub model: Option<String>,
+    #[serde(rename = "isDefault", default)]
+    pub is_default: bool,
+}
+
 // ============================================================================
 // Connection Helpers
 // ============================================================================
@@ -437,6 +620,8 @@ async fn resolve_with_system_dns(host: &str, port: u16) -> Result<std::net::Sock
 /// This bypasses tokio-tungstenite's connection logic which has issues on macOS with Tailscale
 async fn connect_with_manual_tcp(
     url_str: &str,
+    tracer: &ConnectionTracer,
+    resolver: &Resolver,
 ) -> Result<
     tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
     GatewayError,
@@ -464,29 +649,40 @@ async fn connect_with_manual_tcp(
         });
     let use_tls = url_str.starts_with("wss://");
 
-    // Step 1: Create IPv4-only TCP connection using socket2 (blocking operation)
+    // Step 1: Resolve hostname - IPv4 only
     log_protocol_error("Manual TCP", &format!("Resolving {} (IPv4 only)...", host));
 
-    let host_clone = host.to_string();
-    let tcp_stream = tokio::task::spawn_blocking(move || {
-        use socket2::{Domain, Protocol, Socket, Type};
-        use std::net::{SocketAddr, ToSocketAddrs};
-
-        // Resolve hostname - IPv4 only
-        let addrs: Vec<SocketAddr> = format!("{}:{}", host_clone, port)
-            .to_socket_addrs()
-            .map_err(|e| format!("DNS resolution failed: {}", e))?
-            .filter(|a| a.is_ipv4())
-            .collect();
+    let dns_started = Instant::now();
+    let dns_error = |message: String| GatewayError::Network {
+        message,
+        retryable: true,
+        retry_after: Some(Duration::from_millis(BACKOFF_INITIAL_MS)),
+    };
+    let lookup = resolver.resolve(host, port).await.map_err(|e| {
+        tracer.failed(TraceStep::Dns, dns_started, &e);
+        dns_error(format!("DNS resolution failed: {}", e))
+    })?;
+    let addrs: Vec<std::net::SocketAddr> =
+        lookup.addrs.into_iter().filter(|a| a.is_ipv4()).collect();
+    if addrs.is_empty() {
+        tracer.failed(TraceStep::Dns, dns_started, "No IPv4 addresses found");
+        return Err(dns_error("No IPv4 addresses found".to_string()));
+    }
+    tracer.ok(
+        TraceStep::Dns,
+        dns_started,
+        Some(format!("{:?} via {}", addrs, lookup.via)),
+    );
 
-        if addrs.is_empty() {
-            return Err("No IPv4 addresses found".to_string());
-        }
+    log_protocol_error(
+        "Manual TCP",
+        &format!("Resolved to {} IPv4 addr(s): {:?}", addrs.len(), addrs),
+    );
 
-        log_protocol_error(
-            "Manual TCP",
-            &format!("Resolved to {} IPv4 addr(s): {:?}", addrs.len(), addrs),
-        );
+    // Step 2: Create IPv4-only TCP connection using socket2 (blocking operation)
+    let tracer_clone = tracer.clone();
+    let tcp_stream = tokio::task::spawn_blocking(move || {
+        use socket2::{Domain, Protocol, Socket, Type};
 
         // Create IPv4-only socket
         let socket = Socket::new(Domain::IPV4, Type::STREAM, Some(Protocol::TCP))
@@ -501,9 +697,14 @@ async fn connect_with_manual_tcp(
         let addr = addrs[0];
         log_protocol_error("Manual TCP", &format!("Connecting to {} (IPv4)...", addr));
 
+        let tcp_started = Instant::now();
         socket
             .connect_timeout(&addr.into(), Duration::from_secs(10))
-            .map_err(|e| format!("TCP connect failed: {}", e))?;
+            .map_err(|e| {
+                tracer_clone.failed(TraceStep::Tcp, tcp_started, &e);
+                format!("TCP connect failed: {}", e)
+            })?;
+        tracer_clone.ok(TraceStep::Tcp, tcp_started, Some(addr.to_string()));
 
         log_protocol_error("Manual TCP", "TCP connection established");
 
@@ -527,7 +728,7 @@ async fn connect_with_manual_tcp(
         retry_after: Some(Duration::from_millis(BACKOFF_INITIAL_MS)),
     })?;
 
-    // Step 2: Convert to tokio TcpStream
+    // Step 3: Convert to tokio TcpStream
     let tokio_stream =
         tokio::net::TcpStream::from_std(tcp_stream).map_err(|e| GatewayError::Network {
             message: format!("Failed to convert to tokio stream: {}", e),
@@ -537,7 +738,7 @@ async fn connect_with_manual_tcp(
 
     log_protocol_error("Manual TCP", "Converted to tokio stream");
 
-    // Step 3: Upgrade to WebSocket
+    // Step 4: Upgrade to WebSocket
     if use_tls {
         log_protocol_error("Manual TCP", "Performing TLS handshake...");
 
@@ -552,15 +753,16 @@ async fn connect_with_manual_tcp(
                 })?;
 
         let connector = tokio_native_tls::TlsConnector::from(tls_connector);
-        let tls_stream =
-            connector
-                .connect(host, tokio_stream)
-                .await
-                .map_err(|e| GatewayError::Network {
-                    message: format!("TLS handshake failed: {}", e),
-                    retryable: true,
-                    retry_after: Some(Duration::from_millis(BACKOFF_INITIAL_MS)),
-                })?;
+        let tls_started = Instant::now();
+        let tls_stream = connector.connect(host, tokio_stream).await.map_err(|e| {
+            tracer.failed(TraceStep::Tls, tls_started, &e);
+            GatewayError::Network {
+                message: format!("TLS handshake failed: {}", e),
+                retryable: true,
+                retry_after: Some(Duration::from_millis(BACKOFF_INITIAL_MS)),
+            }
+        })?;
+        tracer.ok(TraceStep::Tls, tls_started, None);
 
         log_protocol_error(
             "Manual TCP",
@@ -569,14 +771,19 @@ async fn connect_with_manual_tcp(
 
         // Wrap TLS stream in MaybeTlsStream and upgrade to WebSocket
         let maybe_tls_stream = tokio_tungstenite::MaybeTlsStream::NativeTls(tls_stream);
+        let upgrade_started = Instant::now();
         let ws_stream = tokio_tungstenite::client_async(url_str, maybe_tls_stream)
             .await
-            .map_err(|e| GatewayError::Network {
-                message: format!("WebSocket upgrade failed: {}", e),
-                retryable: true,
-                retry_after: Some(Duration::from_millis(BACKOFF_INITIAL_MS)),
+            .map_err(|e| {
+                tracer.failed(TraceStep::WsUpgrade, upgrade_started, &e);
+                GatewayError::Network {
+                    message: format!("WebSocket upgrade failed: {}", e),
+                    retryable: true,
+                    retry_after: Some(Duration::from_millis(BACKOFF_INITIAL_MS)),
+                }
             })?
             .0;
+        tracer.ok(TraceStep::WsUpgrade, upgrade_started, None);
 
         log_protocol_error("Manual TCP", "WebSocket connection established (TLS)");
         Ok(ws_stream)
@@ -585,20 +792,86 @@ async fn connect_with_manual_tcp(
 
         // Wrap plain TCP stream in MaybeTlsStream and upgrade to WebSocket
         let maybe_tls_stream = tokio_tungstenite::MaybeTlsStream::Plain(tokio_stream);
+        let upgrade_started = Instant::now();
         let ws_stream = tokio_tungstenite::client_async(url_str, maybe_tls_stream)
             .await
-            .map_err(|e| GatewayError::Network {
-                message: format!("WebSocket upgrade failed: {}", e),
-                retryable: true,
-                retry_after: Some(Duration::from_millis(BACKOFF_INITIAL_MS)),
+            .map_err(|e| {
+                tracer.failed(TraceStep::WsUpgrade, upgrade_started, &e);
+                GatewayError::Network {
+                    message: format!("WebSocket upgrade failed: {}", e),
+                    retryable: true,
+                    retry_after: Some(Duration::from_millis(BACKOFF_INITIAL_MS)),
+                }
             })?
             .0;
+        tracer.ok(TraceStep::WsUpgrade, upgrade_started, None);
 
         log_protocol_error("Manual TCP", "WebSocket connection established (plain)");
         Ok(ws_stream)
     }
 }
 
+/// Connect with tokio-tungstenite, resolving and opening the TCP connection
+/// first so each step shows up in the connection trace
+async fn connect_standard(
+    url_str: &str,
+    connector: Connector,
+    tracer: &ConnectionTracer,
+    resolver: &Resolver,
+) -> Result<
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
+    String,
+> {
+    let parsed_url = url::Url::parse(url_str).map_err(|e| format!("Invalid URL: {}", e))?;
+    let host = parsed_url.host_str().ok_or("URL missing host")?;
+    let port = parsed_url.port_or_known_default().unwrap_or(80);
+
+    let dns_started = Instant::now();
+    let lookup = match resolver.resolve(host, port).await {
+        Ok(lookup) => lookup,
+        Err(e) => {
+            tracer.failed(TraceStep::Dns, dns_started, &e);
+            return Err(format!("DNS resolution failed: {}", e));
+        }
+    };
+    let addrs = lookup.addrs;
+    tracer.ok(
+        TraceStep::Dns,
+        dns_started,
+        Some(format!("{:?} via {}", addrs, lookup.via)),
+    );
+
+    let tcp_started = Instant::now();
+    let stream = match tokio::net::TcpStream::connect(&addrs[..]).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            tracer.failed(TraceStep::Tcp, tcp_started, &e);
+            return Err(format!("TCP connect failed: {}", e));
+        }
+    };
+    tracer.ok(
+        TraceStep::Tcp,
+        tcp_started,
+        stream.peer_addr().ok().map(|addr| addr.to_string()),
+    );
+
+    // TLS (for wss://) and the upgrade happen in one call here
+    let upgrade_started = Instant::now();
+    match tokio_tungstenite::client_async_tls_with_config(url_str, stream, None, Some(connector))
+        .await
+    {
+        Ok((ws_stream, _)) => {
+            let detail = (parsed_url.scheme() == "wss").then(|| "TLS + upgrade".to_string());
+            tracer.ok(TraceStep::WsUpgrade, upgrade_started, detail);
+            Ok(ws_stream)
+        }
+        Err(e) => {
+            tracer.failed(TraceStep::WsUpgrade, upgrade_started, &e);
+            Err(e.to_string())
+        }
+    }
+}
+
 /// Determine if protocol upgrade (ws:// → wss://) is safe
 /// SECURITY: Never allow downgrade from wss:// to ws:// (MITM risk)
 fn get_safe_alternate_url(url: &str) -> Option<String> {
@@ -622,6 +895,8 @@ fn get_safe_alternate_url(url: &str) -> Option<String> {
 /// SECURITY: This function will NEVER downgrade from wss:// to ws:// to prevent MITM attacks
 async fn try_connect_with_fallback(
     url: &str,
+    tracer: &ConnectionTracer,
+    resolver: &Resolver,
 ) -> Result<
     (
         tokio_tungstenite::WebSocketStream<
@@ -647,8 +922,11 @@ async fn try_connect_with_fallback(
         );
 
         // Try manual TCP connection with the original URL
-        let first_attempt =
-            tokio::time::timeout(timeout_duration, connect_with_manual_tcp(url)).await;
+        let first_attempt = tokio::time::timeout(
+            timeout_duration,
+            connect_with_manual_tcp(url, tracer, resolver),
+        )
+        .await;
 
         match first_attempt {
             Ok(Ok(stream)) => {
@@ -667,7 +945,7 @@ async fn try_connect_with_fallback(
 
                     let second_attempt = tokio::time::timeout(
                         timeout_duration,
-                        connect_with_manual_tcp(&alternate_url),
+                        connect_with_manual_tcp(&alternate_url, tracer, resolver),
                     )
                     .await;
 
@@ -711,7 +989,7 @@ async fn try_connect_with_fallback(
                 if let Some(alternate_url) = get_safe_alternate_url(url) {
                     let second_attempt = tokio::time::timeout(
                         timeout_duration,
-                        connect_with_manual_tcp(&alternate_url),
+                        connect_with_manual_tcp(&alternate_url, tracer, resolver),
                     )
                     .await;
 
@@ -748,32 +1026,27 @@ async fn try_connect_with_fallback(
         // First, try the URL as provided
         let first_attempt = tokio::time::timeout(
             timeout_duration,
-            connect_async_tls_with_config(url, None, false, Some(connector.clone())),
+            connect_standard(url, connector.clone(), tracer, resolver),
         )
         .await;
 
         match first_attempt {
-            Ok(Ok((stream, _))) => Ok((stream, url.to_string(), false)),
+            Ok(Ok(stream)) => Ok((stream, url.to_string(), false)),
             Ok(Err(first_err)) => {
-                log_protocol_error("Primary connection failed", &first_err.to_string());
+                log_protocol_error("Primary connection failed", &first_err);
 
                 // SECURITY: Only try upgrade (ws:// → wss://), never downgrade
                 if let Some(alternate_url) = get_safe_alternate_url(url) {
                     let second_attempt = tokio::time::timeout(
                         timeout_duration,
-                        connect_async_tls_with_config(
-                            &alternate_url,
-                            None,
-                            false,
-                            Some(connector.clone()),
-                        ),
+                        connect_standard(&alternate_url, connector.clone(), tracer, resolver),
                     )
                     .await;
 
                     match second_attempt {
-                        Ok(Ok((stream, _))) => Ok((stream, alternate_url, true)),
+                        Ok(Ok(stream)) => Ok((stream, alternate_url, true)),
                         Ok(Err(e)) => {
-                            log_protocol_error("Upgrade connection failed", &e.to_string());
+                            log_protocol_error("Upgrade connection failed", &e);
                             Err(GatewayError::Network {
                                 message: format!("Unable to connect to Gateway at {}", url),
                                 retryable: true,
@@ -800,12 +1073,12 @@ async fn try_connect_with_fallback(
                 if let Some(alternate_url) = get_safe_alternate_url(url) {
                     let second_attempt = tokio::time::timeout(
                         timeout_duration,
-                        connect_async_tls_with_config(&alternate_url, None, false, Some(connector)),
+                        connect_standard(&alternate_url, connector, tracer, resolver),
                     )
                     .await;
 
                     match second_attempt {
-                        Ok(Ok((stream, _))) => Ok((stream, alternate_url, true)),
+                        Ok(Ok(stream)) => Ok((stream, alternate_url, true)),
                         _ => Err(GatewayError::Timeout {
                             timeout_secs: timeout_duration.as_secs() * 2,
                             request_id: None,
@@ -824,7 +1097,7 @@ async fn try_connect_with_fallback(
 
 /// Log protocol errors for debugging
 fn log_protocol_error(context: &str, error: &str) {
-    eprintln!("[Gateway Protocol Error] {}: {}", context, error);
+    log::error!("[Gateway Protocol Error] {}: {}", context, error);
 }
 
 // ============================================================================
@@ -872,18 +1145,410 @@ fn build_input_items(message: &str, attachments: &[AttachmentData]) -> serde_jso
     serde_json::Value::Array(items)
 }
 
+// ============================================================================
+// Request/Response
+// ============================================================================
+
+impl GatewayState {
+    /// Send a request to the Gateway and wait for its response payload
+    pub(crate) async fn request(
+        &self,
+        method: &str,
+        params: Option<serde_json::Value>,
+    ) -> Result<Option<serde_json::Value>, GatewayError> {
+        send_request(
+            &self.inner,
+            method,
+            params,
+            Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
+        )
+        .await
+    }
+}
+
+/// Send a request, retrying retryable failures as allowed by the method's retry policy
+async fn send_request(
+    state: &GatewayStateInner,
+    method: &str,
+    params: Option<serde_json::Value>,
+    timeout: Duration,
+) -> Result<Option<serde_json::Value>, GatewayError> {
+    let has_idempotency_key = params
+        .as_ref()
+        .and_then(|p| p.get("idempotencyKey"))
+        .is_some();
+    let max_retries = retry_policy(method).max_retries(has_idempotency_key);
+
+    let mut attempt = 0;
+    loop {
+        match send_request_once(state, method, params.clone(), timeout).await {
+            Err(e) if e.is_retryable() && attempt < max_retries => {
+                attempt += 1;
+                log_protocol_error(
+                    "Request retry",
+                    &format!("{} attempt {}/{}: {}", method, attempt, max_retries, e),
+                );
+                tokio::time::sleep(request_retry_delay(attempt)).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Send a request over the active connection and correlate its response
+async fn send_request_once(
+    state: &GatewayStateInner,
+    method: &str,
+    params: Option<serde_json::Value>,
+    timeout: Duration,
+) -> Result<Option<serde_json::Value>, GatewayError> {
+    let request = GatewayRequest::new(method, params);
+    let request_id = request.id.clone();
+    let json = serde_json::to_string(&request).map_err(|e| GatewayError::Validation {
+        message: e.to_string(),
+        field: None,
+    })?;
+
+    let (response_tx, response_rx) = oneshot::channel();
+    state.pending_requests.lock().await.insert(
+        request_id.clone(),
+        PendingRequest {
+            sender: response_tx,
+            created_at: Instant::now(),
+            timeout,
+        },
+    );
+
+    let send_result = match state.sender.lock().await.as_ref() {
+        Some(sender) => sender
+            .send(OutgoingMessage::Raw(json))
+            .await
+            .map_err(|e| (e.to_string(), true)),
+        // Retrying immediately cannot help without a connection
+        None => Err(("Not connected to Gateway".to_string(), false)),
+    };
+    if let Err((message, retryable)) = send_result {
+        state.pending_requests.lock().await.remove(&request_id);
+        return Err(GatewayError::Network {
+            message,
+            retryable,
+            retry_after: None,
+        });
+    }
+
+    match tokio::time::timeout(timeout, response_rx).await {
+        Ok(Ok(response)) => {
+            if response.ok == Some(true) {
+                Ok(response.payload)
+            } else {
+                let error = response.error.unwrap_or(RawGatewayError {
+                    code: "UNKNOWN".to_string(),
+                    message: format!("{} failed", method),
+                    details: None,
+                    retryable: None,
+                });
+                Err(GatewayError::from_gateway_response(
+                    error.code,
+                    error.message,
+                    error.details,
+                    error.retryable,
+                ))
+            }
+        }
+        Ok(Err(_)) => {
+            state.pending_requests.lock().await.remove(&request_id);
+            Err(GatewayError::Closed {
+                reason: "Connection closed before response".to_string(),
+                code: None,
+                retryable: true,
+            })
+        }
+        Err(_) => {
+            state.pending_requests.lock().await.remove(&request_id);
+            Err(GatewayError::Timeout {
+                timeout_secs: timeout.as_secs(),
+                request_id: Some(request_id),
+            })
+        }
+    }
+}
+
+/// Maximum time to wait for chat.abort acknowledgements during shutdown
+const ABORT_ON_EXIT_TIMEOUT_SECS: u64 = 2;
+
+impl GatewayState {
+    /// Abort every run in the registry so it stops consuming tokens server-side
+    ///
+    /// Called on app exit, before the connection is torn down.
+    pub async fn abort_all_runs(&self) {
+        self.abort_runs(|_| true).await;
+    }
+
+    /// Abort the runs started from a window, and those not tied to any
+    /// window, when it closes
+    pub async fn abort_window_runs(&self, label: &str) {
+        self.abort_runs(|run| run.window.as_deref().is_none_or(|window| window == label))
+            .await;
+    }
+
+    async fn abort_runs(&self, matches: impl Fn(&ActiveRun) -> bool) {
+        let runs: Vec<(String, ActiveRun)> = {
+            let mut active_runs = self.inner.active_runs.lock().await;
+            let run_ids: Vec<String> = active_runs
+                .iter()
+                .filter(|(_, run)| matches(run))
+                .map(|(run_id, _)| run_id.clone())
+                .collect();
+            run_ids
+                .iter()
+                .filter_map(|run_id| active_runs.remove_entry(run_id))
+                .collect()
+        };
+        if runs.is_empty() {
+            return;
+        }
+
+        log_protocol_error(
+            "Shutdown",
+            &format!("Aborting {} active run(s)", runs.len()),
+        );
+
+        let aborts = runs.iter().map(|(run_id, run)| {
+            let mut params = serde_json::json!({ "runId": run_id });
+            if let Some(session_key) = &run.session_key {
+                params["sessionKey"] = serde_json::json!(session_key);
+            }
+            send_request(
+                &self.inner,
+                "chat.abort",
+                Some(params),
+                Duration::from_secs(ABORT_ON_EXIT_TIMEOUT_SECS),
+            )
+        });
+
+        for result in futures_util::future::join_all(aborts).await {
+            if let Err(e) = result {
+                log_protocol_error("Shutdown", &format!("chat.abort failed: {}", e));
+            }
+        }
+    }
+}
+
 // ============================================================================
 // Tauri Commands
 // ============================================================================
 
 /// Connect to Clawdbot Gateway
+///
+/// `profile` names the keychain entry the token came from (the default one
+/// when omitted), which token rotation writes the replacement to.
 #[tauri::command]
 pub async fn connect(
     app: AppHandle,
     state: State<'_, GatewayState>,
     url: String,
     token: String,
-) -> Result<ConnectResult, String> {
+    profile: Option<String>,
+) -> Result<ConnectResult, CommandError> {
+    let result = connect_endpoint(&app, &state, url, token).await?;
+    *state.inner.token_profile.lock().await = profile.unwrap_or_default();
+    Ok(result)
+}
+
+/// Connect, through the home/away endpoint for this network if configured
+async fn connect_endpoint(
+    app: &AppHandle,
+    state: &GatewayState,
+    url: String,
+    token: String,
+) -> Result<ConnectResult, CommandError> {
+    let Some(selected) = endpoints::select(app, &url).await else {
+        let result = connect_gateway(app, state, url, token).await?;
+        endpoints::set_active(app, None);
+        return Ok(result);
+    };
+    let mut result = connect_gateway(app, state, selected.url.clone(), token).await?;
+    // A ws/wss fallback applies to the endpoint, not to the URL the caller saved
+    result.protocol_switched = false;
+    endpoints::set_active(app, Some(selected));
+    Ok(result)
+}
+
+/// Connect with new credentials and store the token only once the Gateway
+/// accepts it
+///
+/// A live connection on other credentials is kept until a probe confirms the
+/// new ones. If the new connection then fails, or its token can't be stored,
+/// it is dropped and the previous connection restored, so the stored and
+/// active credentials never disagree.
+#[tauri::command]
+pub async fn connect_and_save(
+    app: AppHandle,
+    state: State<'_, GatewayState>,
+    profile: String,
+    url: String,
+    token: String,
+) -> Result<ConnectResult, CommandError> {
+    if url.trim().is_empty() {
+        return Err(CommandError::validation("Gateway URL is required"));
+    }
+    redact::register_secret(&token);
+
+    let current = state
+        .inner
+        .stored_credentials
+        .lock()
+        .await
+        .as_ref()
+        .map(|credentials| (credentials.url.clone(), credentials.token.clone()));
+    let connected = state.inner.connection_state.read().await.is_connected();
+    let switching = connected && current != Some((url.clone(), token.clone()));
+    let previous = if switching { current } else { None };
+
+    if switching {
+        let report = probe(&app, &url, Some(&token)).await;
+        match report.authenticated {
+            Some(true) => {}
+            Some(false) => {
+                return Err(CommandError::auth(
+                    report.error.unwrap_or_else(|| "Token rejected".to_string()),
+                ))
+            }
+            None => {
+                return Err(CommandError::network(
+                    report
+                        .error
+                        .unwrap_or_else(|| "Gateway unreachable".to_string()),
+                ))
+            }
+        }
+        state.inner.shutdown.store(true, Ordering::SeqCst);
+        *state.inner.sender.lock().await = None;
+        *state.inner.connection_state.write().await = ConnectionState::Disconnected;
+        let _ = app.emit("gateway:state", ConnectionState::Disconnected);
+    }
+
+    let result = match connect_endpoint(&app, &state, url, token.clone()).await {
+        Ok(result) => result,
+        Err(e) => {
+            restore_connection(&app, &state, previous).await;
+            return Err(e);
+        }
+    };
+
+    let key = keychain::gateway_token_key(&profile);
+    let stored = if token.is_empty() {
+        // A Gateway without auth: drop any token stored before (there may be none)
+        let _ = keychain::keychain_delete(keychain::SERVICE_NAME.to_string(), key).await;
+        Ok(())
+    } else {
+        keychain::keychain_set(keychain::SERVICE_NAME.to_string(), key, token).await
+    };
+    if let Err(e) = stored {
+        log::error!("[Gateway] Connected but failed to store the token: {}", e);
+        state.inner.shutdown.store(true, Ordering::SeqCst);
+        *state.inner.sender.lock().await = None;
+        *state.inner.stored_credentials.lock().await = None;
+        *state.inner.connection_state.write().await = ConnectionState::Disconnected;
+        let _ = app.emit("gateway:state", ConnectionState::Disconnected);
+        restore_connection(&app, &state, previous).await;
+        return Err(e);
+    }
+    *state.inner.token_profile.lock().await = profile.clone();
+    log::info!(
+        "[Gateway] Connected and stored the token for profile '{}'",
+        profile
+    );
+    Ok(result)
+}
+
+/// Reconnect with the credentials in use before a failed switch
+async fn restore_connection(
+    app: &AppHandle,
+    state: &GatewayState,
+    previous: Option<(String, String)>,
+) {
+    let Some((url, token)) = previous else {
+        return;
+    };
+    log::info!("[Gateway] Switch failed, restoring the previous connection");
+    if let Err(e) = connect_gateway(app, state, url, token).await {
+        log::error!("[Gateway] Failed to restore the previous connection: {}", e);
+    }
+}
+
+impl GatewayState {
+    /// Connect unless already connected, for background work (e.g. scheduled
+    /// prompts) that runs without the frontend
+    ///
+    /// Uses the credentials of the last successful connection, or
+    /// `fallback_url` with the active profile's token from the keychain.
+    pub(crate) async fn ensure_connected(
+        &self,
+        app: &AppHandle,
+        fallback_url: Option<&str>,
+    ) -> Result<(), CommandError> {
+        if self.inner.connection_state.read().await.is_connected() {
+            return Ok(());
+        }
+        let credentials = self.inner.stored_credentials.lock().await.clone();
+        let (url, token) = match credentials {
+            Some(credentials) => (credentials.url, credentials.token),
+            None => {
+                let url = fallback_url
+                    .ok_or_else(|| CommandError::validation("No Gateway to connect to"))?;
+                let token = keychain::keychain_get(
+                    keychain::SERVICE_NAME.to_string(),
+                    self.token_key().await,
+                )
+                .await
+                .unwrap_or_default();
+                (url.to_string(), token)
+            }
+        };
+        connect_gateway(app, self, url, token).await.map(|_| ())
+    }
+
+    /// URL of the current connection, also while reconnecting to it
+    pub(crate) async fn connected_url(&self) -> Option<String> {
+        self.inner
+            .stored_credentials
+            .lock()
+            .await
+            .as_ref()
+            .map(|credentials| credentials.url.clone())
+    }
+
+    /// Move the connection to another URL of the same Gateway, keeping the
+    /// token
+    ///
+    /// Queued messages are kept and sent once the new connection is up.
+    pub(crate) async fn switch_url(
+        &self,
+        app: &AppHandle,
+        url: String,
+    ) -> Result<ConnectResult, CommandError> {
+        let token = self
+            .inner
+            .stored_credentials
+            .lock()
+            .await
+            .as_ref()
+            .map(|credentials| credentials.token.clone())
+            .ok_or_else(|| CommandError::validation("Not connected"))?;
+        self.inner.shutdown.store(true, Ordering::SeqCst);
+        *self.inner.sender.lock().await = None;
+        *self.inner.connection_state.write().await = ConnectionState::Disconnected;
+        connect_gateway(app, self, url, token).await
+    }
+}
+
+async fn connect_gateway(
+    app: &AppHandle,
+    state: &GatewayState,
+    url: String,
+    token: String,
+) -> Result<ConnectResult, CommandError> {
     // DEBUG: Log what URL and token status we received from frontend
     log_protocol_error("CONNECT CALLED", &format!("URL received: {}", url));
     let token_status = if token.is_empty() {
@@ -892,6 +1557,7 @@ pub async fn connect(
         format!("present ({} chars)", token.len())
     };
     log_protocol_error("CONNECT CALLED", &format!("Token status: {}", token_status));
+    redact::register_secret(&token);
 
     // CRITICAL FIX: Acquire connection mutex to prevent race conditions
     // This ensures only one connection attempt runs at a time
@@ -934,7 +1600,7 @@ pub async fn connect(
     let _ = app.emit("gateway:state", ConnectionState::Connecting);
 
     // Perform actual connection
-    match connect_internal(&app, Arc::clone(&state.inner), &url, &token, new_session_id).await {
+    match connect_internal(app, Arc::clone(&state.inner), &url, &token, new_session_id).await {
         Ok(result) => {
             // Only store credentials AFTER successful connection
             *state.inner.stored_credentials.lock().await = Some(StoredCredentials {
@@ -949,31 +1615,31 @@ pub async fn connect(
                 ConnectionState::Connected { session_id: None },
             );
 
+            state
+                .inner
+                .failed_reconnect_cycles
+                .store(0, Ordering::SeqCst);
+            startup::remember_gateway(app, &result.used_url).await;
+
             // Drain message queue
+            apply_pending_models(app, &state.inner).await;
             drain_message_queue(&state.inner).await;
+            drain_offline_queue(app, &state.inner).await;
 
             Ok(result)
         }
         Err(e) => {
-            let error_msg = e.user_message();
-            *state.inner.connection_state.write().await = ConnectionState::Failed {
-                reason: error_msg.clone(),
-                can_retry: e.is_retryable(),
-            };
-            let _ = app.emit(
-                "gateway:state",
-                ConnectionState::Failed {
-                    reason: error_msg.clone(),
-                    can_retry: e.is_retryable(),
-                },
-            );
+            let error = CommandError::from(e.clone());
+            let fail_state = ConnectionState::failed(&error);
+            *state.inner.connection_state.write().await = fail_state.clone();
+            let _ = app.emit("gateway:state", fail_state);
 
             // If retryable, start reconnection loop
             if e.is_retryable() && !e.requires_reauth() {
                 start_reconnection_loop(app.clone(), state.inner.clone()).await;
             }
 
-            Err(error_msg)
+            Err(error)
         }
     }
 }
@@ -985,6 +1651,14 @@ enum HandshakeResult {
     Error { code: String, message: String },
 }
 
+/// Resolver configured for Gateway connections
+pub(crate) fn dns_resolver(app: &AppHandle) -> Resolver {
+    match app.try_state::<DnsState>() {
+        Some(dns) => dns.resolver(),
+        None => Resolver::default(),
+    }
+}
+
 /// Internal connection logic
 async fn connect_internal(
     app: &AppHandle,
@@ -993,7 +1667,20 @@ async fn connect_internal(
     token: &str,
     session_id: u64,
 ) -> Result<ConnectResult, GatewayError> {
-    let (ws_stream, used_url, protocol_switched) = try_connect_with_fallback(url).await?;
+    let tracer = ConnectionTracer::new(url);
+    if let Ok(mut trace) = state.connection_trace.lock() {
+        *trace = Some(tracer.clone());
+    }
+    let resolver = dns_resolver(app);
+    let (ws_stream, used_url, protocol_switched) =
+        match try_connect_with_fallback(url, &tracer, &resolver).await {
+            Ok(connection) => connection,
+            Err(e) => {
+                tracer.finish(Some(e.to_string()));
+                return Err(e);
+            }
+        };
+    let upgraded_at = Instant::now();
 
     let (mut write, mut read) = ws_stream.split();
 
@@ -1015,13 +1702,22 @@ async fn connect_internal(
     let app_clone = app.clone();
     tokio::spawn(async move {
         while let Some(msg) = rx.recv().await {
+            if !chaos::pass_frame(&app_clone, "outbound").await {
+                continue;
+            }
             let ws_msg = match msg {
-                OutgoingMessage::Raw(text) => WsMessage::Text(text.into()),
+                OutgoingMessage::Raw(text) => {
+                    frame_tap::tap(&app_clone, FrameDirection::Outbound, &text);
+                    WsMessage::Text(text.into())
+                }
                 OutgoingMessage::Ping => WsMessage::Ping(vec![].into()),
             };
+            if let Some(data_saver) = app_clone.try_state::<DataSaverState>() {
+                data_saver.record_sent(ws_msg.len());
+            }
             if let Err(e) = write.send(ws_msg).await {
                 log_protocol_error("Failed to send message", &e.to_string());
-                let _ = app_clone.emit("gateway:error", e.to_string());
+                let _ = app_clone.emit("gateway:error", CommandError::network(e.to_string()));
                 break;
             }
         }
@@ -1037,20 +1733,31 @@ async fn connect_internal(
     // CRITICAL FIX: Use state's pending_requests instead of creating a local copy
     // This ensures get_models and other commands share the same request map as the handler
     let pending_clone = Arc::clone(&state.pending_requests);
-    let health_metrics = Arc::new(Mutex::new(HealthMetrics::default()));
-    let health_clone = health_metrics.clone();
-    let active_runs: Arc<Mutex<HashMap<String, Instant>>> = Arc::new(Mutex::new(HashMap::new()));
-    let runs_clone = active_runs.clone();
+    let health_clone = Arc::clone(&state.health_metrics);
+    let runs_clone = Arc::clone(&state.active_runs);
     let handshake_tx_clone = handshake_tx.clone();
+    let tracer_clone = tracer.clone();
 
     // Spawn message handler with session ID validation
     tokio::spawn(async move {
         log_protocol_error("MSG_HANDLER", &format!("Started for session {}", handler_session_id));
         
-        while let Some(msg) = read.next().await {
-            // Check if this handler is stale (session ID changed)
-            let current_session_id = *state_for_handler.connection_session_id.lock().await;
-            if current_session_id != handler_session_id {
+        loop {
+            let msg = tokio::select! {
+                msg = read.next() => match msg {
+                    Some(msg) => msg,
+                    None => break,
+                },
+                // Chaos mode: simulate the network dropping the connection
+                _ = chaos::forced_disconnect(&app_clone) => Err(WsError::Io(std::io::Error::new(
+                    std::io::ErrorKind::ConnectionReset,
+                    "Chaos: forced disconnect",
+                ))),
+            };
+
+            // Check if this handler is stale (session ID changed)
+            let current_session_id = *state_for_handler.connection_session_id.lock().await;
+            if current_session_id != handler_session_id {
                 log_protocol_error(
                     "MSG_HANDLER",
                     &format!(
@@ -1063,7 +1770,11 @@ async fn connect_internal(
             
             match msg {
                 Ok(WsMessage::Text(text)) => {
+                    if !chaos::pass_frame(&app_clone, "inbound").await {
+                        continue;
+                    }
                     let text_str = text.to_string();
+                    frame_tap::tap(&app_clone, FrameDirection::Inbound, &text_str);
                     
                     // Log message length only (no content for privacy)
                     log_protocol_error("INCOMING MSG", &format!("len={}", text_str.len()));
@@ -1071,6 +1782,21 @@ async fn connect_internal(
                     // Validate and parse frame
                     match validate_frame(&text_str) {
                         Ok(frame) => {
+                            if let ValidatedFrame::Event { event, .. } = &frame {
+                                if event == "connect.challenge" {
+                                    tracer_clone.ok(TraceStep::Challenge, upgraded_at, None);
+                                }
+                            }
+                            if let Some(data_saver) = app_clone.try_state::<DataSaverState>() {
+                                let session_key = match &frame {
+                                    ValidatedFrame::Event {
+                                        payload: Some(payload),
+                                        ..
+                                    } => payload.get("sessionKey").and_then(|k| k.as_str()),
+                                    _ => None,
+                                };
+                                data_saver.record_received(session_key, text_str.len());
+                            }
                             handle_validated_frame(
                                 frame,
                                 &app_clone,
@@ -1089,9 +1815,8 @@ async fn connect_internal(
                     }
                 }
                 Ok(WsMessage::Pong(_)) => {
-                    // Ping response - record latency
-                    // Note: We'd need to track ping send time for accurate latency
-                    health_clone.lock().await.record_latency(0);
+                    // Ping response - record round-trip latency
+                    health_clone.lock().await.record_pong();
                 }
                 Ok(WsMessage::Close(frame)) => {
                     let reason = frame
@@ -1106,7 +1831,12 @@ async fn connect_internal(
                     
                     let _ = app_clone.emit("gateway:disconnected", reason.clone());
                     let _ = app_clone.emit("gateway:state", ConnectionState::Disconnected);
-                    
+                    accessibility::announce(
+                        &app_clone,
+                        "Disconnected from Gateway",
+                        Priority::Assertive,
+                    );
+
                     // Signal handshake failure if we close before completing
                     if let Some(tx) = handshake_tx_clone.lock().await.take() {
                         let _ = tx.send(HandshakeResult::Error {
@@ -1120,15 +1850,13 @@ async fn connect_internal(
                     log_protocol_error("WebSocket error", &format!("session={} err={}", handler_session_id, e));
                     
                     // CRITICAL: Update connection state on error
-                    let fail_state = ConnectionState::Failed {
-                        reason: e.to_string(),
-                        can_retry: true,
-                    };
+                    let error = CommandError::network(e.to_string());
+                    let fail_state = ConnectionState::failed(&error);
                     *state_for_handler.connection_state.write().await = fail_state.clone();
                     *state_for_handler.sender.lock().await = None;
                     state_for_handler.active_runs.lock().await.clear();
                     
-                    let _ = app_clone.emit("gateway:error", e.to_string());
+                    let _ = app_clone.emit("gateway:error", error);
                     let _ = app_clone.emit("gateway:state", fail_state);
                     
                     // Signal handshake failure on error
@@ -1147,20 +1875,21 @@ async fn connect_internal(
     });
 
     // Start ping/pong health monitor
-    start_health_monitor(app.clone(), tx.clone(), health_metrics.clone()).await;
+    start_health_monitor(app.clone(), tx.clone(), Arc::clone(&state.health_metrics)).await;
 
     // Start streaming timeout monitor
-    start_stream_timeout_monitor(app.clone(), active_runs.clone()).await;
+    start_stream_timeout_monitor(app.clone(), Arc::clone(&state.active_runs)).await;
 
     // CRITICAL-1: Start cleanup task for expired pending requests
-    start_pending_requests_cleanup(Arc::clone(&state.pending_requests)).await;
+    start_pending_requests_cleanup(&app, Arc::clone(&state.pending_requests)).await;
 
     // CRITICAL FIX: Wait for handshake to complete before returning success
     // Timeout after 30 seconds (should be plenty for handshake)
     let handshake_timeout = Duration::from_secs(30);
-    match tokio::time::timeout(handshake_timeout, handshake_rx).await {
+    let result = match tokio::time::timeout(handshake_timeout, handshake_rx).await {
         Ok(Ok(HandshakeResult::Success)) => {
             log_protocol_error("CONNECT", "Handshake completed successfully");
+            tracer.ok(TraceStep::HelloOk, upgraded_at, None);
             Ok(ConnectResult {
                 success: true,
                 used_url,
@@ -1169,6 +1898,11 @@ async fn connect_internal(
         }
         Ok(Ok(HandshakeResult::Error { code, message })) => {
             log_protocol_error("CONNECT", &format!("Handshake failed: [{}] {}", code, message));
+            tracer.failed(
+                TraceStep::HelloOk,
+                upgraded_at,
+                format!("[{}] {}", code, message),
+            );
             Err(GatewayError::Gateway {
                 code,
                 message: message.clone(),
@@ -1178,6 +1912,7 @@ async fn connect_internal(
         }
         Ok(Err(_)) => {
             log_protocol_error("CONNECT", "Handshake channel closed unexpectedly");
+            tracer.failed(TraceStep::HelloOk, upgraded_at, "Connection closed");
             Err(GatewayError::Network {
                 message: "Connection closed before handshake completed".to_string(),
                 retryable: true,
@@ -1186,11 +1921,44 @@ async fn connect_internal(
         }
         Err(_) => {
             log_protocol_error("CONNECT", "Handshake timed out");
+            tracer.failed(TraceStep::HelloOk, upgraded_at, "Timed out");
             Err(GatewayError::Timeout {
                 timeout_secs: handshake_timeout.as_secs(),
                 request_id: None,
             })
         }
+    };
+    tracer.finish(result.as_ref().err().map(|e| e.to_string()));
+    result
+}
+
+/// The `connect` request answering `connect.challenge`
+fn connect_request(token: &str) -> GatewayRequest {
+    GatewayRequest {
+        msg_type: "req".to_string(),
+        id: uuid::Uuid::new_v4().to_string(),
+        method: "connect".to_string(),
+        params: Some(
+            serde_json::to_value(ConnectParams {
+                min_protocol: PROTOCOL_VERSION,
+                max_protocol: PROTOCOL_VERSION,
+                client: ClientInfo {
+                    id: "openclaw-control-ui".to_string(), // Must match gateway schema
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    platform: get_platform(),
+                    mode: "ui".to_string(), // Must be "webchat", "cli", "ui", "backend", "probe", or "test"
+                },
+                role: "operator".to_string(),
+                scopes: vec!["operator.read".to_string(), "operator.write".to_string()],
+                caps: vec![], // Optional, skipped if empty
+                auth: AuthInfo {
+                    token: Redacted(token.to_string()),
+                },
+                locale: "en-US".to_string(),
+                user_agent: format!("moltz/{}", env!("CARGO_PKG_VERSION")),
+            })
+            .unwrap(),
+        ),
     }
 }
 
@@ -1201,7 +1969,7 @@ async fn handle_validated_frame(
     tx: &mpsc::Sender<OutgoingMessage>,
     token: &str,
     pending_requests: &Arc<Mutex<HashMap<String, PendingRequest>>>,
-    active_runs: &Arc<Mutex<HashMap<String, Instant>>>,
+    active_runs: &Arc<Mutex<HashMap<String, ActiveRun>>>,
     handshake_tx: &Arc<Mutex<Option<oneshot::Sender<HandshakeResult>>>>,
 ) {
     match frame {
@@ -1209,35 +1977,7 @@ async fn handle_validated_frame(
             match event.as_str() {
                 "connect.challenge" => {
                     // Send connect request
-                    let connect_req = GatewayRequest {
-                        msg_type: "req".to_string(),
-                        id: uuid::Uuid::new_v4().to_string(),
-                        method: "connect".to_string(),
-                        params: Some(
-                            serde_json::to_value(ConnectParams {
-                                min_protocol: PROTOCOL_VERSION,
-                                max_protocol: PROTOCOL_VERSION,
-                                client: ClientInfo {
-                                    id: "openclaw-control-ui".to_string(), // Must match gateway schema
-                                    version: env!("CARGO_PKG_VERSION").to_string(),
-                                    platform: get_platform(),
-                                    mode: "ui".to_string(), // Must be "webchat", "cli", "ui", "backend", "probe", or "test"
-                                },
-                                role: "operator".to_string(),
-                                scopes: vec![
-                                    "operator.read".to_string(),
-                                    "operator.write".to_string(),
-                                ],
-                                caps: vec![], // Optional, skipped if empty
-                                auth: AuthInfo {
-                                    token: token.to_string(),
-                                },
-                                locale: "en-US".to_string(),
-                                user_agent: format!("moltz/{}", env!("CARGO_PKG_VERSION")),
-                            })
-                            .unwrap(),
-                        ),
-                    };
+                    let connect_req = connect_request(token);
 
                     if let Ok(json) = serde_json::to_string(&connect_req) {
                         log_protocol_error(
@@ -1255,50 +1995,193 @@ async fn handle_validated_frame(
                 "chat" => {
                     if let Some(payload) = payload {
                         if let Ok(chat_event) = serde_json::from_value::<ChatEvent>(payload) {
-                            // Update streaming timeout tracker
-                            if let Some(run_id) = &chat_event.run_id {
-                                active_runs
-                                    .lock()
-                                    .await
-                                    .insert(run_id.clone(), Instant::now());
+                            // Replies to scheduled prompts go to the scheduler only
+                            let text = chat_event
+                                .message
+                                .as_ref()
+                                .and_then(extract_chat_message_text);
+                            if scheduler::capture_chat_event(app, &chat_event, text).await {
+                                return;
                             }
 
+                            // Update streaming timeout tracker, capturing the
+                            // originating window when the run is first seen
+                            let window = match &chat_event.run_id {
+                                Some(run_id) => {
+                                    let known = active_runs
+                                        .lock()
+                                        .await
+                                        .get(run_id)
+                                        .map(|run| run.window.clone());
+                                    let session_key = chat_event.session_key.as_deref();
+                                    let (window, stream_timeout) = match known {
+                                        Some(window) => (window, None),
+                                        None => {
+                                            journal::record_run_started(app, run_id, session_key);
+                                            (
+                                                session_window(app, session_key).await,
+                                                Some(
+                                                    session_stream_timeout(app, session_key).await,
+                                                ),
+                                            )
+                                        }
+                                    };
+                                    let mut runs = active_runs.lock().await;
+                                    let run = runs.entry(run_id.clone()).or_insert_with(|| {
+                                        ActiveRun::new(
+                                            chat_event.session_key.clone(),
+                                            window.clone(),
+                                            stream_timeout.unwrap_or(Duration::from_secs(
+                                                DEFAULT_STREAM_TIMEOUT_SECS,
+                                            )),
+                                        )
+                                    });
+                                    run.session_key = chat_event.session_key.clone();
+                                    run.last_activity = Instant::now();
+                                    window
+                                }
+                                None => {
+                                    session_window(app, chat_event.session_key.as_deref()).await
+                                }
+                            };
+                            let window = window.as_deref();
+
                             match chat_event.state.as_deref() {
                                 Some("delta") => {
                                     if let Some(msg) = &chat_event.message {
                                         if let Some(content) = extract_chat_message_text(msg) {
-                                            let _ = app.emit("gateway:stream", content);
+                                            if let Some(run_id) = &chat_event.run_id {
+                                                if let Some(run) =
+                                                    active_runs.lock().await.get_mut(run_id)
+                                                {
+                                                    run.text.push_str(&content);
+                                                }
+                                            }
+                                            let batch = batch_stream_delta(
+                                                app,
+                                                active_runs,
+                                                chat_event.run_id.as_deref(),
+                                                content,
+                                            )
+                                            .await;
+                                            if let Some(batch) = batch {
+                                                emit_run_event(
+                                                    app,
+                                                    window,
+                                                    chat_event.session_key.as_deref(),
+                                                    "gateway:stream",
+                                                    batch,
+                                                );
+                                            }
                                         }
                                     }
                                 }
                                 Some("final") => {
                                     // Remove from active runs
+                                    let mut streamed = String::new();
                                     if let Some(run_id) = &chat_event.run_id {
-                                        active_runs.lock().await.remove(run_id);
+                                        let mut run = active_runs.lock().await.remove(run_id);
+                                        if let Some(run) = &mut run {
+                                            streamed = std::mem::take(&mut run.text);
+                                        }
+                                        flush_pending_stream(app, window, run);
+                                        journal::record_run_ended(app, run_id);
+                                        notifications::finish_progress(
+                                            app,
+                                            run_id,
+                                            RunOutcome::Completed,
+                                            Some(&chat_event),
+                                        )
+                                        .await;
                                     }
-                                    // Emit completion with usage stats
-                                    let _ = app.emit(
+                                    // Use the final message when no deltas were streamed
+                                    let content = if streamed.is_empty() {
+                                        chat_event
+                                            .message
+                                            .as_ref()
+                                            .and_then(extract_chat_message_text)
+                                            .unwrap_or_default()
+                                    } else {
+                                        streamed
+                                    };
+                                    let message_id =
+                                        message_store::record_completed(app, &chat_event, content)
+                                            .await;
+                                    // Emit completion with usage stats and the stored message
+                                    emit_run_event(
+                                        app,
+                                        window,
+                                        chat_event.session_key.as_deref(),
                                         "gateway:complete",
                                         serde_json::json!({
                                             "usage": chat_event.usage,
                                             "stopReason": chat_event.stop_reason,
+                                            "runId": chat_event.run_id,
+                                            "messageId": message_id,
                                         }),
                                     );
+                                    accessibility::announce(
+                                        app,
+                                        "Response complete",
+                                        Priority::Polite,
+                                    );
+                                    quota::record_usage(app, chat_event.usage.as_ref()).await;
+                                    tray::note_reply(app);
                                 }
                                 Some("aborted") => {
                                     if let Some(run_id) = &chat_event.run_id {
-                                        active_runs.lock().await.remove(run_id);
+                                        let run = active_runs.lock().await.remove(run_id);
+                                        flush_pending_stream(app, window, run);
+                                        journal::record_run_ended(app, run_id);
+                                        notifications::finish_progress(
+                                            app,
+                                            run_id,
+                                            RunOutcome::Aborted,
+                                            None,
+                                        )
+                                        .await;
                                     }
-                                    let _ = app.emit("gateway:aborted", ());
+                                    emit_run_event(
+                                        app,
+                                        window,
+                                        chat_event.session_key.as_deref(),
+                                        "gateway:aborted",
+                                        (),
+                                    );
+                                    accessibility::announce(
+                                        app,
+                                        "Response stopped",
+                                        Priority::Polite,
+                                    );
                                 }
                                 Some("error") => {
                                     if let Some(run_id) = &chat_event.run_id {
-                                        active_runs.lock().await.remove(run_id);
+                                        let run = active_runs.lock().await.remove(run_id);
+                                        flush_pending_stream(app, window, run);
+                                        journal::record_run_ended(app, run_id);
+                                        notifications::finish_progress(
+                                            app,
+                                            run_id,
+                                            RunOutcome::Failed,
+                                            None,
+                                        )
+                                        .await;
                                     }
                                     let error_msg = chat_event
                                         .error_message
                                         .unwrap_or_else(|| "Unknown error".to_string());
-                                    let _ = app.emit("gateway:error", error_msg);
+                                    accessibility::announce(
+                                        app,
+                                        format!("Response failed: {}", error_msg),
+                                        Priority::Assertive,
+                                    );
+                                    emit_run_event(
+                                        app,
+                                        window,
+                                        chat_event.session_key.as_deref(),
+                                        "gateway:error",
+                                        CommandError::gateway(error_msg),
+                                    );
                                 }
                                 _ => {}
                             }
@@ -1312,6 +2195,12 @@ async fn handle_validated_frame(
                     let _ = app.emit("gateway:disconnected", "Server shutdown");
                 }
                 _ => {
+                    // Let registered handlers / frontend subscriptions take it
+                    let registry = app.state::<EventRegistry>();
+                    if registry.dispatch(app, &event, payload.as_ref()) {
+                        return;
+                    }
+
                     // Unknown event - emit for debugging
                     let _ = app.emit(
                         "gateway:event",
@@ -1348,6 +2237,22 @@ async fn handle_validated_frame(
             if is_connect_response && ok {
                 log_protocol_error("CONNECT SUCCESS", "Received hello-ok from gateway");
                 let _ = app.emit("gateway:connected", ());
+                accessibility::announce(app, "Connected to Gateway", Priority::Polite);
+
+                // Fetch usage limits and token expiry in the background (the
+                // responses arrive through this loop)
+                let refresh_app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    let _ = quota::refresh(&refresh_app).await;
+                    let _ = token_expiry::refresh(&refresh_app).await;
+                });
+
+                // Warn up front about an old/incompatible gateway instead of
+                // failing mysteriously later
+                if let Some(warning) = payload.as_ref().and_then(check_gateway_compat) {
+                    log_protocol_error("Compatibility", &warning.issues.join("; "));
+                    let _ = app.emit("gateway:compat_warning", warning);
+                }
                 
                 // Signal handshake success
                 if let Some(tx) = handshake_tx.lock().await.take() {
@@ -1377,7 +2282,13 @@ async fn handle_validated_frame(
                 let _ = pending_req.sender.send(response.clone());
             }
 
-            // Emit for general listeners
+            // Emit for general listeners. Every window receives this, so
+            // secrets in the payload (e.g. a rotated token) are masked; the
+            // pending request above still gets the original
+            let mut response = response;
+            if let Some(payload) = response.payload.as_mut() {
+                redact::redact_json(payload);
+            }
             let _ = app.emit("gateway:response", response);
         }
         ValidatedFrame::Request { .. } => {
@@ -1386,110 +2297,185 @@ async fn handle_validated_frame(
     }
 }
 
+/// A connection monitor that hasn't looped for this long is stuck and gets
+/// restarted by the supervisor
+const MONITOR_STALL_AFTER: Duration = Duration::from_secs(5 * 60);
+
 /// Start health monitoring with ping/pong
 async fn start_health_monitor(
     app: AppHandle,
     tx: mpsc::Sender<OutgoingMessage>,
-    _health_metrics: Arc<Mutex<HealthMetrics>>,
+    health_metrics: Arc<Mutex<HealthMetrics>>,
 ) {
-    tokio::spawn(async move {
-        let ping_interval = Duration::from_secs(DEFAULT_PING_INTERVAL_SECS);
-        let _ping_timeout = Duration::from_secs(DEFAULT_PING_TIMEOUT_SECS);
-
-        loop {
-            tokio::time::sleep(ping_interval).await;
+    let handle = app.clone();
+    supervisor::supervise(
+        &handle,
+        "gateway.health_monitor",
+        Some(MONITOR_STALL_AFTER),
+        move |heartbeat| {
+            health_monitor(
+                app.clone(),
+                tx.clone(),
+                Arc::clone(&health_metrics),
+                heartbeat,
+            )
+        },
+    );
+}
 
-            // Send ping
-            if tx.send(OutgoingMessage::Ping).await.is_err() {
-                // Channel closed, connection lost
-                let _ = app.emit("gateway:disconnected", "Connection lost");
-                break;
+async fn health_monitor(
+    app: AppHandle,
+    tx: mpsc::Sender<OutgoingMessage>,
+    health_metrics: Arc<Mutex<HealthMetrics>>,
+    heartbeat: Heartbeat,
+) {
+    let ping_interval = Duration::from_secs(DEFAULT_PING_INTERVAL_SECS);
+    let ping_timeout = Duration::from_secs(DEFAULT_PING_TIMEOUT_SECS);
+
+    loop {
+        heartbeat.beat();
+        // Ping less often on battery
+        tokio::time::sleep(power::throttled_interval(&app, ping_interval)).await;
+
+        {
+            let mut metrics = health_metrics.lock().await;
+            // A ping still unanswered past the timeout counts as a failure
+            if metrics
+                .ping_sent_at
+                .is_some_and(|sent_at| sent_at.elapsed() > ping_timeout)
+            {
+                metrics.record_failure();
             }
+            metrics.record_ping_sent();
         }
-    });
+
+        // Send ping
+        if tx.send(OutgoingMessage::Ping).await.is_err() {
+            // Channel closed, connection lost
+            let _ = app.emit("gateway:disconnected", "Connection lost");
+            accessibility::announce(&app, "Connection lost", Priority::Assertive);
+            break;
+        }
+    }
 }
 
 /// Start streaming timeout monitor
 async fn start_stream_timeout_monitor(
     app: AppHandle,
-    active_runs: Arc<Mutex<HashMap<String, Instant>>>,
+    active_runs: Arc<Mutex<HashMap<String, ActiveRun>>>,
 ) {
-    tokio::spawn(async move {
-        let check_interval = Duration::from_secs(5);
-        let stream_timeout = Duration::from_secs(DEFAULT_STREAM_TIMEOUT_SECS);
+    let handle = app.clone();
+    supervisor::supervise(
+        &handle,
+        "gateway.stream_timeout_monitor",
+        Some(MONITOR_STALL_AFTER),
+        move |heartbeat| stream_timeout_monitor(app.clone(), Arc::clone(&active_runs), heartbeat),
+    );
+}
 
-        loop {
-            tokio::time::sleep(check_interval).await;
+async fn stream_timeout_monitor(
+    app: AppHandle,
+    active_runs: Arc<Mutex<HashMap<String, ActiveRun>>>,
+    heartbeat: Heartbeat,
+) {
+    let check_interval = Duration::from_secs(5);
 
-            let mut runs = active_runs.lock().await;
-            let mut timed_out = Vec::new();
+    loop {
+        heartbeat.beat();
+        tokio::time::sleep(check_interval).await;
 
-            for (run_id, last_activity) in runs.iter() {
-                if last_activity.elapsed() > stream_timeout {
-                    timed_out.push(run_id.clone());
-                }
-            }
+        let mut runs = active_runs.lock().await;
+        let mut timed_out = Vec::new();
 
-            for run_id in timed_out {
-                runs.remove(&run_id);
-                let _ = app.emit(
-                    "gateway:stream_timeout",
-                    serde_json::json!({
-                        "runId": run_id,
-                        "timeoutSecs": stream_timeout.as_secs()
-                    }),
-                );
+        for (run_id, run) in runs.iter() {
+            if run.last_activity.elapsed() > run.stream_timeout {
+                timed_out.push(run_id.clone());
             }
         }
-    });
+
+        for run_id in timed_out {
+            let run = runs.remove(&run_id);
+            let window = run.as_ref().and_then(|run| run.window.clone());
+            let session_key = run.as_ref().and_then(|run| run.session_key.clone());
+            let timeout_secs = run.as_ref().map_or(DEFAULT_STREAM_TIMEOUT_SECS, |run| {
+                run.stream_timeout.as_secs()
+            });
+            flush_pending_stream(&app, window.as_deref(), run);
+            journal::record_run_ended(&app, &run_id);
+            notifications::finish_progress(&app, &run_id, RunOutcome::Failed, None).await;
+            emit_run_event(
+                &app,
+                window.as_deref(),
+                session_key.as_deref(),
+                "gateway:stream_timeout",
+                serde_json::json!({
+                    "runId": run_id,
+                    "timeoutSecs": timeout_secs
+                }),
+            );
+        }
+    }
 }
 
 /// CRITICAL-1: Cleanup task for expired pending requests
 async fn start_pending_requests_cleanup(
+    app: &AppHandle,
     pending_requests: Arc<Mutex<HashMap<String, PendingRequest>>>,
 ) {
-    tokio::spawn(async move {
-        let cleanup_interval = Duration::from_secs(30);
+    supervisor::supervise(
+        app,
+        "gateway.pending_requests_cleanup",
+        Some(MONITOR_STALL_AFTER),
+        move |heartbeat| pending_requests_cleanup(Arc::clone(&pending_requests), heartbeat),
+    );
+}
 
-        loop {
-            tokio::time::sleep(cleanup_interval).await;
+async fn pending_requests_cleanup(
+    pending_requests: Arc<Mutex<HashMap<String, PendingRequest>>>,
+    heartbeat: Heartbeat,
+) {
+    let cleanup_interval = Duration::from_secs(30);
 
-            let mut pending = pending_requests.lock().await;
-            let now = Instant::now();
+    loop {
+        heartbeat.beat();
+        tokio::time::sleep(cleanup_interval).await;
 
-            // Remove requests older than their timeout + 1 minute grace period
-            pending.retain(|_, req| {
-                now.duration_since(req.created_at) < req.timeout + Duration::from_secs(60)
-            });
-        }
-    });
+        let mut pending = pending_requests.lock().await;
+        let now = Instant::now();
+
+        // Remove requests older than their timeout + 1 minute grace period
+        pending.retain(|_, req| {
+            now.duration_since(req.created_at) < req.timeout + Duration::from_secs(60)
+        });
+    }
 }
 
+/// Interval between `gateway:reconnect_countdown` events
+const RECONNECT_COUNTDOWN_TICK: Duration = Duration::from_secs(1);
+
 /// Start reconnection loop with exponential backoff
 async fn start_reconnection_loop(app: AppHandle, state: Arc<GatewayStateInner>) {
+    // A connect started elsewhere (e.g. restoring after a failed switch)
+    // moves the session ID on and supersedes this loop
+    let mut own_session_id = *state.connection_session_id.lock().await;
     tokio::spawn(async move {
         loop {
             let attempt = state.reconnect_attempt.fetch_add(1, Ordering::SeqCst) + 1;
 
             if attempt > MAX_RECONNECT_ATTEMPTS {
                 // Give up
-                *state.connection_state.write().await = ConnectionState::Failed {
-                    reason: format!(
-                        "Failed to reconnect after {} attempts",
-                        MAX_RECONNECT_ATTEMPTS
-                    ),
-                    can_retry: true,
-                };
-                let _ = app.emit(
-                    "gateway:state",
-                    ConnectionState::Failed {
-                        reason: format!(
-                            "Failed to reconnect after {} attempts",
-                            MAX_RECONNECT_ATTEMPTS
-                        ),
-                        can_retry: true,
-                    },
-                );
+                let fail_state = ConnectionState::failed(&CommandError::network(format!(
+                    "Failed to reconnect after {} attempts",
+                    MAX_RECONNECT_ATTEMPTS
+                )));
+                *state.connection_state.write().await = fail_state.clone();
+                let _ = app.emit("gateway:state", fail_state);
+
+                let cycles = state.failed_reconnect_cycles.fetch_add(1, Ordering::SeqCst) + 1;
+                if cycles == incident::INCIDENT_AFTER_FAILED_CYCLES {
+                    let app = app.clone();
+                    tokio::spawn(async move { incident::capture(&app, cycles).await });
+                }
                 break;
             }
 
@@ -1516,8 +2502,31 @@ async fn start_reconnection_loop(app: AppHandle, state: Arc<GatewayStateInner>)
                 },
             );
 
-            // Wait for backoff
-            tokio::time::sleep(backoff).await;
+            // Wait for backoff, ticking a countdown until it ends or the
+            // user asks to retry now. Only a wake during this wait counts,
+            // so the listener is registered once for all of it
+            let deadline = Instant::now() + backoff;
+            let wake = state.reconnect_wake.notified();
+            tokio::pin!(wake);
+            wake.as_mut().enable();
+            loop {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                let _ = app.emit(
+                    "gateway:reconnect_countdown",
+                    serde_json::json!({
+                        "attempt": attempt,
+                        "maxAttempts": MAX_RECONNECT_ATTEMPTS,
+                        "remainingMs": remaining.as_millis() as u64,
+                    }),
+                );
+                if remaining.is_zero() {
+                    break;
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(remaining.min(RECONNECT_COUNTDOWN_TICK)) => {}
+                    _ = &mut wake => break,
+                }
+            }
 
             if state.shutdown.load(Ordering::SeqCst) {
                 break;
@@ -1529,13 +2538,18 @@ async fn start_reconnection_loop(app: AppHandle, state: Arc<GatewayStateInner>)
                 // Get current session ID (incrementing it for the new attempt)
                 let new_session_id = {
                     let mut session_id = state.connection_session_id.lock().await;
+                    if *session_id != own_session_id {
+                        break;
+                    }
                     *session_id = session_id.wrapping_add(1);
                     *session_id
                 };
+                own_session_id = new_session_id;
                 match connect_internal(&app, Arc::clone(&state), &creds.url, &creds.token, new_session_id).await {
                     Ok(_) => {
                         // Success!
                         state.reconnect_attempt.store(0, Ordering::SeqCst);
+                        state.failed_reconnect_cycles.store(0, Ordering::SeqCst);
                         *state.connection_state.write().await =
                             ConnectionState::Connected { session_id: None };
                         let _ = app.emit(
@@ -1545,23 +2559,17 @@ async fn start_reconnection_loop(app: AppHandle, state: Arc<GatewayStateInner>)
                         let _ = app.emit("gateway:reconnected", attempt);
 
                         // Drain message queue
+                        apply_pending_models(&app, &state).await;
                         drain_message_queue(&state).await;
+                        drain_offline_queue(&app, &state).await;
                         break;
                     }
                     Err(e) => {
                         if e.requires_reauth() {
                             // Auth error - stop reconnecting
-                            *state.connection_state.write().await = ConnectionState::Failed {
-                                reason: e.user_message(),
-                                can_retry: false,
-                            };
-                            let _ = app.emit(
-                                "gateway:state",
-                                ConnectionState::Failed {
-                                    reason: e.user_message(),
-                                    can_retry: false,
-                                },
-                            );
+                            let fail_state = ConnectionState::failed(&CommandError::from(e));
+                            *state.connection_state.write().await = fail_state.clone();
+                            let _ = app.emit("gateway:state", fail_state);
                             break;
                         }
                         // Continue loop for other errors
@@ -1575,6 +2583,38 @@ async fn start_reconnection_loop(app: AppHandle, state: Arc<GatewayStateInner>)
     });
 }
 
+/// Switch a session's model on the Gateway
+///
+/// The session keeps the model pending until the Gateway accepts it, so a
+/// failed switch is retried before the session's next send.
+async fn apply_session_model(
+    sessions: &SessionState,
+    state: &GatewayStateInner,
+    session_key: &str,
+    model: &str,
+) {
+    let patch = serde_json::json!({ "key": session_key, "model": model });
+    let timeout = Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS);
+    if let Err(e) = send_request(state, "sessions.patch", Some(patch), timeout).await {
+        log::error!("[Gateway] Failed to switch session model: {}", e);
+        return;
+    }
+    if let Err(e) = sessions.confirm_model(session_key, model).await {
+        log::error!("[Gateway] Failed to save the session model: {}", e);
+    }
+}
+
+/// Switch sessions to models chosen while the Gateway couldn't be told
+/// (offline, reconnecting, or a failed switch), ahead of their queued sends
+async fn apply_pending_models(app: &AppHandle, state: &GatewayStateInner) {
+    let Some(sessions) = app.try_state::<SessionState>() else {
+        return;
+    };
+    for (session_key, model) in sessions.pending_models().await {
+        apply_session_model(&sessions, state, &session_key, &model).await;
+    }
+}
+
 /// Drain and send queued messages
 async fn drain_message_queue(state: &GatewayStateInner) {
     let sender = state.sender.lock().await;
@@ -1601,9 +2641,17 @@ async fn drain_message_queue(state: &GatewayStateInner) {
             {
                 processed.insert(msg.id.clone());
             } else if msg.can_retry() {
-                // Put back in queue for retry
+                // Put back in queue for retry (budget set by the method's retry policy)
                 msg.increment_retry();
                 queue.push_back(msg);
+            } else {
+                log_protocol_error(
+                    "Queue",
+                    &format!(
+                        "Dropping {} ({}): retry policy exhausted",
+                        msg.id, msg.method
+                    ),
+                );
             }
         }
 
@@ -1621,10 +2669,67 @@ async fn drain_message_queue(state: &GatewayStateInner) {
     }
 }
 
+/// Send messages queued in offline mode, unless offline mode is still on
+///
+/// Messages that cannot be sent stay queued (in order) for the next attempt.
+async fn drain_offline_queue(app: &AppHandle, state: &GatewayStateInner) {
+    let offline = app.state::<OfflineState>();
+    if offline.is_enabled().await {
+        return;
+    }
+
+    let queue = offline.take_queue().await;
+    if queue.is_empty() {
+        return;
+    }
+
+    let sender = state.sender.lock().await.clone();
+    let mut unsent = Vec::new();
+    let mut sent = 0;
+    for msg in queue {
+        let delivered = match (&sender, unsent.is_empty()) {
+            (Some(tx), true) => tx
+                .send(OutgoingMessage::Raw(msg.json.clone()))
+                .await
+                .is_ok(),
+            _ => false,
+        };
+        if delivered {
+            state
+                .processed_ids
+                .lock()
+                .await
+                .insert(msg.request_id.clone());
+            sent += 1;
+        } else {
+            unsent.push(msg);
+        }
+    }
+
+    let remaining = unsent.len();
+    offline.restore(unsent).await;
+    let _ = app.emit(
+        "gateway:offline_drained",
+        serde_json::json!({ "sent": sent, "remaining": remaining }),
+    );
+}
+
+impl GatewayState {
+    /// Drain the offline queue over the current connection (if any)
+    pub(crate) async fn drain_offline_queue(&self, app: &AppHandle) {
+        if self.inner.connection_state.read().await.is_connected() {
+            apply_pending_models(app, &self.inner).await;
+        }
+        drain_offline_queue(app, &self.inner).await;
+    }
+}
+
 /// Disconnect from Gateway
 #[tauri::command]
-pub async fn disconnect(state: State<'_, GatewayState>) -> Result<(), String> {
+pub async fn disconnect(state: State<'_, GatewayState>) -> Result<(), CommandError> {
     state.inner.shutdown.store(true, Ordering::SeqCst);
+    // Let a reconnection loop waiting out its backoff see the shutdown
+    state.inner.reconnect_wake.notify_waiters();
     *state.inner.sender.lock().await = None;
     *state.inner.connection_state.write().await = ConnectionState::Disconnected;
     *state.inner.pending_requests.lock().await = HashMap::new();
@@ -1633,17 +2738,65 @@ pub async fn disconnect(state: State<'_, GatewayState>) -> Result<(), String> {
     Ok(())
 }
 
+/// Skip the rest of the reconnection backoff and retry immediately
+///
+/// Returns false when not reconnecting. During an attempt this does nothing:
+/// the retry is already happening, and the next backoff runs in full.
+#[tauri::command]
+pub async fn reconnect_now(state: State<'_, GatewayState>) -> Result<bool, CommandError> {
+    let waiting = matches!(
+        *state.inner.connection_state.read().await,
+        ConnectionState::Reconnecting { .. }
+    );
+    if waiting {
+        log::info!("[Gateway] Reconnecting now at the user's request");
+        state.inner.reconnect_wake.notify_waiters();
+    }
+    Ok(waiting)
+}
+
 /// Send a chat message to Gateway
 #[tauri::command]
 pub async fn send_message(
+    app: AppHandle,
+    window: tauri::Window,
     state: State<'_, GatewayState>,
+    offline: State<'_, OfflineState>,
+    sessions: State<'_, SessionState>,
     params: ChatParams,
-) -> Result<String, String> {
-    let connection_state = state.inner.connection_state.read().await.clone();
+) -> Result<SendOutcome, CommandError> {
+    let status = state.send_status(offline.is_enabled().await).await;
+    match status {
+        SendStatus::NotConnected => return Err(CommandError::not_connected()),
+        SendStatus::BlockedNeedsReauth => {
+            log::info!("[Gateway] Message not sent: the Gateway needs new credentials");
+            return Ok(SendOutcome {
+                request_id: uuid::Uuid::new_v4().to_string(),
+                status,
+            });
+        }
+        _ => {}
+    }
 
     // Build request
     let request_id = uuid::Uuid::new_v4().to_string();
-    let idempotency_key = uuid::Uuid::new_v4().to_string();
+    let idempotency_key = params
+        .idempotency_key
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    // Fill in the session's model/thinking defaults when the caller omits them
+    let requested = SessionDefaults {
+        model: params.model.clone(),
+        thinking: params.thinking.clone(),
+    };
+    let resolved = match &params.session_key {
+        Some(session_key) => sessions.resolve_defaults(session_key, requested).await?,
+        None => ResolvedDefaults {
+            defaults: requested,
+            model_changed: false,
+        },
+    };
 
     // Build request params - always use "message" field (not "input")
     // Only include thinking field if it has a value (Gateway rejects null)
@@ -1652,11 +2805,23 @@ pub async fn send_message(
         "sessionKey": params.session_key,
         "idempotencyKey": idempotency_key,
     });
-    
-    // Add thinking only if present
-    if let Some(ref thinking) = params.thinking {
+
+    // Add thinking only if enabled ("off" is the remembered "no thinking")
+    if let Some(thinking) = resolved
+        .defaults
+        .thinking
+        .as_deref()
+        .filter(|t| *t != "off")
+    {
         base_params["thinking"] = serde_json::json!(thinking);
     }
+
+    // Route to the agent chosen for this session, if any
+    if let Some(session_key) = &params.session_key {
+        if let Some(agent_id) = sessions.agent_for(session_key).await {
+            base_params["agentId"] = serde_json::json!(agent_id);
+        }
+    }
     
     // Attachments go in separate "attachments" array per Gateway protocol
     let request_params = if params.attachments.is_empty() {
@@ -1694,8 +2859,48 @@ pub async fn send_message(
 
     let json = serde_json::to_string(&request).map_err(|e| e.to_string())?;
 
-    // If reconnecting, queue the message
-    if matches!(connection_state, ConnectionState::Reconnecting { .. }) {
+    // Remember which window sent on this session so its run events go back there
+    if let Some(session_key) = &params.session_key {
+        state
+            .inner
+            .session_windows
+            .lock()
+            .await
+            .insert(session_key.clone(), window.label().to_string());
+
+        // Reasoning runs can stay silent for a while before the first token
+        let reasoning = thinking_enabled(resolved.defaults.thinking.as_deref())
+            || match &resolved.defaults.model {
+                Some(model) => state.inner.reasoning_models.lock().await.contains(model),
+                None => false,
+            };
+        state.inner.session_stream_timeouts.lock().await.insert(
+            session_key.clone(),
+            stream_timeout(params.stream_timeout_secs, reasoning),
+        );
+    }
+
+    // Offline mode: always queue to disk, sent when the user goes back online
+    if status == SendStatus::QueuedOffline {
+        let queued = offline.enqueue(request_id.clone(), json).await?;
+        let _ = app.emit(
+            "gateway:offline_queued",
+            serde_json::json!({ "requestId": request_id, "queued": queued }),
+        );
+        return Ok(SendOutcome { request_id, status });
+    }
+
+    // If (re)connecting, queue the message
+    if status == SendStatus::QueuedReconnecting {
+        journal::record_send(
+            &app,
+            &request_id,
+            &idempotency_key,
+            params.session_key.as_deref(),
+            &params.message,
+        )
+        .await;
+
         let mut queue = state.inner.message_queue.lock().await;
 
         // CRITICAL-3: Enforce max queue size (drop oldest messages)
@@ -1704,18 +2909,49 @@ pub async fn send_message(
             queue.pop_front();
         }
 
-        queue.push_back(QueuedMessage::new(request_id.clone(), json));
-        return Ok(request_id);
+        queue.push_back(QueuedMessage::new(request_id.clone(), "chat.send", json));
+        return Ok(SendOutcome { request_id, status });
+    }
+
+    // The model is a session setting: switch it before sending
+    if let (true, Some(session_key), Some(model)) = (
+        resolved.model_changed,
+        &params.session_key,
+        &resolved.defaults.model,
+    ) {
+        apply_session_model(&sessions, &state.inner, session_key, model).await;
     }
 
+    // Journal first, so a crash once the frame is out can't lose track of it
+    journal::record_send(
+        &app,
+        &request_id,
+        &idempotency_key,
+        params.session_key.as_deref(),
+        &params.message,
+    )
+    .await;
+
     // Try to send
     let sender = state.inner.sender.lock().await;
-    let sender = sender.as_ref().ok_or("Not connected")?;
+    let sent_bytes = json.len();
+    let sent = match sender.as_ref() {
+        Some(sender) => sender
+            .send(OutgoingMessage::Raw(json.clone()))
+            .await
+            .map_err(|e| CommandError::network(e.to_string())),
+        None => Err(CommandError::not_connected()),
+    };
+    if let Err(e) = sent {
+        journal::record_send_failed(&app, &request_id);
+        return Err(e);
+    }
 
-    sender
-        .send(OutgoingMessage::Raw(json.clone()))
-        .await
-        .map_err(|e| e.to_string())?;
+    if let (Some(session_key), Some(data_saver)) =
+        (&params.session_key, app.try_state::<DataSaverState>())
+    {
+        data_saver.record_session_sent(session_key, sent_bytes);
+    }
 
     // Track for dedup
     state
@@ -1725,12 +2961,21 @@ pub async fn send_message(
         .await
         .insert(request_id.clone());
 
-    Ok(request_id)
+    Ok(SendOutcome { request_id, status })
+}
+
+/// How a message sent now would be handled
+#[tauri::command]
+pub async fn get_send_status(
+    state: State<'_, GatewayState>,
+    offline: State<'_, OfflineState>,
+) -> Result<SendStatus, CommandError> {
+    Ok(state.send_status(offline.is_enabled().await).await)
 }
 
 /// Get connection status
 #[tauri::command]
-pub async fn get_connection_status(state: State<'_, GatewayState>) -> Result<bool, String> {
+pub async fn get_connection_status(state: State<'_, GatewayState>) -> Result<bool, CommandError> {
     Ok(state.inner.connection_state.read().await.is_connected())
 }
 
@@ -1738,7 +2983,7 @@ pub async fn get_connection_status(state: State<'_, GatewayState>) -> Result<boo
 #[tauri::command]
 pub async fn get_connection_state(
     state: State<'_, GatewayState>,
-) -> Result<ConnectionState, String> {
+) -> Result<ConnectionState, CommandError> {
     Ok(state.inner.connection_state.read().await.clone())
 }
 
@@ -1746,90 +2991,541 @@ pub async fn get_connection_state(
 #[tauri::command]
 pub async fn get_connection_quality(
     state: State<'_, GatewayState>,
-) -> Result<ConnectionQuality, String> {
+) -> Result<ConnectionQuality, CommandError> {
     Ok(state.inner.health_metrics.lock().await.quality())
 }
 
-/// Request available models from Gateway
+/// Get health metrics including latency percentiles and histogram
 #[tauri::command]
-pub async fn get_models(
-    _app: AppHandle,
+pub async fn get_health_metrics(
     state: State<'_, GatewayState>,
-) -> Result<Vec<ModelInfo>, String> {
-    let sender_guard = state.inner.sender.lock().await;
-    let sender = sender_guard.as_ref().ok_or("Not connected to Gateway")?;
+) -> Result<HealthSnapshot, CommandError> {
+    Ok(state.health_snapshot().await)
+}
 
-    let request = GatewayRequest::new("models.list", Some(serde_json::json!({})));
-    let request_id = request.id.clone();
+/// Get the step-by-step timeline of the most recent connection attempt
+#[tauri::command]
+pub async fn get_last_connection_trace(
+    state: State<'_, GatewayState>,
+) -> Result<Option<ConnectionTrace>, CommandError> {
+    Ok(state.last_connection_trace())
+}
 
-    let (response_tx, response_rx) = oneshot::channel();
+/// How long a probe waits for the challenge and the hello-ok
+const PROBE_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Result of probing a Gateway
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProbeReport {
+    /// The WebSocket upgrade succeeded
+    pub reachable: bool,
+    /// The Gateway sent `connect.challenge`
+    #[serde(rename = "protocolOk")]
+    pub protocol_ok: bool,
+    /// Whether the token was accepted; `None` when no token was given
+    pub authenticated: Option<bool>,
+    /// URL that answered (may be upgraded to wss://)
+    #[serde(rename = "usedUrl")]
+    pub used_url: Option<String>,
+    #[serde(rename = "protocolSwitched")]
+    pub protocol_switched: bool,
+    /// Compatibility issues reported in the hello-ok
+    #[serde(rename = "compatIssues")]
+    pub compat_issues: Vec<String>,
+    pub error: Option<String>,
+    /// Step-by-step timeline of the probe
+    pub trace: Option<ConnectionTrace>,
+}
 
-    {
-        let mut pending = state.inner.pending_requests.lock().await;
-        pending.insert(
-            request_id.clone(),
-            PendingRequest {
-                sender: response_tx,
-                created_at: Instant::now(),
-                timeout: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
-            },
-        );
+/// Outcome of the probe's protocol handshake
+enum ProbeHandshake {
+    /// Challenge received, no token to authenticate with
+    Challenged,
+    /// hello-ok with its payload
+    Accepted(Option<serde_json::Value>),
+    /// The connect request was refused
+    Rejected(String),
+}
+
+/// Wait for the challenge and, with a token, answer it
+async fn probe_handshake(
+    ws_stream: &mut tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >,
+    token: Option<&str>,
+    tracer: &ConnectionTracer,
+    upgraded_at: Instant,
+) -> Result<ProbeHandshake, String> {
+    let mut connect_id = None;
+    while let Some(msg) = ws_stream.next().await {
+        let text = match msg {
+            Ok(WsMessage::Text(text)) => text.to_string(),
+            Ok(WsMessage::Close(frame)) => {
+                let reason = frame.map(|f| f.reason.to_string()).unwrap_or_default();
+                return Err(format!("Connection closed by the Gateway {}", reason)
+                    .trim()
+                    .to_string());
+            }
+            Ok(_) => continue,
+            Err(e) => return Err(e.to_string()),
+        };
+        match validate_frame(&text).map_err(|e| e.to_string())? {
+            ValidatedFrame::Event { event, .. } if event == "connect.challenge" => {
+                tracer.ok(TraceStep::Challenge, upgraded_at, None);
+                let Some(token) = token else {
+                    return Ok(ProbeHandshake::Challenged);
+                };
+                let request = connect_request(token);
+                connect_id = Some(request.id.clone());
+                let json = serde_json::to_string(&request).map_err(|e| e.to_string())?;
+                ws_stream
+                    .send(WsMessage::Text(json.into()))
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+            ValidatedFrame::Response {
+                id,
+                ok,
+                payload,
+                error,
+            } if connect_id.as_deref() == Some(id.as_str()) => {
+                if ok {
+                    tracer.ok(TraceStep::HelloOk, upgraded_at, None);
+                    return Ok(ProbeHandshake::Accepted(payload));
+                }
+                let message = error
+                    .map(|e| format!("[{}] {}", e.code, e.message))
+                    .unwrap_or_else(|| "Connect refused".to_string());
+                tracer.failed(TraceStep::HelloOk, upgraded_at, &message);
+                return Ok(ProbeHandshake::Rejected(message));
+            }
+            _ => {}
+        }
     }
+    Err("Connection closed".to_string())
+}
 
-    let json = serde_json::to_string(&request).map_err(|e| e.to_string())?;
-    sender
-        .send(OutgoingMessage::Raw(json))
-        .await
-        .map_err(|e| e.to_string())?;
+/// Probe a Gateway without touching the connection
+async fn probe(app: &AppHandle, url: &str, token: Option<&str>) -> ProbeReport {
+    let tracer = ConnectionTracer::new(url);
+    let mut report = ProbeReport::default();
+    let resolver = dns_resolver(app);
+
+    let error = match try_connect_with_fallback(url, &tracer, &resolver).await {
+        Err(e) => Some(e.to_string()),
+        Ok((mut ws_stream, used_url, protocol_switched)) => {
+            report.reachable = true;
+            report.used_url = Some(used_url);
+            report.protocol_switched = protocol_switched;
+
+            let upgraded_at = Instant::now();
+            let handshake = tokio::time::timeout(
+                PROBE_HANDSHAKE_TIMEOUT,
+                probe_handshake(&mut ws_stream, token, &tracer, upgraded_at),
+            )
+            .await;
+            let _ = ws_stream.close(None).await;
+
+            match handshake {
+                Ok(Ok(ProbeHandshake::Challenged)) => {
+                    report.protocol_ok = true;
+                    None
+                }
+                Ok(Ok(ProbeHandshake::Accepted(payload))) => {
+                    report.protocol_ok = true;
+                    report.authenticated = Some(true);
+                    if let Some(warning) = payload.as_ref().and_then(check_gateway_compat) {
+                        report.compat_issues = warning.issues;
+                    }
+                    None
+                }
+                Ok(Ok(ProbeHandshake::Rejected(message))) => {
+                    report.protocol_ok = true;
+                    report.authenticated = Some(false);
+                    Some(message)
+                }
+                Ok(Err(e)) => Some(e),
+                Err(_) => {
+                    let challenged = tracer.snapshot().is_some_and(|trace| {
+                        trace.steps.iter().any(|s| s.step == TraceStep::Challenge)
+                    });
+                    let step = if challenged {
+                        TraceStep::HelloOk
+                    } else {
+                        TraceStep::Challenge
+                    };
+                    tracer.failed(step, upgraded_at, "Timed out");
+                    report.protocol_ok = challenged;
+                    Some(format!(
+                        "No answer from the Gateway within {}s",
+                        PROBE_HANDSHAKE_TIMEOUT.as_secs()
+                    ))
+                }
+            }
+        }
+    };
 
-    drop(sender_guard);
+    tracer.finish(error.clone());
+    report.error = error;
+    report.trace = tracer.snapshot();
+    report
+}
 
-    match tokio::time::timeout(
-        Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
-        response_rx,
-    )
-    .await
+/// Check that a Gateway is reachable and speaks the protocol, without
+/// storing credentials or changing the connection state
+///
+/// With a token, the connect handshake is completed to check the token too.
+#[tauri::command]
+pub async fn probe_gateway(
+    app: AppHandle,
+    url: String,
+    token: Option<String>,
+) -> Result<ProbeReport, CommandError> {
+    let token = token.filter(|t| !t.is_empty());
+    if let Some(token) = &token {
+        redact::register_secret(token);
+    }
+    log_protocol_error("PROBE", &format!("Probing {}", url));
+    Ok(probe(&app, &url, token.as_deref()).await)
+}
+
+impl GatewayState {
+    pub(crate) async fn health_snapshot(&self) -> HealthSnapshot {
+        self.inner.health_metrics.lock().await.snapshot()
+    }
+
+    pub(crate) async fn connection_state(&self) -> ConnectionState {
+        self.inner.connection_state.read().await.clone()
+    }
+
+    /// How a message sent now would be handled
+    pub(crate) async fn send_status(&self, offline: bool) -> SendStatus {
+        let state = self.inner.connection_state.read().await.clone();
+        let quality = self.inner.health_metrics.lock().await.quality();
+        SendStatus::assess(&state, quality, offline)
+    }
+
+    pub(crate) fn last_connection_trace(&self) -> Option<ConnectionTrace> {
+        self.inner
+            .connection_trace
+            .lock()
+            .ok()?
+            .as_ref()?
+            .snapshot()
+    }
+
+    /// Host of the Gateway last connected to (e.g. for status displays)
+    pub(crate) async fn gateway_host(&self) -> Option<String> {
+        let credentials = self.inner.stored_credentials.lock().await;
+        let url = url::Url::parse(&credentials.as_ref()?.url).ok()?;
+        url.host_str().map(str::to_string)
+    }
+
+    /// Number of runs in the registry
+    pub(crate) async fn active_run_count(&self) -> usize {
+        self.inner.active_runs.lock().await.len()
+    }
+
+    /// Start time and origin of every active run
+    pub(crate) async fn run_progress(&self) -> Vec<RunProgress> {
+        self.inner
+            .active_runs
+            .lock()
+            .await
+            .iter()
+            .map(|(run_id, run)| RunProgress {
+                run_id: run_id.clone(),
+                started_at: run.started_at,
+                window: run.window.clone(),
+            })
+            .collect()
+    }
+}
+
+impl GatewayState {
+    /// Models from the last successful `models.list`
+    pub(crate) async fn known_models(&self) -> Vec<ModelInfo> {
+        self.inner.known_models.lock().await.clone()
+    }
+}
+
+/// Request available models from Gateway
+#[tauri::command]
+pub async fn get_models(
+    _app: AppHandle,
+    state: State<'_, GatewayState>,
+) -> Result<Vec<ModelInfo>, CommandError> {
+    match state
+        .request("models.list", Some(serde_json::json!({})))
+        .await
     {
-        Ok(Ok(response)) => {
-            if response.ok == Some(true) {
-                if let Some(payload) = response.payload {
-                    if let Some(models_val) = payload.get("models") {
-                        if let Ok(models) =
-                            serde_json::from_value::<Vec<ModelInfo>>(models_val.clone())
-                        {
-                            return Ok(models);
-                        }
-                    }
+        Ok(payload) => {
+            if let Some(models_val) = payload.as_ref().and_then(|p| p.get("models")) {
+                if let Ok(models) = serde_json::from_value::<Vec<ModelInfo>>(models_val.clone()) {
+                    // Remembered so reasoning runs get a longer stream timeout
+                    *state.inner.reasoning_models.lock().await = models
+                        .iter()
+                        .filter(|m| m.reasoning == Some(true))
+                        .map(|m| m.id.clone())
+                        .collect();
+                    *state.inner.known_models.lock().await = models.clone();
+                    return Ok(models);
                 }
-            } else if let Some(error) = response.error {
-                return Err(format!("Gateway error: {}", error.message));
             }
             // No models available from gateway - return empty list
             Ok(Vec::new())
         }
-        Ok(Err(_)) => {
-            state
-                .inner
-                .pending_requests
-                .lock()
-                .await
-                .remove(&request_id);
-            // Request failed - return empty list
+        Err(GatewayError::Network { .. }) => Err(CommandError::not_connected()),
+        Err(e @ GatewayError::Gateway { .. }) | Err(e @ GatewayError::Auth { .. }) => Err(e.into()),
+        // Request failed or timed out - return empty list
+        Err(_) => Ok(Vec::new()),
+    }
+}
+
+/// List the agents available on the Gateway
+#[tauri::command]
+pub async fn agents_list(state: State<'_, GatewayState>) -> Result<Vec<AgentInfo>, CommandError> {
+    match state
+        .request("agents.list", Some(serde_json::json!({})))
+        .await
+    {
+        Ok(payload) => {
+            if let Some(agents_val) = payload.as_ref().and_then(|p| p.get("agents")) {
+                if let Ok(agents) = serde_json::from_value::<Vec<AgentInfo>>(agents_val.clone()) {
+                    return Ok(agents);
+                }
+            }
+            // Gateway without multiple agents
             Ok(Vec::new())
         }
-        Err(_) => {
-            state
-                .inner
-                .pending_requests
-                .lock()
-                .await
-                .remove(&request_id);
-            // Timeout - return empty list
-            Ok(Vec::new())
+        Err(GatewayError::Network { .. }) => Err(CommandError::not_connected()),
+        Err(e @ GatewayError::Gateway { .. }) | Err(e @ GatewayError::Auth { .. }) => Err(e.into()),
+        Err(_) => Ok(Vec::new()),
+    }
+}
+
+/// Most requests accepted in one batch
+const MAX_BATCH_REQUESTS: usize = 20;
+
+/// Read-only methods a batch may send
+///
+/// Anything that changes state (sends, aborts, session patches, token
+/// rotation) goes through its own command, which keeps the run registry,
+/// session defaults and keychain in step.
+const BATCHABLE_METHODS: &[&str] = &[
+    "models.list",
+    "sessions.list",
+    "agents.list",
+    "status",
+    "config.get",
+    "usage.status",
+];
+
+/// One request of a `gateway_batch`
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchRequest {
+    /// Caller's key for the response; defaults to the request's index
+    #[serde(default)]
+    pub id: Option<String>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Option<serde_json::Value>,
+}
+
+/// Response to one request of a batch
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchResponse {
+    pub id: String,
+    pub method: String,
+    pub ok: bool,
+    pub payload: Option<serde_json::Value>,
+    pub error: Option<CommandError>,
+}
+
+/// Check a batch before sending any of it
+fn validate_batch(requests: &[BatchRequest]) -> Result<(), String> {
+    if requests.is_empty() {
+        return Err("A batch needs at least one request".to_string());
+    }
+    if requests.len() > MAX_BATCH_REQUESTS {
+        return Err(format!(
+            "A batch takes at most {} requests",
+            MAX_BATCH_REQUESTS
+        ));
+    }
+    let mut ids = HashSet::new();
+    for request in requests {
+        if request.method.trim().is_empty() {
+            return Err("Every request needs a method".to_string());
+        }
+        if !BATCHABLE_METHODS.contains(&request.method.as_str()) {
+            return Err(format!("{} can't be batched", request.method));
+        }
+        if let Some(id) = &request.id {
+            if !ids.insert(id.as_str()) {
+                return Err(format!("Duplicate request ID: {}", id));
+            }
+        }
+    }
+    Ok(())
+}
+
+impl GatewayState {
+    /// Send requests concurrently and collect their responses in the order
+    /// of `requests`
+    ///
+    /// Each request is correlated and retried on its own, so one failure
+    /// doesn't fail the others.
+    pub(crate) async fn batch(&self, requests: Vec<BatchRequest>) -> Vec<BatchResponse> {
+        let responses = requests
+            .into_iter()
+            .enumerate()
+            .map(|(index, request)| async move {
+                let id = request.id.unwrap_or_else(|| index.to_string());
+                let params = request.params.or_else(|| Some(serde_json::json!({})));
+                match self.request(&request.method, params).await {
+                    Ok(payload) => BatchResponse {
+                        id,
+                        method: request.method,
+                        ok: true,
+                        payload,
+                        error: None,
+                    },
+                    Err(e) => BatchResponse {
+                        id,
+                        method: request.method,
+                        ok: false,
+                        payload: None,
+                        error: Some(e.into()),
+                    },
+                }
+            });
+        futures_util::future::join_all(responses).await
+    }
+}
+
+/// Send several Gateway requests at once and return all their responses
+///
+/// Saves round trips for screens that need several lists (models, sessions,
+/// agents) together. Responses come back in request order; a failed request
+/// carries its error instead of failing the batch.
+#[tauri::command]
+pub async fn gateway_batch(
+    state: State<'_, GatewayState>,
+    requests: Vec<BatchRequest>,
+) -> Result<Vec<BatchResponse>, CommandError> {
+    validate_batch(&requests).map_err(CommandError::validation)?;
+    if !state.inner.connection_state.read().await.is_connected() {
+        return Err(CommandError::not_connected());
+    }
+    Ok(state.batch(requests).await)
+}
+
+impl GatewayState {
+    /// Keychain entry of the connection's token
+    async fn token_key(&self) -> String {
+        keychain::gateway_token_key(&self.inner.token_profile.lock().await)
+    }
+
+    /// Store a replacement token under the active profile's keychain entry
+    /// and in the reconnect credentials
+    pub(crate) async fn replace_token(&self, token: &str) -> Result<(), CommandError> {
+        keychain::keychain_set(
+            keychain::SERVICE_NAME.to_string(),
+            self.token_key().await,
+            token.to_string(),
+        )
+        .await?;
+        if let Some(credentials) = self.inner.stored_credentials.lock().await.as_mut() {
+            credentials.token = token.to_string();
         }
+        Ok(())
     }
 }
 
+/// Result of a token rotation (the new token itself stays in the backend)
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenRotation {
+    /// When the replacement token was stored
+    #[serde(rename = "rotatedAt")]
+    pub rotated_at: DateTime<Utc>,
+    /// Whether the Gateway confirmed revocation of the old token
+    #[serde(rename = "oldTokenRevoked")]
+    pub old_token_revoked: bool,
+}
+
+/// Rotate the Gateway token
+///
+/// Asks the Gateway for a replacement token, stores it in the keychain and
+/// the reconnect credentials, then revokes the old token. If the new token
+/// cannot be stored it is revoked instead, so the old token stays the only
+/// valid one.
+#[tauri::command]
+pub async fn rotate_token(
+    app: AppHandle,
+    state: State<'_, GatewayState>,
+) -> Result<TokenRotation, CommandError> {
+    let old_token = state
+        .inner
+        .stored_credentials
+        .lock()
+        .await
+        .as_ref()
+        .map(|c| c.token.clone())
+        .ok_or_else(CommandError::not_connected)?;
+
+    let payload = state
+        .request("token.rotate", Some(serde_json::json!({})))
+        .await?;
+    let new_token = payload
+        .as_ref()
+        .and_then(|p| p.get("token"))
+        .and_then(|t| t.as_str())
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| CommandError::protocol("Gateway did not return a token"))?
+        .to_string();
+
+    if let Err(e) = state.replace_token(&new_token).await {
+        // Roll back: the old token remains in use
+        let _ = state
+            .request(
+                "token.revoke",
+                Some(serde_json::json!({ "token": new_token })),
+            )
+            .await;
+        return Err(CommandError::internal(format!(
+            "Failed to store new token: {}",
+            e
+        )));
+    }
+
+    token_expiry::record_expiry(&app, payload.as_ref().and_then(token_expiry::parse_expiry)).await;
+
+    let old_token_revoked = match state
+        .request(
+            "token.revoke",
+            Some(serde_json::json!({ "token": old_token })),
+        )
+        .await
+    {
+        Ok(_) => true,
+        Err(e) => {
+            log_protocol_error("Token rotation", &format!("Old token not revoked: {}", e));
+            false
+        }
+    };
+
+    let _ = app.emit(
+        "gateway:token_rotated",
+        serde_json::json!({ "oldTokenRevoked": old_token_revoked }),
+    );
+
+    Ok(TokenRotation {
+        rotated_at: Utc::now(),
+        old_token_revoked,
+    })
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -1874,7 +3570,7 @@ mod tests {
             scopes: vec!["operator.read".to_string(), "operator.write".to_string()],
             caps: vec![],
             auth: AuthInfo {
-                token: "test-token".to_string(),
+                token: Redacted("test-token".to_string()),
             },
             locale: "en-US".to_string(),
             user_agent: "moltz/0.1.0".to_string(),
@@ -1898,11 +3594,76 @@ mod tests {
         assert!(state.inner.message_queue.lock().await.is_empty());
     }
 
+    #[test]
+    fn test_validate_batch() {
+        let request = |id: Option<&str>, method: &str| BatchRequest {
+            id: id.map(str::to_string),
+            method: method.to_string(),
+            params: None,
+        };
+        assert!(
+            validate_batch(&[request(None, "models.list"), request(None, "agents.list")]).is_ok()
+        );
+        assert!(validate_batch(&[]).is_err());
+        assert!(validate_batch(&[request(None, "chat.send")]).is_err());
+        assert!(validate_batch(&[request(None, "token.rotate")]).is_err());
+        assert!(validate_batch(&[request(None, "sessions.patch")]).is_err());
+        assert!(validate_batch(&[
+            request(Some("a"), "models.list"),
+            request(Some("a"), "sessions.list")
+        ])
+        .is_err());
+        let too_many: Vec<BatchRequest> = (0..=MAX_BATCH_REQUESTS)
+            .map(|_| request(None, "models.list"))
+            .collect();
+        assert!(validate_batch(&too_many).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_batch_not_connected_fails_each_request() {
+        let state = GatewayState::default();
+        let responses = state
+            .batch(vec![
+                BatchRequest {
+                    id: Some("models".to_string()),
+                    method: "models.list".to_string(),
+                    params: None,
+                },
+                BatchRequest {
+                    id: None,
+                    method: "agents.list".to_string(),
+                    params: None,
+                },
+            ])
+            .await;
+        let ids: Vec<&str> = responses.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["models", "1"]);
+        assert!(responses.iter().all(|r| !r.ok && r.error.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_abort_window_runs_leaves_other_windows() {
+        let state = GatewayState::default();
+        {
+            let mut runs = state.inner.active_runs.lock().await;
+            for (run_id, window) in [("a", Some("main")), ("b", Some("quickinput")), ("c", None)] {
+                let run = ActiveRun::new(None, window.map(str::to_string), Duration::from_secs(60));
+                runs.insert(run_id.to_string(), run);
+            }
+        }
+
+        state.abort_window_runs("main").await;
+
+        let runs = state.inner.active_runs.lock().await;
+        let remaining: Vec<&str> = runs.keys().map(String::as_str).collect();
+        assert_eq!(remaining, ["b"]);
+    }
+
     #[tokio::test]
     async fn test_message_queue() {
         let state = GatewayState::default();
 
-        let msg = QueuedMessage::new("test-1".to_string(), "{}".to_string());
+        let msg = QueuedMessage::new("test-1".to_string(), "chat.send", "{}".to_string());
         state.inner.message_queue.lock().await.push_back(msg);
 
         assert_eq!(state.inner.message_queue.lock().await.len(), 1);
@@ -0,0 +1,229 @@
+//! Guest mode: a time-boxed session that leaves nothing behind
+//!
+//! While guest mode is on:
+//! - store files are not written ([`crate::store::write_atomic`] keeps
+//!   changes in memory), and neither are the send journal, archives or OS
+//!   recent documents
+//! - secrets written or deleted only shadow the keychain in memory; existing
+//!   ones can still be read, so the configured Gateway keeps working
+//! - token usage and Quick Ask prompts are not recorded
+//! - commands that write files outside the app's stores (exports, Obsidian
+//!   notes, workspace downloads, drag-out files, the discovery hosts file)
+//!   are refused with an error ([`ensure_inactive`])
+//! - the frontend keeps conversations and settings in memory only
+//!
+//! It ends when its time is up or the user ends it: the shadowed secrets and
+//! the drag-out temp files are wiped and the app restarts, dropping every
+//! in-memory state. Quitting wipes the same way, and nothing else was
+//! written to begin with. Guest mode is never persisted, so a launch always
+//! starts outside it.
+
+use crate::drag_out;
+use crate::error::CommandError;
+use crate::redact::Redacted;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+const DEFAULT_MINUTES: u32 = 60;
+const MIN_MINUTES: u32 = 5;
+const MAX_MINUTES: u32 = 8 * 60;
+
+/// A running guest session
+struct GuestSession {
+    started_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    /// Secrets written (Some) or deleted (None) during the session, keyed by
+    /// (service, key)
+    secrets: HashMap<(String, String), Option<Redacted<String>>>,
+}
+
+impl GuestSession {
+    fn shadowed(&self, service: &str, key: &str) -> Option<Option<String>> {
+        self.secrets
+            .get(&(service.to_string(), key.to_string()))
+            .map(|value| value.as_ref().map(|v| v.0.clone()))
+    }
+
+    fn shadow(&mut self, service: &str, key: &str, value: Option<&str>) {
+        self.secrets.insert(
+            (service.to_string(), key.to_string()),
+            value.map(|v| Redacted(v.to_string())),
+        );
+    }
+}
+
+static SESSION: LazyLock<Mutex<Option<GuestSession>>> = LazyLock::new(|| Mutex::new(None));
+
+fn session() -> MutexGuard<'static, Option<GuestSession>> {
+    SESSION
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Guest mode state, for the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct GuestStatus {
+    pub active: bool,
+    #[serde(rename = "startedAt")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(rename = "endsAt")]
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+fn status() -> GuestStatus {
+    let session = session();
+    GuestStatus {
+        active: session.is_some(),
+        started_at: session.as_ref().map(|s| s.started_at),
+        ends_at: session.as_ref().map(|s| s.ends_at),
+    }
+}
+
+/// Whether guest mode is on
+pub fn active() -> bool {
+    session().is_some()
+}
+
+/// Refuse `action` while guest mode is on, for commands that write files
+/// the store layer doesn't cover
+pub fn ensure_inactive(action: &str) -> Result<(), CommandError> {
+    if active() {
+        return Err(CommandError::validation(format!(
+            "{} is not available in guest mode",
+            action
+        )));
+    }
+    Ok(())
+}
+
+/// A secret as shadowed by guest mode: None if the session hasn't touched
+/// it, Some(None) if it was deleted
+pub(crate) fn shadowed_secret(service: &str, key: &str) -> Option<Option<String>> {
+    session().as_ref()?.shadowed(service, key)
+}
+
+/// Record a secret write (or a delete, with None) in memory instead of the
+/// keychain; false outside guest mode
+pub(crate) fn shadow_secret(service: &str, key: &str, value: Option<&str>) -> bool {
+    match session().as_mut() {
+        Some(session) => {
+            session.shadow(service, key, value);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Drop what the session kept: shadowed secrets and drag-out temp files
+pub fn wipe() {
+    if session().take().is_none() {
+        return;
+    }
+    let _ = std::fs::remove_dir_all(drag_out::temp_root());
+    log::info!("[Guest] Guest mode ended, session data wiped");
+}
+
+/// End guest mode and restart the app to drop every in-memory state
+fn end(app: &AppHandle) {
+    wipe();
+    let _ = app.emit("guest:ended", ());
+    app.request_restart();
+}
+
+/// Session length, validated
+fn duration_minutes(minutes: Option<u32>) -> Result<u32, String> {
+    let minutes = minutes.unwrap_or(DEFAULT_MINUTES);
+    if !(MIN_MINUTES..=MAX_MINUTES).contains(&minutes) {
+        return Err(format!(
+            "Guest mode lasts between {} and {} minutes",
+            MIN_MINUTES, MAX_MINUTES
+        ));
+    }
+    Ok(minutes)
+}
+
+/// Turn guest mode on for `minutes` (an hour by default)
+#[tauri::command]
+pub async fn start_guest_mode(
+    app: AppHandle,
+    minutes: Option<u32>,
+) -> Result<GuestStatus, CommandError> {
+    let minutes = duration_minutes(minutes).map_err(CommandError::validation)?;
+    {
+        let mut session = session();
+        if session.is_some() {
+            return Err(CommandError::validation("Guest mode is already on"));
+        }
+        let started_at = Utc::now();
+        *session = Some(GuestSession {
+            started_at,
+            ends_at: started_at + chrono::Duration::minutes(minutes.into()),
+            secrets: HashMap::new(),
+        });
+    }
+    log::info!("[Guest] Guest mode on for {} minutes", minutes);
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(u64::from(minutes) * 60)).await;
+        if active() {
+            log::info!("[Guest] Time is up");
+            end(&handle);
+        }
+    });
+
+    let status = status();
+    let _ = app.emit("guest:started", &status);
+    Ok(status)
+}
+
+/// End guest mode now; the app restarts
+#[tauri::command]
+pub async fn end_guest_mode(app: AppHandle) -> Result<(), CommandError> {
+    if !active() {
+        return Err(CommandError::validation("Guest mode is not on"));
+    }
+    end(&app);
+    Ok(())
+}
+
+/// Whether guest mode is on, and until when
+#[tauri::command]
+pub async fn get_guest_status() -> Result<GuestStatus, CommandError> {
+    Ok(status())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_minutes() {
+        assert_eq!(duration_minutes(None), Ok(DEFAULT_MINUTES));
+        assert_eq!(duration_minutes(Some(MIN_MINUTES)), Ok(MIN_MINUTES));
+        assert!(duration_minutes(Some(MIN_MINUTES - 1)).is_err());
+        assert!(duration_minutes(Some(MAX_MINUTES + 1)).is_err());
+    }
+
+    #[test]
+    fn test_shadowed_secrets() {
+        // A local session: the global one would affect other tests' writes
+        let mut session = GuestSession {
+            started_at: Utc::now(),
+            ends_at: Utc::now(),
+            secrets: HashMap::new(),
+        };
+        session.shadow("svc", "key", Some("value"));
+        session.shadow("svc", "gone", None);
+        assert_eq!(
+            session.shadowed("svc", "key"),
+            Some(Some("value".to_string()))
+        );
+        assert_eq!(session.shadowed("svc", "gone"), Some(None));
+        assert_eq!(session.shadowed("svc", "other"), None);
+    }
+}
@@ -0,0 +1,227 @@
+//! Incident reports for repeated connection failures
+//!
+//! A connection that keeps failing tends to get reported long after the
+//! fact, when the trace and logs that would explain it are gone. Once
+//! [`INCIDENT_AFTER_FAILED_CYCLES`] reconnection cycles in a row have given
+//! up (every attempt failed, with no successful connection in between), the
+//! diagnostics bundle (connection state, health, the last connection trace,
+//! supervised tasks) and the recent log lines are saved as an incident file
+//! in the logs directory, scrubbed of credentials like any diagnostics
+//! export. A notification points at it; the frontend shows the details and
+//! can open a prefilled bug report.
+//!
+//! Incident files count toward the logs' size cap, so storage maintenance
+//! prunes old ones.
+
+use crate::diagnostics::{self, DiagnosticsBundle};
+use crate::error::CommandError;
+use crate::gateway::GatewayState;
+use crate::redact;
+use crate::{notifications, storage};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Failed reconnection cycles in a row that trigger an incident report
+pub const INCIDENT_AFTER_FAILED_CYCLES: u32 = 2;
+
+/// Subdirectory of the logs directory holding incident files
+const INCIDENTS_DIR: &str = "incidents";
+
+/// Incident files listed, newest first
+const MAX_LISTED: usize = 20;
+
+/// What an incident file is about
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncidentSummary {
+    pub id: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "failedCycles")]
+    pub failed_cycles: u32,
+    /// Why the last connection attempt failed
+    #[serde(rename = "lastError")]
+    pub last_error: Option<String>,
+}
+
+/// Contents of an incident file
+#[derive(Debug, Clone, Serialize)]
+struct Incident {
+    #[serde(flatten)]
+    summary: IncidentSummary,
+    diagnostics: DiagnosticsBundle,
+    #[serde(rename = "recentLogs")]
+    recent_logs: Vec<String>,
+}
+
+fn incidents_dir(app: &AppHandle) -> PathBuf {
+    storage::log_dir(app)
+        .unwrap_or_else(|| std::env::temp_dir().join("moltz"))
+        .join(INCIDENTS_DIR)
+}
+
+/// Path of an incident file; IDs are generated names, never paths
+fn incident_path(app: &AppHandle, id: &str) -> Result<PathBuf, CommandError> {
+    let valid =
+        id.starts_with("incident-") && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !valid {
+        return Err(CommandError::validation("Unknown incident"));
+    }
+    Ok(incidents_dir(app).join(format!("{}.json", id)))
+}
+
+/// Capture an incident report and tell the user about it (not in guest mode)
+pub async fn capture(app: &AppHandle, failed_cycles: u32) {
+    // Nothing would be saved
+    if crate::guest::active() {
+        return;
+    }
+    let Some(gateway) = app.try_state::<GatewayState>() else {
+        return;
+    };
+    let diagnostics = diagnostics::collect(&gateway).await;
+    let created_at = Utc::now();
+    let summary = IncidentSummary {
+        id: format!("incident-{}", created_at.format("%Y%m%d-%H%M%S")),
+        created_at,
+        failed_cycles,
+        last_error: diagnostics
+            .connection_trace
+            .as_ref()
+            .and_then(|trace| trace.error.clone()),
+    };
+    let incident = Incident {
+        summary: summary.clone(),
+        diagnostics,
+        recent_logs: redact::recent_log_lines(),
+    };
+
+    let path = incidents_dir(app).join(format!("{}.json", summary.id));
+    let written = serde_json::to_value(&incident)
+        .map_err(|e| e.to_string())
+        .and_then(|mut value| {
+            redact::redact_json(&mut value);
+            crate::store::save(&path, &value)
+        });
+    if let Err(e) = written {
+        log::error!("[Incident] Failed to save {}: {}", path.display(), e);
+        return;
+    }
+    log::info!(
+        "[Incident] Saved {} after {} failed reconnection cycles",
+        path.display(),
+        failed_cycles
+    );
+    let _ = app.emit("incident:created", &summary);
+
+    let body = format!(
+        "Reconnecting failed {} times in a row. A report with the details was saved.",
+        failed_cycles
+    );
+    let handle = app.clone();
+    let id = summary.id.clone();
+    notifications::show_clickable(app, "Connection keeps failing", &body, None, move || {
+        if let Some(window) = handle.get_webview_window("main") {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        let _ = handle.emit("incident:open", id);
+    })
+    .await;
+}
+
+/// Text of a prefilled bug report for an incident
+fn report_body(summary: &IncidentSummary) -> String {
+    format!(
+        "**Repeated connection failures**\n\n\
+         - App: {} ({}/{})\n\
+         - Failed reconnection cycles: {}\n\
+         - Last error: {}\n\
+         - Captured: {}\n\n\
+         Please attach `{}.json` from the app's `logs/{}` folder \
+         (credentials are already removed).",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        summary.failed_cycles,
+        summary.last_error.as_deref().unwrap_or("unknown"),
+        summary.created_at.to_rfc3339(),
+        summary.id,
+        INCIDENTS_DIR
+    )
+}
+
+/// Saved incident reports, newest first
+#[tauri::command]
+pub async fn list_incidents(app: AppHandle) -> Result<Vec<IncidentSummary>, CommandError> {
+    let Ok(entries) = std::fs::read_dir(incidents_dir(&app)) else {
+        return Ok(Vec::new());
+    };
+    let mut incidents: Vec<IncidentSummary> = entries
+        .flatten()
+        .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+        .filter_map(|text| serde_json::from_str(&text).ok())
+        .collect();
+    incidents.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    incidents.truncate(MAX_LISTED);
+    Ok(incidents)
+}
+
+/// Full contents of an incident report
+#[tauri::command]
+pub async fn get_incident(app: AppHandle, id: String) -> Result<serde_json::Value, CommandError> {
+    let text = std::fs::read_to_string(incident_path(&app, &id)?)
+        .map_err(|_| CommandError::validation("Unknown incident"))?;
+    serde_json::from_str(&text).map_err(|e| CommandError::internal(e.to_string()))
+}
+
+/// URL of a prefilled bug report for an incident
+#[tauri::command]
+pub async fn incident_report_url(app: AppHandle, id: String) -> Result<String, CommandError> {
+    let text = std::fs::read_to_string(incident_path(&app, &id)?)
+        .map_err(|_| CommandError::validation("Unknown incident"))?;
+    let summary: IncidentSummary =
+        serde_json::from_str(&text).map_err(|e| CommandError::internal(e.to_string()))?;
+    let url = url::Url::parse_with_params(
+        &format!("{}/issues/new", env!("CARGO_PKG_REPOSITORY")),
+        &[
+            ("title", "Connection keeps failing".to_string()),
+            ("body", report_body(&summary)),
+        ],
+    )
+    .map_err(|e| CommandError::internal(e.to_string()))?;
+    Ok(url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_body() {
+        let summary = IncidentSummary {
+            id: "incident-20261017-101500".to_string(),
+            created_at: Utc::now(),
+            failed_cycles: 2,
+            last_error: Some("TLS handshake failed".to_string()),
+        };
+        let body = report_body(&summary);
+        assert!(body.contains("Failed reconnection cycles: 2"));
+        assert!(body.contains("Last error: TLS handshake failed"));
+        assert!(body.contains("`incident-20261017-101500.json`"));
+
+        // The summary reads back from a full incident file
+        let file = serde_json::json!({
+            "id": summary.id,
+            "createdAt": summary.created_at,
+            "failedCycles": 2,
+            "lastError": "TLS handshake failed",
+            "recentLogs": ["[Gateway] Connecting"],
+        });
+        assert_eq!(
+            serde_json::from_value::<IncidentSummary>(file).unwrap(),
+            summary
+        );
+    }
+}
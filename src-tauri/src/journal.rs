@@ -0,0 +1,505 @@
+//! Write-ahead journal of in-flight chat state
+//!
+//! Sent messages and runs that are streaming live only in memory, so a
+//! crash mid-stream used to lose track of them silently. Every send, run
+//! start and run end is appended to a small journal (one JSON record per
+//! line, synced to disk); a send waits for its record to be on disk before
+//! the frame goes out, and is struck off again if it can't be sent. Once
+//! nothing is in flight the journal is truncated again.
+//!
+//! Records are written by a dedicated task, so no caller blocks on disk I/O.
+//! Message text is sealed (AES-256-GCM, key in the keychain) like the Quick
+//! Ask history; the rest of a record is IDs and timestamps.
+//!
+//! On launch the journal is replayed: messages that never got a run and runs
+//! that never ended are kept as a recovery report, which the frontend offers
+//! to resume (reopen the conversation) or retry (resend with the original
+//! idempotency key, so the Gateway drops it if it did arrive).
+//!
+//! Messages queued in offline mode are not journaled; the offline queue is
+//! already on disk.
+
+use crate::error::CommandError;
+use crate::gateway::{self, ChatParams, GatewayState};
+use crate::guest;
+use crate::keychain;
+use crate::offline::OfflineState;
+use crate::protocol::SendOutcome;
+use crate::sessions::SessionState;
+use crate::store::{self, SEAL_KEY_LEN};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Runtime, State};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{mpsc, oneshot};
+
+/// Journal file name in the app data directory
+const JOURNAL_FILE: &str = "inflight.journal";
+
+/// Keychain key holding the key message text is sealed with
+const JOURNAL_KEY_NAME: &str = "journal_key";
+
+/// A message handed to the Gateway (or queued until reconnected)
+///
+/// In the journal file `message` holds the sealed text, base64-encoded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingSend {
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+    #[serde(rename = "idempotencyKey")]
+    pub idempotency_key: String,
+    #[serde(rename = "sessionKey")]
+    pub session_key: Option<String>,
+    pub message: String,
+    #[serde(rename = "sentAt")]
+    pub sent_at: DateTime<Utc>,
+}
+
+/// A run that was streaming
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveRunRecord {
+    #[serde(rename = "runId")]
+    pub run_id: String,
+    #[serde(rename = "sessionKey")]
+    pub session_key: Option<String>,
+    #[serde(rename = "startedAt")]
+    pub started_at: DateTime<Utc>,
+}
+
+/// One journal record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JournalEntry {
+    Sent(PendingSend),
+    RunStarted(ActiveRunRecord),
+    RunEnded {
+        #[serde(rename = "runId")]
+        run_id: String,
+    },
+    /// A journaled message that could not be handed to the Gateway
+    SendFailed {
+        #[serde(rename = "requestId")]
+        request_id: String,
+    },
+}
+
+/// What is in flight after applying the journal so far
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct InFlight {
+    /// Messages no run has started for yet, oldest first
+    pub unsent: Vec<PendingSend>,
+    /// Runs that have not ended
+    pub runs: Vec<ActiveRunRecord>,
+}
+
+impl InFlight {
+    pub fn is_empty(&self) -> bool {
+        self.unsent.is_empty() && self.runs.is_empty()
+    }
+
+    fn apply(&mut self, entry: JournalEntry) {
+        match entry {
+            JournalEntry::Sent(send) => self.unsent.push(send),
+            JournalEntry::RunStarted(run) => {
+                // The run answers the oldest message sent on its session
+                if let Some(index) = self
+                    .unsent
+                    .iter()
+                    .position(|send| send.session_key == run.session_key)
+                {
+                    self.unsent.remove(index);
+                }
+                self.runs.retain(|existing| existing.run_id != run.run_id);
+                self.runs.push(run);
+            }
+            JournalEntry::RunEnded { run_id } => self.runs.retain(|run| run.run_id != run_id),
+            JournalEntry::SendFailed { request_id } => {
+                self.unsent.retain(|send| send.request_id != request_id)
+            }
+        }
+    }
+
+    /// Replay journal text; a record cut short by a crash is skipped
+    fn replay(text: &str) -> Self {
+        let mut in_flight = Self::default();
+        for entry in text
+            .lines()
+            .filter_map(|line| serde_json::from_str::<JournalEntry>(line).ok())
+        {
+            in_flight.apply(entry);
+        }
+        in_flight
+    }
+}
+
+/// Seal message text for the journal file
+fn seal_message(key: &[u8; SEAL_KEY_LEN], message: &str) -> Result<String, String> {
+    store::seal(key, message.as_bytes())
+        .map(|sealed| base64::engine::general_purpose::STANDARD.encode(sealed))
+}
+
+/// Open message text sealed by [`seal_message`]
+fn open_message(key: &[u8; SEAL_KEY_LEN], sealed: &str) -> Option<String> {
+    let sealed = base64::engine::general_purpose::STANDARD
+        .decode(sealed)
+        .ok()?;
+    String::from_utf8(store::open(key, &sealed).ok()?).ok()
+}
+
+/// A record for the writer, with an optional acknowledgement once it is on
+/// disk
+type WriteRequest = (JournalEntry, Option<oneshot::Sender<()>>);
+
+/// Journal state managed by Tauri
+pub struct JournalState {
+    writer: mpsc::UnboundedSender<WriteRequest>,
+    /// What was in flight when the previous session ended (message text
+    /// still sealed)
+    recovered: Mutex<InFlight>,
+}
+
+impl JournalState {
+    /// Replay the previous session's journal and start a fresh one
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, JOURNAL_FILE);
+        let recovered = std::fs::read_to_string(&path)
+            .map(|text| InFlight::replay(&text))
+            .unwrap_or_default();
+        if !recovered.is_empty() {
+            log::info!(
+                "[Journal] Previous session left {} unsent message(s) and {} unfinished run(s)",
+                recovered.unsent.len(),
+                recovered.runs.len()
+            );
+        }
+        let file = open_truncated(&path);
+        let (writer, requests) = mpsc::unbounded_channel();
+        tauri::async_runtime::spawn(write_journal(path, file, requests));
+        Self {
+            writer,
+            recovered: Mutex::new(recovered),
+        }
+    }
+
+    fn submit(&self, entry: JournalEntry, written: Option<oneshot::Sender<()>>) {
+        let _ = self.writer.send((entry, written));
+    }
+
+    fn recovered(&self) -> InFlight {
+        self.recovered
+            .lock()
+            .map(|recovered| recovered.clone())
+            .unwrap_or_default()
+    }
+
+    /// Take a recovered message out of the report
+    fn take_unsent(&self, request_id: &str) -> Option<PendingSend> {
+        let mut recovered = self.recovered.lock().ok()?;
+        let index = recovered
+            .unsent
+            .iter()
+            .position(|send| send.request_id == request_id)?;
+        Some(recovered.unsent.remove(index))
+    }
+
+    fn dismiss(&self) {
+        if let Ok(mut recovered) = self.recovered.lock() {
+            *recovered = InFlight::default();
+        }
+    }
+}
+
+/// The journal writer: applies records in order, appends and syncs them,
+/// truncating the file once nothing is in flight
+async fn write_journal(
+    path: PathBuf,
+    file: Option<File>,
+    mut requests: mpsc::UnboundedReceiver<WriteRequest>,
+) {
+    let mut file = file.map(tokio::fs::File::from_std);
+    let mut in_flight = InFlight::default();
+    let mut key: Option<[u8; SEAL_KEY_LEN]> = None;
+
+    while let Some((mut entry, written)) = requests.recv().await {
+        in_flight.apply(entry.clone());
+        if in_flight.is_empty() {
+            file = tokio::task::spawn_blocking({
+                let path = path.clone();
+                move || open_truncated(&path)
+            })
+            .await
+            .ok()
+            .flatten()
+            .map(tokio::fs::File::from_std);
+        } else if !guest::active() {
+            // Guest sends are never written down
+            let sealed = match &mut entry {
+                JournalEntry::Sent(send) => {
+                    if key.is_none() {
+                        key = keychain::store_key(JOURNAL_KEY_NAME).await.ok();
+                    }
+                    match key.as_ref().map(|key| seal_message(key, &send.message)) {
+                        Some(Ok(sealed)) => {
+                            send.message = sealed;
+                            true
+                        }
+                        _ => {
+                            log::error!("[Journal] Failed to seal a message, not journaled");
+                            false
+                        }
+                    }
+                }
+                _ => true,
+            };
+            if let (true, Some(file)) = (sealed, file.as_mut()) {
+                if let Err(e) = append(file, &entry).await {
+                    log::error!("[Journal] Failed to write: {}", e);
+                }
+            }
+        }
+        if let Some(written) = written {
+            let _ = written.send(());
+        }
+    }
+}
+
+/// Append one record and sync it to disk
+async fn append(file: &mut tokio::fs::File, entry: &JournalEntry) -> Result<(), String> {
+    let mut line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    line.push('\n');
+    file.write_all(line.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+    file.sync_data().await.map_err(|e| e.to_string())
+}
+
+/// Create (or empty) the journal file, opened for appending
+fn open_truncated(path: &Path) -> Option<File> {
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    match OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(path)
+    {
+        Ok(file) => Some(file),
+        Err(e) => {
+            log::error!("[Journal] Failed to open {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Queue a record without waiting for it to be written
+fn submit(app: &AppHandle, entry: JournalEntry) {
+    if let Some(state) = app.try_state::<JournalState>() {
+        state.submit(entry, None);
+    }
+}
+
+/// Record a message about to be sent (or queued until reconnected), and
+/// wait until the record is on disk
+pub async fn record_send(
+    app: &AppHandle,
+    request_id: &str,
+    idempotency_key: &str,
+    session_key: Option<&str>,
+    message: &str,
+) {
+    let Some(state) = app.try_state::<JournalState>() else {
+        return;
+    };
+    let (done, written) = oneshot::channel();
+    state.submit(
+        JournalEntry::Sent(PendingSend {
+            request_id: request_id.to_string(),
+            idempotency_key: idempotency_key.to_string(),
+            session_key: session_key.map(str::to_string),
+            message: message.to_string(),
+            sent_at: Utc::now(),
+        }),
+        Some(done),
+    );
+    let _ = written.await;
+}
+
+/// Strike off a recorded message that could not be sent after all
+pub fn record_send_failed(app: &AppHandle, request_id: &str) {
+    submit(
+        app,
+        JournalEntry::SendFailed {
+            request_id: request_id.to_string(),
+        },
+    );
+}
+
+/// Record the first chat event of a run
+pub fn record_run_started(app: &AppHandle, run_id: &str, session_key: Option<&str>) {
+    submit(
+        app,
+        JournalEntry::RunStarted(ActiveRunRecord {
+            run_id: run_id.to_string(),
+            session_key: session_key.map(str::to_string),
+            started_at: Utc::now(),
+        }),
+    );
+}
+
+/// Record that a run finished, failed, was aborted or timed out
+pub fn record_run_ended(app: &AppHandle, run_id: &str) {
+    submit(
+        app,
+        JournalEntry::RunEnded {
+            run_id: run_id.to_string(),
+        },
+    );
+}
+
+/// Messages and runs the previous session left in flight
+///
+/// Messages whose text can't be opened (e.g. the key was removed from the
+/// keychain) are left out.
+#[tauri::command]
+pub async fn get_interrupted_state(
+    state: State<'_, JournalState>,
+) -> Result<InFlight, CommandError> {
+    let mut in_flight = state.recovered();
+    if in_flight.unsent.is_empty() {
+        return Ok(in_flight);
+    }
+    let key = keychain::store_key(JOURNAL_KEY_NAME).await?;
+    in_flight.unsent = in_flight
+        .unsent
+        .into_iter()
+        .filter_map(|mut send| {
+            send.message = open_message(&key, &send.message)?;
+            Some(send)
+        })
+        .collect();
+    Ok(in_flight)
+}
+
+/// Resend a message the previous session left unsent, with its original
+/// idempotency key
+#[tauri::command]
+pub async fn retry_interrupted_send(
+    app: AppHandle,
+    window: tauri::Window,
+    journal: State<'_, JournalState>,
+    gateway_state: State<'_, GatewayState>,
+    offline: State<'_, OfflineState>,
+    sessions: State<'_, SessionState>,
+    request_id: String,
+) -> Result<SendOutcome, CommandError> {
+    let send = journal
+        .take_unsent(&request_id)
+        .ok_or_else(|| CommandError::validation("No interrupted message with that ID"))?;
+    let key = keychain::store_key(JOURNAL_KEY_NAME).await?;
+    let message = open_message(&key, &send.message)
+        .ok_or_else(|| CommandError::internal("The interrupted message can't be read"))?;
+    let params = ChatParams {
+        message,
+        session_key: send.session_key,
+        model: None,
+        thinking: None,
+        attachments: Vec::new(),
+        stream_timeout_secs: None,
+        idempotency_key: Some(send.idempotency_key),
+    };
+    gateway::send_message(app, window, gateway_state, offline, sessions, params).await
+}
+
+/// Forget what the previous session left in flight
+#[tauri::command]
+pub async fn dismiss_interrupted_state(state: State<'_, JournalState>) -> Result<(), CommandError> {
+    state.dismiss();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send(request_id: &str, session_key: &str) -> JournalEntry {
+        JournalEntry::Sent(PendingSend {
+            request_id: request_id.to_string(),
+            idempotency_key: format!("idem-{}", request_id),
+            session_key: Some(session_key.to_string()),
+            message: "Hello".to_string(),
+            sent_at: Utc::now(),
+        })
+    }
+
+    fn run_started(run_id: &str, session_key: &str) -> JournalEntry {
+        JournalEntry::RunStarted(ActiveRunRecord {
+            run_id: run_id.to_string(),
+            session_key: Some(session_key.to_string()),
+            started_at: Utc::now(),
+        })
+    }
+
+    #[test]
+    fn test_in_flight_apply() {
+        let mut in_flight = InFlight::default();
+        in_flight.apply(send("r1", "a"));
+        in_flight.apply(send("r2", "b"));
+        in_flight.apply(send("r3", "a"));
+
+        // A run answers the oldest message on its session
+        in_flight.apply(run_started("run-1", "a"));
+        let unsent: Vec<&str> = in_flight
+            .unsent
+            .iter()
+            .map(|send| send.request_id.as_str())
+            .collect();
+        assert_eq!(unsent, ["r2", "r3"]);
+        assert_eq!(in_flight.runs.len(), 1);
+
+        in_flight.apply(JournalEntry::RunEnded {
+            run_id: "run-1".to_string(),
+        });
+        assert!(in_flight.runs.is_empty());
+        assert!(!in_flight.is_empty());
+
+        // A message that could not be sent is struck off
+        in_flight.apply(JournalEntry::SendFailed {
+            request_id: "r2".to_string(),
+        });
+        assert_eq!(in_flight.unsent.len(), 1);
+        assert_eq!(in_flight.unsent[0].request_id, "r3");
+    }
+
+    #[test]
+    fn test_sealed_message() {
+        let key = [7u8; SEAL_KEY_LEN];
+        let sealed = seal_message(&key, "Summarize the report").unwrap();
+        assert!(!sealed.contains("Summarize"));
+        assert_eq!(
+            open_message(&key, &sealed),
+            Some("Summarize the report".to_string())
+        );
+        assert_eq!(open_message(&[8u8; SEAL_KEY_LEN], &sealed), None);
+        assert_eq!(open_message(&key, "not sealed"), None);
+    }
+
+    #[test]
+    fn test_replay_skips_torn_record() {
+        let lines: Vec<String> = [send("r1", "a"), run_started("run-1", "a")]
+            .iter()
+            .map(|entry| serde_json::to_string(entry).unwrap())
+            .collect();
+        let text = format!("{}\n{}\n{{\"type\":\"run_en", lines[0], lines[1]);
+
+        let in_flight = InFlight::replay(&text);
+        assert!(in_flight.unsent.is_empty());
+        assert_eq!(in_flight.runs.len(), 1);
+        assert_eq!(in_flight.runs[0].run_id, "run-1");
+        assert!(InFlight::replay("").is_empty());
+    }
+}
@@ -4,43 +4,325 @@
 //! - macOS: Keychain
 //! - Windows: Credential Manager
 //! - Linux: Secret Service (libsecret)
+//!
+//! In portable mode an encrypted file in the portable folder stands in for
+//! it (see [`crate::portable`]). In guest mode, writes and deletes only
+//! shadow it in memory (see [`crate::guest`]).
+//!
+//! Values passing through are registered for log redaction.
+//!
+//! Reads are cached in an in-memory session so a normal app session touches
+//! the OS store (and, on macOS, its authorization prompt) once. The session
+//! is cleared when the screen locks, the machine sleeps, or it goes unused
+//! for [`SESSION_IDLE_TIMEOUT`].
 
+use crate::error::CommandError;
+use crate::redact::{self, Redacted};
+use crate::{guest, portable, store};
+use base64::Engine;
 use keyring::Entry;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Keychain service name (matches the frontend's keychain.ts)
+pub const SERVICE_NAME: &str = "com.moltz.client";
+
+/// Keychain key holding the Gateway token
+pub const GATEWAY_TOKEN_KEY: &str = "gateway_token";
+
+/// Keychain key holding the Gateway token of a connection profile
+///
+/// The default profile ("" or "default") uses [`GATEWAY_TOKEN_KEY`], which is
+/// what the frontend and reconnects read.
+pub fn gateway_token_key(profile: &str) -> String {
+    match profile.trim() {
+        "" | "default" => GATEWAY_TOKEN_KEY.to_string(),
+        profile => format!("{}.{}", GATEWAY_TOKEN_KEY, profile),
+    }
+}
+
+/// Cached values are dropped after this long without use
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(4 * 60 * 60);
+
+/// How often the session checks for screen lock, sleep and idle timeout
+const SESSION_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Wall-clock time passing this much faster than monotonic time means the
+/// machine was asleep (monotonic clocks stop during sleep)
+const SLEEP_DETECTION_SLACK: Duration = Duration::from_secs(60);
+
+/// A cached keychain value
+struct CachedSecret {
+    value: Redacted<String>,
+    last_used: Instant,
+}
+
+/// In-memory keychain session, keyed by (service, key)
+#[derive(Default)]
+struct KeychainSession {
+    entries: Mutex<HashMap<(String, String), CachedSecret>>,
+}
+
+impl KeychainSession {
+    fn get(&self, service: &str, key: &str, now: Instant) -> Option<String> {
+        let mut entries = self.entries.lock().ok()?;
+        let entry = entries.get_mut(&(service.to_string(), key.to_string()))?;
+        entry.last_used = now;
+        Some(entry.value.0.clone())
+    }
+
+    fn put(&self, service: &str, key: &str, value: &str, now: Instant) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(
+                (service.to_string(), key.to_string()),
+                CachedSecret {
+                    value: Redacted(value.to_string()),
+                    last_used: now,
+                },
+            );
+        }
+    }
 
-/// Get a value from the keychain
+    fn remove(&self, service: &str, key: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(&(service.to_string(), key.to_string()));
+        }
+    }
+
+    /// Drop every cached value; returns how many were dropped
+    fn clear(&self) -> usize {
+        self.entries
+            .lock()
+            .map(|mut entries| entries.drain().count())
+            .unwrap_or(0)
+    }
+
+    /// Drop values unused for [`SESSION_IDLE_TIMEOUT`]
+    fn expire_idle(&self, now: Instant) -> usize {
+        let Ok(mut entries) = self.entries.lock() else {
+            return 0;
+        };
+        let before = entries.len();
+        entries.retain(|_, entry| now.duration_since(entry.last_used) < SESSION_IDLE_TIMEOUT);
+        before - entries.len()
+    }
+}
+
+/// The keychain session (shared by commands and internal callers)
+static SESSION: LazyLock<KeychainSession> = LazyLock::new(Default::default);
+
+/// Whether the machine slept between two checks
+fn slept(wall_elapsed: Duration, monotonic_elapsed: Duration) -> bool {
+    wall_elapsed > monotonic_elapsed + SLEEP_DETECTION_SLACK
+}
+
+#[cfg(target_os = "linux")]
+fn screen_locked() -> bool {
+    let session = std::env::var("XDG_SESSION_ID").unwrap_or_else(|_| "self".to_string());
+    std::process::Command::new("loginctl")
+        .args(["show-session", &session, "-p", "LockedHint", "--value"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).trim() == "yes")
+}
+
+#[cfg(target_os = "macos")]
+fn screen_locked() -> bool {
+    std::process::Command::new("ioreg")
+        .args(["-n", "Root", "-d1"])
+        .output()
+        .is_ok_and(|output| {
+            String::from_utf8_lossy(&output.stdout).contains("\"CGSSessionScreenIsLocked\"=Yes")
+        })
+}
+
+#[cfg(target_os = "windows")]
+fn screen_locked() -> bool {
+    use windows_sys::Win32::System::StationsAndDesktops::{
+        CloseDesktop, OpenInputDesktop, DESKTOP_SWITCHDESKTOP,
+    };
+
+    // The input desktop can't be opened while the lock screen owns it
+    // SAFETY: the handle is closed right after the check
+    unsafe {
+        let desktop = OpenInputDesktop(0, 0, DESKTOP_SWITCHDESKTOP);
+        if desktop.is_null() {
+            return true;
+        }
+        CloseDesktop(desktop);
+    }
+    false
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn screen_locked() -> bool {
+    false
+}
+
+/// Start clearing the keychain session on screen lock, sleep and idle timeout
+pub fn start_session_monitor() {
+    tauri::async_runtime::spawn(async move {
+        let mut last_wall = SystemTime::now();
+        let mut last_monotonic = Instant::now();
+        loop {
+            tokio::time::sleep(SESSION_CHECK_INTERVAL).await;
+
+            let now = Instant::now();
+            let wall_elapsed = SystemTime::now()
+                .duration_since(last_wall)
+                .unwrap_or_default();
+            let locked = tokio::task::spawn_blocking(screen_locked)
+                .await
+                .unwrap_or(false);
+            let reason = if locked {
+                Some("screen locked")
+            } else if slept(wall_elapsed, now.duration_since(last_monotonic)) {
+                Some("system woke from sleep")
+            } else {
+                None
+            };
+
+            match reason {
+                Some(reason) => {
+                    let cleared = SESSION.clear();
+                    if cleared > 0 {
+                        log::info!(
+                            "[Keychain] Session cleared ({}): {} values",
+                            reason,
+                            cleared
+                        );
+                    }
+                }
+                None => {
+                    let expired = SESSION.expire_idle(now);
+                    if expired > 0 {
+                        log::info!("[Keychain] Session expired {} idle values", expired);
+                    }
+                }
+            }
+
+            last_wall = SystemTime::now();
+            last_monotonic = Instant::now();
+        }
+    });
+}
+
+/// Read a value from the OS keychain, or the encrypted file when portable
+fn read_secret(service: &str, key: &str) -> Result<String, keyring::Error> {
+    if let Some(shadowed) = guest::shadowed_secret(service, key) {
+        return shadowed.ok_or(keyring::Error::NoEntry);
+    }
+    match portable::secret_file() {
+        Some(secrets) => secrets.get(service, key),
+        None => Entry::new(service, key)?.get_password(),
+    }
+}
+
+fn write_secret(service: &str, key: &str, value: &str) -> Result<(), keyring::Error> {
+    if guest::shadow_secret(service, key, Some(value)) {
+        return Ok(());
+    }
+    match portable::secret_file() {
+        Some(secrets) => secrets.set(service, key, value),
+        None => Entry::new(service, key)?.set_password(value),
+    }
+}
+
+fn delete_secret(service: &str, key: &str) -> Result<(), keyring::Error> {
+    if guest::shadow_secret(service, key, None) {
+        return Ok(());
+    }
+    match portable::secret_file() {
+        Some(secrets) => secrets.delete(service, key),
+        None => Entry::new(service, key)?.delete_credential(),
+    }
+}
+
+/// Get a value from the keychain session, or the keychain on a miss
 /// Uses spawn_blocking to prevent UI freezing on macOS
 #[tauri::command]
-pub async fn keychain_get(service: String, key: String) -> Result<String, String> {
+pub async fn keychain_get(service: String, key: String) -> Result<String, CommandError> {
+    if let Some(value) = SESSION.get(&service, &key, Instant::now()) {
+        return Ok(value);
+    }
     tokio::task::spawn_blocking(move || {
-        let entry = Entry::new(&service, &key).map_err(|e| e.to_string())?;
-        entry.get_password().map_err(|e| e.to_string())
+        let value = read_secret(&service, &key)?;
+        redact::register_secret(&value);
+        SESSION.put(&service, &key, &value, Instant::now());
+        Ok(value)
     })
     .await
-    .map_err(|e| e.to_string())?
+    .map_err(|e| CommandError::internal(e.to_string()))?
 }
 
 /// Set a value in the keychain
 /// Uses spawn_blocking to prevent UI freezing on macOS
 #[tauri::command]
-pub async fn keychain_set(service: String, key: String, value: String) -> Result<(), String> {
+pub async fn keychain_set(service: String, key: String, value: String) -> Result<(), CommandError> {
+    redact::register_secret(&value);
     tokio::task::spawn_blocking(move || {
-        let entry = Entry::new(&service, &key).map_err(|e| e.to_string())?;
-        entry.set_password(&value).map_err(|e| e.to_string())
+        write_secret(&service, &key, &value)?;
+        SESSION.put(&service, &key, &value, Instant::now());
+        Ok(())
     })
     .await
-    .map_err(|e| e.to_string())?
+    .map_err(|e| CommandError::internal(e.to_string()))?
 }
 
 /// Delete a value from the keychain
 /// Uses spawn_blocking to prevent UI freezing on macOS
 #[tauri::command]
-pub async fn keychain_delete(service: String, key: String) -> Result<(), String> {
-    tokio::task::spawn_blocking(move || {
-        let entry = Entry::new(&service, &key).map_err(|e| e.to_string())?;
-        entry.delete_credential().map_err(|e| e.to_string())
+pub async fn keychain_delete(service: String, key: String) -> Result<(), CommandError> {
+    SESSION.remove(&service, &key);
+    tokio::task::spawn_blocking(move || Ok(delete_secret(&service, &key)?))
+        .await
+        .map_err(|e| CommandError::internal(e.to_string()))?
+}
+
+/// Whether a value is stored
+///
+/// Errors when the keychain itself can't be reached (locked, no Secret
+/// Service, denied access). A value found is cached like `keychain_get`, so
+/// the frontend's read that usually follows doesn't prompt again.
+pub async fn contains(service: &str, key: &str) -> Result<bool, CommandError> {
+    if SESSION.get(service, key, Instant::now()).is_some() {
+        return Ok(true);
+    }
+    let (service, key) = (service.to_string(), key.to_string());
+    tokio::task::spawn_blocking(move || match read_secret(&service, &key) {
+        Ok(value) => {
+            redact::register_secret(&value);
+            SESSION.put(&service, &key, &value, Instant::now());
+            Ok(true)
+        }
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e.into()),
     })
     .await
-    .map_err(|e| e.to_string())?
+    .map_err(|e| CommandError::internal(e.to_string()))?
+}
+
+/// Key for a sealed store file, created and kept in the keychain on first
+/// use
+pub async fn store_key(name: &str) -> Result<[u8; store::SEAL_KEY_LEN], CommandError> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    if contains(SERVICE_NAME, name).await? {
+        let encoded = keychain_get(SERVICE_NAME.to_string(), name.to_string()).await?;
+        return engine
+            .decode(encoded)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| CommandError::internal(format!("Corrupt key {}", name)));
+    }
+    let key: [u8; store::SEAL_KEY_LEN] = rand::random();
+    keychain_set(
+        SERVICE_NAME.to_string(),
+        name.to_string(),
+        engine.encode(key),
+    )
+    .await?;
+    log::info!("[Keychain] Created store key {}", name);
+    Ok(key)
 }
 
 #[cfg(test)]
@@ -60,7 +342,9 @@ mod tests {
             return;
         }
 
-        // Get — verify round-trip works before asserting
+        // Get — verify round-trip works before asserting (bypassing the
+        // session cache so the credential store itself is read)
+        SESSION.clear();
         let result = keychain_get(service.clone(), key.clone()).await;
         if result.is_err() {
             eprintln!("Skipping keychain test: credential store not readable");
@@ -77,4 +361,44 @@ mod tests {
         let result = keychain_get(service, key).await;
         assert!(result.is_err(), "Expected error after delete");
     }
+
+    #[test]
+    fn test_gateway_token_key() {
+        assert_eq!(gateway_token_key(""), GATEWAY_TOKEN_KEY);
+        assert_eq!(gateway_token_key("default"), GATEWAY_TOKEN_KEY);
+        assert_eq!(gateway_token_key(" work "), "gateway_token.work");
+    }
+
+    #[test]
+    fn test_session_cache_and_idle_expiry() {
+        let session = KeychainSession::default();
+        let start = Instant::now();
+        session.put("svc", "token", "secret-value", start);
+        assert_eq!(
+            session
+                .get("svc", "token", start + Duration::from_secs(60))
+                .as_deref(),
+            Some("secret-value")
+        );
+        assert_eq!(session.get("svc", "other", start), None);
+
+        // Reads keep the value alive
+        assert_eq!(session.expire_idle(start + SESSION_IDLE_TIMEOUT), 0);
+        assert_eq!(
+            session.expire_idle(start + Duration::from_secs(60) + SESSION_IDLE_TIMEOUT),
+            1
+        );
+        assert_eq!(session.get("svc", "token", start), None);
+
+        session.put("svc", "token", "secret-value", start);
+        assert_eq!(session.clear(), 1);
+    }
+
+    #[test]
+    fn test_sleep_detection() {
+        let tick = SESSION_CHECK_INTERVAL;
+        assert!(!slept(tick, tick));
+        assert!(!slept(tick + Duration::from_secs(5), tick));
+        assert!(slept(tick + Duration::from_secs(3600), tick));
+    }
 }
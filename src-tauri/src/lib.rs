@@ -6,18 +6,90 @@
 //! - Gateway discovery on local network
 //! - Native system integration (notifications, window management)
 //! - Native menu bar with standard macOS/Windows conventions
+//! - Session key management backed by a local JSON store
 
+mod accessibility;
+mod archive;
+mod chaos;
+mod connection_trace;
+mod data_saver;
+mod deep_link;
+mod diagnostics;
 mod discovery;
+mod dns;
+mod drag_out;
+mod email;
+mod endpoints;
+mod error;
+mod events;
+mod file_watch;
+mod folder_attach;
+mod frame_tap;
 mod gateway;
+mod guest;
+mod incident;
+mod journal;
 mod keychain;
 mod menu;
+mod message_store;
+mod notifications;
+mod obsidian;
+mod offline;
+mod palette;
+mod plaintext;
+mod portable;
+mod power;
+mod presets;
+mod prompt_history;
 mod protocol;
+mod quick_ask;
+mod quota;
+mod recent_files;
+mod redact;
+mod reminders;
+mod replay;
+mod repo_context;
+mod response_cache;
+mod scheduler;
+mod selection;
+mod sessions;
+mod settings_file;
+mod shortcuts;
+mod startup;
+mod storage;
+mod store;
+mod supervisor;
+mod system_events;
+mod system_integration;
+mod test_message;
+mod token_expiry;
 mod tray;
 mod updater;
+#[cfg(target_os = "windows")]
+mod windows_toast;
+mod workspace_files;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let mut builder = tauri::Builder::default()
+    // Every log line, ours or a dependency's, goes through the scrubber
+    redact::init_logging();
+
+    // Before the builder: the webview picks up its data folder from the
+    // environment when it starts
+    portable::init();
+
+    let mut builder = tauri::Builder::default();
+
+    // Registered first: a second launch (a moltz:// link or a Windows toast
+    // clicked while the app runs) hands its arguments over and exits
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    {
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            deep_link::handle_forwarded_args(app, args);
+        }));
+    }
+
+    builder = builder
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
@@ -35,6 +107,37 @@ pub fn run() {
             use tauri::Manager;
             app.manage(gateway::GatewayState::default());
             app.manage(updater::UpdaterState::default());
+            app.manage(sessions::SessionState::load(app.handle()));
+            app.manage(offline::OfflineState::load(app.handle()));
+            app.manage(events::EventRegistry::default());
+            app.manage(notifications::NotificationState::default());
+            app.manage(obsidian::ObsidianState::load(app.handle()));
+            app.manage(file_watch::FileWatchState::default());
+            app.manage(power::PowerState::default());
+            app.manage(accessibility::AccessibilityState::default());
+            app.manage(chaos::ChaosState::default());
+            app.manage(frame_tap::FrameTapState::default());
+            app.manage(replay::ReplayState::default());
+            app.manage(data_saver::DataSaverState::load(app.handle()));
+            app.manage(scheduler::SchedulerState::load(app.handle()));
+            app.manage(reminders::ReminderState::load(app.handle()));
+            app.manage(presets::PresetState::load(app.handle()));
+            app.manage(palette::PaletteState::default());
+            app.manage(quota::QuotaState::load(app.handle()));
+            app.manage(startup::StartupState::load(app.handle()));
+            app.manage(token_expiry::TokenExpiryState::load(app.handle()));
+            app.manage(workspace_files::WorkspaceFilesState::default());
+            app.manage(quick_ask::QuickAskState::default());
+            app.manage(response_cache::ResponseCacheState::load(app.handle()));
+            app.manage(prompt_history::PromptHistoryState::load(app.handle()));
+            app.manage(shortcuts::ShortcutStatusState::default());
+            app.manage(archive::ArchiveState::load(app.handle()));
+            app.manage(dns::DnsState::load(app.handle()));
+            app.manage(endpoints::EndpointState::load(app.handle()));
+            app.manage(message_store::MessageStore::load(app.handle()));
+            app.manage(storage::StorageState::load(app.handle()));
+            app.manage(system_events::SystemEventsState::load(app.handle()));
+            app.manage(journal::JournalState::load(app.handle()));
 
             // Build and set native menu bar (macOS only - Windows uses custom titlebar)
             #[cfg(target_os = "macos")]
@@ -46,18 +149,50 @@ pub fn run() {
             #[cfg(desktop)]
             {
                 // Setup system tray
-                tray::setup_tray(app.handle())?;
+                tray::setup_tray(app.handle());
+                tray::start_status_monitor(app.handle());
+
+                // Backend global shortcuts (ask about selection)
+                shortcuts::setup_shortcuts(app.handle())?;
 
                 // Ensure quickinput window is hidden on startup
                 // (window_state plugin might restore it as visible)
                 if let Some(quickinput) = app.get_webview_window("quickinput") {
                     let _ = quickinput.hide();
                 }
+
+                // Toasts launch moltz:// links, so the scheme must be registered
+                #[cfg(target_os = "windows")]
+                windows_toast::register_protocol();
+
+                // moltz:// links passed on the command line (Windows / Linux)
+                deep_link::handle_launch_args(app.handle());
             }
 
+            // Environment check that routes the frontend on launch
+            startup::start_health_check(app.handle());
+
+            // Progress notifications for long-running runs
+            notifications::start_progress_monitor(app.handle().clone());
+
+            // Typed handling and notifications for cron results and channel messages
+            app.state::<events::EventRegistry>()
+                .register(std::sync::Arc::new(system_events::SystemEventHandler));
+
             // Setup updater - periodic checks and network listener
             updater::setup_periodic_checks(app.handle());
             updater::setup_network_listener(app.handle());
+            updater::setup_power_listener(app.handle());
+            power::start_power_monitor(app.handle());
+            endpoints::start_network_monitor(app.handle());
+            storage::start_maintenance(app.handle());
+            keychain::start_session_monitor();
+            accessibility::start_accessibility_monitor(app.handle());
+
+            // Fire scheduled prompts and reminders
+            scheduler::start_scheduler(app.handle());
+            reminders::start_reminder_monitor(app.handle());
+            token_expiry::start_expiry_monitor(app.handle());
 
             // Check for updates on startup (async, non-blocking)
             let app_handle = app.handle().clone();
@@ -67,13 +202,13 @@ pub fn run() {
                 tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
                 match check_for_updates(app_handle.clone()).await {
                     Ok(info) if info.available => {
-                        println!("Update available on startup: v{}", info.version);
+                        log::info!("Update available on startup: v{}", info.version);
                     }
                     Ok(_) => {
-                        println!("No updates available on startup");
+                        log::info!("No updates available on startup");
                     }
                     Err(e) => {
-                        eprintln!("Startup update check failed: {}", e);
+                        log::error!("Startup update check failed: {}", e);
                     }
                 }
             });
@@ -83,23 +218,189 @@ pub fn run() {
         .on_menu_event(|app, event| {
             menu::handle_menu_event(app, event.id().as_ref());
         })
+        .on_window_event(|window, event| {
+            if window.label() == "main" {
+                match event {
+                    // Closing the main window mid-generation should not leave
+                    // its runs consuming tokens server-side. Only its own
+                    // runs: the app may keep running (Quick Ask, tray), and
+                    // a real exit aborts everything in ExitRequested
+                    tauri::WindowEvent::CloseRequested { .. } => {
+                        use tauri::Manager;
+                        let app = window.app_handle().clone();
+                        tauri::async_runtime::spawn(async move {
+                            app.state::<gateway::GatewayState>()
+                                .abort_window_runs("main")
+                                .await;
+                        });
+                    }
+                    // Replies are read once the window is back in front
+                    tauri::WindowEvent::Focused(true) => tray::clear_unread(),
+                    _ => {}
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             gateway::connect,
+            gateway::connect_and_save,
             gateway::disconnect,
+            gateway::reconnect_now,
             gateway::send_message,
+            gateway::get_send_status,
             gateway::get_connection_status,
             gateway::get_connection_state,
             gateway::get_connection_quality,
+            gateway::get_health_metrics,
+            gateway::get_last_connection_trace,
+            gateway::probe_gateway,
             gateway::get_models,
+            gateway::gateway_batch,
+            gateway::agents_list,
+            gateway::rotate_token,
+            sessions::create_session,
+            sessions::close_session,
+            sessions::list_sessions,
+            sessions::set_session_agent,
+            sessions::get_session_defaults,
+            sessions::set_session_defaults,
+            presets::list_presets,
+            presets::save_preset,
+            presets::delete_preset,
+            presets::apply_preset,
+            presets::export_presets,
+            presets::import_presets,
+            palette::palette_query,
+            palette::set_palette_conversations,
+            palette::run_palette_action,
+            offline::set_offline_mode,
+            offline::get_offline_status,
+            events::subscribe_gateway_events,
+            events::unsubscribe_gateway_events,
+            replay::replay_recent_events,
+            startup::get_startup_health,
+            token_expiry::get_token_expiry,
+            token_expiry::set_token_expiry_warning,
+            token_expiry::start_repair,
+            token_expiry::cancel_repair,
+            workspace_files::list_workspace_files,
+            workspace_files::read_workspace_file,
+            workspace_files::download_workspace_file,
+            workspace_files::cancel_workspace_download,
+            quick_ask::enqueue_quick_ask,
+            quick_ask::list_quick_asks,
+            quick_ask::dismiss_quick_ask,
+            quick_ask::open_quick_ask,
+            response_cache::get_response_cache,
+            response_cache::set_response_cache,
+            response_cache::clear_response_cache,
+            prompt_history::get_prompt_suggestions,
+            prompt_history::get_prompt_history,
+            prompt_history::set_prompt_history,
+            prompt_history::clear_prompt_history,
+            settings_file::export_settings,
+            settings_file::import_settings,
+            archive::archive_conversation,
+            archive::list_archives,
+            archive::read_archive,
+            archive::delete_archive,
+            archive::search_archives,
+            dns::get_dns_settings,
+            dns::set_dns_settings,
+            dns::test_dns,
+            dns::clear_dns_cache,
+            endpoints::get_endpoint_settings,
+            endpoints::set_endpoint_settings,
+            endpoints::get_active_endpoint,
+            message_store::get_completed_message,
+            message_store::list_completed_messages,
+            storage::get_storage_usage,
+            storage::get_storage_settings,
+            storage::set_storage_settings,
+            storage::run_storage_maintenance,
+            system_events::get_system_notification_settings,
+            system_events::set_system_notification_settings,
+            journal::get_interrupted_state,
+            journal::retry_interrupted_send,
+            journal::dismiss_interrupted_state,
+            portable::get_portable_status,
+            guest::start_guest_mode,
+            guest::end_guest_mode,
+            guest::get_guest_status,
+            tray::get_tray_status,
+            tray::run_tray_action,
+            shortcuts::get_shortcut_status,
+            shortcuts::report_shortcut_status,
+            system_integration::reinitialize_system_integration,
+            obsidian::get_obsidian_config,
+            obsidian::set_obsidian_config,
+            obsidian::export_to_obsidian,
+            repo_context::attach_repo_context,
+            folder_attach::attach_folder,
+            power::get_power_status,
+            data_saver::get_data_saver,
+            data_saver::set_data_saver,
+            data_saver::get_data_usage,
+            data_saver::reset_data_usage,
+            chaos::get_chaos_config,
+            chaos::set_chaos_config,
+            chaos::chaos_disconnect,
+            accessibility::get_accessibility_prefs,
+            quota::get_quota_status,
+            email::compose_email,
+            drag_out::drag_out,
+            deep_link::copy_to_clipboard,
+            frame_tap::enable_frame_tap,
+            scheduler::list_scheduled_prompts,
+            scheduler::create_scheduled_prompt,
+            scheduler::update_scheduled_prompt,
+            scheduler::delete_scheduled_prompt,
+            scheduler::run_scheduled_prompt_now,
+            test_message::send_test_message,
+            reminders::set_reminder,
+            reminders::list_reminders,
+            reminders::cancel_reminder,
+            recent_files::get_recent_files,
+            recent_files::add_recent_file,
+            file_watch::watch_attachment,
+            file_watch::unwatch_attachment,
             keychain::keychain_get,
             keychain::keychain_set,
             keychain::keychain_delete,
             discovery::discover_gateways,
+            diagnostics::get_diagnostics,
+            diagnostics::export_diagnostics,
+            incident::list_incidents,
+            incident::get_incident,
+            incident::incident_report_url,
             updater::check_for_updates,
             updater::install_update,
             updater::get_update_status,
             updater::dismiss_update,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| match event {
+            tauri::RunEvent::ExitRequested { .. } => {
+                abort_active_runs(app);
+                guest::wipe();
+            }
+            // moltz:// links opened while running or used to launch (macOS)
+            #[cfg(target_os = "macos")]
+            tauri::RunEvent::Opened { urls } => {
+                for url in urls {
+                    deep_link::handle(app, url.as_str());
+                }
+            }
+            _ => {}
+        });
+}
+
+/// Abort in-flight runs before the connection is torn down
+///
+/// Blocks the event loop, so only called on exit; each abort waits at most a
+/// couple of seconds for its acknowledgement.
+fn abort_active_runs(app: &tauri::AppHandle) {
+    use tauri::Manager;
+    let state = app.state::<gateway::GatewayState>();
+    tauri::async_runtime::block_on(state.abort_all_runs());
 }
@@ -5,7 +5,7 @@
 //! - File: New Conversation, Close
 //! - Edit: Cut, Copy, Paste, Select All
 //! - View: Toggle Sidebar, Zoom
-//! - Conversation: Search, Export
+//! - Conversation: Search, Export, Email
 //! - Window: Minimize, Zoom, standard window controls
 //! - Help: Documentation, Support
 
@@ -24,6 +24,7 @@ pub mod ids {
     pub const TOGGLE_SIDEBAR: &str = "toggle_sidebar";
     pub const SEARCH: &str = "search";
     pub const EXPORT: &str = "export";
+    pub const EMAIL: &str = "email";
     pub const PREFERENCES: &str = "preferences";
     pub const QUICK_ASK: &str = "quick_ask";
 }
@@ -85,6 +86,7 @@ pub fn build_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
                 .accelerator("CmdOrCtrl+Shift+E")
                 .build(app)?,
         )
+        .item(&MenuItemBuilder::with_id(ids::EMAIL, "Email Conversation...").build(app)?)
         .build()?;
 
     // Edit menu with standard items
@@ -170,6 +172,9 @@ pub fn handle_menu_event(app: &AppHandle, event_id: &str) {
         ids::EXPORT => {
             let _ = app.emit("menu:export", ());
         }
+        ids::EMAIL => {
+            let _ = app.emit("menu:email", ());
+        }
         ids::PREFERENCES => {
             let _ = app.emit("menu:preferences", ());
         }
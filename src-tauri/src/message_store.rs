@@ -0,0 +1,169 @@
+//! Local store of completed assistant messages
+//!
+//! Replies stream in as deltas, and the whole message used to exist only in
+//! frontend state: a window reloaded mid-run lost what it had streamed. The
+//! backend now assembles each run's final message, keeps it in
+//! `messages.json` and sends its ID with `gateway:complete`, so the UI can
+//! always fetch the full content.
+//!
+//! Only the most recent [`MAX_MESSAGES`] are kept; the frontend's encrypted
+//! database stays the long-term record.
+
+use crate::error::CommandError;
+use crate::gateway::{ChatEvent, TokenUsage};
+use crate::store;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, Runtime, State};
+use tokio::sync::Mutex;
+
+/// Store file name for completed messages
+const MESSAGES_FILE: &str = "messages.json";
+
+/// Messages kept; the oldest are dropped first
+const MAX_MESSAGES: usize = 100;
+
+/// Final message of a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletedMessage {
+    pub id: String,
+    #[serde(rename = "runId")]
+    pub run_id: Option<String>,
+    #[serde(rename = "sessionKey")]
+    pub session_key: Option<String>,
+    pub content: String,
+    pub usage: Option<TokenUsage>,
+    #[serde(rename = "stopReason")]
+    pub stop_reason: Option<String>,
+    #[serde(rename = "completedAt")]
+    pub completed_at: DateTime<Utc>,
+}
+
+/// Message store managed by Tauri
+pub struct MessageStore {
+    path: PathBuf,
+    messages: Mutex<VecDeque<CompletedMessage>>,
+}
+
+impl MessageStore {
+    /// Load completed messages from the local store
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, MESSAGES_FILE);
+        let messages = store::load(&path);
+        Self {
+            path,
+            messages: Mutex::new(messages),
+        }
+    }
+
+    /// Add a message, dropping the oldest beyond [`MAX_MESSAGES`]
+    ///
+    /// The message stays available in memory if saving fails.
+    async fn insert(&self, message: CompletedMessage) -> Result<(), String> {
+        let mut messages = self.messages.lock().await;
+        messages.push_back(message);
+        while messages.len() > MAX_MESSAGES {
+            messages.pop_front();
+        }
+        store::save(&self.path, &*messages)
+    }
+
+    async fn get(&self, id: &str) -> Option<CompletedMessage> {
+        let messages = self.messages.lock().await;
+        messages.iter().find(|m| m.id == id).cloned()
+    }
+
+    /// Messages of a session, oldest first
+    async fn for_session(&self, session_key: &str) -> Vec<CompletedMessage> {
+        let messages = self.messages.lock().await;
+        messages
+            .iter()
+            .filter(|m| m.session_key.as_deref() == Some(session_key))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Store the final message of a run and return its ID
+pub async fn record_completed(
+    app: &AppHandle,
+    event: &ChatEvent,
+    content: String,
+) -> Option<String> {
+    let store = app.try_state::<MessageStore>()?;
+    let message = CompletedMessage {
+        id: uuid::Uuid::new_v4().to_string(),
+        run_id: event.run_id.clone(),
+        session_key: event.session_key.clone(),
+        content,
+        usage: event.usage.clone(),
+        stop_reason: event.stop_reason.clone(),
+        completed_at: Utc::now(),
+    };
+    let id = message.id.clone();
+    if let Err(e) = store.insert(message).await {
+        log::error!("[Messages] Failed to save completed message: {}", e);
+    }
+    Some(id)
+}
+
+/// A completed message by ID
+#[tauri::command]
+pub async fn get_completed_message(
+    store: State<'_, MessageStore>,
+    id: String,
+) -> Result<Option<CompletedMessage>, CommandError> {
+    Ok(store.get(&id).await)
+}
+
+/// Completed messages still kept for a session, oldest first
+#[tauri::command]
+pub async fn list_completed_messages(
+    store: State<'_, MessageStore>,
+    session_key: String,
+) -> Result<Vec<CompletedMessage>, CommandError> {
+    Ok(store.for_session(&session_key).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: usize, session_key: &str) -> CompletedMessage {
+        CompletedMessage {
+            id: id.to_string(),
+            run_id: None,
+            session_key: Some(session_key.to_string()),
+            content: format!("Reply {}", id),
+            usage: None,
+            stop_reason: None,
+            completed_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_insert_keeps_most_recent() {
+        let dir = std::env::temp_dir().join(format!("moltz-messages-{}", uuid::Uuid::new_v4()));
+        let store = MessageStore {
+            path: dir.join(MESSAGES_FILE),
+            messages: Mutex::new(VecDeque::new()),
+        };
+        for id in 0..MAX_MESSAGES + 5 {
+            let session_key = if id % 2 == 0 { "even" } else { "odd" };
+            store.insert(message(id, session_key)).await.unwrap();
+        }
+
+        assert!(store.get("4").await.is_none());
+        assert_eq!(store.get("5").await.unwrap().content, "Reply 5");
+        let odd = store.for_session("odd").await;
+        assert_eq!(odd.len(), MAX_MESSAGES / 2);
+        assert_eq!(odd.first().unwrap().id, "5");
+
+        // Saved to disk
+        let saved: VecDeque<CompletedMessage> = store::load(&dir.join(MESSAGES_FILE));
+        assert_eq!(saved.len(), MAX_MESSAGES);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,380 @@
+//! Native notifications posted by the backend
+//!
+//! Long-running runs get a progress notification ("Still working… 2m
+//! elapsed") that turns into a completion notification when the run ends.
+//! Update semantics differ per OS:
+//! - Linux: notifications are replaced in place (freedesktop `replaces_id`)
+//! - macOS / Windows: the notification plugin cannot update a posted
+//!   notification, so progress is posted once and followed by a separate
+//!   completion notification
+//!
+//! The completion notification summarizes the run from the final chat event:
+//! duration, output tokens and, when the run didn't simply finish its turn,
+//! why it stopped.
+
+use crate::gateway::{ChatEvent, GatewayState};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+/// Runs longer than this get a progress notification
+pub const PROGRESS_THRESHOLD_SECS: u64 = 60;
+
+/// Minimum interval between in-place progress updates
+const PROGRESS_UPDATE_INTERVAL_SECS: u64 = 30;
+
+/// How often active runs are checked
+const PROGRESS_CHECK_INTERVAL_SECS: u64 = 10;
+
+/// Whether posted notifications can be updated in place on this platform
+const UPDATES_IN_PLACE: bool = cfg!(target_os = "linux");
+
+/// Start time and origin of an active run
+#[derive(Debug, Clone)]
+pub struct RunProgress {
+    pub run_id: String,
+    pub started_at: Instant,
+    /// Label of the window that initiated the run
+    pub window: Option<String>,
+}
+
+/// How a run ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RunOutcome {
+    Completed,
+    Failed,
+    Aborted,
+}
+
+/// A progress notification that has been posted for a run
+struct ProgressNotification {
+    started_at: Instant,
+    last_posted: Instant,
+    /// Platform notification ID, used to replace the notification in place
+    id: Option<u32>,
+}
+
+/// Notification state managed by Tauri
+#[derive(Default)]
+pub struct NotificationState {
+    progress: Mutex<HashMap<String, ProgressNotification>>,
+}
+
+/// Show a notification, replacing `replaces` where the platform supports it
+///
+/// Returns the platform notification ID if it can be replaced later.
+pub(crate) async fn show(
+    app: &AppHandle,
+    replaces: Option<u32>,
+    title: &str,
+    body: &str,
+) -> Option<u32> {
+    #[cfg(target_os = "linux")]
+    {
+        let app_name = app
+            .config()
+            .product_name
+            .clone()
+            .unwrap_or_else(|| "Moltz".to_string());
+        let (title, body) = (title.to_string(), body.to_string());
+        let result = tokio::task::spawn_blocking(move || {
+            let mut notification = notify_rust::Notification::new();
+            notification.appname(&app_name).summary(&title).body(&body);
+            if let Some(id) = replaces {
+                notification.id(id);
+            }
+            notification.show().map(|handle| handle.id())
+        })
+        .await;
+        match result {
+            Ok(Ok(id)) => Some(id),
+            Ok(Err(e)) => {
+                log::error!("[Notifications] Failed to show notification: {}", e);
+                None
+            }
+            Err(_) => None,
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        use tauri_plugin_notification::NotificationExt;
+
+        let _ = replaces;
+        if let Err(e) = app.notification().builder().title(title).body(body).show() {
+            log::error!("[Notifications] Failed to show notification: {}", e);
+        }
+        None
+    }
+}
+
+/// Show a notification that runs `on_click` when it is clicked
+///
+/// Only Linux reports clicks back (freedesktop actions). On Windows, `link`
+/// (a `moltz://` URL) is opened instead when the toast is clicked, which
+/// also works after the app has exited. Elsewhere the notification is
+/// informational and callers should also surface the event in the app.
+pub(crate) async fn show_clickable<F>(
+    app: &AppHandle,
+    title: &str,
+    body: &str,
+    link: Option<String>,
+    on_click: F,
+) where
+    F: FnOnce() + Send + 'static,
+{
+    #[cfg(target_os = "linux")]
+    {
+        let app_name = app
+            .config()
+            .product_name
+            .clone()
+            .unwrap_or_else(|| "Moltz".to_string());
+        let mut notification = notify_rust::Notification::new();
+        notification
+            .appname(&app_name)
+            .summary(title)
+            .body(body)
+            .action("default", "Open");
+        // Waiting for the action blocks until the notification is closed
+        tauri::async_runtime::spawn_blocking(move || match notification.show() {
+            Ok(handle) => handle.wait_for_action(|action| {
+                if action == "default" {
+                    on_click();
+                }
+            }),
+            Err(e) => log::error!("[Notifications] Failed to show notification: {}", e),
+        });
+        let _ = link;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let _ = on_click;
+        let Some(link) = link else {
+            show(app, None, title, body).await;
+            return;
+        };
+        let (handle, title, body) = (app.clone(), title.to_string(), body.to_string());
+        let result = tokio::task::spawn_blocking(move || {
+            crate::windows_toast::show(&handle, &title, &body, &link)
+        })
+        .await;
+        if let Ok(Err(e)) = result {
+            log::error!("[Notifications] Failed to show notification: {}", e);
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    {
+        let _ = (on_click, link);
+        show(app, None, title, body).await;
+    }
+}
+
+/// Format elapsed time for the progress notification body
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{}s elapsed", secs)
+    } else {
+        format!("{}m elapsed", secs / 60)
+    }
+}
+
+/// Format a run's duration, e.g. `3m 12s`
+fn format_duration(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{}s", s),
+        (m, 0) => format!("{}m", m),
+        (m, s) => format!("{}m {}s", m, s),
+    }
+}
+
+/// Why a run stopped, when it didn't simply finish its turn
+fn stop_reason_text(stop_reason: &str) -> Option<String> {
+    match stop_reason {
+        "end_turn" | "stop" | "stop_sequence" => None,
+        "max_tokens" | "length" => Some("hit the token limit".to_string()),
+        "tool_use" => Some("waiting on a tool".to_string()),
+        "refusal" => Some("declined to answer".to_string()),
+        other => Some(format!("stopped: {}", other.replace('_', " "))),
+    }
+}
+
+/// Body of a completion notification, e.g.
+/// `Finished in 3m 12s · 1840 output tokens · hit the token limit`
+fn summary_body(outcome: RunOutcome, elapsed: Duration, event: Option<&ChatEvent>) -> String {
+    let duration = format_duration(elapsed);
+    let mut parts = vec![match outcome {
+        RunOutcome::Completed => format!("Finished in {}", duration),
+        RunOutcome::Failed | RunOutcome::Aborted => format!("Stopped after {}", duration),
+    }];
+    if let Some(event) = event {
+        match event.usage.as_ref().and_then(|usage| usage.output) {
+            Some(1) => parts.push("1 output token".to_string()),
+            Some(tokens) => parts.push(format!("{} output tokens", tokens)),
+            None => {}
+        }
+        if let Some(reason) = event.stop_reason.as_deref().and_then(stop_reason_text) {
+            parts.push(reason);
+        }
+    }
+    parts.join(" · ")
+}
+
+/// Whether a progress notification should be posted (or updated) now
+fn progress_due(run_elapsed: Duration, since_last_post: Option<Duration>) -> bool {
+    if run_elapsed < Duration::from_secs(PROGRESS_THRESHOLD_SECS) {
+        return false;
+    }
+    match since_last_post {
+        None => true,
+        Some(since) => {
+            UPDATES_IN_PLACE && since >= Duration::from_secs(PROGRESS_UPDATE_INTERVAL_SECS)
+        }
+    }
+}
+
+/// Whether the window that initiated a run is focused (no need to notify)
+fn window_focused(app: &AppHandle, window: Option<&str>) -> bool {
+    app.get_webview_window(window.unwrap_or("main"))
+        .and_then(|w| w.is_focused().ok())
+        .unwrap_or(false)
+}
+
+/// Post or update progress notifications for long-running runs
+async fn update_progress(app: &AppHandle, runs: Vec<RunProgress>) {
+    let Some(state) = app.try_state::<NotificationState>() else {
+        return;
+    };
+
+    for run in runs {
+        let elapsed = run.started_at.elapsed();
+        let (since_last_post, id) = {
+            let progress = state.progress.lock().await;
+            match progress.get(&run.run_id) {
+                Some(p) => (Some(p.last_posted.elapsed()), p.id),
+                None => (None, None),
+            }
+        };
+
+        if !progress_due(elapsed, since_last_post) {
+            continue;
+        }
+        // Don't start notifying about a run the user is watching
+        if since_last_post.is_none() && window_focused(app, run.window.as_deref()) {
+            continue;
+        }
+
+        let id = show(app, id, "Still working…", &format_elapsed(elapsed))
+            .await
+            .or(id);
+        state.progress.lock().await.insert(
+            run.run_id,
+            ProgressNotification {
+                started_at: run.started_at,
+                last_posted: Instant::now(),
+                id,
+            },
+        );
+    }
+}
+
+/// Turn a run's progress notification (if any) into a completion notification
+///
+/// `event` is the run's final chat event, whose usage data is summarized.
+pub async fn finish_progress(
+    app: &AppHandle,
+    run_id: &str,
+    outcome: RunOutcome,
+    event: Option<&ChatEvent>,
+) {
+    let Some(state) = app.try_state::<NotificationState>() else {
+        return;
+    };
+    let Some(progress) = state.progress.lock().await.remove(run_id) else {
+        return;
+    };
+
+    let title = match outcome {
+        RunOutcome::Completed => "Response ready",
+        RunOutcome::Failed => "Response failed",
+        RunOutcome::Aborted => "Response stopped",
+    };
+    let body = summary_body(outcome, progress.started_at.elapsed(), event);
+    show(app, progress.id, title, &body).await;
+}
+
+/// Start the progress notification monitor
+pub fn start_progress_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(PROGRESS_CHECK_INTERVAL_SECS)).await;
+
+            let runs = match app.try_state::<GatewayState>() {
+                Some(gateway) => gateway.run_progress().await,
+                None => continue,
+            };
+            update_progress(&app, runs).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_secs(45)), "45s elapsed");
+        assert_eq!(format_elapsed(Duration::from_secs(150)), "2m elapsed");
+    }
+
+    #[test]
+    fn test_summary_body() {
+        let event: ChatEvent = serde_json::from_value(serde_json::json!({
+            "state": "final",
+            "usage": { "input": 1200, "output": 1840 },
+            "stopReason": "max_tokens"
+        }))
+        .unwrap();
+        assert_eq!(
+            summary_body(
+                RunOutcome::Completed,
+                Duration::from_secs(192),
+                Some(&event)
+            ),
+            "Finished in 3m 12s · 1840 output tokens · hit the token limit"
+        );
+
+        let event: ChatEvent =
+            serde_json::from_value(serde_json::json!({ "stopReason": "end_turn" })).unwrap();
+        assert_eq!(
+            summary_body(
+                RunOutcome::Completed,
+                Duration::from_secs(120),
+                Some(&event)
+            ),
+            "Finished in 2m"
+        );
+        assert_eq!(
+            summary_body(RunOutcome::Failed, Duration::from_secs(61), None),
+            "Stopped after 1m 1s"
+        );
+    }
+
+    #[test]
+    fn test_progress_due() {
+        let threshold = Duration::from_secs(PROGRESS_THRESHOLD_SECS);
+        assert!(!progress_due(threshold - Duration::from_secs(1), None));
+        assert!(progress_due(threshold, None));
+
+        // Updates only where notifications can be replaced in place
+        let since = Duration::from_secs(PROGRESS_UPDATE_INTERVAL_SECS);
+        assert_eq!(progress_due(threshold * 3, Some(since)), UPDATES_IN_PLACE);
+        assert!(!progress_due(threshold * 3, Some(Duration::from_secs(1))));
+    }
+}
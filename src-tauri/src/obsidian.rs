@@ -0,0 +1,437 @@
+//! Obsidian vault export
+//!
+//! Writes conversations as Markdown notes into a configured vault folder:
+//! - YAML frontmatter with dates, model and tags
+//! - An index note linking every exported conversation, and a link back to
+//!   it from each note
+//! - Stable filenames: a note is found again by the `moltz-id` in its
+//!   frontmatter, so re-exporting updates it in place even if the
+//!   conversation was renamed
+
+use crate::error::CommandError;
+use crate::guest;
+use crate::store;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime, State};
+use tokio::sync::Mutex;
+
+/// Store file name for the Obsidian export settings
+const OBSIDIAN_FILE: &str = "obsidian.json";
+
+/// Name of the index note linking all exported conversations
+const INDEX_NOTE: &str = "Moltz Conversations";
+
+/// Default folder inside the vault
+const DEFAULT_FOLDER: &str = "Moltz";
+
+/// Obsidian export settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ObsidianConfig {
+    /// Vault root directory
+    #[serde(rename = "vaultPath")]
+    pub vault_path: Option<String>,
+    /// Folder inside the vault for exported notes
+    pub folder: Option<String>,
+}
+
+impl ObsidianConfig {
+    fn export_dir(&self) -> Result<PathBuf, String> {
+        let vault = self
+            .vault_path
+            .as_deref()
+            .filter(|p| !p.is_empty())
+            .ok_or("No Obsidian vault configured")?;
+        let folder = self
+            .folder
+            .as_deref()
+            .filter(|f| !f.is_empty())
+            .unwrap_or(DEFAULT_FOLDER);
+        Ok(Path::new(vault).join(folder))
+    }
+}
+
+/// Obsidian export state managed by Tauri
+pub struct ObsidianState {
+    path: PathBuf,
+    config: Mutex<ObsidianConfig>,
+}
+
+impl ObsidianState {
+    /// Load the export settings from the local store
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, OBSIDIAN_FILE);
+        let config = store::load(&path);
+        Self {
+            path,
+            config: Mutex::new(config),
+        }
+    }
+}
+
+/// A message to export
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExportMessage {
+    pub role: String,
+    pub content: String,
+    pub timestamp: DateTime<Utc>,
+    #[serde(rename = "modelUsed")]
+    pub model_used: Option<String>,
+}
+
+/// A conversation to export
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExportConversation {
+    pub id: String,
+    pub title: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: DateTime<Utc>,
+    pub model: Option<String>,
+    #[serde(rename = "isPinned", default)]
+    pub is_pinned: bool,
+    pub messages: Vec<ExportMessage>,
+}
+
+/// Result of an Obsidian export
+#[derive(Debug, Clone, Serialize)]
+pub struct ObsidianExportResult {
+    /// Folder the notes were written to
+    pub folder: String,
+    /// Note names written, keyed by conversation ID
+    pub notes: HashMap<String, String>,
+}
+
+/// Make a title safe to use as a note file name
+fn note_file_stem(title: &str) -> String {
+    let stem: String = title
+        .chars()
+        .filter(|c| {
+            !matches!(
+                c,
+                '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' | '#' | '^' | '[' | ']'
+            )
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let stem: String = stem.chars().take(100).collect();
+    if stem.is_empty() {
+        "Untitled".to_string()
+    } else {
+        stem
+    }
+}
+
+/// Read the `moltz-id` from a note's frontmatter
+fn frontmatter_id(content: &str) -> Option<&str> {
+    let rest = content.strip_prefix("---\n")?;
+    let end = rest.find("\n---")?;
+    rest[..end]
+        .lines()
+        .find_map(|line| line.strip_prefix("moltz-id:"))
+        .map(|id| id.trim().trim_matches('"'))
+}
+
+/// Find existing notes in the export folder, keyed by conversation ID
+fn existing_notes(dir: &Path) -> HashMap<String, String> {
+    let mut notes = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return notes;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+        let (Ok(content), Some(stem)) = (
+            std::fs::read_to_string(&path),
+            path.file_stem().and_then(|s| s.to_str()),
+        ) else {
+            continue;
+        };
+        if let Some(id) = frontmatter_id(&content) {
+            notes.insert(id.to_string(), stem.to_string());
+        }
+    }
+    notes
+}
+
+/// Assign a note name to every conversation, reusing existing names
+fn assign_note_names(
+    conversations: &[ExportConversation],
+    existing: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut names: HashMap<String, String> = HashMap::new();
+    let mut taken: Vec<String> = existing.values().cloned().collect();
+    taken.push(INDEX_NOTE.to_string());
+
+    for conversation in conversations {
+        if let Some(name) = existing.get(&conversation.id) {
+            names.insert(conversation.id.clone(), name.clone());
+            continue;
+        }
+        let stem = note_file_stem(&conversation.title);
+        let name = if taken.iter().any(|t| t.eq_ignore_ascii_case(&stem)) {
+            let short_id: String = conversation.id.chars().take(8).collect();
+            format!("{} ({})", stem, short_id)
+        } else {
+            stem
+        };
+        taken.push(name.clone());
+        names.insert(conversation.id.clone(), name);
+    }
+    names
+}
+
+/// Quote a string for YAML frontmatter
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+/// Render a conversation as an Obsidian note
+fn render_note(conversation: &ExportConversation) -> String {
+    let mut tags = vec!["moltz".to_string()];
+    if conversation.is_pinned {
+        tags.push("pinned".to_string());
+    }
+    if let Some(model) = &conversation.model {
+        // e.g. "anthropic/claude-sonnet-4-5" -> "model/anthropic"
+        if let Some(provider) = model.split('/').next().filter(|p| !p.is_empty()) {
+            tags.push(format!("model/{}", provider));
+        }
+    }
+
+    let mut lines = vec![
+        "---".to_string(),
+        format!("title: {}", yaml_string(&conversation.title)),
+        format!("created: {}", conversation.created_at.to_rfc3339()),
+        format!("updated: {}", conversation.updated_at.to_rfc3339()),
+    ];
+    if let Some(model) = &conversation.model {
+        lines.push(format!("model: {}", yaml_string(model)));
+    }
+    lines.push(format!("tags: [{}]", tags.join(", ")));
+    lines.push(format!("moltz-id: {}", conversation.id));
+    lines.push("---".to_string());
+    lines.push(String::new());
+    lines.push(format!("# {}", conversation.title));
+    lines.push(String::new());
+    lines.push(format!("Part of [[{}]]", INDEX_NOTE));
+    lines.push(String::new());
+
+    for message in &conversation.messages {
+        let role = match message.role.as_str() {
+            "user" => "User",
+            "assistant" => "Assistant",
+            _ => "System",
+        };
+        let model = match (&message.model_used, message.role.as_str()) {
+            (Some(model), "assistant") => format!(" ({})", model),
+            _ => String::new(),
+        };
+        lines.push(format!(
+            "## {}{} · {}",
+            role,
+            model,
+            message.timestamp.format("%Y-%m-%d %H:%M")
+        ));
+        lines.push(String::new());
+        lines.push(message.content.clone());
+        lines.push(String::new());
+    }
+
+    lines.join("\n")
+}
+
+/// Render the index note: this export's notes newest first, then notes from
+/// earlier exports
+fn render_index(
+    conversations: &[ExportConversation],
+    names: &HashMap<String, String>,
+    earlier: &[String],
+) -> String {
+    let mut sorted: Vec<&ExportConversation> = conversations.iter().collect();
+    sorted.sort_by_key(|c| std::cmp::Reverse(c.updated_at));
+
+    let mut lines = vec![
+        "---".to_string(),
+        "tags: [moltz]".to_string(),
+        "---".to_string(),
+        String::new(),
+        format!("# {}", INDEX_NOTE),
+        String::new(),
+    ];
+    for conversation in sorted {
+        if let Some(name) = names.get(&conversation.id) {
+            lines.push(format!(
+                "- [[{}]] · {}",
+                name,
+                conversation.updated_at.format("%Y-%m-%d")
+            ));
+        }
+    }
+    for name in earlier {
+        lines.push(format!("- [[{}]]", name));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Write a note atomically
+fn write_note(dir: &Path, name: &str, content: &str) -> Result<(), String> {
+    let path = dir.join(format!("{}.md", name));
+    let tmp_path = dir.join(format!(".{}.md.tmp", name));
+    std::fs::write(&tmp_path, content).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp_path, &path).map_err(|e| e.to_string())
+}
+
+/// Export conversations into a vault folder
+fn export_notes(
+    dir: &Path,
+    conversations: &[ExportConversation],
+) -> Result<HashMap<String, String>, String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+
+    let existing = existing_notes(dir);
+    let names = assign_note_names(conversations, &existing);
+
+    for conversation in conversations {
+        if let Some(name) = names.get(&conversation.id) {
+            write_note(dir, name, &render_note(conversation))?;
+        }
+    }
+
+    // The index also covers notes from earlier exports
+    let mut earlier: Vec<String> = existing
+        .iter()
+        .filter(|(id, _)| !names.contains_key(*id))
+        .map(|(_, name)| name.clone())
+        .collect();
+    earlier.sort();
+    write_note(
+        dir,
+        INDEX_NOTE,
+        &render_index(conversations, &names, &earlier),
+    )?;
+
+    Ok(names)
+}
+
+/// Get the Obsidian export settings
+#[tauri::command]
+pub async fn get_obsidian_config(
+    state: State<'_, ObsidianState>,
+) -> Result<ObsidianConfig, CommandError> {
+    Ok(state.config.lock().await.clone())
+}
+
+/// Set the Obsidian export settings
+#[tauri::command]
+pub async fn set_obsidian_config(
+    state: State<'_, ObsidianState>,
+    config: ObsidianConfig,
+) -> Result<(), CommandError> {
+    if let Some(vault) = config.vault_path.as_deref().filter(|p| !p.is_empty()) {
+        if !Path::new(vault).is_dir() {
+            return Err(CommandError::validation(format!(
+                "Vault folder does not exist: {}",
+                vault
+            )));
+        }
+    }
+    let mut current = state.config.lock().await;
+    store::save(&state.path, &config)?;
+    *current = config;
+    Ok(())
+}
+
+/// Export conversations as notes into the configured Obsidian vault
+#[tauri::command]
+pub async fn export_to_obsidian(
+    state: State<'_, ObsidianState>,
+    conversations: Vec<ExportConversation>,
+) -> Result<ObsidianExportResult, CommandError> {
+    guest::ensure_inactive("Exporting to Obsidian")?;
+    let dir = state
+        .config
+        .lock()
+        .await
+        .export_dir()
+        .map_err(CommandError::validation)?;
+
+    let export_dir = dir.clone();
+    let notes = tokio::task::spawn_blocking(move || export_notes(&export_dir, &conversations))
+        .await
+        .map_err(|e| CommandError::internal(e.to_string()))?
+        .map_err(CommandError::io)?;
+
+    Ok(ObsidianExportResult {
+        folder: dir.to_string_lossy().to_string(),
+        notes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conversation(id: &str, title: &str) -> ExportConversation {
+        ExportConversation {
+            id: id.to_string(),
+            title: title.to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            model: Some("anthropic/claude-sonnet-4-5".to_string()),
+            is_pinned: false,
+            messages: vec![ExportMessage {
+                role: "user".to_string(),
+                content: "Hello".to_string(),
+                timestamp: Utc::now(),
+                model_used: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_note_file_stem() {
+        assert_eq!(note_file_stem("What is  a/b: test?"), "What is ab test");
+        assert_eq!(note_file_stem("[[]]"), "Untitled");
+    }
+
+    #[test]
+    fn test_render_note_frontmatter() {
+        let note = render_note(&conversation("abc-123", "Title \"quoted\""));
+        assert!(note.starts_with("---\ntitle: \"Title \\\"quoted\\\"\""));
+        assert!(note.contains("model: \"anthropic/claude-sonnet-4-5\""));
+        assert!(note.contains("tags: [moltz, model/anthropic]"));
+        assert_eq!(frontmatter_id(&note), Some("abc-123"));
+        assert!(note.contains("[[Moltz Conversations]]"));
+    }
+
+    #[test]
+    fn test_reexport_keeps_filename() {
+        let dir = std::env::temp_dir().join(format!("moltz-obsidian-{}", uuid::Uuid::new_v4()));
+
+        let names = export_notes(&dir, &[conversation("id-1", "First title")]).unwrap();
+        assert_eq!(names["id-1"], "First title");
+
+        // Renamed conversation updates the same note
+        let names = export_notes(&dir, &[conversation("id-1", "Renamed")]).unwrap();
+        assert_eq!(names["id-1"], "First title");
+
+        // Another conversation with a clashing title gets a distinct name
+        let names = export_notes(&dir, &[conversation("id-2", "First title")]).unwrap();
+        assert_eq!(names["id-2"], "First title (id-2)");
+
+        let index = std::fs::read_to_string(dir.join("Moltz Conversations.md")).unwrap();
+        assert!(index.contains("[[First title]]"));
+        assert!(index.contains("[[First title (id-2)]]"));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,286 @@
+//! Explicit offline mode
+//!
+//! When the user turns offline mode on, `send_message` never touches the
+//! socket: every message is queued to disk and acknowledged with a
+//! `gateway:offline_queued` event. Turning offline mode off drains the queue
+//! over the live connection, or on the next successful connect.
+//!
+//! Queued frames carry message text and attachments, so the queue is sealed
+//! in `offline_queue.bin` under a key kept in the keychain; only the on/off
+//! flag stays in plain JSON.
+
+use crate::error::CommandError;
+use crate::gateway::GatewayState;
+use crate::keychain;
+use crate::store::{self, SEAL_KEY_LEN};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Runtime, State};
+use tokio::sync::Mutex;
+
+/// Store file name for the offline flag (plain JSON, nothing private)
+const OFFLINE_FILE: &str = "offline_queue.json";
+
+/// Store file name for the sealed queue
+const QUEUE_FILE: &str = "offline_queue.bin";
+
+/// Keychain key holding the queue's encryption key
+const QUEUE_KEY_NAME: &str = "offline_queue_key";
+
+/// A message queued while offline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineMessage {
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+    /// Fully built request frame, sent as-is when draining
+    pub json: String,
+    #[serde(rename = "queuedAt")]
+    pub queued_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Persisted offline flag
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct OfflineData {
+    enabled: bool,
+    /// Queue kept here in plain JSON by earlier versions; moved into the
+    /// sealed file on first unlock
+    #[serde(default, skip_serializing)]
+    queue: Vec<OfflineMessage>,
+}
+
+/// Offline mode status for the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct OfflineStatus {
+    pub enabled: bool,
+    pub queued: usize,
+}
+
+/// The decrypted queue and the key it is sealed with
+struct Unlocked {
+    key: [u8; SEAL_KEY_LEN],
+    queue: Vec<OfflineMessage>,
+}
+
+struct OfflineInner {
+    data: OfflineData,
+    /// None until the queue is first needed
+    unlocked: Option<Unlocked>,
+}
+
+/// Offline mode state managed by Tauri
+pub struct OfflineState {
+    path: PathBuf,
+    queue_path: PathBuf,
+    inner: Mutex<OfflineInner>,
+}
+
+impl OfflineState {
+    /// Load the offline flag; the queue itself stays sealed until used
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, OFFLINE_FILE);
+        let data = store::load(&path);
+        Self {
+            path,
+            queue_path: store::store_path(app, QUEUE_FILE),
+            inner: Mutex::new(OfflineInner {
+                data,
+                unlocked: None,
+            }),
+        }
+    }
+
+    /// The decrypted queue, unsealing it on first use
+    async fn unlock<'a>(
+        &self,
+        inner: &'a mut OfflineInner,
+    ) -> Result<&'a mut Unlocked, CommandError> {
+        let unlocked = match inner.unlocked.take() {
+            Some(unlocked) => unlocked,
+            None => {
+                let key = keychain::store_key(QUEUE_KEY_NAME).await?;
+                let mut queue: Vec<OfflineMessage> = store::load_sealed(&self.queue_path, &key);
+                if !inner.data.queue.is_empty() {
+                    let mut legacy = std::mem::take(&mut inner.data.queue);
+                    legacy.append(&mut queue);
+                    queue = legacy;
+                    store::save_sealed(&self.queue_path, &key, &queue)?;
+                    store::save(&self.path, &inner.data)?;
+                }
+                Unlocked { key, queue }
+            }
+        };
+        Ok(inner.unlocked.insert(unlocked))
+    }
+
+    pub async fn is_enabled(&self) -> bool {
+        self.inner.lock().await.data.enabled
+    }
+
+    pub async fn status(&self) -> Result<OfflineStatus, CommandError> {
+        let mut inner = self.inner.lock().await;
+        let queued = self.unlock(&mut inner).await?.queue.len();
+        Ok(OfflineStatus {
+            enabled: inner.data.enabled,
+            queued,
+        })
+    }
+
+    /// Queue a message, returning the new queue length
+    pub async fn enqueue(&self, request_id: String, json: String) -> Result<usize, CommandError> {
+        let mut inner = self.inner.lock().await;
+        let unlocked = self.unlock(&mut inner).await?;
+        unlocked.queue.push(OfflineMessage {
+            request_id,
+            json,
+            queued_at: chrono::Utc::now(),
+        });
+        store::save_sealed(&self.queue_path, &unlocked.key, &unlocked.queue)?;
+        Ok(unlocked.queue.len())
+    }
+
+    /// Take every queued message for sending
+    pub async fn take_queue(&self) -> Vec<OfflineMessage> {
+        let mut inner = self.inner.lock().await;
+        let unlocked = match self.unlock(&mut inner).await {
+            Ok(unlocked) => unlocked,
+            Err(e) => {
+                log::error!("[Offline] Failed to unseal the queue: {}", e);
+                return Vec::new();
+            }
+        };
+        let queue = std::mem::take(&mut unlocked.queue);
+        if !queue.is_empty() {
+            if let Err(e) = store::save_sealed(&self.queue_path, &unlocked.key, &unlocked.queue) {
+                log::error!("[Offline] Failed to persist drained queue: {}", e);
+            }
+        }
+        queue
+    }
+
+    /// Put unsent messages back at the front of the queue, preserving order
+    pub async fn restore(&self, mut unsent: Vec<OfflineMessage>) {
+        if unsent.is_empty() {
+            return;
+        }
+        let mut inner = self.inner.lock().await;
+        let unlocked = match self.unlock(&mut inner).await {
+            Ok(unlocked) => unlocked,
+            Err(e) => {
+                log::error!("[Offline] Failed to unseal the queue: {}", e);
+                return;
+            }
+        };
+        unsent.append(&mut unlocked.queue);
+        unlocked.queue = unsent;
+        if let Err(e) = store::save_sealed(&self.queue_path, &unlocked.key, &unlocked.queue) {
+            log::error!("[Offline] Failed to persist restored queue: {}", e);
+        }
+    }
+
+    async fn set_enabled(&self, enabled: bool) -> Result<(), CommandError> {
+        let mut inner = self.inner.lock().await;
+        // Moves a queue left by an earlier version before the flag is saved
+        // without it
+        self.unlock(&mut inner).await?;
+        inner.data.enabled = enabled;
+        Ok(store::save(&self.path, &inner.data)?)
+    }
+}
+
+/// Turn offline mode on or off
+///
+/// Going back online drains the queue immediately if connected; otherwise it
+/// is drained on the next successful connect.
+#[tauri::command]
+pub async fn set_offline_mode(
+    app: AppHandle,
+    offline: State<'_, OfflineState>,
+    gateway: State<'_, GatewayState>,
+    enabled: bool,
+) -> Result<OfflineStatus, CommandError> {
+    offline.set_enabled(enabled).await?;
+
+    if !enabled {
+        gateway.drain_offline_queue(&app).await;
+    }
+
+    let status = offline.status().await?;
+    let _ = app.emit("gateway:offline_mode", status.clone());
+    Ok(status)
+}
+
+/// Get offline mode status and queue length
+#[tauri::command]
+pub async fn get_offline_status(
+    offline: State<'_, OfflineState>,
+) -> Result<OfflineStatus, CommandError> {
+    offline.status().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_state() -> OfflineState {
+        let name = format!("moltz-offline-{}", uuid::Uuid::new_v4());
+        OfflineState {
+            path: std::env::temp_dir().join(format!("{}.json", name)),
+            queue_path: std::env::temp_dir().join(format!("{}.bin", name)),
+            inner: Mutex::new(OfflineInner {
+                data: OfflineData::default(),
+                unlocked: Some(Unlocked {
+                    key: [7; SEAL_KEY_LEN],
+                    queue: Vec::new(),
+                }),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restore_preserves_order() {
+        let state = temp_state();
+        state
+            .enqueue("a".to_string(), "{}".to_string())
+            .await
+            .unwrap();
+        state
+            .enqueue("b".to_string(), "{}".to_string())
+            .await
+            .unwrap();
+
+        let taken = state.take_queue().await;
+        assert_eq!(taken.len(), 2);
+        state
+            .enqueue("c".to_string(), "{}".to_string())
+            .await
+            .unwrap();
+        state.restore(taken).await;
+
+        let ids: Vec<String> = state
+            .take_queue()
+            .await
+            .into_iter()
+            .map(|m| m.request_id)
+            .collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+
+        let _ = std::fs::remove_file(&state.queue_path);
+    }
+
+    #[tokio::test]
+    async fn test_queue_is_sealed_on_disk() {
+        let state = temp_state();
+        let frame = r#"{"method":"chat.send","params":{"message":"meet at noon"}}"#;
+        state
+            .enqueue("a".to_string(), frame.to_string())
+            .await
+            .unwrap();
+
+        let on_disk = std::fs::read(&state.queue_path).unwrap();
+        assert!(!String::from_utf8_lossy(&on_disk).contains("meet at noon"));
+        let queue: Vec<OfflineMessage> = store::load_sealed(&state.queue_path, &[7; SEAL_KEY_LEN]);
+        assert_eq!(queue[0].json, frame);
+
+        let _ = std::fs::remove_file(&state.queue_path);
+    }
+}
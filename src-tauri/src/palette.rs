@@ -0,0 +1,453 @@
+//! Data provider for the command palette (Cmd+K)
+//!
+//! The palette used to build and filter its list in the webview, matching
+//! plain substrings. `palette_query` now does the indexing and ranking here,
+//! over:
+//! - actions: the menu and tray commands, run through the same handlers
+//!   (`run_palette_action`), and new chats from presets
+//! - recent conversations: titles live encrypted in the frontend database, so
+//!   the webview hands over an index of them (`set_palette_conversations`),
+//!   kept in memory only
+//! - models from the Gateway's last `models.list`
+//! - settings: appearance toggles and the sections of the Settings dialog
+//!
+//! Matching is fuzzy: the query's characters must appear in order, and
+//! matches at word starts and in runs score higher. Each result carries the
+//! positions of the matched characters (in chars) for highlighting.
+
+use crate::error::CommandError;
+use crate::gateway::GatewayState;
+use crate::menu;
+use crate::presets::PresetState;
+use crate::token_expiry;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use tokio::sync::Mutex;
+
+/// Results returned for a query
+const MAX_RESULTS: usize = 50;
+
+/// Conversations listed when the query is empty
+const RECENT_CONVERSATIONS: usize = 5;
+
+/// Conversations indexed; the most recently updated are kept
+const MAX_CONVERSATIONS: usize = 500;
+
+/// Palette-only action ID for the tray's re-pair command
+const REPAIR: &str = "repair";
+
+/// Actions: ID (the menu item's), label, shortcut, keywords
+const ACTIONS: &[(&str, &str, Option<&str>, &[&str])] = &[
+    (
+        menu::ids::NEW_CONVERSATION,
+        "New Conversation",
+        Some("CmdOrCtrl+N"),
+        &["chat", "create", "start"],
+    ),
+    (
+        menu::ids::QUICK_ASK,
+        "Quick Ask",
+        Some("CmdOrCtrl+Shift+Space"),
+        &["question", "popup"],
+    ),
+    (
+        menu::ids::SEARCH,
+        "Search Conversations",
+        None,
+        &["find", "messages"],
+    ),
+    (
+        menu::ids::EXPORT,
+        "Export Conversation",
+        Some("CmdOrCtrl+Shift+E"),
+        &["save", "markdown", "pdf"],
+    ),
+    (
+        menu::ids::EMAIL,
+        "Email Conversation",
+        None,
+        &["send", "mail"],
+    ),
+    (
+        menu::ids::TOGGLE_SIDEBAR,
+        "Toggle Sidebar",
+        Some("CmdOrCtrl+\\"),
+        &["hide", "show", "panel"],
+    ),
+    (
+        menu::ids::PREFERENCES,
+        "Open Settings",
+        Some("CmdOrCtrl+,"),
+        &["preferences", "configure"],
+    ),
+    (REPAIR, "Re-pair Gateway", None, &["token", "pairing"]),
+];
+
+/// Settings: ID, label, keywords
+///
+/// `settings:` IDs open the Settings dialog; the others are toggles the
+/// frontend applies itself.
+const SETTINGS: &[(&str, &str, &[&str])] = &[
+    ("theme-light", "Light Theme", &["appearance"]),
+    ("theme-dark", "Dark Theme", &["appearance", "night"]),
+    ("theme-system", "System Theme", &["appearance", "auto"]),
+    ("compact-mode", "Toggle Compact Mode", &["density"]),
+    (
+        "reinitialize-integration",
+        "Re-register Tray & Shortcuts",
+        &["hotkey", "icon", "repair"],
+    ),
+    (
+        "settings:connection",
+        "Connection Settings",
+        &["gateway", "url", "token"],
+    ),
+    (
+        "settings:chat",
+        "Chat Settings",
+        &["model", "thinking", "prompt"],
+    ),
+    ("settings:dns", "DNS Settings", &["resolver", "network"]),
+    ("settings:data", "Data Usage Settings", &["saver", "cache"]),
+    (
+        "settings:limits",
+        "Usage Limits",
+        &["quota", "tokens", "budget"],
+    ),
+    (
+        "settings:scheduled",
+        "Scheduled Prompts",
+        &["cron", "reminder"],
+    ),
+    (
+        "settings:notifications",
+        "Notification Settings",
+        &["alerts", "events"],
+    ),
+    (
+        "settings:storage",
+        "Storage Settings",
+        &["logs", "disk", "archive"],
+    ),
+];
+
+/// What a palette entry does when picked
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaletteKind {
+    /// Run with `run_palette_action`
+    Action,
+    /// New chat from the preset with this ID
+    Preset,
+    Conversation,
+    Model,
+    Setting,
+}
+
+/// A palette entry
+#[derive(Debug, Clone, Serialize)]
+pub struct PaletteItem {
+    pub kind: PaletteKind,
+    pub id: String,
+    pub label: String,
+    pub detail: Option<String>,
+    pub shortcut: Option<String>,
+    /// Extra words the entry is found by, not shown
+    #[serde(skip)]
+    keywords: Vec<String>,
+}
+
+impl PaletteItem {
+    fn new(kind: PaletteKind, id: &str, label: &str) -> Self {
+        Self {
+            kind,
+            id: id.to_string(),
+            label: label.to_string(),
+            detail: None,
+            shortcut: None,
+            keywords: Vec::new(),
+        }
+    }
+}
+
+/// A ranked result
+#[derive(Debug, Clone, Serialize)]
+pub struct PaletteMatch {
+    #[serde(flatten)]
+    pub item: PaletteItem,
+    pub score: i64,
+    /// Char positions in the label that matched the query
+    pub positions: Vec<usize>,
+}
+
+/// A conversation as indexed by the palette
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaletteConversation {
+    pub id: String,
+    pub title: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: DateTime<Utc>,
+    #[serde(rename = "messageCount", default)]
+    pub message_count: usize,
+}
+
+/// Conversation index managed by Tauri
+#[derive(Default)]
+pub struct PaletteState {
+    /// Most recently updated first
+    conversations: Mutex<Vec<PaletteConversation>>,
+}
+
+/// Score `text` against a query (lowercase, no whitespace), with the char
+/// positions that matched; None unless every query char appears in order
+fn fuzzy_match(query: &[char], text: &str) -> Option<(i64, Vec<usize>)> {
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    let word_start = |i: usize| {
+        i == 0
+            || !chars[i - 1].is_alphanumeric()
+            || (chars[i - 1].is_lowercase() && chars[i].is_uppercase())
+    };
+
+    let first = *query.first()?;
+    let mut best: Option<(i64, Vec<usize>)> = None;
+    // Greedy from every place the first char occurs, keeping the best run
+    for start in (0..lower.len()).filter(|&i| lower[i] == first) {
+        let mut positions = vec![start];
+        let mut next = start + 1;
+        for &wanted in &query[1..] {
+            match (next..lower.len()).find(|&i| lower[i] == wanted) {
+                Some(i) => {
+                    positions.push(i);
+                    next = i + 1;
+                }
+                None => break,
+            }
+        }
+        if positions.len() < query.len() {
+            // Later starts only have fewer chars left
+            break;
+        }
+
+        let mut score = 0i64;
+        for (n, &i) in positions.iter().enumerate() {
+            score += 1;
+            if word_start(i) {
+                score += 6;
+            }
+            if n > 0 {
+                let gap = (i - positions[n - 1] - 1) as i64;
+                score += if gap == 0 { 4 } else { -gap.min(3) };
+            }
+        }
+        if start == 0 {
+            score += 8;
+        } else {
+            score -= (start as i64).min(5);
+        }
+        if best.as_ref().is_none_or(|(s, _)| score > *s) {
+            best = Some((score, positions));
+        }
+    }
+    best
+}
+
+/// Score an item: its label counts fully, its detail and keywords for half
+fn score_item(query: &[char], item: &PaletteItem) -> Option<(i64, Vec<usize>)> {
+    let label = fuzzy_match(query, &item.label);
+    let other = item
+        .detail
+        .iter()
+        .chain(&item.keywords)
+        .filter_map(|text| fuzzy_match(query, text))
+        .map(|(score, _)| score / 2)
+        .max();
+    match (label, other) {
+        (Some((score, positions)), Some(other)) if other > score => Some((other, positions)),
+        (Some(found), _) => Some(found),
+        (None, Some(other)) => Some((other, Vec::new())),
+        (None, None) => None,
+    }
+}
+
+/// Rank items against a query; an empty query keeps them in order
+fn rank(items: Vec<PaletteItem>, text: &str) -> Vec<PaletteMatch> {
+    let query: Vec<char> = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return items
+            .into_iter()
+            .take(MAX_RESULTS)
+            .map(|item| PaletteMatch {
+                item,
+                score: 0,
+                positions: Vec::new(),
+            })
+            .collect();
+    }
+    let mut matches: Vec<PaletteMatch> = items
+        .into_iter()
+        .filter_map(|item| {
+            score_item(&query, &item).map(|(score, positions)| PaletteMatch {
+                item,
+                score,
+                positions,
+            })
+        })
+        .collect();
+    // Stable: ties keep the kind order and recency of the index
+    matches.sort_by(|a, b| b.score.cmp(&a.score));
+    matches.truncate(MAX_RESULTS);
+    matches
+}
+
+fn static_items() -> (Vec<PaletteItem>, Vec<PaletteItem>) {
+    let actions = ACTIONS
+        .iter()
+        .map(|(id, label, shortcut, keywords)| PaletteItem {
+            shortcut: shortcut.map(str::to_string),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            ..PaletteItem::new(PaletteKind::Action, id, label)
+        })
+        .collect();
+    let settings = SETTINGS
+        .iter()
+        .map(|(id, label, keywords)| PaletteItem {
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            ..PaletteItem::new(PaletteKind::Setting, id, label)
+        })
+        .collect();
+    (actions, settings)
+}
+
+/// Search actions, presets, conversations, models and settings
+#[tauri::command]
+pub async fn palette_query(
+    state: State<'_, PaletteState>,
+    gateway: State<'_, GatewayState>,
+    presets: State<'_, PresetState>,
+    text: String,
+) -> Result<Vec<PaletteMatch>, CommandError> {
+    let (mut items, settings) = static_items();
+    items.extend(presets.all().await.into_iter().map(|preset| PaletteItem {
+        detail: Some(preset.model.unwrap_or_else(|| "Preset".to_string())),
+        ..PaletteItem::new(
+            PaletteKind::Preset,
+            &preset.id,
+            &format!("New Chat: {}", preset.name),
+        )
+    }));
+
+    let conversations = state.conversations.lock().await;
+    let listed = if text.trim().is_empty() {
+        RECENT_CONVERSATIONS
+    } else {
+        conversations.len()
+    };
+    items.extend(conversations.iter().take(listed).map(|conversation| {
+        let title = match conversation.title.trim() {
+            "" => "Untitled",
+            title => title,
+        };
+        PaletteItem {
+            detail: Some(format!("{} messages", conversation.message_count)),
+            ..PaletteItem::new(PaletteKind::Conversation, &conversation.id, title)
+        }
+    }));
+    drop(conversations);
+
+    items.extend(
+        gateway
+            .known_models()
+            .await
+            .into_iter()
+            .map(|model| PaletteItem {
+                detail: Some(model.provider),
+                keywords: vec![model.id.clone()],
+                ..PaletteItem::new(
+                    PaletteKind::Model,
+                    &model.id,
+                    &format!("Switch to {}", model.name),
+                )
+            }),
+    );
+    items.extend(settings);
+
+    Ok(rank(items, &text))
+}
+
+/// Replace the palette's conversation index
+#[tauri::command]
+pub async fn set_palette_conversations(
+    state: State<'_, PaletteState>,
+    mut conversations: Vec<PaletteConversation>,
+) -> Result<(), CommandError> {
+    conversations.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    conversations.truncate(MAX_CONVERSATIONS);
+    *state.conversations.lock().await = conversations;
+    Ok(())
+}
+
+/// Run a palette action through the menu and tray handlers
+#[tauri::command]
+pub async fn run_palette_action(app: AppHandle, id: String) -> Result<(), CommandError> {
+    match id.as_str() {
+        REPAIR => token_expiry::open_repair(&app),
+        id if ACTIONS.iter().any(|(action, ..)| *action == id) => menu::handle_menu_event(&app, id),
+        _ => return Err(CommandError::validation("Unknown palette action")),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(text: &str) -> Vec<char> {
+        text.chars().collect()
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        let (_, positions) = fuzzy_match(&query("nc"), "New Conversation").unwrap();
+        assert_eq!(positions, [0, 4]);
+        assert!(fuzzy_match(&query("xyz"), "New Conversation").is_none());
+        assert!(fuzzy_match(&query("cn"), "New Conversation").is_some());
+        assert!(fuzzy_match(&query("nwc"), "New").is_none());
+
+        // Word starts and runs beat scattered matches
+        let (initials, _) = fuzzy_match(&query("ts"), "Toggle Sidebar").unwrap();
+        let (scattered, _) = fuzzy_match(&query("ts"), "Test results").unwrap();
+        assert!(initials > scattered);
+        let (run, _) = fuzzy_match(&query("dark"), "Dark Theme").unwrap();
+        let (spread, _) = fuzzy_match(&query("dark"), "Data Usage Settings Rank").unwrap();
+        assert!(run > spread);
+    }
+
+    #[test]
+    fn test_rank() {
+        let (actions, settings) = static_items();
+        let items: Vec<PaletteItem> = actions.into_iter().chain(settings).collect();
+        let count = items.len();
+
+        let results = rank(items.clone(), "");
+        assert_eq!(results.len(), count.min(MAX_RESULTS));
+        assert_eq!(results[0].item.id, menu::ids::NEW_CONVERSATION);
+
+        let results = rank(items.clone(), "new conv");
+        assert_eq!(results[0].item.id, menu::ids::NEW_CONVERSATION);
+        assert_eq!(results[0].positions.len(), 7);
+
+        // Found by keyword, nothing to highlight
+        let results = rank(items, "hotkey");
+        assert_eq!(results[0].item.id, "reinitialize-integration");
+        assert!(results[0].positions.is_empty());
+    }
+}
@@ -0,0 +1,427 @@
+//! Markdown to plain text
+//!
+//! Responses are Markdown, which reads badly wherever it isn't rendered:
+//! notification bodies, email bodies, text read aloud. This strips the
+//! syntax and keeps the words:
+//! - Headings, emphasis, strikethrough and code spans lose their markers
+//! - Links and images become their text; autolinks their URL
+//! - List items get a bullet, task boxes are dropped, numbers are kept
+//! - Code blocks are kept verbatim without their fences
+//! - Table rows become cells separated by ` · `; rules and table
+//!   separators are dropped
+//!
+//! [`PlaintextStream`] converts line by line as a response streams in, so
+//! partial text can be shown or spoken before the run ends.
+
+/// Separator between table cells
+const CELL_SEPARATOR: &str = " · ";
+
+/// Incremental Markdown to plain text conversion
+#[derive(Debug, Default)]
+pub struct PlaintextStream {
+    /// Text after the last complete line
+    pending: String,
+    /// Marker character and length of the open code fence
+    fence: Option<(char, usize)>,
+}
+
+impl PlaintextStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a chunk; returns the plain text of the lines it completed
+    pub fn push(&mut self, chunk: &str) -> String {
+        self.pending.push_str(chunk);
+        let Some(last_newline) = self.pending.rfind('\n') else {
+            return String::new();
+        };
+        let complete: String = self.pending.drain(..=last_newline).collect();
+        let mut text = String::new();
+        for line in complete.lines() {
+            if let Some(line) = self.convert_line(line) {
+                text.push_str(&line);
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    /// Convert what remains after the last chunk
+    pub fn finish(mut self) -> String {
+        let rest = std::mem::take(&mut self.pending);
+        rest.lines()
+            .filter_map(|line| self.convert_line(line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Plain text of one line, `None` for lines that only carry syntax
+    fn convert_line(&mut self, line: &str) -> Option<String> {
+        let trimmed = line.trim_start();
+        if let Some((marker, len)) = fence_marker(trimmed) {
+            match self.fence {
+                None => {
+                    self.fence = Some((marker, len));
+                    return None;
+                }
+                Some((open, open_len)) if open == marker && len >= open_len => {
+                    // A closing fence has nothing after the marker
+                    if trimmed.trim_start_matches(marker).trim().is_empty() {
+                        self.fence = None;
+                        return None;
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+        if self.fence.is_some() {
+            return Some(line.to_string());
+        }
+        block_to_plain(line)
+    }
+}
+
+/// Marker and length of a code fence line (three or more ` or ~)
+fn fence_marker(line: &str) -> Option<(char, usize)> {
+    let marker = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = line.chars().take_while(|c| *c == marker).count();
+    (len >= 3).then_some((marker, len))
+}
+
+/// Whether a line is a thematic break (`---`, `***`, `___`) or a setext
+/// heading underline (`===`)
+fn is_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    let Some(first) = compact.chars().next() else {
+        return false;
+    };
+    matches!(first, '-' | '*' | '_' | '=')
+        && compact.len() >= 3
+        && compact.chars().all(|c| c == first)
+}
+
+/// Whether a line is a table's header separator, e.g. `|---|:--:|`
+fn is_table_separator(line: &str) -> bool {
+    line.contains('-') && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+/// Convert a line outside code blocks
+fn block_to_plain(line: &str) -> Option<String> {
+    let indent_len = line.len() - line.trim_start().len();
+    let (indent, mut rest) = line.split_at(indent_len);
+
+    if rest.is_empty() {
+        return Some(String::new());
+    }
+    if is_rule(rest) {
+        return None;
+    }
+
+    // Blockquotes, possibly nested
+    while let Some(quoted) = rest.strip_prefix('>') {
+        rest = quoted.strip_prefix(' ').unwrap_or(quoted);
+    }
+
+    // Headings
+    let hashes = rest.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&hashes)
+        && rest[hashes..]
+            .chars()
+            .next()
+            .is_none_or(char::is_whitespace)
+    {
+        let heading = rest[hashes..].trim();
+        let heading = heading.trim_end_matches('#').trim_end();
+        return Some(inline_to_plain(heading));
+    }
+
+    // Tables
+    if rest.starts_with('|') {
+        if is_table_separator(rest) {
+            return None;
+        }
+        let cells: Vec<String> = rest
+            .trim()
+            .trim_matches('|')
+            .split('|')
+            .map(|cell| inline_to_plain(cell.trim()))
+            .collect();
+        return Some(format!("{}{}", indent, cells.join(CELL_SEPARATOR)));
+    }
+
+    // List items
+    let mut prefix = String::new();
+    if let Some(item) = rest
+        .strip_prefix("- ")
+        .or_else(|| rest.strip_prefix("* "))
+        .or_else(|| rest.strip_prefix("+ "))
+    {
+        prefix.push_str("• ");
+        rest = item;
+    } else {
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        if (1..=9).contains(&digits) {
+            let after = &rest[digits..];
+            if after.starts_with(". ") || after.starts_with(") ") {
+                prefix = format!("{}. ", &rest[..digits]);
+                rest = &after[2..];
+            }
+        }
+    }
+    if !prefix.is_empty() {
+        for task_box in ["[ ] ", "[x] ", "[X] "] {
+            if let Some(task) = rest.strip_prefix(task_box) {
+                rest = task;
+            }
+        }
+    }
+
+    Some(format!("{}{}{}", indent, prefix, inline_to_plain(rest)))
+}
+
+/// Length of the run of `c` starting at `at`
+fn run_len(chars: &[char], at: usize, c: char) -> usize {
+    chars[at..].iter().take_while(|x| **x == c).count()
+}
+
+/// Index of the closing bracket matching the opening one at `open`
+fn matching(chars: &[char], open: usize, open_c: char, close_c: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = open;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            c if c == open_c => depth += 1,
+            c if c == close_c => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Text of a link or image starting with `[` at `open`, and the index after
+/// it: `[text](url)` or `[text][ref]`
+fn parse_link(chars: &[char], open: usize) -> Option<(String, usize)> {
+    let close = matching(chars, open, '[', ']')?;
+    let text: String = chars[open + 1..close].iter().collect();
+    match chars.get(close + 1) {
+        Some('(') => {
+            let end = matching(chars, close + 1, '(', ')')?;
+            Some((text, end + 1))
+        }
+        Some('[') => {
+            let end = matching(chars, close + 1, '[', ']')?;
+            Some((text, end + 1))
+        }
+        _ => None,
+    }
+}
+
+/// Content of `<...>` at `open`: the URL of an autolink, `Some("")` for an
+/// HTML tag, `None` if it is neither
+fn parse_angle(chars: &[char], open: usize) -> Option<(String, usize)> {
+    let close = open + chars[open..].iter().position(|c| *c == '>')?;
+    let inner: String = chars[open + 1..close].iter().collect();
+    if inner.is_empty() {
+        return None;
+    }
+    let is_url = ["http://", "https://", "mailto:"]
+        .iter()
+        .any(|scheme| inner.starts_with(scheme))
+        && !inner.contains(' ');
+    if is_url {
+        return Some((inner.trim_start_matches("mailto:").to_string(), close + 1));
+    }
+    let name = inner.trim_start_matches('/');
+    let is_tag = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .take_while(|c| !c.is_whitespace() && *c != '/')
+            .all(|c| c.is_ascii_alphanumeric() || c == '-');
+    is_tag.then(|| (String::new(), close + 1))
+}
+
+/// Strip inline syntax
+fn inline_to_plain(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' if chars.get(i + 1).is_some_and(|n| n.is_ascii_punctuation()) => {
+                out.push(chars[i + 1]);
+                i += 2;
+            }
+            '`' => {
+                let n = run_len(&chars, i, '`');
+                let close = (i + n..chars.len()).find(|&j| {
+                    chars[j] == '`'
+                        && run_len(&chars, j, '`') == n
+                        && (j == 0 || chars[j - 1] != '`')
+                });
+                match close {
+                    Some(end) => {
+                        let code: String = chars[i + n..end].iter().collect();
+                        out.push_str(code.trim());
+                        i = end + n;
+                    }
+                    None => {
+                        out.extend(&chars[i..i + n]);
+                        i += n;
+                    }
+                }
+            }
+            '!' if chars.get(i + 1) == Some(&'[') => match parse_link(&chars, i + 1) {
+                Some((alt, next)) => {
+                    out.push_str(&inline_to_plain(&alt));
+                    i = next;
+                }
+                None => {
+                    out.push('!');
+                    i += 1;
+                }
+            },
+            '[' => match parse_link(&chars, i) {
+                Some((link_text, next)) => {
+                    out.push_str(&inline_to_plain(&link_text));
+                    i = next;
+                }
+                None => {
+                    out.push('[');
+                    i += 1;
+                }
+            },
+            '<' => match parse_angle(&chars, i) {
+                Some((content, next)) => {
+                    out.push_str(&content);
+                    i = next;
+                }
+                None => {
+                    out.push('<');
+                    i += 1;
+                }
+            },
+            '*' | '_' | '~' => {
+                let n = run_len(&chars, i, c);
+                let before = i.checked_sub(1).map(|j| chars[j]);
+                let after = chars.get(i + n).copied();
+                let space_before = before.is_none_or(char::is_whitespace);
+                let space_after = after.is_none_or(char::is_whitespace);
+                let intraword = before.is_some_and(char::is_alphanumeric)
+                    && after.is_some_and(char::is_alphanumeric);
+                let literal =
+                    (space_before && space_after) || (c == '_' && intraword) || (c == '~' && n < 2);
+                if literal {
+                    out.extend(&chars[i..i + n]);
+                }
+                i += n;
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Plain text of a Markdown document, with runs of blank lines collapsed
+pub fn to_plaintext(markdown: &str) -> String {
+    let mut stream = PlaintextStream::new();
+    let mut text = stream.push(markdown);
+    text.push_str(&stream.finish());
+
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.trim().lines().map(str::trim_end) {
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// One-line plain text summary of at most `max_chars` characters, for
+/// notification bodies and excerpts
+pub fn summary(markdown: &str, max_chars: usize) -> String {
+    let line = to_plaintext(markdown)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    match line.char_indices().nth(max_chars) {
+        Some((cut, _)) => format!("{}…", &line[..cut]),
+        None => line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline_syntax() {
+        assert_eq!(
+            inline_to_plain("**Bold**, _em_, ~~gone~~ and `code`"),
+            "Bold, em, gone and code"
+        );
+        assert_eq!(
+            inline_to_plain("See [the docs](https://x.dev/a_(b)) or ![a chart](c.png)"),
+            "See the docs or a chart"
+        );
+        assert_eq!(
+            inline_to_plain("<https://x.dev> and <br/> and 2 < 3"),
+            "https://x.dev and  and 2 < 3"
+        );
+        // Not emphasis
+        assert_eq!(
+            inline_to_plain("snake_case_name, 2 * 3, a~b"),
+            "snake_case_name, 2 * 3, a~b"
+        );
+        assert_eq!(inline_to_plain(r"\*literal\* and [x]"), "*literal* and [x]");
+        assert_eq!(inline_to_plain("``a ` b``"), "a ` b");
+    }
+
+    #[test]
+    fn test_blocks() {
+        let markdown = "# Title #\n\nSome *text*.\n\n---\n\n> quoted **bit**\n\n- [x] done\n* item\n2) second\n\n| A | B |\n|---|:-:|\n| `1` | 2 |\n\n```rust\nlet x = **y**;\n```\n";
+        assert_eq!(
+            to_plaintext(markdown),
+            "Title\n\nSome text.\n\nquoted bit\n\n• done\n• item\n2. second\n\nA · B\n1 · 2\n\nlet x = **y**;"
+        );
+    }
+
+    #[test]
+    fn test_stream_matches_whole_conversion() {
+        let markdown = "## Plan\n\n1. **Fetch** data\n```\nraw *text*\n```\nDone `now`";
+        let mut stream = PlaintextStream::new();
+        let mut streamed = String::new();
+        for chunk in markdown.as_bytes().chunks(5) {
+            streamed.push_str(&stream.push(std::str::from_utf8(chunk).unwrap()));
+        }
+        streamed.push_str(&stream.finish());
+        assert_eq!(streamed, "Plan\n\n1. Fetch data\nraw *text*\nDone now");
+
+        // Incomplete lines are held back
+        let mut stream = PlaintextStream::new();
+        assert_eq!(stream.push("**Hel"), "");
+        assert_eq!(stream.push("lo**\nwor"), "Hello\n");
+        assert_eq!(stream.finish(), "wor");
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(
+            summary("# Answer\n\nUse `cargo test`\n- fast", 100),
+            "Answer Use cargo test • fast"
+        );
+        assert_eq!(summary("**abcdef**", 3), "abc…");
+    }
+}
@@ -0,0 +1,268 @@
+//! Portable mode
+//!
+//! Started with `--portable`, or with a `portable` file next to the
+//! executable, the app keeps everything in a `MoltzData` folder beside the
+//! executable instead of the user profile, so it can run from a USB stick
+//! or on a machine where it can't write elsewhere:
+//! - stores and settings in `MoltzData/data`
+//! - logs in `MoltzData/logs`, caches in `MoltzData/cache`
+//! - the webview's data, including the conversation database, in
+//!   `MoltzData/webview` (WebView2 user data folder on Windows, XDG
+//!   directories on Linux; WKWebView on macOS can't be moved)
+//! - secrets in an encrypted file instead of the OS keychain
+//!
+//! The secrets file is sealed with AES-256-GCM under a key kept in the same
+//! folder: it keeps tokens out of plain text, but whoever holds the folder
+//! holds the secrets, as with the rest of the portable data.
+
+use crate::store::{self, SEAL_KEY_LEN};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// Command-line flag enabling portable mode
+pub const PORTABLE_FLAG: &str = "--portable";
+
+/// File next to the executable enabling portable mode
+pub const MARKER_FILE: &str = "portable";
+
+/// Folder next to the executable holding the portable data
+const DATA_FOLDER: &str = "MoltzData";
+
+/// Encrypted secrets and their key, in the data directory
+const SECRETS_FILE: &str = "secrets.bin";
+const SECRETS_KEY_FILE: &str = "secrets.key";
+
+/// Portable data folder, resolved once at startup (None = not portable)
+static ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+static SECRETS: OnceLock<SecretFile> = OnceLock::new();
+
+/// Portable data folder for these arguments and executable directory
+fn detect(args: &[String], exe_dir: &Path) -> Option<PathBuf> {
+    let enabled =
+        args.iter().any(|arg| arg == PORTABLE_FLAG) || exe_dir.join(MARKER_FILE).is_file();
+    enabled.then(|| exe_dir.join(DATA_FOLDER))
+}
+
+/// Detect portable mode and point the webview at the portable folder
+///
+/// Called first thing in `run`, before any window or thread exists.
+pub fn init() {
+    let root = ROOT.get_or_init(|| {
+        let exe_dir = std::env::current_exe()
+            .ok()?
+            .parent()
+            .map(Path::to_path_buf)?;
+        let args: Vec<String> = std::env::args().collect();
+        detect(&args, &exe_dir)
+    });
+    let Some(root) = root else {
+        return;
+    };
+    log::info!("[Portable] Keeping app data in {}", root.display());
+    if let Err(e) = std::fs::create_dir_all(root) {
+        log::error!("[Portable] Failed to create {}: {}", root.display(), e);
+    }
+
+    // The webview reads these when it starts: its profile (IndexedDB, where
+    // the conversation database lives) follows them into the folder
+    #[cfg(target_os = "windows")]
+    if let Some(webview) = webview_dir() {
+        std::env::set_var("WEBVIEW2_USER_DATA_FOLDER", webview);
+    }
+    #[cfg(target_os = "linux")]
+    {
+        std::env::set_var("XDG_DATA_HOME", root.join("webview"));
+        std::env::set_var("XDG_CACHE_HOME", root.join("cache"));
+    }
+}
+
+/// Portable data folder, if running portable
+pub fn root() -> Option<&'static Path> {
+    ROOT.get().and_then(|root| root.as_deref())
+}
+
+/// Directory for stores and settings
+pub fn data_dir() -> Option<PathBuf> {
+    root().map(|root| root.join("data"))
+}
+
+pub fn log_dir() -> Option<PathBuf> {
+    root().map(|root| root.join("logs"))
+}
+
+pub fn cache_dir() -> Option<PathBuf> {
+    root().map(|root| root.join("cache"))
+}
+
+/// WebView2 user data folder, if running portable
+#[cfg(target_os = "windows")]
+pub fn webview_dir() -> Option<PathBuf> {
+    root().map(|root| root.join("webview"))
+}
+
+/// Encrypted secrets file replacing the keychain, if running portable
+pub fn secret_file() -> Option<&'static SecretFile> {
+    let dir = data_dir()?;
+    Some(SECRETS.get_or_init(|| SecretFile::new(&dir)))
+}
+
+fn failure(error: impl std::fmt::Display) -> keyring::Error {
+    keyring::Error::PlatformFailure(error.to_string().into())
+}
+
+/// Encrypted file standing in for the OS keychain
+///
+/// Errors are `keyring` errors so callers treat both backends alike
+/// (a missing value is `NoEntry`).
+pub struct SecretFile {
+    path: PathBuf,
+    key_path: PathBuf,
+    /// Serializes read-modify-write cycles
+    lock: Mutex<()>,
+}
+
+impl SecretFile {
+    fn new(dir: &Path) -> Self {
+        Self {
+            path: dir.join(SECRETS_FILE),
+            key_path: dir.join(SECRETS_KEY_FILE),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// The file's key, created on first use
+    fn key(&self) -> Result<[u8; SEAL_KEY_LEN], keyring::Error> {
+        match std::fs::read(&self.key_path) {
+            Ok(bytes) => bytes.try_into().map_err(|_| failure("Corrupt secrets key")),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let key: [u8; SEAL_KEY_LEN] = rand::random();
+                store::write_atomic(&self.key_path, &key).map_err(failure)?;
+                Ok(key)
+            }
+            Err(e) => Err(failure(e)),
+        }
+    }
+
+    fn read(&self, key: &[u8; SEAL_KEY_LEN]) -> Result<HashMap<String, String>, keyring::Error> {
+        let sealed = match std::fs::read(&self.path) {
+            Ok(sealed) => sealed,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(e) => return Err(failure(e)),
+        };
+        let plaintext = store::open(key, &sealed).map_err(failure)?;
+        serde_json::from_slice(&plaintext).map_err(failure)
+    }
+
+    fn write(
+        &self,
+        key: &[u8; SEAL_KEY_LEN],
+        secrets: &HashMap<String, String>,
+    ) -> Result<(), keyring::Error> {
+        let plaintext = serde_json::to_vec(secrets).map_err(failure)?;
+        let sealed = store::seal(key, &plaintext).map_err(failure)?;
+        store::write_atomic(&self.path, &sealed).map_err(failure)
+    }
+
+    fn entry(service: &str, key: &str) -> String {
+        format!("{}/{}", service, key)
+    }
+
+    pub fn get(&self, service: &str, key: &str) -> Result<String, keyring::Error> {
+        let _guard = self.lock.lock().map_err(failure)?;
+        let secrets = self.read(&self.key()?)?;
+        secrets
+            .get(&Self::entry(service, key))
+            .cloned()
+            .ok_or(keyring::Error::NoEntry)
+    }
+
+    pub fn set(&self, service: &str, key: &str, value: &str) -> Result<(), keyring::Error> {
+        let _guard = self.lock.lock().map_err(failure)?;
+        let file_key = self.key()?;
+        let mut secrets = self.read(&file_key)?;
+        secrets.insert(Self::entry(service, key), value.to_string());
+        self.write(&file_key, &secrets)
+    }
+
+    pub fn delete(&self, service: &str, key: &str) -> Result<(), keyring::Error> {
+        let _guard = self.lock.lock().map_err(failure)?;
+        let file_key = self.key()?;
+        let mut secrets = self.read(&file_key)?;
+        if secrets.remove(&Self::entry(service, key)).is_none() {
+            return Err(keyring::Error::NoEntry);
+        }
+        self.write(&file_key, &secrets)
+    }
+}
+
+/// Whether the app runs portable, and from where
+#[derive(Debug, Clone, Serialize)]
+pub struct PortableStatus {
+    pub enabled: bool,
+    pub root: Option<String>,
+}
+
+/// Report whether the app runs in portable mode
+#[tauri::command]
+pub async fn get_portable_status() -> Result<PortableStatus, crate::error::CommandError> {
+    Ok(PortableStatus {
+        enabled: root().is_some(),
+        root: root().map(|root| root.display().to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("moltz-portable-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_detect() {
+        let dir = temp_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(detect(&args(&["moltz"]), &dir), None);
+        assert_eq!(
+            detect(&args(&["moltz", PORTABLE_FLAG]), &dir),
+            Some(dir.join(DATA_FOLDER))
+        );
+        std::fs::write(dir.join(MARKER_FILE), "").unwrap();
+        assert_eq!(detect(&args(&["moltz"]), &dir), Some(dir.join(DATA_FOLDER)));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_secret_file() {
+        let dir = temp_dir();
+        let secrets = SecretFile::new(&dir);
+
+        assert!(matches!(
+            secrets.get("svc", "token"),
+            Err(keyring::Error::NoEntry)
+        ));
+        secrets.set("svc", "token", "secret-value").unwrap();
+        secrets.set("svc", "other", "second").unwrap();
+        assert_eq!(secrets.get("svc", "token").unwrap(), "secret-value");
+
+        // Nothing readable on disk
+        let on_disk = std::fs::read(dir.join(SECRETS_FILE)).unwrap();
+        assert!(!String::from_utf8_lossy(&on_disk).contains("secret-value"));
+
+        secrets.delete("svc", "token").unwrap();
+        assert!(matches!(
+            secrets.get("svc", "token"),
+            Err(keyring::Error::NoEntry)
+        ));
+        assert_eq!(secrets.get("svc", "other").unwrap(), "second");
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
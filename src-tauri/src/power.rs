@@ -0,0 +1,172 @@
+//! Power-status monitor
+//!
+//! Polls whether the machine runs on battery and throttles background work
+//! while it does:
+//! - Gateway pings are sent less often
+//! - Periodic and reconnect-triggered update checks are deferred until the
+//!   machine is back on AC power
+//!
+//! Changes are emitted as `power:changed` so the frontend can pause its own
+//! background work. Desktops (no battery) are always treated as on AC power.
+
+use crate::error::CommandError;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// How often the power source is checked
+const POWER_CHECK_INTERVAL_SECS: u64 = 60;
+
+/// Ping interval multiplier while on battery
+pub const BATTERY_PING_INTERVAL_FACTOR: u64 = 3;
+
+/// Power status sent to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct PowerStatus {
+    #[serde(rename = "onBattery")]
+    pub on_battery: bool,
+}
+
+/// Power state managed by Tauri
+#[derive(Default)]
+pub struct PowerState {
+    on_battery: AtomicBool,
+}
+
+/// Whether the machine currently runs on battery
+pub fn on_battery<R: Runtime>(app: &AppHandle<R>) -> bool {
+    app.try_state::<PowerState>()
+        .is_some_and(|state| state.on_battery.load(Ordering::Relaxed))
+}
+
+/// Scale a background interval for the current power source
+pub fn throttled_interval<R: Runtime>(app: &AppHandle<R>, interval: Duration) -> Duration {
+    if on_battery(app) {
+        interval * BATTERY_PING_INTERVAL_FACTOR as u32
+    } else {
+        interval
+    }
+}
+
+/// Decide from `/sys/class/power_supply` entries (type, online) whether we
+/// run on battery: a battery is present and no mains/USB supply is online
+#[cfg(any(target_os = "linux", test))]
+fn on_battery_from_supplies(supplies: &[(String, bool)]) -> bool {
+    let has_battery = supplies.iter().any(|(kind, _)| kind == "Battery");
+    let external_online = supplies
+        .iter()
+        .any(|(kind, online)| kind != "Battery" && *online);
+    has_battery && !external_online
+}
+
+#[cfg(target_os = "linux")]
+fn read_on_battery() -> bool {
+    let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+        return false;
+    };
+    let supplies: Vec<(String, bool)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            // Skip peripherals (wireless mice, keyboards) that report a battery
+            let scope = std::fs::read_to_string(path.join("scope")).unwrap_or_default();
+            if scope.trim() == "Device" {
+                return None;
+            }
+            let kind = std::fs::read_to_string(path.join("type")).ok()?;
+            let online = std::fs::read_to_string(path.join("online"))
+                .map(|v| v.trim() == "1")
+                .unwrap_or(false);
+            Some((kind.trim().to_string(), online))
+        })
+        .collect();
+    on_battery_from_supplies(&supplies)
+}
+
+#[cfg(target_os = "macos")]
+fn read_on_battery() -> bool {
+    std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).contains("'Battery Power'"))
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "windows")]
+fn read_on_battery() -> bool {
+    use windows_sys::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut status: SYSTEM_POWER_STATUS = unsafe { std::mem::zeroed() };
+    // SAFETY: `status` is a valid, writable SYSTEM_POWER_STATUS
+    if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+        return false;
+    }
+    // 0 = offline, 1 = online, 255 = unknown
+    status.ACLineStatus == 0
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn read_on_battery() -> bool {
+    false
+}
+
+/// Start polling the power source
+pub fn start_power_monitor(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let on_battery = tokio::task::spawn_blocking(read_on_battery)
+                .await
+                .unwrap_or(false);
+
+            let state = app.state::<PowerState>();
+            let was_on_battery = state.on_battery.swap(on_battery, Ordering::Relaxed);
+            if was_on_battery != on_battery {
+                log::info!(
+                    "[Power] Now on {}",
+                    if on_battery { "battery" } else { "AC power" }
+                );
+                let _ = app.emit("power:changed", PowerStatus { on_battery });
+            }
+
+            tokio::time::sleep(Duration::from_secs(POWER_CHECK_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+/// Get the current power status
+#[tauri::command]
+pub async fn get_power_status(app: AppHandle) -> Result<PowerStatus, CommandError> {
+    Ok(PowerStatus {
+        on_battery: on_battery(&app),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_on_battery_from_supplies() {
+        let supply = |kind: &str, online: bool| (kind.to_string(), online);
+
+        // Laptop unplugged
+        assert!(on_battery_from_supplies(&[
+            supply("Mains", false),
+            supply("Battery", false),
+        ]));
+        // Laptop plugged in (over mains or USB-C)
+        assert!(!on_battery_from_supplies(&[
+            supply("Mains", true),
+            supply("Battery", false),
+        ]));
+        assert!(!on_battery_from_supplies(&[
+            supply("USB", true),
+            supply("Battery", false),
+        ]));
+        // Desktop without a battery
+        assert!(!on_battery_from_supplies(&[supply("Mains", true)]));
+        assert!(!on_battery_from_supplies(&[]));
+    }
+}
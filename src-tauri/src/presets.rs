@@ -0,0 +1,301 @@
+//! Conversation presets
+//!
+//! A preset bundles the setup of a new conversation: system prompt, model,
+//! thinking level and sampling settings. Applying a preset to a session sends
+//! it to the Gateway with `sessions.patch`; new sessions created with a
+//! preset are patched as part of their setup.
+//!
+//! Presets are stored locally and can be shared as `.json` preset files.
+
+use crate::error::CommandError;
+use crate::gateway::GatewayState;
+use crate::guest;
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime, State};
+use tokio::sync::Mutex;
+
+/// Store file name for presets
+const PRESETS_FILE: &str = "presets.json";
+
+/// Version of the preset file format
+const PRESET_FILE_VERSION: u32 = 1;
+
+/// Thinking levels accepted by the Gateway
+const THINKING_LEVELS: &[&str] = &["off", "minimal", "low", "medium", "high"];
+
+/// A conversation preset
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Preset {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "systemPrompt")]
+    pub system_prompt: Option<String>,
+    pub model: Option<String>,
+    /// One of [`THINKING_LEVELS`]
+    pub thinking: Option<String>,
+    /// Sampling temperature (0.0 - 2.0)
+    pub temperature: Option<f32>,
+    #[serde(rename = "maxTokens")]
+    pub max_tokens: Option<u32>,
+}
+
+impl Preset {
+    fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Preset name cannot be empty".to_string());
+        }
+        if let Some(thinking) = &self.thinking {
+            if !THINKING_LEVELS.contains(&thinking.as_str()) {
+                return Err(format!("Invalid thinking level '{}'", thinking));
+            }
+        }
+        if let Some(temperature) = self.temperature {
+            if !(0.0..=2.0).contains(&temperature) {
+                return Err("Temperature must be between 0 and 2".to_string());
+            }
+        }
+        if self.max_tokens == Some(0) {
+            return Err("Max tokens must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// `sessions.patch` params applying this preset to a session
+    pub fn session_patch(&self, session_key: &str) -> serde_json::Value {
+        let mut params = serde_json::json!({ "key": session_key });
+        if let Some(system_prompt) = &self.system_prompt {
+            params["systemPrompt"] = serde_json::json!(system_prompt);
+        }
+        if let Some(model) = &self.model {
+            params["model"] = serde_json::json!(model);
+        }
+        if let Some(thinking) = &self.thinking {
+            params["thinkingLevel"] = serde_json::json!(thinking);
+        }
+        if let Some(temperature) = self.temperature {
+            params["temperature"] = serde_json::json!(temperature);
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            params["maxTokens"] = serde_json::json!(max_tokens);
+        }
+        params
+    }
+}
+
+/// Shared preset file
+#[derive(Debug, Serialize, Deserialize)]
+struct PresetFile {
+    version: u32,
+    presets: Vec<Preset>,
+}
+
+/// Parse a preset file, giving imported presets fresh IDs
+fn parse_preset_file(content: &str) -> Result<Vec<Preset>, String> {
+    let file: PresetFile =
+        serde_json::from_str(content).map_err(|e| format!("Invalid preset file: {}", e))?;
+    if file.version > PRESET_FILE_VERSION {
+        return Err(format!(
+            "Preset file version {} is newer than supported ({})",
+            file.version, PRESET_FILE_VERSION
+        ));
+    }
+    file.presets
+        .into_iter()
+        .map(|mut preset| {
+            preset.validate()?;
+            preset.id = uuid::Uuid::new_v4().to_string();
+            Ok(preset)
+        })
+        .collect()
+}
+
+/// Preset state managed by Tauri
+pub struct PresetState {
+    path: PathBuf,
+    presets: Mutex<Vec<Preset>>,
+}
+
+impl PresetState {
+    /// Load presets from the local store
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, PRESETS_FILE);
+        Self {
+            presets: Mutex::new(store::load(&path)),
+            path,
+        }
+    }
+
+    /// Find a preset by ID
+    pub async fn get(&self, id: &str) -> Option<Preset> {
+        self.presets
+            .lock()
+            .await
+            .iter()
+            .find(|p| p.id == id)
+            .cloned()
+    }
+
+    /// All presets, in the order they were created
+    pub async fn all(&self) -> Vec<Preset> {
+        self.presets.lock().await.clone()
+    }
+}
+
+/// List presets
+#[tauri::command]
+pub async fn list_presets(state: State<'_, PresetState>) -> Result<Vec<Preset>, CommandError> {
+    Ok(state.presets.lock().await.clone())
+}
+
+/// Create a preset (empty ID) or update an existing one
+#[tauri::command]
+pub async fn save_preset(
+    state: State<'_, PresetState>,
+    mut preset: Preset,
+) -> Result<Preset, CommandError> {
+    preset.validate().map_err(CommandError::validation)?;
+    let mut presets = state.presets.lock().await;
+    if preset.id.is_empty() {
+        preset.id = uuid::Uuid::new_v4().to_string();
+        presets.push(preset.clone());
+    } else {
+        let existing = presets
+            .iter_mut()
+            .find(|p| p.id == preset.id)
+            .ok_or_else(|| CommandError::validation(format!("Preset not found: {}", preset.id)))?;
+        *existing = preset.clone();
+    }
+    store::save(&state.path, &*presets)?;
+    Ok(preset)
+}
+
+/// Delete a preset
+#[tauri::command]
+pub async fn delete_preset(state: State<'_, PresetState>, id: String) -> Result<(), CommandError> {
+    let mut presets = state.presets.lock().await;
+    let before = presets.len();
+    presets.retain(|p| p.id != id);
+    if presets.len() == before {
+        return Err(CommandError::validation(format!(
+            "Preset not found: {}",
+            id
+        )));
+    }
+    store::save(&state.path, &*presets).map_err(CommandError::internal)
+}
+
+/// Apply a preset to a session on the Gateway
+#[tauri::command]
+pub async fn apply_preset(
+    state: State<'_, PresetState>,
+    gateway: State<'_, GatewayState>,
+    session_key: String,
+    preset_id: String,
+) -> Result<(), CommandError> {
+    let preset = state
+        .get(&preset_id)
+        .await
+        .ok_or_else(|| CommandError::validation(format!("Preset not found: {}", preset_id)))?;
+    gateway
+        .request("sessions.patch", Some(preset.session_patch(&session_key)))
+        .await
+        .map(|_| ())
+        .map_err(CommandError::from)
+}
+
+/// Export presets (all, or the given IDs) to a preset file
+#[tauri::command]
+pub async fn export_presets(
+    state: State<'_, PresetState>,
+    path: String,
+    ids: Option<Vec<String>>,
+) -> Result<usize, CommandError> {
+    guest::ensure_inactive("Exporting presets")?;
+    let presets: Vec<Preset> = state
+        .presets
+        .lock()
+        .await
+        .iter()
+        .filter(|p| ids.as_ref().is_none_or(|ids| ids.contains(&p.id)))
+        .cloned()
+        .collect();
+    let count = presets.len();
+    let file = PresetFile {
+        version: PRESET_FILE_VERSION,
+        presets,
+    };
+    let json = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json)
+        .map_err(|e| CommandError::io(format!("Failed to write {}: {}", path, e)))?;
+    Ok(count)
+}
+
+/// Import presets from a preset file, adding them to the existing ones
+#[tauri::command]
+pub async fn import_presets(
+    state: State<'_, PresetState>,
+    path: String,
+) -> Result<Vec<Preset>, CommandError> {
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| CommandError::io(format!("Failed to read {}: {}", path, e)))?;
+    let imported = parse_preset_file(&content).map_err(CommandError::validation)?;
+
+    let mut presets = state.presets.lock().await;
+    presets.extend(imported.iter().cloned());
+    store::save(&state.path, &*presets)?;
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset() -> Preset {
+        Preset {
+            id: "p1".to_string(),
+            name: "Code reviewer".to_string(),
+            system_prompt: Some("Review code tersely".to_string()),
+            model: Some("anthropic/claude-sonnet-4".to_string()),
+            thinking: Some("high".to_string()),
+            temperature: None,
+            max_tokens: Some(4096),
+        }
+    }
+
+    #[test]
+    fn test_session_patch_includes_set_fields_only() {
+        let params = preset().session_patch("moltz:abc");
+        assert_eq!(params["key"], "moltz:abc");
+        assert_eq!(params["systemPrompt"], "Review code tersely");
+        assert_eq!(params["thinkingLevel"], "high");
+        assert_eq!(params["maxTokens"], 4096);
+        assert!(params.get("temperature").is_none());
+    }
+
+    #[test]
+    fn test_preset_file_round_trip_and_validation() {
+        let file = PresetFile {
+            version: PRESET_FILE_VERSION,
+            presets: vec![preset()],
+        };
+        let imported = parse_preset_file(&serde_json::to_string(&file).unwrap()).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].name, "Code reviewer");
+        // Imports never clobber existing presets
+        assert_ne!(imported[0].id, "p1");
+
+        let mut invalid = preset();
+        invalid.thinking = Some("extreme".to_string());
+        let file = PresetFile {
+            version: PRESET_FILE_VERSION,
+            presets: vec![invalid],
+        };
+        assert!(parse_preset_file(&serde_json::to_string(&file).unwrap()).is_err());
+
+        let future = r#"{"version": 99, "presets": []}"#;
+        assert!(parse_preset_file(future).is_err());
+    }
+}
@@ -0,0 +1,362 @@
+//! Local history of Quick Ask prompts, for recall and autocomplete
+//!
+//! Questions sent from Quick Ask are remembered so the window can suggest
+//! them again as the user types (`get_prompt_suggestions`). The history never
+//! leaves the machine:
+//! - it is sealed in `prompt_history.bin` under a key kept in the keychain,
+//!   and only decrypted in memory on first use
+//! - it is not part of diagnostics bundles, settings files or logs (prompt
+//!   text is never logged)
+//! - turning it off clears it, and it can be cleared at any time
+//!
+//! Repeated prompts are stored once (compared with whitespace collapsed and
+//! case ignored) with a use count; the least recently used are dropped past
+//! the configured size.
+
+use crate::error::CommandError;
+use crate::store::{self, SEAL_KEY_LEN};
+use crate::{guest, keychain};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime, State};
+use tokio::sync::Mutex;
+
+/// Store file name for the settings (plain JSON, nothing private)
+const SETTINGS_FILE: &str = "prompt_history.json";
+
+/// Store file name for the sealed history
+const HISTORY_FILE: &str = "prompt_history.bin";
+
+/// Keychain key holding the history's encryption key
+const HISTORY_KEY_NAME: &str = "prompt_history_key";
+
+const DEFAULT_MAX_ENTRIES: usize = 200;
+const MAX_ENTRIES_LIMIT: usize = 2_000;
+
+/// Suggestions returned when the caller doesn't ask for a number
+const DEFAULT_SUGGESTIONS: usize = 5;
+const MAX_SUGGESTIONS: usize = 20;
+
+/// History settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptHistorySettings {
+    pub enabled: bool,
+    /// Prompts kept; the least recently used are dropped first
+    #[serde(rename = "maxEntries")]
+    pub max_entries: usize,
+}
+
+impl Default for PromptHistorySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_entries: DEFAULT_MAX_ENTRIES,
+        }
+    }
+}
+
+impl PromptHistorySettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_ENTRIES_LIMIT).contains(&self.max_entries) {
+            return Err(format!("Keep between 1 and {} prompts", MAX_ENTRIES_LIMIT));
+        }
+        Ok(())
+    }
+}
+
+/// Settings plus the number of prompts kept, for the settings pane
+#[derive(Debug, Clone, Serialize)]
+pub struct PromptHistoryStatus {
+    #[serde(flatten)]
+    pub settings: PromptHistorySettings,
+    /// None until the history has been unlocked this session
+    pub entries: Option<usize>,
+}
+
+/// A remembered prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptEntry {
+    /// Latest wording used
+    pub prompt: String,
+    pub count: u32,
+    #[serde(rename = "lastUsed")]
+    pub last_used: DateTime<Utc>,
+}
+
+/// Whitespace collapsed and lowercased, to spot repeats and match prefixes
+fn normalize(prompt: &str) -> String {
+    prompt
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Remember a prompt, keeping at most `max_entries` (history is oldest first)
+fn record(history: &mut Vec<PromptEntry>, prompt: &str, max_entries: usize, now: DateTime<Utc>) {
+    let key = normalize(prompt);
+    let count = match history.iter().position(|e| normalize(&e.prompt) == key) {
+        Some(index) => history.remove(index).count.saturating_add(1),
+        None => 1,
+    };
+    history.push(PromptEntry {
+        prompt: prompt.to_string(),
+        count,
+        last_used: now,
+    });
+    let excess = history.len().saturating_sub(max_entries);
+    history.drain(..excess);
+}
+
+/// Prompts starting with `prefix`, most used first (most recent first for an
+/// empty prefix); the prompt already typed in full is left out
+fn suggest(history: &[PromptEntry], prefix: &str, limit: usize) -> Vec<PromptEntry> {
+    let prefix = normalize(prefix);
+    let mut matches: Vec<&PromptEntry> = history
+        .iter()
+        .filter(|e| {
+            let prompt = normalize(&e.prompt);
+            prompt.starts_with(&prefix) && prompt != prefix
+        })
+        .collect();
+    if prefix.is_empty() {
+        matches.sort_by(|a, b| b.last_used.cmp(&a.last_used));
+    } else {
+        matches.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| b.last_used.cmp(&a.last_used))
+        });
+    }
+    matches.into_iter().take(limit).cloned().collect()
+}
+
+struct Unlocked {
+    key: [u8; SEAL_KEY_LEN],
+    /// Oldest first
+    entries: Vec<PromptEntry>,
+}
+
+struct PromptHistoryInner {
+    settings: PromptHistorySettings,
+    /// Decrypted on first use, so startup doesn't touch the keychain
+    unlocked: Option<Unlocked>,
+}
+
+/// Prompt history managed by Tauri
+pub struct PromptHistoryState {
+    settings_path: PathBuf,
+    history_path: PathBuf,
+    inner: Mutex<PromptHistoryInner>,
+}
+
+impl PromptHistoryState {
+    /// Load the settings; the history itself stays sealed until used
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let settings_path = store::store_path(app, SETTINGS_FILE);
+        let settings = store::load(&settings_path);
+        Self {
+            settings_path,
+            history_path: store::store_path(app, HISTORY_FILE),
+            inner: Mutex::new(PromptHistoryInner {
+                settings,
+                unlocked: None,
+            }),
+        }
+    }
+
+    /// The decrypted history, unsealing it on first use
+    async fn unlock<'a>(
+        &self,
+        inner: &'a mut PromptHistoryInner,
+    ) -> Result<&'a mut Unlocked, CommandError> {
+        let unlocked = match inner.unlocked.take() {
+            Some(unlocked) => unlocked,
+            None => {
+                let key = keychain::store_key(HISTORY_KEY_NAME).await?;
+                let entries = store::load_sealed(&self.history_path, &key);
+                Unlocked { key, entries }
+            }
+        };
+        Ok(inner.unlocked.insert(unlocked))
+    }
+
+    /// Remember a prompt sent from Quick Ask, if the history is on (and not
+    /// in guest mode)
+    pub async fn record(&self, prompt: &str) {
+        let mut inner = self.inner.lock().await;
+        if !inner.settings.enabled || guest::active() {
+            return;
+        }
+        let max_entries = inner.settings.max_entries;
+        let result = match self.unlock(&mut inner).await {
+            Ok(unlocked) => {
+                record(&mut unlocked.entries, prompt, max_entries, Utc::now());
+                store::save_sealed(&self.history_path, &unlocked.key, &unlocked.entries)
+                    .map_err(CommandError::internal)
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            log::error!("[PromptHistory] Failed to record a prompt: {}", e);
+        }
+    }
+
+    async fn suggestions(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<PromptEntry>, CommandError> {
+        let mut inner = self.inner.lock().await;
+        if !inner.settings.enabled {
+            return Ok(Vec::new());
+        }
+        let unlocked = self.unlock(&mut inner).await?;
+        Ok(suggest(&unlocked.entries, prefix, limit))
+    }
+
+    /// Forget every prompt
+    async fn clear(&self) -> Result<(), CommandError> {
+        let mut inner = self.inner.lock().await;
+        if let Some(unlocked) = inner.unlocked.as_mut() {
+            unlocked.entries.clear();
+        }
+        remove_history(&self.history_path)
+    }
+
+    /// Replace the settings; turning the history off also clears it
+    async fn apply(&self, settings: PromptHistorySettings) -> Result<(), CommandError> {
+        let mut inner = self.inner.lock().await;
+        store::save(&self.settings_path, &settings)?;
+        if !settings.enabled {
+            inner.unlocked = None;
+            remove_history(&self.history_path)?;
+        } else if let Some(unlocked) = inner.unlocked.as_mut() {
+            let excess = unlocked.entries.len().saturating_sub(settings.max_entries);
+            if excess > 0 {
+                unlocked.entries.drain(..excess);
+                store::save_sealed(&self.history_path, &unlocked.key, &unlocked.entries)?;
+            }
+        }
+        inner.settings = settings;
+        Ok(())
+    }
+
+    async fn status(&self) -> PromptHistoryStatus {
+        let inner = self.inner.lock().await;
+        PromptHistoryStatus {
+            settings: inner.settings.clone(),
+            entries: inner.unlocked.as_ref().map(|u| u.entries.len()),
+        }
+    }
+}
+
+fn remove_history(path: &std::path::Path) -> Result<(), CommandError> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(CommandError::internal(e.to_string())),
+    }
+}
+
+/// Remembered Quick Ask prompts starting with `prefix`, best first
+#[tauri::command]
+pub async fn get_prompt_suggestions(
+    state: State<'_, PromptHistoryState>,
+    prefix: String,
+    limit: Option<usize>,
+) -> Result<Vec<PromptEntry>, CommandError> {
+    let limit = limit
+        .unwrap_or(DEFAULT_SUGGESTIONS)
+        .clamp(1, MAX_SUGGESTIONS);
+    state.suggestions(&prefix, limit).await
+}
+
+/// Get the history settings and size
+#[tauri::command]
+pub async fn get_prompt_history(
+    state: State<'_, PromptHistoryState>,
+) -> Result<PromptHistoryStatus, CommandError> {
+    Ok(state.status().await)
+}
+
+/// Turn the history on or off and set its size
+///
+/// Turning it off also clears it.
+#[tauri::command]
+pub async fn set_prompt_history(
+    state: State<'_, PromptHistoryState>,
+    settings: PromptHistorySettings,
+) -> Result<PromptHistoryStatus, CommandError> {
+    settings.validate().map_err(CommandError::validation)?;
+    state.apply(settings).await?;
+    Ok(state.status().await)
+}
+
+/// Forget all remembered prompts
+#[tauri::command]
+pub async fn clear_prompt_history(
+    state: State<'_, PromptHistoryState>,
+) -> Result<PromptHistoryStatus, CommandError> {
+    state.clear().await?;
+    Ok(state.status().await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_record_merges_repeats() {
+        let now = Utc::now();
+        let mut history = Vec::new();
+        record(&mut history, "What is Rust?", 10, now);
+        record(&mut history, "Summarize this", 10, now);
+        record(
+            &mut history,
+            "what  is rust?",
+            10,
+            now + Duration::seconds(1),
+        );
+
+        assert_eq!(history.len(), 2);
+        let last = history.last().unwrap();
+        assert_eq!(last.prompt, "what  is rust?");
+        assert_eq!(last.count, 2);
+
+        // The least recently used go first
+        record(&mut history, "Translate to French", 2, now);
+        let prompts: Vec<&str> = history.iter().map(|e| e.prompt.as_str()).collect();
+        assert_eq!(prompts, ["what  is rust?", "Translate to French"]);
+    }
+
+    #[test]
+    fn test_suggest() {
+        let now = Utc::now();
+        let entry = |prompt: &str, count, age_secs| PromptEntry {
+            prompt: prompt.to_string(),
+            count,
+            last_used: now - Duration::seconds(age_secs),
+        };
+        let history = vec![
+            entry("Summarize the meeting", 1, 10),
+            entry("Summarize this article", 4, 300),
+            entry("Translate to French", 2, 0),
+            entry("summarize", 9, 5),
+        ];
+        let prompts =
+            |entries: Vec<PromptEntry>| entries.into_iter().map(|e| e.prompt).collect::<Vec<_>>();
+
+        assert_eq!(
+            prompts(suggest(&history, "SUMMARIZE ", 5)),
+            ["Summarize this article", "Summarize the meeting"]
+        );
+        assert_eq!(
+            prompts(suggest(&history, "", 2)),
+            ["Translate to French", "summarize"]
+        );
+        assert!(suggest(&history, "nothing", 5).is_empty());
+    }
+}
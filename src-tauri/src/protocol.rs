@@ -4,12 +4,17 @@
 //! - Error classification (network, gateway, auth)
 //! - Protocol message validation
 //! - Connection state management
-//! - Retry strategies
+//! - Send guard: how a message is handled in the current state
+//! - Stream timeouts for reasoning models
+//! - Gateway version compatibility checks
+//! - Retry strategies and per-method retry policies
 
 #![allow(dead_code)]
 
+use crate::error::{CommandError, ErrorCode};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 /// Current protocol version
@@ -18,6 +23,9 @@ pub const PROTOCOL_VERSION: i32 = 3;
 /// Connection configuration defaults
 pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
 pub const DEFAULT_STREAM_TIMEOUT_SECS: u64 = 60;
+pub const REASONING_STREAM_TIMEOUT_SECS: u64 = 180;
+pub const MIN_STREAM_TIMEOUT_SECS: u64 = 10;
+pub const MAX_STREAM_TIMEOUT_SECS: u64 = 1_800;
 pub const DEFAULT_PING_INTERVAL_SECS: u64 = 30;
 pub const DEFAULT_PING_TIMEOUT_SECS: u64 = 10;
 
@@ -27,6 +35,10 @@ pub const BACKOFF_MAX_MS: u64 = 60_000; // 60 seconds
 pub const BACKOFF_MULTIPLIER: f64 = 2.0;
 pub const MAX_RECONNECT_ATTEMPTS: u32 = 10;
 
+/// Request retry configuration (much shorter than reconnect backoff)
+pub const REQUEST_RETRY_INITIAL_MS: u64 = 500;
+pub const REQUEST_RETRY_MAX_MS: u64 = 4_000;
+
 // ============================================================================
 // Error Classification
 // ============================================================================
@@ -227,12 +239,21 @@ pub enum ConnectionState {
     /// Failed to connect, given up
     Failed {
         reason: String,
+        code: ErrorCode,
         #[serde(rename = "canRetry")]
         can_retry: bool,
     },
 }
 
 impl ConnectionState {
+    pub fn failed(error: &CommandError) -> Self {
+        Self::Failed {
+            reason: error.message.clone(),
+            code: error.code,
+            can_retry: error.retryable,
+        }
+    }
+
     pub fn is_connected(&self) -> bool {
         matches!(self, Self::Connected { .. })
     }
@@ -261,6 +282,9 @@ pub enum ConnectionQuality {
     Unknown, // Not enough data
 }
 
+/// Upper bounds (ms) of the latency histogram buckets; a final bucket catches the rest
+pub const LATENCY_BUCKETS_MS: &[u64] = &[25, 50, 100, 200, 300, 500, 1000, 2000, 5000];
+
 /// Health metrics for connection quality assessment
 #[derive(Debug, Clone, Default)]
 pub struct HealthMetrics {
@@ -274,11 +298,47 @@ pub struct HealthMetrics {
     pub last_ping_success: Option<std::time::Instant>,
     /// Messages sent since last ack
     pub pending_acks: u32,
+    /// When the outstanding ping was sent (cleared on pong)
+    pub ping_sent_at: Option<Instant>,
+    /// Sliding window of (sample time, latency ms) for percentiles and histogram
+    pub latency_window: VecDeque<(Instant, u64)>,
+}
+
+/// Latency distribution over the sliding window
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LatencyHistogram {
+    pub samples: usize,
+    #[serde(rename = "windowSecs")]
+    pub window_secs: u64,
+    #[serde(rename = "minMs")]
+    pub min_ms: Option<u64>,
+    #[serde(rename = "maxMs")]
+    pub max_ms: Option<u64>,
+    #[serde(rename = "p50Ms")]
+    pub p50_ms: Option<u64>,
+    #[serde(rename = "p95Ms")]
+    pub p95_ms: Option<u64>,
+    #[serde(rename = "p99Ms")]
+    pub p99_ms: Option<u64>,
+    pub buckets: Vec<HistogramBucket>,
+}
+
+/// Number of samples at or below `le_ms` (and above the previous bucket)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HistogramBucket {
+    /// Bucket upper bound in ms; `None` for the overflow bucket
+    #[serde(rename = "leMs")]
+    pub le_ms: Option<u64>,
+    pub count: usize,
 }
 
 impl HealthMetrics {
     /// Maximum latencies to track
     const MAX_LATENCIES: usize = 10;
+    /// Sliding window length for percentiles
+    const WINDOW: Duration = Duration::from_secs(15 * 60);
+    /// Hard cap on samples kept in the window
+    const MAX_WINDOW_SAMPLES: usize = 1000;
 
     pub fn record_latency(&mut self, latency_ms: u64) {
         self.latencies.push(latency_ms);
@@ -287,18 +347,100 @@ impl HealthMetrics {
         }
         self.recent_successes += 1;
         self.last_ping_success = Some(std::time::Instant::now());
+
+        self.latency_window.push_back((Instant::now(), latency_ms));
+        self.prune_window();
     }
 
     pub fn record_failure(&mut self) {
         self.recent_failures += 1;
     }
 
+    /// Mark a ping as sent so the matching pong can be timed
+    pub fn record_ping_sent(&mut self) {
+        self.ping_sent_at = Some(Instant::now());
+    }
+
+    /// Record a pong, returning the measured round-trip latency
+    pub fn record_pong(&mut self) -> Option<u64> {
+        let sent_at = self.ping_sent_at.take()?;
+        let latency_ms = sent_at.elapsed().as_millis() as u64;
+        self.record_latency(latency_ms);
+        Some(latency_ms)
+    }
+
     pub fn reset(&mut self) {
         self.latencies.clear();
         self.recent_failures = 0;
         self.recent_successes = 0;
         self.last_ping_success = None;
         self.pending_acks = 0;
+        self.ping_sent_at = None;
+        self.latency_window.clear();
+    }
+
+    fn prune_window(&mut self) {
+        while self.latency_window.len() > Self::MAX_WINDOW_SAMPLES {
+            self.latency_window.pop_front();
+        }
+        while let Some((at, _)) = self.latency_window.front() {
+            if at.elapsed() > Self::WINDOW {
+                self.latency_window.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn sorted_window(&self) -> Vec<u64> {
+        let mut samples: Vec<u64> = self
+            .latency_window
+            .iter()
+            .filter(|(at, _)| at.elapsed() <= Self::WINDOW)
+            .map(|(_, latency)| *latency)
+            .collect();
+        samples.sort_unstable();
+        samples
+    }
+
+    /// Nearest-rank percentile (0-100) of latencies in the sliding window
+    pub fn percentile(&self, p: f64) -> Option<u64> {
+        percentile_of_sorted(&self.sorted_window(), p)
+    }
+
+    /// Latency histogram and percentiles over the sliding window
+    pub fn histogram(&self) -> LatencyHistogram {
+        let samples = self.sorted_window();
+
+        let mut buckets: Vec<HistogramBucket> = LATENCY_BUCKETS_MS
+            .iter()
+            .map(|le| HistogramBucket {
+                le_ms: Some(*le),
+                count: 0,
+            })
+            .collect();
+        buckets.push(HistogramBucket {
+            le_ms: None,
+            count: 0,
+        });
+        for latency in &samples {
+            let index = LATENCY_BUCKETS_MS
+                .iter()
+                .position(|le| latency <= le)
+                .unwrap_or(LATENCY_BUCKETS_MS.len());
+            buckets[index].count += 1;
+        }
+
+        LatencyHistogram {
+            samples: samples.len(),
+            window_secs: Self::WINDOW.as_secs(),
+            min_ms: samples.first().copied(),
+            max_ms: samples.last().copied(),
+            p50_ms: percentile_of_sorted(&samples, 50.0),
+            p95_ms: percentile_of_sorted(&samples, 95.0),
+            p99_ms: percentile_of_sorted(&samples, 99.0),
+            buckets,
+        }
     }
 
     /// Calculate average latency
@@ -341,6 +483,117 @@ impl HealthMetrics {
     }
 }
 
+/// Point-in-time view of connection health for the UI and diagnostics
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthSnapshot {
+    pub quality: ConnectionQuality,
+    #[serde(rename = "averageLatencyMs")]
+    pub average_latency_ms: Option<u64>,
+    #[serde(rename = "recentFailures")]
+    pub recent_failures: u32,
+    #[serde(rename = "recentSuccesses")]
+    pub recent_successes: u32,
+    pub latency: LatencyHistogram,
+}
+
+impl HealthMetrics {
+    pub fn snapshot(&self) -> HealthSnapshot {
+        HealthSnapshot {
+            quality: self.quality(),
+            average_latency_ms: self.average_latency(),
+            recent_failures: self.recent_failures,
+            recent_successes: self.recent_successes,
+            latency: self.histogram(),
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending-sorted sample set
+fn percentile_of_sorted(sorted: &[u64], p: f64) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+// ============================================================================
+// Send Guard
+// ============================================================================
+
+/// How a message sent now is handled
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SendStatus {
+    /// Sent over a healthy connection
+    Sent,
+    /// Sent, but the connection is poor: expect a delayed reply
+    Degraded,
+    /// Offline mode is on: queued to disk and sent when back online
+    QueuedOffline,
+    /// Queued while connecting and sent once connected
+    QueuedReconnecting,
+    /// Not sent: the Gateway rejected the credentials
+    BlockedNeedsReauth,
+    /// Not sent: no connection and none in progress
+    NotConnected,
+}
+
+impl SendStatus {
+    /// Decide from the connection state and quality, and whether offline
+    /// mode is on
+    pub fn assess(state: &ConnectionState, quality: ConnectionQuality, offline: bool) -> Self {
+        if offline {
+            return Self::QueuedOffline;
+        }
+        match state {
+            ConnectionState::Connected { .. } if quality == ConnectionQuality::Poor => {
+                Self::Degraded
+            }
+            ConnectionState::Connected { .. } => Self::Sent,
+            ConnectionState::Connecting | ConnectionState::Reconnecting { .. } => {
+                Self::QueuedReconnecting
+            }
+            ConnectionState::Failed {
+                code: ErrorCode::Auth,
+                ..
+            } => Self::BlockedNeedsReauth,
+            ConnectionState::Disconnected | ConnectionState::Failed { .. } => Self::NotConnected,
+        }
+    }
+}
+
+/// Result of sending a message
+#[derive(Debug, Clone, Serialize)]
+pub struct SendOutcome {
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+    pub status: SendStatus,
+}
+
+// ============================================================================
+// Stream Timeouts
+// ============================================================================
+
+/// How long a run may go without a chat event before it is timed out
+///
+/// An explicit override wins (clamped to a sane range). Otherwise runs that
+/// reason before answering can stay silent for a while, so they get
+/// [`REASONING_STREAM_TIMEOUT_SECS`] instead of the default.
+pub fn stream_timeout(override_secs: Option<u64>, reasoning: bool) -> Duration {
+    let secs = match override_secs {
+        Some(secs) => secs.clamp(MIN_STREAM_TIMEOUT_SECS, MAX_STREAM_TIMEOUT_SECS),
+        None if reasoning => REASONING_STREAM_TIMEOUT_SECS,
+        None => DEFAULT_STREAM_TIMEOUT_SECS,
+    };
+    Duration::from_secs(secs)
+}
+
+/// Whether a thinking level asks the model to reason ("off" does not)
+pub fn thinking_enabled(thinking: Option<&str>) -> bool {
+    thinking.is_some_and(|t| !t.is_empty() && t != "off")
+}
+
 // ============================================================================
 // Protocol Message Validation
 // ============================================================================
@@ -457,6 +710,143 @@ pub fn validate_frame(json: &str) -> Result<ValidatedFrame, GatewayError> {
     }
 }
 
+// ============================================================================
+// Gateway Compatibility
+// ============================================================================
+
+/// Oldest Gateway server version known to work with this client
+pub const MIN_GATEWAY_VERSION: &str = "2026.1.0";
+
+/// Gateway methods the client relies on, with what breaks without them
+pub const CLIENT_METHODS: &[(&str, &str)] = &[
+    ("chat.send", "sending messages"),
+    ("chat.abort", "stopping a response in progress"),
+    ("models.list", "model selection"),
+    ("sessions.patch", "conversation session tracking"),
+    ("sessions.delete", "closing conversation sessions"),
+];
+
+/// Compatibility problems detected from the hello-ok payload
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CompatWarning {
+    #[serde(rename = "serverVersion")]
+    pub server_version: Option<String>,
+    pub protocol: Option<i64>,
+    #[serde(rename = "minVersion")]
+    pub min_version: String,
+    /// Approximate age of a date-versioned Gateway, in months
+    #[serde(rename = "ageMonths")]
+    pub age_months: Option<i64>,
+    #[serde(rename = "missingMethods")]
+    pub missing_methods: Vec<String>,
+    /// Human-readable description of each problem
+    pub issues: Vec<String>,
+}
+
+/// Parse a dotted numeric version ("2026.1.24", "v1.2.3-beta") into components
+fn parse_version(version: &str) -> Vec<u64> {
+    version
+        .trim_start_matches('v')
+        .split(['.', '-', '+'])
+        .map_while(|part| part.parse::<u64>().ok())
+        .collect()
+}
+
+/// Compare two dotted versions, treating missing components as zero
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let (a, b) = (parse_version(a), parse_version(b));
+    let len = a.len().max(b.len());
+    for i in 0..len {
+        let ord = a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0));
+        if ord != std::cmp::Ordering::Equal {
+            return ord;
+        }
+    }
+    std::cmp::Ordering::Equal
+}
+
+/// Months elapsed since a date-based version ("YYYY.M.D") was released
+fn version_age_months(version: &str, now: chrono::NaiveDate) -> Option<i64> {
+    use chrono::Datelike;
+
+    let parts = parse_version(version);
+    let (year, month) = (*parts.first()? as i32, *parts.get(1)? as i32);
+    if !(2000..=2100).contains(&year) || !(1..=12).contains(&month) {
+        return None;
+    }
+    Some(((now.year() - year) * 12 + (now.month() as i32 - month)).max(0) as i64)
+}
+
+/// Check a hello-ok payload against the known-compatible Gateway range
+///
+/// Returns `None` when the Gateway looks fully compatible.
+pub fn check_gateway_compat(hello: &serde_json::Value) -> Option<CompatWarning> {
+    let server_version = hello
+        .get("server")
+        .and_then(|s| s.get("version"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let protocol = hello.get("protocol").and_then(|p| p.as_i64());
+    let age_months = server_version
+        .as_deref()
+        .and_then(|v| version_age_months(v, chrono::Utc::now().date_naive()));
+
+    let mut issues = Vec::new();
+
+    if let Some(protocol) = protocol {
+        if protocol != PROTOCOL_VERSION as i64 {
+            issues.push(format!(
+                "Gateway speaks protocol v{}, this client expects v{}",
+                protocol, PROTOCOL_VERSION
+            ));
+        }
+    }
+
+    if let Some(version) = &server_version {
+        if compare_versions(version, MIN_GATEWAY_VERSION) == std::cmp::Ordering::Less {
+            let age = age_months
+                .filter(|months| *months > 0)
+                .map(|months| format!(" (about {} months old)", months))
+                .unwrap_or_default();
+            issues.push(format!(
+                "Your gateway is version {}{}; version {} or newer is recommended",
+                version, age, MIN_GATEWAY_VERSION
+            ));
+        }
+    }
+
+    // Only check methods when the gateway advertises its feature list
+    let mut missing_methods = Vec::new();
+    if let Some(methods) = hello
+        .get("features")
+        .and_then(|f| f.get("methods"))
+        .and_then(|m| m.as_array())
+    {
+        for (method, feature) in CLIENT_METHODS {
+            if !methods.iter().any(|m| m.as_str() == Some(method)) {
+                missing_methods.push(method.to_string());
+                issues.push(format!(
+                    "{} unavailable ({} not supported)",
+                    feature, method
+                ));
+            }
+        }
+    }
+
+    if issues.is_empty() {
+        return None;
+    }
+
+    Some(CompatWarning {
+        server_version,
+        protocol,
+        min_version: MIN_GATEWAY_VERSION.to_string(),
+        age_months,
+        missing_methods,
+        issues,
+    })
+}
+
 // ============================================================================
 // Exponential Backoff
 // ============================================================================
@@ -486,6 +876,62 @@ fn rand_simple() -> f64 {
     ((nanos % 1000) as f64) / 1000.0
 }
 
+// ============================================================================
+// Retry Policies
+// ============================================================================
+
+/// How a Gateway request method may be retried after a retryable failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryPolicy {
+    /// Read-only or otherwise idempotent - always safe to resend
+    Always { max_retries: u32 },
+    /// Mutating, but deduplicated by the Gateway via `idempotencyKey`
+    /// - only resend when the request carries one
+    WithIdempotencyKey { max_retries: u32 },
+    /// Never resend automatically
+    Never,
+}
+
+impl RetryPolicy {
+    /// Number of automatic retries allowed for a request
+    pub fn max_retries(&self, has_idempotency_key: bool) -> u32 {
+        match self {
+            Self::Always { max_retries } => *max_retries,
+            Self::WithIdempotencyKey { max_retries } if has_idempotency_key => *max_retries,
+            _ => 0,
+        }
+    }
+}
+
+/// Per-method retry policy table. Methods not listed are never retried.
+pub const RETRY_POLICIES: &[(&str, RetryPolicy)] = &[
+    ("models.list", RetryPolicy::Always { max_retries: 3 }),
+    ("sessions.list", RetryPolicy::Always { max_retries: 3 }),
+    ("status", RetryPolicy::Always { max_retries: 3 }),
+    ("config.get", RetryPolicy::Always { max_retries: 3 }),
+    (
+        "chat.send",
+        RetryPolicy::WithIdempotencyKey { max_retries: 3 },
+    ),
+];
+
+/// Look up the retry policy for a request method
+pub fn retry_policy(method: &str) -> RetryPolicy {
+    RETRY_POLICIES
+        .iter()
+        .find(|(m, _)| *m == method)
+        .map(|(_, policy)| *policy)
+        .unwrap_or(RetryPolicy::Never)
+}
+
+/// Delay before the given request retry attempt (1-based)
+pub fn request_retry_delay(attempt: u32) -> Duration {
+    let delay_ms = REQUEST_RETRY_INITIAL_MS
+        .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(REQUEST_RETRY_MAX_MS);
+    Duration::from_millis(delay_ms)
+}
+
 // ============================================================================
 // Message Queue
 // ============================================================================
@@ -494,6 +940,7 @@ fn rand_simple() -> f64 {
 #[derive(Debug, Clone)]
 pub struct QueuedMessage {
     pub id: String,
+    pub method: String,
     pub json: String,
     pub created_at: std::time::Instant,
     pub retry_count: u32,
@@ -501,13 +948,20 @@ pub struct QueuedMessage {
 }
 
 impl QueuedMessage {
-    pub fn new(id: String, json: String) -> Self {
+    /// Queue a request frame; its retry budget comes from the method's policy
+    pub fn new(id: String, method: &str, json: String) -> Self {
+        let has_idempotency_key = serde_json::from_str::<serde_json::Value>(&json)
+            .ok()
+            .and_then(|frame| frame.get("params")?.get("idempotencyKey").cloned())
+            .is_some();
+
         Self {
             id,
+            method: method.to_string(),
             json,
             created_at: std::time::Instant::now(),
             retry_count: 0,
-            max_retries: 3,
+            max_retries: retry_policy(method).max_retries(has_idempotency_key),
         }
     }
 
@@ -599,6 +1053,38 @@ mod tests {
         assert!(error.is_retryable());
     }
 
+    #[test]
+    fn test_gateway_compat_ok() {
+        let hello = serde_json::json!({
+            "type": "hello-ok",
+            "protocol": 3,
+            "server": { "version": "2026.2.1" },
+            "features": { "methods": ["chat.send", "chat.abort", "models.list", "sessions.patch", "sessions.delete"] }
+        });
+        assert_eq!(check_gateway_compat(&hello), None);
+    }
+
+    #[test]
+    fn test_gateway_compat_old_version_and_missing_methods() {
+        let hello = serde_json::json!({
+            "type": "hello-ok",
+            "protocol": 3,
+            "server": { "version": "2025.7.3" },
+            "features": { "methods": ["chat.send", "models.list"] }
+        });
+        let warning = check_gateway_compat(&hello).unwrap();
+        assert_eq!(warning.server_version.as_deref(), Some("2025.7.3"));
+        assert!(warning.missing_methods.contains(&"chat.abort".to_string()));
+        assert!(warning.issues.iter().any(|i| i.contains("2025.7.3")));
+    }
+
+    #[test]
+    fn test_version_age_months() {
+        let now = chrono::NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        assert_eq!(version_age_months("2025.7.3", now), Some(6));
+        assert_eq!(version_age_months("1.2.3", now), None);
+    }
+
     #[test]
     fn test_backoff_calculation() {
         let d1 = calculate_backoff(1);
@@ -636,10 +1122,138 @@ mod tests {
         assert_eq!(metrics.quality(), ConnectionQuality::Fair);
     }
 
+    #[test]
+    fn test_latency_percentiles() {
+        let mut metrics = HealthMetrics::default();
+        assert_eq!(metrics.percentile(50.0), None);
+
+        for latency in 1..=100 {
+            metrics.record_latency(latency);
+        }
+        assert_eq!(metrics.percentile(50.0), Some(50));
+        assert_eq!(metrics.percentile(95.0), Some(95));
+        assert_eq!(metrics.percentile(99.0), Some(99));
+    }
+
+    #[test]
+    fn test_latency_histogram_buckets() {
+        let mut metrics = HealthMetrics::default();
+        for latency in [10, 40, 90, 90, 2500, 9000] {
+            metrics.record_latency(latency);
+        }
+
+        let histogram = metrics.histogram();
+        assert_eq!(histogram.samples, 6);
+        assert_eq!(histogram.min_ms, Some(10));
+        assert_eq!(histogram.max_ms, Some(9000));
+        assert_eq!(histogram.buckets[0].count, 1); // <= 25
+        assert_eq!(histogram.buckets[2].count, 2); // <= 100
+        assert_eq!(histogram.buckets.last().unwrap().count, 1); // overflow
+        let total: usize = histogram.buckets.iter().map(|b| b.count).sum();
+        assert_eq!(total, 6);
+    }
+
+    #[test]
+    fn test_pong_without_ping_ignored() {
+        let mut metrics = HealthMetrics::default();
+        assert_eq!(metrics.record_pong(), None);
+        metrics.record_ping_sent();
+        assert!(metrics.record_pong().is_some());
+        assert_eq!(metrics.latency_window.len(), 1);
+    }
+
     #[test]
     fn test_queued_message_expiry() {
-        let msg = QueuedMessage::new("test".to_string(), "{}".to_string());
+        let json =
+            r#"{"type":"req","id":"test","method":"chat.send","params":{"idempotencyKey":"k"}}"#;
+        let msg = QueuedMessage::new("test".to_string(), "chat.send", json.to_string());
         assert!(!msg.is_expired());
         assert!(msg.can_retry());
     }
+
+    #[test]
+    fn test_retry_policy_lookup() {
+        assert_eq!(retry_policy("models.list").max_retries(false), 3);
+        assert_eq!(retry_policy("chat.send").max_retries(true), 3);
+        // chat.send without idempotency confirmation must not be resent
+        assert_eq!(retry_policy("chat.send").max_retries(false), 0);
+        assert_eq!(retry_policy("unknown.method"), RetryPolicy::Never);
+    }
+
+    #[test]
+    fn test_queued_message_without_idempotency_key_not_retried() {
+        let json = r#"{"type":"req","id":"test","method":"chat.send","params":{}}"#;
+        let msg = QueuedMessage::new("test".to_string(), "chat.send", json.to_string());
+        assert!(!msg.can_retry());
+    }
+
+    #[test]
+    fn test_stream_timeout() {
+        assert_eq!(
+            stream_timeout(None, false),
+            Duration::from_secs(DEFAULT_STREAM_TIMEOUT_SECS)
+        );
+        assert_eq!(
+            stream_timeout(None, true),
+            Duration::from_secs(REASONING_STREAM_TIMEOUT_SECS)
+        );
+        assert_eq!(stream_timeout(Some(300), false), Duration::from_secs(300));
+        assert_eq!(
+            stream_timeout(Some(1), true),
+            Duration::from_secs(MIN_STREAM_TIMEOUT_SECS)
+        );
+        assert_eq!(
+            stream_timeout(Some(u64::MAX), false),
+            Duration::from_secs(MAX_STREAM_TIMEOUT_SECS)
+        );
+
+        assert!(thinking_enabled(Some("high")));
+        assert!(!thinking_enabled(Some("off")));
+        assert!(!thinking_enabled(Some("")));
+        assert!(!thinking_enabled(None));
+    }
+
+    #[test]
+    fn test_send_status_assess() {
+        let connected = ConnectionState::Connected { session_id: None };
+        assert_eq!(
+            SendStatus::assess(&connected, ConnectionQuality::Good, false),
+            SendStatus::Sent
+        );
+        assert_eq!(
+            SendStatus::assess(&connected, ConnectionQuality::Poor, false),
+            SendStatus::Degraded
+        );
+        // Offline mode queues whatever the connection
+        assert_eq!(
+            SendStatus::assess(&connected, ConnectionQuality::Good, true),
+            SendStatus::QueuedOffline
+        );
+        assert_eq!(
+            SendStatus::assess(
+                &ConnectionState::Connecting,
+                ConnectionQuality::Unknown,
+                false
+            ),
+            SendStatus::QueuedReconnecting
+        );
+
+        let rejected = ConnectionState::failed(&CommandError::auth("Token rejected"));
+        assert_eq!(
+            SendStatus::assess(&rejected, ConnectionQuality::Unknown, false),
+            SendStatus::BlockedNeedsReauth
+        );
+        let unreachable = ConnectionState::failed(&CommandError::network("Unreachable"));
+        assert_eq!(
+            SendStatus::assess(&unreachable, ConnectionQuality::Unknown, false),
+            SendStatus::NotConnected
+        );
+    }
+
+    #[test]
+    fn test_request_retry_delay_capped() {
+        assert_eq!(request_retry_delay(1), Duration::from_millis(500));
+        assert_eq!(request_retry_delay(2), Duration::from_millis(1000));
+        assert_eq!(request_retry_delay(10), Duration::from_millis(4000));
+    }
 }
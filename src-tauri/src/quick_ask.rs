@@ -0,0 +1,338 @@
+//! Quick Ask run queue
+//!
+//! Questions typed in the Quick Ask window are queued here instead of being
+//! handed to the main window one by one, so several can be fired
+//! back-to-back:
+//! - Each question gets its own session and runs in the background through
+//!   the scheduler's reply capture, up to [`MAX_CONCURRENT_RUNS`] at once
+//! - Every state change is emitted as `quickask:updated`, which the Quick Ask
+//!   window shows as a card per question
+//! - Each answer is posted as a notification; clicking it (or "Open" on the
+//!   card) emits `quickask:open` to the main window, which turns the
+//!   question and answer into a conversation on the same session
+//!
+//! With the response cache on, a question answered recently comes back
+//! finished straight away (marked `cached`) unless the caller bypasses the
+//! cache.
+//!
+//! The queue is in-memory only and keeps the [`MAX_KEPT`] most recent
+//! questions.
+
+use crate::error::CommandError;
+use crate::prompt_history::PromptHistoryState;
+use crate::response_cache::{CacheKey, ResponseCacheState};
+use crate::{deep_link, notifications, plaintext, scheduler, sessions};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::{Mutex, Semaphore};
+
+/// Questions answered in parallel; later ones wait in the queue
+const MAX_CONCURRENT_RUNS: usize = 3;
+
+/// Questions kept (finished ones are dropped first)
+const MAX_KEPT: usize = 20;
+
+/// How long to wait for an answer
+const RUN_TIMEOUT_SECS: u64 = 5 * 60;
+
+/// Length of the question shown in notification titles
+const TITLE_CHARS: usize = 60;
+
+/// Length of the answer shown in notification bodies
+const BODY_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuickAskStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+/// A queued question and, once finished, its answer
+#[derive(Debug, Clone, Serialize)]
+pub struct QuickAsk {
+    pub id: String,
+    pub message: String,
+    /// Session the question runs on (also the conversation id when opened)
+    #[serde(rename = "sessionKey")]
+    pub session_key: String,
+    pub status: QuickAskStatus,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "finishedAt")]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Answered from the response cache
+    pub cached: bool,
+}
+
+impl QuickAsk {
+    fn is_finished(&self) -> bool {
+        matches!(self.status, QuickAskStatus::Done | QuickAskStatus::Failed)
+    }
+}
+
+/// Quick Ask queue managed by Tauri
+pub struct QuickAskState {
+    /// Oldest first
+    asks: Mutex<VecDeque<QuickAsk>>,
+    runs: Arc<Semaphore>,
+}
+
+impl Default for QuickAskState {
+    fn default() -> Self {
+        Self {
+            asks: Mutex::new(VecDeque::new()),
+            runs: Arc::new(Semaphore::new(MAX_CONCURRENT_RUNS)),
+        }
+    }
+}
+
+/// Add a question, dropping the oldest finished ones past [`MAX_KEPT`]
+fn push_bounded(asks: &mut VecDeque<QuickAsk>, ask: QuickAsk) {
+    asks.push_back(ask);
+    while asks.len() > MAX_KEPT {
+        match asks.iter().position(QuickAsk::is_finished) {
+            Some(index) => {
+                asks.remove(index);
+            }
+            None => break,
+        }
+    }
+}
+
+/// First `max` characters of a single-line version of `text`
+fn truncate(text: &str, max: usize) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match line.char_indices().nth(max) {
+        Some((cut, _)) => format!("{}…", &line[..cut]),
+        None => line,
+    }
+}
+
+/// Apply a change to a question and emit `quickask:updated`
+async fn update(app: &AppHandle, id: &str, change: impl FnOnce(&mut QuickAsk)) -> Option<QuickAsk> {
+    let state = app.state::<QuickAskState>();
+    let updated = {
+        let mut asks = state.asks.lock().await;
+        let ask = asks.iter_mut().find(|a| a.id == id)?;
+        change(ask);
+        ask.clone()
+    };
+    let _ = app.emit("quickask:updated", &updated);
+    Some(updated)
+}
+
+/// Cache key of a question (Quick Ask runs on the Gateway's default model,
+/// without attachments)
+fn cache_key(message: &str) -> CacheKey {
+    CacheKey::new(None, message, &[])
+}
+
+/// Show the main window and have it open a question as a conversation
+fn open_in_main(app: &AppHandle, ask: &QuickAsk) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    let _ = app.emit_to("main", "quickask:open", ask);
+}
+
+/// Wait for a run slot, run the question and report the answer
+async fn run(app: AppHandle, id: String) {
+    let state = app.state::<QuickAskState>();
+    let Ok(_permit) = state.runs.clone().acquire_owned().await else {
+        return;
+    };
+    // Dismissed while queued
+    let Some(ask) = update(&app, &id, |ask| ask.status = QuickAskStatus::Running).await else {
+        return;
+    };
+
+    let result = scheduler::send_and_collect(
+        &app,
+        &ask.session_key,
+        &ask.message,
+        None,
+        Duration::from_secs(RUN_TIMEOUT_SECS),
+    )
+    .await;
+    if let Ok(answer) = &result {
+        let cache = app.state::<ResponseCacheState>();
+        if let Err(e) = cache.insert(cache_key(&ask.message), answer).await {
+            log::error!("[QuickAsk] Failed to cache answer: {}", e);
+        }
+    }
+    let ok = result.is_ok();
+    let Some(ask) = update(&app, &id, |ask| {
+        ask.finished_at = Some(Utc::now());
+        match result {
+            Ok(answer) => {
+                ask.status = QuickAskStatus::Done;
+                ask.answer = Some(answer);
+            }
+            Err(e) => {
+                ask.status = QuickAskStatus::Failed;
+                ask.error = Some(e);
+            }
+        }
+    })
+    .await
+    else {
+        return;
+    };
+    log::info!("[QuickAsk] Question {} finished (ok: {})", id, ok);
+
+    let title = if ok {
+        truncate(&ask.message, TITLE_CHARS)
+    } else {
+        format!("Couldn't answer: {}", truncate(&ask.message, TITLE_CHARS))
+    };
+    let body = match ask.answer.as_deref() {
+        Some(answer) => plaintext::summary(answer, BODY_CHARS),
+        None => truncate(ask.error.as_deref().unwrap_or_default(), BODY_CHARS),
+    };
+    let link = deep_link::conversation_url(&ask.session_key, None);
+    let handle = app.clone();
+    notifications::show_clickable(&app, &title, &body, Some(link), move || {
+        open_in_main(&handle, &ask)
+    })
+    .await;
+}
+
+/// Queue a question; it runs in the background without blocking the next
+///
+/// Returns it already answered when the response cache has a fresh answer,
+/// unless `bypass_cache` is set.
+#[tauri::command]
+pub async fn enqueue_quick_ask(
+    app: AppHandle,
+    state: State<'_, QuickAskState>,
+    cache: State<'_, ResponseCacheState>,
+    message: String,
+    bypass_cache: Option<bool>,
+) -> Result<QuickAsk, CommandError> {
+    let message = message.trim().to_string();
+    if message.is_empty() {
+        return Err(CommandError::validation("Type a question first"));
+    }
+    // Remembered in the background: unsealing the history on first use may
+    // wait on the keychain
+    let (handle, prompt) = (app.clone(), message.clone());
+    tauri::async_runtime::spawn(async move {
+        handle.state::<PromptHistoryState>().record(&prompt).await;
+    });
+    let cached_answer = if bypass_cache.unwrap_or(false) {
+        None
+    } else {
+        cache.lookup(&cache_key(&message)).await
+    };
+    let mut ask = QuickAsk {
+        id: uuid::Uuid::new_v4().to_string(),
+        message,
+        session_key: sessions::generate_session_key(),
+        status: QuickAskStatus::Queued,
+        answer: None,
+        error: None,
+        created_at: Utc::now(),
+        finished_at: None,
+        cached: false,
+    };
+    if let Some(answer) = cached_answer {
+        ask.status = QuickAskStatus::Done;
+        ask.answer = Some(answer);
+        ask.finished_at = Some(ask.created_at);
+        ask.cached = true;
+    }
+    push_bounded(&mut *state.asks.lock().await, ask.clone());
+    let _ = app.emit("quickask:updated", &ask);
+    if !ask.cached {
+        tauri::async_runtime::spawn(run(app, ask.id.clone()));
+    }
+    Ok(ask)
+}
+
+/// Questions in the queue, oldest first
+#[tauri::command]
+pub async fn list_quick_asks(
+    state: State<'_, QuickAskState>,
+) -> Result<Vec<QuickAsk>, CommandError> {
+    Ok(state.asks.lock().await.iter().cloned().collect())
+}
+
+/// Remove a question from the queue (one that is already running still
+/// finishes on the Gateway, but isn't reported)
+#[tauri::command]
+pub async fn dismiss_quick_ask(
+    state: State<'_, QuickAskState>,
+    id: String,
+) -> Result<(), CommandError> {
+    state.asks.lock().await.retain(|a| a.id != id);
+    Ok(())
+}
+
+/// Open a question and its answer in the main window
+#[tauri::command]
+pub async fn open_quick_ask(
+    app: AppHandle,
+    state: State<'_, QuickAskState>,
+    id: String,
+) -> Result<(), CommandError> {
+    let ask = state
+        .asks
+        .lock()
+        .await
+        .iter()
+        .find(|a| a.id == id)
+        .cloned()
+        .ok_or_else(|| CommandError::validation("Question not found"))?;
+    open_in_main(&app, &ask);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ask(id: &str, status: QuickAskStatus) -> QuickAsk {
+        QuickAsk {
+            id: id.to_string(),
+            message: "question".to_string(),
+            session_key: format!("moltz:{}", id),
+            status,
+            answer: None,
+            error: None,
+            created_at: Utc::now(),
+            finished_at: None,
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn test_push_bounded_keeps_unfinished() {
+        let mut asks = VecDeque::new();
+        push_bounded(&mut asks, ask("done", QuickAskStatus::Done));
+        for i in 0..MAX_KEPT {
+            push_bounded(&mut asks, ask(&i.to_string(), QuickAskStatus::Queued));
+        }
+        assert_eq!(asks.len(), MAX_KEPT);
+        assert!(asks.iter().all(|a| a.id != "done"));
+
+        // Nothing finished to drop: the queue grows rather than losing work
+        push_bounded(&mut asks, ask("extra", QuickAskStatus::Running));
+        assert_eq!(asks.len(), MAX_KEPT + 1);
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short\nanswer", 20), "short answer");
+        assert_eq!(truncate("abcdef", 3), "abc…");
+    }
+}
@@ -0,0 +1,369 @@
+//! Usage quota tracking and limit warnings
+//!
+//! Limits come from the Gateway's `usage.status` method (fetched on connect
+//! and when the settings pane asks for the status). Between fetches, token
+//! and request consumption from completed runs is added locally so warnings
+//! don't wait for the next fetch.
+//!
+//! When a limit crosses 80%, 95% or 100% of its allowance,
+//! `gateway:quota_warning` is emitted and a notification is shown, once per
+//! threshold and period.
+
+use crate::error::CommandError;
+use crate::gateway::{GatewayState, TokenUsage};
+use crate::guest;
+use crate::notifications;
+use crate::store;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+use tokio::sync::Mutex;
+
+/// Store file name for quota tracking
+const QUOTA_FILE: &str = "quota.json";
+
+/// Percentages of a limit at which to warn
+const WARNING_THRESHOLDS: &[u8] = &[80, 95, 100];
+
+/// A limit reported by the Gateway
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuotaLimit {
+    pub id: String,
+    /// Display name (e.g. "Daily tokens")
+    pub name: String,
+    /// What is counted: "tokens", "requests" or a currency
+    pub unit: String,
+    pub used: f64,
+    pub limit: f64,
+    #[serde(rename = "resetsAt")]
+    pub resets_at: Option<DateTime<Utc>>,
+}
+
+impl QuotaLimit {
+    /// Fraction of the allowance used
+    pub fn fraction(&self) -> f64 {
+        if self.limit > 0.0 {
+            self.used / self.limit
+        } else {
+            0.0
+        }
+    }
+
+    /// Account for local consumption since the last fetch
+    fn add_usage(&mut self, tokens: u64) {
+        match self.unit.as_str() {
+            "tokens" => self.used += tokens as f64,
+            "requests" => self.used += 1.0,
+            // Costs can't be estimated locally
+            _ => {}
+        }
+    }
+}
+
+/// Consumption tracked locally, for the current day and month
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LocalUsage {
+    /// `YYYY-MM-DD` (UTC)
+    pub day: String,
+    #[serde(rename = "dayTokens")]
+    pub day_tokens: u64,
+    #[serde(rename = "dayRequests")]
+    pub day_requests: u64,
+    /// `YYYY-MM` (UTC)
+    pub month: String,
+    #[serde(rename = "monthTokens")]
+    pub month_tokens: u64,
+    #[serde(rename = "monthRequests")]
+    pub month_requests: u64,
+}
+
+impl LocalUsage {
+    fn record(&mut self, tokens: u64, now: DateTime<Utc>) {
+        let day = now.format("%Y-%m-%d").to_string();
+        if self.day != day {
+            self.day = day;
+            self.day_tokens = 0;
+            self.day_requests = 0;
+        }
+        let month = now.format("%Y-%m").to_string();
+        if self.month != month {
+            self.month = month;
+            self.month_tokens = 0;
+            self.month_requests = 0;
+        }
+        self.day_tokens += tokens;
+        self.day_requests += 1;
+        self.month_tokens += tokens;
+        self.month_requests += 1;
+    }
+}
+
+/// Persisted quota data
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct QuotaData {
+    #[serde(default)]
+    limits: Vec<QuotaLimit>,
+    #[serde(default)]
+    local: LocalUsage,
+    /// Highest threshold already warned about, per limit ID
+    #[serde(default)]
+    warned: HashMap<String, u8>,
+    #[serde(rename = "updatedAt", default)]
+    updated_at: Option<DateTime<Utc>>,
+}
+
+/// Quota status for the settings pane
+#[derive(Debug, Clone, Serialize)]
+pub struct QuotaStatus {
+    pub limits: Vec<QuotaLimit>,
+    pub local: LocalUsage,
+    /// When the limits were last fetched from the Gateway
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Payload of `gateway:quota_warning`
+#[derive(Debug, Clone, Serialize)]
+pub struct QuotaWarning {
+    pub limit: QuotaLimit,
+    /// Threshold crossed, in percent
+    pub threshold: u8,
+}
+
+/// Parse limits from a `usage.status` payload
+///
+/// Accepts `{ "limits": [...] }` or `{ "quota": { "limits": [...] } }`;
+/// entries without a positive limit are skipped.
+fn parse_limits(payload: &serde_json::Value) -> Vec<QuotaLimit> {
+    let limits = payload
+        .get("limits")
+        .or_else(|| payload.get("quota").and_then(|q| q.get("limits")))
+        .and_then(|l| l.as_array());
+    let Some(limits) = limits else {
+        return Vec::new();
+    };
+    limits
+        .iter()
+        .filter_map(|entry| {
+            let id = entry.get("id")?.as_str()?.to_string();
+            let limit = entry.get("limit")?.as_f64().filter(|l| *l > 0.0)?;
+            Some(QuotaLimit {
+                name: entry
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .unwrap_or(&id)
+                    .to_string(),
+                unit: entry
+                    .get("unit")
+                    .and_then(|u| u.as_str())
+                    .unwrap_or("tokens")
+                    .to_string(),
+                used: entry.get("used").and_then(|u| u.as_f64()).unwrap_or(0.0),
+                limit,
+                resets_at: entry
+                    .get("resetsAt")
+                    .and_then(|r| r.as_str())
+                    .and_then(|r| r.parse().ok()),
+                id,
+            })
+        })
+        .collect()
+}
+
+/// Highest newly crossed threshold, if any
+fn crossed_threshold(fraction: f64, warned: Option<u8>) -> Option<u8> {
+    let percent = fraction * 100.0;
+    WARNING_THRESHOLDS
+        .iter()
+        .copied()
+        .filter(|threshold| percent >= *threshold as f64)
+        .filter(|threshold| warned.is_none_or(|w| *threshold > w))
+        .max()
+}
+
+impl QuotaData {
+    /// Collect warnings for newly crossed thresholds and forget thresholds of
+    /// limits that dropped back (a new period started)
+    fn check_thresholds(&mut self) -> Vec<QuotaWarning> {
+        let mut warnings = Vec::new();
+        for limit in &self.limits {
+            let warned = self.warned.get(&limit.id).copied();
+            if let Some(threshold) = crossed_threshold(limit.fraction(), warned) {
+                self.warned.insert(limit.id.clone(), threshold);
+                warnings.push(QuotaWarning {
+                    limit: limit.clone(),
+                    threshold,
+                });
+            } else if warned.is_some() && crossed_threshold(limit.fraction(), None).is_none() {
+                self.warned.remove(&limit.id);
+            }
+        }
+        warnings
+    }
+}
+
+/// Quota state managed by Tauri
+pub struct QuotaState {
+    path: PathBuf,
+    data: Mutex<QuotaData>,
+}
+
+impl QuotaState {
+    /// Load quota data from the local store
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, QUOTA_FILE);
+        Self {
+            data: Mutex::new(store::load(&path)),
+            path,
+        }
+    }
+
+    async fn status(&self) -> QuotaStatus {
+        let data = self.data.lock().await;
+        QuotaStatus {
+            limits: data.limits.clone(),
+            local: data.local.clone(),
+            updated_at: data.updated_at,
+        }
+    }
+}
+
+/// Emit and notify about crossed thresholds
+async fn warn(app: &AppHandle, warnings: Vec<QuotaWarning>) {
+    for warning in warnings {
+        let body = if warning.threshold >= 100 {
+            format!("{} limit reached", warning.limit.name)
+        } else {
+            format!(
+                "{} is at {}% of its limit",
+                warning.limit.name, warning.threshold
+            )
+        };
+        notifications::show(app, None, "Usage limit", &body).await;
+        let _ = app.emit("gateway:quota_warning", warning);
+    }
+}
+
+/// Update the data and persist it, then warn about crossed thresholds
+async fn update(app: &AppHandle, apply: impl FnOnce(&mut QuotaData)) {
+    let Some(state) = app.try_state::<QuotaState>() else {
+        return;
+    };
+    let warnings = {
+        let mut data = state.data.lock().await;
+        apply(&mut data);
+        let warnings = data.check_thresholds();
+        if let Err(e) = store::save(&state.path, &*data) {
+            log::error!("[Quota] Failed to save quota data: {}", e);
+        }
+        warnings
+    };
+    warn(app, warnings).await;
+}
+
+/// Record the usage of a completed run (not in guest mode)
+pub async fn record_usage(app: &AppHandle, usage: Option<&TokenUsage>) {
+    if guest::active() {
+        return;
+    }
+    let tokens = usage
+        .and_then(|u| {
+            u.total_tokens
+                .or_else(|| Some(u.input.unwrap_or(0) + u.output.unwrap_or(0)))
+        })
+        .unwrap_or(0)
+        .max(0) as u64;
+    update(app, |data| {
+        data.local.record(tokens, Utc::now());
+        for limit in &mut data.limits {
+            limit.add_usage(tokens);
+        }
+    })
+    .await;
+}
+
+/// Fetch limits from the Gateway
+///
+/// Gateways without `usage.status` report no limits; local tracking still
+/// applies.
+pub async fn refresh(app: &AppHandle) -> Result<(), String> {
+    let gateway = app.state::<GatewayState>();
+    let payload = gateway
+        .request("usage.status", None)
+        .await
+        .map_err(|e| e.user_message())?;
+    let limits = payload.as_ref().map(parse_limits).unwrap_or_default();
+    update(app, |data| {
+        data.limits = limits;
+        data.updated_at = Some(Utc::now());
+    })
+    .await;
+    Ok(())
+}
+
+/// Get the quota status, refreshing limits from the Gateway when connected
+#[tauri::command]
+pub async fn get_quota_status(
+    app: AppHandle,
+    state: State<'_, QuotaState>,
+    gateway: State<'_, GatewayState>,
+) -> Result<QuotaStatus, CommandError> {
+    if gateway.connection_state().await.is_connected() {
+        if let Err(e) = refresh(&app).await {
+            log::error!("[Quota] Failed to refresh limits: {}", e);
+        }
+    }
+    Ok(state.status().await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_limits() {
+        let payload = serde_json::json!({
+            "limits": [
+                { "id": "tokens.daily", "name": "Daily tokens", "used": 8000, "limit": 10000,
+                  "resetsAt": "2026-01-02T00:00:00Z" },
+                { "id": "requests.hourly", "unit": "requests", "used": 3, "limit": 50 },
+                { "id": "unlimited", "limit": 0 }
+            ]
+        });
+        let limits = parse_limits(&payload);
+        assert_eq!(limits.len(), 2);
+        assert_eq!(limits[0].fraction(), 0.8);
+        assert!(limits[0].resets_at.is_some());
+        assert_eq!(limits[1].name, "requests.hourly");
+        assert_eq!(limits[1].unit, "requests");
+        assert!(parse_limits(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_thresholds_warn_once_per_period() {
+        let mut data = QuotaData {
+            limits: parse_limits(&serde_json::json!({
+                "limits": [{ "id": "daily", "used": 7900, "limit": 10000 }]
+            })),
+            ..Default::default()
+        };
+        assert!(data.check_thresholds().is_empty());
+
+        data.limits[0].add_usage(200);
+        let warnings = data.check_thresholds();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].threshold, 80);
+        assert!(data.check_thresholds().is_empty());
+
+        // Jumping past several thresholds warns once, for the highest
+        data.limits[0].add_usage(2000);
+        assert_eq!(data.check_thresholds()[0].threshold, 100);
+
+        // A new period re-arms the warnings
+        data.limits[0].used = 100.0;
+        assert!(data.check_thresholds().is_empty());
+        assert!(data.warned.is_empty());
+    }
+}
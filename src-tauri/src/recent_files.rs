@@ -0,0 +1,378 @@
+//! OS recent-documents integration
+//!
+//! Lists recently used files that can be attached to a message, and registers
+//! files written by Moltz (e.g. exports) in the OS recents list:
+//! - Linux: `~/.local/share/recently-used.xbel` (freedesktop bookmarks)
+//! - macOS: Spotlight (`kMDItemLastUsedDate`); registering is not supported
+//! - Windows: the Recent folder shortcuts; registering uses `SHAddToRecentDocs`
+
+use crate::error::CommandError;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Maximum number of recent files returned
+const MAX_RECENT_FILES: usize = 50;
+
+/// Attachable file kinds (mirrors the attachment picker in ChatInput)
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RecentFileKind {
+    Image,
+    Document,
+    Code,
+}
+
+/// A recently used file
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentFile {
+    pub path: String,
+    pub name: String,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+    pub kind: RecentFileKind,
+    pub size: u64,
+}
+
+/// MIME type and kind of an attachable file, by extension
+fn attachable_type(path: &Path) -> Option<(&'static str, RecentFileKind)> {
+    use RecentFileKind::*;
+
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    let entry = match ext.as_str() {
+        "png" => ("image/png", Image),
+        "jpg" | "jpeg" => ("image/jpeg", Image),
+        "gif" => ("image/gif", Image),
+        "webp" => ("image/webp", Image),
+        "pdf" => ("application/pdf", Document),
+        "txt" => ("text/plain", Document),
+        "md" => ("text/markdown", Document),
+        "html" => ("text/html", Document),
+        "csv" => ("text/csv", Document),
+        "json" => ("application/json", Document),
+        "js" | "ts" | "jsx" | "tsx" | "py" | "rs" | "go" | "java" | "c" | "cpp" | "h" | "css"
+        | "yaml" | "yml" | "toml" | "xml" => ("text/plain", Code),
+        _ => return None,
+    };
+    Some(entry)
+}
+
+/// Keep existing, attachable files matching the filter, in order, without duplicates
+fn filter_recent(paths: Vec<PathBuf>, filter: Option<RecentFileKind>) -> Vec<RecentFile> {
+    let mut files: Vec<RecentFile> = Vec::new();
+    for path in paths {
+        let Some((mime_type, kind)) = attachable_type(&path) else {
+            continue;
+        };
+        if filter.is_some_and(|f| f != kind) {
+            continue;
+        }
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        let path_str = path.to_string_lossy().to_string();
+        if files.iter().any(|f| f.path == path_str) {
+            continue;
+        }
+        files.push(RecentFile {
+            name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path: path_str,
+            mime_type: mime_type.to_string(),
+            kind,
+            size: metadata.len(),
+        });
+        if files.len() >= MAX_RECENT_FILES {
+            break;
+        }
+    }
+    files
+}
+
+// ============================================================================
+// Linux: freedesktop recently-used.xbel
+// ============================================================================
+
+#[cfg(target_os = "linux")]
+fn xbel_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("recently-used.xbel"))
+}
+
+/// Parse bookmarks from XBEL content, newest first
+fn parse_xbel(content: &str) -> Vec<PathBuf> {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let mut reader = Reader::from_str(content);
+    let mut entries: Vec<(String, PathBuf)> = Vec::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) if e.local_name().as_ref() == b"bookmark" => {
+                let attr = |name: &str| {
+                    e.try_get_attribute(name)
+                        .ok()
+                        .flatten()
+                        .and_then(|a| a.unescape_value().ok().map(|v| v.to_string()))
+                };
+                let path = attr("href")
+                    .and_then(|href| url::Url::parse(&href).ok())
+                    .and_then(|url| url.to_file_path().ok());
+                if let Some(path) = path {
+                    let modified = attr("modified").unwrap_or_default();
+                    entries.push((modified, path));
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    // Timestamps are ISO 8601, so they sort lexically
+    entries.sort_by(|a, b| b.0.cmp(&a.0));
+    entries.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Escape a value for an XML attribute
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Add (or refresh) a bookmark in XBEL content
+fn add_xbel_bookmark(content: &str, href: &str, mime_type: &str, now: &str) -> String {
+    const EMPTY_XBEL: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<xbel version=\"1.0\"\n      xmlns:bookmark=\"http://www.freedesktop.org/standards/desktop-bookmarks\"\n      xmlns:mime=\"http://www.freedesktop.org/standards/shared-mime-info\"\n>\n</xbel>\n";
+
+    let mut xbel = if content.contains("</xbel>") {
+        content.to_string()
+    } else {
+        EMPTY_XBEL.to_string()
+    };
+
+    // Drop an existing bookmark for the same file
+    let href = xml_escape(href);
+    let marker = format!("<bookmark href=\"{}\"", href);
+    if let Some(start) = xbel.find(&marker) {
+        if let Some(end) = xbel[start..].find("</bookmark>") {
+            let mut end = start + end + "</bookmark>".len();
+            if xbel[end..].starts_with('\n') {
+                end += 1;
+            }
+            let line_start = xbel[..start].rfind('\n').map(|i| i + 1).unwrap_or(start);
+            xbel.replace_range(line_start..end, "");
+        }
+    }
+
+    let bookmark = format!(
+        "  <bookmark href=\"{href}\" added=\"{now}\" modified=\"{now}\" visited=\"{now}\">\n    <info>\n      <metadata owner=\"http://freedesktop.org\">\n        <mime:mime-type type=\"{mime_type}\"/>\n        <bookmark:applications>\n          <bookmark:application name=\"Moltz\" exec=\"&apos;moltz %u&apos;\" modified=\"{now}\" count=\"1\"/>\n        </bookmark:applications>\n      </metadata>\n    </info>\n  </bookmark>\n"
+    );
+    if let Some(end) = xbel.rfind("</xbel>") {
+        xbel.insert_str(end, &bookmark);
+    }
+    xbel
+}
+
+#[cfg(target_os = "linux")]
+async fn os_recent_paths() -> Vec<PathBuf> {
+    let Some(path) = xbel_path() else {
+        return Vec::new();
+    };
+    match tokio::fs::read_to_string(&path).await {
+        Ok(content) => parse_xbel(&content),
+        Err(_) => Vec::new(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+async fn os_add_recent(path: &Path, mime_type: &str) -> Result<(), String> {
+    let xbel = xbel_path().ok_or("No data directory")?;
+    let href = url::Url::from_file_path(path)
+        .map_err(|_| format!("Invalid path: {}", path.display()))?
+        .to_string();
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, true);
+
+    let content = tokio::fs::read_to_string(&xbel).await.unwrap_or_default();
+    let updated = add_xbel_bookmark(&content, &href, mime_type, &now);
+
+    let tmp = xbel.with_extension("xbel.tmp");
+    tokio::fs::write(&tmp, updated)
+        .await
+        .map_err(|e| e.to_string())?;
+    tokio::fs::rename(&tmp, &xbel)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// macOS: Spotlight
+// ============================================================================
+
+#[cfg(target_os = "macos")]
+async fn os_recent_paths() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    let output = tokio::process::Command::new("mdfind")
+        .arg("-onlyin")
+        .arg(&home)
+        .arg("kMDItemLastUsedDate >= $time.today(-14)")
+        .output()
+        .await;
+    let Ok(output) = output else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<(std::time::SystemTime, PathBuf)> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(PathBuf::from)
+        .filter(|p| attachable_type(p).is_some())
+        .filter_map(|p| {
+            let accessed = std::fs::metadata(&p).and_then(|m| m.accessed()).ok()?;
+            Some((accessed, p))
+        })
+        .collect();
+    paths.sort_by(|a, b| b.0.cmp(&a.0));
+    paths.into_iter().map(|(_, p)| p).collect()
+}
+
+#[cfg(target_os = "macos")]
+async fn os_add_recent(_path: &Path, _mime_type: &str) -> Result<(), String> {
+    // No API outside AppKit's NSDocumentController; Spotlight picks up files
+    // when they are opened
+    Ok(())
+}
+
+// ============================================================================
+// Windows: Recent folder
+// ============================================================================
+
+#[cfg(target_os = "windows")]
+async fn os_recent_paths() -> Vec<PathBuf> {
+    // Resolve the Recent folder's shortcuts, newest first
+    const SCRIPT: &str = "$s = New-Object -ComObject WScript.Shell; \
+        Get-ChildItem \"$env:APPDATA\\Microsoft\\Windows\\Recent\\*.lnk\" | \
+        Sort-Object LastWriteTime -Descending | Select-Object -First 300 | \
+        ForEach-Object { $s.CreateShortcut($_.FullName).TargetPath }";
+
+    let output = tokio::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+        .output()
+        .await;
+    match output {
+        Ok(output) => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .map(PathBuf::from)
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+#[cfg(target_os = "windows")]
+async fn os_add_recent(path: &Path, _mime_type: &str) -> Result<(), String> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::UI::Shell::{SHAddToRecentDocs, SHARD_PATHW};
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    // SAFETY: `wide` is a NUL-terminated UTF-16 path that outlives the call
+    unsafe { SHAddToRecentDocs(SHARD_PATHW as u32, wide.as_ptr() as *const _) };
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+async fn os_recent_paths() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+async fn os_add_recent(_path: &Path, _mime_type: &str) -> Result<(), String> {
+    Ok(())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Get recently used files that can be attached, newest first
+#[tauri::command]
+pub async fn get_recent_files(
+    filter: Option<RecentFileKind>,
+) -> Result<Vec<RecentFile>, CommandError> {
+    let paths = os_recent_paths().await;
+    tokio::task::spawn_blocking(move || filter_recent(paths, filter))
+        .await
+        .map_err(|e| CommandError::internal(e.to_string()))
+}
+
+/// Register a file written by Moltz (e.g. an export) in the OS recents list
+///
+/// Skipped in guest mode.
+#[tauri::command]
+pub async fn add_recent_file(path: String) -> Result<(), CommandError> {
+    if crate::guest::active() {
+        return Ok(());
+    }
+    let path = PathBuf::from(path);
+    let mime_type = attachable_type(&path)
+        .map(|(mime, _)| mime)
+        .unwrap_or("application/octet-stream");
+    os_add_recent(&path, mime_type)
+        .await
+        .map_err(CommandError::internal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XBEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xbel version="1.0"
+      xmlns:bookmark="http://www.freedesktop.org/standards/desktop-bookmarks"
+      xmlns:mime="http://www.freedesktop.org/standards/shared-mime-info"
+>
+  <bookmark href="file:///home/me/old.md" added="2026-01-01T10:00:00Z" modified="2026-01-01T10:00:00Z" visited="2026-01-01T10:00:00Z">
+  </bookmark>
+  <bookmark href="file:///home/me/My%20Notes.txt" added="2026-02-01T10:00:00Z" modified="2026-02-01T10:00:00Z" visited="2026-02-01T10:00:00Z">
+  </bookmark>
+</xbel>
+"#;
+
+    #[test]
+    fn test_parse_xbel_newest_first() {
+        let paths = parse_xbel(XBEL);
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/home/me/My Notes.txt"),
+                PathBuf::from("/home/me/old.md"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_add_xbel_bookmark_replaces_existing() {
+        let now = "2026-03-01T10:00:00.000000Z";
+        let updated = add_xbel_bookmark(XBEL, "file:///home/me/old.md", "text/markdown", now);
+        assert_eq!(updated.matches("file:///home/me/old.md").count(), 1);
+        assert_eq!(parse_xbel(&updated)[0], PathBuf::from("/home/me/old.md"));
+
+        let created = add_xbel_bookmark("", "file:///tmp/a.md", "text/markdown", now);
+        assert_eq!(parse_xbel(&created), vec![PathBuf::from("/tmp/a.md")]);
+    }
+
+    #[test]
+    fn test_attachable_type() {
+        assert_eq!(
+            attachable_type(Path::new("a.PNG")),
+            Some(("image/png", RecentFileKind::Image))
+        );
+        assert_eq!(attachable_type(Path::new("a.exe")), None);
+    }
+}
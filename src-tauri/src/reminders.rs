@@ -0,0 +1,237 @@
+//! Follow-up reminders on messages
+//!
+//! "Remind me about this answer tomorrow": a reminder points at a message in
+//! a conversation and fires a native notification at the chosen time.
+//! Clicking the notification (where the platform reports clicks) brings up the
+//! main window and emits `reminder:open` so the frontend opens the
+//! conversation at that message. Every fired reminder is also emitted as
+//! `reminder:due`, for platforms without notification clicks.
+//!
+//! Reminders are stored locally; ones that came due while the app was closed
+//! fire on the next start.
+
+use crate::error::CommandError;
+use crate::{deep_link, notifications, store};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+use tokio::sync::Mutex;
+
+/// Store file name for reminders
+const REMINDERS_FILE: &str = "reminders.json";
+
+/// How often due reminders are checked
+const REMINDER_CHECK_INTERVAL_SECS: u64 = 20;
+
+/// Longest note shown in the notification body
+const MAX_NOTE_CHARS: usize = 500;
+
+/// A pending reminder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reminder {
+    pub id: String,
+    #[serde(rename = "sessionKey")]
+    pub session_key: String,
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    pub when: DateTime<Utc>,
+    pub note: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Payload of `reminder:due` and `reminder:open`
+#[derive(Debug, Clone, Serialize)]
+pub struct ReminderTarget {
+    pub id: String,
+    #[serde(rename = "sessionKey")]
+    pub session_key: String,
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    pub note: Option<String>,
+}
+
+impl From<&Reminder> for ReminderTarget {
+    fn from(reminder: &Reminder) -> Self {
+        Self {
+            id: reminder.id.clone(),
+            session_key: reminder.session_key.clone(),
+            message_id: reminder.message_id.clone(),
+            note: reminder.note.clone(),
+        }
+    }
+}
+
+/// Reminder state managed by Tauri
+pub struct ReminderState {
+    path: PathBuf,
+    reminders: Mutex<Vec<Reminder>>,
+}
+
+impl ReminderState {
+    /// Load pending reminders from the local store
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, REMINDERS_FILE);
+        Self {
+            reminders: Mutex::new(store::load(&path)),
+            path,
+        }
+    }
+
+    /// Remove and return reminders due at `now`, persisting the rest
+    async fn take_due(&self, now: DateTime<Utc>) -> Vec<Reminder> {
+        let mut reminders = self.reminders.lock().await;
+        let (due, pending): (Vec<_>, Vec<_>) = reminders.drain(..).partition(|r| r.when <= now);
+        *reminders = pending;
+        if !due.is_empty() {
+            if let Err(e) = store::save(&self.path, &*reminders) {
+                log::error!("[Reminders] Failed to save reminders: {}", e);
+            }
+        }
+        due
+    }
+}
+
+/// Bring up the main window and open the reminder's conversation
+fn open_reminder(app: &AppHandle, target: ReminderTarget) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+    let _ = app.emit_to("main", "reminder:open", target);
+}
+
+/// Post a due reminder
+async fn fire(app: &AppHandle, reminder: &Reminder) {
+    let target = ReminderTarget::from(reminder);
+    let body = match &reminder.note {
+        Some(note) if !note.trim().is_empty() => note.clone(),
+        _ => "You asked to be reminded about this answer".to_string(),
+    };
+
+    let _ = app.emit("reminder:due", target.clone());
+    let link = deep_link::conversation_url(&target.session_key, Some(&target.message_id));
+    let handle = app.clone();
+    notifications::show_clickable(app, "Reminder", &body, Some(link), move || {
+        open_reminder(&handle, target)
+    })
+    .await;
+}
+
+/// Start firing due reminders
+pub fn start_reminder_monitor(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let due = app.state::<ReminderState>().take_due(Utc::now()).await;
+            for reminder in &due {
+                fire(&app, reminder).await;
+            }
+            tokio::time::sleep(Duration::from_secs(REMINDER_CHECK_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+/// Set a reminder on a message
+#[tauri::command]
+pub async fn set_reminder(
+    state: State<'_, ReminderState>,
+    session_key: String,
+    message_id: String,
+    when: DateTime<Utc>,
+    note: Option<String>,
+) -> Result<Reminder, CommandError> {
+    if when <= Utc::now() {
+        return Err(CommandError::validation(
+            "Reminder time must be in the future",
+        ));
+    }
+    if note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_NOTE_CHARS)
+    {
+        return Err(CommandError::validation(format!(
+            "Reminder note is too long (max {} characters)",
+            MAX_NOTE_CHARS
+        )));
+    }
+
+    let reminder = Reminder {
+        id: uuid::Uuid::new_v4().to_string(),
+        session_key,
+        message_id,
+        when,
+        note,
+        created_at: Utc::now(),
+    };
+    let mut reminders = state.reminders.lock().await;
+    reminders.push(reminder.clone());
+    reminders.sort_by_key(|r| r.when);
+    store::save(&state.path, &*reminders)?;
+    Ok(reminder)
+}
+
+/// List pending reminders, soonest first
+#[tauri::command]
+pub async fn list_reminders(
+    state: State<'_, ReminderState>,
+) -> Result<Vec<Reminder>, CommandError> {
+    Ok(state.reminders.lock().await.clone())
+}
+
+/// Cancel a pending reminder
+#[tauri::command]
+pub async fn cancel_reminder(
+    state: State<'_, ReminderState>,
+    id: String,
+) -> Result<(), CommandError> {
+    let mut reminders = state.reminders.lock().await;
+    let before = reminders.len();
+    reminders.retain(|r| r.id != id);
+    if reminders.len() == before {
+        return Err(CommandError::validation(format!(
+            "Reminder not found: {}",
+            id
+        )));
+    }
+    store::save(&state.path, &*reminders).map_err(CommandError::internal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_take_due_keeps_pending() {
+        let path =
+            std::env::temp_dir().join(format!("moltz-reminders-{}.json", uuid::Uuid::new_v4()));
+        let now = Utc::now();
+        let reminder = |id: &str, offset_secs: i64| Reminder {
+            id: id.to_string(),
+            session_key: "s1".to_string(),
+            message_id: "m1".to_string(),
+            when: now + chrono::Duration::seconds(offset_secs),
+            note: None,
+            created_at: now,
+        };
+        let state = ReminderState {
+            path: path.clone(),
+            reminders: Mutex::new(vec![reminder("past", -60), reminder("future", 3600)]),
+        };
+
+        let due = state.take_due(now).await;
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, "past");
+
+        // The remaining reminder was persisted
+        let stored: Vec<Reminder> = store::load(&path);
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].id, "future");
+        assert!(state.take_due(now).await.is_empty());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
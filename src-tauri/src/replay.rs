@@ -0,0 +1,179 @@
+//! Replay buffer of recent run events, per session
+//!
+//! Run events (`gateway:stream`, `gateway:complete`, ...) are emitted to the
+//! window that started the run. A window opened mid-stream (a new
+//! conversation window, Quick Ask) calls `replay_recent_events` to catch up
+//! on what it missed instead of showing a truncated response.
+//!
+//! The buffer is in-memory only and bounded by event count, age and number of
+//! sessions.
+
+use crate::error::CommandError;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+/// Events kept per session
+const MAX_EVENTS_PER_SESSION: usize = 512;
+
+/// Events older than this are dropped
+const MAX_EVENT_AGE: Duration = Duration::from_secs(5 * 60);
+
+/// Sessions tracked at once; the least recently active is dropped first
+const MAX_SESSIONS: usize = 32;
+
+/// A buffered run event
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayedEvent {
+    /// Sequence number, increasing per buffer (for de-duplicating against
+    /// live events)
+    pub seq: u64,
+    /// Frontend event name, e.g. `gateway:stream`
+    pub event: String,
+    pub payload: serde_json::Value,
+    pub at: DateTime<Utc>,
+    #[serde(skip)]
+    recorded: Instant,
+}
+
+#[derive(Default)]
+struct ReplayBuffers {
+    sessions: HashMap<String, VecDeque<ReplayedEvent>>,
+    next_seq: u64,
+}
+
+impl ReplayBuffers {
+    fn record(&mut self, session_key: &str, event: &str, payload: serde_json::Value, now: Instant) {
+        if !self.sessions.contains_key(session_key) && self.sessions.len() >= MAX_SESSIONS {
+            let stalest = self
+                .sessions
+                .iter()
+                .min_by_key(|(_, events)| events.back().map(|e| e.recorded))
+                .map(|(key, _)| key.clone());
+            if let Some(key) = stalest {
+                self.sessions.remove(&key);
+            }
+        }
+
+        self.next_seq += 1;
+        let events = self.sessions.entry(session_key.to_string()).or_default();
+        events.push_back(ReplayedEvent {
+            seq: self.next_seq,
+            event: event.to_string(),
+            payload,
+            at: Utc::now(),
+            recorded: now,
+        });
+        while events.len() > MAX_EVENTS_PER_SESSION {
+            events.pop_front();
+        }
+        Self::expire(events, now);
+    }
+
+    fn expire(events: &mut VecDeque<ReplayedEvent>, now: Instant) {
+        while events
+            .front()
+            .is_some_and(|e| now.duration_since(e.recorded) > MAX_EVENT_AGE)
+        {
+            events.pop_front();
+        }
+    }
+
+    fn recent(
+        &mut self,
+        session_key: &str,
+        after_seq: Option<u64>,
+        now: Instant,
+    ) -> Vec<ReplayedEvent> {
+        let Some(events) = self.sessions.get_mut(session_key) else {
+            return Vec::new();
+        };
+        Self::expire(events, now);
+        events
+            .iter()
+            .filter(|e| after_seq.is_none_or(|after| e.seq > after))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Replay buffer managed by Tauri
+#[derive(Default)]
+pub struct ReplayState {
+    buffers: Mutex<ReplayBuffers>,
+}
+
+/// Buffer an emitted run event for its session
+pub fn record<S: Serialize>(app: &AppHandle, session_key: Option<&str>, event: &str, payload: &S) {
+    let Some(session_key) = session_key else {
+        return;
+    };
+    let Some(state) = app.try_state::<ReplayState>() else {
+        return;
+    };
+    let Ok(payload) = serde_json::to_value(payload) else {
+        return;
+    };
+    let Ok(mut buffers) = state.buffers.lock() else {
+        return;
+    };
+    buffers.record(session_key, event, payload, Instant::now());
+}
+
+/// Recent run events for a session, oldest first
+///
+/// Pass `after_seq` (the last `seq` already seen) to get only newer events.
+#[tauri::command]
+pub fn replay_recent_events(
+    state: State<'_, ReplayState>,
+    session_key: String,
+    after_seq: Option<u64>,
+) -> Result<Vec<ReplayedEvent>, CommandError> {
+    let mut buffers = state
+        .buffers
+        .lock()
+        .map_err(|e| CommandError::internal(e.to_string()))?;
+    Ok(buffers.recent(&session_key, after_seq, Instant::now()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_buffer_bounds() {
+        let mut buffers = ReplayBuffers::default();
+        let start = Instant::now();
+        for i in 0..MAX_EVENTS_PER_SESSION + 10 {
+            buffers.record("main", "gateway:stream", serde_json::json!(i), start);
+        }
+        buffers.record("other", "gateway:complete", serde_json::json!({}), start);
+
+        let events = buffers.recent("main", None, start);
+        assert_eq!(events.len(), MAX_EVENTS_PER_SESSION);
+        assert_eq!(events[0].payload, serde_json::json!(10));
+
+        let last = events.last().unwrap().seq;
+        assert!(buffers.recent("main", Some(last), start).is_empty());
+        assert_eq!(buffers.recent("main", Some(last - 2), start).len(), 2);
+
+        // Old events age out
+        let later = start + MAX_EVENT_AGE + Duration::from_secs(1);
+        assert!(buffers.recent("other", None, later).is_empty());
+
+        // The stalest session makes room for new ones
+        for i in 0..MAX_SESSIONS {
+            buffers.record(
+                &format!("s{}", i),
+                "gateway:stream",
+                serde_json::json!(i),
+                later,
+            );
+        }
+        assert_eq!(buffers.sessions.len(), MAX_SESSIONS);
+        assert!(!buffers.sessions.contains_key("other"));
+    }
+}
@@ -0,0 +1,256 @@
+//! Git context attachments
+//!
+//! Collects context from a local repository and returns it as a text
+//! attachment for the next message:
+//! - `diff`: unstaged working tree changes
+//! - `staged`: staged changes
+//! - `tree`: file tree summary (respects .gitignore)
+//!
+//! Output is capped to a size budget; diffs are cut at file boundaries and the
+//! omitted files are listed.
+
+use crate::error::CommandError;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tokio::process::Command;
+
+/// Size budget for the collected context
+const MAX_CONTEXT_BYTES: usize = 200 * 1024;
+
+/// Maximum number of files listed in a tree summary
+const MAX_TREE_FILES: usize = 2000;
+
+/// What to collect from the repository
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RepoContextMode {
+    Diff,
+    Staged,
+    Tree,
+}
+
+/// Repository context ready to attach (same shape as a frontend attachment)
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoContextAttachment {
+    pub id: String,
+    pub filename: String,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+    /// Base64 encoded content
+    pub data: String,
+    /// Repository root
+    pub path: String,
+    /// Whether the content was cut to fit the size budget
+    pub truncated: bool,
+}
+
+/// Run git in a repository and return stdout
+async fn git(repo: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Cut a diff to the budget at `diff --git` boundaries
+///
+/// Returns the kept diff and the paths of the omitted files.
+fn truncate_diff(diff: &str, budget: usize) -> (String, Vec<String>) {
+    let mut kept = String::new();
+    let mut omitted = Vec::new();
+
+    let mut files: Vec<&str> = Vec::new();
+    let mut start = 0;
+    for (i, _) in diff.match_indices("diff --git ") {
+        if i > start {
+            files.push(&diff[start..i]);
+        }
+        start = i;
+    }
+    if start < diff.len() {
+        files.push(&diff[start..]);
+    }
+
+    for file in files {
+        if kept.len() + file.len() <= budget {
+            kept.push_str(file);
+        } else {
+            let name = file
+                .lines()
+                .next()
+                .and_then(|line| line.rsplit(" b/").next())
+                .unwrap_or("unknown")
+                .to_string();
+            omitted.push(name);
+        }
+    }
+    (kept, omitted)
+}
+
+/// Render a file list as an indented tree
+fn render_tree(files: &[&str]) -> String {
+    let mut lines = Vec::new();
+    let mut previous: Vec<&str> = Vec::new();
+
+    for file in files {
+        let parts: Vec<&str> = file.split('/').collect();
+        let (dirs, name) = parts.split_at(parts.len() - 1);
+
+        let common = previous
+            .iter()
+            .zip(dirs.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        for (depth, dir) in dirs.iter().enumerate().skip(common) {
+            lines.push(format!("{}{}/", "  ".repeat(depth), dir));
+        }
+        lines.push(format!("{}{}", "  ".repeat(dirs.len()), name[0]));
+        previous = dirs.to_vec();
+    }
+    lines.join("\n")
+}
+
+/// Collect context from a repository
+async fn collect(repo: &Path, mode: RepoContextMode) -> Result<(String, bool), String> {
+    let branch = git(repo, &["rev-parse", "--abbrev-ref", "HEAD"])
+        .await
+        .map(|b| b.trim().to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+    let name = repo
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut header = format!("Repository: {} (branch {})\n", name, branch);
+
+    let (body, truncated) = match mode {
+        RepoContextMode::Diff | RepoContextMode::Staged => {
+            let args: &[&str] = if mode == RepoContextMode::Staged {
+                &["diff", "--cached", "--no-color", "--no-ext-diff"]
+            } else {
+                &["diff", "--no-color", "--no-ext-diff"]
+            };
+            let diff = git(repo, args).await?;
+            if diff.trim().is_empty() {
+                return Err(if mode == RepoContextMode::Staged {
+                    "No staged changes".to_string()
+                } else {
+                    "No uncommitted changes".to_string()
+                });
+            }
+            header.push_str(if mode == RepoContextMode::Staged {
+                "Staged changes:\n\n"
+            } else {
+                "Uncommitted changes:\n\n"
+            });
+            let (kept, omitted) = truncate_diff(&diff, MAX_CONTEXT_BYTES);
+            let mut body = kept;
+            if !omitted.is_empty() {
+                body.push_str(&format!(
+                    "\n[{} file(s) omitted to fit the size limit: {}]\n",
+                    omitted.len(),
+                    omitted.join(", ")
+                ));
+            }
+            (body, !omitted.is_empty())
+        }
+        RepoContextMode::Tree => {
+            let listing = git(
+                repo,
+                &["ls-files", "--cached", "--others", "--exclude-standard"],
+            )
+            .await?;
+            let mut files: Vec<&str> = listing.lines().filter(|l| !l.is_empty()).collect();
+            files.sort_unstable();
+            files.dedup();
+            let total = files.len();
+            let truncated = total > MAX_TREE_FILES;
+            files.truncate(MAX_TREE_FILES);
+
+            header.push_str(&format!("File tree ({} files):\n\n", total));
+            let mut body = render_tree(&files);
+            if truncated {
+                body.push_str(&format!("\n[{} more files not shown]", total - files.len()));
+            }
+            // A very deep tree can still exceed the budget
+            if body.len() > MAX_CONTEXT_BYTES {
+                let mut cut = MAX_CONTEXT_BYTES;
+                while !body.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                body.truncate(cut);
+                (body, true)
+            } else {
+                (body, truncated)
+            }
+        }
+    };
+
+    Ok((header + &body, truncated))
+}
+
+/// Collect git context from a local repository as an attachment
+#[tauri::command]
+pub async fn attach_repo_context(
+    path: String,
+    mode: RepoContextMode,
+) -> Result<RepoContextAttachment, CommandError> {
+    let root = git(Path::new(&path), &["rev-parse", "--show-toplevel"])
+        .await
+        .map_err(|_| CommandError::validation(format!("Not a git repository: {}", path)))?;
+    let root = Path::new(root.trim()).to_path_buf();
+
+    let (content, truncated) = collect(&root, mode).await?;
+
+    let name = root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "repo".to_string());
+    let filename = match mode {
+        RepoContextMode::Diff => format!("{}-changes.diff.txt", name),
+        RepoContextMode::Staged => format!("{}-staged.diff.txt", name),
+        RepoContextMode::Tree => format!("{}-tree.txt", name),
+    };
+
+    Ok(RepoContextAttachment {
+        id: uuid::Uuid::new_v4().to_string(),
+        filename,
+        mime_type: "text/plain".to_string(),
+        data: base64::engine::general_purpose::STANDARD.encode(content),
+        path: root.to_string_lossy().to_string(),
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_diff_at_file_boundaries() {
+        let diff = "diff --git a/one.rs b/one.rs\n+aaaa\ndiff --git a/two.rs b/two.rs\n+bbbbbbbbbbbbbbbbbbbb\n";
+        let (kept, omitted) = truncate_diff(diff, 40);
+        assert_eq!(kept, "diff --git a/one.rs b/one.rs\n+aaaa\n");
+        assert_eq!(omitted, vec!["two.rs"]);
+
+        let (kept, omitted) = truncate_diff(diff, 1000);
+        assert_eq!(kept, diff);
+        assert!(omitted.is_empty());
+    }
+
+    #[test]
+    fn test_render_tree() {
+        let tree = render_tree(&["README.md", "src/lib.rs", "src/ui/app.rs", "src/ui/view.rs"]);
+        assert_eq!(
+            tree,
+            "README.md\nsrc/\n  lib.rs\n  ui/\n    app.rs\n    view.rs"
+        );
+    }
+}
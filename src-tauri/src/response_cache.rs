@@ -0,0 +1,304 @@
+//! Local cache of answers to repeated identical prompts
+//!
+//! Opt-in. When enabled, a Quick Ask question whose model, normalized prompt
+//! and attachments match a previous one answered within the TTL gets that
+//! answer back instantly instead of a new run, saving tokens on repetitive
+//! lookups. Callers can bypass the cache per question ("Ask again").
+//!
+//! Prompts are normalized by trimming, collapsing whitespace and lowercasing.
+//! Attachments are keyed by an FNV-1a hash of their contents, so the key is
+//! stable across app versions.
+
+use crate::error::CommandError;
+use crate::store;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime, State};
+use tokio::sync::Mutex;
+
+/// Store file name for the cache and its settings
+const RESPONSE_CACHE_FILE: &str = "response_cache.json";
+
+/// Entries kept; the oldest are dropped first
+const MAX_ENTRIES: usize = 200;
+
+/// Default time to live of an entry
+const DEFAULT_TTL_HOURS: u32 = 24;
+
+/// Cache settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResponseCacheSettings {
+    pub enabled: bool,
+    #[serde(rename = "ttlHours")]
+    pub ttl_hours: u32,
+}
+
+impl ResponseCacheSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.ttl_hours == 0 {
+            return Err("Keep answers at least one hour".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl Default for ResponseCacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_hours: DEFAULT_TTL_HOURS,
+        }
+    }
+}
+
+/// Settings plus the number of cached answers, for the settings pane
+#[derive(Debug, Clone, Serialize)]
+pub struct ResponseCacheStatus {
+    #[serde(flatten)]
+    pub settings: ResponseCacheSettings,
+    pub entries: usize,
+}
+
+/// A cached answer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    model: Option<String>,
+    prompt: String,
+    #[serde(rename = "attachmentsHash")]
+    attachments_hash: String,
+    answer: String,
+    #[serde(rename = "createdAt")]
+    created_at: DateTime<Utc>,
+}
+
+/// Lookup key of a prompt
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheKey {
+    model: Option<String>,
+    prompt: String,
+    attachments_hash: String,
+}
+
+impl CacheKey {
+    pub fn new(model: Option<&str>, prompt: &str, attachments: &[&[u8]]) -> Self {
+        Self {
+            model: model.map(str::to_string),
+            prompt: normalize_prompt(prompt),
+            attachments_hash: attachments_hash(attachments),
+        }
+    }
+
+    fn matches(&self, entry: &CacheEntry) -> bool {
+        self.model == entry.model
+            && self.prompt == entry.prompt
+            && self.attachments_hash == entry.attachments_hash
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ResponseCacheData {
+    #[serde(default)]
+    settings: ResponseCacheSettings,
+    /// Oldest first
+    #[serde(default)]
+    entries: Vec<CacheEntry>,
+}
+
+impl ResponseCacheData {
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let ttl = chrono::Duration::hours(self.settings.ttl_hours as i64);
+        self.entries.retain(|e| now - e.created_at < ttl);
+        let excess = self.entries.len().saturating_sub(MAX_ENTRIES);
+        self.entries.drain(..excess);
+    }
+}
+
+/// Response cache managed by Tauri
+pub struct ResponseCacheState {
+    path: PathBuf,
+    data: Mutex<ResponseCacheData>,
+}
+
+impl ResponseCacheState {
+    /// Load the cache from the local store, dropping expired entries
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, RESPONSE_CACHE_FILE);
+        let mut data: ResponseCacheData = store::load(&path);
+        data.prune(Utc::now());
+        Self {
+            path,
+            data: Mutex::new(data),
+        }
+    }
+
+    /// A fresh cached answer for the key, if caching is on
+    pub async fn lookup(&self, key: &CacheKey) -> Option<String> {
+        let data = self.data.lock().await;
+        if !data.settings.enabled {
+            return None;
+        }
+        let ttl = chrono::Duration::hours(data.settings.ttl_hours as i64);
+        let now = Utc::now();
+        data.entries
+            .iter()
+            .rev()
+            .find(|e| key.matches(e) && now - e.created_at < ttl)
+            .map(|e| e.answer.clone())
+    }
+
+    /// Remember an answer, if caching is on
+    pub async fn insert(&self, key: CacheKey, answer: &str) -> Result<(), String> {
+        let mut data = self.data.lock().await;
+        if !data.settings.enabled {
+            return Ok(());
+        }
+        data.entries.retain(|e| !key.matches(e));
+        data.entries.push(CacheEntry {
+            model: key.model,
+            prompt: key.prompt,
+            attachments_hash: key.attachments_hash,
+            answer: answer.to_string(),
+            created_at: Utc::now(),
+        });
+        data.prune(Utc::now());
+        store::save(&self.path, &*data)
+    }
+
+    /// Current settings
+    pub async fn settings(&self) -> ResponseCacheSettings {
+        self.data.lock().await.settings.clone()
+    }
+
+    /// Replace the settings; turning the cache off also clears it
+    pub async fn apply(&self, settings: ResponseCacheSettings) -> Result<(), String> {
+        let mut data = self.data.lock().await;
+        if !settings.enabled {
+            data.entries.clear();
+        }
+        data.settings = settings;
+        data.prune(Utc::now());
+        store::save(&self.path, &*data)
+    }
+
+    async fn status(&self) -> ResponseCacheStatus {
+        let data = self.data.lock().await;
+        ResponseCacheStatus {
+            settings: data.settings.clone(),
+            entries: data.entries.len(),
+        }
+    }
+}
+
+/// Trim, collapse whitespace and lowercase a prompt
+fn normalize_prompt(prompt: &str) -> String {
+    prompt
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// FNV-1a hash of the attachments' contents, in order
+fn attachments_hash(attachments: &[&[u8]]) -> String {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    let mut hash = OFFSET;
+    for attachment in attachments {
+        // Length prefix so ["ab", "c"] and ["a", "bc"] differ
+        for byte in (attachment.len() as u64)
+            .to_le_bytes()
+            .iter()
+            .chain(attachment.iter())
+        {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// Get the cache settings and size
+#[tauri::command]
+pub async fn get_response_cache(
+    state: State<'_, ResponseCacheState>,
+) -> Result<ResponseCacheStatus, CommandError> {
+    Ok(state.status().await)
+}
+
+/// Turn the cache on or off and set its TTL
+///
+/// Turning it off also clears it.
+#[tauri::command]
+pub async fn set_response_cache(
+    state: State<'_, ResponseCacheState>,
+    settings: ResponseCacheSettings,
+) -> Result<ResponseCacheStatus, CommandError> {
+    settings.validate().map_err(CommandError::validation)?;
+    state.apply(settings).await?;
+    Ok(state.status().await)
+}
+
+/// Forget all cached answers
+#[tauri::command]
+pub async fn clear_response_cache(
+    state: State<'_, ResponseCacheState>,
+) -> Result<ResponseCacheStatus, CommandError> {
+    {
+        let mut data = state.data.lock().await;
+        data.entries.clear();
+        store::save(&state.path, &*data)?;
+    }
+    Ok(state.status().await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &CacheKey, age_hours: i64) -> CacheEntry {
+        CacheEntry {
+            model: key.model.clone(),
+            prompt: key.prompt.clone(),
+            attachments_hash: key.attachments_hash.clone(),
+            answer: "42".to_string(),
+            created_at: Utc::now() - chrono::Duration::hours(age_hours),
+        }
+    }
+
+    #[test]
+    fn test_key_normalizes_prompt() {
+        let a = CacheKey::new(Some("m"), "  What is   the\nanswer? ", &[]);
+        let b = CacheKey::new(Some("m"), "what is the answer?", &[]);
+        assert_eq!(a, b);
+        assert_ne!(a, CacheKey::new(None, "what is the answer?", &[]));
+        assert_ne!(
+            a,
+            CacheKey::new(Some("m"), "what is the answer?", &[&b"file"[..]])
+        );
+    }
+
+    #[test]
+    fn test_attachments_hash_is_order_and_boundary_sensitive() {
+        assert_ne!(
+            attachments_hash(&[&b"ab"[..], b"c"]),
+            attachments_hash(&[&b"a"[..], b"bc"])
+        );
+        assert_eq!(
+            attachments_hash(&[&b"x"[..]]),
+            attachments_hash(&[&b"x"[..]])
+        );
+    }
+
+    #[test]
+    fn test_prune_drops_expired_and_oldest() {
+        let key = CacheKey::new(None, "q", &[]);
+        let mut data = ResponseCacheData::default();
+        data.entries.push(entry(&key, DEFAULT_TTL_HOURS as i64 + 1));
+        for _ in 0..MAX_ENTRIES + 5 {
+            data.entries.push(entry(&key, 0));
+        }
+        data.prune(Utc::now());
+        assert_eq!(data.entries.len(), MAX_ENTRIES);
+    }
+}
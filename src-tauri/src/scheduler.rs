@@ -0,0 +1,626 @@
+//! Scheduled prompts
+//!
+//! Recurring messages ("every weekday at 9:00, summarize my inbox") stored
+//! locally and sent by the backend, without the frontend being involved:
+//! - Schedules fire at a local time of day on selected weekdays, in the
+//!   schedule's IANA timezone (or the system timezone)
+//! - When a schedule fires, the scheduler connects to the Gateway if needed,
+//!   sends the message and collects the reply from the run's chat events
+//! - The result is posted as a notification and emitted as
+//!   `scheduler:run_complete`
+//!
+//! Runs missed while the app was closed are caught up on startup if they are
+//! less than [`MISSED_RUN_GRACE_SECS`] late, and skipped otherwise.
+//!
+//! The reply capture ([`send_and_collect`]) is shared with the Quick Ask
+//! queue, which runs questions in the background the same way.
+
+use crate::error::CommandError;
+use crate::gateway::{ChatEvent, GatewayState};
+use crate::sessions::SessionState;
+use crate::{notifications, plaintext, sessions, store};
+use chrono::{DateTime, Datelike, Local, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+use tokio::sync::{mpsc, oneshot, Mutex};
+
+/// Store file name for schedules
+const SCHEDULES_FILE: &str = "schedules.json";
+
+/// How often due schedules are checked
+const SCHEDULER_TICK_SECS: u64 = 30;
+
+/// Late runs within this window are still sent on startup
+pub const MISSED_RUN_GRACE_SECS: i64 = 60 * 60;
+
+/// How long to wait for a scheduled run's reply
+const SCHEDULED_RUN_TIMEOUT_SECS: u64 = 10 * 60;
+
+/// Length of the reply excerpt kept as the last result
+const RESULT_EXCERPT_CHARS: usize = 280;
+
+/// When a schedule fires
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Recurrence {
+    /// Local time of day, `HH:MM`
+    pub time: String,
+    /// ISO weekdays (1 = Monday ... 7 = Sunday); empty means every day
+    #[serde(default)]
+    pub days: Vec<u8>,
+}
+
+impl Recurrence {
+    fn time_of_day(&self) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(&self.time, "%H:%M").ok()
+    }
+
+    fn runs_on(&self, weekday: chrono::Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&(weekday.number_from_monday() as u8))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.time_of_day().is_none() {
+            return Err(format!("Invalid time '{}' (expected HH:MM)", self.time));
+        }
+        if let Some(day) = self.days.iter().find(|d| !(1..=7).contains(*d)) {
+            return Err(format!("Invalid weekday {} (expected 1-7)", day));
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of the last run of a schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledRunResult {
+    pub ok: bool,
+    pub at: DateTime<Utc>,
+    /// Start of the reply, or the error
+    pub excerpt: String,
+}
+
+/// A recurring prompt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledPrompt {
+    pub id: String,
+    pub name: String,
+    pub message: String,
+    #[serde(rename = "sessionKey")]
+    pub session_key: String,
+    pub recurrence: Recurrence,
+    /// IANA timezone (e.g. `Europe/Paris`); None = system timezone
+    pub timezone: Option<String>,
+    /// Gateway to connect to when the app is not connected
+    #[serde(rename = "gatewayUrl")]
+    pub gateway_url: Option<String>,
+    pub enabled: bool,
+    #[serde(rename = "nextRun")]
+    pub next_run: Option<DateTime<Utc>>,
+    #[serde(rename = "lastResult")]
+    pub last_result: Option<ScheduledRunResult>,
+}
+
+/// Fields the user edits
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduledPromptInput {
+    pub name: String,
+    pub message: String,
+    /// Existing session to send on; a new one is created when missing
+    #[serde(rename = "sessionKey")]
+    pub session_key: Option<String>,
+    pub recurrence: Recurrence,
+    pub timezone: Option<String>,
+    #[serde(rename = "gatewayUrl")]
+    pub gateway_url: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Payload of `scheduler:run_complete`
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledRunComplete {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "sessionKey")]
+    pub session_key: String,
+    pub ok: bool,
+    /// Full reply, or the error
+    pub text: String,
+}
+
+/// Progress of a background run, for callers that report it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RunStage {
+    /// Connected to the Gateway
+    Connected,
+    /// The Gateway accepted the message
+    Sent,
+    /// The first streamed text arrived
+    Streaming,
+}
+
+/// Reply being collected for a background run
+struct RunCapture {
+    text: String,
+    done: Option<oneshot::Sender<Result<String, String>>>,
+    /// Taken when the first delta arrives
+    stages: Option<mpsc::UnboundedSender<RunStage>>,
+}
+
+/// Scheduler state managed by Tauri
+pub struct SchedulerState {
+    path: PathBuf,
+    schedules: Mutex<Vec<ScheduledPrompt>>,
+    /// Background runs in flight, keyed by session key
+    captures: Mutex<HashMap<String, RunCapture>>,
+}
+
+impl SchedulerState {
+    /// Load schedules from the local store, skipping runs missed too long ago
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, SCHEDULES_FILE);
+        let mut schedules: Vec<ScheduledPrompt> = store::load(&path);
+        let now = Utc::now();
+        for schedule in &mut schedules {
+            let missed = schedule
+                .next_run
+                .is_some_and(|next| (now - next).num_seconds() > MISSED_RUN_GRACE_SECS);
+            if missed || schedule.next_run.is_none() {
+                schedule.next_run = next_run(schedule, now);
+            }
+        }
+        Self {
+            path,
+            schedules: Mutex::new(schedules),
+            captures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn save(&self, schedules: &[ScheduledPrompt]) -> Result<(), String> {
+        store::save(&self.path, &schedules)
+    }
+}
+
+/// Next time a recurrence fires after `after`, in the given timezone
+fn next_occurrence<Tz: TimeZone>(
+    tz: &Tz,
+    recurrence: &Recurrence,
+    after: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let time = recurrence.time_of_day()?;
+    let start = after.with_timezone(tz).date_naive();
+    for offset in 0..=7 {
+        let date = start + chrono::Days::new(offset);
+        if !recurrence.runs_on(date.weekday()) {
+            continue;
+        }
+        let naive = date.and_time(time);
+        // A time skipped by a DST change fires an hour later
+        let local = tz.from_local_datetime(&naive).earliest().or_else(|| {
+            tz.from_local_datetime(&(naive + chrono::Duration::hours(1)))
+                .earliest()
+        })?;
+        let utc = local.with_timezone(&Utc);
+        if utc > after {
+            return Some(utc);
+        }
+    }
+    None
+}
+
+/// Next run of a schedule after `after` (None when disabled or invalid)
+fn next_run(schedule: &ScheduledPrompt, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if !schedule.enabled {
+        return None;
+    }
+    match &schedule.timezone {
+        Some(name) => {
+            let tz: chrono_tz::Tz = name.parse().ok()?;
+            next_occurrence(&tz, &schedule.recurrence, after)
+        }
+        None => next_occurrence(&Local, &schedule.recurrence, after),
+    }
+}
+
+fn validate_input(input: &ScheduledPromptInput) -> Result<(), String> {
+    if input.message.trim().is_empty() {
+        return Err("Message cannot be empty".to_string());
+    }
+    input.recurrence.validate()?;
+    if let Some(name) = &input.timezone {
+        name.parse::<chrono_tz::Tz>()
+            .map_err(|_| format!("Unknown timezone '{}'", name))?;
+    }
+    Ok(())
+}
+
+fn excerpt(text: &str) -> String {
+    plaintext::summary(text, RESULT_EXCERPT_CHARS)
+}
+
+// ============================================================================
+// Run capture (fed by the Gateway's chat event handler)
+// ============================================================================
+
+/// Feed a chat event to the background run waiting on its session
+///
+/// Returns true when the event belongs to a background run; such events are
+/// not forwarded to any window.
+pub async fn capture_chat_event(app: &AppHandle, event: &ChatEvent, text: Option<String>) -> bool {
+    let Some(session_key) = &event.session_key else {
+        return false;
+    };
+    let Some(state) = app.try_state::<SchedulerState>() else {
+        return false;
+    };
+    let mut captures = state.captures.lock().await;
+    let Some(capture) = captures.get_mut(session_key) else {
+        return false;
+    };
+
+    let outcome = match event.state.as_deref() {
+        Some("delta") => {
+            if let Some(stages) = capture.stages.take() {
+                let _ = stages.send(RunStage::Streaming);
+            }
+            if let Some(text) = text {
+                capture.text.push_str(&text);
+            }
+            None
+        }
+        Some("final") => {
+            // Use the final message when no deltas were streamed
+            if capture.text.is_empty() {
+                if let Some(text) = text {
+                    capture.text = text;
+                }
+            }
+            Some(Ok(std::mem::take(&mut capture.text)))
+        }
+        Some("aborted") => Some(Err("Run was aborted".to_string())),
+        Some("error") => Some(Err(event
+            .error_message
+            .clone()
+            .unwrap_or_else(|| "Unknown error".to_string()))),
+        _ => None,
+    };
+    if let Some(outcome) = outcome {
+        if let Some(done) = capture.done.take() {
+            let _ = done.send(outcome);
+        }
+    }
+    true
+}
+
+/// Send a message on a session in the background and wait for the reply
+///
+/// Connects first if needed (to `gateway_url` when there is no previous
+/// connection). Only one background run per session at a time.
+pub(crate) async fn send_and_collect(
+    app: &AppHandle,
+    session_key: &str,
+    message: &str,
+    gateway_url: Option<&str>,
+    timeout: Duration,
+) -> Result<String, String> {
+    send_and_collect_with_stages(app, session_key, message, gateway_url, timeout, None).await
+}
+
+/// [`send_and_collect`], reporting each stage the run reaches to `stages`
+pub(crate) async fn send_and_collect_with_stages(
+    app: &AppHandle,
+    session_key: &str,
+    message: &str,
+    gateway_url: Option<&str>,
+    timeout: Duration,
+    stages: Option<mpsc::UnboundedSender<RunStage>>,
+) -> Result<String, String> {
+    let state = app.state::<SchedulerState>();
+    let gateway = app.state::<GatewayState>();
+
+    gateway
+        .ensure_connected(app, gateway_url)
+        .await
+        .map_err(|e| e.to_string())?;
+    let report = |stage| {
+        if let Some(stages) = &stages {
+            let _ = stages.send(stage);
+        }
+    };
+    report(RunStage::Connected);
+
+    let (done_tx, done_rx) = oneshot::channel();
+    {
+        let mut captures = state.captures.lock().await;
+        if captures.contains_key(session_key) {
+            return Err("The previous run is still in progress".to_string());
+        }
+        captures.insert(
+            session_key.to_string(),
+            RunCapture {
+                text: String::new(),
+                done: Some(done_tx),
+                stages: stages.clone(),
+            },
+        );
+    }
+
+    let mut params = serde_json::json!({
+        "message": message,
+        "sessionKey": session_key,
+        "idempotencyKey": uuid::Uuid::new_v4().to_string(),
+    });
+    if let Some(agent_id) = app.state::<SessionState>().agent_for(session_key).await {
+        params["agentId"] = serde_json::json!(agent_id);
+    }
+    let result = match gateway.request("chat.send", Some(params)).await {
+        Ok(_) => {
+            report(RunStage::Sent);
+            match tokio::time::timeout(timeout, done_rx).await {
+                Ok(Ok(outcome)) => outcome,
+                Ok(Err(_)) => Err("Run was interrupted".to_string()),
+                Err(_) => Err("Timed out waiting for the reply".to_string()),
+            }
+        }
+        Err(e) => Err(e.user_message()),
+    };
+
+    state.captures.lock().await.remove(session_key);
+    result
+}
+
+/// Send a schedule's message and wait for the reply
+async fn execute(app: &AppHandle, schedule: &ScheduledPrompt) -> Result<String, String> {
+    send_and_collect(
+        app,
+        &schedule.session_key,
+        &schedule.message,
+        schedule.gateway_url.as_deref(),
+        Duration::from_secs(SCHEDULED_RUN_TIMEOUT_SECS),
+    )
+    .await
+}
+
+/// Run a schedule and report the result
+async fn run_and_report(app: AppHandle, schedule: ScheduledPrompt) {
+    log::info!("[Scheduler] Running '{}'", schedule.name);
+    let result = execute(&app, &schedule).await;
+
+    let (ok, text) = match result {
+        Ok(text) => (true, text),
+        Err(e) => (false, e),
+    };
+    let last_result = ScheduledRunResult {
+        ok,
+        at: Utc::now(),
+        excerpt: excerpt(&text),
+    };
+
+    let state = app.state::<SchedulerState>();
+    {
+        let mut schedules = state.schedules.lock().await;
+        if let Some(stored) = schedules.iter_mut().find(|s| s.id == schedule.id) {
+            stored.last_result = Some(last_result.clone());
+        }
+        if let Err(e) = state.save(&schedules).await {
+            log::error!("[Scheduler] Failed to save run result: {}", e);
+        }
+    }
+
+    let title = if ok {
+        schedule.name.clone()
+    } else {
+        format!("{} failed", schedule.name)
+    };
+    notifications::show(&app, None, &title, &last_result.excerpt).await;
+    let _ = app.emit(
+        "scheduler:run_complete",
+        ScheduledRunComplete {
+            id: schedule.id,
+            name: schedule.name,
+            session_key: schedule.session_key,
+            ok,
+            text,
+        },
+    );
+}
+
+/// Start firing due schedules
+pub fn start_scheduler(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let due: Vec<ScheduledPrompt> = {
+                let state = app.state::<SchedulerState>();
+                let mut schedules = state.schedules.lock().await;
+                let now = Utc::now();
+                let mut due = Vec::new();
+                for schedule in schedules.iter_mut() {
+                    if schedule.next_run.is_some_and(|next| next <= now) {
+                        due.push(schedule.clone());
+                        schedule.next_run = next_run(schedule, now);
+                    }
+                }
+                if !due.is_empty() {
+                    if let Err(e) = state.save(&schedules).await {
+                        log::error!("[Scheduler] Failed to save schedules: {}", e);
+                    }
+                }
+                due
+            };
+
+            for schedule in due {
+                tauri::async_runtime::spawn(run_and_report(app.clone(), schedule));
+            }
+
+            tokio::time::sleep(Duration::from_secs(SCHEDULER_TICK_SECS)).await;
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// List scheduled prompts
+#[tauri::command]
+pub async fn list_scheduled_prompts(
+    state: State<'_, SchedulerState>,
+) -> Result<Vec<ScheduledPrompt>, CommandError> {
+    Ok(state.schedules.lock().await.clone())
+}
+
+/// Create a scheduled prompt
+#[tauri::command]
+pub async fn create_scheduled_prompt(
+    state: State<'_, SchedulerState>,
+    input: ScheduledPromptInput,
+) -> Result<ScheduledPrompt, CommandError> {
+    validate_input(&input).map_err(CommandError::validation)?;
+    let mut schedule = ScheduledPrompt {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: input.name,
+        message: input.message,
+        session_key: input
+            .session_key
+            .unwrap_or_else(sessions::generate_session_key),
+        recurrence: input.recurrence,
+        timezone: input.timezone,
+        gateway_url: input.gateway_url,
+        enabled: input.enabled,
+        next_run: None,
+        last_result: None,
+    };
+    schedule.next_run = next_run(&schedule, Utc::now());
+
+    let mut schedules = state.schedules.lock().await;
+    schedules.push(schedule.clone());
+    state.save(&schedules).await?;
+    Ok(schedule)
+}
+
+/// Update a scheduled prompt
+#[tauri::command]
+pub async fn update_scheduled_prompt(
+    state: State<'_, SchedulerState>,
+    id: String,
+    input: ScheduledPromptInput,
+) -> Result<ScheduledPrompt, CommandError> {
+    validate_input(&input).map_err(CommandError::validation)?;
+    let mut schedules = state.schedules.lock().await;
+    let schedule = schedules
+        .iter_mut()
+        .find(|s| s.id == id)
+        .ok_or_else(|| CommandError::validation(format!("Scheduled prompt not found: {}", id)))?;
+
+    schedule.name = input.name;
+    schedule.message = input.message;
+    if let Some(session_key) = input.session_key {
+        schedule.session_key = session_key;
+    }
+    schedule.recurrence = input.recurrence;
+    schedule.timezone = input.timezone;
+    schedule.gateway_url = input.gateway_url;
+    schedule.enabled = input.enabled;
+    schedule.next_run = next_run(schedule, Utc::now());
+    let updated = schedule.clone();
+
+    state.save(&schedules).await?;
+    Ok(updated)
+}
+
+/// Delete a scheduled prompt
+#[tauri::command]
+pub async fn delete_scheduled_prompt(
+    state: State<'_, SchedulerState>,
+    id: String,
+) -> Result<(), CommandError> {
+    let mut schedules = state.schedules.lock().await;
+    let before = schedules.len();
+    schedules.retain(|s| s.id != id);
+    if schedules.len() == before {
+        return Err(CommandError::validation(format!(
+            "Scheduled prompt not found: {}",
+            id
+        )));
+    }
+    state.save(&schedules).await.map_err(CommandError::internal)
+}
+
+/// Run a scheduled prompt now (the result arrives as `scheduler:run_complete`)
+#[tauri::command]
+pub async fn run_scheduled_prompt_now(
+    app: AppHandle,
+    state: State<'_, SchedulerState>,
+    id: String,
+) -> Result<(), CommandError> {
+    let schedule = state
+        .schedules
+        .lock()
+        .await
+        .iter()
+        .find(|s| s.id == id)
+        .cloned()
+        .ok_or_else(|| CommandError::validation(format!("Scheduled prompt not found: {}", id)))?;
+    tauri::async_runtime::spawn(run_and_report(app, schedule));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weekdays_at(time: &str) -> Recurrence {
+        Recurrence {
+            time: time.to_string(),
+            days: vec![1, 2, 3, 4, 5],
+        }
+    }
+
+    #[test]
+    fn test_next_occurrence_skips_weekend() {
+        let tz = chrono_tz::Europe::Paris;
+        // Friday 2026-03-13 10:00 Paris (09:00 UTC), after the 9:00 run
+        let after = Utc.with_ymd_and_hms(2026, 3, 13, 9, 0, 0).unwrap();
+        let next = next_occurrence(&tz, &weekdays_at("09:00"), after).unwrap();
+        // Monday 2026-03-16 09:00 Paris = 08:00 UTC
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 3, 16, 8, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_next_occurrence_same_day_and_dst() {
+        let tz = chrono_tz::Europe::Paris;
+        let every_day = |time: &str| Recurrence {
+            time: time.to_string(),
+            days: vec![],
+        };
+
+        // Later the same day
+        let after = Utc.with_ymd_and_hms(2026, 3, 13, 6, 0, 0).unwrap();
+        let next = next_occurrence(&tz, &every_day("09:00"), after).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 3, 13, 8, 0, 0).unwrap());
+
+        // 02:30 does not exist on 2026-03-29 in Paris (clocks go 02:00 -> 03:00)
+        let after = Utc.with_ymd_and_hms(2026, 3, 28, 23, 0, 0).unwrap();
+        let next = next_occurrence(&tz, &every_day("02:30"), after).unwrap();
+        // Fires at 03:30 CEST = 01:30 UTC
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 3, 29, 1, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn test_recurrence_validation() {
+        assert!(weekdays_at("09:00").validate().is_ok());
+        assert!(weekdays_at("9am").validate().is_err());
+        let bad_day = Recurrence {
+            time: "09:00".to_string(),
+            days: vec![0],
+        };
+        assert!(bad_day.validate().is_err());
+    }
+}
@@ -0,0 +1,188 @@
+//! Capture the text selection of the frontmost app
+//!
+//! - Linux: read the PRIMARY selection (no copy needed), falling back to a
+//!   simulated copy
+//! - macOS / Windows: simulate Cmd/Ctrl+C and read the clipboard, then restore
+//!   the previous clipboard contents
+//!
+//! On macOS simulating the copy requires the Accessibility permission.
+
+use arboard::Clipboard;
+use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// How long to wait for the frontmost app to put the selection on the clipboard
+const COPY_TIMEOUT_MS: u64 = 500;
+
+/// Clipboard poll interval while waiting for the copy
+const COPY_POLL_MS: u64 = 25;
+
+/// Selection captured from the frontmost app
+#[derive(Debug, Clone, Serialize)]
+pub struct CapturedSelection {
+    pub text: String,
+    /// Name of the app the selection was taken from (best-effort)
+    #[serde(rename = "sourceApp")]
+    pub source_app: Option<String>,
+}
+
+/// Capture the current selection and the frontmost app name
+///
+/// Must be called before any of our windows take focus. Blocking; run it on a
+/// blocking thread.
+pub fn capture() -> Result<CapturedSelection, String> {
+    let source_app = frontmost_app_name();
+
+    #[cfg(target_os = "linux")]
+    if let Some(text) = primary_selection() {
+        return Ok(CapturedSelection { text, source_app });
+    }
+
+    let text = copy_selection()?;
+    Ok(CapturedSelection { text, source_app })
+}
+
+/// Read the X11/Wayland PRIMARY selection
+#[cfg(target_os = "linux")]
+fn primary_selection() -> Option<String> {
+    use arboard::{GetExtLinux, LinuxClipboardKind};
+
+    let mut clipboard = Clipboard::new().ok()?;
+    clipboard
+        .get()
+        .clipboard(LinuxClipboardKind::Primary)
+        .text()
+        .ok()
+        .filter(|text| !text.trim().is_empty())
+}
+
+/// Simulate a copy in the frontmost app and read the result, restoring the
+/// previous clipboard text
+fn copy_selection() -> Result<String, String> {
+    let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
+    let previous = clipboard.get_text().ok();
+    // Clear so a failed copy is not mistaken for the old clipboard contents
+    let _ = clipboard.clear();
+
+    simulate_copy()?;
+
+    let deadline = Instant::now() + Duration::from_millis(COPY_TIMEOUT_MS);
+    let mut copied = None;
+    while Instant::now() < deadline {
+        if let Ok(text) = clipboard.get_text() {
+            if !text.is_empty() {
+                copied = Some(text);
+                break;
+            }
+        }
+        std::thread::sleep(Duration::from_millis(COPY_POLL_MS));
+    }
+
+    if let Some(previous) = previous {
+        let _ = clipboard.set_text(previous);
+    }
+
+    copied
+        .filter(|text| !text.trim().is_empty())
+        .ok_or_else(|| "No text selected".to_string())
+}
+
+/// Press Cmd/Ctrl+C in the frontmost app
+fn simulate_copy() -> Result<(), String> {
+    let mut enigo = Enigo::new(&Settings::default()).map_err(|e| e.to_string())?;
+
+    // The hotkey's modifiers may still be held; release them so the app sees
+    // a plain copy rather than e.g. Ctrl+Alt+C
+    for modifier in [Key::Alt, Key::Shift, Key::Control, Key::Meta] {
+        let _ = enigo.key(modifier, Direction::Release);
+    }
+
+    #[cfg(target_os = "macos")]
+    let copy_modifier = Key::Meta;
+    #[cfg(not(target_os = "macos"))]
+    let copy_modifier = Key::Control;
+
+    enigo
+        .key(copy_modifier, Direction::Press)
+        .map_err(|e| e.to_string())?;
+    let result = enigo.key(Key::Unicode('c'), Direction::Click);
+    let _ = enigo.key(copy_modifier, Direction::Release);
+    result.map_err(|e| e.to_string())
+}
+
+/// Name of the frontmost application
+#[cfg(target_os = "macos")]
+fn frontmost_app_name() -> Option<String> {
+    let output = std::process::Command::new("osascript")
+        .args([
+            "-e",
+            "tell application \"System Events\" to get name of first application process whose frontmost is true",
+        ])
+        .output()
+        .ok()?;
+    non_empty(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+/// Name of the frontmost application
+#[cfg(target_os = "windows")]
+fn frontmost_app_name() -> Option<String> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowThreadProcessId,
+    };
+
+    // SAFETY: plain Win32 calls; the process handle is closed before returning
+    // and the buffer length is passed alongside the buffer.
+    let path = unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_null() {
+            return None;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, &mut pid);
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process.is_null() {
+            return None;
+        }
+        let mut buffer = [0u16; 260];
+        let mut len = buffer.len() as u32;
+        let ok =
+            QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, buffer.as_mut_ptr(), &mut len);
+        CloseHandle(process);
+        if ok == 0 {
+            return None;
+        }
+        String::from_utf16_lossy(&buffer[..len as usize])
+    };
+
+    let name = std::path::Path::new(&path).file_stem()?.to_string_lossy();
+    non_empty(&name)
+}
+
+/// Name of the frontmost application (X11 only, needs xdotool)
+#[cfg(target_os = "linux")]
+fn frontmost_app_name() -> Option<String> {
+    let output = std::process::Command::new("xdotool")
+        .args(["getactivewindow", "getwindowclassname"])
+        .output()
+        .ok()?;
+    non_empty(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn frontmost_app_name() -> Option<String> {
+    None
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s.to_string())
+    }
+}
@@ -0,0 +1,447 @@
+//! Session key generation and lifecycle management
+//!
+//! Session keys used to be whatever the frontend passed. The session manager
+//! now owns them:
+//! - Generates collision-free keys
+//! - Tracks which sessions are open
+//! - Associates each session with a conversation in the local store
+//! - Informs the Gateway when sessions are created or closed
+//! - Remembers the agent chosen for each session, which `send_message`
+//!   passes along with every `chat.send`
+//! - Remembers each session's model and thinking level, which `send_message`
+//!   fills in when the frontend omits them. A newly chosen model stays
+//!   pending until the Gateway accepts the `sessions.patch` switching to it
+
+use crate::error::CommandError;
+use crate::gateway::GatewayState;
+use crate::presets::PresetState;
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime, State};
+use tokio::sync::Mutex;
+
+/// Store file name for session records
+const SESSIONS_FILE: &str = "sessions.json";
+
+/// Prefix for client-generated session keys
+const SESSION_KEY_PREFIX: &str = "moltz";
+
+/// A session tracked by the client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRecord {
+    #[serde(rename = "sessionKey")]
+    pub session_key: String,
+    #[serde(rename = "conversationId")]
+    pub conversation_id: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(rename = "closedAt")]
+    pub closed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Agent chosen for this session; None = the Gateway's default agent
+    #[serde(rename = "agentId", default)]
+    pub agent_id: Option<String>,
+    /// Model the Gateway last accepted for this session
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Model chosen for this session that the Gateway hasn't accepted yet
+    #[serde(rename = "pendingModel", default)]
+    pub pending_model: Option<String>,
+    /// Thinking level last chosen for this session ("off" = disabled)
+    #[serde(default)]
+    pub thinking: Option<String>,
+}
+
+/// A session's default model and thinking level
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SessionDefaults {
+    pub model: Option<String>,
+    pub thinking: Option<String>,
+}
+
+/// Defaults resolved for a send
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedDefaults {
+    pub defaults: SessionDefaults,
+    /// The resolved model still has to be switched to on the Gateway
+    pub model_changed: bool,
+}
+
+/// Fill in omitted values from a record's defaults and remember explicit ones
+///
+/// An explicit model other than the accepted one becomes pending; choosing
+/// the accepted one again drops a pending switch.
+fn resolve_defaults(record: &mut SessionRecord, requested: SessionDefaults) -> ResolvedDefaults {
+    if let Some(model) = requested.model {
+        record.pending_model = (record.model.as_ref() != Some(&model)).then_some(model);
+    }
+    if requested.thinking.is_some() {
+        record.thinking = requested.thinking;
+    }
+    ResolvedDefaults {
+        defaults: SessionDefaults {
+            model: record
+                .pending_model
+                .clone()
+                .or_else(|| record.model.clone()),
+            thinking: record.thinking.clone(),
+        },
+        model_changed: record.pending_model.is_some(),
+    }
+}
+
+/// Record that the Gateway accepted `model` for a record's session
+fn confirm_model(record: &mut SessionRecord, model: &str) {
+    record.model = Some(model.to_string());
+    if record.pending_model.as_deref() == Some(model) {
+        record.pending_model = None;
+    }
+}
+
+impl SessionRecord {
+    fn new(session_key: String, conversation_id: Option<String>) -> Self {
+        Self {
+            session_key,
+            conversation_id,
+            created_at: chrono::Utc::now(),
+            closed_at: None,
+            agent_id: None,
+            model: None,
+            pending_model: None,
+            thinking: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.closed_at.is_none()
+    }
+}
+
+/// Session manager state managed by Tauri
+pub struct SessionState {
+    path: PathBuf,
+    sessions: Mutex<HashMap<String, SessionRecord>>,
+}
+
+impl SessionState {
+    /// Load session records from the local store
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, SESSIONS_FILE);
+        let sessions = store::load(&path);
+        Self {
+            path,
+            sessions: Mutex::new(sessions),
+        }
+    }
+
+    /// Find the open session associated with a conversation
+    pub async fn find_open_for_conversation(&self, conversation_id: &str) -> Option<SessionRecord> {
+        self.sessions
+            .lock()
+            .await
+            .values()
+            .find(|s| s.is_open() && s.conversation_id.as_deref() == Some(conversation_id))
+            .cloned()
+    }
+
+    /// Agent chosen for a session
+    pub async fn agent_for(&self, session_key: &str) -> Option<String> {
+        self.sessions
+            .lock()
+            .await
+            .get(session_key)
+            .and_then(|s| s.agent_id.clone())
+    }
+
+    /// Resolve a send's model and thinking level against the session defaults
+    ///
+    /// Omitted values are filled in from the session; explicit ones become
+    /// the session's new defaults.
+    pub async fn resolve_defaults(
+        &self,
+        session_key: &str,
+        requested: SessionDefaults,
+    ) -> Result<ResolvedDefaults, String> {
+        let mut sessions = self.sessions.lock().await;
+        let record = sessions
+            .entry(session_key.to_string())
+            .or_insert_with(|| SessionRecord::new(session_key.to_string(), None));
+        let before = (record.pending_model.clone(), record.thinking.clone());
+        let resolved = resolve_defaults(record, requested);
+        if before != (record.pending_model.clone(), record.thinking.clone()) {
+            store::save(&self.path, &*sessions)?;
+        }
+        Ok(resolved)
+    }
+
+    /// Sessions whose chosen model the Gateway hasn't accepted yet
+    pub async fn pending_models(&self) -> Vec<(String, String)> {
+        self.sessions
+            .lock()
+            .await
+            .values()
+            .filter_map(|r| Some((r.session_key.clone(), r.pending_model.clone()?)))
+            .collect()
+    }
+
+    /// Record that the Gateway accepted a session's model
+    pub async fn confirm_model(&self, session_key: &str, model: &str) -> Result<(), String> {
+        let mut sessions = self.sessions.lock().await;
+        let Some(record) = sessions.get_mut(session_key) else {
+            return Ok(());
+        };
+        confirm_model(record, model);
+        store::save(&self.path, &*sessions)
+    }
+
+    async fn insert(&self, record: SessionRecord) -> Result<(), String> {
+        let mut sessions = self.sessions.lock().await;
+        sessions.insert(record.session_key.clone(), record);
+        store::save(&self.path, &*sessions)
+    }
+
+    async fn mark_closed(&self, session_key: &str) -> Result<Option<SessionRecord>, String> {
+        let mut sessions = self.sessions.lock().await;
+        let record = match sessions.get_mut(session_key) {
+            Some(record) => {
+                if record.closed_at.is_none() {
+                    record.closed_at = Some(chrono::Utc::now());
+                }
+                record.clone()
+            }
+            None => return Ok(None),
+        };
+        store::save(&self.path, &*sessions)?;
+        Ok(Some(record))
+    }
+}
+
+/// Generate a new unique session key
+pub fn generate_session_key() -> String {
+    format!("{}:{}", SESSION_KEY_PREFIX, uuid::Uuid::new_v4())
+}
+
+/// Create a new session, optionally tied to a conversation and set up from
+/// a preset
+///
+/// If the conversation already has an open session it is returned as-is.
+#[tauri::command]
+pub async fn create_session(
+    sessions: State<'_, SessionState>,
+    gateway: State<'_, GatewayState>,
+    presets: State<'_, PresetState>,
+    conversation_id: Option<String>,
+    preset_id: Option<String>,
+) -> Result<SessionRecord, CommandError> {
+    let preset = match &preset_id {
+        Some(id) => Some(
+            presets
+                .get(id)
+                .await
+                .ok_or_else(|| CommandError::validation(format!("Preset not found: {}", id)))?,
+        ),
+        None => None,
+    };
+
+    if let Some(conversation_id) = &conversation_id {
+        if let Some(existing) = sessions.find_open_for_conversation(conversation_id).await {
+            return Ok(existing);
+        }
+    }
+
+    let record = SessionRecord::new(generate_session_key(), conversation_id);
+    sessions.insert(record.clone()).await?;
+
+    // Informing the gateway is best-effort: the session is usable either way
+    // and the gateway creates it lazily on the first chat.send.
+    let mut params = match &preset {
+        Some(preset) => preset.session_patch(&record.session_key),
+        None => serde_json::json!({ "key": record.session_key }),
+    };
+    if let Some(conversation_id) = &record.conversation_id {
+        params["label"] = serde_json::json!(conversation_id);
+    }
+    if let Err(e) = gateway.request("sessions.patch", Some(params)).await {
+        log::error!("[Sessions] Gateway not informed of new session: {}", e);
+    }
+
+    Ok(record)
+}
+
+/// Close a session and tell the Gateway to release it
+#[tauri::command]
+pub async fn close_session(
+    sessions: State<'_, SessionState>,
+    gateway: State<'_, GatewayState>,
+    session_key: String,
+) -> Result<SessionRecord, CommandError> {
+    let record = sessions
+        .mark_closed(&session_key)
+        .await?
+        .ok_or_else(|| CommandError::validation(format!("Unknown session: {}", session_key)))?;
+
+    if let Err(e) = gateway
+        .request(
+            "sessions.delete",
+            Some(serde_json::json!({ "key": session_key })),
+        )
+        .await
+    {
+        log::error!("[Sessions] Gateway not informed of closed session: {}", e);
+    }
+
+    Ok(record)
+}
+
+/// Choose the agent for a session (None = the Gateway's default agent)
+///
+/// Sessions the client has not created yet (e.g. conversations whose ID is
+/// used as the session key) get a record on first use.
+#[tauri::command]
+pub async fn set_session_agent(
+    sessions: State<'_, SessionState>,
+    gateway: State<'_, GatewayState>,
+    session_key: String,
+    agent_id: Option<String>,
+) -> Result<SessionRecord, CommandError> {
+    let record = {
+        let mut records = sessions.sessions.lock().await;
+        let record = records
+            .entry(session_key.clone())
+            .or_insert_with(|| SessionRecord::new(session_key.clone(), None));
+        record.agent_id = agent_id.clone();
+        let record = record.clone();
+        store::save(&sessions.path, &*records)?;
+        record
+    };
+
+    // Best-effort, like session creation: chat.send carries the agent anyway
+    let params = serde_json::json!({ "key": session_key, "agentId": agent_id });
+    if let Err(e) = gateway.request("sessions.patch", Some(params)).await {
+        log::error!("[Sessions] Gateway not informed of agent change: {}", e);
+    }
+
+    Ok(record)
+}
+
+/// Get a session's default model and thinking level
+///
+/// The model is the one the Gateway accepted, not a pending switch.
+#[tauri::command]
+pub async fn get_session_defaults(
+    sessions: State<'_, SessionState>,
+    session_key: String,
+) -> Result<SessionDefaults, CommandError> {
+    Ok(sessions
+        .sessions
+        .lock()
+        .await
+        .get(&session_key)
+        .map(|r| SessionDefaults {
+            model: r.model.clone(),
+            thinking: r.thinking.clone(),
+        })
+        .unwrap_or_default())
+}
+
+/// Set a session's default model and/or thinking level
+#[tauri::command]
+pub async fn set_session_defaults(
+    sessions: State<'_, SessionState>,
+    session_key: String,
+    defaults: SessionDefaults,
+) -> Result<SessionDefaults, CommandError> {
+    sessions
+        .resolve_defaults(&session_key, defaults)
+        .await
+        .map(|resolved| resolved.defaults)
+        .map_err(CommandError::internal)
+}
+
+/// List all known sessions (open and closed)
+#[tauri::command]
+pub async fn list_sessions(
+    sessions: State<'_, SessionState>,
+) -> Result<Vec<SessionRecord>, CommandError> {
+    let mut records: Vec<SessionRecord> =
+        sessions.sessions.lock().await.values().cloned().collect();
+    records.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_session_key_unique() {
+        let a = generate_session_key();
+        let b = generate_session_key();
+        assert!(a.starts_with("moltz:"));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_session_record_serialization() {
+        let record = SessionRecord {
+            session_key: "moltz:abc".to_string(),
+            conversation_id: Some("conv-1".to_string()),
+            created_at: chrono::Utc::now(),
+            closed_at: None,
+            agent_id: Some("research".to_string()),
+            model: None,
+            pending_model: None,
+            thinking: None,
+        };
+        assert!(record.is_open());
+
+        let json = serde_json::to_string(&record).unwrap();
+        assert!(json.contains("sessionKey"));
+        assert!(json.contains("conversationId"));
+        assert!(json.contains("agentId"));
+
+        // Records stored before agent selection existed still load
+        let legacy = r#"{"sessionKey":"moltz:old","conversationId":null,"createdAt":"2026-01-01T00:00:00Z","closedAt":null}"#;
+        let parsed: SessionRecord = serde_json::from_str(legacy).unwrap();
+        assert!(parsed.agent_id.is_none());
+    }
+
+    #[test]
+    fn test_resolve_defaults_fills_omitted_and_remembers_explicit() {
+        let mut record = SessionRecord::new("moltz:abc".to_string(), None);
+        let requested = |model: Option<&str>, thinking: Option<&str>| SessionDefaults {
+            model: model.map(str::to_string),
+            thinking: thinking.map(str::to_string),
+        };
+
+        // First explicit choice becomes the default, pending until accepted
+        let resolved = resolve_defaults(&mut record, requested(Some("sonnet"), Some("low")));
+        assert!(resolved.model_changed);
+        assert_eq!(resolved.defaults, requested(Some("sonnet"), Some("low")));
+        assert!(record.model.is_none());
+
+        // Still pending on a later send that omits it
+        let resolved = resolve_defaults(&mut record, requested(None, None));
+        assert!(resolved.model_changed);
+        assert_eq!(resolved.defaults, requested(Some("sonnet"), Some("low")));
+
+        // Once accepted, omitted values are filled in without a switch
+        confirm_model(&mut record, "sonnet");
+        assert_eq!(record.model.as_deref(), Some("sonnet"));
+        let resolved = resolve_defaults(&mut record, requested(None, None));
+        assert!(!resolved.model_changed);
+        assert_eq!(resolved.defaults, requested(Some("sonnet"), Some("low")));
+
+        // Same model again is not a change; thinking can be turned off
+        let resolved = resolve_defaults(&mut record, requested(Some("sonnet"), Some("off")));
+        assert!(!resolved.model_changed);
+        assert_eq!(resolved.defaults.thinking.as_deref(), Some("off"));
+
+        // Going back to the accepted model drops a switch still pending
+        resolve_defaults(&mut record, requested(Some("opus"), None));
+        let resolved = resolve_defaults(&mut record, requested(Some("sonnet"), None));
+        assert!(!resolved.model_changed);
+        assert!(record.pending_model.is_none());
+    }
+}
@@ -0,0 +1,157 @@
+//! Export and import of the Rust-managed settings
+//!
+//! A settings file lets a team share a known-good configuration. It holds:
+//! - Data saver on/off
+//! - Response cache on/off and TTL
+//! - The discovery hosts file (`gateways.toml`), verbatim
+//! - DNS resolver settings
+//!
+//! Secrets are never written: the Gateway token stays in the keychain and
+//! none of the exported settings carry credentials. Every section is
+//! optional, so a file only sets what it contains and files from newer
+//! builds with more sections still import. A file is validated as a whole
+//! before anything is applied.
+
+use crate::data_saver::DataSaverState;
+use crate::discovery;
+use crate::dns::{DnsSettings, DnsState};
+use crate::error::CommandError;
+use crate::guest;
+use crate::response_cache::{ResponseCacheSettings, ResponseCacheState};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+/// Version of the settings file format
+const SETTINGS_FILE_VERSION: u32 = 1;
+
+/// Shared settings file
+#[derive(Debug, Serialize, Deserialize)]
+struct SettingsFile {
+    version: u32,
+    #[serde(rename = "exportedAt", default)]
+    exported_at: Option<DateTime<Utc>>,
+    #[serde(rename = "dataSaver", default)]
+    data_saver: Option<bool>,
+    #[serde(rename = "responseCache", default)]
+    response_cache: Option<ResponseCacheSettings>,
+    /// Content of `gateways.toml`
+    #[serde(rename = "discoveryHosts", default)]
+    discovery_hosts: Option<String>,
+    #[serde(default)]
+    dns: Option<DnsSettings>,
+}
+
+/// Parse and validate a settings file
+fn parse_settings_file(content: &str) -> Result<SettingsFile, String> {
+    let file: SettingsFile =
+        serde_json::from_str(content).map_err(|e| format!("Invalid settings file: {}", e))?;
+    if file.version > SETTINGS_FILE_VERSION {
+        return Err(format!(
+            "Settings file version {} is newer than supported ({})",
+            file.version, SETTINGS_FILE_VERSION
+        ));
+    }
+    if let Some(settings) = &file.response_cache {
+        settings.validate()?;
+    }
+    if let Some(hosts) = &file.discovery_hosts {
+        discovery::validate_hosts_file(hosts)?;
+    }
+    if let Some(settings) = &file.dns {
+        settings.validate()?;
+    }
+    Ok(file)
+}
+
+/// Export the settings to a file
+#[tauri::command]
+pub async fn export_settings(
+    data_saver: State<'_, DataSaverState>,
+    response_cache: State<'_, ResponseCacheState>,
+    dns: State<'_, DnsState>,
+    path: String,
+) -> Result<(), CommandError> {
+    guest::ensure_inactive("Exporting settings")?;
+    let file = SettingsFile {
+        version: SETTINGS_FILE_VERSION,
+        exported_at: Some(Utc::now()),
+        data_saver: Some(data_saver.is_enabled()),
+        response_cache: Some(response_cache.settings().await),
+        discovery_hosts: discovery::read_hosts_file(),
+        dns: Some(dns.settings()),
+    };
+    let json = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    log::info!("[Settings] Exported settings to {}", path);
+    Ok(())
+}
+
+/// Import settings from a file, replacing the sections it contains
+///
+/// Returns the names of the sections applied.
+#[tauri::command]
+pub async fn import_settings(
+    app: AppHandle,
+    data_saver: State<'_, DataSaverState>,
+    response_cache: State<'_, ResponseCacheState>,
+    dns: State<'_, DnsState>,
+    path: String,
+) -> Result<Vec<String>, CommandError> {
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let file = parse_settings_file(&content).map_err(CommandError::validation)?;
+    // Checked up front so a refused section doesn't leave the rest half-applied
+    if file.discovery_hosts.is_some() {
+        guest::ensure_inactive("Importing discovery hosts")?;
+    }
+
+    let mut applied = Vec::new();
+    if let Some(enabled) = file.data_saver {
+        data_saver.set_enabled(&app, enabled)?;
+        applied.push("dataSaver".to_string());
+    }
+    if let Some(settings) = file.response_cache {
+        response_cache.apply(settings).await?;
+        applied.push("responseCache".to_string());
+    }
+    if let Some(hosts) = file.discovery_hosts {
+        discovery::write_hosts_file(&hosts)?;
+        applied.push("discoveryHosts".to_string());
+    }
+    if let Some(settings) = file.dns {
+        dns.apply(settings)?;
+        applied.push("dns".to_string());
+    }
+    log::info!("[Settings] Imported {:?} from {}", applied, path);
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_settings_file() {
+        let file = parse_settings_file(
+            r#"{"version": 1, "dataSaver": true, "discoveryHosts": "[[gateway]]\nhost = \"nas.local\"\n"}"#,
+        )
+        .unwrap();
+        assert_eq!(file.data_saver, Some(true));
+        assert!(file.response_cache.is_none());
+
+        // Unknown sections from newer builds are ignored
+        assert!(parse_settings_file(r#"{"version": 1, "shortcuts": {}}"#).is_ok());
+
+        assert!(parse_settings_file(r#"{"version": 99}"#).is_err());
+        assert!(parse_settings_file(
+            r#"{"version": 1, "responseCache": {"enabled": true, "ttlHours": 0}}"#
+        )
+        .is_err());
+        assert!(parse_settings_file(r#"{"version": 1, "discoveryHosts": "[[gateway"}"#).is_err());
+        assert!(parse_settings_file(
+            r#"{"version": 1, "dns": {"default": {"mode": "custom", "server": "dns.example"}}}"#
+        )
+        .is_err());
+    }
+}
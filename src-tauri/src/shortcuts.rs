@@ -0,0 +1,397 @@
+//! Global shortcuts registered by the backend
+//!
+//! - Ask about selection (Cmd/Ctrl+Alt+A): captures the selection of the
+//!   frontmost app and opens Quick Ask pre-filled with it
+//!
+//! The Quick Ask toggle (Cmd/Ctrl+Shift+Space) is registered by the frontend,
+//! which reports the outcome with `report_shortcut_status`.
+//!
+//! Wayland compositors don't deliver global key grabs to apps, so under a
+//! Wayland session both shortcuts are bound through the XDG desktop portal
+//! (`org.freedesktop.portal.GlobalShortcuts`) instead, where the desktop may
+//! ask the user to confirm or pick the keys. Without the portal the native
+//! registration is kept but flagged, as it only fires while an X11 app has
+//! focus. The status of each shortcut is emitted as `shortcuts:status` so the
+//! UI can warn about shortcuts that won't work.
+//!
+//! A native registration that fails is retried a few times, since a crashed
+//! instance may still hold the keys; if it keeps failing, the status reports
+//! the conflict. `reinitialize` registers everything again on demand, asking
+//! the frontend to redo Quick Ask with `shortcuts:reregister`.
+
+use crate::error::CommandError;
+use crate::selection;
+use crate::system_integration::RETRY_DELAYS;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+/// Shortcut for "ask about selection"
+pub const ASK_SELECTION_SHORTCUT: &str = "CommandOrControl+Alt+A";
+
+/// Shortcut for the Quick Ask toggle (registered by the frontend)
+pub const QUICK_ASK_SHORTCUT: &str = "CommandOrControl+Shift+Space";
+
+/// Shortcut IDs
+pub mod ids {
+    pub const ASK_SELECTION: &str = "ask_selection";
+    pub const QUICK_ASK: &str = "quick_ask";
+}
+
+/// Shortcuts bound through the portal: ID, description, accelerator
+#[cfg(target_os = "linux")]
+const PORTAL_SHORTCUTS: &[(&str, &str, &str)] = &[
+    (
+        ids::ASK_SELECTION,
+        "Ask about the selected text",
+        ASK_SELECTION_SHORTCUT,
+    ),
+    (ids::QUICK_ASK, "Open Quick Ask", QUICK_ASK_SHORTCUT),
+];
+
+/// How a shortcut is registered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutBackend {
+    /// The OS key grab of the global-shortcut plugin
+    Native,
+    /// The XDG desktop portal (Wayland)
+    Portal,
+}
+
+/// Registration status of a shortcut
+#[derive(Debug, Clone, Serialize)]
+pub struct ShortcutStatus {
+    pub id: String,
+    pub accelerator: String,
+    pub backend: ShortcutBackend,
+    pub registered: bool,
+    /// Keys the user picked in the portal dialog
+    pub trigger: Option<String>,
+    /// Why the shortcut failed or may not fire
+    pub warning: Option<String>,
+    /// Another app holds the keys
+    pub conflict: bool,
+}
+
+/// Shortcut statuses managed by Tauri
+#[derive(Default)]
+pub struct ShortcutStatusState {
+    statuses: Mutex<Vec<ShortcutStatus>>,
+}
+
+/// Current statuses
+fn statuses(app: &AppHandle) -> Vec<ShortcutStatus> {
+    let state = app.state::<ShortcutStatusState>();
+    let statuses = state.statuses.lock();
+    statuses
+        .map(|statuses| statuses.clone())
+        .unwrap_or_default()
+}
+
+/// Whether the portal has bound a shortcut
+fn portal_bound(statuses: &[ShortcutStatus], id: &str) -> bool {
+    statuses
+        .iter()
+        .any(|s| s.id == id && s.backend == ShortcutBackend::Portal && s.registered)
+}
+
+/// Record a shortcut's status and emit `shortcuts:status`
+fn set_status(app: &AppHandle, status: ShortcutStatus) {
+    let state = app.state::<ShortcutStatusState>();
+    let statuses = {
+        let Ok(mut statuses) = state.statuses.lock() else {
+            return;
+        };
+        statuses.retain(|s| s.id != status.id);
+        statuses.push(status);
+        statuses.clone()
+    };
+    let _ = app.emit("shortcuts:status", statuses);
+}
+
+/// Whether the app runs in a Wayland session
+#[cfg(target_os = "linux")]
+fn is_wayland() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some()
+        || std::env::var("XDG_SESSION_TYPE").is_ok_and(|t| t == "wayland")
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_wayland() -> bool {
+    false
+}
+
+/// Warning for native shortcuts under Wayland without the portal
+const WAYLAND_NATIVE_WARNING: &str =
+    "This desktop has no global shortcut portal; the shortcut only works while an X11 app has focus";
+
+/// Register the backend's global shortcuts
+pub fn setup_shortcuts(app: &AppHandle) -> tauri::Result<()> {
+    #[cfg(target_os = "linux")]
+    if is_wayland() {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = portal::bind(&app).await {
+                log::error!("[Shortcuts] Desktop portal unavailable: {}", e);
+                if !register_native(&app) {
+                    retry_native(&app).await;
+                }
+            }
+        });
+        return Ok(());
+    }
+    if !register_native(app) {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            retry_native(&app).await;
+        });
+    }
+    Ok(())
+}
+
+/// Register the backend's shortcuts again and have the frontend redo
+/// Quick Ask
+///
+/// Shortcuts bound through the portal are left alone: the desktop keeps
+/// them for the session.
+pub async fn reinitialize(app: &AppHandle) -> Vec<ShortcutStatus> {
+    let current = statuses(app);
+    let portal_session = current.iter().any(|s| s.backend == ShortcutBackend::Portal);
+    if !portal_session && !register_native(app) {
+        retry_native(app).await;
+    }
+    if !portal_bound(&current, ids::QUICK_ASK) {
+        let _ = app.emit("shortcuts:reregister", ());
+    }
+    statuses(app)
+}
+
+/// Register "ask about selection" with the global-shortcut plugin,
+/// replacing a registration left from before re-initializing
+fn register_native(app: &AppHandle) -> bool {
+    let shortcuts = app.global_shortcut();
+    if shortcuts.is_registered(ASK_SELECTION_SHORTCUT) {
+        let _ = shortcuts.unregister(ASK_SELECTION_SHORTCUT);
+    }
+    let result = shortcuts.on_shortcut(ASK_SELECTION_SHORTCUT, |app, _shortcut, event| {
+        // Act on release so the hotkey's keys are up before the copy
+        if event.state() == ShortcutState::Released {
+            ask_about_selection(app.clone());
+        }
+    });
+    let warning = match &result {
+        Ok(()) => is_wayland().then(|| WAYLAND_NATIVE_WARNING.to_string()),
+        Err(e) => {
+            // Another app (or a crashed instance) may own the shortcut; not fatal
+            log::error!(
+                "[Shortcuts] Failed to register {}: {}",
+                ASK_SELECTION_SHORTCUT,
+                e
+            );
+            Some(format!("Another app may be using these keys ({})", e))
+        }
+    };
+    set_status(
+        app,
+        ShortcutStatus {
+            id: ids::ASK_SELECTION.to_string(),
+            accelerator: ASK_SELECTION_SHORTCUT.to_string(),
+            backend: ShortcutBackend::Native,
+            registered: result.is_ok(),
+            trigger: None,
+            warning,
+            conflict: result.is_err(),
+        },
+    );
+    result.is_ok()
+}
+
+/// Retry a failed native registration
+async fn retry_native(app: &AppHandle) -> bool {
+    for delay in RETRY_DELAYS {
+        tokio::time::sleep(delay).await;
+        if register_native(app) {
+            log::info!(
+                "[Shortcuts] Registered {} after retrying",
+                ASK_SELECTION_SHORTCUT
+            );
+            return true;
+        }
+    }
+    false
+}
+
+/// Portal trigger for an accelerator, e.g. `CTRL+SHIFT+space`
+/// (XDG shortcuts specification)
+#[cfg(any(target_os = "linux", test))]
+fn portal_trigger(accelerator: &str) -> String {
+    accelerator
+        .split('+')
+        .map(|part| match part {
+            "CommandOrControl" | "CmdOrCtrl" | "Control" | "Ctrl" => "CTRL".to_string(),
+            "Alt" | "Option" => "ALT".to_string(),
+            "Shift" => "SHIFT".to_string(),
+            "Super" | "Meta" | "Command" | "Cmd" => "LOGO".to_string(),
+            key => key.to_lowercase(),
+        })
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// Binding through the XDG desktop portal
+#[cfg(target_os = "linux")]
+mod portal {
+    use super::*;
+    use ashpd::desktop::global_shortcuts::{GlobalShortcuts, NewShortcut};
+    use futures_util::StreamExt;
+
+    /// Bind the shortcuts and run their actions until the session ends
+    pub async fn bind(app: &AppHandle) -> ashpd::Result<()> {
+        let proxy = GlobalShortcuts::new().await?;
+        let session = proxy.create_session().await?;
+        let requested: Vec<NewShortcut> = PORTAL_SHORTCUTS
+            .iter()
+            .map(|(id, description, accelerator)| {
+                NewShortcut::new(*id, *description)
+                    .preferred_trigger(Some(portal_trigger(accelerator).as_str()))
+            })
+            .collect();
+        let bound = proxy
+            .bind_shortcuts(&session, &requested, None)
+            .await?
+            .response()?;
+
+        for (id, _, accelerator) in PORTAL_SHORTCUTS {
+            let shortcut = bound.shortcuts().iter().find(|s| s.id() == *id);
+            set_status(
+                app,
+                ShortcutStatus {
+                    id: id.to_string(),
+                    accelerator: accelerator.to_string(),
+                    backend: ShortcutBackend::Portal,
+                    registered: shortcut.is_some(),
+                    trigger: shortcut.map(|s| s.trigger_description().to_string()),
+                    warning: shortcut
+                        .is_none()
+                        .then(|| "Not assigned in the desktop's shortcut settings".to_string()),
+                    conflict: false,
+                },
+            );
+        }
+
+        // The bindings last as long as `session`
+        let mut activated = std::pin::pin!(proxy.receive_activated().await?);
+        while let Some(event) = activated.next().await {
+            match event.shortcut_id() {
+                ids::ASK_SELECTION => ask_about_selection(app.clone()),
+                ids::QUICK_ASK => toggle_quick_ask(app),
+                _ => {}
+            }
+        }
+        drop(session);
+        Ok(())
+    }
+}
+
+/// Show or hide the Quick Ask window (portal binding of the frontend's
+/// shortcut)
+#[cfg(target_os = "linux")]
+fn toggle_quick_ask(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("quickinput") {
+        if window.is_visible().unwrap_or(false) {
+            let _ = window.hide();
+        } else {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    }
+}
+
+/// Capture the selection and open Quick Ask pre-filled with it
+fn ask_about_selection(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Capture before our window takes focus
+        let captured = tokio::task::spawn_blocking(selection::capture).await;
+
+        let Some(window) = app.get_webview_window("quickinput") else {
+            return;
+        };
+        let _ = window.show();
+        let _ = window.set_focus();
+
+        match captured {
+            Ok(Ok(selection)) => {
+                let _ = app.emit_to("quickinput", "quickinput:prefill", selection);
+            }
+            Ok(Err(e)) => {
+                log::error!("[Shortcuts] No selection captured: {}", e);
+            }
+            Err(e) => {
+                log::error!("[Shortcuts] Selection capture failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Registration status of every global shortcut
+#[tauri::command]
+pub async fn get_shortcut_status(
+    state: State<'_, ShortcutStatusState>,
+) -> Result<Vec<ShortcutStatus>, CommandError> {
+    Ok(state.statuses.lock().map_err(|e| e.to_string())?.clone())
+}
+
+/// Record the outcome of a shortcut the frontend registers
+///
+/// Ignored when the portal already handles the shortcut.
+#[tauri::command]
+pub async fn report_shortcut_status(
+    app: AppHandle,
+    state: State<'_, ShortcutStatusState>,
+    id: String,
+    registered: bool,
+    error: Option<String>,
+) -> Result<(), CommandError> {
+    if id != ids::QUICK_ASK {
+        return Err(CommandError::validation(format!(
+            "Unknown shortcut: {}",
+            id
+        )));
+    }
+    let statuses = state.statuses.lock().map_err(|e| e.to_string())?.clone();
+    if portal_bound(&statuses, &id) {
+        return Ok(());
+    }
+    let conflict = error.is_some();
+    let warning = match error {
+        Some(e) => Some(e),
+        None => is_wayland().then(|| WAYLAND_NATIVE_WARNING.to_string()),
+    };
+    set_status(
+        &app,
+        ShortcutStatus {
+            id,
+            accelerator: QUICK_ASK_SHORTCUT.to_string(),
+            backend: ShortcutBackend::Native,
+            registered,
+            trigger: None,
+            warning,
+            conflict,
+        },
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_portal_trigger() {
+        assert_eq!(portal_trigger(ASK_SELECTION_SHORTCUT), "CTRL+ALT+a");
+        assert_eq!(portal_trigger(QUICK_ASK_SHORTCUT), "CTRL+SHIFT+space");
+        assert_eq!(portal_trigger("Super+Shift+K"), "LOGO+SHIFT+k");
+    }
+}
@@ -0,0 +1,262 @@
+//! Startup health check
+//!
+//! Right after launch the backend checks the environment once (keychain
+//! access, stored credentials, Gateway discovery, whether the last Gateway is
+//! still reachable) and emits a single `startup:health` report with the
+//! action the frontend should take: onboarding, reconnect, or a fix-it
+//! screen. The report is also kept for windows that load after it was
+//! emitted (`get_startup_health`).
+//!
+//! The URL of the last successful connection is persisted for this check.
+
+use crate::discovery;
+use crate::error::CommandError;
+use crate::keychain;
+use crate::store;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+use tokio::sync::Mutex;
+
+/// Store file name for the last connected Gateway
+const LAST_GATEWAY_FILE: &str = "last_gateway.json";
+
+/// The last Gateway connected to successfully
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LastGateway {
+    url: String,
+    #[serde(rename = "connectedAt")]
+    connected_at: DateTime<Utc>,
+}
+
+/// What the frontend should do after launch
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupAction {
+    /// Nothing configured yet: run onboarding
+    Onboarding,
+    /// Connect to the last Gateway with the stored credentials
+    Reconnect,
+    /// Something needs the user's attention first (see `issues`)
+    FixIt,
+}
+
+/// A problem found by the health check
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupIssue {
+    /// The OS keychain can't be read (locked, missing Secret Service, denied)
+    KeychainUnavailable,
+    /// The last Gateway didn't answer
+    GatewayUnreachable,
+}
+
+/// Gateways found by discovery
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscoverySummary {
+    pub found: usize,
+    pub reachable: usize,
+    /// URLs of the reachable Gateways, fastest first
+    #[serde(rename = "reachableUrls")]
+    pub reachable_urls: Vec<String>,
+}
+
+/// The last connected Gateway and whether it answered
+#[derive(Debug, Clone, Serialize)]
+pub struct LastGatewayHealth {
+    pub url: String,
+    #[serde(rename = "connectedAt")]
+    pub connected_at: DateTime<Utc>,
+    pub reachable: bool,
+    #[serde(rename = "responseTimeMs")]
+    pub response_time_ms: Option<u64>,
+}
+
+/// The `startup:health` report
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupHealth {
+    #[serde(rename = "checkedAt")]
+    pub checked_at: DateTime<Utc>,
+    #[serde(rename = "keychainAccessible")]
+    pub keychain_accessible: bool,
+    #[serde(rename = "credentialsStored")]
+    pub credentials_stored: bool,
+    pub discovery: DiscoverySummary,
+    #[serde(rename = "lastGateway")]
+    pub last_gateway: Option<LastGatewayHealth>,
+    pub action: StartupAction,
+    pub issues: Vec<StartupIssue>,
+}
+
+/// Startup health state managed by Tauri
+pub struct StartupState {
+    path: PathBuf,
+    last_gateway: Mutex<Option<LastGateway>>,
+    report: Mutex<Option<StartupHealth>>,
+}
+
+impl StartupState {
+    /// Load the last connected Gateway from the local store
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, LAST_GATEWAY_FILE);
+        let last_gateway = store::load(&path);
+        Self {
+            path,
+            last_gateway: Mutex::new(last_gateway),
+            report: Mutex::new(None),
+        }
+    }
+}
+
+/// Remember a Gateway the app just connected to
+pub async fn remember_gateway(app: &AppHandle, url: &str) {
+    let Some(state) = app.try_state::<StartupState>() else {
+        return;
+    };
+    let gateway = LastGateway {
+        url: url.to_string(),
+        connected_at: Utc::now(),
+    };
+    if let Err(e) = store::save(&state.path, &gateway) {
+        log::error!("[Startup] Failed to save last Gateway: {}", e);
+    }
+    *state.last_gateway.lock().await = Some(gateway);
+}
+
+/// Pick the action and issues for the check results
+fn assess(
+    keychain_accessible: bool,
+    credentials_stored: bool,
+    last_gateway: Option<&LastGatewayHealth>,
+) -> (StartupAction, Vec<StartupIssue>) {
+    if !keychain_accessible {
+        return (
+            StartupAction::FixIt,
+            vec![StartupIssue::KeychainUnavailable],
+        );
+    }
+    match last_gateway {
+        Some(gateway) if gateway.reachable => (StartupAction::Reconnect, Vec::new()),
+        Some(_) => (StartupAction::FixIt, vec![StartupIssue::GatewayUnreachable]),
+        None if credentials_stored => (StartupAction::Reconnect, Vec::new()),
+        None => (StartupAction::Onboarding, Vec::new()),
+    }
+}
+
+/// Run the checks and build the report
+async fn check(state: &StartupState) -> StartupHealth {
+    let credentials = keychain::contains(keychain::SERVICE_NAME, keychain::GATEWAY_TOKEN_KEY).await;
+    if let Err(e) = &credentials {
+        log::error!("[Startup] Keychain unavailable: {}", e);
+    }
+
+    let last = state.last_gateway.lock().await.clone();
+    let probe_last = async {
+        match &last {
+            Some(last) => Some(discovery::test_gateway(last.url.clone(), "Last Gateway").await),
+            None => None,
+        }
+    };
+    let (discovered, probed) = tokio::join!(discovery::discover_gateways(), probe_last);
+    let discovered = discovered.unwrap_or_default();
+
+    let mut reachable: Vec<_> = discovered.iter().filter(|g| g.reachable).collect();
+    reachable.sort_by_key(|g| g.response_time_ms);
+    let discovery = DiscoverySummary {
+        found: discovered.len(),
+        reachable: reachable.len(),
+        reachable_urls: reachable.iter().map(|g| g.url.clone()).collect(),
+    };
+
+    let last_gateway = last.zip(probed).map(|(last, probed)| LastGatewayHealth {
+        url: last.url,
+        connected_at: last.connected_at,
+        reachable: probed.reachable,
+        response_time_ms: probed.response_time_ms,
+    });
+
+    let keychain_accessible = credentials.is_ok();
+    let credentials_stored = credentials.unwrap_or(false);
+    let (action, issues) = assess(
+        keychain_accessible,
+        credentials_stored,
+        last_gateway.as_ref(),
+    );
+
+    StartupHealth {
+        checked_at: Utc::now(),
+        keychain_accessible,
+        credentials_stored,
+        discovery,
+        last_gateway,
+        action,
+        issues,
+    }
+}
+
+/// Run the startup health check and emit `startup:health`
+pub fn start_health_check(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<StartupState>();
+        let report = check(&state).await;
+        log::info!(
+            "[Startup] Health: {:?} (issues: {:?})",
+            report.action,
+            report.issues
+        );
+        *state.report.lock().await = Some(report.clone());
+        let _ = app.emit("startup:health", report);
+    });
+}
+
+/// The startup health report, or None while the check is still running
+/// (wait for `startup:health` then)
+#[tauri::command]
+pub async fn get_startup_health(
+    state: State<'_, StartupState>,
+) -> Result<Option<StartupHealth>, CommandError> {
+    Ok(state.report.lock().await.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_gateway(reachable: bool) -> LastGatewayHealth {
+        LastGatewayHealth {
+            url: "ws://localhost:18789".to_string(),
+            connected_at: Utc::now(),
+            reachable,
+            response_time_ms: reachable.then_some(5),
+        }
+    }
+
+    #[test]
+    fn test_assess_routes_deterministically() {
+        assert_eq!(
+            assess(false, true, Some(&last_gateway(true))),
+            (
+                StartupAction::FixIt,
+                vec![StartupIssue::KeychainUnavailable]
+            )
+        );
+        assert_eq!(
+            assess(true, false, None),
+            (StartupAction::Onboarding, Vec::new())
+        );
+        assert_eq!(
+            assess(true, true, None),
+            (StartupAction::Reconnect, Vec::new())
+        );
+        assert_eq!(
+            assess(true, false, Some(&last_gateway(true))),
+            (StartupAction::Reconnect, Vec::new())
+        );
+        assert_eq!(
+            assess(true, true, Some(&last_gateway(false))),
+            (StartupAction::FixIt, vec![StartupIssue::GatewayUnreachable])
+        );
+    }
+}
@@ -0,0 +1,458 @@
+//! Log and cache size management
+//!
+//! Nothing else cleans up the app's logs, the webview's caches (images,
+//! fetched resources) or the temp files left by dragging answers out, so
+//! they grow without bound. A maintenance task keeps each category under a
+//! configurable cap by deleting its oldest files first. It runs shortly after
+//! startup, then every few hours, and whenever the caps change.
+//!
+//! Settings shows the space in use and what the last run reclaimed through
+//! `get_storage_usage`.
+
+use crate::error::CommandError;
+use crate::{drag_out, portable, power, store, supervisor};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager, Runtime, State};
+
+/// Store file name for the storage caps
+const STORAGE_FILE: &str = "storage.json";
+
+/// Default cap for logs
+const DEFAULT_LOG_LIMIT_MB: u64 = 50;
+
+/// Default cap for caches and temp files
+const DEFAULT_CACHE_LIMIT_MB: u64 = 500;
+
+/// Wait after startup before the first run, to stay out of the way of
+/// connecting and loading conversations
+const STARTUP_DELAY: Duration = Duration::from_secs(60);
+
+/// How often maintenance runs
+const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+fn default_log_limit_mb() -> u64 {
+    DEFAULT_LOG_LIMIT_MB
+}
+
+fn default_cache_limit_mb() -> u64 {
+    DEFAULT_CACHE_LIMIT_MB
+}
+
+/// Size caps, in megabytes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageSettings {
+    #[serde(rename = "logLimitMb", default = "default_log_limit_mb")]
+    pub log_limit_mb: u64,
+    #[serde(rename = "cacheLimitMb", default = "default_cache_limit_mb")]
+    pub cache_limit_mb: u64,
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            log_limit_mb: DEFAULT_LOG_LIMIT_MB,
+            cache_limit_mb: DEFAULT_CACHE_LIMIT_MB,
+        }
+    }
+}
+
+impl StorageSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.log_limit_mb == 0 || self.cache_limit_mb == 0 {
+            return Err("Size limits must be at least 1 MB".to_string());
+        }
+        Ok(())
+    }
+
+    fn limit_bytes(&self, category: StorageCategory) -> u64 {
+        let mb = match category {
+            StorageCategory::Logs => self.log_limit_mb,
+            StorageCategory::Cache => self.cache_limit_mb,
+        };
+        mb.saturating_mul(BYTES_PER_MB)
+    }
+}
+
+/// Kind of disposable data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageCategory {
+    Logs,
+    Cache,
+}
+
+impl StorageCategory {
+    const ALL: [Self; 2] = [Self::Logs, Self::Cache];
+
+    /// Directories holding the category's files
+    fn dirs<R: Runtime>(self, app: &AppHandle<R>) -> Vec<PathBuf> {
+        match self {
+            Self::Logs => log_dir(app).into_iter().collect(),
+            Self::Cache => portable_cache_dirs()
+                .or_else(|| app.path().app_cache_dir().ok().map(webview_cache_dirs))
+                .unwrap_or_default()
+                .into_iter()
+                .chain([drag_out::temp_root()])
+                .collect(),
+        }
+    }
+}
+
+/// The app's logs directory (the portable one when running portable)
+pub fn log_dir<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    portable::log_dir().or_else(|| app.path().app_log_dir().ok())
+}
+
+/// Cache directories when running portable: the portable cache folder, plus
+/// the webview's caches on Windows where its profile has a folder of its own
+fn portable_cache_dirs() -> Option<Vec<PathBuf>> {
+    let cache_dir = portable::cache_dir()?;
+    #[cfg(target_os = "windows")]
+    let webview = portable::webview_dir()
+        .map(webview_cache_dirs)
+        .unwrap_or_default();
+    #[cfg(not(target_os = "windows"))]
+    let webview = Vec::new();
+    Some([cache_dir].into_iter().chain(webview).collect())
+}
+
+/// Cache directories of the webview under the app cache directory
+///
+/// WebView2 keeps its whole profile there, including the storage holding
+/// the conversation database, so only its cache folders are pruned.
+#[cfg(target_os = "windows")]
+fn webview_cache_dirs(cache_dir: PathBuf) -> Vec<PathBuf> {
+    let profile = cache_dir.join("EBWebView").join("Default");
+    ["Cache", "Code Cache", "GPUCache"]
+        .iter()
+        .map(|name| profile.join(name))
+        .collect()
+}
+
+/// Cache directories of the webview under the app cache directory
+#[cfg(not(target_os = "windows"))]
+fn webview_cache_dirs(cache_dir: PathBuf) -> Vec<PathBuf> {
+    vec![cache_dir]
+}
+
+/// Space used by a category
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryUsage {
+    pub category: StorageCategory,
+    pub bytes: u64,
+    pub files: usize,
+    #[serde(rename = "limitBytes")]
+    pub limit_bytes: u64,
+}
+
+/// What a maintenance run removed
+#[derive(Debug, Clone, Serialize)]
+pub struct MaintenanceReport {
+    #[serde(rename = "ranAt")]
+    pub ran_at: DateTime<Utc>,
+    #[serde(rename = "filesRemoved")]
+    pub files_removed: usize,
+    #[serde(rename = "reclaimedBytes")]
+    pub reclaimed_bytes: u64,
+}
+
+/// Space in use, for the settings pane
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageUsage {
+    pub categories: Vec<CategoryUsage>,
+    #[serde(rename = "lastMaintenance")]
+    pub last_maintenance: Option<MaintenanceReport>,
+}
+
+#[derive(Debug)]
+struct FileEntry {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+}
+
+/// Regular files under `dir`, recursively; symlinks are not followed
+fn collect_files(dir: &Path, files: &mut Vec<FileEntry>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            collect_files(&entry.path(), files);
+        } else if metadata.is_file() {
+            files.push(FileEntry {
+                path: entry.path(),
+                size: metadata.len(),
+                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            });
+        }
+    }
+}
+
+/// Split off the oldest files until the rest fit in `limit` bytes
+fn oldest_over_limit(mut files: Vec<FileEntry>, limit: u64) -> Vec<FileEntry> {
+    files.sort_by_key(|file| file.modified);
+    let mut total: u64 = files.iter().map(|file| file.size).sum();
+    let mut count = 0;
+    for file in &files {
+        if total <= limit {
+            break;
+        }
+        total -= file.size;
+        count += 1;
+    }
+    files.truncate(count);
+    files
+}
+
+/// Delete the oldest files under `dirs` beyond `limit` bytes, returning
+/// the number of files removed and the bytes reclaimed
+fn prune(dirs: &[PathBuf], limit: u64) -> (usize, u64) {
+    let mut files = Vec::new();
+    for dir in dirs {
+        collect_files(dir, &mut files);
+    }
+
+    let mut removed = 0;
+    let mut reclaimed = 0;
+    for file in oldest_over_limit(files, limit) {
+        match std::fs::remove_file(&file.path) {
+            Ok(()) => {
+                removed += 1;
+                reclaimed += file.size;
+                // Drop the directory too if that was its last file
+                if let Some(parent) = file.path.parent() {
+                    if !dirs.iter().any(|dir| dir == parent) {
+                        let _ = std::fs::remove_dir(parent);
+                    }
+                }
+            }
+            Err(e) => log::error!("[Storage] Failed to remove {}: {}", file.path.display(), e),
+        }
+    }
+    (removed, reclaimed)
+}
+
+fn usage(dirs: &[PathBuf]) -> (u64, usize) {
+    let mut files = Vec::new();
+    for dir in dirs {
+        collect_files(dir, &mut files);
+    }
+    (files.iter().map(|file| file.size).sum(), files.len())
+}
+
+/// Storage state managed by Tauri
+pub struct StorageState {
+    path: PathBuf,
+    settings: std::sync::RwLock<StorageSettings>,
+    last_maintenance: std::sync::Mutex<Option<MaintenanceReport>>,
+    /// Keeps the periodic run and a manual one from pruning at the same time
+    running: tokio::sync::Mutex<()>,
+}
+
+impl StorageState {
+    /// Load the caps from the local store
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, STORAGE_FILE);
+        let settings = store::load(&path);
+        Self {
+            path,
+            settings: std::sync::RwLock::new(settings),
+            last_maintenance: std::sync::Mutex::new(None),
+            running: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn settings(&self) -> StorageSettings {
+        self.settings
+            .read()
+            .map(|settings| settings.clone())
+            .unwrap_or_default()
+    }
+
+    /// Validate, save and apply settings
+    pub fn apply(&self, settings: StorageSettings) -> Result<(), String> {
+        settings.validate()?;
+        store::save(&self.path, &settings)?;
+        if let Ok(mut current) = self.settings.write() {
+            *current = settings;
+        }
+        Ok(())
+    }
+
+    fn last_maintenance(&self) -> Option<MaintenanceReport> {
+        self.last_maintenance
+            .lock()
+            .ok()
+            .and_then(|last| last.clone())
+    }
+}
+
+/// Prune every category down to its cap
+pub async fn run_maintenance(app: &AppHandle) -> MaintenanceReport {
+    let state = app.state::<StorageState>();
+    let _guard = state.running.lock().await;
+    let settings = state.settings();
+    let jobs: Vec<(Vec<PathBuf>, u64)> = StorageCategory::ALL
+        .iter()
+        .map(|category| (category.dirs(app), settings.limit_bytes(*category)))
+        .collect();
+
+    let (files_removed, reclaimed_bytes) = tokio::task::spawn_blocking(move || {
+        jobs.iter()
+            .map(|(dirs, limit)| prune(dirs, *limit))
+            .fold((0, 0), |(files, bytes), (f, b)| (files + f, bytes + b))
+    })
+    .await
+    .unwrap_or_default();
+
+    if files_removed > 0 {
+        log::info!(
+            "[Storage] Removed {} old file(s), reclaimed {} bytes",
+            files_removed,
+            reclaimed_bytes
+        );
+    }
+    let report = MaintenanceReport {
+        ran_at: Utc::now(),
+        files_removed,
+        reclaimed_bytes,
+    };
+    if let Ok(mut last) = state.last_maintenance.lock() {
+        *last = Some(report.clone());
+    }
+    report
+}
+
+/// Start the periodic maintenance task
+pub fn start_maintenance(app: &AppHandle) {
+    let handle = app.clone();
+    supervisor::supervise(app, "storage.maintenance", None, move |_| {
+        let app = handle.clone();
+        async move {
+            tokio::time::sleep(STARTUP_DELAY).await;
+            loop {
+                run_maintenance(&app).await;
+                tokio::time::sleep(power::throttled_interval(&app, MAINTENANCE_INTERVAL)).await;
+            }
+        }
+    });
+}
+
+/// Space used by logs and caches, with their caps and the last run
+#[tauri::command]
+pub async fn get_storage_usage(
+    app: AppHandle,
+    state: State<'_, StorageState>,
+) -> Result<StorageUsage, CommandError> {
+    let settings = state.settings();
+    let jobs: Vec<(StorageCategory, Vec<PathBuf>)> = StorageCategory::ALL
+        .iter()
+        .map(|category| (*category, category.dirs(&app)))
+        .collect();
+    let measured = tokio::task::spawn_blocking(move || {
+        jobs.into_iter()
+            .map(|(category, dirs)| (category, usage(&dirs)))
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| CommandError::internal(e.to_string()))?;
+
+    let categories = measured
+        .into_iter()
+        .map(|(category, (bytes, files))| CategoryUsage {
+            category,
+            bytes,
+            files,
+            limit_bytes: settings.limit_bytes(category),
+        })
+        .collect();
+    Ok(StorageUsage {
+        categories,
+        last_maintenance: state.last_maintenance(),
+    })
+}
+
+/// Current size caps
+#[tauri::command]
+pub async fn get_storage_settings(
+    state: State<'_, StorageState>,
+) -> Result<StorageSettings, CommandError> {
+    Ok(state.settings())
+}
+
+/// Replace the size caps and prune down to them
+#[tauri::command]
+pub async fn set_storage_settings(
+    app: AppHandle,
+    state: State<'_, StorageState>,
+    settings: StorageSettings,
+) -> Result<(), CommandError> {
+    settings.validate().map_err(CommandError::validation)?;
+    state.apply(settings)?;
+    tauri::async_runtime::spawn(async move {
+        run_maintenance(&app).await;
+    });
+    Ok(())
+}
+
+/// Prune now instead of waiting for the next run
+#[tauri::command]
+pub async fn run_storage_maintenance(app: AppHandle) -> Result<MaintenanceReport, CommandError> {
+    Ok(run_maintenance(&app).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(path: &Path, size: usize, age: Duration) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![b'x'; size]).unwrap();
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() - age).unwrap();
+    }
+
+    #[test]
+    fn test_prune_removes_oldest_first() {
+        let dir = std::env::temp_dir().join(format!("moltz-storage-{}", uuid::Uuid::new_v4()));
+        let hour = Duration::from_secs(60 * 60);
+        write_file(&dir.join("old/a.log"), 10, hour * 3);
+        write_file(&dir.join("b.log"), 10, hour * 2);
+        write_file(&dir.join("c.log"), 10, hour);
+
+        let (removed, reclaimed) = prune(std::slice::from_ref(&dir), 15);
+        assert_eq!((removed, reclaimed), (2, 20));
+        assert!(dir.join("c.log").exists());
+        // The emptied subdirectory went too
+        assert!(!dir.join("old").exists());
+        assert_eq!(usage(std::slice::from_ref(&dir)), (10, 1));
+
+        // Already under the cap
+        assert_eq!(prune(std::slice::from_ref(&dir), 15), (0, 0));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_settings_defaults_and_validation() {
+        let settings: StorageSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings, StorageSettings::default());
+        assert_eq!(
+            settings.limit_bytes(StorageCategory::Logs),
+            50 * BYTES_PER_MB
+        );
+        let zero = StorageSettings {
+            log_limit_mb: 0,
+            ..settings
+        };
+        assert!(zero.validate().is_err());
+    }
+}
@@ -0,0 +1,185 @@
+//! Local JSON store for Rust-managed app data
+//!
+//! Small records (sessions, per-conversation settings, ...) are kept as
+//! JSON files in the app data directory. Writes go through a temp file +
+//! rename so a crash mid-write never leaves a truncated file behind.
+//!
+//! In guest mode nothing is written (see [`crate::guest`]).
+//!
+//! Records that shouldn't sit on disk in plain text are sealed with
+//! AES-256-GCM under a key the caller keeps (in the keychain).
+
+use crate::{guest, portable};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Resolve the path of a store file in the app data directory (the portable
+/// data folder when running portable)
+pub fn store_path<R: Runtime>(app: &AppHandle<R>, name: &str) -> PathBuf {
+    portable::data_dir()
+        .or_else(|| app.path().app_data_dir().ok())
+        .unwrap_or_else(|| std::env::temp_dir().join("moltz"))
+        .join(name)
+}
+
+/// Load a store file, falling back to the default value if it is missing or corrupt
+pub fn load<T: DeserializeOwned + Default>(path: &Path) -> T {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::error!("[Store] Ignoring corrupt {}: {}", path.display(), e);
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+/// Atomically write a store file
+pub fn save<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    write_atomic(path, json.as_bytes())
+}
+
+/// Write a file through a temp file + rename
+///
+/// Nothing is written in guest mode: callers keep their state in memory.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if guest::active() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    std::fs::write(&tmp_path, bytes).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp_path, path).map_err(|e| e.to_string())
+}
+
+/// Length of the keys sealed data is encrypted with
+pub const SEAL_KEY_LEN: usize = 32;
+
+/// Encrypt data as nonce || ciphertext || tag
+pub fn seal(key: &[u8; SEAL_KEY_LEN], plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let key = LessSafeKey::new(
+        UnboundKey::new(&AES_256_GCM, key).map_err(|_| "Invalid key".to_string())?,
+    );
+    let nonce: [u8; NONCE_LEN] = rand::random();
+    let mut in_out = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::empty(),
+        &mut in_out,
+    )
+    .map_err(|_| "Encryption failed".to_string())?;
+    let mut sealed = nonce.to_vec();
+    sealed.extend(in_out);
+    Ok(sealed)
+}
+
+/// Decrypt data sealed by [`seal`], failing if it was tampered with or
+/// sealed with another key
+pub fn open(key: &[u8; SEAL_KEY_LEN], sealed: &[u8]) -> Result<Vec<u8>, String> {
+    let failed = || "Decryption failed".to_string();
+    if sealed.len() < NONCE_LEN {
+        return Err(failed());
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let key = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, key).map_err(|_| failed())?);
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = key
+        .open_in_place(
+            Nonce::try_assume_unique_for_key(nonce).map_err(|_| failed())?,
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| failed())?;
+    Ok(plaintext.to_vec())
+}
+
+/// Load an encrypted store file, falling back to the default value if it is
+/// missing, corrupt or sealed with another key
+pub fn load_sealed<T: DeserializeOwned + Default>(path: &Path, key: &[u8; SEAL_KEY_LEN]) -> T {
+    let Ok(sealed) = std::fs::read(path) else {
+        return T::default();
+    };
+    open(key, &sealed)
+        .and_then(|plaintext| serde_json::from_slice(&plaintext).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            log::error!("[Store] Ignoring unreadable {}: {}", path.display(), e);
+            T::default()
+        })
+}
+
+/// Atomically write an encrypted store file
+pub fn save_sealed<T: Serialize>(
+    path: &Path,
+    key: &[u8; SEAL_KEY_LEN],
+    value: &T,
+) -> Result<(), String> {
+    let json = serde_json::to_vec(value).map_err(|e| e.to_string())?;
+    write_atomic(path, &seal(key, &json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("moltz-store-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("test.json");
+
+        let mut value = HashMap::new();
+        value.insert("key".to_string(), 42u32);
+        save(&path, &value).unwrap();
+
+        let loaded: HashMap<String, u32> = load(&path);
+        assert_eq!(loaded.get("key"), Some(&42));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_load_missing_returns_default() {
+        let path =
+            std::env::temp_dir().join(format!("moltz-missing-{}.json", uuid::Uuid::new_v4()));
+        let loaded: HashMap<String, u32> = load(&path);
+        assert!(loaded.is_empty());
+    }
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = [7u8; SEAL_KEY_LEN];
+        let sealed = seal(&key, b"token").unwrap();
+        assert_ne!(&sealed[NONCE_LEN..], b"token");
+        assert_eq!(open(&key, &sealed).unwrap(), b"token");
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(open(&key, &tampered).is_err());
+        assert!(open(&[8u8; SEAL_KEY_LEN], &sealed).is_err());
+        assert!(open(&key, &[0u8; 4]).is_err());
+    }
+
+    #[test]
+    fn test_sealed_round_trip() {
+        let dir = std::env::temp_dir().join(format!("moltz-store-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("test.bin");
+        let key = [3u8; SEAL_KEY_LEN];
+
+        let value = vec!["private prompt".to_string()];
+        save_sealed(&path, &key, &value).unwrap();
+        let on_disk = std::fs::read(&path).unwrap();
+        assert!(!String::from_utf8_lossy(&on_disk).contains("private"));
+
+        assert_eq!(load_sealed::<Vec<String>>(&path, &key), value);
+        // Another key reads nothing rather than failing
+        assert!(load_sealed::<Vec<String>>(&path, &[4u8; SEAL_KEY_LEN]).is_empty());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
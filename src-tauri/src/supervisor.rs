@@ -0,0 +1,345 @@
+//! Supervisor for long-running background tasks
+//!
+//! The Gateway monitors (health pings, stream timeouts, pending request
+//! cleanup) and storage maintenance used to be spawned and forgotten: one
+//! that panicked or got stuck was gone for the rest of the session without a
+//! trace. Tasks started through [`supervise`] are tracked by name instead:
+//! - a task that panics is restarted after a backoff, up to
+//!   [`MAX_RESTARTS`] times in a row
+//! - a task reports liveness through its [`Heartbeat`]; one that stops
+//!   beating for longer than its stall limit is aborted and restarted
+//! - supervising a name again replaces the running instance (each Gateway
+//!   connection starts its own monitors)
+//! - a task that returns on its own is done and not restarted
+//!
+//! Every restart is logged and emitted as `internal:task_restarted`, and the
+//! task list is part of the diagnostics bundle.
+
+use crate::redact;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::task::AbortHandle;
+
+/// Restarts in a row before a task is given up on
+const MAX_RESTARTS: u32 = 5;
+
+/// A task that ran this long before failing starts a fresh restart count
+const HEALTHY_RUN: Duration = Duration::from_secs(10 * 60);
+
+/// Longest wait before a restart
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+/// How often the watchdog looks for stalled tasks
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(5);
+
+type TaskFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type TaskFactory = Arc<dyn Fn(Heartbeat) -> TaskFuture + Send + Sync>;
+
+static EPOCH: LazyLock<Instant> = LazyLock::new(Instant::now);
+static TASKS: LazyLock<Mutex<HashMap<&'static str, TaskEntry>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+static WATCHDOG: OnceLock<()> = OnceLock::new();
+
+fn now_ms() -> u64 {
+    EPOCH.elapsed().as_millis() as u64
+}
+
+fn tasks() -> MutexGuard<'static, HashMap<&'static str, TaskEntry>> {
+    TASKS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Liveness signal a supervised task sends from its loop
+#[derive(Clone)]
+pub struct Heartbeat(Arc<HeartbeatInner>);
+
+struct HeartbeatInner {
+    /// Milliseconds since [`EPOCH`]
+    last_beat_ms: AtomicU64,
+    /// Set by the watchdog before it aborts the task
+    stalled: AtomicBool,
+}
+
+impl Heartbeat {
+    fn new() -> Self {
+        Self(Arc::new(HeartbeatInner {
+            last_beat_ms: AtomicU64::new(now_ms()),
+            stalled: AtomicBool::new(false),
+        }))
+    }
+
+    /// Report that the task is still making progress
+    pub fn beat(&self) {
+        self.0.last_beat_ms.store(now_ms(), Ordering::Relaxed);
+    }
+
+    fn since_last_beat(&self) -> Duration {
+        Duration::from_millis(now_ms().saturating_sub(self.0.last_beat_ms.load(Ordering::Relaxed)))
+    }
+
+    fn stalled(&self) -> bool {
+        self.0.stalled.load(Ordering::Relaxed)
+    }
+}
+
+/// Why a task had to be restarted
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskFailure {
+    Panicked,
+    Stalled,
+}
+
+struct TaskEntry {
+    /// Tells this instance apart from one that replaced it
+    generation: u64,
+    abort: AbortHandle,
+    heartbeat: Heartbeat,
+    stall_after: Option<Duration>,
+    restarts: u32,
+    last_failure: Option<TaskFailure>,
+    gave_up: bool,
+}
+
+/// A supervised task, for diagnostics
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskStatus {
+    pub name: String,
+    pub running: bool,
+    pub restarts: u32,
+    #[serde(rename = "lastFailure")]
+    pub last_failure: Option<TaskFailure>,
+    #[serde(rename = "gaveUp")]
+    pub gave_up: bool,
+    #[serde(rename = "secsSinceHeartbeat")]
+    pub secs_since_heartbeat: u64,
+}
+
+/// Payload of `internal:task_restarted`
+#[derive(Debug, Clone, Serialize)]
+struct TaskRestarted {
+    task: &'static str,
+    reason: TaskFailure,
+    /// Panic message, credentials scrubbed
+    message: Option<String>,
+    /// Restarts in a row, this one included
+    restarts: u32,
+    #[serde(rename = "delayMs")]
+    delay_ms: u64,
+}
+
+/// Wait before restarting after `restarts` restarts in a row: 1 s, 2 s, 4 s...
+fn restart_delay(restarts: u32) -> Duration {
+    Duration::from_secs(1u64 << restarts.min(6)).min(MAX_RESTART_DELAY)
+}
+
+/// Restarts in a row counting this failure, or None to give up
+fn next_restart(restarts: u32, ran_for: Duration) -> Option<u32> {
+    let restarts = if ran_for >= HEALTHY_RUN { 0 } else { restarts };
+    (restarts < MAX_RESTARTS).then_some(restarts + 1)
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> Option<String> {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .map(|message| redact::scrub(&message))
+}
+
+/// Run a background task under supervision
+///
+/// `task` is called again for every restart. Tasks with a `stall_after`
+/// limit must call [`Heartbeat::beat`] more often than that.
+pub fn supervise<F, Fut>(
+    app: &AppHandle,
+    name: &'static str,
+    stall_after: Option<Duration>,
+    task: F,
+) where
+    F: Fn(Heartbeat) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let factory: TaskFactory = Arc::new(move |heartbeat| Box::pin(task(heartbeat)));
+    start_watchdog();
+    launch(app.clone(), name, stall_after, factory, 0, None);
+}
+
+fn launch(
+    app: AppHandle,
+    name: &'static str,
+    stall_after: Option<Duration>,
+    factory: TaskFactory,
+    restarts: u32,
+    last_failure: Option<TaskFailure>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let heartbeat = Heartbeat::new();
+        let generation = NEXT_GENERATION.fetch_add(1, Ordering::Relaxed);
+        let started = Instant::now();
+        let handle = tokio::spawn(factory(heartbeat.clone()));
+        let previous = tasks().insert(
+            name,
+            TaskEntry {
+                generation,
+                abort: handle.abort_handle(),
+                heartbeat: heartbeat.clone(),
+                stall_after,
+                restarts,
+                last_failure,
+                gave_up: false,
+            },
+        );
+        if let Some(previous) = previous {
+            previous.abort.abort();
+        }
+
+        let (reason, message) = match handle.await {
+            Err(e) if e.is_panic() => (TaskFailure::Panicked, panic_message(e.into_panic())),
+            Err(_) if heartbeat.stalled() => (TaskFailure::Stalled, None),
+            // Returned on its own, replaced or stopped
+            _ => {
+                let mut registry = tasks();
+                if registry
+                    .get(name)
+                    .is_some_and(|e| e.generation == generation)
+                {
+                    registry.remove(name);
+                }
+                return;
+            }
+        };
+
+        let Some(restarts) = next_restart(restarts, started.elapsed()) else {
+            log::error!(
+                "[Supervisor] {} failed ({:?}) {} times in a row, giving up",
+                name,
+                reason,
+                MAX_RESTARTS + 1
+            );
+            if let Some(entry) = tasks().get_mut(name) {
+                if entry.generation == generation {
+                    entry.last_failure = Some(reason);
+                    entry.gave_up = true;
+                }
+            }
+            return;
+        };
+        let delay = restart_delay(restarts - 1);
+        log::error!(
+            "[Supervisor] {} {:?}{}, restarting in {}s",
+            name,
+            reason,
+            message
+                .as_deref()
+                .map(|message| format!(": {}", message))
+                .unwrap_or_default(),
+            delay.as_secs()
+        );
+        let _ = app.emit(
+            "internal:task_restarted",
+            TaskRestarted {
+                task: name,
+                reason,
+                message,
+                restarts,
+                delay_ms: delay.as_millis() as u64,
+            },
+        );
+
+        tokio::time::sleep(delay).await;
+        // Replaced while waiting: the new instance owns the name
+        let current = tasks()
+            .get(name)
+            .is_some_and(|e| e.generation == generation);
+        if current {
+            launch(app, name, stall_after, factory, restarts, Some(reason));
+        }
+    });
+}
+
+/// Abort tasks that stopped beating, once
+fn start_watchdog() {
+    WATCHDOG.get_or_init(|| {
+        tauri::async_runtime::spawn(async {
+            loop {
+                tokio::time::sleep(WATCHDOG_INTERVAL).await;
+                for (name, entry) in tasks().iter() {
+                    let Some(limit) = entry.stall_after else {
+                        continue;
+                    };
+                    let silent_for = entry.heartbeat.since_last_beat();
+                    if silent_for > limit
+                        && !entry.abort.is_finished()
+                        && !entry.heartbeat.stalled()
+                    {
+                        log::error!(
+                            "[Supervisor] {} has not made progress for {}s",
+                            name,
+                            silent_for.as_secs()
+                        );
+                        entry.heartbeat.0.stalled.store(true, Ordering::Relaxed);
+                        entry.abort.abort();
+                    }
+                }
+            }
+        });
+    });
+}
+
+/// Supervised tasks, by name
+pub fn task_statuses() -> Vec<TaskStatus> {
+    let mut statuses: Vec<TaskStatus> = tasks()
+        .iter()
+        .map(|(name, entry)| TaskStatus {
+            name: name.to_string(),
+            running: !entry.abort.is_finished(),
+            restarts: entry.restarts,
+            last_failure: entry.last_failure,
+            gave_up: entry.gave_up,
+            secs_since_heartbeat: entry.heartbeat.since_last_beat().as_secs(),
+        })
+        .collect();
+    statuses.sort_by(|a, b| a.name.cmp(&b.name));
+    statuses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_delay() {
+        assert_eq!(restart_delay(0), Duration::from_secs(1));
+        assert_eq!(restart_delay(3), Duration::from_secs(8));
+        assert_eq!(restart_delay(10), MAX_RESTART_DELAY);
+    }
+
+    #[test]
+    fn test_next_restart() {
+        let quick = Duration::from_secs(5);
+        assert_eq!(next_restart(0, quick), Some(1));
+        assert_eq!(next_restart(MAX_RESTARTS - 1, quick), Some(MAX_RESTARTS));
+        assert_eq!(next_restart(MAX_RESTARTS, quick), None);
+        // A long healthy run forgives earlier failures
+        assert_eq!(next_restart(MAX_RESTARTS, HEALTHY_RUN), Some(1));
+    }
+
+    #[test]
+    fn test_panic_message() {
+        assert_eq!(panic_message(Box::new("boom")), Some("boom".to_string()));
+        assert_eq!(
+            panic_message(Box::new(String::from("index out of bounds"))),
+            Some("index out of bounds".to_string())
+        );
+        assert_eq!(panic_message(Box::new(42)), None);
+    }
+}
@@ -0,0 +1,404 @@
+//! Gateway system events: scheduled job results and channel messages
+//!
+//! The Gateway reports cron job runs (`cron.*`) and messages arriving on its
+//! channels (`channel.*`) outside of any chat run. They are parsed into typed
+//! events, forwarded to the frontend as `system:cron` and
+//! `system:channel_message`, and posted as desktop notifications according to
+//! the user's rules, so a scheduled agent task that finishes while no
+//! conversation is open still gets noticed. Clicking a notification opens
+//! the session the event belongs to.
+
+use crate::error::CommandError;
+use crate::events::GatewayEventHandler;
+use crate::{deep_link, notifications, plaintext, store};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+/// Store file name for the notification rules
+const SYSTEM_NOTIFICATIONS_FILE: &str = "system_notifications.json";
+
+/// Longest notification body, in characters
+const BODY_CHARS: usize = 160;
+
+/// Which cron job results are posted as notifications
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CronNotify {
+    #[default]
+    All,
+    Failures,
+    Off,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Notification rules for system events
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SystemNotificationSettings {
+    #[serde(rename = "cronResults", default)]
+    pub cron_results: CronNotify,
+    #[serde(rename = "channelMessages", default = "default_true")]
+    pub channel_messages: bool,
+    /// Also notify while the main window has focus (it shows the event too)
+    #[serde(rename = "whenFocused", default)]
+    pub when_focused: bool,
+}
+
+impl Default for SystemNotificationSettings {
+    fn default() -> Self {
+        Self {
+            cron_results: CronNotify::All,
+            channel_messages: true,
+            when_focused: false,
+        }
+    }
+}
+
+impl SystemNotificationSettings {
+    /// Whether an event should be posted, given whether the main window has
+    /// focus
+    fn should_notify(&self, event: &SystemEvent, focused: bool) -> bool {
+        if focused && !self.when_focused {
+            return false;
+        }
+        match event {
+            SystemEvent::Cron(result) => match self.cron_results {
+                CronNotify::All => true,
+                CronNotify::Failures => !result.ok,
+                CronNotify::Off => false,
+            },
+            SystemEvent::Channel(_) => self.channel_messages,
+        }
+    }
+}
+
+/// Result of a scheduled (cron) job run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CronResult {
+    #[serde(rename = "jobId")]
+    pub job_id: Option<String>,
+    pub name: Option<String>,
+    pub ok: bool,
+    /// The job's output, or its error when it failed
+    pub summary: Option<String>,
+    #[serde(rename = "sessionKey")]
+    pub session_key: Option<String>,
+}
+
+/// Message that arrived on one of the Gateway's channels
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChannelMessage {
+    pub channel: String,
+    pub sender: Option<String>,
+    pub text: String,
+    #[serde(rename = "sessionKey")]
+    pub session_key: Option<String>,
+}
+
+/// A typed system event
+#[derive(Debug, Clone, PartialEq)]
+pub enum SystemEvent {
+    Cron(CronResult),
+    Channel(ChannelMessage),
+}
+
+/// First non-empty string among `keys`
+fn str_field(payload: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|key| payload.get(*key)?.as_str())
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+impl SystemEvent {
+    /// Parse a Gateway event; None for events that are not a job result or
+    /// a message (e.g. `cron.started`)
+    fn parse(event: &str, payload: &Value) -> Option<Self> {
+        let session_key = str_field(payload, &["sessionKey", "session"]);
+        if event.starts_with("cron.") {
+            let ok = match str_field(payload, &["status", "state"])?.as_str() {
+                "ok" | "success" | "succeeded" | "completed" | "done" => true,
+                "error" | "failed" | "failure" | "timeout" => false,
+                _ => return None,
+            };
+            let summary = if ok {
+                str_field(payload, &["summary", "result", "text", "output"])
+            } else {
+                str_field(payload, &["error", "errorMessage", "summary"])
+            };
+            return Some(Self::Cron(CronResult {
+                job_id: str_field(payload, &["jobId", "id"]),
+                name: str_field(payload, &["name", "label"]),
+                ok,
+                summary,
+                session_key,
+            }));
+        }
+        if event.starts_with("channel.") {
+            return Some(Self::Channel(ChannelMessage {
+                channel: str_field(payload, &["channel", "provider"])?,
+                sender: str_field(payload, &["from", "sender", "senderName"]),
+                text: str_field(payload, &["text", "message", "body"])?,
+                session_key,
+            }));
+        }
+        None
+    }
+
+    fn session_key(&self) -> Option<&str> {
+        match self {
+            Self::Cron(result) => result.session_key.as_deref(),
+            Self::Channel(message) => message.session_key.as_deref(),
+        }
+    }
+
+    /// Notification title and body
+    fn notification(&self) -> (String, String) {
+        match self {
+            Self::Cron(result) => {
+                let name = result.name.as_deref().unwrap_or("Scheduled task");
+                let title = if result.ok {
+                    format!("{} finished", name)
+                } else {
+                    format!("{} failed", name)
+                };
+                let body = result
+                    .summary
+                    .as_deref()
+                    .map(|summary| plaintext::summary(summary, BODY_CHARS))
+                    .unwrap_or_default();
+                (title, body)
+            }
+            Self::Channel(message) => {
+                let title = match &message.sender {
+                    Some(sender) => format!("{} on {}", sender, message.channel),
+                    None => format!("New message on {}", message.channel),
+                };
+                (title, plaintext::summary(&message.text, BODY_CHARS))
+            }
+        }
+    }
+
+    /// Forward the event to the frontend
+    fn emit(&self, app: &AppHandle) {
+        let _ = match self {
+            Self::Cron(result) => app.emit("system:cron", result),
+            Self::Channel(message) => app.emit("system:channel_message", message),
+        };
+    }
+}
+
+/// Notification rules managed by Tauri
+pub struct SystemEventsState {
+    path: PathBuf,
+    settings: std::sync::RwLock<SystemNotificationSettings>,
+}
+
+impl SystemEventsState {
+    /// Load the rules from the local store
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, SYSTEM_NOTIFICATIONS_FILE);
+        let settings = store::load(&path);
+        Self {
+            path,
+            settings: std::sync::RwLock::new(settings),
+        }
+    }
+
+    pub fn settings(&self) -> SystemNotificationSettings {
+        self.settings
+            .read()
+            .map(|settings| settings.clone())
+            .unwrap_or_default()
+    }
+
+    /// Save and apply rules
+    pub fn apply(&self, settings: SystemNotificationSettings) -> Result<(), String> {
+        store::save(&self.path, &settings)?;
+        if let Ok(mut current) = self.settings.write() {
+            *current = settings;
+        }
+        Ok(())
+    }
+}
+
+/// Handles `cron.*` and `channel.*` Gateway events
+pub struct SystemEventHandler;
+
+impl GatewayEventHandler for SystemEventHandler {
+    fn events(&self) -> &[&str] {
+        &["cron.*", "channel.*"]
+    }
+
+    fn handle(&self, app: &AppHandle, event: &str, payload: Option<&Value>) {
+        let Some(system_event) = payload.and_then(|payload| SystemEvent::parse(event, payload))
+        else {
+            // Nothing typed to do with it: keep it visible like other events
+            let _ = app.emit(
+                "gateway:event",
+                serde_json::json!({ "event": event, "payload": payload }),
+            );
+            return;
+        };
+        system_event.emit(app);
+
+        let focused = app
+            .get_webview_window("main")
+            .and_then(|window| window.is_focused().ok())
+            .unwrap_or(false);
+        let settings = app
+            .try_state::<SystemEventsState>()
+            .map(|state| state.settings())
+            .unwrap_or_default();
+        if !settings.should_notify(&system_event, focused) {
+            return;
+        }
+
+        log::info!("[SystemEvents] Notifying about {}", event);
+        let (title, body) = system_event.notification();
+        let link = system_event
+            .session_key()
+            .map(|session_key| deep_link::conversation_url(session_key, None));
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let handle = app.clone();
+            let target = link.clone();
+            notifications::show_clickable(&app, &title, &body, link, move || match target {
+                Some(link) => deep_link::handle(&handle, &link),
+                None => {
+                    if let Some(window) = handle.get_webview_window("main") {
+                        let _ = window.show();
+                        let _ = window.set_focus();
+                    }
+                }
+            })
+            .await;
+        });
+    }
+}
+
+/// Get the notification rules for system events
+#[tauri::command]
+pub async fn get_system_notification_settings(
+    state: State<'_, SystemEventsState>,
+) -> Result<SystemNotificationSettings, CommandError> {
+    Ok(state.settings())
+}
+
+/// Replace the notification rules for system events
+#[tauri::command]
+pub async fn set_system_notification_settings(
+    state: State<'_, SystemEventsState>,
+    settings: SystemNotificationSettings,
+) -> Result<(), CommandError> {
+    state.apply(settings)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_system_events() {
+        let finished = json!({
+            "jobId": "job-1",
+            "name": "Daily digest",
+            "status": "ok",
+            "summary": "**3** new issues",
+            "sessionKey": "agent:main:cron",
+        });
+        assert_eq!(
+            SystemEvent::parse("cron.finished", &finished),
+            Some(SystemEvent::Cron(CronResult {
+                job_id: Some("job-1".to_string()),
+                name: Some("Daily digest".to_string()),
+                ok: true,
+                summary: Some("**3** new issues".to_string()),
+                session_key: Some("agent:main:cron".to_string()),
+            }))
+        );
+
+        let failed = json!({ "id": "job-2", "status": "failed", "error": "Timed out" });
+        let Some(SystemEvent::Cron(result)) = SystemEvent::parse("cron.finished", &failed) else {
+            panic!("expected a cron result");
+        };
+        assert!(!result.ok);
+        assert_eq!(result.summary.as_deref(), Some("Timed out"));
+
+        // Runs starting are not results
+        assert_eq!(
+            SystemEvent::parse("cron.started", &json!({ "status": "running" })),
+            None
+        );
+
+        let message = json!({ "channel": "telegram", "from": "Ana", "text": "Ping" });
+        assert_eq!(
+            SystemEvent::parse("channel.message", &message),
+            Some(SystemEvent::Channel(ChannelMessage {
+                channel: "telegram".to_string(),
+                sender: Some("Ana".to_string()),
+                text: "Ping".to_string(),
+                session_key: None,
+            }))
+        );
+        assert_eq!(
+            SystemEvent::parse("channel.message", &json!({ "channel": "telegram" })),
+            None
+        );
+    }
+
+    #[test]
+    fn test_should_notify() {
+        let cron = |ok| {
+            SystemEvent::Cron(CronResult {
+                job_id: None,
+                name: None,
+                ok,
+                summary: None,
+                session_key: None,
+            })
+        };
+        let channel = SystemEvent::Channel(ChannelMessage {
+            channel: "slack".to_string(),
+            sender: None,
+            text: "Hi".to_string(),
+            session_key: None,
+        });
+
+        let defaults = SystemNotificationSettings::default();
+        assert!(defaults.should_notify(&cron(true), false));
+        assert!(defaults.should_notify(&channel, false));
+        assert!(!defaults.should_notify(&cron(true), true));
+
+        let failures = SystemNotificationSettings {
+            cron_results: CronNotify::Failures,
+            channel_messages: false,
+            when_focused: true,
+        };
+        assert!(!failures.should_notify(&cron(true), true));
+        assert!(failures.should_notify(&cron(false), true));
+        assert!(!failures.should_notify(&channel, false));
+    }
+
+    #[test]
+    fn test_notification_text() {
+        let result = SystemEvent::Cron(CronResult {
+            job_id: None,
+            name: Some("Backup".to_string()),
+            ok: false,
+            summary: Some("Disk full".to_string()),
+            session_key: None,
+        });
+        assert_eq!(
+            result.notification(),
+            ("Backup failed".to_string(), "Disk full".to_string())
+        );
+    }
+}
@@ -0,0 +1,41 @@
+//! Recovery of the tray icon and global shortcuts
+//!
+//! When the app crashes and restarts quickly, the previous instance's tray
+//! icon or shortcut grabs can linger with the OS for a moment and registering
+//! them again fails. The tray and the shortcuts retry with [`RETRY_DELAYS`]
+//! and report what still failed; `reinitialize_system_integration` redoes
+//! both on demand, without restarting the app.
+
+use crate::error::CommandError;
+use crate::shortcuts::{self, ShortcutStatus};
+use crate::tray::{self, TrayStatus};
+use serde::Serialize;
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// Delays between attempts to create the tray icon or register a shortcut
+pub(crate) const RETRY_DELAYS: [Duration; 3] = [
+    Duration::from_secs(1),
+    Duration::from_secs(3),
+    Duration::from_secs(10),
+];
+
+/// Outcome of re-registering the tray icon and global shortcuts
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemIntegrationStatus {
+    pub tray: TrayStatus,
+    pub shortcuts: Vec<ShortcutStatus>,
+}
+
+/// Recreate the tray icon and register the global shortcuts again
+///
+/// Quick Ask is registered by the frontend, which redoes it on
+/// `shortcuts:reregister` and reports back through `shortcuts:status`.
+#[tauri::command]
+pub async fn reinitialize_system_integration(
+    app: AppHandle,
+) -> Result<SystemIntegrationStatus, CommandError> {
+    log::info!("[Integration] Re-registering the tray icon and global shortcuts");
+    let (tray, shortcuts) = tokio::join!(tray::reinitialize(&app), shortcuts::reinitialize(&app));
+    Ok(SystemIntegrationStatus { tray, shortcuts })
+}
@@ -0,0 +1,247 @@
+//! End-to-end test message
+//!
+//! After the first connect, onboarding sends a short canned prompt on a
+//! throwaway session and follows it through each stage:
+//! - connect: the Gateway connection is up
+//! - send: the Gateway accepted `chat.send`
+//! - stream: streamed text arrived
+//! - complete: the run finished with a reply
+//!
+//! The report lists the stages reached, so onboarding can confirm that
+//! everything works or point at the stage that failed. The throwaway
+//! session is deleted afterwards.
+
+use crate::error::CommandError;
+use crate::gateway::GatewayState;
+use crate::scheduler::{self, RunStage};
+use crate::{plaintext, sessions};
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc;
+
+/// Prompt sent as the test message
+const TEST_PROMPT: &str = "This is a connection test. Reply with one short sentence.";
+
+/// How long the reply may take
+const TEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Length of the reply excerpt in the report
+const REPLY_EXCERPT_CHARS: usize = 200;
+
+/// A stage of the test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestStage {
+    Connect,
+    Send,
+    Stream,
+    Complete,
+}
+
+/// Result of one stage
+#[derive(Debug, Clone, Serialize)]
+pub struct TestStageResult {
+    pub stage: TestStage,
+    pub ok: bool,
+    /// Time since the previous stage
+    #[serde(rename = "durationMs")]
+    pub duration_ms: u64,
+    pub detail: Option<String>,
+}
+
+/// Outcome of a test message
+#[derive(Debug, Clone, Serialize)]
+pub struct TestMessageReport {
+    /// Every stage passed
+    pub ok: bool,
+    /// Stages reached, in order; a failed stage is the last one unless the
+    /// reply arrived without streaming
+    pub stages: Vec<TestStageResult>,
+    /// Plain text excerpt of the reply
+    pub reply: Option<String>,
+}
+
+/// Build the report from the stages reached (with their time since the
+/// start) and the run's outcome
+fn build_report(
+    reached: &[(RunStage, Duration)],
+    result: Result<String, String>,
+    total: Duration,
+) -> TestMessageReport {
+    let mut stages = Vec::new();
+    let mut previous = Duration::ZERO;
+    let mut elapsed = |at: Duration| {
+        let duration = at.saturating_sub(previous);
+        previous = previous.max(at);
+        duration.as_millis() as u64
+    };
+
+    for (stage, run_stage) in [
+        (TestStage::Connect, RunStage::Connected),
+        (TestStage::Send, RunStage::Sent),
+        (TestStage::Stream, RunStage::Streaming),
+    ] {
+        if let Some((_, at)) = reached.iter().find(|(reached, _)| *reached == run_stage) {
+            stages.push(TestStageResult {
+                stage,
+                ok: true,
+                duration_ms: elapsed(*at),
+                detail: None,
+            });
+            continue;
+        }
+
+        let detail = match &result {
+            Err(e) => e.clone(),
+            Ok(_) => "The reply arrived without streaming".to_string(),
+        };
+        stages.push(TestStageResult {
+            stage,
+            ok: false,
+            duration_ms: elapsed(total),
+            detail: Some(detail),
+        });
+        // A reply without streaming still completed
+        if stage != TestStage::Stream || result.is_err() {
+            return TestMessageReport {
+                ok: false,
+                stages,
+                reply: None,
+            };
+        }
+    }
+
+    let (ok, detail, reply) = match result {
+        Ok(text) if text.trim().is_empty() => {
+            (false, Some("The reply was empty".to_string()), None)
+        }
+        Ok(text) => (
+            true,
+            None,
+            Some(plaintext::summary(&text, REPLY_EXCERPT_CHARS)),
+        ),
+        Err(e) => (false, Some(e), None),
+    };
+    stages.push(TestStageResult {
+        stage: TestStage::Complete,
+        ok,
+        duration_ms: elapsed(total),
+        detail,
+    });
+    TestMessageReport {
+        ok: stages.iter().all(|stage| stage.ok),
+        stages,
+        reply,
+    }
+}
+
+/// Send a canned prompt on a throwaway session and report each stage
+///
+/// Connects to `gateway_url` first if there is no connection yet.
+#[tauri::command]
+pub async fn send_test_message(
+    app: AppHandle,
+    gateway_url: Option<String>,
+) -> Result<TestMessageReport, CommandError> {
+    let session_key = sessions::generate_session_key();
+    let (stages_tx, mut stages_rx) = mpsc::unbounded_channel();
+    let started = Instant::now();
+
+    let run = scheduler::send_and_collect_with_stages(
+        &app,
+        &session_key,
+        TEST_PROMPT,
+        gateway_url.as_deref(),
+        TEST_TIMEOUT,
+        Some(stages_tx),
+    );
+    tokio::pin!(run);
+    let mut reached = Vec::new();
+    let result = loop {
+        tokio::select! {
+            Some(stage) = stages_rx.recv() => reached.push((stage, started.elapsed())),
+            result = &mut run => break result,
+        }
+    };
+    while let Ok(stage) = stages_rx.try_recv() {
+        reached.push((stage, started.elapsed()));
+    }
+    let total = started.elapsed();
+
+    // The Gateway only knows the session once it accepted the message
+    if reached.iter().any(|(stage, _)| *stage == RunStage::Sent) {
+        let gateway = app.state::<GatewayState>();
+        let params = serde_json::json!({ "key": session_key });
+        if let Err(e) = gateway.request("sessions.delete", Some(params)).await {
+            log::error!("[TestMessage] Throwaway session not deleted: {}", e);
+        }
+    }
+
+    let report = build_report(&reached, result, total);
+    log::info!(
+        "[TestMessage] Finished (ok: {}, stages: {})",
+        report.ok,
+        report.stages.len()
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_report_all_stages() {
+        let reached = [
+            (RunStage::Connected, ms(100)),
+            (RunStage::Sent, ms(150)),
+            (RunStage::Streaming, ms(900)),
+        ];
+        let report = build_report(&reached, Ok("**Hello** there".to_string()), ms(1400));
+        assert!(report.ok);
+        let durations: Vec<u64> = report.stages.iter().map(|s| s.duration_ms).collect();
+        assert_eq!(durations, [100, 50, 750, 500]);
+        assert_eq!(report.reply.as_deref(), Some("Hello there"));
+    }
+
+    #[test]
+    fn test_report_pinpoints_failure() {
+        let report = build_report(&[], Err("Connection refused".to_string()), ms(50));
+        assert!(!report.ok);
+        assert_eq!(report.stages.len(), 1);
+        assert_eq!(report.stages[0].stage, TestStage::Connect);
+        assert_eq!(
+            report.stages[0].detail.as_deref(),
+            Some("Connection refused")
+        );
+
+        let reached = [(RunStage::Connected, ms(10)), (RunStage::Sent, ms(20))];
+        let report = build_report(
+            &reached,
+            Err("Timed out waiting for the reply".to_string()),
+            ms(60_000),
+        );
+        let failed = report.stages.last().unwrap();
+        assert_eq!((failed.stage, failed.ok), (TestStage::Stream, false));
+
+        // The reply arrived, but was not streamed
+        let report = build_report(&reached, Ok("Hi".to_string()), ms(500));
+        assert!(!report.ok);
+        let outcomes: Vec<(TestStage, bool)> =
+            report.stages.iter().map(|s| (s.stage, s.ok)).collect();
+        assert_eq!(
+            outcomes,
+            [
+                (TestStage::Connect, true),
+                (TestStage::Send, true),
+                (TestStage::Stream, false),
+                (TestStage::Complete, true)
+            ]
+        );
+    }
+}
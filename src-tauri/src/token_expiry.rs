@@ -0,0 +1,457 @@
+//! Gateway token expiry warnings and re-pairing
+//!
+//! The token's expiry is fetched with `token.info` after each connect (and
+//! taken from `token.rotate` / pairing responses) and kept in the local
+//! store. Once the token is within the warning window (7 days by default) the
+//! user gets one notification per token, a `gateway:token_expiring` event,
+//! and a "Re-pair Gateway" entry in the tray menu.
+//!
+//! Re-pairing uses the Gateway's device-code flow while the current token
+//! still works:
+//! - `device.pair.start` returns `{ deviceCode, userCode, verificationUrl,
+//!   expiresIn, interval }`; the URL is shown as a QR code to scan with a
+//!   device that is signed in to the Gateway's control panel
+//! - `device.pair.poll { deviceCode }` returns `{ status: "pending" |
+//!   "approved" | "denied" | "expired", token?, expiresAt? }`
+//!
+//! On approval the new token replaces the old one in the keychain and the
+//! reconnect credentials, and `gateway:repaired` is emitted.
+
+use crate::error::CommandError;
+use crate::gateway::GatewayState;
+use crate::notifications;
+use crate::store;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+use tokio::sync::Mutex;
+
+/// Store file name for token metadata
+const TOKEN_FILE: &str = "token_meta.json";
+
+/// Default warning window before expiry
+const DEFAULT_WARN_DAYS: u32 = 7;
+
+/// How often the expiry is checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Poll interval when the Gateway doesn't suggest one
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 5;
+
+/// Persisted token metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TokenMeta {
+    expires_at: Option<DateTime<Utc>>,
+    /// Expiry already warned about (one warning per token)
+    warned_for: Option<DateTime<Utc>>,
+    #[serde(default = "default_warn_days")]
+    warn_days: u32,
+}
+
+fn default_warn_days() -> u32 {
+    DEFAULT_WARN_DAYS
+}
+
+impl Default for TokenMeta {
+    fn default() -> Self {
+        Self {
+            expires_at: None,
+            warned_for: None,
+            warn_days: DEFAULT_WARN_DAYS,
+        }
+    }
+}
+
+impl TokenMeta {
+    /// Whole days left before expiry while within the warning window
+    fn expiring_in_days(&self, now: DateTime<Utc>) -> Option<i64> {
+        let expires_at = self.expires_at?;
+        let left = expires_at - now;
+        (left <= chrono::Duration::days(self.warn_days as i64)).then(|| left.num_days().max(0))
+    }
+
+    /// Whether a warning is due (expiring, and not yet warned for this expiry)
+    fn should_warn(&self, now: DateTime<Utc>) -> bool {
+        self.expiring_in_days(now).is_some() && self.warned_for != self.expires_at
+    }
+}
+
+/// Token expiry status for the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenExpiryStatus {
+    #[serde(rename = "expiresAt")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Days left while within the warning window, otherwise None
+    #[serde(rename = "expiringInDays")]
+    pub expiring_in_days: Option<i64>,
+    #[serde(rename = "warnDays")]
+    pub warn_days: u32,
+}
+
+/// Payload of `gateway:token_expiring`
+#[derive(Debug, Clone, Serialize)]
+struct TokenExpiring {
+    #[serde(rename = "expiresAt")]
+    expires_at: DateTime<Utc>,
+    #[serde(rename = "daysLeft")]
+    days_left: i64,
+}
+
+/// A started re-pairing, shown to the user
+#[derive(Debug, Clone, Serialize)]
+pub struct PairingCode {
+    #[serde(rename = "userCode")]
+    pub user_code: String,
+    #[serde(rename = "verificationUrl")]
+    pub verification_url: String,
+    /// QR code of the verification URL, as an SVG document
+    #[serde(rename = "qrSvg")]
+    pub qr_svg: String,
+    #[serde(rename = "expiresAt")]
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Token expiry state managed by Tauri
+pub struct TokenExpiryState {
+    path: PathBuf,
+    data: Mutex<TokenMeta>,
+    /// Device code of the re-pairing in progress (polling stops when it changes)
+    pairing: Mutex<Option<String>>,
+}
+
+impl TokenExpiryState {
+    /// Load token metadata from the local store
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = store::store_path(app, TOKEN_FILE);
+        Self {
+            data: Mutex::new(store::load(&path)),
+            path,
+            pairing: Mutex::new(None),
+        }
+    }
+
+    async fn status(&self) -> TokenExpiryStatus {
+        let data = self.data.lock().await;
+        TokenExpiryStatus {
+            expires_at: data.expires_at,
+            expiring_in_days: data.expiring_in_days(Utc::now()),
+            warn_days: data.warn_days,
+        }
+    }
+
+    async fn update(&self, apply: impl FnOnce(&mut TokenMeta)) {
+        let mut data = self.data.lock().await;
+        apply(&mut data);
+        if let Err(e) = store::save(&self.path, &*data) {
+            log::error!("[Token] Failed to save token metadata: {}", e);
+        }
+    }
+}
+
+/// Parse `expiresAt` from a Gateway payload (epoch milliseconds or RFC 3339)
+pub fn parse_expiry(payload: &serde_json::Value) -> Option<DateTime<Utc>> {
+    match payload.get("expiresAt")? {
+        serde_json::Value::Number(ms) => DateTime::from_timestamp_millis(ms.as_i64()?),
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Record the expiry of the current token, then warn if it is close
+pub async fn record_expiry(app: &AppHandle, expires_at: Option<DateTime<Utc>>) {
+    let Some(state) = app.try_state::<TokenExpiryState>() else {
+        return;
+    };
+    state.update(|data| data.expires_at = expires_at).await;
+    check(app).await;
+}
+
+/// Fetch the token's expiry from the Gateway
+///
+/// Gateways without `token.info` issue tokens that don't expire.
+pub async fn refresh(app: &AppHandle) -> Result<(), String> {
+    let gateway = app.state::<GatewayState>();
+    let payload = gateway
+        .request("token.info", None)
+        .await
+        .map_err(|e| e.user_message())?;
+    record_expiry(app, payload.as_ref().and_then(parse_expiry)).await;
+    Ok(())
+}
+
+/// Days left before expiry while within the warning window
+pub async fn expiring_in_days(app: &AppHandle) -> Option<i64> {
+    let state = app.try_state::<TokenExpiryState>()?;
+    let data = state.data.lock().await;
+    data.expiring_in_days(Utc::now())
+}
+
+/// Open the main window on the re-pair prompt
+pub fn open_repair(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    let _ = app.emit("menu:repair", ());
+}
+
+/// Warn once per token when it enters the warning window
+async fn check(app: &AppHandle) {
+    let Some(state) = app.try_state::<TokenExpiryState>() else {
+        return;
+    };
+    let now = Utc::now();
+    let warning = {
+        let mut data = state.data.lock().await;
+        if !data.should_warn(now) {
+            return;
+        }
+        data.warned_for = data.expires_at;
+        if let Err(e) = store::save(&state.path, &*data) {
+            log::error!("[Token] Failed to save token metadata: {}", e);
+        }
+        match (data.expires_at, data.expiring_in_days(now)) {
+            (Some(expires_at), Some(days_left)) => TokenExpiring {
+                expires_at,
+                days_left,
+            },
+            _ => return,
+        }
+    };
+
+    let body = match warning.days_left {
+        0 => "Your Gateway token expires today. Re-pair now to stay connected.".to_string(),
+        1 => "Your Gateway token expires tomorrow. Re-pair now to stay connected.".to_string(),
+        n => format!(
+            "Your Gateway token expires in {} days. Re-pair now to stay connected.",
+            n
+        ),
+    };
+    let _ = app.emit("gateway:token_expiring", warning);
+    let handle = app.clone();
+    notifications::show_clickable(app, "Gateway token expiring", &body, None, move || {
+        open_repair(&handle)
+    })
+    .await;
+}
+
+/// Start checking the token expiry periodically
+pub fn start_expiry_monitor(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            check(&app).await;
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Render a QR code as SVG
+fn qr_svg(data: &str) -> Result<String, CommandError> {
+    let code = qrcode::QrCode::new(data.as_bytes())
+        .map_err(|e| CommandError::internal(format!("Failed to render QR code: {}", e)))?;
+    Ok(code
+        .render::<qrcode::render::svg::Color>()
+        .min_dimensions(200, 200)
+        .build())
+}
+
+/// Poll the Gateway until the pairing is approved, denied or expired
+async fn poll_pairing(
+    app: AppHandle,
+    device_code: String,
+    interval: Duration,
+    expires_at: DateTime<Utc>,
+) {
+    let state = app.state::<TokenExpiryState>();
+    let gateway = app.state::<GatewayState>();
+    let outcome = loop {
+        tokio::time::sleep(interval).await;
+        if state.pairing.lock().await.as_deref() != Some(device_code.as_str()) {
+            return;
+        }
+        if Utc::now() > expires_at {
+            break Err(CommandError::validation("The pairing code expired"));
+        }
+        let payload = match gateway
+            .request(
+                "device.pair.poll",
+                Some(serde_json::json!({ "deviceCode": device_code })),
+            )
+            .await
+        {
+            Ok(payload) => payload.unwrap_or_default(),
+            // Transient: keep polling while the code is valid
+            Err(e) if e.is_retryable() => continue,
+            Err(e) => break Err(e.into()),
+        };
+        match payload.get("status").and_then(|s| s.as_str()) {
+            Some("approved") => {
+                let token = payload
+                    .get("token")
+                    .and_then(|t| t.as_str())
+                    .filter(|t| !t.is_empty());
+                break match token {
+                    Some(token) => gateway
+                        .replace_token(token)
+                        .await
+                        .map(|_| parse_expiry(&payload)),
+                    None => Err(CommandError::protocol("Gateway did not return a token")),
+                };
+            }
+            Some("denied") => break Err(CommandError::auth("Pairing was denied")),
+            Some("expired") => break Err(CommandError::validation("The pairing code expired")),
+            _ => {}
+        }
+    };
+
+    *state.pairing.lock().await = None;
+    match outcome {
+        Ok(expires_at) => {
+            log::info!("[Token] Re-paired with the Gateway");
+            state
+                .update(|data| {
+                    data.expires_at = expires_at;
+                    data.warned_for = None;
+                })
+                .await;
+            let _ = app.emit(
+                "gateway:repaired",
+                serde_json::json!({ "expiresAt": expires_at }),
+            );
+        }
+        Err(e) => {
+            log::error!("[Token] Re-pairing failed: {}", e);
+            let _ = app.emit("gateway:repair_failed", e);
+        }
+    }
+}
+
+/// Get the token expiry status
+#[tauri::command]
+pub async fn get_token_expiry(
+    state: State<'_, TokenExpiryState>,
+) -> Result<TokenExpiryStatus, CommandError> {
+    Ok(state.status().await)
+}
+
+/// Set how many days before expiry to warn
+#[tauri::command]
+pub async fn set_token_expiry_warning(
+    app: AppHandle,
+    state: State<'_, TokenExpiryState>,
+    days: u32,
+) -> Result<TokenExpiryStatus, CommandError> {
+    if days == 0 {
+        return Err(CommandError::validation("Warn at least one day ahead"));
+    }
+    state.update(|data| data.warn_days = days).await;
+    check(&app).await;
+    Ok(state.status().await)
+}
+
+/// Start re-pairing with the Gateway (device code + QR)
+///
+/// Replaces any re-pairing in progress. The outcome is reported with
+/// `gateway:repaired` or `gateway:repair_failed`.
+#[tauri::command]
+pub async fn start_repair(
+    app: AppHandle,
+    state: State<'_, TokenExpiryState>,
+    gateway: State<'_, GatewayState>,
+) -> Result<PairingCode, CommandError> {
+    let payload = gateway
+        .request("device.pair.start", Some(serde_json::json!({})))
+        .await?
+        .unwrap_or_default();
+    let field = |name: &str| {
+        payload
+            .get(name)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| CommandError::protocol(format!("Pairing response is missing {}", name)))
+    };
+    let device_code = field("deviceCode")?;
+    let user_code = field("userCode")?;
+    let verification_url = field("verificationUrl")?;
+    let expires_in = payload
+        .get("expiresIn")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(600);
+    let interval = payload
+        .get("interval")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_POLL_INTERVAL_SECS)
+        .max(1);
+
+    let code = PairingCode {
+        qr_svg: qr_svg(&verification_url)?,
+        user_code,
+        verification_url,
+        expires_at: Utc::now() + chrono::Duration::seconds(expires_in),
+    };
+
+    *state.pairing.lock().await = Some(device_code.clone());
+    tauri::async_runtime::spawn(poll_pairing(
+        app,
+        device_code,
+        Duration::from_secs(interval),
+        code.expires_at,
+    ));
+    Ok(code)
+}
+
+/// Stop waiting for the re-pairing in progress
+#[tauri::command]
+pub async fn cancel_repair(state: State<'_, TokenExpiryState>) -> Result<(), CommandError> {
+    *state.pairing.lock().await = None;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expiry() {
+        let expected: DateTime<Utc> = "2024-01-16T16:00:00Z".parse().unwrap();
+        assert_eq!(
+            parse_expiry(&serde_json::json!({ "expiresAt": 1705420800000i64 })),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_expiry(&serde_json::json!({ "expiresAt": "2024-01-16T16:00:00Z" })),
+            Some(expected)
+        );
+        assert_eq!(parse_expiry(&serde_json::json!({})), None);
+    }
+
+    #[test]
+    fn test_warns_once_within_window() {
+        let now: DateTime<Utc> = "2026-03-01T12:00:00Z".parse().unwrap();
+        let mut meta = TokenMeta {
+            expires_at: Some(now + chrono::Duration::days(30)),
+            ..Default::default()
+        };
+        assert_eq!(meta.expiring_in_days(now), None);
+        assert!(!meta.should_warn(now));
+
+        meta.expires_at = Some(now + chrono::Duration::days(3) + chrono::Duration::hours(2));
+        assert_eq!(meta.expiring_in_days(now), Some(3));
+        assert!(meta.should_warn(now));
+
+        meta.warned_for = meta.expires_at;
+        assert!(!meta.should_warn(now));
+
+        // A new token (different expiry) warns again
+        meta.expires_at = Some(now + chrono::Duration::days(2));
+        assert!(meta.should_warn(now));
+
+        // Already expired
+        meta.expires_at = Some(now - chrono::Duration::days(1));
+        assert_eq!(meta.expiring_in_days(now), Some(0));
+    }
+}
@@ -5,12 +5,40 @@
 //! - Quick Ask
 //! - Show/Hide Window
 //! - Quit
+//!
+//! The tooltip shows live status: connection, runs in progress and replies
+//! that arrived while the main window was in the background. While the
+//! Gateway token is about to expire, the tooltip says so and a "Re-pair
+//! Gateway" item is enabled.
+//!
+//! On Linux the tray is an AppIndicator (StatusNotifierItem), which needs a
+//! StatusNotifier host: stock GNOME has none unless the AppIndicator
+//! extension is enabled, and building the icon there "succeeds" without
+//! showing anything. The host is checked first; when there is none, or the
+//! AppIndicator library is missing, no tray is built and `tray:unavailable`
+//! is emitted instead. The main window then shows the status and tray
+//! actions itself (it listens for `tray:status` and calls
+//! `run_tray_action`).
+//!
+//! Creating the icon is retried a few times, as a crashed instance's icon may
+//! still be registered or the StatusNotifier host may still be starting; a
+//! late success is emitted as `tray:available`.
 
+use crate::accessibility;
+use crate::error::CommandError;
+use crate::gateway::GatewayState;
+use crate::protocol::ConnectionState;
+use crate::system_integration::RETRY_DELAYS;
+use crate::token_expiry;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
 use tauri::{
     image::Image,
     menu::{Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    AppHandle, Manager,
+    AppHandle, Emitter, Manager, Wry,
 };
 
 /// Tray menu item IDs
@@ -18,11 +46,215 @@ pub mod ids {
     pub const SHOW_HIDE: &str = "tray_show_hide";
     pub const NEW_CONVERSATION: &str = "tray_new_conversation";
     pub const QUICK_ASK: &str = "tray_quick_ask";
+    pub const REPAIR: &str = "tray_repair";
     pub const QUIT: &str = "tray_quit";
 }
 
-/// Build and setup the system tray
-pub fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
+/// Tray icon ID
+const TRAY_ID: &str = "main";
+
+/// How often the tooltip is refreshed
+const STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Replies received while the main window was unfocused
+static UNREAD: AtomicU32 = AtomicU32::new(0);
+
+/// "Re-pair Gateway" menu item, enabled while the token is about to expire
+static REPAIR_ITEM: Mutex<Option<MenuItem<Wry>>> = Mutex::new(None);
+
+/// Outcome of the latest tray setup
+static TRAY_STATUS: Mutex<Option<TrayStatus>> = Mutex::new(None);
+
+/// The tray icon was (re)built and needs the current tooltip and menu state
+static TRAY_REBUILT: AtomicBool = AtomicBool::new(false);
+
+/// Last status text, for the window fallback
+static STATUS_TEXT: Mutex<String> = Mutex::new(String::new());
+
+/// How the tray icon is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayProtocol {
+    /// macOS menu bar / Windows notification area
+    Native,
+    /// AppIndicator registered with a StatusNotifier host (Linux)
+    StatusNotifier,
+}
+
+/// Whether the tray could be set up
+#[derive(Debug, Clone, Serialize)]
+pub struct TrayStatus {
+    pub available: bool,
+    pub protocol: Option<TrayProtocol>,
+    /// Why there is no tray
+    pub reason: Option<String>,
+    /// What the tooltip shows (connection, runs, unread, token expiry)
+    #[serde(rename = "statusText")]
+    pub status_text: String,
+}
+
+/// Tray icon (the app icon)
+fn tray_icon() -> tauri::Result<Image<'static>> {
+    Image::from_bytes(include_bytes!("../icons/icon.png"))
+}
+
+/// Switch the tray icon between the regular and high-contrast variants
+pub fn set_high_contrast(app: &AppHandle, high_contrast: bool) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let icon = match tray_icon() {
+        Ok(icon) if high_contrast => accessibility::high_contrast_icon(&icon),
+        Ok(icon) => icon,
+        Err(_) => return,
+    };
+    let _ = tray.set_icon(Some(icon));
+}
+
+/// Parse the output of `gdbus call ... NameHasOwner`, e.g. `(true,)`
+#[cfg(any(target_os = "linux", test))]
+fn parse_name_has_owner(output: &str) -> Option<bool> {
+    match output.trim().trim_start_matches('(').trim_end_matches(')') {
+        "true," => Some(true),
+        "false," => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether a StatusNotifier host is running (`None` if it can't be told)
+#[cfg(target_os = "linux")]
+fn status_notifier_host_running() -> Option<bool> {
+    let output = std::process::Command::new("gdbus")
+        .args([
+            "call",
+            "--session",
+            "--dest",
+            "org.freedesktop.DBus",
+            "--object-path",
+            "/org/freedesktop/DBus",
+            "--method",
+            "org.freedesktop.DBus.NameHasOwner",
+            "org.kde.StatusNotifierWatcher",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_name_has_owner(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// The tray protocol available on this desktop
+#[cfg(target_os = "linux")]
+fn detect_protocol() -> Result<TrayProtocol, String> {
+    match status_notifier_host_running() {
+        Some(false) => Err(
+            "No StatusNotifier host is running (on GNOME, enable the AppIndicator extension)"
+                .to_string(),
+        ),
+        // Unknown: try the AppIndicator anyway
+        Some(true) | None => Ok(TrayProtocol::StatusNotifier),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn detect_protocol() -> Result<TrayProtocol, String> {
+    Ok(TrayProtocol::Native)
+}
+
+/// Create the tray icon, replacing an existing one, and record the outcome
+fn try_setup_tray(app: &AppHandle) -> TrayStatus {
+    let _ = app.remove_tray_by_id(TRAY_ID);
+    let result = detect_protocol().and_then(|protocol| {
+        build_tray(app)
+            .map(|_| protocol)
+            .map_err(|e| format!("Failed to create the tray icon: {}", e))
+    });
+    let status = match result {
+        Ok(protocol) => TrayStatus {
+            available: true,
+            protocol: Some(protocol),
+            reason: None,
+            status_text: String::new(),
+        },
+        Err(reason) => TrayStatus {
+            available: false,
+            protocol: None,
+            reason: Some(reason),
+            status_text: String::new(),
+        },
+    };
+    if let Ok(mut current) = TRAY_STATUS.lock() {
+        *current = Some(status.clone());
+    }
+    status
+}
+
+/// Retry creating the tray icon, emitting `tray:available` on success
+async fn retry_tray(app: &AppHandle, mut status: TrayStatus) -> TrayStatus {
+    for delay in RETRY_DELAYS {
+        tokio::time::sleep(delay).await;
+        status = try_setup_tray(app);
+        if status.available {
+            log::info!(
+                "[Tray] Tray icon created after retrying ({:?})",
+                status.protocol
+            );
+            let _ = app.emit("tray:available", &status);
+            break;
+        }
+    }
+    status
+}
+
+/// Set up the system tray, or report that there is none
+///
+/// Never fails the app setup: without a tray the window fallback takes over
+/// while creating the icon is retried.
+pub fn setup_tray(app: &AppHandle) {
+    let status = try_setup_tray(app);
+    if status.available {
+        log::info!("[Tray] Tray icon created ({:?})", status.protocol);
+        return;
+    }
+    log::error!(
+        "[Tray] No tray, using the window fallback: {}",
+        status.reason.as_deref().unwrap_or_default()
+    );
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        // Deferred so the main window's listeners are mounted first
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        let _ = app.emit("tray:unavailable", &status);
+        retry_tray(&app, status).await;
+    });
+}
+
+/// Rebuild the tray icon, retrying if that fails
+pub async fn reinitialize(app: &AppHandle) -> TrayStatus {
+    let status = try_setup_tray(app);
+    if status.available {
+        log::info!("[Tray] Tray icon recreated ({:?})", status.protocol);
+        let _ = app.emit("tray:available", &status);
+        return status;
+    }
+    log::error!(
+        "[Tray] Failed to recreate the tray icon: {}",
+        status.reason.as_deref().unwrap_or_default()
+    );
+    let _ = app.emit("tray:unavailable", &status);
+    retry_tray(app, status).await
+}
+
+/// Whether the tray icon exists
+fn tray_available() -> bool {
+    TRAY_STATUS
+        .lock()
+        .is_ok_and(|status| status.as_ref().is_some_and(|status| status.available))
+}
+
+/// Build the tray icon and its menu
+fn build_tray(app: &AppHandle) -> tauri::Result<()> {
     // Create tray menu
     let show_hide = MenuItem::with_id(app, ids::SHOW_HIDE, "Show Moltz", true, None::<&str>)?;
     let new_conv = MenuItem::with_id(
@@ -39,18 +271,29 @@ pub fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
         true,
         Some("CmdOrCtrl+Shift+Space"),
     )?;
+    let repair = MenuItem::with_id(app, ids::REPAIR, repair_label(None), false, None::<&str>)?;
     let quit = MenuItem::with_id(app, ids::QUIT, "Quit Moltz", true, Some("CmdOrCtrl+Q"))?;
 
-    let menu = Menu::with_items(app, &[&show_hide, &new_conv, &quick_ask, &quit])?;
+    let menu = Menu::with_items(app, &[&show_hide, &new_conv, &quick_ask, &repair, &quit])?;
+    if let Ok(mut item) = REPAIR_ITEM.lock() {
+        *item = Some(repair);
+    }
 
-    // Load tray icon (use app icon)
-    let icon = Image::from_bytes(include_bytes!("../icons/icon.png"))?;
+    // Load tray icon (use app icon; the accessibility monitor swaps in the
+    // high-contrast variant when needed)
+    let icon = tray_icon()?;
 
     // Build tray icon
-    let _tray = TrayIconBuilder::new()
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
         .icon(icon)
         .menu(&menu)
-        .tooltip("Moltz - Your AI Assistant")
+        .tooltip(status_tooltip(
+            &ConnectionState::Disconnected,
+            None,
+            0,
+            0,
+            None,
+        ))
         .show_menu_on_left_click(false)
         .on_menu_event(|app, event| {
             handle_tray_menu_event(app, event.id.as_ref());
@@ -75,9 +318,140 @@ pub fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
         })
         .build(app)?;
 
+    TRAY_REBUILT.store(true, Ordering::Relaxed);
     Ok(())
 }
 
+/// Days until expiry, as shown in the tray
+fn expiry_text(days: i64) -> String {
+    match days {
+        0 => "Token expires today".to_string(),
+        1 => "Token expires tomorrow".to_string(),
+        n => format!("Token expires in {} days", n),
+    }
+}
+
+/// Label of the re-pair menu item
+fn repair_label(token_days: Option<i64>) -> String {
+    match token_days {
+        Some(days) => format!("Re-pair Gateway ({})…", expiry_text(days).to_lowercase()),
+        None => "Re-pair Gateway…".to_string(),
+    }
+}
+
+/// Tooltip text for the current status
+fn status_tooltip(
+    state: &ConnectionState,
+    host: Option<&str>,
+    runs: usize,
+    unread: u32,
+    token_days: Option<i64>,
+) -> String {
+    format!(
+        "Moltz - {}",
+        status_text(state, host, runs, unread, token_days)
+    )
+}
+
+/// Current status (connection, runs, unread, token expiry)
+fn status_text(
+    state: &ConnectionState,
+    host: Option<&str>,
+    runs: usize,
+    unread: u32,
+    token_days: Option<i64>,
+) -> String {
+    let server = host.unwrap_or("Gateway");
+    let mut parts = vec![match state {
+        ConnectionState::Connected { .. } => format!("Connected to {}", server),
+        ConnectionState::Connecting => format!("Connecting to {}…", server),
+        ConnectionState::Reconnecting { attempt, .. } => {
+            format!("Reconnecting to {} (attempt {})", server, attempt)
+        }
+        ConnectionState::Disconnected => "Not connected".to_string(),
+        ConnectionState::Failed { .. } => format!("Can't reach {}", server),
+    }];
+    match runs {
+        0 => {}
+        1 => parts.push("1 run in progress".to_string()),
+        n => parts.push(format!("{} runs in progress", n)),
+    }
+    if unread > 0 {
+        parts.push(format!("{} unread", unread));
+    }
+    if let Some(days) = token_days {
+        parts.push(expiry_text(days));
+    }
+    parts.join(" · ")
+}
+
+/// Count a reply as unread unless the main window has focus
+pub fn note_reply(app: &AppHandle) {
+    let focused = app
+        .get_webview_window("main")
+        .and_then(|window| window.is_focused().ok())
+        .unwrap_or(false);
+    if !focused {
+        UNREAD.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Mark replies as read (the main window gained focus)
+pub fn clear_unread() {
+    UNREAD.store(0, Ordering::Relaxed);
+}
+
+/// Keep the tooltip in sync with the Gateway state, run registry, unread
+/// counter and token expiry
+///
+/// Without a tray, changes are emitted as `tray:status` for the window
+/// fallback instead.
+pub fn start_status_monitor(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut last = String::new();
+        let mut last_token_days = None;
+        loop {
+            let gateway = app.state::<GatewayState>();
+            let token_days = token_expiry::expiring_in_days(&app).await;
+            let text = status_text(
+                &gateway.connection_state().await,
+                gateway.gateway_host().await.as_deref(),
+                gateway.active_run_count().await,
+                UNREAD.load(Ordering::Relaxed),
+                token_days,
+            );
+            // A rebuilt icon starts with the default tooltip and menu
+            if TRAY_REBUILT.swap(false, Ordering::Relaxed) {
+                last.clear();
+                last_token_days = None;
+            }
+            if text != last {
+                if let Some(tray) = app.tray_by_id(TRAY_ID) {
+                    let _ = tray.set_tooltip(Some(&format!("Moltz - {}", text)));
+                }
+                if !tray_available() {
+                    let _ = app.emit("tray:status", &text);
+                }
+                if let Ok(mut status_text) = STATUS_TEXT.lock() {
+                    status_text.clone_from(&text);
+                }
+                last = text;
+            }
+            if token_days != last_token_days {
+                if let Ok(item) = REPAIR_ITEM.lock() {
+                    if let Some(repair) = item.as_ref() {
+                        let _ = repair.set_text(repair_label(token_days));
+                        let _ = repair.set_enabled(token_days.is_some());
+                    }
+                }
+                last_token_days = token_days;
+            }
+            tokio::time::sleep(STATUS_REFRESH_INTERVAL).await;
+        }
+    });
+}
+
 /// Handle tray menu events
 fn handle_tray_menu_event(app: &AppHandle, event_id: &str) {
     match event_id {
@@ -97,7 +471,6 @@ fn handle_tray_menu_event(app: &AppHandle, event_id: &str) {
                 let _ = window.show();
                 let _ = window.set_focus();
             }
-            use tauri::Emitter;
             let _ = app.emit("menu:new_conversation", ());
         }
         ids::QUICK_ASK => {
@@ -111,9 +484,84 @@ fn handle_tray_menu_event(app: &AppHandle, event_id: &str) {
                 }
             }
         }
+        ids::REPAIR => token_expiry::open_repair(app),
         ids::QUIT => {
-            std::process::exit(0);
+            // Exit through the event loop so ExitRequested cleanup runs
+            app.exit(0);
         }
         _ => {}
     }
 }
+
+/// Whether the tray is available, and the current status text
+#[tauri::command]
+pub async fn get_tray_status() -> Result<TrayStatus, CommandError> {
+    let current = TRAY_STATUS.lock().ok().and_then(|status| status.clone());
+    let mut status = current.unwrap_or(TrayStatus {
+        available: false,
+        protocol: None,
+        reason: Some("The tray is not set up on this platform".to_string()),
+        status_text: String::new(),
+    });
+    if let Ok(text) = STATUS_TEXT.lock() {
+        status.status_text.clone_from(&text);
+    }
+    Ok(status)
+}
+
+/// Run a tray menu action from the window fallback
+#[tauri::command]
+pub async fn run_tray_action(app: AppHandle, id: String) -> Result<(), CommandError> {
+    const ACTIONS: &[&str] = &[
+        ids::SHOW_HIDE,
+        ids::NEW_CONVERSATION,
+        ids::QUICK_ASK,
+        ids::REPAIR,
+        ids::QUIT,
+    ];
+    if !ACTIONS.contains(&id.as_str()) {
+        return Err(CommandError::validation(format!(
+            "Unknown tray action: {}",
+            id
+        )));
+    }
+    handle_tray_menu_event(&app, &id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_tooltip() {
+        let connected = ConnectionState::Connected { session_id: None };
+        assert_eq!(
+            status_tooltip(&connected, Some("den-server"), 1, 2, None),
+            "Moltz - Connected to den-server · 1 run in progress · 2 unread"
+        );
+        assert_eq!(
+            status_tooltip(&connected, Some("den-server"), 3, 0, None),
+            "Moltz - Connected to den-server · 3 runs in progress"
+        );
+        assert_eq!(
+            status_tooltip(&ConnectionState::Disconnected, None, 0, 0, None),
+            "Moltz - Not connected"
+        );
+        assert_eq!(
+            status_tooltip(&connected, Some("den-server"), 0, 0, Some(3)),
+            "Moltz - Connected to den-server · Token expires in 3 days"
+        );
+        assert_eq!(
+            repair_label(Some(1)),
+            "Re-pair Gateway (token expires tomorrow)…"
+        );
+    }
+
+    #[test]
+    fn test_parse_name_has_owner() {
+        assert_eq!(parse_name_has_owner("(true,)\n"), Some(true));
+        assert_eq!(parse_name_has_owner("(false,)"), Some(false));
+        assert_eq!(parse_name_has_owner("Error: no bus"), None);
+    }
+}
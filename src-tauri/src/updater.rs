@@ -6,7 +6,10 @@
 //! - Network reconnection detection
 //! - Non-intrusive update notifications
 //! - User consent before download/install
+//! - Background checks deferred while on battery
 
+use crate::error::CommandError;
+use crate::power;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Listener, Manager, Runtime};
@@ -27,18 +30,20 @@ pub struct UpdaterState {
     pub last_check: Arc<Mutex<Option<std::time::SystemTime>>>,
     pub pending_update: Arc<Mutex<Option<UpdateInfo>>>,
     pub is_checking: Arc<Mutex<bool>>,
+    /// A background check was skipped on battery and runs on AC power
+    pub deferred_check: Arc<Mutex<bool>>,
 }
 
 /// Check for updates without showing built-in dialog
 #[tauri::command]
-pub async fn check_for_updates<R: Runtime>(app: AppHandle<R>) -> Result<UpdateInfo, String> {
+pub async fn check_for_updates<R: Runtime>(app: AppHandle<R>) -> Result<UpdateInfo, CommandError> {
     let state = app.state::<UpdaterState>();
 
     // Prevent concurrent checks
     {
         let mut is_checking = state.is_checking.lock().await;
         if *is_checking {
-            return Err("Update check already in progress".to_string());
+            return Err(CommandError::validation("Update check already in progress"));
         }
         *is_checking = true;
     }
@@ -53,12 +58,19 @@ pub async fn check_for_updates<R: Runtime>(app: AppHandle<R>) -> Result<UpdateIn
 
 /// Download and install the update
 #[tauri::command]
-pub async fn install_update<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+pub async fn install_update<R: Runtime>(app: AppHandle<R>) -> Result<(), CommandError> {
     use tauri_plugin_updater::UpdaterExt;
 
-    let updater = app.updater_builder().build().map_err(|e| e.to_string())?;
+    let updater = app
+        .updater_builder()
+        .build()
+        .map_err(|e| CommandError::internal(e.to_string()))?;
 
-    if let Some(update) = updater.check().await.map_err(|e| e.to_string())? {
+    let update = updater
+        .check()
+        .await
+        .map_err(|e| CommandError::network(e.to_string()))?;
+    if let Some(update) = update {
         // Download and install
         update
             .download_and_install(
@@ -78,11 +90,11 @@ pub async fn install_update<R: Runtime>(app: AppHandle<R>) -> Result<(), String>
                 },
             )
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| CommandError::internal(e.to_string()))?;
 
         Ok(())
     } else {
-        Err("No update available".to_string())
+        Err(CommandError::validation("No update available"))
     }
 }
 
@@ -90,7 +102,7 @@ pub async fn install_update<R: Runtime>(app: AppHandle<R>) -> Result<(), String>
 #[tauri::command]
 pub async fn get_update_status<R: Runtime>(
     app: AppHandle<R>,
-) -> Result<Option<UpdateInfo>, String> {
+) -> Result<Option<UpdateInfo>, CommandError> {
     let state = app.state::<UpdaterState>();
     let pending = state.pending_update.lock().await;
     Ok(pending.clone())
@@ -98,14 +110,14 @@ pub async fn get_update_status<R: Runtime>(
 
 /// Clear pending update notification
 #[tauri::command]
-pub async fn dismiss_update<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+pub async fn dismiss_update<R: Runtime>(app: AppHandle<R>) -> Result<(), CommandError> {
     let state = app.state::<UpdaterState>();
     *state.pending_update.lock().await = None;
     Ok(())
 }
 
 /// Internal function to perform the actual update check
-async fn perform_update_check<R: Runtime>(app: &AppHandle<R>) -> Result<UpdateInfo, String> {
+async fn perform_update_check<R: Runtime>(app: &AppHandle<R>) -> Result<UpdateInfo, CommandError> {
     use tauri_plugin_updater::UpdaterExt;
 
     let current_version = app.package_info().version.to_string();
@@ -115,7 +127,7 @@ async fn perform_update_check<R: Runtime>(app: &AppHandle<R>) -> Result<UpdateIn
         Ok(u) => u,
         Err(e) => {
             // Updater not configured or disabled - this is fine
-            eprintln!("Updater not available: {}", e);
+            log::error!("Updater not available: {}", e);
             return Ok(UpdateInfo {
                 available: false,
                 version: current_version.clone(),
@@ -166,7 +178,7 @@ async fn perform_update_check<R: Runtime>(app: &AppHandle<R>) -> Result<UpdateIn
                 || error_msg.contains("not found") 
             {
                 // No releases published yet - this is expected for development
-                eprintln!("No releases found (expected during development): {}", e);
+                log::error!("No releases found (expected during development): {}", e);
                 return Ok(UpdateInfo {
                     available: false,
                     version: current_version.clone(),
@@ -176,8 +188,11 @@ async fn perform_update_check<R: Runtime>(app: &AppHandle<R>) -> Result<UpdateIn
                 });
             }
             
-            eprintln!("Update check failed: {}", e);
-            Err(format!("Failed to check for updates: {}", e))
+            log::error!("Update check failed: {}", e);
+            Err(CommandError::network(format!(
+                "Failed to check for updates: {}",
+                e
+            )))
         }
     }
 }
@@ -197,16 +212,55 @@ pub fn setup_periodic_checks<R: Runtime>(app: &AppHandle<R>) {
         loop {
             interval_timer.tick().await;
 
+            if defer_on_battery(&app_handle).await {
+                continue;
+            }
+
             // Perform update check
             if let Ok(info) = perform_update_check(&app_handle).await {
                 if info.available {
-                    println!("Update available: v{}", info.version);
+                    log::info!("Update available: v{}", info.version);
                 }
             }
         }
     });
 }
 
+/// Defer a background update check while on battery
+///
+/// Returns true when the check was deferred.
+async fn defer_on_battery<R: Runtime>(app: &AppHandle<R>) -> bool {
+    if !power::on_battery(app) {
+        return false;
+    }
+    *app.state::<UpdaterState>().deferred_check.lock().await = true;
+    true
+}
+
+/// Run a deferred update check once back on AC power
+pub fn setup_power_listener<R: Runtime>(app: &AppHandle<R>) {
+    let app_handle = app.clone();
+
+    let _ = app.listen("power:changed", move |event| {
+        let on_battery = serde_json::from_str::<serde_json::Value>(event.payload())
+            .ok()
+            .and_then(|v| v.get("onBattery").and_then(|b| b.as_bool()))
+            .unwrap_or(false);
+        if on_battery {
+            return;
+        }
+        let app = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let deferred =
+                std::mem::take(&mut *app.state::<UpdaterState>().deferred_check.lock().await);
+            if deferred {
+                log::info!("On AC power, running deferred update check...");
+                let _ = perform_update_check(&app).await;
+            }
+        });
+    });
+}
+
 /// Listen for network reconnection events from gateway
 pub fn setup_network_listener<R: Runtime>(app: &AppHandle<R>) {
     let app_handle = app.clone();
@@ -215,7 +269,10 @@ pub fn setup_network_listener<R: Runtime>(app: &AppHandle<R>) {
     let _ = app.listen("gateway:reconnected", move |_event| {
         let app = app_handle.clone();
         tauri::async_runtime::spawn(async move {
-            println!("Gateway reconnected, checking for updates...");
+            if defer_on_battery(&app).await {
+                return;
+            }
+            log::info!("Gateway reconnected, checking for updates...");
             let _ = perform_update_check(&app).await;
         });
     });
@@ -0,0 +1,134 @@
+//! Windows toasts that survive the app exiting
+//!
+//! Toasts posted through the notification plugin are only clickable while
+//! the app runs. Toasts that lead somewhere (a conversation, a reminder) are
+//! instead posted with protocol activation: clicking one launches its
+//! `moltz://` link, which starts the app if it has exited (the link is read
+//! from the launch arguments) or is forwarded to the running instance by the
+//! single-instance plugin. Either way it ends up in the deep-link routing.
+//!
+//! Protocol activation needs the `moltz:` scheme registered, which
+//! [`register_protocol`] does per user at startup, pointing at the current
+//! executable so it follows the app when it moves.
+
+use crate::deep_link::SCHEME;
+use tauri::AppHandle;
+use windows::core::HSTRING;
+use windows::Data::Xml::Dom::XmlDocument;
+use windows::UI::Notifications::{ToastNotification, ToastNotificationManager};
+
+/// Escape text for toast XML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Toast XML that launches `link` when clicked
+fn toast_xml(title: &str, body: &str, link: &str) -> String {
+    format!(
+        r#"<toast launch="{}" activationType="protocol"><visual><binding template="ToastGeneric"><text>{}</text><text>{}</text></binding></visual></toast>"#,
+        escape(link),
+        escape(title),
+        escape(body)
+    )
+}
+
+/// Show a toast that opens `link` when clicked, even after the app exited
+pub fn show(app: &AppHandle, title: &str, body: &str, link: &str) -> Result<(), String> {
+    let doc = XmlDocument::new().map_err(|e| e.to_string())?;
+    doc.LoadXml(&HSTRING::from(toast_xml(title, body, link)))
+        .map_err(|e| e.to_string())?;
+    let toast = ToastNotification::CreateToastNotification(&doc).map_err(|e| e.to_string())?;
+    // Same app ID the installer gives the Start menu shortcut
+    let app_id = HSTRING::from(app.config().identifier.as_str());
+    ToastNotificationManager::CreateToastNotifierWithId(&app_id)
+        .and_then(|notifier| notifier.Show(&toast))
+        .map_err(|e| e.to_string())
+}
+
+/// Set a string value under `HKEY_CURRENT_USER\<subkey>` (`None` name sets
+/// the default value)
+fn set_registry_value(subkey: &str, name: Option<&str>, value: &str) -> Result<(), String> {
+    use windows_sys::Win32::Foundation::ERROR_SUCCESS;
+    use windows_sys::Win32::System::Registry::{
+        RegCloseKey, RegCreateKeyExW, RegSetValueExW, HKEY, HKEY_CURRENT_USER, KEY_WRITE,
+        REG_OPTION_NON_VOLATILE, REG_SZ,
+    };
+
+    fn wide(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain(Some(0)).collect()
+    }
+
+    let subkey_w = wide(subkey);
+    let name_w = name.map(wide);
+    let value_w = wide(value);
+    let mut key: HKEY = std::ptr::null_mut();
+    // SAFETY: all strings are NUL-terminated UTF-16 buffers that outlive the
+    // calls, and `key` is closed once the value is written
+    unsafe {
+        let status = RegCreateKeyExW(
+            HKEY_CURRENT_USER,
+            subkey_w.as_ptr(),
+            0,
+            std::ptr::null(),
+            REG_OPTION_NON_VOLATILE,
+            KEY_WRITE,
+            std::ptr::null(),
+            &mut key,
+            std::ptr::null_mut(),
+        );
+        if status != ERROR_SUCCESS {
+            return Err(format!("Failed to open {} ({})", subkey, status));
+        }
+        let status = RegSetValueExW(
+            key,
+            name_w.as_ref().map_or(std::ptr::null(), |n| n.as_ptr()),
+            0,
+            REG_SZ,
+            value_w.as_ptr() as *const u8,
+            (value_w.len() * 2) as u32,
+        );
+        RegCloseKey(key);
+        if status != ERROR_SUCCESS {
+            return Err(format!("Failed to write {} ({})", subkey, status));
+        }
+    }
+    Ok(())
+}
+
+/// Register the `moltz:` scheme for the current user
+pub fn register_protocol() {
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            log::error!("[Toast] Cannot resolve the executable: {}", e);
+            return;
+        }
+    };
+    let key = format!("Software\\Classes\\{}", SCHEME);
+    let command = format!("\"{}\" \"%1\"", exe.display());
+    let result = set_registry_value(&key, None, "URL:Moltz")
+        .and_then(|_| set_registry_value(&key, Some("URL Protocol"), ""))
+        .and_then(|_| {
+            set_registry_value(&format!("{}\\shell\\open\\command", key), None, &command)
+        });
+    if let Err(e) = result {
+        log::error!("[Toast] Failed to register the {} scheme: {}", SCHEME, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toast_xml_escapes_and_launches_link() {
+        let xml = toast_xml("Q & A", "<b>", "moltz://open?conversation=c1&message=m1");
+        assert!(xml.contains(r#"launch="moltz://open?conversation=c1&amp;message=m1""#));
+        assert!(xml.contains(r#"activationType="protocol""#));
+        assert!(xml.contains("<text>Q &amp; A</text><text>&lt;b&gt;</text>"));
+    }
+}
@@ -0,0 +1,414 @@
+//! Browse and download files from agent workspaces on the Gateway
+//!
+//! Agent runs can leave artifacts (reports, generated code, images) in the
+//! session's workspace on the Gateway host. These commands wrap the
+//! Gateway's file methods:
+//! - `files.list { sessionKey, path }` → `{ entries: [{ name, path, type,
+//!   size, modifiedAt }] }` (`type` is `"file"` or `"directory"`)
+//! - `files.read { sessionKey, path, offset, length }` → `{ data, size }`
+//!   where `data` is base64 and `size` the total file size
+//!
+//! Downloads read the file in [`CHUNK_SIZE`] pieces, stream them to a
+//! `.part` file next to the destination and emit
+//! `files:download_progress` after each chunk. The file is renamed into
+//! place once complete, so a cancelled or failed download never leaves a
+//! truncated file behind.
+
+use crate::error::CommandError;
+use crate::gateway::GatewayState;
+use crate::guest;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, State};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// Bytes requested per `files.read` call
+const CHUNK_SIZE: u64 = 256 * 1024;
+
+/// Largest file returned inline by `read_workspace_file`
+const MAX_READ_BYTES: u64 = 2 * 1024 * 1024;
+
+/// A file or directory in an agent workspace
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct WorkspaceEntry {
+    pub name: String,
+    /// Path relative to the workspace root, as the Gateway reports it
+    pub path: String,
+    #[serde(rename = "isDir")]
+    pub is_dir: bool,
+    /// Size in bytes (files only)
+    pub size: Option<u64>,
+    #[serde(rename = "modifiedAt")]
+    pub modified_at: Option<DateTime<Utc>>,
+}
+
+/// Contents of a workspace file read inline
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceFile {
+    pub path: String,
+    pub size: u64,
+    /// UTF-8 text, or `None` for binary files (see `data`)
+    pub text: Option<String>,
+    /// Base64 contents of binary files
+    pub data: Option<String>,
+}
+
+/// Payload of `files:download_progress`
+#[derive(Debug, Clone, Serialize)]
+struct DownloadProgress<'a> {
+    #[serde(rename = "downloadId")]
+    download_id: &'a str,
+    path: &'a str,
+    received: u64,
+    total: Option<u64>,
+}
+
+/// Result of a completed download
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadedFile {
+    /// Local path the file was saved to
+    pub destination: String,
+    pub size: u64,
+}
+
+/// Downloads in progress, by frontend-supplied id
+#[derive(Default)]
+pub struct WorkspaceFilesState {
+    active: Mutex<HashSet<String>>,
+}
+
+/// Parse a `files.list` response, directories first then by name
+fn parse_entries(payload: &serde_json::Value) -> Vec<WorkspaceEntry> {
+    let mut entries: Vec<WorkspaceEntry> = payload
+        .get("entries")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let path = item.get("path").and_then(|v| v.as_str())?.to_string();
+                    let name = item
+                        .get("name")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string)
+                        .unwrap_or_else(|| remote_file_name(&path).to_string());
+                    Some(WorkspaceEntry {
+                        name,
+                        is_dir: item.get("type").and_then(|v| v.as_str()) == Some("directory"),
+                        size: item.get("size").and_then(|v| v.as_u64()),
+                        modified_at: item
+                            .get("modifiedAt")
+                            .and_then(|v| v.as_str())
+                            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                            .map(|dt| dt.with_timezone(&Utc)),
+                        path,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    entries.sort_by(|a, b| {
+        b.is_dir
+            .cmp(&a.is_dir)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    entries
+}
+
+/// Last component of a Gateway path (always `/`-separated)
+fn remote_file_name(path: &str) -> &str {
+    path.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(path)
+}
+
+/// Local file name for a Gateway path, safe on every OS
+fn local_file_name(path: &str) -> String {
+    let name: String = remote_file_name(path)
+        .chars()
+        .map(|c| match c {
+            '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let name = name.trim_matches(|c: char| c == '.' || c.is_whitespace());
+    if name.is_empty() {
+        "download".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// `dir/name`, or `dir/name (1)`, `dir/name (2)`, ... if it already exists
+fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    let candidate = dir.join(name);
+    if !candidate.exists() {
+        return candidate;
+    }
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, Some(ext)),
+        _ => (name, None),
+    };
+    (1..)
+        .map(|n| match ext {
+            Some(ext) => dir.join(format!("{} ({}).{}", stem, n, ext)),
+            None => dir.join(format!("{} ({})", stem, n)),
+        })
+        .find(|path| !path.exists())
+        .expect("unbounded range")
+}
+
+/// Read one chunk: returns the bytes and the total size, if reported
+async fn read_chunk(
+    gateway: &GatewayState,
+    session_key: &str,
+    path: &str,
+    offset: u64,
+    length: u64,
+) -> Result<(Vec<u8>, Option<u64>), CommandError> {
+    let payload = gateway
+        .request(
+            "files.read",
+            Some(serde_json::json!({
+                "sessionKey": session_key,
+                "path": path,
+                "offset": offset,
+                "length": length,
+            })),
+        )
+        .await?
+        .unwrap_or_default();
+    let data = payload
+        .get("data")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| CommandError::protocol("File read response is missing data"))?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| CommandError::protocol(format!("Invalid file data: {}", e)))?;
+    Ok((bytes, payload.get("size").and_then(|v| v.as_u64())))
+}
+
+/// List a directory of the session's workspace (the root when `path` is
+/// omitted)
+#[tauri::command]
+pub async fn list_workspace_files(
+    gateway: State<'_, GatewayState>,
+    session_key: String,
+    path: Option<String>,
+) -> Result<Vec<WorkspaceEntry>, CommandError> {
+    let payload = gateway
+        .request(
+            "files.list",
+            Some(serde_json::json!({
+                "sessionKey": session_key,
+                "path": path.unwrap_or_default(),
+            })),
+        )
+        .await?
+        .unwrap_or_default();
+    Ok(parse_entries(&payload))
+}
+
+/// Read a workspace file inline, for previews (up to [`MAX_READ_BYTES`])
+#[tauri::command]
+pub async fn read_workspace_file(
+    gateway: State<'_, GatewayState>,
+    session_key: String,
+    path: String,
+) -> Result<WorkspaceFile, CommandError> {
+    let (bytes, size) = read_chunk(&gateway, &session_key, &path, 0, MAX_READ_BYTES + 1).await?;
+    let size = size.unwrap_or(bytes.len() as u64);
+    if size > MAX_READ_BYTES || bytes.len() as u64 > MAX_READ_BYTES {
+        return Err(CommandError::validation(
+            "File is too large to preview; download it instead",
+        ));
+    }
+    let (text, data) = match String::from_utf8(bytes) {
+        Ok(text) => (Some(text), None),
+        Err(e) => (
+            None,
+            Some(base64::engine::general_purpose::STANDARD.encode(e.into_bytes())),
+        ),
+    };
+    Ok(WorkspaceFile {
+        path,
+        size,
+        text,
+        data,
+    })
+}
+
+/// Download a workspace file to disk
+///
+/// Saves into `destination_dir` (the Downloads folder by default) without
+/// overwriting existing files. Progress is emitted as
+/// `files:download_progress` with the given `download_id`.
+#[tauri::command]
+pub async fn download_workspace_file(
+    app: AppHandle,
+    state: State<'_, WorkspaceFilesState>,
+    gateway: State<'_, GatewayState>,
+    download_id: String,
+    session_key: String,
+    path: String,
+    destination_dir: Option<String>,
+) -> Result<DownloadedFile, CommandError> {
+    guest::ensure_inactive("Downloading files")?;
+    let dir = match destination_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => dirs::download_dir()
+            .ok_or_else(|| CommandError::internal("Could not find the Downloads folder"))?,
+    };
+    if !dir.is_dir() {
+        return Err(CommandError::validation(format!(
+            "{} is not a folder",
+            dir.display()
+        )));
+    }
+
+    if !state.active.lock().await.insert(download_id.clone()) {
+        return Err(CommandError::validation("Download is already running"));
+    }
+    let destination = unique_path(&dir, &local_file_name(&path));
+    let partial = destination.with_file_name(format!(
+        "{}.part",
+        destination
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    ));
+
+    let result = stream_to_file(
+        &app,
+        &state,
+        &gateway,
+        &download_id,
+        &session_key,
+        &path,
+        &partial,
+    )
+    .await;
+    state.active.lock().await.remove(&download_id);
+
+    match result {
+        Ok(size) => {
+            tokio::fs::rename(&partial, &destination)
+                .await
+                .map_err(|e| CommandError::internal(format!("Failed to save file: {}", e)))?;
+            Ok(DownloadedFile {
+                destination: destination.to_string_lossy().into_owned(),
+                size,
+            })
+        }
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&partial).await;
+            log::error!("[WorkspaceFiles] Download of {} failed: {}", path, e);
+            Err(e)
+        }
+    }
+}
+
+/// Stream a remote file into `partial`, returning the bytes written
+async fn stream_to_file(
+    app: &AppHandle,
+    state: &WorkspaceFilesState,
+    gateway: &GatewayState,
+    download_id: &str,
+    session_key: &str,
+    path: &str,
+    partial: &Path,
+) -> Result<u64, CommandError> {
+    let mut file = tokio::fs::File::create(partial)
+        .await
+        .map_err(|e| CommandError::internal(format!("Failed to create file: {}", e)))?;
+    let mut received: u64 = 0;
+    loop {
+        if !state.active.lock().await.contains(download_id) {
+            return Err(CommandError::validation("Download cancelled"));
+        }
+        let (bytes, total) = read_chunk(gateway, session_key, path, received, CHUNK_SIZE).await?;
+        file.write_all(&bytes)
+            .await
+            .map_err(|e| CommandError::internal(format!("Failed to write file: {}", e)))?;
+        received += bytes.len() as u64;
+        let _ = app.emit(
+            "files:download_progress",
+            DownloadProgress {
+                download_id,
+                path,
+                received,
+                total,
+            },
+        );
+        let done = match total {
+            Some(total) => received >= total,
+            None => (bytes.len() as u64) < CHUNK_SIZE,
+        };
+        if done || bytes.is_empty() {
+            break;
+        }
+    }
+    file.flush()
+        .await
+        .map_err(|e| CommandError::internal(format!("Failed to write file: {}", e)))?;
+    Ok(received)
+}
+
+/// Stop a download in progress; its partial file is removed
+#[tauri::command]
+pub async fn cancel_workspace_download(
+    state: State<'_, WorkspaceFilesState>,
+    download_id: String,
+) -> Result<(), CommandError> {
+    state.active.lock().await.remove(&download_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entries_sorts_directories_first() {
+        let payload = serde_json::json!({
+            "entries": [
+                { "name": "report.md", "path": "out/report.md", "type": "file", "size": 42,
+                  "modifiedAt": "2026-03-01T10:00:00Z" },
+                { "path": "out/charts", "type": "directory" },
+                { "name": "missing-path" },
+            ]
+        });
+        let entries = parse_entries(&payload);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "charts");
+        assert!(entries[0].is_dir);
+        assert_eq!(entries[1].size, Some(42));
+        assert!(entries[1].modified_at.is_some());
+    }
+
+    #[test]
+    fn test_local_file_name() {
+        assert_eq!(local_file_name("out/report.md"), "report.md");
+        assert_eq!(local_file_name("a/b:c?.txt"), "b_c_.txt");
+        assert_eq!(local_file_name("dir/"), "dir");
+        assert_eq!(local_file_name(".."), "download");
+    }
+
+    #[test]
+    fn test_unique_path_does_not_overwrite() {
+        let dir = std::env::temp_dir().join(format!("moltz-files-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(unique_path(&dir, "a.txt"), dir.join("a.txt"));
+        std::fs::write(dir.join("a.txt"), "").unwrap();
+        assert_eq!(unique_path(&dir, "a.txt"), dir.join("a (1).txt"));
+        std::fs::write(dir.join("README"), "").unwrap();
+        assert_eq!(unique_path(&dir, "README"), dir.join("README (1)"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
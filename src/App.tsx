@@ -1,9 +1,36 @@
 ﻿import { useEffect, useState, useRef, lazy, Suspense } from "react";
 import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { invoke } from "@tauri-apps/api/core";
 import { register, unregister } from "@tauri-apps/plugin-global-shortcut";
 import { Window } from "@tauri-apps/api/window";
 import { OnboardingFlow } from "./components/onboarding/OnboardingFlow";
+import {
+  applyAccessibilityPrefs,
+  getAccessibilityPrefs,
+  type AccessibilityPrefs,
+} from "./lib/accessibility";
+import type { QuotaWarning } from "./lib/quota";
+import { emailConversation } from "./lib/email";
+import {
+  copyAnswerToClipboard,
+  openConversationAt,
+  type DeepLinkAsk,
+  type DeepLinkOpen,
+} from "./lib/deepLink";
+import type { QuickAsk } from "./lib/quickAsk";
+import { Announcer } from "./components/Announcer";
+import { GuestModeBanner } from "./components/GuestMode";
+import { IncidentNotice } from "./components/IncidentNotice";
+import { RecoveryNotice } from "./components/RecoveryNotice";
+import { TokenExpiryNotice } from "./components/TokenExpiryNotice";
+import { TrayFallback } from "./components/TrayFallback";
+import {
+  getShortcutStatus,
+  reportShortcutStatus,
+  SHORTCUT_LABELS,
+  type ShortcutStatus,
+} from "./lib/shortcuts";
 import { UpdateNotification } from "./components/UpdateNotification";
 import { useStore, type ModelInfo } from "./stores/store";
 import { useShallow } from "zustand/react/shallow";
@@ -11,7 +38,15 @@ import { cn } from "./lib/utils";
 import { ToastContainer, useToast } from "./components/ui/toast";
 import { Spinner } from "./components/ui/spinner";
 import { loadPersistedData } from "./lib/persistence";
-import { translateError, getErrorTitle } from "./lib/errors";
+import {
+  translateError,
+  getErrorTitle,
+  errorCode,
+  errorMessage,
+  type CommandError,
+} from "./lib/errors";
+import { openReminderTarget, type ReminderTarget } from "./lib/reminders";
+import { getCompletedMessage, type RunComplete } from "./lib/messageStore";
 
 // Lazy load main app components for better initial load time
 // These will be preloaded during onboarding
@@ -36,6 +71,7 @@ const CommandPalette = lazy(() =>
   })),
 );
 import { ModelSelector } from "./components/ModelSelector";
+import { AgentSelector } from "./components/AgentSelector";
 
 // Check if running on macOS (for traffic light padding)
 const isMacOS =
@@ -134,7 +170,7 @@ function AppContent() {
   const [showCommandPalette, setShowCommandPalette] = useState(false);
   // Note: showSearchDialog planned for future use
   const [, setShowSearchDialog] = useState(false);
-  const { toasts, dismissToast, showError, showSuccess } = useToast();
+  const { toasts, dismissToast, showError, showSuccess, showInfo } = useToast();
   // PERF: Use selective subscriptions with shallow equality to prevent unnecessary re-renders
   const {
     currentConversationId,
@@ -178,6 +214,8 @@ function AppContent() {
   const connectionCancelledRef = useRef(false);
   // Track the URL we're currently connecting with to prevent re-triggers
   const lastAttemptedUrlRef = useRef<string | null>(null);
+  // Conversation started by a moltz://ask link whose answer goes to the clipboard
+  const copyAnswerConversationRef = useRef<string | null>(null);
 
   // Load persisted data and validate onboarding status
   useEffect(() => {
@@ -292,6 +330,19 @@ function AppContent() {
     return () => mediaQuery.removeEventListener("change", handleChange);
   }, [settings.theme]);
 
+  // Apply OS accessibility preferences (motion, contrast, text size)
+  useEffect(() => {
+    getAccessibilityPrefs()
+      .then((prefs) => prefs && applyAccessibilityPrefs(prefs))
+      .catch(() => {});
+    const unlisten = listen<AccessibilityPrefs>("accessibility:changed", (event) =>
+      applyAccessibilityPrefs(event.payload),
+    );
+    return () => {
+      unlisten.then((fn) => fn()).catch(() => {});
+    };
+  }, []);
+
   // Keyboard shortcuts
   useEffect(() => {
     const handleKeyDown = (e: KeyboardEvent) => {
@@ -464,16 +515,16 @@ function AppContent() {
           return;
         }
 
-        const errorMessage =
-          typeof err === "string" ? err : "Connection failed";
-        setConnectionError(errorMessage);
+        const message = errorMessage(err) || "Connection failed";
+        setConnectionError(message);
         setIsConnecting(false);
         setCancelConnection(null);
         connectingFlag = false;
 
         // Detect auth errors and auto-open settings
-        const lowerError = errorMessage.toLowerCase();
+        const lowerError = message.toLowerCase();
         if (
+          errorCode(err) === "auth" ||
           lowerError.includes("unauthorized") ||
           lowerError.includes("authentication") ||
           lowerError.includes("token") ||
@@ -556,6 +607,10 @@ function AppContent() {
       if (countdownInterval) clearInterval(countdownInterval);
     };
 
+    // Run events are emitted only to the window that started the run, so
+    // listen on this window rather than app-wide
+    const appWindow = getCurrentWebviewWindow();
+
     const unlisten = Promise.all([
       listen("gateway:connected", async () => {
         if (!eventListenerMounted) return;
@@ -645,9 +700,7 @@ function AppContent() {
           } catch (err) {
             if (!eventListenerMounted) return;
             console.error("Reconnection failed:", err);
-            const errorMessage =
-              typeof err === "string" ? err : "Reconnection failed";
-            setConnectionError(errorMessage);
+            setConnectionError(errorMessage(err) || "Reconnection failed");
             setIsConnecting(false);
             // Schedule next retry with backoff
             disconnectAttempts++;
@@ -695,19 +748,41 @@ function AppContent() {
           }
         }, 1000);
       }),
-      listen<string>("gateway:stream", (event) => {
+      appWindow.listen<string>("gateway:stream", (event) => {
         if (!eventListenerMounted) return;
         appendToCurrentMessage(event.payload);
       }),
-      listen<{
-        usage?: { input?: number; output?: number; totalTokens?: number };
-        stopReason?: string;
-      }>("gateway:complete", (event) => {
+      appWindow.listen<RunComplete>("gateway:complete", async (event) => {
+        if (!eventListenerMounted) return;
+        // The stored final message covers deltas this window missed
+        const messageId = event.payload?.messageId;
+        const stored = messageId
+          ? await getCompletedMessage(messageId).catch(() => null)
+          : null;
         if (!eventListenerMounted) return;
-        completeCurrentMessage(event.payload?.usage);
+        completeCurrentMessage(event.payload?.usage, stored?.content);
+
+        const copyConversationId = copyAnswerConversationRef.current;
+        const { currentConversation } = useStore.getState();
+        if (
+          copyConversationId &&
+          currentConversation?.id === copyConversationId
+        ) {
+          copyAnswerConversationRef.current = null;
+          const answer = [...currentConversation.messages]
+            .reverse()
+            .find((m) => m.role === "assistant")?.content;
+          if (answer) {
+            copyAnswerToClipboard(answer)
+              .then(() => showSuccess("Answer copied to clipboard"))
+              .catch((err) =>
+                showError(`Failed to copy answer: ${errorMessage(err)}`),
+              );
+          }
+        }
       }),
       // P1: Handle streaming errors and timeouts gracefully
-      listen<{ runId: string; timeoutSecs: number }>(
+      appWindow.listen<{ runId: string; timeoutSecs: number }>(
         "gateway:stream_timeout",
         (event) => {
           if (!eventListenerMounted) return;
@@ -732,18 +807,19 @@ function AppContent() {
           );
         },
       ),
-      listen<string>("gateway:error", (event) => {
+      appWindow.listen<CommandError>("gateway:error", (event) => {
         if (!eventListenerMounted) return;
         console.error("Gateway error during streaming:", event.payload);
+        const message = errorMessage(event.payload);
         // Complete current message if streaming
         const { currentStreamingMessageId } = useStore.getState();
         if (currentStreamingMessageId) {
-          appendToCurrentMessage(`\n\n⚠️ *Error: ${event.payload}*`);
+          appendToCurrentMessage(`\n\n⚠️ *Error: ${message}*`);
           completeCurrentMessage();
         }
-        showError(event.payload);
+        showError(message);
       }),
-      listen("gateway:aborted", () => {
+      appWindow.listen("gateway:aborted", () => {
         if (!eventListenerMounted) return;
         // Stream aborted by user - silent cleanup
         // Complete current message cleanly
@@ -753,9 +829,9 @@ function AppContent() {
         }
       }),
       // Listen for quick input submissions
-      listen<{ message: string }>("quickinput:submit", (event) => {
+      listen<DeepLinkAsk>("quickinput:submit", (event) => {
         if (!eventListenerMounted) return;
-        const { message } = event.payload;
+        const { message, copyAnswer } = event.payload;
         if (message) {
           // Create a new conversation with the message from quick input
           const { createConversation, selectConversation } =
@@ -764,6 +840,7 @@ function AppContent() {
           // Create new conversation
           const newConv = createConversation();
           selectConversation(newConv.id);
+          copyAnswerConversationRef.current = copyAnswer ? newConv.id : null;
 
           // Add the user message - the ChatView will detect and send it
           // We use a small delay to ensure the conversation is set
@@ -775,6 +852,37 @@ function AppContent() {
           }, 100);
         }
       }),
+      // Open a background Quick Ask answer as a conversation on its session
+      listen<QuickAsk>("quickask:open", (event) => {
+        if (!eventListenerMounted) return;
+        const ask = event.payload;
+        const {
+          conversations,
+          createConversation,
+          selectConversation,
+          addMessage,
+        } = useStore.getState();
+        if (conversations.some((c) => c.id === ask.sessionKey)) {
+          selectConversation(ask.sessionKey);
+          return;
+        }
+        createConversation(ask.sessionKey);
+        addMessage(ask.sessionKey, { role: "user", content: ask.message });
+        if (ask.answer) {
+          addMessage(ask.sessionKey, {
+            role: "assistant",
+            content: ask.answer,
+          });
+        } else if (ask.error) {
+          addMessage(ask.sessionKey, { role: "system", content: ask.error });
+        }
+      }),
+      // moltz://open links (Windows toasts clicked, also after a restart)
+      listen<DeepLinkOpen>("deeplink:open", (event) => {
+        if (!eventListenerMounted) return;
+        const { conversationId, messageId } = event.payload;
+        openConversationAt(conversationId, messageId);
+      }),
       // Menu event listeners
       listen("menu:new_conversation", () => {
         if (!eventListenerMounted) return;
@@ -811,30 +919,136 @@ function AppContent() {
         // Trigger export dialog
         window.dispatchEvent(new CustomEvent("menu:open_export"));
       }),
+      listen("menu:email", () => {
+        if (!eventListenerMounted) return;
+        const { conversations, currentConversationId } = useStore.getState();
+        const conversation = conversations.find(
+          (c) => c.id === currentConversationId,
+        );
+        if (!conversation) return;
+        emailConversation(conversation)
+          .then((draft) => {
+            if (draft?.truncated) {
+              showInfo("Conversation shortened to fit the email draft");
+            }
+          })
+          .catch((err) =>
+            showError(`Failed to open email draft: ${errorMessage(err)}`),
+          );
+      }),
+      // Reminders: open the conversation when the notification is clicked,
+      // and show fired reminders in-app (not every platform reports clicks)
+      listen<ReminderTarget>("reminder:open", (event) => {
+        if (!eventListenerMounted) return;
+        openReminderTarget(event.payload);
+      }),
+      listen<QuotaWarning>("gateway:quota_warning", (event) => {
+        if (!eventListenerMounted) return;
+        const { limit, threshold } = event.payload;
+        const message =
+          threshold >= 100
+            ? `${limit.name} limit reached`
+            : `${limit.name} is at ${threshold}% of its limit`;
+        (threshold >= 100 ? showError : showInfo)(message);
+      }),
+      // The old token is revoked, so later reconnects need the new one
+      listen("gateway:token_rotated", () => {
+        useStore
+          .getState()
+          .reloadGatewayToken()
+          .catch((err) => {
+            console.error("Failed to reload the rotated token:", err);
+          });
+      }),
+      listen<ReminderTarget>("reminder:due", (event) => {
+        if (!eventListenerMounted) return;
+        const conversation = useStore
+          .getState()
+          .conversations.find((c) => c.id === event.payload.sessionKey);
+        showInfo(
+          `Reminder: ${event.payload.note || conversation?.title || "follow up on an answer"}`,
+          10000,
+        );
+      }),
     ]);
 
     // Register global shortcut for quick input (Cmd/Ctrl+Shift+Space)
     const shortcut = navigator.platform.includes("Mac")
       ? "Command+Shift+Space"
       : "Control+Shift+Space";
-    register(shortcut, async () => {
-      const quickInputWindow = new Window("quickinput");
-      const isVisible = await quickInputWindow.isVisible();
-      if (isVisible) {
-        await quickInputWindow.hide();
-      } else {
-        await quickInputWindow.show();
-        await quickInputWindow.setFocus();
-      }
-    }).catch((err) => {
-      console.error("Failed to register global shortcut:", err);
+    // A registration left by an earlier page load is dropped first
+    const registerQuickAsk = () =>
+      unregister(shortcut)
+        .catch(() => {})
+        .then(() =>
+          register(shortcut, async () => {
+            const quickInputWindow = new Window("quickinput");
+            const isVisible = await quickInputWindow.isVisible();
+            if (isVisible) {
+              await quickInputWindow.hide();
+            } else {
+              await quickInputWindow.show();
+              await quickInputWindow.setFocus();
+            }
+          }),
+        )
+        .then(() => reportShortcutStatus("quick_ask", true))
+        .catch((err) => {
+          console.error("Failed to register global shortcut:", err);
+          reportShortcutStatus("quick_ask", false, errorMessage(err)).catch(
+            () => {},
+          );
+        });
+    void registerQuickAsk();
+    // Asked by the backend when re-registering tray and shortcuts
+    const unlistenReregister = listen("shortcuts:reregister", () => {
+      if (!eventListenerMounted) return;
+      void registerQuickAsk();
     });
 
+    // Once the desktop portal binds Quick Ask (Wayland) it owns the toggle;
+    // warn once about shortcuts that won't fire
+    const warnedShortcuts = new Set<string>();
+    const handleShortcutStatuses = (statuses: ShortcutStatus[]) => {
+      for (const status of statuses) {
+        if (
+          status.id === "quick_ask" &&
+          status.backend === "portal" &&
+          status.registered
+        ) {
+          unregister(shortcut).catch(() => {});
+        }
+        if (status.registered && !status.warning) {
+          // Warn again if it fails after re-registering
+          warnedShortcuts.delete(status.id);
+          continue;
+        }
+        if (warnedShortcuts.has(status.id)) continue;
+        warnedShortcuts.add(status.id);
+        showInfo(
+          `${SHORTCUT_LABELS[status.id]} shortcut may not work: ${status.warning ?? "not registered"}`,
+          10000,
+        );
+      }
+    };
+    const unlistenShortcuts = listen<ShortcutStatus[]>(
+      "shortcuts:status",
+      (event) => {
+        if (!eventListenerMounted) return;
+        handleShortcutStatuses(event.payload);
+      },
+    );
+    getShortcutStatus()
+      .then(handleShortcutStatuses)
+      .catch((err) => console.error("Failed to get shortcut status:", err));
+
     return () => {
       eventListenerMounted = false;
       clearTimers();
       // Unregister global shortcut
       unregister(shortcut).catch(() => {});
+      unlistenShortcuts.then((fn) => fn());
+      unlistenReregister.then((fn) => fn());
       // Safely clean up event listeners (guard against double-cleanup in React Strict Mode)
       unlisten
         .then((listeners) => {
@@ -858,6 +1072,7 @@ function AppContent() {
     showSuccess,
     retryQueuedMessages,
     showError,
+    showInfo,
   ]);
 
   // Preload heavy components during onboarding for smooth transition
@@ -911,9 +1126,15 @@ function AppContent() {
   return (
     <>
       <ToastContainer toasts={toasts} onDismiss={dismissToast} />
+      <Announcer />
       <UpdateNotification
         onUpdateDismissed={() => setHasUpdateDismissed(true)}
       />
+      <TokenExpiryNotice />
+      <RecoveryNotice />
+      <IncidentNotice />
+      <GuestModeBanner />
+      <TrayFallback />
       {/* Skip to main content link for keyboard navigation */}
       <a
         href="#main-content"
@@ -1085,7 +1306,10 @@ function AppContent() {
             <div className="flex items-center gap-3" data-tauri-drag-region>
               {/* Model selector - only show when connected and has a conversation */}
               {connected && currentConversation && (
-                <ModelSelector compact={settings.compactMode} />
+                <>
+                  <AgentSelector compact={settings.compactMode} />
+                  <ModelSelector compact={settings.compactMode} />
+                </>
               )}
 
               {/* Command palette hint */}
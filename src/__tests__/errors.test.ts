@@ -4,6 +4,9 @@ import {
   formatErrorForDisplay,
   getErrorTitle,
   logError,
+  errorCode,
+  errorMessage,
+  type CommandError,
 } from "../lib/errors";
 
 describe("Error Translation", () => {
@@ -441,4 +444,30 @@ describe("Error Translation", () => {
       );
     });
   });
+
+  describe("Backend command errors", () => {
+    const notConnected: CommandError = {
+      code: "network",
+      message: "Not connected to Gateway",
+      retryable: true,
+    };
+
+    it("should read message and code from command errors", () => {
+      expect(errorMessage(notConnected)).toBe("Not connected to Gateway");
+      expect(errorCode(notConnected)).toBe("network");
+    });
+
+    it("should fall back for plain errors", () => {
+      expect(errorMessage("boom")).toBe("boom");
+      expect(errorMessage(new Error("boom"))).toBe("boom");
+      expect(errorCode("boom")).toBeUndefined();
+    });
+
+    it("should translate command errors by message", () => {
+      expect(
+        translateError({ ...notConnected, message: "Connection refused" })
+          .title,
+      ).toBe("Can't connect");
+    });
+  });
 });
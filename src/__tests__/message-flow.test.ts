@@ -202,6 +202,29 @@ describe("Message Flow", () => {
       expect(updatedConv.messages[0].usage!.totalTokens).toBe(170);
     });
 
+    it("should replace streamed content with the stored final message", () => {
+      const store = useStore.getState();
+      const conv = store.createConversation();
+
+      store.addMessage(conv.id, {
+        role: "assistant",
+        content: "",
+        isStreaming: true,
+      });
+
+      // Deltas missed while the window reloaded
+      store.appendToCurrentMessage("tail of the reply");
+      store.completeCurrentMessage(undefined, "Full reply, tail of the reply");
+
+      const updatedConv = useStore
+        .getState()
+        .conversations.find((c) => c.id === conv.id)!;
+      expect(updatedConv.messages[0].content).toBe(
+        "Full reply, tail of the reply",
+      );
+      expect(updatedConv.messages[0].isStreaming).toBe(false);
+    });
+
     it("should handle appending to non-existent streaming message gracefully", () => {
       const store = useStore.getState();
 
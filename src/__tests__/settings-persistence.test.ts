@@ -54,6 +54,17 @@ vi.mock("@tauri-apps/api/core", () => ({
       delete keychainStore[params?.key || ""];
       return Promise.resolve();
     }
+    if (command === "connect_and_save") {
+      if (params?.url?.includes("unreachable")) {
+        return Promise.reject(new Error("Gateway unreachable"));
+      }
+      keychainStore["gateway_token"] = params?.token || "";
+      return Promise.resolve({
+        success: true,
+        used_url: params?.url,
+        protocol_switched: false,
+      });
+    }
     return Promise.reject(new Error("Unknown command"));
   }),
 }));
@@ -152,6 +163,34 @@ describe("Settings Persistence", () => {
       // Token should be removed from keychain
       expect(keychainStore["gateway_token"]).toBeUndefined();
     });
+
+    it("should save Gateway credentials once connected", async () => {
+      await useStore
+        .getState()
+        .connectAndSaveGateway("ws://gateway:18789", "new-token");
+
+      const settings = useStore.getState().settings;
+      expect(settings.gatewayUrl).toBe("ws://gateway:18789");
+      expect(settings.gatewayToken).toBe("new-token");
+      expect(keychainStore["gateway_token"]).toBe("new-token");
+    });
+
+    it("should keep the saved Gateway when the new one fails", async () => {
+      await useStore
+        .getState()
+        .connectAndSaveGateway("ws://gateway:18789", "old-token");
+
+      await expect(
+        useStore
+          .getState()
+          .connectAndSaveGateway("ws://unreachable:18789", "new-token"),
+      ).rejects.toThrow("Gateway unreachable");
+
+      const settings = useStore.getState().settings;
+      expect(settings.gatewayUrl).toBe("ws://gateway:18789");
+      expect(settings.gatewayToken).toBe("old-token");
+      expect(keychainStore["gateway_token"]).toBe("old-token");
+    });
   });
 
   describe("loading settings from localStorage", () => {
@@ -189,6 +228,20 @@ describe("Settings Persistence", () => {
       expect(settings.gatewayToken).toBe("keychain-token-abc");
     });
 
+    it("should pick up a rotated token and refresh the fallback", async () => {
+      keychainStore["gateway_token"] = "old-token";
+      await useStore.getState().loadSettings();
+
+      // The backend stores the replacement itself
+      keychainStore["gateway_token"] = "rotated-token";
+      await useStore.getState().reloadGatewayToken();
+
+      expect(useStore.getState().settings.gatewayToken).toBe("rotated-token");
+      expect(localStorageMock._store()["moltz-token-fallback"]).toBe(
+        "rotated-token",
+      );
+    });
+
     it("should handle empty localStorage gracefully", async () => {
       // localStorage returns null for unset keys
       const store = useStore.getState();
@@ -0,0 +1,184 @@
+import { useState, useRef, useEffect } from "react";
+import { useStore } from "../stores/store";
+import { useShallow } from "zustand/react/shallow";
+import { cn } from "../lib/utils";
+import { ChevronDown, Check, Bot } from "lucide-react";
+import {
+  AgentInfo,
+  agentLabel,
+  getSessionAgent,
+  listAgents,
+  setSessionAgent,
+} from "../lib/agents";
+
+interface AgentSelectorProps {
+  compact?: boolean;
+}
+
+/**
+ * Per-conversation agent picker; hidden when the Gateway has a single agent
+ */
+export function AgentSelector({ compact = false }: AgentSelectorProps) {
+  const [open, setOpen] = useState(false);
+  const [agents, setAgents] = useState<AgentInfo[]>([]);
+  const [selectedId, setSelectedId] = useState<string | null>(null);
+  const buttonRef = useRef<HTMLButtonElement>(null);
+  const menuRef = useRef<HTMLDivElement>(null);
+
+  const { currentConversationId, connected } = useStore(
+    useShallow((state) => ({
+      currentConversationId: state.currentConversationId,
+      connected: state.connected,
+    })),
+  );
+
+  // Agents are per Gateway: refresh on (re)connection
+  useEffect(() => {
+    if (!connected) return;
+    listAgents()
+      .then((list) => setAgents(list ?? []))
+      .catch((err) => console.error("Failed to fetch agents:", err));
+  }, [connected]);
+
+  // Conversation IDs double as session keys
+  useEffect(() => {
+    if (!currentConversationId) return;
+    let cancelled = false;
+    getSessionAgent(currentConversationId)
+      .then((agentId) => {
+        if (!cancelled) setSelectedId(agentId);
+      })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+    };
+  }, [currentConversationId]);
+
+  // Close on outside click or Escape
+  useEffect(() => {
+    if (!open) return;
+
+    const handleClick = (e: MouseEvent) => {
+      if (
+        menuRef.current &&
+        !menuRef.current.contains(e.target as Node) &&
+        buttonRef.current &&
+        !buttonRef.current.contains(e.target as Node)
+      ) {
+        setOpen(false);
+      }
+    };
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") {
+        setOpen(false);
+        buttonRef.current?.focus();
+      }
+    };
+
+    document.addEventListener("mousedown", handleClick);
+    document.addEventListener("keydown", handleKeyDown);
+    return () => {
+      document.removeEventListener("mousedown", handleClick);
+      document.removeEventListener("keydown", handleKeyDown);
+    };
+  }, [open]);
+
+  if (agents.length < 2 || !currentConversationId) {
+    return null;
+  }
+
+  const activeAgent =
+    agents.find((a) => a.id === selectedId) ?? agents.find((a) => a.isDefault);
+
+  const handleSelect = async (agentId: string | null) => {
+    setOpen(false);
+    const previous = selectedId;
+    setSelectedId(agentId);
+    try {
+      await setSessionAgent(currentConversationId, agentId);
+    } catch (err) {
+      console.error("Failed to set agent:", err);
+      setSelectedId(previous);
+    }
+  };
+
+  return (
+    <div className="relative">
+      <button
+        ref={buttonRef}
+        onClick={() => setOpen(!open)}
+        className={cn(
+          "flex items-center gap-1.5 rounded-md transition-colors",
+          "hover:bg-muted focus:outline-none focus:ring-2 focus:ring-primary/50",
+          compact ? "px-2 py-1 text-xs" : "px-2.5 py-1.5 text-sm",
+        )}
+        aria-haspopup="listbox"
+        aria-expanded={open}
+        aria-label={`Current agent: ${activeAgent ? agentLabel(activeAgent) : "Default"}`}
+      >
+        <Bot className="w-3.5 h-3.5" />
+        <span className="font-medium truncate max-w-[120px]">
+          {activeAgent ? agentLabel(activeAgent) : "Default agent"}
+        </span>
+        <ChevronDown
+          className={cn(
+            "w-3.5 h-3.5 text-muted-foreground transition-transform",
+            open && "rotate-180",
+          )}
+        />
+      </button>
+
+      {open && (
+        <div
+          ref={menuRef}
+          className={cn(
+            "absolute top-full mt-1 z-50 min-w-[200px] max-w-[280px] right-0",
+            "bg-popover border border-border rounded-lg shadow-lg",
+            "animate-in fade-in slide-in-from-top-2 duration-150",
+          )}
+          role="listbox"
+          aria-label="Select agent"
+        >
+          <div className="p-1 max-h-[300px] overflow-y-auto">
+            {agents.map((agent) => {
+              const isActive = agent.id === activeAgent?.id;
+              return (
+                <button
+                  key={agent.id}
+                  onClick={() =>
+                    handleSelect(agent.isDefault ? null : agent.id)
+                  }
+                  className={cn(
+                    "w-full flex items-center gap-2 px-3 py-2 rounded-md text-left transition-colors",
+                    isActive ? "bg-primary/10 text-primary" : "hover:bg-muted",
+                  )}
+                  role="option"
+                  aria-selected={isActive}
+                >
+                  <div className="flex-1 min-w-0">
+                    <div className="text-sm font-medium truncate flex items-center gap-1.5">
+                      {agentLabel(agent)}
+                      {agent.isDefault && (
+                        <span className="text-[10px] px-1 py-0.5 rounded bg-muted text-muted-foreground">
+                          default
+                        </span>
+                      )}
+                    </div>
+                    {(agent.description || agent.model) && (
+                      <div className="text-xs text-muted-foreground truncate">
+                        {agent.description || agent.model}
+                      </div>
+                    )}
+                  </div>
+                  {isActive && (
+                    <Check className="w-4 h-4 text-primary flex-shrink-0" />
+                  )}
+                </button>
+              );
+            })}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,49 @@
+/**
+ * Screen-reader live regions for backend announcements (`a11y:announce`)
+ * Announcements the backend already posted natively (macOS) are skipped
+ */
+
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+
+interface Announcement {
+  message: string;
+  priority: "polite" | "assertive";
+  native: boolean;
+}
+
+export function Announcer() {
+  const [polite, setPolite] = useState("");
+  const [assertive, setAssertive] = useState("");
+
+  useEffect(() => {
+    let clearTimer: ReturnType<typeof setTimeout> | undefined;
+    const unlisten = listen<Announcement>("a11y:announce", (event) => {
+      const { message, priority, native } = event.payload;
+      if (native) return;
+      const set = priority === "assertive" ? setAssertive : setPolite;
+      set(message);
+      // Clear afterwards so the same message is read again next time
+      clearTimeout(clearTimer);
+      clearTimer = setTimeout(() => {
+        setPolite("");
+        setAssertive("");
+      }, 3000);
+    });
+    return () => {
+      clearTimeout(clearTimer);
+      unlisten.then((fn) => fn()).catch(() => {});
+    };
+  }, []);
+
+  return (
+    <>
+      <div className="sr-only" role="status" aria-live="polite" aria-atomic="true">
+        {polite}
+      </div>
+      <div className="sr-only" role="alert" aria-live="assertive" aria-atomic="true">
+        {assertive}
+      </div>
+    </>
+  );
+}
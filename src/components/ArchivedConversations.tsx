@@ -0,0 +1,218 @@
+import { type FormEvent, useCallback, useEffect, useState } from "react";
+import { ArchiveRestore, Search, Trash2 } from "lucide-react";
+import {
+  ArchiveEntry,
+  ArchiveMatch,
+  DEFAULT_ARCHIVE_AFTER_DAYS,
+  archiveOlderThan,
+  deleteArchive,
+  listArchives,
+  restoreArchivedConversation,
+  searchArchives,
+} from "../lib/archive";
+import { formatBytes } from "../lib/dataSaver";
+import { openConversationAt } from "../lib/deepLink";
+import { errorMessage } from "../lib/errors";
+
+interface ArchivedConversationsProps {
+  onError: (message: string) => void;
+  onSuccess: (message: string) => void;
+}
+
+/**
+ * Settings section to archive old conversations and search or restore
+ * archived ones
+ */
+export function ArchivedConversations({
+  onError,
+  onSuccess,
+}: ArchivedConversationsProps) {
+  const [archives, setArchives] = useState<ArchiveEntry[]>([]);
+  const [days, setDays] = useState(DEFAULT_ARCHIVE_AFTER_DAYS);
+  const [busy, setBusy] = useState(false);
+  const [query, setQuery] = useState("");
+  const [matches, setMatches] = useState<ArchiveMatch[] | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      setArchives(await listArchives());
+    } catch (err) {
+      onError(`Failed to load archives: ${errorMessage(err)}`);
+    }
+  }, [onError]);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  const handleArchive = async () => {
+    setBusy(true);
+    try {
+      const count = await archiveOlderThan(days);
+      onSuccess(
+        count > 0
+          ? `Archived ${count} conversation(s)`
+          : "No conversations to archive",
+      );
+      await refresh();
+    } catch (err) {
+      onError(`Failed to archive conversations: ${errorMessage(err)}`);
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleRestore = async (id: string, messageId?: string) => {
+    try {
+      await restoreArchivedConversation(id);
+      openConversationAt(id, messageId);
+      setMatches(
+        (current) => current?.filter((m) => m.conversationId !== id) ?? null,
+      );
+      await refresh();
+    } catch (err) {
+      onError(`Failed to restore conversation: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleDelete = async (entry: ArchiveEntry) => {
+    if (!window.confirm(`Permanently delete "${entry.title}"?`)) return;
+    try {
+      await deleteArchive(entry.id);
+      await refresh();
+    } catch (err) {
+      onError(`Failed to delete archive: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleSearch = async (e: FormEvent) => {
+    e.preventDefault();
+    if (!query.trim()) {
+      setMatches(null);
+      return;
+    }
+    try {
+      setMatches(await searchArchives(query));
+    } catch (err) {
+      onError(`Failed to search archives: ${errorMessage(err)}`);
+    }
+  };
+
+  const titleOf = (id: string) =>
+    archives.find((a) => a.id === id)?.title ?? "Conversation";
+
+  const inputClass =
+    "px-3 py-1.5 text-sm border border-border rounded-lg bg-background focus:outline-none focus:ring-2 focus:ring-primary/50";
+  const buttonClass =
+    "px-3 py-1.5 rounded-lg text-xs font-medium border border-border hover:bg-muted transition-colors disabled:opacity-50";
+
+  return (
+    <div className="space-y-3">
+      <div className="flex items-center justify-between gap-3 p-3 border border-border rounded-xl">
+        <label className="flex items-center gap-2 text-xs text-muted-foreground">
+          Archive unpinned chats untouched for
+          <input
+            type="number"
+            min={1}
+            value={days}
+            onChange={(e) => setDays(Math.max(1, Number(e.target.value) || 1))}
+            className={`${inputClass} w-20`}
+          />
+          days
+        </label>
+        <button
+          type="button"
+          onClick={handleArchive}
+          disabled={busy}
+          className={`${buttonClass} flex-shrink-0`}
+        >
+          {busy ? "Archiving…" : "Archive"}
+        </button>
+      </div>
+
+      {archives.length > 0 && (
+        <>
+          <form onSubmit={handleSearch} className="flex gap-2">
+            <input
+              type="search"
+              value={query}
+              onChange={(e) => setQuery(e.target.value)}
+              placeholder="Search archived messages"
+              className={`${inputClass} flex-1`}
+            />
+            <button
+              type="submit"
+              className={buttonClass}
+              aria-label="Search archives"
+            >
+              <Search className="w-3.5 h-3.5" />
+            </button>
+          </form>
+
+          {matches && (
+            <div className="space-y-1">
+              {matches.length === 0 && (
+                <p className="text-xs text-muted-foreground">No matches</p>
+              )}
+              {matches.map((match) => (
+                <button
+                  key={match.messageId}
+                  type="button"
+                  onClick={() =>
+                    handleRestore(match.conversationId, match.messageId)
+                  }
+                  className="w-full p-2 text-left rounded-lg hover:bg-muted transition-colors"
+                  title="Restore and open"
+                >
+                  <span className="text-xs font-medium block truncate">
+                    {titleOf(match.conversationId)}
+                  </span>
+                  <span className="text-xs text-muted-foreground line-clamp-2">
+                    {match.snippet}
+                  </span>
+                </button>
+              ))}
+            </div>
+          )}
+
+          {archives.map((entry) => (
+            <div
+              key={entry.id}
+              className="flex items-center justify-between gap-3 p-3 border border-border rounded-xl"
+            >
+              <div className="space-y-1 min-w-0">
+                <span className="text-sm font-medium truncate block">
+                  {entry.title}
+                </span>
+                <p className="text-xs text-muted-foreground">
+                  {entry.messageCount} message(s) · {formatBytes(entry.size)} ·
+                  archived {new Date(entry.archivedAt).toLocaleDateString()}
+                </p>
+              </div>
+              <div className="flex items-center gap-1 flex-shrink-0">
+                <button
+                  type="button"
+                  onClick={() => handleRestore(entry.id)}
+                  className="p-1.5 rounded-lg text-muted-foreground hover:text-foreground hover:bg-muted transition-colors"
+                  aria-label={`Restore ${entry.title}`}
+                  title="Restore"
+                >
+                  <ArchiveRestore className="w-3.5 h-3.5" />
+                </button>
+                <button
+                  type="button"
+                  onClick={() => handleDelete(entry)}
+                  className="p-1.5 rounded-lg text-muted-foreground hover:text-destructive hover:bg-muted transition-colors"
+                  aria-label={`Delete ${entry.title}`}
+                  title="Delete permanently"
+                >
+                  <Trash2 className="w-3.5 h-3.5" />
+                </button>
+              </div>
+            </div>
+          ))}
+        </>
+      )}
+    </div>
+  );
+}
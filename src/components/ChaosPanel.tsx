@@ -0,0 +1,125 @@
+import { useEffect, useState } from "react";
+import { Unplug } from "lucide-react";
+import { Switch } from "./ui/switch";
+import {
+  ChaosConfig,
+  chaosDisconnect,
+  getChaosConfig,
+  setChaosConfig,
+} from "../lib/chaos";
+import { errorMessage } from "../lib/errors";
+
+interface ChaosPanelProps {
+  onError: (message: string) => void;
+}
+
+const defaultConfig: ChaosConfig = {
+  enabled: false,
+  latencyMs: 0,
+  dropRate: 0,
+  disconnectIntervalSecs: null,
+};
+
+/**
+ * Developer settings section for connection chaos mode
+ * (rendered in development builds only)
+ */
+export function ChaosPanel({ onError }: ChaosPanelProps) {
+  const [config, setConfig] = useState<ChaosConfig>(defaultConfig);
+
+  useEffect(() => {
+    getChaosConfig()
+      .then((loaded) => setConfig(loaded ?? defaultConfig))
+      .catch(() => {});
+  }, []);
+
+  // Settings apply immediately, like the theme
+  const update = (changes: Partial<ChaosConfig>) => {
+    const next = { ...config, ...changes };
+    setConfig(next);
+    setChaosConfig(next).catch((err) =>
+      onError(`Failed to update chaos mode: ${errorMessage(err)}`),
+    );
+  };
+
+  return (
+    <div className="space-y-3">
+      <div className="flex items-center justify-between p-3 border border-border rounded-xl">
+        <div className="space-y-1">
+          <span className="text-sm font-medium">Connection Chaos</span>
+          <p className="text-xs text-muted-foreground">
+            Inject latency, dropped frames and disconnects to test
+            reconnection
+          </p>
+        </div>
+        <Switch
+          checked={config.enabled}
+          onCheckedChange={(enabled) => update({ enabled })}
+          aria-label="Toggle connection chaos"
+        />
+      </div>
+      {config.enabled && (
+        <div className="p-3 border border-border rounded-xl space-y-3 text-sm">
+          <label className="block space-y-1">
+            <span className="text-xs text-muted-foreground">
+              Latency: up to {config.latencyMs} ms
+            </span>
+            <input
+              type="range"
+              min={0}
+              max={5000}
+              step={100}
+              value={config.latencyMs}
+              onChange={(e) => update({ latencyMs: Number(e.target.value) })}
+              className="w-full"
+            />
+          </label>
+          <label className="block space-y-1">
+            <span className="text-xs text-muted-foreground">
+              Dropped frames: {Math.round(config.dropRate * 100)}%
+            </span>
+            <input
+              type="range"
+              min={0}
+              max={0.5}
+              step={0.01}
+              value={config.dropRate}
+              onChange={(e) => update({ dropRate: Number(e.target.value) })}
+              className="w-full"
+            />
+          </label>
+          <div className="flex items-center gap-2">
+            <select
+              value={config.disconnectIntervalSecs ?? ""}
+              onChange={(e) =>
+                update({
+                  disconnectIntervalSecs:
+                    e.target.value === "" ? null : Number(e.target.value),
+                })
+              }
+              aria-label="Forced disconnect interval"
+              className="flex-1 px-2 py-1.5 text-sm border border-border rounded-lg bg-background"
+            >
+              <option value="">No periodic disconnects</option>
+              <option value="30">Disconnect every 30s</option>
+              <option value="60">Disconnect every minute</option>
+              <option value="300">Disconnect every 5 minutes</option>
+            </select>
+            <button
+              type="button"
+              onClick={() =>
+                chaosDisconnect().catch((err) =>
+                  onError(`Failed to force disconnect: ${errorMessage(err)}`),
+                )
+              }
+              className="flex items-center gap-1.5 px-3 py-1.5 rounded-lg text-xs font-medium border border-border hover:bg-muted transition-colors"
+            >
+              <Unplug className="w-3.5 h-3.5" />
+              Disconnect now
+            </button>
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
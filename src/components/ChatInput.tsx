@@ -1,8 +1,10 @@
 import React, { useState, useRef, KeyboardEvent, useEffect } from "react";
 import { open } from "@tauri-apps/plugin-dialog";
 import { readFile } from "@tauri-apps/plugin-fs";
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { cn } from "../lib/utils";
-import { translateError } from "../lib/errors";
+import { errorMessage, translateError } from "../lib/errors";
 import { Spinner } from "./ui/spinner";
 import {
   Paperclip,
@@ -12,7 +14,15 @@ import {
   Image as ImageIcon,
   AlertCircle,
   RotateCcw,
+  GitBranch,
+  History,
+  Eye,
+  EyeOff,
+  RefreshCw,
+  FolderOpen,
 } from "lucide-react";
+import { getRecentFiles, RecentFile } from "../lib/recentFiles";
+import { compressImage, useDataSaver } from "../lib/dataSaver";
 
 // Attachment with base64 data ready to send
 export interface PreparedAttachment {
@@ -22,6 +32,48 @@ export interface PreparedAttachment {
   data: string; // base64
   path: string; // original path for display
   previewUrl?: string; // data URL for image preview
+  watchable?: boolean; // read from a local file that can be watched for edits
+}
+
+// Payload of attachment:changed (a watched attachment's file was edited)
+interface AttachmentChanged {
+  attachmentId: string;
+  path: string;
+  filename: string;
+}
+
+// Git context that can be attached from a local repository
+type RepoContextMode = "diff" | "staged" | "tree";
+
+const REPO_CONTEXT_OPTIONS: { mode: RepoContextMode; label: string }[] = [
+  { mode: "diff", label: "Uncommitted changes" },
+  { mode: "staged", label: "Staged changes" },
+  { mode: "tree", label: "File tree" },
+];
+
+// Folder attachments: one attachment per text file, or a single zip archive
+const FOLDER_OPTIONS: { archive: boolean; label: string }[] = [
+  { archive: false, label: "Text files" },
+  { archive: true, label: "Zip archive" },
+];
+
+interface FolderAttachment {
+  attachments: PreparedAttachment[];
+  manifest: {
+    root: string;
+    files: { path: string; size: number }[];
+    skipped: { path: string; reason: string }[];
+    totalSize: number;
+  };
+}
+
+interface RepoContextAttachment {
+  id: string;
+  filename: string;
+  mimeType: string;
+  data: string;
+  path: string;
+  truncated: boolean;
 }
 
 // Supported file types and their MIME mappings
@@ -85,6 +137,64 @@ function arrayBufferToBase64(buffer: ArrayBuffer): string {
   return btoa(binary);
 }
 
+// Read a local file into an attachment; throws with a user-facing message
+// (images are compressed when `compress` is set, for data saver mode)
+async function prepareAttachment(
+  path: string,
+  compress = false,
+): Promise<PreparedAttachment> {
+  // Get filename from path
+  const filename = path.split(/[/\\]/).pop() || path;
+  const mimeType = getMimeType(filename);
+
+  if (!mimeType) {
+    throw new Error(`Unsupported file type: ${filename}`);
+  }
+
+  // Read file as binary
+  let fileData: Uint8Array;
+  try {
+    fileData = await readFile(path);
+  } catch (err) {
+    console.error(`Failed to read file ${path}:`, err);
+    throw new Error(`Failed to read: ${filename}`);
+  }
+
+  // Check file size
+  if (fileData.byteLength > MAX_FILE_SIZE) {
+    throw new Error(`File too large (max 10MB): ${filename}`);
+  }
+
+  // Convert to base64
+  let base64 = arrayBufferToBase64(fileData.buffer);
+  let finalMimeType = mimeType;
+  if (compress && isImageMime(mimeType)) {
+    try {
+      const compressed = await compressImage(base64, mimeType);
+      base64 = compressed.data;
+      finalMimeType = compressed.mimeType;
+    } catch (err) {
+      console.warn(`Failed to compress ${filename}, sending original:`, err);
+    }
+  }
+
+  // Create preview URL for images
+  let previewUrl: string | undefined;
+  if (isImageMime(finalMimeType)) {
+    previewUrl = `data:${finalMimeType};base64,${base64}`;
+  }
+
+  return {
+    id: crypto.randomUUID(),
+    filename,
+    mimeType: finalMimeType,
+    data: base64,
+    path,
+    previewUrl,
+    watchable: true,
+  };
+}
+
 // Maximum message length (100KB - accommodates most use cases while preventing abuse)
 const MAX_MESSAGE_LENGTH = 100000;
 
@@ -107,6 +217,15 @@ export function ChatInput({
   const [fileError, setFileError] = useState<string | null>(null);
   const [messageTooLong, setMessageTooLong] = useState(false);
   const [isFocused, setIsFocused] = useState(false);
+  const [repoMenuOpen, setRepoMenuOpen] = useState(false);
+  const [folderMenuOpen, setFolderMenuOpen] = useState(false);
+  const [recentMenuOpen, setRecentMenuOpen] = useState(false);
+  const [recentFiles, setRecentFiles] = useState<RecentFile[] | null>(null);
+  const [watchedIds, setWatchedIds] = useState<Set<string>>(new Set());
+  const [changedFile, setChangedFile] = useState<AttachmentChanged | null>(
+    null,
+  );
+  const dataSaver = useDataSaver();
   const internalRef = useRef<HTMLTextAreaElement>(null);
   const textareaRef = externalRef || internalRef;
 
@@ -142,6 +261,17 @@ export function ChatInput({
     };
   }, [onSend]);
 
+  // Offer to re-send watched attachments when their file is edited
+  useEffect(() => {
+    const unlisten = getCurrentWebviewWindow().listen<AttachmentChanged>(
+      "attachment:changed",
+      (event) => setChangedFile(event.payload),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   const handleSend = () => {
     if (disabled) return;
     if (!message.trim() && attachments.length === 0) return;
@@ -166,6 +296,30 @@ export function ChatInput({
     }
   };
 
+  // Read files from disk and add them as attachments
+  const attachPaths = async (paths: string[]) => {
+    const newAttachments: PreparedAttachment[] = [];
+    const errors: string[] = [];
+
+    for (const path of paths) {
+      try {
+        newAttachments.push(await prepareAttachment(path, dataSaver));
+      } catch (err) {
+        errors.push(errorMessage(err));
+      }
+    }
+
+    if (errors.length > 0) {
+      setFileError(errors.join("; "));
+      // Auto-dismiss after 8 seconds for file errors
+      setTimeout(() => setFileError(null), 8000);
+    }
+
+    if (newAttachments.length > 0) {
+      setAttachments((prev) => [...prev, ...newAttachments]);
+    }
+  };
+
   const handleAttach = async () => {
     if (disabled || isLoadingFiles) return;
     setFileError(null);
@@ -207,76 +361,178 @@ export function ChatInput({
 
       const paths = Array.isArray(selected) ? selected : [selected];
       setIsLoadingFiles(true);
+      await attachPaths(paths);
+    } catch (err) {
+      console.error("Failed to open file dialog:", err);
+      const friendly = translateError(errorMessage(err));
+      setFileError(
+        `${friendly.title}: ${friendly.message}${friendly.suggestion ? " " + friendly.suggestion : ""}`,
+      );
+      setTimeout(() => setFileError(null), 8000);
+    } finally {
+      setIsLoadingFiles(false);
+    }
+  };
 
-      const newAttachments: PreparedAttachment[] = [];
-      const errors: string[] = [];
+  const toggleRecentMenu = async () => {
+    if (recentMenuOpen) {
+      setRecentMenuOpen(false);
+      return;
+    }
+    setRepoMenuOpen(false);
+    setFolderMenuOpen(false);
+    setRecentMenuOpen(true);
+    setRecentFiles(null);
+    try {
+      setRecentFiles(await getRecentFiles());
+    } catch (err) {
+      console.error("Failed to load recent files:", err);
+      setRecentFiles([]);
+    }
+  };
 
-      for (const path of paths) {
-        try {
-          // Get filename from path
-          const filename = path.split(/[/\\]/).pop() || path;
-          const mimeType = getMimeType(filename);
+  const handleAttachRecent = async (file: RecentFile) => {
+    setRecentMenuOpen(false);
+    if (disabled || isLoadingFiles) return;
+    setFileError(null);
 
-          if (!mimeType) {
-            errors.push(`Unsupported file type: ${filename}`);
-            continue;
-          }
+    setIsLoadingFiles(true);
+    try {
+      await attachPaths([file.path]);
+    } finally {
+      setIsLoadingFiles(false);
+    }
+  };
 
-          // Read file as binary
-          const fileData = await readFile(path);
+  const handleAttachFolder = async (archive: boolean) => {
+    setFolderMenuOpen(false);
+    if (disabled || isLoadingFiles) return;
+    setFileError(null);
 
-          // Check file size
-          if (fileData.byteLength > MAX_FILE_SIZE) {
-            errors.push(`File too large (max 10MB): ${filename}`);
-            continue;
-          }
+    try {
+      const selected = await open({
+        directory: true,
+        title: "Choose a folder to attach",
+      });
+      if (typeof selected !== "string") return;
 
-          // Convert to base64
-          const base64 = arrayBufferToBase64(fileData.buffer);
+      setIsLoadingFiles(true);
+      const folder = await invoke<FolderAttachment>("attach_folder", {
+        path: selected,
+        options: { archive },
+      });
+      setAttachments((prev) => [...prev, ...folder.attachments]);
+      if (folder.manifest.skipped.length > 0) {
+        setFileError(
+          `${folder.manifest.skipped.length} file(s) skipped (see the manifest)`,
+        );
+        setTimeout(() => setFileError(null), 8000);
+      }
+    } catch (err) {
+      console.error("Failed to attach folder:", err);
+      const friendly = translateError(errorMessage(err));
+      setFileError(`${friendly.title}: ${friendly.message}`);
+      setTimeout(() => setFileError(null), 8000);
+    } finally {
+      setIsLoadingFiles(false);
+    }
+  };
 
-          // Create preview URL for images
-          let previewUrl: string | undefined;
-          if (isImageMime(mimeType)) {
-            previewUrl = `data:${mimeType};base64,${base64}`;
-          }
+  const handleAttachRepo = async (mode: RepoContextMode) => {
+    setRepoMenuOpen(false);
+    if (disabled || isLoadingFiles) return;
+    setFileError(null);
 
-          newAttachments.push({
-            id: crypto.randomUUID(),
-            filename,
-            mimeType,
-            data: base64,
-            path,
-            previewUrl,
-          });
-        } catch (err) {
-          console.error(`Failed to read file ${path}:`, err);
-          const filename = path.split(/[/\\]/).pop() || path;
-          errors.push(`Failed to read: ${filename}`);
-        }
-      }
+    try {
+      const selected = await open({
+        directory: true,
+        title: "Choose a git repository",
+      });
+      if (typeof selected !== "string") return;
 
-      if (errors.length > 0) {
-        setFileError(errors.join("; "));
-        // Auto-dismiss after 8 seconds for file errors
+      setIsLoadingFiles(true);
+      const context = await invoke<RepoContextAttachment>(
+        "attach_repo_context",
+        { path: selected, mode },
+      );
+      setAttachments((prev) => [
+        ...prev,
+        {
+          id: context.id,
+          filename: context.filename,
+          mimeType: context.mimeType,
+          data: context.data,
+          path: context.path,
+        },
+      ]);
+      if (context.truncated) {
+        setFileError("Repository context was trimmed to fit the size limit");
         setTimeout(() => setFileError(null), 8000);
       }
-
-      if (newAttachments.length > 0) {
-        setAttachments((prev) => [...prev, ...newAttachments]);
-      }
     } catch (err) {
-      console.error("Failed to open file dialog:", err);
-      const friendly = translateError(err instanceof Error ? err : String(err));
-      setFileError(
-        `${friendly.title}: ${friendly.message}${friendly.suggestion ? " " + friendly.suggestion : ""}`,
-      );
+      console.error("Failed to attach repository context:", err);
+      const friendly = translateError(errorMessage(err));
+      setFileError(`${friendly.title}: ${friendly.message}`);
       setTimeout(() => setFileError(null), 8000);
     } finally {
       setIsLoadingFiles(false);
     }
   };
 
+  const toggleWatch = async (file: PreparedAttachment) => {
+    const watching = watchedIds.has(file.id);
+    try {
+      if (watching) {
+        await invoke("unwatch_attachment", { attachmentId: file.id });
+      } else {
+        await invoke("watch_attachment", {
+          attachmentId: file.id,
+          path: file.path,
+        });
+      }
+      setWatchedIds((prev) => {
+        const next = new Set(prev);
+        if (watching) {
+          next.delete(file.id);
+        } else {
+          next.add(file.id);
+        }
+        return next;
+      });
+    } catch (err) {
+      console.error("Failed to toggle file watch:", err);
+      setFileError(errorMessage(err));
+      setTimeout(() => setFileError(null), 8000);
+    }
+  };
+
+  const stopWatching = (attachmentId: string) => {
+    if (!watchedIds.has(attachmentId)) return;
+    invoke("unwatch_attachment", { attachmentId }).catch((err) =>
+      console.error("Failed to stop watching file:", err),
+    );
+    setWatchedIds((prev) => {
+      const next = new Set(prev);
+      next.delete(attachmentId);
+      return next;
+    });
+  };
+
+  const handleSendUpdated = async (changed: AttachmentChanged) => {
+    setChangedFile(null);
+    if (disabled) return;
+    try {
+      const updated = await prepareAttachment(changed.path, dataSaver);
+      onSend(`Updated version of ${changed.filename}`, [updated]);
+    } catch (err) {
+      setFileError(errorMessage(err));
+      setTimeout(() => setFileError(null), 8000);
+    }
+  };
+
   const removeAttachment = (index: number) => {
+    const removed = attachments[index];
+    if (removed) stopWatching(removed.id);
     setAttachments((prev) => prev.filter((_, i) => i !== index));
   };
 
@@ -316,6 +572,43 @@ export function ChatInput({
         </div>
       )}
 
+      {/* Watched file changed */}
+      {changedFile && (
+        <div
+          className="flex items-center gap-2 mb-3 px-3 py-2 bg-primary/10 border border-primary/20 rounded-lg text-sm animate-in fade-in slide-in-from-bottom-2 duration-200"
+          role="status"
+          aria-live="polite"
+        >
+          <RefreshCw className="w-4 h-4 flex-shrink-0" aria-hidden="true" />
+          <span className="flex-1 truncate">
+            {changedFile.filename} changed
+          </span>
+          <button
+            onClick={() => handleSendUpdated(changedFile)}
+            disabled={disabled}
+            className="px-2 py-0.5 rounded bg-primary text-primary-foreground text-xs hover:bg-primary/90 transition-colors disabled:opacity-50"
+          >
+            Send updated version
+          </button>
+          <button
+            onClick={() => {
+              stopWatching(changedFile.attachmentId);
+              setChangedFile(null);
+            }}
+            className="px-2 py-0.5 rounded text-xs hover:bg-primary/10 transition-colors"
+          >
+            Stop watching
+          </button>
+          <button
+            onClick={() => setChangedFile(null)}
+            className="p-0.5 hover:bg-primary/10 rounded transition-colors"
+            aria-label="Dismiss"
+          >
+            <X className="w-3.5 h-3.5" />
+          </button>
+        </div>
+      )}
+
       {/* Message too long warning */}
       {(messageTooLong || message.length > MAX_MESSAGE_LENGTH) && (
         <div
@@ -353,6 +646,30 @@ export function ChatInput({
                 <FileText className="w-4 h-4 text-muted-foreground" />
               )}
               <span className="truncate max-w-[150px]">{file.filename}</span>
+              {file.watchable && (
+                <button
+                  onClick={() => toggleWatch(file)}
+                  className={cn(
+                    "p-0.5 hover:bg-background rounded transition-colors",
+                    watchedIds.has(file.id)
+                      ? "text-primary"
+                      : "text-muted-foreground hover:text-foreground",
+                  )}
+                  title={
+                    watchedIds.has(file.id)
+                      ? "Stop watching for edits"
+                      : "Watch for edits and offer to re-send"
+                  }
+                  aria-label={`${watchedIds.has(file.id) ? "Stop watching" : "Watch"} ${file.filename}`}
+                  aria-pressed={watchedIds.has(file.id)}
+                >
+                  {watchedIds.has(file.id) ? (
+                    <Eye className="w-3.5 h-3.5" />
+                  ) : (
+                    <EyeOff className="w-3.5 h-3.5" />
+                  )}
+                </button>
+              )}
               <button
                 onClick={() => removeAttachment(i)}
                 className="p-0.5 text-muted-foreground hover:text-foreground hover:bg-background rounded transition-colors"
@@ -393,6 +710,135 @@ export function ChatInput({
           )}
         </button>
 
+        {/* Recent files button */}
+        <div className="relative flex-shrink-0">
+          <button
+            onClick={toggleRecentMenu}
+            disabled={disabled || isLoadingFiles}
+            className={cn(
+              "p-3 -ml-2 text-muted-foreground hover:text-foreground transition-colors",
+              (disabled || isLoadingFiles) && "cursor-not-allowed",
+            )}
+            title="Attach a recently used file"
+            aria-label="Attach recent file"
+            aria-haspopup="menu"
+            aria-expanded={recentMenuOpen}
+          >
+            <History className="w-5 h-5" />
+          </button>
+          {recentMenuOpen && (
+            <div
+              role="menu"
+              className="absolute bottom-full left-0 mb-2 w-72 max-h-80 overflow-y-auto rounded-lg border border-border bg-background shadow-lg py-1 z-10"
+            >
+              {recentFiles === null ? (
+                <div className="flex items-center gap-2 px-3 py-1.5 text-sm text-muted-foreground">
+                  <Spinner size="sm" /> Loading recent files...
+                </div>
+              ) : recentFiles.length === 0 ? (
+                <div className="px-3 py-1.5 text-sm text-muted-foreground">
+                  No recent files
+                </div>
+              ) : (
+                recentFiles.map((file) => (
+                  <button
+                    key={file.path}
+                    role="menuitem"
+                    onClick={() => handleAttachRecent(file)}
+                    className="w-full flex items-center gap-2 text-left px-3 py-1.5 text-sm hover:bg-muted transition-colors"
+                    title={file.path}
+                  >
+                    {file.kind === "image" ? (
+                      <ImageIcon className="w-4 h-4 flex-shrink-0 text-muted-foreground" />
+                    ) : (
+                      <FileText className="w-4 h-4 flex-shrink-0 text-muted-foreground" />
+                    )}
+                    <span className="truncate">{file.name}</span>
+                  </button>
+                ))
+              )}
+            </div>
+          )}
+        </div>
+
+        {/* Attach folder button */}
+        <div className="relative flex-shrink-0">
+          <button
+            onClick={() => {
+              setRecentMenuOpen(false);
+              setRepoMenuOpen(false);
+              setFolderMenuOpen((open) => !open);
+            }}
+            disabled={disabled || isLoadingFiles}
+            className={cn(
+              "p-3 -ml-2 text-muted-foreground hover:text-foreground transition-colors",
+              (disabled || isLoadingFiles) && "cursor-not-allowed",
+            )}
+            title="Attach a folder's text files (honors .gitignore)"
+            aria-label="Attach folder"
+            aria-haspopup="menu"
+            aria-expanded={folderMenuOpen}
+          >
+            <FolderOpen className="w-5 h-5" />
+          </button>
+          {folderMenuOpen && (
+            <div
+              role="menu"
+              className="absolute bottom-full left-0 mb-2 w-48 rounded-lg border border-border bg-background shadow-lg py-1 z-10"
+            >
+              {FOLDER_OPTIONS.map((option) => (
+                <button
+                  key={option.label}
+                  role="menuitem"
+                  onClick={() => handleAttachFolder(option.archive)}
+                  className="w-full text-left px-3 py-1.5 text-sm hover:bg-muted transition-colors"
+                >
+                  {option.label}
+                </button>
+              ))}
+            </div>
+          )}
+        </div>
+
+        {/* Attach git context button */}
+        <div className="relative flex-shrink-0">
+          <button
+            onClick={() => {
+              setRecentMenuOpen(false);
+              setFolderMenuOpen(false);
+              setRepoMenuOpen((open) => !open);
+            }}
+            disabled={disabled || isLoadingFiles}
+            className={cn(
+              "p-3 -ml-2 text-muted-foreground hover:text-foreground transition-colors",
+              (disabled || isLoadingFiles) && "cursor-not-allowed",
+            )}
+            title="Attach git context from a repository"
+            aria-label="Attach git context"
+            aria-haspopup="menu"
+            aria-expanded={repoMenuOpen}
+          >
+            <GitBranch className="w-5 h-5" />
+          </button>
+          {repoMenuOpen && (
+            <div
+              role="menu"
+              className="absolute bottom-full left-0 mb-2 w-48 rounded-lg border border-border bg-background shadow-lg py-1 z-10"
+            >
+              {REPO_CONTEXT_OPTIONS.map((option) => (
+                <button
+                  key={option.mode}
+                  role="menuitem"
+                  onClick={() => handleAttachRepo(option.mode)}
+                  className="w-full text-left px-3 py-1.5 text-sm hover:bg-muted transition-colors"
+                >
+                  {option.label}
+                </button>
+              ))}
+            </div>
+          )}
+        </div>
+
         {/* Text input */}
         <label htmlFor="message-input" className="sr-only">
           Message input
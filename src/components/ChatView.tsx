@@ -6,7 +6,18 @@ import { ChatInput, PreparedAttachment } from "./ChatInput";
 import { MessageBubble } from "./MessageBubble";
 import { ConfirmDialog } from "./ui/confirm-dialog";
 import { MessageSkeleton } from "./ui/skeleton";
-import { translateError, logError } from "../lib/errors";
+import {
+  translateError,
+  logError,
+  errorCode,
+  errorMessage,
+} from "../lib/errors";
+import {
+  getSendStatus,
+  isBlocked,
+  SEND_STATUS_NOTICES,
+  type SendOutcome,
+} from "../lib/sendGuard";
 import {
   ArrowDown,
   AlertTriangle,
@@ -18,6 +29,7 @@ import {
 import { Button } from "./ui/button";
 
 import { cn } from "../lib/utils";
+import { setReminder, tomorrowMorning } from "../lib/reminders";
 
 export function ChatView() {
   // PERF: Use selective subscriptions with shallow equality to prevent unnecessary re-renders
@@ -238,6 +250,24 @@ export function ChatView() {
     }
   };
 
+  // Tell the user how the backend handled a message; a blocked message never
+  // gets a reply, so its placeholder goes
+  const showSendNotice = useCallback(
+    (outcome: SendOutcome, placeholderId: string) => {
+      if (!currentConversation) return;
+      if (isBlocked(outcome)) {
+        completeCurrentMessage();
+        deleteMessage(currentConversation.id, placeholderId);
+      }
+      const notice = SEND_STATUS_NOTICES[outcome.status];
+      if (notice) {
+        setError(notice);
+        setTimeout(() => setError(null), isBlocked(outcome) ? 15000 : 5000);
+      }
+    },
+    [currentConversation, completeCurrentMessage, deleteMessage],
+  );
+
   // Actually execute the edit (after confirmation if needed)
   const executeEdit = useCallback(
     async (messageId: string, newContent: string) => {
@@ -255,26 +285,25 @@ export function ChatView() {
 
       try {
         // Add placeholder for assistant response
-        addMessage(currentConversation.id, {
+        const placeholder = addMessage(currentConversation.id, {
           role: "assistant",
           content: "",
           isStreaming: true,
         });
 
         // Send to gateway with updated message
-        await invoke("send_message", {
+        const outcome = await invoke<SendOutcome>("send_message", {
           params: {
             message: newContent,
             session_key: currentConversation.id,
             model: currentConversation.model || settings.defaultModel,
-            thinking: currentConversation.thinkingEnabled ? "low" : null,
+            thinking: currentConversation.thinkingEnabled ? "low" : "off",
           },
         });
+        showSendNotice(outcome, placeholder.id);
       } catch (err: unknown) {
         console.error("Failed to send edited message:", err);
-        const friendly = translateError(
-          err instanceof Error ? err : String(err),
-        );
+        const friendly = translateError(errorMessage(err));
         setError(
           `${friendly.title}: ${friendly.message}${friendly.suggestion ? "\n" + friendly.suggestion : ""}`,
         );
@@ -289,6 +318,7 @@ export function ChatView() {
       deleteMessagesAfter,
       updateMessage,
       addMessage,
+      showSendNotice,
     ],
   );
 
@@ -329,6 +359,15 @@ export function ChatView() {
     setPendingEdit(null);
   }, []);
 
+  // Remind the user about an answer tomorrow morning
+  const handleRemind = useCallback(
+    async (messageId: string) => {
+      if (!currentConversation) return;
+      await setReminder(currentConversation.id, messageId, tomorrowMorning());
+    },
+    [currentConversation],
+  );
+
   // Handle regenerating an assistant response
   const handleRegenerate = useCallback(
     async (messageId: string) => {
@@ -357,26 +396,25 @@ export function ChatView() {
 
       try {
         // Add placeholder for new assistant response
-        addMessage(currentConversation.id, {
+        const placeholder = addMessage(currentConversation.id, {
           role: "assistant",
           content: "",
           isStreaming: true,
         });
 
         // Resend the preceding user message
-        await invoke("send_message", {
+        const outcome = await invoke<SendOutcome>("send_message", {
           params: {
             message: precedingUserMessage.content,
             session_key: currentConversation.id,
             model: currentConversation.model || settings.defaultModel,
-            thinking: currentConversation.thinkingEnabled ? "low" : null,
+            thinking: currentConversation.thinkingEnabled ? "low" : "off",
           },
         });
+        showSendNotice(outcome, placeholder.id);
       } catch (err: unknown) {
         console.error("Failed to regenerate response:", err);
-        const friendly = translateError(
-          err instanceof Error ? err : String(err),
-        );
+        const friendly = translateError(errorMessage(err));
         setError(
           `${friendly.title}: ${friendly.message}${friendly.suggestion ? "\n" + friendly.suggestion : ""}`,
         );
@@ -392,6 +430,7 @@ export function ChatView() {
       settings.defaultModel,
       deleteMessage,
       addMessage,
+      showSendNotice,
     ],
   );
 
@@ -417,30 +456,47 @@ export function ChatView() {
       })),
     });
 
-    // If not connected, queue the message immediately
+    // If not connected, queue the message immediately - unless the Gateway
+    // rejected the token, in which case it would never go out
     if (!connected) {
-      markMessageQueued(currentConversation.id, userMessage.id);
+      const status = await getSendStatus().catch(
+        () => "not_connected" as const,
+      );
       setIsSending(false);
-      setError("Not connected - message will be sent when reconnected");
+      if (status === "blocked_needs_reauth") {
+        markMessageFailed(
+          currentConversation.id,
+          userMessage.id,
+          "Gateway token rejected",
+        );
+        setLastFailedMessage({ content, attachments });
+        setError(SEND_STATUS_NOTICES.blocked_needs_reauth ?? null);
+        setTimeout(() => setError(null), 15000);
+        return;
+      }
+      markMessageQueued(currentConversation.id, userMessage.id);
+      setError(
+        SEND_STATUS_NOTICES[status] ?? SEND_STATUS_NOTICES.not_connected ?? null,
+      );
       setTimeout(() => setError(null), 5000);
       return;
     }
 
     try {
       // Add placeholder for assistant response
-      addMessage(currentConversation.id, {
+      const placeholder = addMessage(currentConversation.id, {
         role: "assistant",
         content: "",
         isStreaming: true,
       });
 
       // Send to gateway with attachments
-      await invoke("send_message", {
+      const outcome = await invoke<SendOutcome>("send_message", {
         params: {
           message: content,
           session_key: currentConversation.id,
           model: currentConversation.model || settings.defaultModel,
-          thinking: currentConversation.thinkingEnabled ? "low" : null,
+          thinking: currentConversation.thinkingEnabled ? "low" : "off",
           attachments: attachments.map((a) => ({
             id: a.id,
             filename: a.filename,
@@ -450,22 +506,34 @@ export function ChatView() {
         },
       });
 
-      // Mark user message as sent (no longer pending)
-      markMessageSent(currentConversation.id, userMessage.id);
+      if (isBlocked(outcome)) {
+        markMessageFailed(
+          currentConversation.id,
+          userMessage.id,
+          "Gateway token rejected",
+        );
+        setLastFailedMessage({ content, attachments });
+      } else {
+        // Mark user message as sent (no longer pending); queued messages
+        // are the backend's to deliver
+        markMessageSent(currentConversation.id, userMessage.id);
+      }
+      showSendNotice(outcome, placeholder.id);
     } catch (err: unknown) {
       logError(
-        err instanceof Error ? err : String(err),
+        err instanceof Error ? err : errorMessage(err),
         "ChatView.handleSendMessage",
         {
           conversationId: currentConversation.id,
           hasAttachments: attachments.length > 0,
         },
       );
-      const friendly = translateError(err instanceof Error ? err : String(err));
-      const errorMsg = String(err).replace("Error: ", "");
+      const friendly = translateError(errorMessage(err));
+      const errorMsg = errorMessage(err).replace("Error: ", "");
 
       // Check if it's a connection error - queue for retry
       if (
+        errorCode(err) === "network" ||
         errorMsg.toLowerCase().includes("connection") ||
         errorMsg.toLowerCase().includes("network") ||
         errorMsg.toLowerCase().includes("disconnected")
@@ -547,6 +615,7 @@ export function ChatView() {
                     message={message}
                     onEdit={handleEditMessage}
                     onRegenerate={handleRegenerate}
+                    onRemind={handleRemind}
                     isLastAssistantMessage={
                       message.id === lastAssistantMessageId
                     }
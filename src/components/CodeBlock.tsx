@@ -4,6 +4,7 @@
  * Features:
  * - Syntax highlighting via highlight.js
  * - Copy button with visual feedback
+ * - Drag handle to drop the code out of the app as a file
  * - Language labels + filename detection
  * - Line numbers (toggleable)
  * - Wrap toggle for long lines
@@ -27,7 +28,9 @@ import {
   ExternalLink,
   FileCode,
   AlertCircle,
+  GripVertical,
 } from "lucide-react";
+import { dragOut } from "../lib/dragOut";
 
 interface CodeBlockProps {
   code: string;
@@ -410,6 +413,23 @@ ${code}
             </button>
           )}
 
+          {/* Drag out as a file */}
+          <button
+            onMouseDown={(e) => {
+              if (e.button !== 0) return;
+              e.preventDefault();
+              dragOut(code, {
+                language,
+                name: filename?.replace(/\.\w+$/, ""),
+              }).catch((err) => console.error("Drag-out failed:", err));
+            }}
+            className="flex items-center text-xs px-1.5 py-1 rounded transition-colors text-zinc-400 hover:text-zinc-200 hover:bg-zinc-700/50 cursor-grab focus:outline-none focus:ring-2 focus:ring-primary/50"
+            title="Drag out as a file"
+            aria-label="Drag code out as a file"
+          >
+            <GripVertical className="w-3.5 h-3.5" strokeWidth={2} />
+          </button>
+
           {/* Copy button */}
           <button
             onClick={handleCopy}
@@ -1,5 +1,5 @@
 import { useState, useEffect, useCallback, useMemo, useRef } from "react";
-import { useStore, ModelInfo } from "../stores/store";
+import { useStore } from "../stores/store";
 import { useShallow } from "zustand/react/shallow";
 import { cn } from "../lib/utils";
 import {
@@ -12,10 +12,24 @@ import {
   Cpu,
   MessageSquare,
   Zap,
-  PanelLeftClose,
   PanelLeft,
   Command,
+  MessageSquarePlus,
+  RefreshCw,
+  Download,
+  Mail,
+  KeyRound,
 } from "lucide-react";
+import { Preset, createConversationFromPreset, listPresets } from "../lib/presets";
+import { reinitializeSystemIntegration } from "../lib/systemIntegration";
+import {
+  PaletteKind,
+  PaletteMatch,
+  formatShortcut,
+  paletteQuery,
+  runPaletteAction,
+  setPaletteConversations,
+} from "../lib/palette";
 
 interface CommandPaletteProps {
   open: boolean;
@@ -24,14 +38,64 @@ interface CommandPaletteProps {
   onOpenSearch: () => void;
 }
 
+type CommandCategory = "actions" | "navigation" | "models" | "settings";
+
 interface CommandItem {
   id: string;
   label: string;
+  /** Label characters to highlight */
+  positions: number[];
   description?: string;
   icon: React.ReactNode;
   shortcut?: string;
   action: () => void;
-  category: "actions" | "navigation" | "models" | "settings";
+  category: CommandCategory;
+}
+
+const CATEGORIES: Record<PaletteKind, CommandCategory> = {
+  action: "actions",
+  preset: "actions",
+  conversation: "navigation",
+  model: "models",
+  setting: "settings",
+};
+
+const GROUP_LABELS: Record<CommandCategory, string> = {
+  actions: "Actions",
+  navigation: "Recent Chats",
+  models: "Models",
+  settings: "Settings",
+};
+
+const ACTION_ICONS: Record<string, React.ReactNode> = {
+  new_conversation: <Plus className="w-4 h-4" />,
+  quick_ask: <Zap className="w-4 h-4" />,
+  search: <Search className="w-4 h-4" />,
+  export: <Download className="w-4 h-4" />,
+  email: <Mail className="w-4 h-4" />,
+  toggle_sidebar: <PanelLeft className="w-4 h-4" />,
+  preferences: <Settings className="w-4 h-4" />,
+  repair: <KeyRound className="w-4 h-4" />,
+  "theme-light": <Sun className="w-4 h-4" />,
+  "theme-dark": <Moon className="w-4 h-4" />,
+  "theme-system": <Monitor className="w-4 h-4" />,
+  "compact-mode": <Zap className="w-4 h-4" />,
+  "reinitialize-integration": <RefreshCw className="w-4 h-4" />,
+};
+
+function iconFor(match: PaletteMatch): React.ReactNode {
+  const icon = ACTION_ICONS[match.id];
+  if (icon) return icon;
+  switch (match.kind) {
+    case "preset":
+      return <MessageSquarePlus className="w-4 h-4" />;
+    case "conversation":
+      return <MessageSquare className="w-4 h-4" />;
+    case "model":
+      return <Cpu className="w-4 h-4" />;
+    default:
+      return <Settings className="w-4 h-4" />;
+  }
 }
 
 export function CommandPalette({
@@ -42,205 +106,162 @@ export function CommandPalette({
 }: CommandPaletteProps) {
   const [query, setQuery] = useState("");
   const [selectedIndex, setSelectedIndex] = useState(0);
+  const [presets, setPresets] = useState<Preset[]>([]);
+  const [matches, setMatches] = useState<PaletteMatch[]>([]);
+  const [indexed, setIndexed] = useState(false);
   const inputRef = useRef<HTMLInputElement>(null);
   const listRef = useRef<HTMLDivElement>(null);
 
   const {
-    createConversation,
     selectConversation,
     conversations,
     settings,
     updateSettings,
-    availableModels,
     currentConversationId,
   } = useStore(
     useShallow((state) => ({
-      createConversation: state.createConversation,
       selectConversation: state.selectConversation,
       conversations: state.conversations,
       settings: state.settings,
       updateSettings: state.updateSettings,
-      availableModels: state.availableModels,
       currentConversationId: state.currentConversationId,
     }))
   );
 
-  const currentConversation = currentConversationId
-    ? conversations.find((c) => c.id === currentConversationId)
-    : null;
+  // Hand the backend the conversation index when opened
+  useEffect(() => {
+    if (!open) return;
+    setIndexed(false);
+    setPaletteConversations(
+      conversations
+        .filter((conv) => conv.id !== currentConversationId)
+        .map((conv) => ({
+          id: conv.id,
+          title: conv.title,
+          updatedAt: new Date(conv.updatedAt).toISOString(),
+          messageCount: conv.messages.length,
+        }))
+    )
+      .catch((err) => console.error("Failed to index conversations:", err))
+      .finally(() => setIndexed(true));
+  }, [open, conversations, currentConversationId]);
 
-  // Build command list
-  const commands = useMemo<CommandItem[]>(() => {
-    const items: CommandItem[] = [
-      // Actions
-      {
-        id: "new-chat",
-        label: "New Chat",
-        description: "Start a new conversation",
-        icon: <Plus className="w-4 h-4" />,
-        shortcut: "⌘N",
-        action: () => {
-          createConversation();
-          onClose();
-        },
-        category: "actions",
-      },
-      {
-        id: "search",
-        label: "Search Messages",
-        description: "Search across all conversations",
-        icon: <Search className="w-4 h-4" />,
-        shortcut: "⌘F",
-        action: () => {
-          onClose();
-          onOpenSearch();
-        },
-        category: "actions",
-      },
-      // Settings
-      {
-        id: "settings",
-        label: "Open Settings",
-        description: "Configure Moltz",
-        icon: <Settings className="w-4 h-4" />,
-        shortcut: "⌘,",
-        action: () => {
-          onClose();
-          onOpenSettings();
-        },
-        category: "settings",
-      },
-      {
-        id: "toggle-compact",
-        label: settings.compactMode ? "Disable Compact Mode" : "Enable Compact Mode",
-        description: "Toggle information density",
-        icon: <Zap className="w-4 h-4" />,
-        action: () => {
-          updateSettings({ compactMode: !settings.compactMode });
-          onClose();
-        },
-        category: "settings",
-      },
-      {
-        id: "toggle-sidebar",
-        label: settings.sidebarCollapsed ? "Expand Sidebar" : "Collapse Sidebar",
-        description: "Toggle sidebar visibility",
-        icon: settings.sidebarCollapsed ? <PanelLeft className="w-4 h-4" /> : <PanelLeftClose className="w-4 h-4" />,
-        shortcut: "⌘\\",
-        action: () => {
-          updateSettings({ sidebarCollapsed: !settings.sidebarCollapsed });
-          onClose();
-        },
-        category: "settings",
-      },
-      // Theme
-      {
-        id: "theme-light",
-        label: "Light Theme",
-        icon: <Sun className="w-4 h-4" />,
-        action: () => {
-          updateSettings({ theme: "light" });
-          onClose();
-        },
-        category: "settings",
-      },
-      {
-        id: "theme-dark",
-        label: "Dark Theme",
-        icon: <Moon className="w-4 h-4" />,
-        action: () => {
-          updateSettings({ theme: "dark" });
-          onClose();
-        },
-        category: "settings",
-      },
-      {
-        id: "theme-system",
-        label: "System Theme",
-        icon: <Monitor className="w-4 h-4" />,
-        action: () => {
-          updateSettings({ theme: "system" });
-          onClose();
-        },
-        category: "settings",
-      },
-    ];
+  // Ranked entries for the query; stale responses are dropped
+  useEffect(() => {
+    if (!open || !indexed) return;
+    let current = true;
+    paletteQuery(query)
+      .then((results) => {
+        if (current) setMatches(results);
+      })
+      .catch((err) => console.error("Palette query failed:", err));
+    return () => {
+      current = false;
+    };
+  }, [open, indexed, query, presets]);
 
-    // Add model switching commands
-    availableModels.forEach((model: ModelInfo) => {
-      const isCurrentDefault = settings.defaultModel === model.id;
-      items.push({
-        id: `model-${model.id}`,
-        label: `Switch to ${model.name}`,
-        description: isCurrentDefault ? "Current default" : model.provider,
-        icon: <Cpu className="w-4 h-4" />,
-        action: () => {
-          if (currentConversation) {
-            useStore.getState().updateConversation(currentConversation.id, { model: model.id });
+  const runEntry = useCallback(
+    (match: PaletteMatch) => {
+      onClose();
+      switch (match.kind) {
+        case "action":
+          // Dialogs owned by the app, the rest go through the menu handlers
+          if (match.id === "search") {
+            onOpenSearch();
+          } else if (match.id === "preferences") {
+            onOpenSettings();
+          } else {
+            runPaletteAction(match.id).catch((err) =>
+              console.error("Failed to run palette action:", err)
+            );
           }
-          onClose();
-        },
-        category: "models",
-      });
-    });
-
-    // Add recent conversations for quick navigation
-    const recentConvs = conversations.slice(0, 5);
-    recentConvs.forEach((conv) => {
-      if (conv.id !== currentConversationId) {
-        items.push({
-          id: `conv-${conv.id}`,
-          label: conv.title || "Untitled",
-          description: `${conv.messages.length} messages`,
-          icon: <MessageSquare className="w-4 h-4" />,
-          action: () => {
-            selectConversation(conv.id);
-            onClose();
-          },
-          category: "navigation",
-        });
+          break;
+        case "preset": {
+          const preset = presets.find((p) => p.id === match.id);
+          if (preset) {
+            createConversationFromPreset(preset).catch((err) =>
+              console.error("Failed to apply preset:", err)
+            );
+          }
+          break;
+        }
+        case "conversation":
+          selectConversation(match.id);
+          break;
+        case "model": {
+          const state = useStore.getState();
+          if (state.currentConversationId) {
+            state.updateConversation(state.currentConversationId, {
+              model: match.id,
+            });
+          }
+          break;
+        }
+        case "setting":
+          if (match.id.startsWith("settings:")) {
+            onOpenSettings();
+          } else if (match.id === "compact-mode") {
+            updateSettings({ compactMode: !settings.compactMode });
+          } else if (match.id === "reinitialize-integration") {
+            reinitializeSystemIntegration().catch((err) =>
+              console.error("Failed to re-register tray and shortcuts:", err)
+            );
+          } else if (match.id.startsWith("theme-")) {
+            updateSettings({
+              theme: match.id.slice("theme-".length) as
+                | "light"
+                | "dark"
+                | "system",
+            });
+          }
+          break;
       }
-    });
-
-    return items;
-  }, [
-    createConversation,
-    onClose,
-    onOpenSearch,
-    onOpenSettings,
-    settings,
-    updateSettings,
-    availableModels,
-    conversations,
-    currentConversation,
-    currentConversationId,
-    selectConversation,
-  ]);
+    },
+    [
+      onClose,
+      onOpenSearch,
+      onOpenSettings,
+      presets,
+      selectConversation,
+      settings.compactMode,
+      updateSettings,
+    ]
+  );
 
-  // Filter commands based on query
-  const filteredCommands = useMemo(() => {
-    if (!query) return commands;
-    const lowerQuery = query.toLowerCase();
-    return commands.filter(
-      (cmd) =>
-        cmd.label.toLowerCase().includes(lowerQuery) ||
-        cmd.description?.toLowerCase().includes(lowerQuery)
-    );
-  }, [commands, query]);
+  const commands = useMemo<CommandItem[]>(
+    () =>
+      matches.map((match) => ({
+        id: `${match.kind}-${match.id}`,
+        label: match.label,
+        positions: match.positions,
+        description:
+          match.kind === "model" && match.id === settings.defaultModel
+            ? "Current default"
+            : match.detail ?? undefined,
+        icon: iconFor(match),
+        shortcut: match.shortcut ? formatShortcut(match.shortcut) : undefined,
+        action: () => runEntry(match),
+        category: CATEGORIES[match.kind],
+      })),
+    [matches, runEntry, settings.defaultModel]
+  );
 
-  // Group commands by category
+  // Group commands by category, groups ordered by their best match
   const groupedCommands = useMemo(() => {
-    const groups: Record<string, CommandItem[]> = {
-      actions: [],
-      navigation: [],
-      models: [],
-      settings: [],
-    };
-    filteredCommands.forEach((cmd) => {
-      groups[cmd.category].push(cmd);
+    const groups = new Map<CommandCategory, CommandItem[]>();
+    commands.forEach((cmd) => {
+      const group = groups.get(cmd.category) ?? [];
+      group.push(cmd);
+      groups.set(cmd.category, group);
     });
-    return groups;
-  }, [filteredCommands]);
+    return Array.from(groups.entries());
+  }, [commands]);
+
+  // Commands in display order, for keyboard navigation
+  const filteredCommands = useMemo(
+    () => groupedCommands.flatMap(([, group]) => group),
+    [groupedCommands]
+  );
 
   // Reset selection when query changes
   useEffect(() => {
@@ -256,6 +277,14 @@ export function CommandPalette({
     }
   }, [open]);
 
+  // Refresh presets when opened (they are edited in Settings)
+  useEffect(() => {
+    if (!open) return;
+    listPresets()
+      .then((list) => setPresets(list ?? []))
+      .catch(() => setPresets([]));
+  }, [open]);
+
   // Keyboard navigation
   const handleKeyDown = useCallback(
     (e: React.KeyboardEvent) => {
@@ -332,64 +361,9 @@ export function CommandPalette({
               </div>
             ) : (
               <>
-                {/* Actions */}
-                {groupedCommands.actions.length > 0 && (
-                  <CommandGroup label="Actions">
-                    {groupedCommands.actions.map((cmd) => {
-                      const index = flatIndex++;
-                      return (
-                        <CommandRow
-                          key={cmd.id}
-                          command={cmd}
-                          selected={index === selectedIndex}
-                          onSelect={cmd.action}
-                          onHover={() => setSelectedIndex(index)}
-                        />
-                      );
-                    })}
-                  </CommandGroup>
-                )}
-
-                {/* Navigation */}
-                {groupedCommands.navigation.length > 0 && (
-                  <CommandGroup label="Recent Chats">
-                    {groupedCommands.navigation.map((cmd) => {
-                      const index = flatIndex++;
-                      return (
-                        <CommandRow
-                          key={cmd.id}
-                          command={cmd}
-                          selected={index === selectedIndex}
-                          onSelect={cmd.action}
-                          onHover={() => setSelectedIndex(index)}
-                        />
-                      );
-                    })}
-                  </CommandGroup>
-                )}
-
-                {/* Models */}
-                {groupedCommands.models.length > 0 && (
-                  <CommandGroup label="Models">
-                    {groupedCommands.models.map((cmd) => {
-                      const index = flatIndex++;
-                      return (
-                        <CommandRow
-                          key={cmd.id}
-                          command={cmd}
-                          selected={index === selectedIndex}
-                          onSelect={cmd.action}
-                          onHover={() => setSelectedIndex(index)}
-                        />
-                      );
-                    })}
-                  </CommandGroup>
-                )}
-
-                {/* Settings */}
-                {groupedCommands.settings.length > 0 && (
-                  <CommandGroup label="Settings">
-                    {groupedCommands.settings.map((cmd) => {
+                {groupedCommands.map(([category, group]) => (
+                  <CommandGroup key={category} label={GROUP_LABELS[category]}>
+                    {group.map((cmd) => {
                       const index = flatIndex++;
                       return (
                         <CommandRow
@@ -402,7 +376,7 @@ export function CommandPalette({
                       );
                     })}
                   </CommandGroup>
-                )}
+                ))}
               </>
             )}
           </div>
@@ -467,7 +441,9 @@ function CommandRow({
         {command.icon}
       </span>
       <div className="flex-1 min-w-0">
-        <div className="text-sm font-medium truncate">{command.label}</div>
+        <div className="text-sm font-medium truncate">
+          <HighlightedLabel label={command.label} positions={command.positions} />
+        </div>
         {command.description && (
           <div className="text-xs text-muted-foreground truncate">
             {command.description}
@@ -482,3 +458,28 @@ function CommandRow({
     </button>
   );
 }
+
+/** Label with the characters that matched the query emphasized */
+function HighlightedLabel({
+  label,
+  positions,
+}: {
+  label: string;
+  positions: number[];
+}) {
+  if (positions.length === 0) return <>{label}</>;
+  const matched = new Set(positions);
+  return (
+    <>
+      {Array.from(label).map((char, i) =>
+        matched.has(i) ? (
+          <span key={i} className="text-primary underline underline-offset-2">
+            {char}
+          </span>
+        ) : (
+          char
+        )
+      )}
+    </>
+  );
+}
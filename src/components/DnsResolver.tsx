@@ -0,0 +1,226 @@
+import { useCallback, useEffect, useState } from "react";
+import { X } from "lucide-react";
+import {
+  DOH_PRESETS,
+  DnsMode,
+  DnsSettings,
+  clearDnsCache,
+  gatewayHost,
+  getDnsSettings,
+  setDnsSettings,
+  testDns,
+} from "../lib/dns";
+import { errorMessage } from "../lib/errors";
+
+interface DnsResolverProps {
+  /** Gateway URL being edited; its host can get its own resolver */
+  gatewayUrl: string;
+  onError: (message: string) => void;
+  onSuccess: (message: string) => void;
+}
+
+const describe = (mode: DnsMode) =>
+  mode.mode === "custom"
+    ? mode.server
+    : mode.mode === "doh"
+      ? mode.url
+      : "System DNS";
+
+/** Address of a resolved socket address, without the port */
+const addressOf = (addr: string) => addr.replace(/:\d+$/, "");
+
+/**
+ * Settings section to pick the resolver used to find the Gateway
+ */
+export function DnsResolver({
+  gatewayUrl,
+  onError,
+  onSuccess,
+}: DnsResolverProps) {
+  const host = gatewayHost(gatewayUrl);
+  const [settings, setSettings] = useState<DnsSettings | null>(null);
+  /** Edit the override of the Gateway host instead of the default */
+  const [perHost, setPerHost] = useState(false);
+  const [draft, setDraft] = useState<DnsMode>({ mode: "system" });
+  const [testResult, setTestResult] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      const current = await getDnsSettings();
+      setSettings(current);
+      const override = host ? current.overrides[host] : undefined;
+      setPerHost(!!override);
+      setDraft(override ?? current.default);
+    } catch (err) {
+      onError(`Failed to load DNS settings: ${errorMessage(err)}`);
+    }
+  }, [host, onError]);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  if (!settings) return null;
+
+  const changeMode = (mode: DnsMode["mode"]) => {
+    setTestResult(null);
+    if (mode === "custom") setDraft({ mode, server: "" });
+    else if (mode === "doh") setDraft({ mode, url: DOH_PRESETS[0] });
+    else setDraft({ mode });
+  };
+
+  const save = async (next: DnsSettings) => {
+    try {
+      await setDnsSettings(next);
+      onSuccess("DNS settings saved");
+      await refresh();
+    } catch (err) {
+      onError(`Failed to save DNS settings: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleSave = () => {
+    const overrides = { ...settings.overrides };
+    if (host) delete overrides[host];
+    if (perHost && host) {
+      void save({ ...settings, overrides: { ...overrides, [host]: draft } });
+    } else {
+      void save({ default: draft, overrides });
+    }
+  };
+
+  const handleRemoveOverride = (overrideHost: string) => {
+    const overrides = { ...settings.overrides };
+    delete overrides[overrideHost];
+    void save({ ...settings, overrides });
+  };
+
+  const handleTest = async () => {
+    if (!host) return;
+    setTestResult("Resolving…");
+    try {
+      const lookup = await testDns(host, draft);
+      setTestResult(
+        `${lookup.addrs.map(addressOf).join(", ") || "No addresses"} via ${lookup.via}${lookup.cached ? " (cached)" : ""}`,
+      );
+    } catch (err) {
+      setTestResult(errorMessage(err));
+    }
+  };
+
+  const inputClass =
+    "w-full px-3 py-2 text-sm border border-border rounded-lg bg-background focus:outline-none focus:ring-2 focus:ring-primary/50";
+  const buttonClass =
+    "px-3 py-1.5 rounded-lg text-xs font-medium border border-border hover:bg-muted transition-colors disabled:opacity-50";
+  const otherOverrides = Object.entries(settings.overrides).filter(
+    ([overrideHost]) => overrideHost !== host,
+  );
+
+  return (
+    <div className="p-3 border border-border rounded-xl space-y-3">
+      <p className="text-xs text-muted-foreground">
+        Resolve the Gateway with another resolver when your network&apos;s DNS
+        blocks or slows Tailscale names.
+      </p>
+      <div className="grid grid-cols-2 gap-2">
+        <select
+          value={draft.mode}
+          onChange={(e) => changeMode(e.target.value as DnsMode["mode"])}
+          aria-label="DNS resolver"
+          className={inputClass}
+        >
+          <option value="system">System DNS</option>
+          <option value="custom">Custom DNS server</option>
+          <option value="doh">DNS-over-HTTPS</option>
+        </select>
+        <select
+          value={perHost ? "host" : "all"}
+          onChange={(e) => setPerHost(e.target.value === "host")}
+          disabled={!host}
+          aria-label="Apply resolver to"
+          className={inputClass}
+        >
+          <option value="all">All Gateways</option>
+          {host && <option value="host">Only {host}</option>}
+        </select>
+      </div>
+      {draft.mode === "custom" && (
+        <input
+          type="text"
+          value={draft.server}
+          onChange={(e) => setDraft({ mode: "custom", server: e.target.value })}
+          placeholder="DNS server IP, e.g. 100.100.100.100"
+          className={inputClass}
+        />
+      )}
+      {draft.mode === "doh" && (
+        <>
+          <input
+            type="url"
+            value={draft.url}
+            onChange={(e) => setDraft({ mode: "doh", url: e.target.value })}
+            list="doh-presets"
+            placeholder="https://1.1.1.1/dns-query"
+            className={inputClass}
+          />
+          <datalist id="doh-presets">
+            {DOH_PRESETS.map((url) => (
+              <option key={url} value={url} />
+            ))}
+          </datalist>
+        </>
+      )}
+      {testResult && (
+        <p className="text-xs text-muted-foreground break-all">{testResult}</p>
+      )}
+      <div className="flex flex-wrap gap-2">
+        <button type="button" onClick={handleSave} className={buttonClass}>
+          Save
+        </button>
+        <button
+          type="button"
+          onClick={handleTest}
+          disabled={!host}
+          className={buttonClass}
+        >
+          Test
+        </button>
+        <button
+          type="button"
+          onClick={() =>
+            clearDnsCache()
+              .then(() => onSuccess("DNS cache cleared"))
+              .catch((err) =>
+                onError(`Failed to clear DNS cache: ${errorMessage(err)}`),
+              )
+          }
+          className={buttonClass}
+        >
+          Clear cache
+        </button>
+      </div>
+      {otherOverrides.length > 0 && (
+        <div className="space-y-1">
+          {otherOverrides.map(([overrideHost, mode]) => (
+            <div
+              key={overrideHost}
+              className="flex items-center justify-between gap-2 text-xs text-muted-foreground"
+            >
+              <span className="truncate">
+                {overrideHost} → {describe(mode)}
+              </span>
+              <button
+                type="button"
+                onClick={() => handleRemoveOverride(overrideHost)}
+                className="p-1 rounded hover:text-destructive hover:bg-muted transition-colors"
+                aria-label={`Remove resolver for ${overrideHost}`}
+              >
+                <X className="w-3 h-3" />
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
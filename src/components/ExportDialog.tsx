@@ -15,7 +15,10 @@ import {
   generateFilename,
   getFileExtension,
 } from "../lib/export";
-import { translateError } from "../lib/errors";
+import { exportToObsidian } from "../lib/obsidian";
+import { emailConversation } from "../lib/email";
+import { addRecentFile } from "../lib/recentFiles";
+import { errorMessage, translateError } from "../lib/errors";
 import { Button } from "./ui/button";
 import {
   X,
@@ -29,6 +32,7 @@ import {
   Loader2,
   RotateCcw,
   AlertTriangle,
+  Mail,
 } from "lucide-react";
 import { cn } from "../lib/utils";
 import { useFocusTrap } from "../lib/useFocusTrap";
@@ -138,11 +142,12 @@ export function ExportDialog({
 
       if (filePath) {
         await writeTextFile(filePath, content);
+        void addRecentFile(filePath);
         onClose();
       }
     } catch (err: unknown) {
       console.error("Export failed:", err);
-      const friendly = translateError(err instanceof Error ? err : String(err));
+      const friendly = translateError(errorMessage(err));
       setError(
         `${friendly.title}: ${friendly.message}${friendly.suggestion ? "\n" + friendly.suggestion : ""}`,
       );
@@ -151,6 +156,50 @@ export function ExportDialog({
     }
   };
 
+  const handleObsidianExport = async () => {
+    setIsExporting(true);
+    setError(null);
+
+    try {
+      const result = await exportToObsidian([conversation]);
+      if (result) {
+        onClose();
+      }
+    } catch (err: unknown) {
+      console.error("Obsidian export failed:", err);
+      const friendly = translateError(errorMessage(err));
+      setError(
+        `${friendly.title}: ${friendly.message}${friendly.suggestion ? "\n" + friendly.suggestion : ""}`,
+      );
+    } finally {
+      setIsExporting(false);
+    }
+  };
+
+  const handleEmail = async () => {
+    setError(null);
+    try {
+      const draft = await emailConversation(conversation, {
+        includeTimestamps,
+        includeMetadata,
+        includeThinking,
+      });
+      if (draft?.truncated) {
+        setError(
+          "The conversation was too long for an email link and was shortened. Save it to a file to share the full text.",
+        );
+        return;
+      }
+      onClose();
+    } catch (err: unknown) {
+      console.error("Email export failed:", err);
+      const friendly = translateError(errorMessage(err));
+      setError(
+        `${friendly.title}: ${friendly.message}${friendly.suggestion ? "\n" + friendly.suggestion : ""}`,
+      );
+    }
+  };
+
   const handleCopyToClipboard = async () => {
     try {
       const content = exportConversation(conversation, {
@@ -165,7 +214,7 @@ export function ExportDialog({
       setTimeout(() => setCopied(false), 2000);
     } catch (err: unknown) {
       console.error("Copy failed:", err);
-      const friendly = translateError(err instanceof Error ? err : String(err));
+      const friendly = translateError(errorMessage(err));
       setError(
         `${friendly.title}: ${friendly.message}${friendly.suggestion ? "\n" + friendly.suggestion : ""}`,
       );
@@ -369,6 +418,25 @@ export function ExportDialog({
               >
                 Cancel
               </Button>
+              <Button
+                onClick={handleEmail}
+                variant="ghost"
+                size="sm"
+                leftIcon={<Mail className="w-4 h-4" />}
+                disabled={isExporting}
+                title="Open a draft with this conversation in your mail app"
+              >
+                Email…
+              </Button>
+              <Button
+                onClick={handleObsidianExport}
+                variant="ghost"
+                size="sm"
+                disabled={isExporting}
+                title="Write this conversation as a note into your Obsidian vault"
+              >
+                Obsidian
+              </Button>
               <Button
                 onClick={handleExport}
                 variant="primary"
@@ -0,0 +1,162 @@
+import { useEffect, useState } from "react";
+import { UserX } from "lucide-react";
+import { errorMessage } from "../lib/errors";
+import {
+  GUEST_DURATIONS_MINUTES,
+  GuestStatus,
+  endGuestMode,
+  getGuestStatus,
+  onGuestStarted,
+  startGuestMode,
+} from "../lib/guest";
+import { useStore } from "../stores/store";
+
+function useGuestStatus(): GuestStatus | null {
+  const [status, setStatus] = useState<GuestStatus | null>(null);
+
+  useEffect(() => {
+    getGuestStatus()
+      .then(setStatus)
+      .catch(() => setStatus(null));
+    const unlisten = onGuestStarted(setStatus);
+    return () => {
+      unlisten.then((fn) => fn()).catch(() => {});
+    };
+  }, []);
+
+  return status;
+}
+
+function formatDuration(minutes: number): string {
+  return minutes < 60 ? `${minutes} minutes` : `${minutes / 60} h`;
+}
+
+interface GuestModeSettingsProps {
+  onError: (message: string) => void;
+  onSuccess: (message: string) => void;
+}
+
+/**
+ * Settings section starting a time-boxed guest session
+ */
+export function GuestModeSettings({
+  onError,
+  onSuccess,
+}: GuestModeSettingsProps) {
+  const status = useGuestStatus();
+  const [minutes, setMinutes] = useState(60);
+  const [busy, setBusy] = useState(false);
+
+  const handleStart = async () => {
+    setBusy(true);
+    try {
+      await startGuestMode(minutes);
+      // The owner's conversations stay out of reach until the restart
+      useStore.setState({ conversations: [], currentConversationId: null });
+      onSuccess("Guest mode on");
+    } catch (err) {
+      onError(`Failed to start guest mode: ${errorMessage(err)}`);
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleEnd = () => {
+    endGuestMode().catch((err) =>
+      onError(`Failed to end guest mode: ${errorMessage(err)}`),
+    );
+  };
+
+  const buttonClass =
+    "px-3 py-1.5 rounded-lg text-xs font-medium border border-border hover:bg-muted transition-colors disabled:opacity-50";
+
+  return (
+    <div className="p-3 border border-border rounded-xl space-y-3">
+      <p className="text-xs text-muted-foreground">
+        Conversations, settings and credentials are kept in memory only, and
+        no usage is recorded. Everything is wiped and Moltz restarts when the
+        time is up, when you end it, or when you quit.
+      </p>
+      {status?.active ? (
+        <div className="flex items-center justify-between gap-3">
+          <span className="text-xs text-muted-foreground">
+            Guest mode ends at{" "}
+            {status.endsAt && new Date(status.endsAt).toLocaleTimeString()}
+          </span>
+          <button type="button" onClick={handleEnd} className={buttonClass}>
+            End now
+          </button>
+        </div>
+      ) : (
+        <div className="flex items-center justify-between gap-3">
+          <label className="flex items-center gap-2 text-xs text-muted-foreground">
+            For
+            <select
+              value={minutes}
+              onChange={(e) => setMinutes(Number(e.target.value))}
+              className="px-2 py-1.5 text-sm border border-border rounded-lg bg-background focus:outline-none focus:ring-2 focus:ring-primary/50"
+            >
+              {GUEST_DURATIONS_MINUTES.map((option) => (
+                <option key={option} value={option}>
+                  {formatDuration(option)}
+                </option>
+              ))}
+            </select>
+          </label>
+          <button
+            type="button"
+            onClick={handleStart}
+            disabled={busy}
+            className={buttonClass}
+          >
+            Start guest mode
+          </button>
+        </div>
+      )}
+    </div>
+  );
+}
+
+/**
+ * Banner shown for the whole guest session, with the time left
+ */
+export function GuestModeBanner() {
+  const status = useGuestStatus();
+  const [now, setNow] = useState(() => Date.now());
+
+  useEffect(() => {
+    if (!status?.active) return;
+    const timer = window.setInterval(() => setNow(Date.now()), 30_000);
+    return () => window.clearInterval(timer);
+  }, [status?.active]);
+
+  if (!status?.active || !status.endsAt) {
+    return null;
+  }
+
+  const minutesLeft = Math.max(
+    0,
+    Math.ceil((new Date(status.endsAt).getTime() - now) / 60_000),
+  );
+
+  return (
+    <div
+      role="status"
+      className="fixed top-2 left-1/2 -translate-x-1/2 z-50 flex items-center gap-3 px-3 py-1.5 rounded-full shadow-lg bg-amber-100 dark:bg-amber-900/60 text-amber-900 dark:text-amber-100 text-xs"
+    >
+      <UserX className="w-3.5 h-3.5" />
+      <span>Guest mode · {minutesLeft} min left · nothing is saved</span>
+      <button
+        type="button"
+        onClick={() => {
+          endGuestMode().catch((err) =>
+            console.error("Failed to end guest mode:", err),
+          );
+        }}
+        className="font-medium hover:underline"
+      >
+        End now
+      </button>
+    </div>
+  );
+}
@@ -0,0 +1,118 @@
+import { useCallback, useEffect, useState } from "react";
+import { Switch } from "./ui/switch";
+import {
+  ActiveEndpoint,
+  EndpointSettings,
+  getActiveEndpoint,
+  getEndpointSettings,
+  onActiveEndpoint,
+  setEndpointSettings,
+} from "../lib/endpoints";
+import { errorMessage } from "../lib/errors";
+
+interface HomeAwayEndpointsProps {
+  /** Gateway URL being edited, the away URL when none is entered */
+  gatewayUrl: string;
+  onError: (message: string) => void;
+  onSuccess: (message: string) => void;
+}
+
+/**
+ * Settings section for a LAN URL used at home and another URL used away
+ */
+export function HomeAwayEndpoints({
+  gatewayUrl,
+  onError,
+  onSuccess,
+}: HomeAwayEndpointsProps) {
+  const [draft, setDraft] = useState<EndpointSettings | null>(null);
+  const [active, setActive] = useState<ActiveEndpoint | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      const [settings, current] = await Promise.all([
+        getEndpointSettings(),
+        getActiveEndpoint(),
+      ]);
+      setDraft(settings);
+      setActive(current);
+    } catch (err) {
+      onError(`Failed to load endpoints: ${errorMessage(err)}`);
+    }
+  }, [onError]);
+
+  useEffect(() => {
+    void refresh();
+    const unlisten = onActiveEndpoint(setActive);
+    return () => {
+      void unlisten.then((fn) => fn());
+    };
+  }, [refresh]);
+
+  if (!draft) return null;
+
+  const save = async (edited: EndpointSettings) => {
+    const next = { ...edited, awayUrl: edited.awayUrl.trim() || gatewayUrl };
+    try {
+      await setEndpointSettings(next);
+      setDraft(next);
+      onSuccess(
+        next.enabled ? "Home/away endpoints saved" : "Home/away switching off",
+      );
+    } catch (err) {
+      onError(`Failed to save endpoints: ${errorMessage(err)}`);
+    }
+  };
+
+  const inputClass =
+    "w-full px-3 py-2 text-sm border border-border rounded-lg bg-background focus:outline-none focus:ring-2 focus:ring-primary/50";
+
+  return (
+    <div className="p-3 border border-border rounded-xl space-y-3">
+      <div className="flex items-center justify-between gap-3">
+        <div>
+          <p className="text-sm font-medium">Switch between home and away</p>
+          <p className="text-xs text-muted-foreground">
+            Use the LAN URL when it is reachable and the other URL elsewhere,
+            reconnecting when the network changes.
+          </p>
+        </div>
+        <Switch
+          checked={draft.enabled}
+          onCheckedChange={(enabled) => void save({ ...draft, enabled })}
+          aria-label="Switch between home and away endpoints"
+        />
+      </div>
+      <input
+        type="text"
+        value={draft.homeUrl}
+        onChange={(e) => setDraft({ ...draft, homeUrl: e.target.value })}
+        placeholder="Home (LAN) URL, e.g. ws://192.168.1.20:18789"
+        className={inputClass}
+      />
+      <input
+        type="text"
+        value={draft.awayUrl}
+        onChange={(e) => setDraft({ ...draft, awayUrl: e.target.value })}
+        placeholder={
+          gatewayUrl || "Away URL, e.g. wss://gateway.tail1234.ts.net"
+        }
+        className={inputClass}
+      />
+      <div className="flex items-center justify-between gap-2">
+        <span className="text-xs text-muted-foreground truncate">
+          {active
+            ? `Using the ${active.endpoint} endpoint (${active.url})`
+            : "Not connected through these endpoints"}
+        </span>
+        <button
+          type="button"
+          onClick={() => void save(draft)}
+          className="px-3 py-1.5 rounded-lg text-xs font-medium border border-border hover:bg-muted transition-colors flex-shrink-0"
+        >
+          Save
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -1,6 +1,14 @@
 import { useState, useCallback, SyntheticEvent, MouseEvent } from "react";
 import { cn } from "../lib/utils";
-import { X, Loader2, ImageOff, ExternalLink, ZoomIn } from "lucide-react";
+import { useDataSaver } from "../lib/dataSaver";
+import {
+  X,
+  Loader2,
+  ImageOff,
+  ExternalLink,
+  ZoomIn,
+  Image as ImageIcon,
+} from "lucide-react";
 
 interface ImageRendererProps {
   src: string;
@@ -48,6 +56,8 @@ export function ImageRenderer({
   const [isLoading, setIsLoading] = useState(true);
   const [hasError, setHasError] = useState(false);
   const [isExpanded, setIsExpanded] = useState(false);
+  const [loadRemote, setLoadRemote] = useState(false);
+  const dataSaver = useDataSaver();
   const [dimensions, setDimensions] = useState<{
     width: number;
     height: number;
@@ -92,6 +102,24 @@ export function ImageRenderer({
     );
   }
 
+  // Data saver: remote images load on demand
+  if (dataSaver && !loadRemote && /^https?:\/\//i.test(sanitizedSrc)) {
+    return (
+      <button
+        type="button"
+        onClick={() => setLoadRemote(true)}
+        className={cn(
+          "inline-flex items-center gap-2 px-3 py-2 bg-muted hover:bg-muted/80 rounded-lg text-sm text-muted-foreground transition-colors",
+          className,
+        )}
+        title={sanitizedSrc}
+      >
+        <ImageIcon className="w-4 h-4" />
+        <span>Load image (data saver is on)</span>
+      </button>
+    );
+  }
+
   // Calculate constrained dimensions
   const getConstrainedStyle = () => {
     if (!dimensions) return { maxWidth, maxHeight };
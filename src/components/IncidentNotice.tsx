@@ -0,0 +1,147 @@
+import { useState, useEffect } from "react";
+import { motion, AnimatePresence } from "framer-motion";
+import { WifiOff, X, AlertCircle } from "lucide-react";
+import { open } from "@tauri-apps/plugin-shell";
+import { cn } from "../lib/utils";
+import { errorMessage } from "../lib/errors";
+import {
+  getIncident,
+  incidentReportUrl,
+  onIncidentCreated,
+  onIncidentOpen,
+  type Incident,
+  type IncidentSummary,
+} from "../lib/incidents";
+
+/** Log lines shown in the details */
+const LOG_TAIL = 20;
+
+/**
+ * Point at the incident report saved after repeated connection failures,
+ * with a prefilled bug report and the captured details
+ */
+export function IncidentNotice() {
+  const [incident, setIncident] = useState<IncidentSummary | null>(null);
+  const [details, setDetails] = useState<Incident | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const showDetails = async (id: string) => {
+    setError(null);
+    try {
+      const full = await getIncident(id);
+      setIncident(full);
+      setDetails(full);
+    } catch (err) {
+      setError(errorMessage(err));
+    }
+  };
+
+  useEffect(() => {
+    const unlisteners = [
+      onIncidentCreated((summary) => {
+        setIncident(summary);
+        setDetails(null);
+        setError(null);
+      }),
+      onIncidentOpen((id) => void showDetails(id)),
+    ];
+    return () => {
+      unlisteners.forEach((p) => p.then((unlisten) => unlisten()));
+    };
+  }, []);
+
+  if (!incident) {
+    return null;
+  }
+
+  const handleDismiss = () => {
+    setIncident(null);
+    setDetails(null);
+    setError(null);
+  };
+
+  const handleReport = async () => {
+    setError(null);
+    try {
+      await open(await incidentReportUrl(incident.id));
+    } catch (err) {
+      setError(errorMessage(err));
+    }
+  };
+
+  const linkClass =
+    "text-xs font-medium text-blue-600 dark:text-blue-400 hover:underline";
+
+  return (
+    <AnimatePresence>
+      <motion.div
+        initial={{ opacity: 0, y: 50 }}
+        animate={{ opacity: 1, y: 0 }}
+        exit={{ opacity: 0, y: 50 }}
+        role="alert"
+        className={cn(
+          "fixed bottom-4 right-4 z-50",
+          "w-96 rounded-lg shadow-2xl",
+          "bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700",
+          "overflow-hidden",
+        )}
+      >
+        <div className="flex items-start gap-3 p-4 pb-3 border-b border-gray-200 dark:border-gray-700">
+          <div className="flex-shrink-0 w-10 h-10 rounded-full bg-red-100 dark:bg-red-900/30 flex items-center justify-center">
+            <WifiOff className="w-5 h-5 text-red-600 dark:text-red-400" />
+          </div>
+          <div className="flex-1 min-w-0">
+            <h3 className="font-semibold text-sm text-gray-900 dark:text-white">
+              Connection keeps failing
+            </h3>
+            <p className="text-xs text-gray-600 dark:text-gray-400 mt-0.5">
+              Reconnecting failed {incident.failedCycles} times in a row. A
+              report was saved to the logs folder.
+            </p>
+          </div>
+          <button
+            onClick={handleDismiss}
+            className="flex-shrink-0 p-1 rounded hover:bg-gray-100 dark:hover:bg-gray-700 transition-colors"
+            aria-label="Dismiss"
+          >
+            <X className="w-4 h-4 text-gray-500 dark:text-gray-400" />
+          </button>
+        </div>
+
+        <div className="p-4 space-y-2">
+          {error && (
+            <div className="p-2 rounded bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 flex items-start gap-2">
+              <AlertCircle className="w-4 h-4 text-red-600 dark:text-red-400 flex-shrink-0 mt-0.5" />
+              <p className="text-xs text-red-700 dark:text-red-300">{error}</p>
+            </div>
+          )}
+          {details && (
+            <div className="space-y-1 text-xs text-gray-600 dark:text-gray-400">
+              <p>
+                Last error: {details.lastError ?? "unknown"} ·{" "}
+                {new Date(details.createdAt).toLocaleString()}
+              </p>
+              <pre className="max-h-40 overflow-auto p-2 rounded bg-gray-50 dark:bg-gray-900 font-mono text-[10px] whitespace-pre-wrap break-all">
+                {details.recentLogs.slice(-LOG_TAIL).join("\n")}
+              </pre>
+              <p className="font-mono break-all">{details.id}.json</p>
+            </div>
+          )}
+          <div className="flex justify-end gap-4">
+            {!details && (
+              <button
+                onClick={() => void showDetails(incident.id)}
+                className={linkClass}
+              >
+                View details
+              </button>
+            )}
+            <button onClick={handleReport} className={linkClass}>
+              Send report
+            </button>
+          </div>
+        </div>
+      </motion.div>
+    </AnimatePresence>
+  );
+}
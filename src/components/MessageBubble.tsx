@@ -23,7 +23,11 @@ import {
   Brain,
   ChevronDown,
   ChevronUp,
+  Bell,
+  BellRing,
+  GripVertical,
 } from "lucide-react";
+import { dragOut } from "../lib/dragOut";
 import { ImageRenderer } from "./ImageRenderer";
 import { Spinner } from "./ui/spinner";
 
@@ -38,6 +42,8 @@ interface MessageBubbleProps {
   message: Message;
   onEdit?: (messageId: string, newContent: string) => void;
   onRegenerate?: (messageId: string) => void;
+  /** Set a follow-up reminder on this message */
+  onRemind?: (messageId: string) => Promise<void>;
   isLastAssistantMessage?: boolean;
   /** Compact mode - tighter spacing for power users */
   compact?: boolean;
@@ -47,6 +53,7 @@ export const MessageBubble = memo(function MessageBubble({
   message,
   onEdit,
   onRegenerate,
+  onRemind,
   isLastAssistantMessage,
   compact = false,
 }: MessageBubbleProps) {
@@ -87,6 +94,19 @@ export const MessageBubble = memo(function MessageBubble({
     setTimeout(() => setMessageCopied(false), 2000);
   };
 
+  const [reminderSet, setReminderSet] = useState(false);
+
+  const handleRemind = async () => {
+    if (!onRemind) return;
+    try {
+      await onRemind(message.id);
+      setReminderSet(true);
+      setTimeout(() => setReminderSet(false), 2000);
+    } catch (err) {
+      console.error("Failed to set reminder:", err);
+    }
+  };
+
   const handleStartEdit = () => {
     setEditContent(message.content);
     setIsEditing(true);
@@ -130,6 +150,7 @@ export const MessageBubble = memo(function MessageBubble({
 
   return (
     <div
+      id={`message-${message.id}`}
       className={cn(
         "group flex animate-message-in",
         compact ? "gap-2" : "gap-3",
@@ -425,6 +446,21 @@ export const MessageBubble = memo(function MessageBubble({
                 <Copy className="w-4 h-4" strokeWidth={2} />
               )}
             </button>
+            {/* Drag the message out as a Markdown file */}
+            <button
+              onMouseDown={(e) => {
+                if (e.button !== 0) return;
+                e.preventDefault();
+                dragOut(message.content, {
+                  name: isUser ? "message" : "answer",
+                }).catch((err) => console.error("Drag-out failed:", err));
+              }}
+              className="p-1.5 text-muted-foreground hover:text-foreground hover:bg-muted rounded-md transition-colors cursor-grab focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-primary/50"
+              title="Drag out as a file"
+              aria-label="Drag message out as a file"
+            >
+              <GripVertical className="w-4 h-4" strokeWidth={2} />
+            </button>
             {/* Edit button for user messages */}
             {isUser && onEdit && (
               <button
@@ -447,6 +483,26 @@ export const MessageBubble = memo(function MessageBubble({
                 <RefreshCw className="w-4 h-4" strokeWidth={2} />
               </button>
             )}
+            {/* Follow-up reminder for assistant messages */}
+            {!isUser && onRemind && (
+              <button
+                onClick={handleRemind}
+                className={cn(
+                  "p-1.5 rounded-md transition-colors focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-primary/50",
+                  reminderSet
+                    ? "text-primary bg-primary/10"
+                    : "text-muted-foreground hover:text-foreground hover:bg-muted",
+                )}
+                title={reminderSet ? "Reminder set for tomorrow 9:00" : "Remind me tomorrow"}
+                aria-label={reminderSet ? "Reminder set" : "Remind me about this answer tomorrow"}
+              >
+                {reminderSet ? (
+                  <BellRing className="w-4 h-4" strokeWidth={2} />
+                ) : (
+                  <Bell className="w-4 h-4" strokeWidth={2} />
+                )}
+              </button>
+            )}
           </div>
         )}
 
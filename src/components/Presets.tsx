@@ -0,0 +1,292 @@
+import { useCallback, useEffect, useState } from "react";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import { Download, MessageSquarePlus, Plus, Trash2, Upload } from "lucide-react";
+import { useStore } from "../stores/store";
+import {
+  Preset,
+  THINKING_LEVELS,
+  ThinkingLevel,
+  createConversationFromPreset,
+  deletePreset,
+  exportPresets,
+  importPresets,
+  listPresets,
+  savePreset,
+} from "../lib/presets";
+import { errorMessage } from "../lib/errors";
+
+interface PresetsProps {
+  onError: (message: string) => void;
+  onSuccess: (message: string) => void;
+}
+
+const emptyPreset = (): Preset => ({
+  id: "",
+  name: "",
+  systemPrompt: null,
+  model: null,
+  thinking: null,
+  temperature: null,
+  maxTokens: null,
+});
+
+const PRESET_FILTERS = [{ name: "Moltz presets", extensions: ["json"] }];
+
+/**
+ * Settings section to manage conversation presets
+ */
+export function Presets({ onError, onSuccess }: PresetsProps) {
+  const availableModels = useStore((state) => state.availableModels);
+  const [presets, setPresets] = useState<Preset[]>([]);
+  const [draft, setDraft] = useState<Preset | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      setPresets((await listPresets()) ?? []);
+    } catch (err) {
+      onError(`Failed to load presets: ${errorMessage(err)}`);
+    }
+  }, [onError]);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  const handleSave = async () => {
+    if (!draft) return;
+    try {
+      await savePreset(draft);
+      setDraft(null);
+      await refresh();
+    } catch (err) {
+      onError(`Failed to save preset: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleDelete = async (preset: Preset) => {
+    try {
+      await deletePreset(preset.id);
+      await refresh();
+    } catch (err) {
+      onError(`Failed to delete preset: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleImport = async () => {
+    try {
+      const path = await open({ multiple: false, filters: PRESET_FILTERS });
+      if (typeof path !== "string") return;
+      const imported = await importPresets(path);
+      onSuccess(`Imported ${imported.length} preset(s)`);
+      await refresh();
+    } catch (err) {
+      onError(`Failed to import presets: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleExport = async () => {
+    try {
+      const path = await save({
+        defaultPath: "moltz-presets.json",
+        filters: PRESET_FILTERS,
+      });
+      if (!path) return;
+      const count = await exportPresets(path);
+      onSuccess(`Exported ${count} preset(s)`);
+    } catch (err) {
+      onError(`Failed to export presets: ${errorMessage(err)}`);
+    }
+  };
+
+  const inputClass =
+    "w-full px-3 py-2 text-sm border border-border rounded-lg bg-background focus:outline-none focus:ring-2 focus:ring-primary/50";
+
+  return (
+    <div className="space-y-3">
+      {presets.map((preset) => (
+        <div
+          key={preset.id}
+          className="flex items-center justify-between gap-3 p-3 border border-border rounded-xl"
+        >
+          <button
+            type="button"
+            onClick={() => setDraft(preset)}
+            className="space-y-1 min-w-0 text-left"
+          >
+            <span className="text-sm font-medium truncate block">
+              {preset.name}
+            </span>
+            <p className="text-xs text-muted-foreground truncate">
+              {[
+                preset.model,
+                preset.thinking && `thinking: ${preset.thinking}`,
+                preset.systemPrompt,
+              ]
+                .filter(Boolean)
+                .join(" · ") || "Default settings"}
+            </p>
+          </button>
+          <div className="flex items-center gap-1 flex-shrink-0">
+            <button
+              type="button"
+              onClick={() =>
+                createConversationFromPreset(preset).catch((err) =>
+                  onError(`Failed to apply preset: ${errorMessage(err)}`),
+                )
+              }
+              className="p-1.5 rounded-lg text-muted-foreground hover:text-foreground hover:bg-muted transition-colors"
+              aria-label={`New chat with ${preset.name}`}
+              title="New chat with this preset"
+            >
+              <MessageSquarePlus className="w-3.5 h-3.5" />
+            </button>
+            <button
+              type="button"
+              onClick={() => handleDelete(preset)}
+              className="p-1.5 rounded-lg text-muted-foreground hover:text-destructive hover:bg-muted transition-colors"
+              aria-label={`Delete ${preset.name}`}
+              title="Delete"
+            >
+              <Trash2 className="w-3.5 h-3.5" />
+            </button>
+          </div>
+        </div>
+      ))}
+
+      {draft ? (
+        <div className="p-3 border border-border rounded-xl space-y-3">
+          <input
+            type="text"
+            value={draft.name}
+            onChange={(e) => setDraft({ ...draft, name: e.target.value })}
+            placeholder="Preset name"
+            className={inputClass}
+          />
+          <textarea
+            value={draft.systemPrompt ?? ""}
+            onChange={(e) =>
+              setDraft({ ...draft, systemPrompt: e.target.value || null })
+            }
+            placeholder="System prompt (optional)"
+            rows={3}
+            className={`${inputClass} resize-none`}
+          />
+          <div className="grid grid-cols-2 gap-2">
+            <select
+              value={draft.model ?? ""}
+              onChange={(e) =>
+                setDraft({ ...draft, model: e.target.value || null })
+              }
+              aria-label="Model"
+              className={inputClass}
+            >
+              <option value="">Default model</option>
+              {availableModels.map((model) => (
+                <option key={model.id} value={model.id}>
+                  {model.name}
+                </option>
+              ))}
+            </select>
+            <select
+              value={draft.thinking ?? ""}
+              onChange={(e) =>
+                setDraft({
+                  ...draft,
+                  thinking: (e.target.value || null) as ThinkingLevel | null,
+                })
+              }
+              aria-label="Thinking level"
+              className={inputClass}
+            >
+              <option value="">Default thinking</option>
+              {THINKING_LEVELS.map((level) => (
+                <option key={level} value={level}>
+                  Thinking: {level}
+                </option>
+              ))}
+            </select>
+            <input
+              type="number"
+              min={0}
+              max={2}
+              step={0.1}
+              value={draft.temperature ?? ""}
+              onChange={(e) =>
+                setDraft({
+                  ...draft,
+                  temperature:
+                    e.target.value === "" ? null : Number(e.target.value),
+                })
+              }
+              placeholder="Temperature"
+              aria-label="Temperature"
+              className={inputClass}
+            />
+            <input
+              type="number"
+              min={1}
+              value={draft.maxTokens ?? ""}
+              onChange={(e) =>
+                setDraft({
+                  ...draft,
+                  maxTokens:
+                    e.target.value === "" ? null : Number(e.target.value),
+                })
+              }
+              placeholder="Max tokens"
+              aria-label="Max tokens"
+              className={inputClass}
+            />
+          </div>
+          <div className="flex justify-end gap-2">
+            <button
+              type="button"
+              onClick={() => setDraft(null)}
+              className="px-3 py-1.5 rounded-lg text-xs font-medium border border-border hover:bg-muted transition-colors"
+            >
+              Cancel
+            </button>
+            <button
+              type="button"
+              onClick={handleSave}
+              disabled={!draft.name.trim()}
+              className="px-3 py-1.5 rounded-lg text-xs font-medium bg-primary text-primary-foreground hover:bg-primary/90 disabled:opacity-50 transition-colors"
+            >
+              {draft.id ? "Save" : "Create"}
+            </button>
+          </div>
+        </div>
+      ) : (
+        <div className="flex gap-2">
+          <button
+            type="button"
+            onClick={() => setDraft(emptyPreset())}
+            className="flex-1 flex items-center gap-2 px-3 py-2 rounded-xl text-sm border border-dashed border-border text-muted-foreground hover:text-foreground hover:bg-muted transition-colors"
+          >
+            <Plus className="w-4 h-4" />
+            Add preset
+          </button>
+          <button
+            type="button"
+            onClick={handleImport}
+            className="p-2 rounded-xl border border-border text-muted-foreground hover:text-foreground hover:bg-muted transition-colors"
+            aria-label="Import presets"
+            title="Import presets"
+          >
+            <Upload className="w-4 h-4" />
+          </button>
+          <button
+            type="button"
+            onClick={handleExport}
+            disabled={presets.length === 0}
+            className="p-2 rounded-xl border border-border text-muted-foreground hover:text-foreground hover:bg-muted disabled:opacity-50 transition-colors"
+            aria-label="Export presets"
+            title="Export presets"
+          >
+            <Download className="w-4 h-4" />
+          </button>
+        </div>
+      )}
+    </div>
+  );
+}
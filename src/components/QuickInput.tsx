@@ -2,17 +2,156 @@
  * QuickInput - Spotlight-style floating input for quick AI queries
  *
  * Triggered by global hotkey (Cmd+Shift+Space on Mac, Ctrl+Shift+Space on Windows)
- * Opens main window with the typed message - instant "ask anywhere" functionality.
+ * Enter queues the question to run in the background, so several can be asked
+ * back-to-back; each answer shows up as a card below the input and as a
+ * notification. Cmd/Ctrl+Enter opens the main window with the message instead.
+ *
+ * Earlier questions matching what's typed are suggested from the local prompt
+ * history; Tab completes the first.
+ *
+ * Also opened pre-filled by the "ask about selection" hotkey (Cmd/Ctrl+Alt+A),
+ * which quotes the text selected in the frontmost app.
+ *
+ * `moltz://ask?...&mode=quick` links open it and submit the query right away.
  */
 
 import { useState, useRef, useEffect, KeyboardEvent } from "react";
-import { emit } from "@tauri-apps/api/event";
+import { emit, listen } from "@tauri-apps/api/event";
 import { getCurrentWindow, Window } from "@tauri-apps/api/window";
 import { cn } from "../lib/utils";
-import { X, Sparkles, ArrowRight } from "lucide-react";
+import {
+  applyAccessibilityPrefs,
+  getAccessibilityPrefs,
+  type AccessibilityPrefs,
+} from "../lib/accessibility";
+import type { DeepLinkAsk } from "../lib/deepLink";
+import { errorMessage } from "../lib/errors";
+import {
+  type PromptSuggestion,
+  getPromptSuggestions,
+} from "../lib/promptHistory";
+import {
+  dismissQuickAsk,
+  enqueueQuickAsk,
+  listQuickAsks,
+  onQuickAskUpdated,
+  openQuickAsk,
+  type QuickAsk,
+} from "../lib/quickAsk";
+import {
+  X,
+  Sparkles,
+  ArrowRight,
+  CornerDownLeft,
+  Loader2,
+  Clock,
+  Check,
+  AlertCircle,
+} from "lucide-react";
+
+/** Selection captured by the backend from the frontmost app */
+interface CapturedSelection {
+  text: string;
+  sourceApp: string | null;
+}
+
+/** Quote the selection (and optionally its source app) above the question */
+function formatSelectionContext(
+  selection: CapturedSelection,
+  includeSource: boolean,
+): string {
+  const header =
+    includeSource && selection.sourceApp
+      ? `Selected in ${selection.sourceApp}:\n`
+      : "";
+  const quoted = selection.text
+    .split("\n")
+    .map((line) => `> ${line}`)
+    .join("\n");
+  return `${header}${quoted}\n\n`;
+}
+
+/** Insert or replace a question, keeping the list oldest first */
+function upsertAsk(asks: QuickAsk[], ask: QuickAsk): QuickAsk[] {
+  return asks.some((a) => a.id === ask.id)
+    ? asks.map((a) => (a.id === ask.id ? ask : a))
+    : [...asks, ask];
+}
+
+function QuickAskCard({
+  ask,
+  onDismiss,
+  onAskAgain,
+}: {
+  ask: QuickAsk;
+  onDismiss: (id: string) => void;
+  onAskAgain: (ask: QuickAsk) => void;
+}) {
+  const statusIcon = {
+    queued: <Clock className="w-3.5 h-3.5 text-muted-foreground" />,
+    running: <Loader2 className="w-3.5 h-3.5 text-primary animate-spin" />,
+    done: <Check className="w-3.5 h-3.5 text-green-600" />,
+    failed: <AlertCircle className="w-3.5 h-3.5 text-destructive" />,
+  }[ask.status];
+
+  return (
+    <li className="px-3 py-2 rounded-lg bg-muted/50 border border-border/50 text-xs">
+      <div className="flex items-center gap-2">
+        <span aria-label={ask.status}>{statusIcon}</span>
+        <span className="flex-1 truncate font-medium">{ask.message}</span>
+        {ask.cached && (
+          <>
+            <span className="text-muted-foreground">Cached</span>
+            <button
+              onClick={() => onAskAgain(ask)}
+              className="px-1.5 rounded hover:bg-muted transition-colors"
+              title="Run the question again instead of reusing the answer"
+            >
+              Ask again
+            </button>
+          </>
+        )}
+        {(ask.status === "done" || ask.status === "failed") && (
+          <button
+            onClick={() =>
+              openQuickAsk(ask.id).catch((err) =>
+                console.error("Failed to open Quick Ask answer:", err),
+              )
+            }
+            className="px-1.5 rounded hover:bg-muted transition-colors"
+          >
+            Open
+          </button>
+        )}
+        <button
+          onClick={() => onDismiss(ask.id)}
+          className="p-0.5 rounded hover:bg-muted transition-colors"
+          aria-label="Dismiss"
+        >
+          <X className="w-3 h-3" />
+        </button>
+      </div>
+      {(ask.answer || ask.error) && (
+        <p
+          className={cn(
+            "mt-1 line-clamp-3 whitespace-pre-wrap",
+            ask.error ? "text-destructive" : "text-muted-foreground",
+          )}
+        >
+          {ask.answer ?? ask.error}
+        </p>
+      )}
+    </li>
+  );
+}
 
 export function QuickInput() {
   const [input, setInput] = useState("");
+  const [selection, setSelection] = useState<CapturedSelection | null>(null);
+  const [includeSource, setIncludeSource] = useState(true);
+  const [asks, setAsks] = useState<QuickAsk[]>([]);
+  const [queueError, setQueueError] = useState<string | null>(null);
+  const [suggestions, setSuggestions] = useState<PromptSuggestion[]>([]);
   const inputRef = useRef<HTMLTextAreaElement>(null);
   const currentWindow = getCurrentWindow();
 
@@ -31,8 +170,93 @@ export function QuickInput() {
     };
   }, [currentWindow]);
 
+  // Scale text with the OS text size (the backend grows the window to fit)
+  useEffect(() => {
+    getAccessibilityPrefs()
+      .then(applyAccessibilityPrefs)
+      .catch(() => {});
+    const unlisten = listen<AccessibilityPrefs>(
+      "accessibility:changed",
+      (event) => applyAccessibilityPrefs(event.payload),
+    );
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Background questions and their answers
+  useEffect(() => {
+    listQuickAsks()
+      .then(setAsks)
+      .catch((err) => console.error("Failed to list Quick Asks:", err));
+    const unlisten = onQuickAskUpdated((ask) =>
+      setAsks((current) => upsertAsk(current, ask)),
+    );
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Earlier questions starting with what's typed, from the local history
+  useEffect(() => {
+    const prefix = input.trim();
+    if (!prefix || selection) {
+      setSuggestions([]);
+      return;
+    }
+    let cancelled = false;
+    const timer = setTimeout(() => {
+      getPromptSuggestions(prefix, 3)
+        .then((found) => {
+          if (!cancelled) setSuggestions(found);
+        })
+        .catch(() => setSuggestions([]));
+    }, 150);
+
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [input, selection]);
+
+  // Pre-fill from the "ask about selection" hotkey
+  useEffect(() => {
+    const unlisten = currentWindow.listen<CapturedSelection>(
+      "quickinput:prefill",
+      (event) => {
+        setSelection(event.payload);
+        setIncludeSource(true);
+        inputRef.current?.focus();
+      },
+    );
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [currentWindow]);
+
+  // Run queries from moltz://ask links immediately
+  useEffect(() => {
+    const unlisten = currentWindow.listen<DeepLinkAsk>(
+      "quickinput:ask",
+      (event) => {
+        setSelection(null);
+        setInput(event.payload.message);
+        submitMessage(event.payload.message, event.payload.copyAnswer);
+      },
+    );
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [currentWindow]);
+
   const handleClose = async () => {
     setInput("");
+    setSelection(null);
     await currentWindow.hide();
   };
 
@@ -48,13 +272,63 @@ export function QuickInput() {
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [currentWindow]);
 
+  const canSubmit = Boolean(input.trim() || selection);
+
+  const composeMessage = () => {
+    const context = selection
+      ? formatSelectionContext(selection, includeSource)
+      : "";
+    return (context + input.trim()).trim();
+  };
+
+  /** Open the question in the main window */
   const handleSubmit = async () => {
-    if (!input.trim()) return;
+    if (!canSubmit) return;
+    await submitMessage(composeMessage(), false);
+  };
 
-    const message = input.trim();
+  /** Run the question in the background and stay open for the next one */
+  const handleQueue = async () => {
+    if (!canSubmit) return;
+    setQueueError(null);
+    try {
+      const ask = await enqueueQuickAsk(composeMessage());
+      setAsks((current) => upsertAsk(current, ask));
+      setInput("");
+      setSelection(null);
+      inputRef.current?.focus();
+    } catch (err) {
+      setQueueError(errorMessage(err));
+    }
+  };
 
+  /** Re-run a cached answer's question, bypassing the cache */
+  const handleAskAgain = async (ask: QuickAsk) => {
+    setQueueError(null);
+    try {
+      const again = await enqueueQuickAsk(ask.message, true);
+      setAsks((current) =>
+        upsertAsk(
+          current.filter((a) => a.id !== ask.id),
+          again,
+        ),
+      );
+      dismissQuickAsk(ask.id).catch(() => {});
+    } catch (err) {
+      setQueueError(errorMessage(err));
+    }
+  };
+
+  const handleDismissAsk = (id: string) => {
+    setAsks((current) => current.filter((a) => a.id !== id));
+    dismissQuickAsk(id).catch((err) =>
+      console.error("Failed to dismiss Quick Ask:", err),
+    );
+  };
+
+  const submitMessage = async (message: string, copyAnswer: boolean) => {
     // Emit event to main window with the message
-    await emit("quickinput:submit", { message });
+    await emit("quickinput:submit", { message, copyAnswer });
 
     // Show and focus main window
     const mainWindow = new Window("main");
@@ -66,9 +340,19 @@ export function QuickInput() {
   };
 
   const handleKeyDown = (e: KeyboardEvent<HTMLTextAreaElement>) => {
+    if (e.key === "Tab" && !e.shiftKey && suggestions.length > 0) {
+      // Complete with the best suggestion
+      e.preventDefault();
+      setInput(suggestions[0].prompt);
+      return;
+    }
     if (e.key === "Enter" && !e.shiftKey) {
       e.preventDefault();
-      handleSubmit();
+      if (e.metaKey || e.ctrlKey) {
+        handleSubmit();
+      } else {
+        handleQueue();
+      }
     }
   };
 
@@ -94,6 +378,38 @@ export function QuickInput() {
         </button>
       </div>
 
+      {/* Captured selection */}
+      {selection && (
+        <div className="mx-4 mt-3 px-3 py-2 rounded-lg bg-muted/50 border border-border/50 text-xs text-muted-foreground">
+          <div className="flex items-center justify-between gap-2 mb-1">
+            {selection.sourceApp && includeSource ? (
+              <span className="font-medium">From {selection.sourceApp}</span>
+            ) : (
+              <span className="font-medium">Selection</span>
+            )}
+            <div className="flex items-center gap-1">
+              {selection.sourceApp && includeSource && (
+                <button
+                  onClick={() => setIncludeSource(false)}
+                  className="px-1.5 rounded hover:bg-muted transition-colors"
+                  title="Don't mention the app name"
+                >
+                  Hide app
+                </button>
+              )}
+              <button
+                onClick={() => setSelection(null)}
+                className="p-0.5 rounded hover:bg-muted transition-colors"
+                aria-label="Remove selection"
+              >
+                <X className="w-3 h-3" />
+              </button>
+            </div>
+          </div>
+          <p className="line-clamp-2 whitespace-pre-wrap">{selection.text}</p>
+        </div>
+      )}
+
       {/* Input area */}
       <div className="flex-1 p-4 flex items-center">
         <textarea
@@ -101,7 +417,11 @@ export function QuickInput() {
           value={input}
           onChange={(e) => setInput(e.target.value)}
           onKeyDown={handleKeyDown}
-          placeholder="Ask anything... (Enter to send, Esc to close)"
+          placeholder={
+            selection
+              ? "Ask about the selection... (Enter to ask, Esc to close)"
+              : "Ask anything... (Enter to ask, Esc to close)"
+          }
           className={cn(
             "w-full min-h-[60px] max-h-[120px] resize-none",
             "bg-transparent text-foreground placeholder:text-muted-foreground",
@@ -112,25 +432,93 @@ export function QuickInput() {
         />
       </div>
 
+      {suggestions.length > 0 && (
+        <ul
+          className="mx-4 mb-2 space-y-0.5 text-xs"
+          aria-label="Earlier questions"
+        >
+          {suggestions.map((suggestion, index) => (
+            <li key={suggestion.prompt}>
+              <button
+                type="button"
+                onClick={() => {
+                  setInput(suggestion.prompt);
+                  inputRef.current?.focus();
+                }}
+                className="w-full flex items-center justify-between gap-2 px-2 py-1 rounded-md text-left text-muted-foreground hover:bg-muted hover:text-foreground transition-colors"
+              >
+                <span className="truncate">{suggestion.prompt}</span>
+                {index === 0 && (
+                  <kbd className="flex-shrink-0 px-1 rounded border border-border/50">
+                    Tab
+                  </kbd>
+                )}
+              </button>
+            </li>
+          ))}
+        </ul>
+      )}
+
+      {queueError && (
+        <p className="mx-4 mb-2 text-xs text-destructive">{queueError}</p>
+      )}
+
+      {/* Background questions, newest first */}
+      {asks.length > 0 && (
+        <ul
+          className="mx-4 mb-3 space-y-2 max-h-44 overflow-y-auto"
+          aria-label="Quick Ask answers"
+          aria-live="polite"
+        >
+          {[...asks].reverse().map((ask) => (
+            <QuickAskCard
+              key={ask.id}
+              ask={ask}
+              onDismiss={handleDismissAsk}
+              onAskAgain={handleAskAgain}
+            />
+          ))}
+        </ul>
+      )}
+
       {/* Footer */}
       <div className="px-4 py-3 border-t border-border/50 bg-muted/30 flex justify-between items-center">
         <span className="text-xs text-muted-foreground">
           {navigator.platform.includes("Mac") ? "⌘⇧Space" : "Ctrl+Shift+Space"}{" "}
           to toggle
         </span>
-        <button
-          onClick={handleSubmit}
-          disabled={!input.trim()}
-          className={cn(
-            "flex items-center gap-2 px-3 py-1.5 rounded-lg text-sm font-medium transition-all",
-            input.trim()
-              ? "bg-primary text-primary-foreground hover:bg-primary/90"
-              : "bg-muted text-muted-foreground cursor-not-allowed",
-          )}
-        >
-          <ArrowRight className="w-4 h-4" />
-          Open in Moltz
-        </button>
+        <div className="flex items-center gap-2">
+          <button
+            onClick={handleSubmit}
+            disabled={!canSubmit}
+            title={
+              navigator.platform.includes("Mac") ? "⌘Enter" : "Ctrl+Enter"
+            }
+            className={cn(
+              "flex items-center gap-2 px-3 py-1.5 rounded-lg text-sm font-medium transition-all",
+              canSubmit
+                ? "hover:bg-muted text-foreground"
+                : "text-muted-foreground cursor-not-allowed",
+            )}
+          >
+            <ArrowRight className="w-4 h-4" />
+            Open in Moltz
+          </button>
+          <button
+            onClick={handleQueue}
+            disabled={!canSubmit}
+            title="Enter"
+            className={cn(
+              "flex items-center gap-2 px-3 py-1.5 rounded-lg text-sm font-medium transition-all",
+              canSubmit
+                ? "bg-primary text-primary-foreground hover:bg-primary/90"
+                : "bg-muted text-muted-foreground cursor-not-allowed",
+            )}
+          >
+            <CornerDownLeft className="w-4 h-4" />
+            Ask
+          </button>
+        </div>
       </div>
     </div>
   );
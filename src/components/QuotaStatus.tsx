@@ -0,0 +1,75 @@
+import { useEffect, useState } from "react";
+import { getQuotaStatus, type QuotaStatus as Status } from "../lib/quota";
+import { errorMessage } from "../lib/errors";
+
+interface QuotaStatusProps {
+  onError: (message: string) => void;
+}
+
+const formatAmount = (value: number, unit: string) =>
+  unit === "tokens" || unit === "requests"
+    ? `${Math.round(value).toLocaleString()} ${unit}`
+    : `${value.toFixed(2)} ${unit}`;
+
+/**
+ * Settings section showing usage limits and local consumption
+ */
+export function QuotaStatus({ onError }: QuotaStatusProps) {
+  const [status, setStatus] = useState<Status | null>(null);
+
+  useEffect(() => {
+    getQuotaStatus()
+      .then((status) => setStatus(status ?? null))
+      .catch((err) =>
+        onError(`Failed to load usage limits: ${errorMessage(err)}`),
+      );
+  }, [onError]);
+
+  if (!status) return null;
+
+  return (
+    <div className="space-y-3">
+      {status.limits.length === 0 && (
+        <p className="text-xs text-muted-foreground">
+          The Gateway reports no usage limits.
+        </p>
+      )}
+      {status.limits.map((limit) => {
+        const percent = Math.min(100, (limit.used / limit.limit) * 100);
+        return (
+          <div key={limit.id} className="p-3 border border-border rounded-xl space-y-2">
+            <div className="flex items-center justify-between gap-2 text-sm">
+              <span className="font-medium truncate">{limit.name}</span>
+              <span className="text-xs text-muted-foreground flex-shrink-0">
+                {formatAmount(limit.used, limit.unit)} /{" "}
+                {formatAmount(limit.limit, limit.unit)}
+              </span>
+            </div>
+            <div
+              className="h-1.5 rounded-full bg-muted overflow-hidden"
+              role="progressbar"
+              aria-valuenow={Math.round(percent)}
+              aria-valuemin={0}
+              aria-valuemax={100}
+              aria-label={`${limit.name} used`}
+            >
+              <div
+                className={`h-full ${percent >= 95 ? "bg-destructive" : percent >= 80 ? "bg-yellow-500" : "bg-primary"}`}
+                style={{ width: `${percent}%` }}
+              />
+            </div>
+            {limit.resetsAt && (
+              <p className="text-xs text-muted-foreground">
+                Resets {new Date(limit.resetsAt).toLocaleString()}
+              </p>
+            )}
+          </div>
+        );
+      })}
+      <p className="text-xs text-muted-foreground">
+        This device: {status.local.dayTokens.toLocaleString()} tokens today,{" "}
+        {status.local.monthTokens.toLocaleString()} this month
+      </p>
+    </div>
+  );
+}
@@ -0,0 +1,151 @@
+import { useState, useEffect } from "react";
+import { motion, AnimatePresence } from "framer-motion";
+import { History, X, AlertCircle } from "lucide-react";
+import { cn } from "../lib/utils";
+import { errorMessage } from "../lib/errors";
+import { openConversationAt } from "../lib/deepLink";
+import {
+  dismissInterruptedState,
+  getInterruptedState,
+  retryInterruptedSend,
+  type InFlight,
+  type PendingSend,
+} from "../lib/recovery";
+
+function preview(message: string): string {
+  const line = message.replace(/\s+/g, " ").trim();
+  return line.length > 60 ? `${line.slice(0, 60)}…` : line;
+}
+
+/**
+ * Offer to resume replies and resend messages that a crash interrupted
+ */
+export function RecoveryNotice() {
+  const [inFlight, setInFlight] = useState<InFlight | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    getInterruptedState()
+      .then((state) => {
+        if (state.unsent.length > 0 || state.runs.length > 0) {
+          setInFlight(state);
+        }
+      })
+      .catch((err) => console.error("Failed to get interrupted state:", err));
+  }, []);
+
+  if (!inFlight) {
+    return null;
+  }
+
+  const handleDismiss = () => {
+    dismissInterruptedState().catch((err) =>
+      console.error("Failed to dismiss interrupted state:", err),
+    );
+    setInFlight(null);
+  };
+
+  const remove = (changes: Partial<InFlight>) => {
+    const next = { ...inFlight, ...changes };
+    if (next.unsent.length === 0 && next.runs.length === 0) {
+      handleDismiss();
+    } else {
+      setInFlight(next);
+    }
+  };
+
+  const handleRetry = async (send: PendingSend) => {
+    setError(null);
+    try {
+      if (send.sessionKey) openConversationAt(send.sessionKey);
+      await retryInterruptedSend(send.requestId);
+      remove({
+        unsent: inFlight.unsent.filter((s) => s.requestId !== send.requestId),
+      });
+    } catch (err) {
+      setError(errorMessage(err));
+    }
+  };
+
+  const handleResume = (runId: string, sessionKey: string | null) => {
+    if (sessionKey) openConversationAt(sessionKey);
+    remove({ runs: inFlight.runs.filter((r) => r.runId !== runId) });
+  };
+
+  const linkClass =
+    "flex-shrink-0 text-xs font-medium text-blue-600 dark:text-blue-400 hover:underline";
+
+  return (
+    <AnimatePresence>
+      <motion.div
+        initial={{ opacity: 0, y: 50 }}
+        animate={{ opacity: 1, y: 0 }}
+        exit={{ opacity: 0, y: 50 }}
+        role="alert"
+        className={cn(
+          "fixed bottom-4 right-4 z-50",
+          "w-96 rounded-lg shadow-2xl",
+          "bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700",
+          "overflow-hidden",
+        )}
+      >
+        <div className="flex items-start gap-3 p-4 pb-3 border-b border-gray-200 dark:border-gray-700">
+          <div className="flex-shrink-0 w-10 h-10 rounded-full bg-blue-100 dark:bg-blue-900/30 flex items-center justify-center">
+            <History className="w-5 h-5 text-blue-600 dark:text-blue-400" />
+          </div>
+          <div className="flex-1 min-w-0">
+            <h3 className="font-semibold text-sm text-gray-900 dark:text-white">
+              Moltz closed unexpectedly
+            </h3>
+            <p className="text-xs text-gray-600 dark:text-gray-400 mt-0.5">
+              Some messages and replies were still in progress
+            </p>
+          </div>
+          <button
+            onClick={handleDismiss}
+            className="flex-shrink-0 p-1 rounded hover:bg-gray-100 dark:hover:bg-gray-700 transition-colors"
+            aria-label="Dismiss"
+          >
+            <X className="w-4 h-4 text-gray-500 dark:text-gray-400" />
+          </button>
+        </div>
+
+        <div className="p-4 space-y-2">
+          {error && (
+            <div className="p-2 rounded bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 flex items-start gap-2">
+              <AlertCircle className="w-4 h-4 text-red-600 dark:text-red-400 flex-shrink-0 mt-0.5" />
+              <p className="text-xs text-red-700 dark:text-red-300">{error}</p>
+            </div>
+          )}
+          {inFlight.runs.map((run) => (
+            <div key={run.runId} className="flex items-center gap-2">
+              <span className="flex-1 text-xs text-gray-600 dark:text-gray-400">
+                Reply interrupted at{" "}
+                {new Date(run.startedAt).toLocaleTimeString()}
+              </span>
+              <button
+                onClick={() => handleResume(run.runId, run.sessionKey)}
+                className={linkClass}
+              >
+                Resume
+              </button>
+            </div>
+          ))}
+          {inFlight.unsent.map((send) => (
+            <div key={send.requestId} className="flex items-center gap-2">
+              <span className="flex-1 min-w-0 truncate text-xs text-gray-600 dark:text-gray-400">
+                “{preview(send.message)}”
+              </span>
+              <button
+                onClick={() => handleRetry(send)}
+                className={linkClass}
+              >
+                Retry
+              </button>
+            </div>
+          ))}
+        </div>
+      </motion.div>
+    </AnimatePresence>
+  );
+}
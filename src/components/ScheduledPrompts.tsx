@@ -0,0 +1,239 @@
+import { useCallback, useEffect, useState } from "react";
+import { Play, Plus, Trash2 } from "lucide-react";
+import { cn } from "../lib/utils";
+import { Switch } from "./ui/switch";
+import {
+  ScheduledPrompt,
+  WEEKDAYS,
+  createScheduledPrompt,
+  deleteScheduledPrompt,
+  describeRecurrence,
+  listScheduledPrompts,
+  runScheduledPromptNow,
+  toInput,
+  updateScheduledPrompt,
+} from "../lib/scheduler";
+import { errorMessage } from "../lib/errors";
+
+interface ScheduledPromptsProps {
+  /** Current conversation's session, offered as the target session */
+  sessionKey?: string | null;
+  onError: (message: string) => void;
+}
+
+const systemTimezone = Intl.DateTimeFormat().resolvedOptions().timeZone;
+
+/**
+ * Settings section to manage scheduled prompts
+ */
+export function ScheduledPrompts({ sessionKey, onError }: ScheduledPromptsProps) {
+  const [prompts, setPrompts] = useState<ScheduledPrompt[]>([]);
+  const [adding, setAdding] = useState(false);
+  const [name, setName] = useState("");
+  const [message, setMessage] = useState("");
+  const [time, setTime] = useState("09:00");
+  const [days, setDays] = useState<number[]>([1, 2, 3, 4, 5]);
+  const [useCurrentSession, setUseCurrentSession] = useState(true);
+
+  const refresh = useCallback(async () => {
+    try {
+      setPrompts((await listScheduledPrompts()) ?? []);
+    } catch (err) {
+      onError(`Failed to load scheduled prompts: ${errorMessage(err)}`);
+    }
+  }, [onError]);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  const handleCreate = async () => {
+    try {
+      await createScheduledPrompt({
+        name: name.trim() || message.trim().slice(0, 40),
+        message,
+        sessionKey: useCurrentSession ? sessionKey : null,
+        recurrence: { time, days },
+        timezone: systemTimezone || null,
+        enabled: true,
+      });
+      setAdding(false);
+      setName("");
+      setMessage("");
+      await refresh();
+    } catch (err) {
+      onError(`Failed to create scheduled prompt: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleToggle = async (prompt: ScheduledPrompt, enabled: boolean) => {
+    try {
+      await updateScheduledPrompt(prompt.id, { ...toInput(prompt), enabled });
+      await refresh();
+    } catch (err) {
+      onError(`Failed to update scheduled prompt: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleDelete = async (prompt: ScheduledPrompt) => {
+    try {
+      await deleteScheduledPrompt(prompt.id);
+      await refresh();
+    } catch (err) {
+      onError(`Failed to delete scheduled prompt: ${errorMessage(err)}`);
+    }
+  };
+
+  const toggleDay = (day: number) =>
+    setDays((prev) =>
+      prev.includes(day) ? prev.filter((d) => d !== day) : [...prev, day],
+    );
+
+  return (
+    <div className="space-y-3">
+      {prompts.map((prompt) => (
+        <div
+          key={prompt.id}
+          className="flex items-center justify-between gap-3 p-3 border border-border rounded-xl"
+        >
+          <div className="space-y-1 min-w-0">
+            <span className="text-sm font-medium truncate block">
+              {prompt.name}
+            </span>
+            <p className="text-xs text-muted-foreground">
+              {describeRecurrence(prompt.recurrence)}
+              {prompt.timezone ? ` (${prompt.timezone})` : ""}
+              {prompt.nextRun &&
+                ` · next ${new Date(prompt.nextRun).toLocaleString()}`}
+            </p>
+            {prompt.lastResult && (
+              <p
+                className={cn(
+                  "text-xs truncate",
+                  prompt.lastResult.ok
+                    ? "text-muted-foreground"
+                    : "text-destructive",
+                )}
+                title={prompt.lastResult.excerpt}
+              >
+                {prompt.lastResult.ok ? "Last: " : "Failed: "}
+                {prompt.lastResult.excerpt}
+              </p>
+            )}
+          </div>
+          <div className="flex items-center gap-1 flex-shrink-0">
+            <button
+              type="button"
+              onClick={() =>
+                runScheduledPromptNow(prompt.id).catch((err) =>
+                  onError(
+                    `Failed to run scheduled prompt: ${errorMessage(err)}`,
+                  ),
+                )
+              }
+              className="p-1.5 rounded-lg text-muted-foreground hover:text-foreground hover:bg-muted transition-colors"
+              aria-label={`Run ${prompt.name} now`}
+              title="Run now"
+            >
+              <Play className="w-3.5 h-3.5" />
+            </button>
+            <button
+              type="button"
+              onClick={() => handleDelete(prompt)}
+              className="p-1.5 rounded-lg text-muted-foreground hover:text-destructive hover:bg-muted transition-colors"
+              aria-label={`Delete ${prompt.name}`}
+              title="Delete"
+            >
+              <Trash2 className="w-3.5 h-3.5" />
+            </button>
+            <Switch
+              checked={prompt.enabled}
+              onCheckedChange={(checked) => handleToggle(prompt, checked)}
+              aria-label={`Toggle ${prompt.name}`}
+            />
+          </div>
+        </div>
+      ))}
+
+      {adding ? (
+        <div className="p-3 border border-border rounded-xl space-y-3">
+          <input
+            type="text"
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+            placeholder="Name (e.g. Morning inbox summary)"
+            className="w-full px-3 py-2 text-sm border border-border rounded-lg bg-background focus:outline-none focus:ring-2 focus:ring-primary/50"
+          />
+          <textarea
+            value={message}
+            onChange={(e) => setMessage(e.target.value)}
+            placeholder="Message to send"
+            rows={2}
+            className="w-full px-3 py-2 text-sm border border-border rounded-lg bg-background resize-none focus:outline-none focus:ring-2 focus:ring-primary/50"
+          />
+          <div className="flex items-center gap-2 flex-wrap">
+            <input
+              type="time"
+              value={time}
+              onChange={(e) => setTime(e.target.value)}
+              aria-label="Time of day"
+              className="px-2 py-1 text-sm border border-border rounded-lg bg-background"
+            />
+            {WEEKDAYS.map((label, i) => (
+              <button
+                key={label}
+                type="button"
+                aria-pressed={days.includes(i + 1)}
+                onClick={() => toggleDay(i + 1)}
+                className={cn(
+                  "px-2 py-1 rounded-lg text-xs font-medium transition-colors",
+                  days.includes(i + 1)
+                    ? "bg-primary text-primary-foreground"
+                    : "border border-border hover:bg-muted",
+                )}
+              >
+                {label}
+              </button>
+            ))}
+          </div>
+          {sessionKey && (
+            <label className="flex items-center gap-2 text-xs text-muted-foreground">
+              <input
+                type="checkbox"
+                checked={useCurrentSession}
+                onChange={(e) => setUseCurrentSession(e.target.checked)}
+              />
+              Send in the current conversation
+            </label>
+          )}
+          <div className="flex justify-end gap-2">
+            <button
+              type="button"
+              onClick={() => setAdding(false)}
+              className="px-3 py-1.5 rounded-lg text-xs font-medium border border-border hover:bg-muted transition-colors"
+            >
+              Cancel
+            </button>
+            <button
+              type="button"
+              onClick={handleCreate}
+              disabled={!message.trim()}
+              className="px-3 py-1.5 rounded-lg text-xs font-medium bg-primary text-primary-foreground hover:bg-primary/90 disabled:opacity-50 transition-colors"
+            >
+              Schedule
+            </button>
+          </div>
+        </div>
+      ) : (
+        <button
+          type="button"
+          onClick={() => setAdding(true)}
+          className="flex items-center gap-2 px-3 py-2 rounded-xl text-sm border border-dashed border-border text-muted-foreground hover:text-foreground hover:bg-muted transition-colors w-full"
+        >
+          <Plus className="w-4 h-4" />
+          Add scheduled prompt
+        </button>
+      )}
+    </div>
+  );
+}
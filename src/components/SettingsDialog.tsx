@@ -2,7 +2,12 @@ import React, { useState, useEffect, useCallback } from "react";
 import { useStore, ModelInfo } from "../stores/store";
 import { useShallow } from "zustand/react/shallow";
 import { invoke } from "@tauri-apps/api/core";
+import {
+  open as openDialog,
+  save as saveDialog,
+} from "@tauri-apps/plugin-dialog";
 import { cn } from "../lib/utils";
+import { errorMessage } from "../lib/errors";
 import {
   Tooltip,
   TooltipContent,
@@ -13,6 +18,46 @@ import { Switch } from "./ui/switch";
 import { Skeleton } from "./ui/skeleton";
 import { useToast } from "./ui/toast";
 import { useFocusTrap } from "../lib/useFocusTrap";
+import {
+  DataUsageReport,
+  formatBytes,
+  getDataUsage,
+  resetDataUsage,
+  setDataSaver,
+  useDataSaver,
+} from "../lib/dataSaver";
+import {
+  ResponseCacheStatus,
+  clearResponseCache,
+  getResponseCache,
+  setResponseCache,
+} from "../lib/responseCache";
+import {
+  PromptHistoryStatus,
+  clearPromptHistory,
+  getPromptHistory,
+  setPromptHistory,
+} from "../lib/promptHistory";
+import { exportSettings, importSettings } from "../lib/settingsFile";
+import {
+  ProbeReport,
+  TRACE_STEP_LABELS,
+  probeGateway,
+} from "../lib/connectionTrace";
+import { ScheduledPrompts } from "./ScheduledPrompts";
+import { Presets } from "./Presets";
+import { ArchivedConversations } from "./ArchivedConversations";
+import { GuestModeSettings } from "./GuestMode";
+import { StorageManagement } from "./StorageManagement";
+import { SystemNotifications } from "./SystemNotifications";
+import { DnsResolver } from "./DnsResolver";
+import { HomeAwayEndpoints } from "./HomeAwayEndpoints";
+import { QuotaStatus } from "./QuotaStatus";
+import { ChaosPanel } from "./ChaosPanel";
+
+const SETTINGS_FILE_FILTERS = [
+  { name: "Moltz settings", extensions: ["json"] },
+];
 
 interface SettingsDialogProps {
   open: boolean;
@@ -20,13 +65,6 @@ interface SettingsDialogProps {
   onRerunSetup?: () => void;
 }
 
-// Result from the connect command
-interface ConnectResult {
-  success: boolean;
-  used_url: string;
-  protocol_switched: boolean;
-}
-
 // Models are fetched from Gateway only - no fallbacks
 
 export function SettingsDialog({
@@ -38,22 +76,24 @@ export function SettingsDialog({
   const {
     settings,
     updateSettings,
+    connectAndSaveGateway,
     connected,
-    setConnected,
     availableModels,
     setAvailableModels,
     modelsLoading,
     setModelsLoading,
+    currentConversationId,
   } = useStore(
     useShallow((state) => ({
       settings: state.settings,
       updateSettings: state.updateSettings,
+      connectAndSaveGateway: state.connectAndSaveGateway,
       connected: state.connected,
-      setConnected: state.setConnected,
       availableModels: state.availableModels,
       setAvailableModels: state.setAvailableModels,
       modelsLoading: state.modelsLoading,
       setModelsLoading: state.setModelsLoading,
+      currentConversationId: state.currentConversationId,
     })),
   );
   const { showSuccess, showError: showToastError } = useToast();
@@ -63,9 +103,16 @@ export function SettingsDialog({
   >("idle");
   const [error, setError] = useState<string | null>(null);
   const [protocolNotice, setProtocolNotice] = useState<string | null>(null);
+  const [probeReport, setProbeReport] = useState<ProbeReport | null>(null);
   const [showToken, setShowToken] = useState(false);
   const [urlError, setUrlError] = useState<string | null>(null);
   const dialogRef = useFocusTrap(open);
+  const dataSaver = useDataSaver();
+  const [dataUsage, setDataUsage] = useState<DataUsageReport | null>(null);
+  const [responseCache, setResponseCacheStatus] =
+    useState<ResponseCacheStatus | null>(null);
+  const [promptHistory, setPromptHistoryStatus] =
+    useState<PromptHistoryStatus | null>(null);
 
   // Only sync form data when dialog opens, not when settings reference changes
   // This prevents reverting edits when the store updates during typing
@@ -76,10 +123,59 @@ export function SettingsDialog({
       setUrlError(null);
       setError(null);
       setProtocolNotice(null);
+      setProbeReport(null);
     }
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [open]);
 
+  // Data usage is a snapshot taken when the dialog opens
+  useEffect(() => {
+    if (!open) return;
+    getDataUsage()
+      .then(setDataUsage)
+      .catch(() => setDataUsage(null));
+    getResponseCache()
+      .then(setResponseCacheStatus)
+      .catch(() => setResponseCacheStatus(null));
+    getPromptHistory()
+      .then(setPromptHistoryStatus)
+      .catch(() => setPromptHistoryStatus(null));
+  }, [open]);
+
+  const handleExportSettings = async () => {
+    try {
+      const path = await saveDialog({
+        defaultPath: "moltz-settings.json",
+        filters: SETTINGS_FILE_FILTERS,
+      });
+      if (!path) return;
+      await exportSettings(path);
+      showSuccess("Settings exported");
+    } catch (err) {
+      showToastError(`Failed to export settings: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleImportSettings = async () => {
+    try {
+      const path = await openDialog({
+        multiple: false,
+        filters: SETTINGS_FILE_FILTERS,
+      });
+      if (typeof path !== "string") return;
+      const sections = await importSettings(path);
+      // Data saver follows its change event; the cache status is a snapshot
+      setResponseCacheStatus(await getResponseCache());
+      showSuccess(
+        sections.length > 0
+          ? `Imported ${sections.length} setting group(s)`
+          : "The file contained no settings",
+      );
+    } catch (err) {
+      showToastError(`Failed to import settings: ${errorMessage(err)}`);
+    }
+  };
+
   // Keyboard shortcut: Escape to close
   useEffect(() => {
     if (!open) return;
@@ -176,29 +272,21 @@ export function SettingsDialog({
       setConnectionStatus("connecting");
       setError(null);
       setProtocolNotice(null);
+      const { gatewayUrl, gatewayToken, ...otherSettings } = formData;
       try {
-        await invoke("disconnect");
-        const result = await invoke<ConnectResult>("connect", {
-          url: formData.gatewayUrl,
-          token: formData.gatewayToken,
-        });
+        // The URL and token are saved only once the Gateway accepts them
+        // (with the working URL if the protocol was switched); the current
+        // connection stays up if they're rejected
+        await connectAndSaveGateway(gatewayUrl, gatewayToken);
+        await updateSettings(otherSettings);
         setConnectionStatus("idle");
-
-        // If protocol was switched, save the working URL
-        if (result.protocol_switched) {
-          await updateSettings({ ...formData, gatewayUrl: result.used_url });
-        } else {
-          await updateSettings(formData);
-        }
-
         showSuccess("Settings saved successfully");
       } catch (err: unknown) {
         setConnectionStatus("error");
-        setError(String(err));
-        setConnected(false);
-        // Still save settings even if connection failed
-        await updateSettings(formData);
-        showToastError("Settings saved, but connection failed");
+        setError(errorMessage(err));
+        // Save everything but the rejected connection settings
+        await updateSettings(otherSettings);
+        showToastError("Settings saved, but the new connection failed");
         return; // Don't close dialog on error
       }
     } else {
@@ -213,27 +301,37 @@ export function SettingsDialog({
     setConnectionStatus("connecting");
     setError(null);
     setProtocolNotice(null);
+    setProbeReport(null);
     try {
-      await invoke("disconnect");
-      const result = await invoke<ConnectResult>("connect", {
-        url: formData.gatewayUrl,
-        token: formData.gatewayToken,
-      });
+      // Probes without touching the current connection
+      const report = await probeGateway(
+        formData.gatewayUrl,
+        formData.gatewayToken || undefined,
+      );
+      setProbeReport(report);
+      if (report.error) {
+        setConnectionStatus("error");
+        setError(report.error);
+        return;
+      }
       setConnectionStatus("idle");
 
       // If protocol was switched, update the URL and show notice
-      if (result.protocol_switched) {
-        setFormData({ ...formData, gatewayUrl: result.used_url });
+      if (report.protocolSwitched && report.usedUrl) {
+        setFormData({ ...formData, gatewayUrl: report.usedUrl });
+        setProtocolNotice(
+          `Reachable using ${report.usedUrl.startsWith("wss://") ? "wss://" : "ws://"} (auto-detected)`,
+        );
+      } else {
         setProtocolNotice(
-          `Connected using ${result.used_url.startsWith("wss://") ? "wss://" : "ws://"} (auto-detected)`,
+          report.authenticated
+            ? "Gateway reachable and token accepted"
+            : "Gateway reachable",
         );
       }
-
-      // Try to fetch models after successful connection
-      fetchModels();
     } catch (err: unknown) {
       setConnectionStatus("error");
-      setError(String(err));
+      setError(errorMessage(err));
     }
   };
 
@@ -464,7 +562,7 @@ export function SettingsDialog({
                     )}
                   >
                     {connectionStatus === "connecting"
-                      ? "Connecting..."
+                      ? "Testing..."
                       : "Test Connection"}
                   </button>
                   <div className="flex items-center gap-2">
@@ -498,9 +596,69 @@ export function SettingsDialog({
                   {protocolNotice}
                 </p>
               )}
+              {probeReport?.trace && probeReport.trace.steps.length > 0 && (
+                <ul className="text-xs text-muted-foreground space-y-0.5">
+                  {probeReport.trace.steps.map((step, i) => (
+                    <li key={i} className="flex gap-2">
+                      <span
+                        className={
+                          step.ok
+                            ? "text-green-600 dark:text-green-400"
+                            : "text-destructive"
+                        }
+                      >
+                        {step.ok ? "✓" : "✗"}
+                      </span>
+                      <span className="w-20 flex-shrink-0">
+                        {TRACE_STEP_LABELS[step.step]}
+                      </span>
+                      <span className="w-14 flex-shrink-0 text-right tabular-nums">
+                        {step.durationMs} ms
+                      </span>
+                      {step.detail && (
+                        <span className="truncate" title={step.detail}>
+                          {step.detail}
+                        </span>
+                      )}
+                    </li>
+                  ))}
+                  {probeReport.compatIssues.map((issue) => (
+                    <li
+                      key={issue}
+                      className="text-amber-600 dark:text-amber-400"
+                    >
+                      {issue}
+                    </li>
+                  ))}
+                </ul>
+              )}
             </div>
           </section>
 
+          {/* Home/Away Section */}
+          <section>
+            <h3 className="text-sm font-semibold text-muted-foreground uppercase tracking-wider mb-3">
+              Home &amp; Away
+            </h3>
+            <HomeAwayEndpoints
+              gatewayUrl={formData.gatewayUrl}
+              onError={showToastError}
+              onSuccess={showSuccess}
+            />
+          </section>
+
+          {/* DNS Section */}
+          <section>
+            <h3 className="text-sm font-semibold text-muted-foreground uppercase tracking-wider mb-3">
+              DNS
+            </h3>
+            <DnsResolver
+              gatewayUrl={formData.gatewayUrl}
+              onError={showToastError}
+              onSuccess={showSuccess}
+            />
+          </section>
+
           {/* Chat Section */}
           <section>
             <h3 className="text-sm font-semibold text-muted-foreground uppercase tracking-wider mb-3">
@@ -708,6 +866,275 @@ export function SettingsDialog({
               </div>
             </fieldset>
           </section>
+
+          {/* Data Usage Section */}
+          <section>
+            <h3 className="text-sm font-semibold text-muted-foreground uppercase tracking-wider mb-3">
+              Data Usage
+            </h3>
+            <div className="space-y-3">
+              <div className="flex items-center justify-between p-3 border border-border rounded-xl">
+                <div className="space-y-1">
+                  <span className="text-sm font-medium">Data Saver</span>
+                  <p className="text-xs text-muted-foreground">
+                    Don't auto-load remote images, compress image attachments
+                    and batch streamed text
+                  </p>
+                </div>
+                <Switch
+                  checked={dataSaver}
+                  onCheckedChange={(checked) => {
+                    // Applies immediately, like the theme
+                    setDataSaver(checked).catch((err) =>
+                      showToastError(
+                        `Failed to update data saver: ${errorMessage(err)}`,
+                      ),
+                    );
+                  }}
+                  aria-label="Toggle data saver"
+                />
+              </div>
+              {responseCache && (
+                <div className="flex items-center justify-between p-3 border border-border rounded-xl">
+                  <div className="space-y-1">
+                    <span className="text-sm font-medium">
+                      Reuse Quick Ask answers
+                    </span>
+                    <p className="text-xs text-muted-foreground">
+                      Answer a repeated question from the last{" "}
+                      {responseCache.ttlHours} hours instantly, without a new
+                      run
+                      {responseCache.entries > 0 && (
+                        <>
+                          {" · "}
+                          <button
+                            type="button"
+                            onClick={() =>
+                              clearResponseCache()
+                                .then(setResponseCacheStatus)
+                                .catch((err) =>
+                                  showToastError(
+                                    `Failed to clear cached answers: ${errorMessage(err)}`,
+                                  ),
+                                )
+                            }
+                            className="underline hover:text-foreground transition-colors"
+                          >
+                            Clear {responseCache.entries} cached
+                          </button>
+                        </>
+                      )}
+                    </p>
+                  </div>
+                  <Switch
+                    checked={responseCache.enabled}
+                    onCheckedChange={(checked) => {
+                      setResponseCache({
+                        enabled: checked,
+                        ttlHours: responseCache.ttlHours,
+                      })
+                        .then(setResponseCacheStatus)
+                        .catch((err) =>
+                          showToastError(
+                            `Failed to update response cache: ${errorMessage(err)}`,
+                          ),
+                        );
+                    }}
+                    aria-label="Toggle reusing Quick Ask answers"
+                  />
+                </div>
+              )}
+              {promptHistory && (
+                <div className="flex items-center justify-between p-3 border border-border rounded-xl">
+                  <div className="space-y-1">
+                    <span className="text-sm font-medium">
+                      Remember Quick Ask prompts
+                    </span>
+                    <p className="text-xs text-muted-foreground">
+                      Suggest earlier questions as you type. Kept encrypted on
+                      this device only
+                      {promptHistory.enabled &&
+                        promptHistory.entries !== 0 && (
+                          <>
+                            {" · "}
+                            <button
+                              type="button"
+                              onClick={() =>
+                                clearPromptHistory()
+                                  .then(setPromptHistoryStatus)
+                                  .catch((err) =>
+                                    showToastError(
+                                      `Failed to clear prompt history: ${errorMessage(err)}`,
+                                    ),
+                                  )
+                              }
+                              className="underline hover:text-foreground transition-colors"
+                            >
+                              Clear history
+                            </button>
+                          </>
+                        )}
+                    </p>
+                  </div>
+                  <Switch
+                    checked={promptHistory.enabled}
+                    onCheckedChange={(checked) => {
+                      setPromptHistory({
+                        enabled: checked,
+                        maxEntries: promptHistory.maxEntries,
+                      })
+                        .then(setPromptHistoryStatus)
+                        .catch((err) =>
+                          showToastError(
+                            `Failed to update prompt history: ${errorMessage(err)}`,
+                          ),
+                        );
+                    }}
+                    aria-label="Toggle remembering Quick Ask prompts"
+                  />
+                </div>
+              )}
+              {dataUsage && (
+                <div className="p-3 border border-border rounded-xl text-sm space-y-2">
+                  <div className="flex items-center justify-between">
+                    <span className="font-medium">Since app start (estimated)</span>
+                    <button
+                      type="button"
+                      onClick={async () => {
+                        await resetDataUsage();
+                        setDataUsage(await getDataUsage());
+                      }}
+                      className="text-xs text-muted-foreground hover:text-foreground transition-colors"
+                    >
+                      Reset
+                    </button>
+                  </div>
+                  <p className="text-xs text-muted-foreground">
+                    {formatBytes(dataUsage.total.sent)} sent,{" "}
+                    {formatBytes(dataUsage.total.received)} received
+                  </p>
+                  {dataUsage.sessions.slice(0, 5).map((session) => (
+                    <div
+                      key={session.sessionKey}
+                      className="flex justify-between gap-2 text-xs text-muted-foreground"
+                    >
+                      <span className="truncate">{session.sessionKey}</span>
+                      <span className="flex-shrink-0">
+                        {formatBytes(session.sent + session.received)}
+                      </span>
+                    </div>
+                  ))}
+                </div>
+              )}
+            </div>
+          </section>
+
+          {/* Usage Limits Section */}
+          <section>
+            <h3 className="text-sm font-semibold text-muted-foreground uppercase tracking-wider mb-3">
+              Usage Limits
+            </h3>
+            <QuotaStatus onError={showToastError} />
+          </section>
+
+          {/* Presets Section */}
+          <section>
+            <h3 className="text-sm font-semibold text-muted-foreground uppercase tracking-wider mb-3">
+              Presets
+            </h3>
+            <Presets onError={showToastError} onSuccess={showSuccess} />
+          </section>
+
+          {/* Scheduled Prompts Section */}
+          <section>
+            <h3 className="text-sm font-semibold text-muted-foreground uppercase tracking-wider mb-3">
+              Scheduled Prompts
+            </h3>
+            <ScheduledPrompts
+              sessionKey={currentConversationId}
+              onError={showToastError}
+            />
+          </section>
+
+          {/* Gateway Notifications Section */}
+          <section>
+            <h3 className="text-sm font-semibold text-muted-foreground uppercase tracking-wider mb-3">
+              Gateway Notifications
+            </h3>
+            <SystemNotifications onError={showToastError} />
+          </section>
+
+          {/* Archive Section */}
+          <section>
+            <h3 className="text-sm font-semibold text-muted-foreground uppercase tracking-wider mb-3">
+              Archive
+            </h3>
+            <ArchivedConversations
+              onError={showToastError}
+              onSuccess={showSuccess}
+            />
+          </section>
+
+          {/* Storage Section */}
+          <section>
+            <h3 className="text-sm font-semibold text-muted-foreground uppercase tracking-wider mb-3">
+              Storage
+            </h3>
+            <StorageManagement
+              onError={showToastError}
+              onSuccess={showSuccess}
+            />
+          </section>
+
+          {/* Guest Mode Section */}
+          <section>
+            <h3 className="text-sm font-semibold text-muted-foreground uppercase tracking-wider mb-3">
+              Guest Mode
+            </h3>
+            <GuestModeSettings
+              onError={showToastError}
+              onSuccess={showSuccess}
+            />
+          </section>
+
+          {/* Settings File Section */}
+          <section>
+            <h3 className="text-sm font-semibold text-muted-foreground uppercase tracking-wider mb-3">
+              Settings File
+            </h3>
+            <div className="flex items-center justify-between gap-3 p-3 border border-border rounded-xl">
+              <p className="text-xs text-muted-foreground">
+                Share data saver, answer reuse, discovery hosts and DNS with
+                your team. Tokens are never included.
+              </p>
+              <div className="flex gap-2 flex-shrink-0">
+                <button
+                  type="button"
+                  onClick={handleImportSettings}
+                  className="px-3 py-1.5 rounded-lg text-xs font-medium border border-border hover:bg-muted transition-colors"
+                >
+                  Import
+                </button>
+                <button
+                  type="button"
+                  onClick={handleExportSettings}
+                  className="px-3 py-1.5 rounded-lg text-xs font-medium border border-border hover:bg-muted transition-colors"
+                >
+                  Export
+                </button>
+              </div>
+            </div>
+          </section>
+
+          {/* Developer Section (development builds only) */}
+          {import.meta.env.DEV && (
+            <section>
+              <h3 className="text-sm font-semibold text-muted-foreground uppercase tracking-wider mb-3">
+                Developer
+              </h3>
+              <ChaosPanel onError={showToastError} />
+            </section>
+          )}
         </div>
 
         {/* Footer */}
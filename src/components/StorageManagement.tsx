@@ -0,0 +1,173 @@
+import { useCallback, useEffect, useState } from "react";
+import { formatBytes } from "../lib/dataSaver";
+import { errorMessage } from "../lib/errors";
+import { PortableStatus, getPortableStatus } from "../lib/portable";
+import {
+  STORAGE_CATEGORY_LABELS,
+  StorageSettings,
+  StorageUsage,
+  getStorageSettings,
+  getStorageUsage,
+  runStorageMaintenance,
+  setStorageSettings,
+} from "../lib/storage";
+
+interface StorageManagementProps {
+  onError: (message: string) => void;
+  onSuccess: (message: string) => void;
+}
+
+/**
+ * Settings section showing the space used by logs and caches, with their
+ * size caps
+ */
+export function StorageManagement({
+  onError,
+  onSuccess,
+}: StorageManagementProps) {
+  const [usage, setUsage] = useState<StorageUsage | null>(null);
+  const [draft, setDraft] = useState<StorageSettings | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [portable, setPortable] = useState<PortableStatus | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      const [current, settings] = await Promise.all([
+        getStorageUsage(),
+        getStorageSettings(),
+      ]);
+      setUsage(current);
+      setDraft(settings);
+    } catch (err) {
+      onError(`Failed to load storage usage: ${errorMessage(err)}`);
+    }
+  }, [onError]);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  useEffect(() => {
+    getPortableStatus()
+      .then(setPortable)
+      .catch(() => setPortable(null));
+  }, []);
+
+  if (!usage || !draft) return null;
+
+  const handleSave = async () => {
+    try {
+      await setStorageSettings(draft);
+      onSuccess("Storage limits saved");
+      await refresh();
+    } catch (err) {
+      onError(`Failed to save storage limits: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleCleanUp = async () => {
+    setBusy(true);
+    try {
+      const report = await runStorageMaintenance();
+      onSuccess(
+        report.filesRemoved > 0
+          ? `Reclaimed ${formatBytes(report.reclaimedBytes)}`
+          : "Nothing to clean up",
+      );
+      await refresh();
+    } catch (err) {
+      onError(`Failed to clean up: ${errorMessage(err)}`);
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const setLimit = (key: keyof StorageSettings, value: string) =>
+    setDraft({ ...draft, [key]: Math.max(1, Math.round(Number(value)) || 1) });
+
+  const inputClass =
+    "px-3 py-1.5 text-sm border border-border rounded-lg bg-background focus:outline-none focus:ring-2 focus:ring-primary/50";
+  const buttonClass =
+    "px-3 py-1.5 rounded-lg text-xs font-medium border border-border hover:bg-muted transition-colors disabled:opacity-50";
+  const last = usage.lastMaintenance;
+
+  return (
+    <div className="p-3 border border-border rounded-xl space-y-3">
+      <p className="text-xs text-muted-foreground">
+        Logs and caches are kept under these limits; the oldest files are
+        removed first.
+      </p>
+      {portable?.enabled && (
+        <p className="text-xs text-muted-foreground">
+          Portable mode: data and secrets are kept in{" "}
+          <span className="font-mono break-all">{portable.root}</span>
+        </p>
+      )}
+      {usage.categories.map((category) => (
+        <div key={category.category} className="space-y-1">
+          <div className="flex justify-between text-xs">
+            <span className="font-medium">
+              {STORAGE_CATEGORY_LABELS[category.category]}
+            </span>
+            <span className="text-muted-foreground">
+              {formatBytes(category.bytes)} of{" "}
+              {formatBytes(category.limitBytes)} · {category.files} file(s)
+            </span>
+          </div>
+          <div className="h-1.5 rounded-full bg-muted overflow-hidden">
+            <div
+              className="h-full bg-primary"
+              style={{
+                width: `${Math.min(100, (category.bytes / category.limitBytes) * 100)}%`,
+              }}
+            />
+          </div>
+        </div>
+      ))}
+      <div className="flex flex-wrap items-center gap-3 text-xs text-muted-foreground">
+        <label className="flex items-center gap-2">
+          Logs
+          <input
+            type="number"
+            min={1}
+            value={draft.logLimitMb}
+            onChange={(e) => setLimit("logLimitMb", e.target.value)}
+            className={`${inputClass} w-24`}
+          />
+          MB
+        </label>
+        <label className="flex items-center gap-2">
+          Caches
+          <input
+            type="number"
+            min={1}
+            value={draft.cacheLimitMb}
+            onChange={(e) => setLimit("cacheLimitMb", e.target.value)}
+            className={`${inputClass} w-24`}
+          />
+          MB
+        </label>
+      </div>
+      <div className="flex items-center justify-between gap-3">
+        <span className="text-xs text-muted-foreground">
+          {last
+            ? `Last cleanup ${new Date(last.ranAt).toLocaleTimeString()}: ${formatBytes(last.reclaimedBytes)} reclaimed`
+            : "No cleanup yet this session"}
+        </span>
+        <div className="flex gap-2 flex-shrink-0">
+          <button
+            type="button"
+            onClick={handleCleanUp}
+            disabled={busy}
+            className={buttonClass}
+          >
+            {busy ? "Cleaning up…" : "Clean up now"}
+          </button>
+          <button type="button" onClick={handleSave} className={buttonClass}>
+            Save
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,82 @@
+import { useEffect, useState } from "react";
+import { errorMessage } from "../lib/errors";
+import {
+  CronNotify,
+  SystemNotificationSettings,
+  getSystemNotificationSettings,
+  setSystemNotificationSettings,
+} from "../lib/systemEvents";
+
+interface SystemNotificationsProps {
+  onError: (message: string) => void;
+}
+
+/**
+ * Settings section choosing which Gateway system events (scheduled job
+ * results, channel messages) post desktop notifications
+ */
+export function SystemNotifications({ onError }: SystemNotificationsProps) {
+  const [settings, setSettings] = useState<SystemNotificationSettings | null>(
+    null,
+  );
+
+  useEffect(() => {
+    getSystemNotificationSettings()
+      .then(setSettings)
+      .catch((err) =>
+        onError(`Failed to load notification rules: ${errorMessage(err)}`),
+      );
+  }, [onError]);
+
+  if (!settings) return null;
+
+  const update = async (changes: Partial<SystemNotificationSettings>) => {
+    const next = { ...settings, ...changes };
+    setSettings(next);
+    try {
+      await setSystemNotificationSettings(next);
+    } catch (err) {
+      onError(`Failed to save notification rules: ${errorMessage(err)}`);
+    }
+  };
+
+  const checkboxClass =
+    "w-4 h-4 rounded border-border text-primary focus:ring-primary/50";
+
+  return (
+    <div className="p-3 border border-border rounded-xl space-y-3 text-sm">
+      <label className="flex items-center justify-between gap-3">
+        Scheduled task results
+        <select
+          value={settings.cronResults}
+          onChange={(e) =>
+            update({ cronResults: e.target.value as CronNotify })
+          }
+          className="px-3 py-1.5 text-sm border border-border rounded-lg bg-background focus:outline-none focus:ring-2 focus:ring-primary/50"
+        >
+          <option value="all">All</option>
+          <option value="failures">Failures only</option>
+          <option value="off">Off</option>
+        </select>
+      </label>
+      <label className="flex items-center gap-2">
+        <input
+          type="checkbox"
+          checked={settings.channelMessages}
+          onChange={(e) => update({ channelMessages: e.target.checked })}
+          className={checkboxClass}
+        />
+        Messages arriving on Gateway channels
+      </label>
+      <label className="flex items-center gap-2">
+        <input
+          type="checkbox"
+          checked={settings.whenFocused}
+          onChange={(e) => update({ whenFocused: e.target.checked })}
+          className={checkboxClass}
+        />
+        Notify even while Moltz is focused
+      </label>
+    </div>
+  );
+}
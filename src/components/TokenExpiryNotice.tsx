@@ -0,0 +1,177 @@
+import { useState, useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { motion, AnimatePresence } from "framer-motion";
+import { KeyRound, X, AlertCircle } from "lucide-react";
+import { cn } from "../lib/utils";
+import { errorMessage, type CommandError } from "../lib/errors";
+import {
+  cancelRepair,
+  getTokenExpiry,
+  startRepair,
+  type PairingCode,
+  type TokenExpiring,
+} from "../lib/tokenExpiry";
+
+function expiryText(days: number): string {
+  if (days <= 0) return "Your Gateway token expires today";
+  if (days === 1) return "Your Gateway token expires tomorrow";
+  return `Your Gateway token expires in ${days} days`;
+}
+
+/**
+ * Token expiry warning with one-click re-pairing (device code + QR)
+ */
+export function TokenExpiryNotice() {
+  const [daysLeft, setDaysLeft] = useState<number | null>(null);
+  const [pairing, setPairing] = useState<PairingCode | null>(null);
+  const [isStarting, setIsStarting] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const unlistenExpiring = listen<TokenExpiring>(
+      "gateway:token_expiring",
+      (event) => setDaysLeft(event.payload.daysLeft),
+    );
+    // Tray menu / notification click
+    const unlistenMenu = listen("menu:repair", () => {
+      getTokenExpiry()
+        .then((status) => setDaysLeft(status.expiringInDays ?? 0))
+        .catch((err) => console.error("Failed to get token expiry:", err));
+    });
+    const unlistenRepaired = listen("gateway:repaired", () => {
+      setPairing(null);
+      setDaysLeft(null);
+    });
+    const unlistenFailed = listen<CommandError>(
+      "gateway:repair_failed",
+      (event) => {
+        setPairing(null);
+        setError(errorMessage(event.payload));
+      },
+    );
+
+    getTokenExpiry()
+      .then((status) => setDaysLeft(status.expiringInDays))
+      .catch((err) => console.error("Failed to get token expiry:", err));
+
+    return () => {
+      unlistenExpiring.then((fn) => fn());
+      unlistenMenu.then((fn) => fn());
+      unlistenRepaired.then((fn) => fn());
+      unlistenFailed.then((fn) => fn());
+    };
+  }, []);
+
+  const handleRepair = async () => {
+    setIsStarting(true);
+    setError(null);
+    try {
+      setPairing(await startRepair());
+    } catch (err) {
+      console.error("Failed to start re-pairing:", err);
+      setError(errorMessage(err));
+    } finally {
+      setIsStarting(false);
+    }
+  };
+
+  const handleDismiss = () => {
+    if (pairing) {
+      cancelRepair().catch((err) =>
+        console.error("Failed to cancel re-pairing:", err),
+      );
+    }
+    setPairing(null);
+    setError(null);
+    setDaysLeft(null);
+  };
+
+  if (daysLeft === null) {
+    return null;
+  }
+
+  return (
+    <AnimatePresence>
+      <motion.div
+        initial={{ opacity: 0, y: 50 }}
+        animate={{ opacity: 1, y: 0 }}
+        exit={{ opacity: 0, y: 50 }}
+        role="alert"
+        className={cn(
+          "fixed bottom-4 right-4 z-50",
+          "w-96 rounded-lg shadow-2xl",
+          "bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700",
+          "overflow-hidden",
+        )}
+      >
+        <div className="flex items-start gap-3 p-4 pb-3 border-b border-gray-200 dark:border-gray-700">
+          <div className="flex-shrink-0 w-10 h-10 rounded-full bg-amber-100 dark:bg-amber-900/30 flex items-center justify-center">
+            <KeyRound className="w-5 h-5 text-amber-600 dark:text-amber-400" />
+          </div>
+          <div className="flex-1 min-w-0">
+            <h3 className="font-semibold text-sm text-gray-900 dark:text-white">
+              {expiryText(daysLeft)}
+            </h3>
+            <p className="text-xs text-gray-600 dark:text-gray-400 mt-0.5">
+              Re-pair now so the Gateway keeps accepting this device
+            </p>
+          </div>
+          <button
+            onClick={handleDismiss}
+            className="flex-shrink-0 p-1 rounded hover:bg-gray-100 dark:hover:bg-gray-700 transition-colors"
+            aria-label="Dismiss"
+          >
+            <X className="w-4 h-4 text-gray-500 dark:text-gray-400" />
+          </button>
+        </div>
+
+        <div className="p-4">
+          {error && (
+            <div className="mb-3 p-2 rounded bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 flex items-start gap-2">
+              <AlertCircle className="w-4 h-4 text-red-600 dark:text-red-400 flex-shrink-0 mt-0.5" />
+              <p className="text-xs text-red-700 dark:text-red-300">{error}</p>
+            </div>
+          )}
+
+          {pairing ? (
+            <div className="space-y-3 text-center">
+              {/* SVG generated by the backend from the verification URL */}
+              <div
+                className="mx-auto w-48 h-48 bg-white rounded [&>svg]:w-full [&>svg]:h-full"
+                dangerouslySetInnerHTML={{ __html: pairing.qrSvg }}
+                aria-label="Pairing QR code"
+              />
+              <p className="text-xs text-gray-600 dark:text-gray-400">
+                Scan the code, or open{" "}
+                <span className="font-medium break-all">
+                  {pairing.verificationUrl}
+                </span>{" "}
+                and enter
+              </p>
+              <p className="font-mono text-lg tracking-widest text-gray-900 dark:text-white">
+                {pairing.userCode}
+              </p>
+              <p className="text-xs text-gray-500 dark:text-gray-400">
+                Waiting for approval…
+              </p>
+            </div>
+          ) : (
+            <button
+              onClick={handleRepair}
+              disabled={isStarting}
+              className={cn(
+                "w-full px-4 py-2 rounded-md text-sm font-medium",
+                "bg-blue-600 hover:bg-blue-700 text-white",
+                "transition-colors focus:outline-none focus:ring-2 focus:ring-blue-500 focus:ring-offset-2",
+                "dark:focus:ring-offset-gray-800",
+                "disabled:opacity-50 disabled:cursor-not-allowed",
+              )}
+            >
+              {isStarting ? "Starting…" : "Re-pair now"}
+            </button>
+          )}
+        </div>
+      </motion.div>
+    </AnimatePresence>
+  );
+}
@@ -0,0 +1,73 @@
+import { useState, useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { Info, Zap } from "lucide-react";
+import { cn } from "../lib/utils";
+import { getTrayStatus, runTrayAction, type TrayStatus } from "../lib/tray";
+
+/**
+ * Stand-in for the system tray on desktops that have none: shows the status
+ * the tray tooltip would show, and the Quick Ask action
+ */
+export function TrayFallback() {
+  const [status, setStatus] = useState<TrayStatus | null>(null);
+
+  useEffect(() => {
+    const unlistenUnavailable = listen<TrayStatus>(
+      "tray:unavailable",
+      (event) => setStatus(event.payload),
+    );
+    const unlistenAvailable = listen<TrayStatus>("tray:available", (event) =>
+      setStatus(event.payload),
+    );
+    const unlistenStatus = listen<string>("tray:status", (event) =>
+      setStatus((current) =>
+        current ? { ...current, statusText: event.payload } : current,
+      ),
+    );
+
+    // The event may have fired before this mounted
+    getTrayStatus()
+      .then((current) => {
+        if (!current.available) setStatus(current);
+      })
+      .catch((err) => console.error("Failed to get tray status:", err));
+
+    return () => {
+      unlistenUnavailable.then((fn) => fn());
+      unlistenAvailable.then((fn) => fn());
+      unlistenStatus.then((fn) => fn());
+    };
+  }, []);
+
+  if (!status || status.available) {
+    return null;
+  }
+
+  return (
+    <div
+      role="status"
+      className={cn(
+        "fixed bottom-4 left-4 z-40",
+        "flex items-center gap-2 max-w-md px-3 py-1.5 rounded-full shadow-lg",
+        "bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700",
+        "text-xs text-gray-600 dark:text-gray-400",
+      )}
+    >
+      <span title={status.reason ?? undefined} className="flex-shrink-0">
+        <Info className="w-3.5 h-3.5" aria-label="No system tray" />
+      </span>
+      <span className="truncate">{status.statusText || "Moltz"}</span>
+      <button
+        onClick={() =>
+          runTrayAction("tray_quick_ask").catch((err) =>
+            console.error("Failed to open Quick Ask:", err),
+          )
+        }
+        className="flex items-center gap-1 flex-shrink-0 px-2 py-0.5 rounded-full hover:bg-gray-100 dark:hover:bg-gray-700 transition-colors"
+      >
+        <Zap className="w-3 h-3" />
+        Quick Ask
+      </button>
+    </div>
+  );
+}
@@ -4,6 +4,7 @@ import { listen } from "@tauri-apps/api/event";
 import { motion, AnimatePresence } from "framer-motion";
 import { Download, X, AlertCircle } from "lucide-react";
 import { cn } from "../lib/utils";
+import { errorMessage } from "../lib/errors";
 
 interface UpdateInfo {
   available: boolean;
@@ -75,7 +76,7 @@ export function UpdateNotification({
       // Update will be installed and app will restart
     } catch (err) {
       console.error("Update installation failed:", err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(errorMessage(err));
       setIsInstalling(false);
     }
   };
@@ -4,6 +4,10 @@ import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { motion, AnimatePresence } from "framer-motion";
 import { useStore, type ModelInfo } from "../../../stores/store";
 import { cn } from "../../../lib/utils";
+import {
+  errorMessage as describeError,
+  type CommandError,
+} from "../../../lib/errors";
 import { Spinner } from "../../ui/spinner";
 import {
   Tooltip,
@@ -24,13 +28,6 @@ interface GatewaySetupStepProps {
   skipAutoDetect?: boolean;
 }
 
-// Result from the connect command
-interface ConnectResult {
-  success: boolean;
-  used_url: string;
-  protocol_switched: boolean;
-}
-
 type ConnectionState =
   | "idle"
   | "detecting"
@@ -232,7 +229,7 @@ function getErrorHint(
 
 // Format raw error message for display
 function formatErrorMessage(err: unknown): string {
-  const errStr = String(err) || "Unknown error";
+  const errStr = describeError(err) || "Unknown error";
   // Remove "Error: " prefix if present for cleaner display
   return errStr.replace(/^Error:\s*/i, "");
 }
@@ -263,7 +260,7 @@ export function GatewaySetupStep({
   const [protocolNotice, setProtocolNotice] = useState<string>("");
   const [isButtonHovered, setIsButtonHovered] = useState(false);
   const [urlAutoFixNotice, setUrlAutoFixNotice] = useState<string>("");
-  const { updateSettings } = useStore();
+  const { connectAndSaveGateway } = useStore();
 
   // Track mounted state and cancellation
   const isMountedRef = useRef(true);
@@ -295,10 +292,8 @@ export function GatewaySetupStep({
       }
 
       try {
-        const result = await invoke<ConnectResult>("connect", {
-          url,
-          token: "",
-        });
+        // Saves the URL (and clears any stored token) once connected
+        const result = await connectAndSaveGateway(url, "");
 
         // Check again after async operation
         if (isCancelledRef.current || !isMountedRef.current) {
@@ -318,9 +313,6 @@ export function GatewaySetupStep({
           );
         }
 
-        // Auto-save settings with the working URL
-        updateSettings({ gatewayUrl: actualUrl, gatewayToken: "" });
-
         // Save progress
         localStorage.setItem(
           "moltz-onboarding-progress",
@@ -348,7 +340,7 @@ export function GatewaySetupStep({
     if (isMountedRef.current && !isCancelledRef.current) {
       setConnectionState("idle");
     }
-  }, [skipAutoDetect, onGatewayUrlChange, updateSettings, onSuccess]);
+  }, [skipAutoDetect, onGatewayUrlChange, connectAndSaveGateway, onSuccess]);
 
   useEffect(() => {
     // Reset refs on mount
@@ -396,13 +388,10 @@ export function GatewaySetupStep({
       );
 
       // Listen for explicit errors
-      unlistenError = await listen<string>("gateway:error", (event) => {
+      unlistenError = await listen<CommandError>("gateway:error", (event) => {
         if (isMountedRef.current && connectionState === "testing") {
           setConnectionState("error");
-          const errMsg =
-            typeof event.payload === "string"
-              ? event.payload
-              : "Connection error";
+          const errMsg = describeError(event.payload) || "Connection error";
           setErrorMessage(errMsg);
           setErrorHint(getErrorHint(errMsg, gatewayUrl));
         }
@@ -478,18 +467,9 @@ export function GatewaySetupStep({
     }
 
     try {
-      await invoke("disconnect");
-
-      // Check if cancelled
-      if (isCancelledRef.current || !isMountedRef.current) {
-        return;
-      }
-
-      // Race between connect and timeout
-      const connectPromise = invoke<ConnectResult>("connect", {
-        url: trimmedUrl,
-        token: trimmedToken,
-      });
+      // Race between connect and timeout. The URL and token are saved only
+      // once the Gateway accepts them (token in the keychain, by the backend)
+      const connectPromise = connectAndSaveGateway(trimmedUrl, trimmedToken);
       const timeoutPromise = new Promise<never>((_, reject) => {
         setTimeout(
           () => reject(new Error(`Connection timed out after ${Math.round(CONNECT_TIMEOUT_MS / 1000)} seconds`)),
@@ -516,15 +496,6 @@ export function GatewaySetupStep({
         );
       }
 
-      // Step 1: Save settings (token goes to keychain if available, otherwise stays in memory)
-      await updateSettings({ gatewayUrl: actualUrl, gatewayToken: trimmedToken });
-
-      // Check if cancelled
-      if (isCancelledRef.current || !isMountedRef.current) return;
-
-      // Note: We don't verify keychain storage anymore - token is in memory store
-      // which is sufficient for the session. Keychain is just for persistence.
-
       // Check if cancelled
       if (isCancelledRef.current || !isMountedRef.current) return;
 
@@ -1,6 +1,13 @@
 import { useEffect, useState, useRef } from "react";
 import { motion } from "framer-motion";
 import { cn } from "../../../lib/utils";
+import { errorMessage } from "../../../lib/errors";
+import {
+  TEST_STAGE_LABELS,
+  TestMessageReport,
+  failedStage,
+  sendTestMessage,
+} from "../../../lib/testMessage";
 
 interface SuccessStepProps {
   onNext: () => void;
@@ -16,6 +23,84 @@ interface ConfettiParticle {
   scale: number;
 }
 
+/**
+ * Sends a test message on request and shows how far it got
+ */
+function TestMessageCheck() {
+  const [running, setRunning] = useState(false);
+  const [report, setReport] = useState<TestMessageReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const run = async () => {
+    setRunning(true);
+    setReport(null);
+    setError(null);
+    try {
+      setReport(await sendTestMessage());
+    } catch (err) {
+      setError(errorMessage(err));
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  const failed = report && failedStage(report);
+
+  return (
+    <div
+      className="max-w-md mx-auto p-4 rounded-xl border border-border text-left space-y-3"
+      aria-live="polite"
+    >
+      <div className="flex items-center justify-between gap-3">
+        <p className="text-sm text-muted-foreground">
+          Check that replies stream end to end.
+        </p>
+        <button
+          type="button"
+          onClick={run}
+          disabled={running}
+          className="px-3 py-1.5 rounded-lg text-xs font-medium border border-border hover:bg-muted transition-colors disabled:opacity-50 flex-shrink-0"
+        >
+          {running ? "Testing…" : "Send a test message"}
+        </button>
+      </div>
+      {report && (
+        <ul className="text-xs space-y-1">
+          {report.stages.map((stage) => (
+            <li key={stage.stage} className="flex gap-2">
+              <span
+                className={
+                  stage.ok
+                    ? "text-green-600 dark:text-green-400"
+                    : "text-destructive"
+                }
+              >
+                {stage.ok ? "✓" : "✗"}
+              </span>
+              <span className="flex-1">{TEST_STAGE_LABELS[stage.stage]}</span>
+              <span className="text-muted-foreground tabular-nums">
+                {stage.durationMs} ms
+              </span>
+            </li>
+          ))}
+        </ul>
+      )}
+      {report?.ok && (
+        <p className="text-sm font-medium text-green-600 dark:text-green-400">
+          Everything works ✅
+        </p>
+      )}
+      {failed && (
+        <p className="text-sm text-destructive">
+          {TEST_STAGE_LABELS[failed.stage]} failed
+          {failed.detail ? `: ${failed.detail}` : ""}
+        </p>
+      )}
+      {error && <p className="text-sm text-destructive">{error}</p>}
+    </div>
+  );
+}
+
 export function SuccessStep({ onNext, onSkip }: SuccessStepProps) {
   const [confetti, setConfetti] = useState<ConfettiParticle[]>([]);
   const primaryButtonRef = useRef<HTMLButtonElement>(null);
@@ -209,6 +294,8 @@ export function SuccessStep({ onNext, onSkip }: SuccessStepProps) {
           ))}
         </div>
 
+        <TestMessageCheck />
+
         {/* CTA */}
         <motion.div
           initial={{ opacity: 0, y: 32 }}
@@ -0,0 +1,29 @@
+/**
+ * OS accessibility preferences (reduce motion, contrast, text size)
+ * Probed by the backend because webviews don't reliably report them through
+ * CSS media queries; changes arrive as `accessibility:changed`
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface AccessibilityPrefs {
+  reduceMotion: boolean;
+  increaseContrast: boolean;
+  /** Preferred text size relative to the default, where the OS has one */
+  textScale: number | null;
+}
+
+export async function getAccessibilityPrefs(): Promise<AccessibilityPrefs> {
+  return invoke<AccessibilityPrefs>("get_accessibility_prefs");
+}
+
+/**
+ * Reflect the preferences on the root element: `reduce-motion` and
+ * `high-contrast` classes, and the root font size for text scaling
+ */
+export function applyAccessibilityPrefs(prefs: AccessibilityPrefs): void {
+  const root = document.documentElement;
+  root.classList.toggle("reduce-motion", prefs.reduceMotion);
+  root.classList.toggle("high-contrast", prefs.increaseContrast);
+  root.style.fontSize = prefs.textScale ? `${prefs.textScale * 100}%` : "";
+}
@@ -0,0 +1,48 @@
+/**
+ * Agent (persona) selection
+ * The Gateway can host several agents; the choice is stored per session by
+ * the backend and sent with every chat.send
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface AgentInfo {
+  id: string;
+  name: string | null;
+  description: string | null;
+  model: string | null;
+  isDefault: boolean;
+}
+
+interface SessionRecord {
+  sessionKey: string;
+  agentId: string | null;
+}
+
+/**
+ * Agents available on the Gateway (empty for single-agent Gateways)
+ */
+export async function listAgents(): Promise<AgentInfo[]> {
+  return invoke<AgentInfo[]>("agents_list");
+}
+
+/**
+ * Agent chosen for a session; null = the Gateway's default agent
+ */
+export async function getSessionAgent(
+  sessionKey: string,
+): Promise<string | null> {
+  const sessions = await invoke<SessionRecord[]>("list_sessions");
+  return sessions?.find((s) => s.sessionKey === sessionKey)?.agentId ?? null;
+}
+
+export async function setSessionAgent(
+  sessionKey: string,
+  agentId: string | null,
+): Promise<void> {
+  await invoke("set_session_agent", { sessionKey, agentId });
+}
+
+export function agentLabel(agent: AgentInfo): string {
+  return agent.name || agent.id;
+}
@@ -0,0 +1,126 @@
+/**
+ * Conversation archive
+ * Moves old conversations out of IndexedDB into compressed per-conversation
+ * files kept by the backend. Archived conversations stay encrypted and
+ * searchable, and can be restored into the sidebar at any time.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { decrypt } from "./encryption";
+import {
+  exportConversationRows,
+  importConversationRows,
+  type ConversationRows,
+} from "./persistence";
+import { useStore } from "../stores/store";
+
+export interface ArchiveEntry {
+  /** Conversation ID */
+  id: string;
+  /** Decrypted title */
+  title: string;
+  /** ISO timestamp */
+  updatedAt: string | null;
+  /** ISO timestamp */
+  archivedAt: string;
+  messageCount: number;
+  /** Archive file size in bytes */
+  size: number;
+}
+
+/** Entry as listed by the backend, title still encrypted */
+type StoredArchiveEntry = Omit<ArchiveEntry, "title"> & {
+  title: string | null;
+};
+
+export interface ArchiveMatch {
+  conversationId: string;
+  messageId: string;
+  /** Lowercased plaintext around the match */
+  snippet: string;
+}
+
+/** Default age after which conversations are archived */
+export const DEFAULT_ARCHIVE_AFTER_DAYS = 90;
+
+/**
+ * Archive a conversation and remove it from the database
+ * Returns false if it is not persisted (nothing to archive)
+ */
+export async function archiveConversation(id: string): Promise<boolean> {
+  const rows = await exportConversationRows(id);
+  if (!rows) return false;
+  await invoke("archive_conversation", { archive: rows });
+  useStore.getState().deleteConversation(id);
+  return true;
+}
+
+/**
+ * Archive unpinned conversations not updated for `days` days
+ * Returns the number archived
+ */
+export async function archiveOlderThan(
+  days: number,
+  now: Date = new Date(),
+): Promise<number> {
+  const cutoff = now.getTime() - days * 24 * 60 * 60 * 1000;
+  const { conversations, currentConversationId } = useStore.getState();
+  const old = conversations.filter(
+    (c) =>
+      !c.isPinned &&
+      c.id !== currentConversationId &&
+      new Date(c.updatedAt).getTime() < cutoff,
+  );
+  let archived = 0;
+  for (const conversation of old) {
+    if (await archiveConversation(conversation.id)) archived++;
+  }
+  return archived;
+}
+
+export async function listArchives(): Promise<ArchiveEntry[]> {
+  const entries = await invoke<StoredArchiveEntry[]>("list_archives");
+  return Promise.all(
+    entries.map(async (entry) => ({
+      ...entry,
+      title: entry.title
+        ? await decrypt(entry.title).catch(() => "Conversation")
+        : "Conversation",
+    })),
+  );
+}
+
+/**
+ * Restore an archived conversation into the database and the sidebar,
+ * then delete its archive file
+ */
+export async function restoreArchivedConversation(id: string): Promise<void> {
+  const rows = await invoke<ConversationRows>("read_archive", { id });
+  const conversation = await importConversationRows(rows);
+  useStore.setState((state) => ({
+    conversations: [
+      conversation,
+      ...state.conversations.filter((c) => c.id !== id),
+    ].sort(
+      (a, b) =>
+        new Date(b.updatedAt).getTime() - new Date(a.updatedAt).getTime(),
+    ),
+  }));
+  await invoke("delete_archive", { id });
+}
+
+/** Permanently delete an archived conversation */
+export async function deleteArchive(id: string): Promise<void> {
+  await invoke("delete_archive", { id });
+}
+
+/** Search archived messages; every word of the query must match */
+export async function searchArchives(
+  query: string,
+  limit?: number,
+): Promise<ArchiveMatch[]> {
+  return invoke<ArchiveMatch[]>("search_archives", {
+    query,
+    limit: limit ?? null,
+  });
+}
@@ -0,0 +1,32 @@
+/**
+ * Connection chaos mode (development builds only)
+ * Injects latency, frame drops and forced disconnects into the Gateway
+ * connection to exercise reconnection and queueing
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface ChaosConfig {
+  enabled: boolean;
+  /** Maximum latency added to each frame */
+  latencyMs: number;
+  /** Fraction of frames dropped (0 - 1) */
+  dropRate: number;
+  /** Force a disconnect this often; null = only on demand */
+  disconnectIntervalSecs: number | null;
+}
+
+export async function getChaosConfig(): Promise<ChaosConfig> {
+  return invoke<ChaosConfig>("get_chaos_config");
+}
+
+export async function setChaosConfig(config: ChaosConfig): Promise<void> {
+  await invoke("set_chaos_config", { config });
+}
+
+/**
+ * Drop the current connection now, as if the network failed
+ */
+export async function chaosDisconnect(): Promise<void> {
+  await invoke("chaos_disconnect");
+}
@@ -0,0 +1,77 @@
+/**
+ * Connection attempt timeline
+ * Each step of the last connection attempt (DNS → hello-ok) with timings and
+ * outcome, for troubleshooting; also part of the diagnostics bundle
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type TraceStep =
+  | "dns"
+  | "tcp"
+  | "tls"
+  | "ws_upgrade"
+  | "challenge"
+  | "hello_ok";
+
+export interface TraceEntry {
+  step: TraceStep;
+  /** Offset from the start of the attempt */
+  startMs: number;
+  durationMs: number;
+  ok: boolean;
+  /** Resolved addresses, peer address or error message */
+  detail: string | null;
+}
+
+export interface ConnectionTrace {
+  url: string;
+  /** ISO timestamp */
+  startedAt: string;
+  /** Null while the attempt is in progress */
+  success: boolean | null;
+  error: string | null;
+  totalMs: number | null;
+  steps: TraceEntry[];
+}
+
+export async function getLastConnectionTrace(): Promise<ConnectionTrace | null> {
+  return invoke<ConnectionTrace | null>("get_last_connection_trace");
+}
+
+/** Result of probing a Gateway without connecting to it */
+export interface ProbeReport {
+  /** The WebSocket upgrade succeeded */
+  reachable: boolean;
+  /** The Gateway sent connect.challenge */
+  protocolOk: boolean;
+  /** Whether the token was accepted; null when no token was given */
+  authenticated: boolean | null;
+  /** URL that answered (may be upgraded to wss://) */
+  usedUrl: string | null;
+  protocolSwitched: boolean;
+  compatIssues: string[];
+  error: string | null;
+  trace: ConnectionTrace | null;
+}
+
+/** Labels of the trace steps, for display */
+export const TRACE_STEP_LABELS: Record<TraceStep, string> = {
+  dns: "DNS",
+  tcp: "TCP",
+  tls: "TLS",
+  ws_upgrade: "WebSocket",
+  challenge: "Challenge",
+  hello_ok: "Hello",
+};
+
+/**
+ * Run the connection checks against a Gateway without storing credentials
+ * or touching the current connection
+ */
+export async function probeGateway(
+  url: string,
+  token?: string,
+): Promise<ProbeReport> {
+  return invoke<ProbeReport>("probe_gateway", { url, token: token ?? null });
+}
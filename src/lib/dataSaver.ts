@@ -0,0 +1,116 @@
+/**
+ * Bandwidth-saver mode
+ * The setting lives in the backend (which also batches streamed text while it
+ * is on); the frontend skips remote inline images and compresses image
+ * attachments harder
+ */
+
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+export interface DataUsage {
+  sent: number;
+  received: number;
+}
+
+export interface DataUsageReport {
+  total: DataUsage;
+  /** Heaviest sessions first */
+  sessions: (DataUsage & { sessionKey: string })[];
+}
+
+/** Longest edge of image attachments in data saver mode */
+const SAVER_MAX_IMAGE_EDGE = 1280;
+/** JPEG quality of image attachments in data saver mode */
+const SAVER_JPEG_QUALITY = 0.6;
+
+export async function getDataSaver(): Promise<boolean> {
+  return invoke<boolean>("get_data_saver");
+}
+
+export async function setDataSaver(enabled: boolean): Promise<void> {
+  await invoke("set_data_saver", { enabled });
+}
+
+/**
+ * Estimated bytes sent/received since the app started
+ */
+export async function getDataUsage(): Promise<DataUsageReport> {
+  return invoke<DataUsageReport>("get_data_usage");
+}
+
+export async function resetDataUsage(): Promise<void> {
+  await invoke("reset_data_usage");
+}
+
+/**
+ * Whether data saver is on, kept in sync across windows
+ */
+export function useDataSaver(): boolean {
+  const [enabled, setEnabled] = useState(false);
+
+  useEffect(() => {
+    let mounted = true;
+    getDataSaver()
+      .then((value) => mounted && setEnabled(value))
+      .catch(() => {});
+    const unlisten = listen<boolean>("data_saver:changed", (event) => {
+      setEnabled(event.payload);
+    });
+    return () => {
+      mounted = false;
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return enabled;
+}
+
+/**
+ * Downscale and re-encode an image as JPEG for data saver mode
+ * Returns the original when re-encoding would not make it smaller
+ */
+export async function compressImage(
+  base64: string,
+  mimeType: string,
+): Promise<{ data: string; mimeType: string }> {
+  // Animated GIFs would lose their animation
+  if (mimeType === "image/gif") return { data: base64, mimeType };
+
+  const image = new Image();
+  image.src = `data:${mimeType};base64,${base64}`;
+  await image.decode();
+
+  const scale = Math.min(
+    1,
+    SAVER_MAX_IMAGE_EDGE / Math.max(image.naturalWidth, image.naturalHeight),
+  );
+  const canvas = document.createElement("canvas");
+  canvas.width = Math.round(image.naturalWidth * scale);
+  canvas.height = Math.round(image.naturalHeight * scale);
+  const context = canvas.getContext("2d");
+  if (!context) return { data: base64, mimeType };
+
+  // JPEG has no alpha; flatten onto white
+  context.fillStyle = "#fff";
+  context.fillRect(0, 0, canvas.width, canvas.height);
+  context.drawImage(image, 0, 0, canvas.width, canvas.height);
+
+  const compressed = canvas
+    .toDataURL("image/jpeg", SAVER_JPEG_QUALITY)
+    .split(",")[1];
+  if (!compressed || compressed.length >= base64.length) {
+    return { data: base64, mimeType };
+  }
+  return { data: compressed, mimeType: "image/jpeg" };
+}
+
+/**
+ * Format a byte count for display
+ */
+export function formatBytes(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`;
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
@@ -0,0 +1,47 @@
+/**
+ * moltz:// deep links: `moltz://ask?q=<text>&mode=quick&copy=1` runs a query
+ * from launchers (Raycast, Alfred, ...) and can copy the answer back;
+ * `moltz://open?conversation=<id>` (used by Windows toasts, also after a
+ * restart) brings up a conversation
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { useStore } from "../stores/store";
+
+/** Payload of `quickinput:ask` (backend) and `quickinput:submit` (Quick Ask) */
+export interface DeepLinkAsk {
+  message: string;
+  /** Copy the answer to the clipboard when the run completes */
+  copyAnswer: boolean;
+}
+
+/** Payload of `deeplink:open` */
+export interface DeepLinkOpen {
+  conversationId: string;
+  messageId: string | null;
+}
+
+/**
+ * Select a conversation and scroll to a message; unknown conversations are
+ * ignored (conversation IDs double as session keys)
+ */
+export function openConversationAt(
+  conversationId: string,
+  messageId?: string | null,
+): void {
+  const { conversations, selectConversation } = useStore.getState();
+  if (!conversations.some((c) => c.id === conversationId)) return;
+  selectConversation(conversationId);
+  if (!messageId) return;
+  // Wait for the conversation to render
+  setTimeout(() => {
+    document
+      .getElementById(`message-${messageId}`)
+      ?.scrollIntoView({ behavior: "smooth", block: "center" });
+  }, 100);
+}
+
+/** Copy an answer to the clipboard (works without window focus) */
+export async function copyAnswerToClipboard(text: string): Promise<void> {
+  await invoke("copy_to_clipboard", { text });
+}
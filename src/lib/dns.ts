@@ -0,0 +1,63 @@
+/**
+ * DNS resolver selection
+ * The Gateway host can be resolved with system DNS, a custom DNS server or
+ * DNS-over-HTTPS, globally or per Gateway host
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type DnsMode =
+  | { mode: "system" }
+  | { mode: "custom"; server: string }
+  | { mode: "doh"; url: string };
+
+export interface DnsSettings {
+  default: DnsMode;
+  /** Lowercase Gateway host → resolver */
+  overrides: Record<string, DnsMode>;
+}
+
+export interface DnsLookup {
+  /** Resolved socket addresses */
+  addrs: string[];
+  /** Resolver that answered */
+  via: string;
+  cached: boolean;
+}
+
+/** DoH endpoints offered as suggestions */
+export const DOH_PRESETS = [
+  "https://1.1.1.1/dns-query",
+  "https://dns.google/dns-query",
+  "https://dns.quad9.net/dns-query",
+];
+
+export async function getDnsSettings(): Promise<DnsSettings> {
+  return invoke<DnsSettings>("get_dns_settings");
+}
+
+/** Replacing the settings clears cached answers */
+export async function setDnsSettings(settings: DnsSettings): Promise<void> {
+  await invoke("set_dns_settings", { settings });
+}
+
+/** Resolve a host with `mode`, or with the resolver configured for it */
+export async function testDns(
+  host: string,
+  mode?: DnsMode,
+): Promise<DnsLookup> {
+  return invoke<DnsLookup>("test_dns", { host, mode: mode ?? null });
+}
+
+export async function clearDnsCache(): Promise<void> {
+  await invoke("clear_dns_cache");
+}
+
+/** Host of a Gateway URL, lowercased, or null if it doesn't parse */
+export function gatewayHost(url: string): string | null {
+  try {
+    return new URL(url).hostname.toLowerCase() || null;
+  } catch {
+    return null;
+  }
+}
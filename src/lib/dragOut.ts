@@ -0,0 +1,28 @@
+/**
+ * Drag-out: drag a message or code block out of the app as a file
+ * (into Finder/Explorer, an email, an IDE, ...)
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface DragOutOptions {
+  /** Code block language; picks the file extension (Markdown when absent) */
+  language?: string;
+  /** File name without extension */
+  name?: string;
+}
+
+/**
+ * Write the content to a temp file and start a native drag session with it.
+ * Call from `mousedown` while the button is held; resolves to the file path.
+ */
+export async function dragOut(
+  content: string,
+  options: DragOutOptions = {},
+): Promise<string> {
+  return invoke<string>("drag_out", {
+    content,
+    language: options.language ?? null,
+    name: options.name ?? null,
+  });
+}
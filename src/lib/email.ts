@@ -0,0 +1,30 @@
+/**
+ * Email export: opens a prefilled draft in the default mail client
+ * (native compose on macOS, mailto elsewhere)
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { Conversation } from "../stores/store";
+import { exportConversation, ExportOptions } from "./export";
+
+export interface EmailDraft {
+  method: "native" | "mailto";
+  /** Whether the body was shortened to fit a mailto link */
+  truncated: boolean;
+}
+
+/**
+ * Open an email draft with the conversation as plain text (and HTML where
+ * the platform's compose API supports rich text); the backend strips the
+ * Markdown export's syntax
+ */
+export async function emailConversation(
+  conversation: Conversation,
+  options: Omit<ExportOptions, "format"> = {},
+): Promise<EmailDraft> {
+  return invoke<EmailDraft>("compose_email", {
+    subject: conversation.title,
+    body: exportConversation(conversation, { ...options, format: "markdown" }),
+    html: exportConversation(conversation, { ...options, format: "html" }),
+  });
+}
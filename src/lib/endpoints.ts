@@ -0,0 +1,46 @@
+/**
+ * Home/away Gateway endpoints
+ * With a LAN URL and a Tailscale/public URL configured for the same Gateway,
+ * the backend connects through the LAN URL while it is reachable and
+ * switches endpoints when the network changes
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export interface EndpointSettings {
+  enabled: boolean;
+  /** Gateway URL on the home network */
+  homeUrl: string;
+  /** Gateway URL everywhere else */
+  awayUrl: string;
+}
+
+export interface ActiveEndpoint {
+  endpoint: "home" | "away";
+  url: string;
+}
+
+export async function getEndpointSettings(): Promise<EndpointSettings> {
+  return invoke<EndpointSettings>("get_endpoint_settings");
+}
+
+/** Saving moves a live connection to the endpoint for this network */
+export async function setEndpointSettings(
+  settings: EndpointSettings,
+): Promise<void> {
+  await invoke("set_endpoint_settings", { settings });
+}
+
+/** Endpoint of the current connection, null if not a configured one */
+export async function getActiveEndpoint(): Promise<ActiveEndpoint | null> {
+  return invoke<ActiveEndpoint | null>("get_active_endpoint");
+}
+
+export async function onActiveEndpoint(
+  callback: (active: ActiveEndpoint | null) => void,
+): Promise<UnlistenFn> {
+  return listen<ActiveEndpoint | null>("endpoints:active", (event) =>
+    callback(event.payload),
+  );
+}
@@ -5,6 +5,52 @@
  * with actionable suggestions.
  */
 
+/**
+ * Stable error category sent by the backend with every command error and
+ * error event (mirrors `ErrorCode` in src-tauri/src/error.rs)
+ */
+export type ErrorCode =
+  | "network"
+  | "auth"
+  | "protocol"
+  | "gateway"
+  | "validation"
+  | "io"
+  | "internal";
+
+/** Error returned by backend commands and carried by `gateway:error` */
+export interface CommandError {
+  code: ErrorCode;
+  message: string;
+  /** Whether trying again later can succeed without user action */
+  retryable: boolean;
+}
+
+export function isCommandError(error: unknown): error is CommandError {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    "code" in error &&
+    "message" in error
+  );
+}
+
+/**
+ * Message text of a rejected `invoke`, an event payload or a thrown value
+ */
+export function errorMessage(error: unknown): string {
+  if (typeof error === "string") return error;
+  if (isCommandError(error) || error instanceof Error) return error.message;
+  return String(error);
+}
+
+/**
+ * Error code of a rejected `invoke` or event payload, if the backend sent one
+ */
+export function errorCode(error: unknown): ErrorCode | undefined {
+  return isCommandError(error) ? error.code : undefined;
+}
+
 interface UserFriendlyError {
   title: string;
   message: string;
@@ -14,8 +60,10 @@ interface UserFriendlyError {
 /**
  * Translate a technical error message into a user-friendly format
  */
-export function translateError(error: string | Error): UserFriendlyError {
-  const errorString = typeof error === "string" ? error : error.message;
+export function translateError(
+  error: string | Error | CommandError,
+): UserFriendlyError {
+  const errorString = errorMessage(error);
   const lowerError = errorString.toLowerCase();
 
   // Connection errors
@@ -246,7 +294,9 @@ export function translateError(error: string | Error): UserFriendlyError {
 /**
  * Format error for display in UI
  */
-export function formatErrorForDisplay(error: string | Error): string {
+export function formatErrorForDisplay(
+  error: string | Error | CommandError,
+): string {
   const friendly = translateError(error);
   if (friendly.suggestion) {
     return `${friendly.title}: ${friendly.message}\n${friendly.suggestion}`;
@@ -257,7 +307,7 @@ export function formatErrorForDisplay(error: string | Error): string {
 /**
  * Get just the title for compact display (e.g., status bar)
  */
-export function getErrorTitle(error: string | Error): string {
+export function getErrorTitle(error: string | Error | CommandError): string {
   return translateError(error).title;
 }
 
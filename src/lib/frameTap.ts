@@ -0,0 +1,41 @@
+/**
+ * Protocol frame tap for the debug console
+ * While enabled, the backend emits every Gateway frame (redacted and
+ * truncated) on `debug:frame`
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+/** Payload of `debug:frame` */
+export interface TappedFrame {
+  direction: "inbound" | "outbound";
+  /** Milliseconds since the Unix epoch */
+  at: number;
+  /** Size of the original frame in bytes */
+  size: number;
+  /** Redacted, possibly truncated frame text */
+  frame: string;
+  truncated: boolean;
+}
+
+export async function enableFrameTap(enabled: boolean): Promise<void> {
+  await invoke("enable_frame_tap", { enabled });
+}
+
+/**
+ * Enable the tap and subscribe to frames; the returned function
+ * unsubscribes and disables the tap again
+ */
+export async function tapFrames(
+  onFrame: (frame: TappedFrame) => void,
+): Promise<UnlistenFn> {
+  const unlisten = await listen<TappedFrame>("debug:frame", (event) =>
+    onFrame(event.payload),
+  );
+  await enableFrameTap(true);
+  return () => {
+    unlisten();
+    enableFrameTap(false).catch(() => {});
+  };
+}
@@ -0,0 +1,50 @@
+/**
+ * Gateway request batching
+ * Several Gateway requests are sent at once and their responses returned
+ * together, in request order; a failed request carries its error instead of
+ * failing the batch
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type { CommandError } from "./errors";
+
+export interface BatchRequest {
+  /** Key for the response; defaults to the request's index */
+  id?: string;
+  /** Gateway method, e.g. "models.list" */
+  method: string;
+  params?: Record<string, unknown>;
+}
+
+export interface BatchResponse<T = unknown> {
+  id: string;
+  method: string;
+  ok: boolean;
+  payload: T | null;
+  error: CommandError | null;
+}
+
+/**
+ * At most 20 requests, of read-only methods only (`models.list`,
+ * `sessions.list`, `agents.list`, `status`, `config.get`, `usage.status`)
+ */
+export async function gatewayBatch(
+  requests: BatchRequest[],
+): Promise<BatchResponse[]> {
+  return invoke<BatchResponse[]>("gateway_batch", {
+    requests: requests.map((request) => ({
+      id: request.id ?? null,
+      method: request.method,
+      params: request.params ?? null,
+    })),
+  });
+}
+
+/** Responses keyed by request ID */
+export function byId(
+  responses: BatchResponse[],
+): Record<string, BatchResponse> {
+  return Object.fromEntries(
+    responses.map((response) => [response.id, response]),
+  );
+}
@@ -0,0 +1,45 @@
+/**
+ * Subscriptions to Gateway event types that have no built-in handling
+ * Subscribed events are forwarded by the backend as `gateway:event:<name>`
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export interface GatewayEvent<T = unknown> {
+  event: string;
+  payload: T | null;
+}
+
+/**
+ * Tauri event name used for a Gateway event (mirrors the backend sanitizing)
+ */
+export function gatewayEventName(event: string): string {
+  return `gateway:event:${event.replace(/[^A-Za-z0-9_-]/g, "_")}`;
+}
+
+/**
+ * Subscribe to a Gateway event type (e.g. "agent.status")
+ * Returns a function that removes the subscription
+ */
+export async function subscribeGatewayEvent<T = unknown>(
+  event: string,
+  callback: (event: GatewayEvent<T>) => void,
+): Promise<UnlistenFn> {
+  const unlisten = await listen<GatewayEvent<T>>(gatewayEventName(event), (e) =>
+    callback(e.payload),
+  );
+  try {
+    await invoke("subscribe_gateway_events", { events: [event] });
+  } catch (err) {
+    unlisten();
+    throw err;
+  }
+
+  return () => {
+    unlisten();
+    invoke("unsubscribe_gateway_events", { events: [event] }).catch((err) =>
+      console.warn("[gatewayEvents] Unsubscribe failed:", err),
+    );
+  };
+}
@@ -0,0 +1,60 @@
+/**
+ * Guest mode
+ * A time-boxed session that leaves nothing behind: the backend stops writing
+ * its stores and shadows keychain writes in memory, and the frontend keeps
+ * conversations and settings in memory only. When it ends (time up, ended
+ * by the user, or the app quits) everything is wiped and the app restarts.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export interface GuestStatus {
+  active: boolean;
+  /** ISO timestamp */
+  startedAt: string | null;
+  /** ISO timestamp */
+  endsAt: string | null;
+}
+
+export const GUEST_DURATIONS_MINUTES = [15, 30, 60, 120, 240];
+
+let active = false;
+
+// Kept in sync for every window
+void listen<GuestStatus>("guest:started", () => {
+  active = true;
+});
+void listen("guest:ended", () => {
+  active = false;
+});
+
+/** Whether guest mode is on, as last reported by the backend */
+export function guestModeActive(): boolean {
+  return active;
+}
+
+export async function getGuestStatus(): Promise<GuestStatus> {
+  const status = await invoke<GuestStatus>("get_guest_status");
+  active = status.active;
+  return status;
+}
+
+export async function startGuestMode(minutes: number): Promise<GuestStatus> {
+  const status = await invoke<GuestStatus>("start_guest_mode", { minutes });
+  active = status.active;
+  return status;
+}
+
+/** End guest mode now; the app restarts */
+export async function endGuestMode(): Promise<void> {
+  return invoke("end_guest_mode");
+}
+
+export async function onGuestStarted(
+  callback: (status: GuestStatus) => void,
+): Promise<UnlistenFn> {
+  return listen<GuestStatus>("guest:started", (event) =>
+    callback(event.payload),
+  );
+}
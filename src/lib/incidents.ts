@@ -0,0 +1,55 @@
+/**
+ * Incident reports for repeated connection failures
+ * When reconnecting keeps failing, the backend saves the diagnostics bundle
+ * and recent logs (credentials removed) to an incident file in the logs
+ * folder and announces it as `incident:created`; clicking its notification
+ * sends `incident:open`
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export interface IncidentSummary {
+  /** Incident file name without extension */
+  id: string;
+  /** ISO timestamp */
+  createdAt: string;
+  /** Reconnection cycles in a row that gave up */
+  failedCycles: number;
+  /** Why the last connection attempt failed */
+  lastError: string | null;
+}
+
+/** A full incident file */
+export interface Incident extends IncidentSummary {
+  diagnostics: Record<string, unknown>;
+  recentLogs: string[];
+}
+
+export async function listIncidents(): Promise<IncidentSummary[]> {
+  return invoke<IncidentSummary[]>("list_incidents");
+}
+
+export async function getIncident(id: string): Promise<Incident> {
+  return invoke<Incident>("get_incident", { id });
+}
+
+/** URL of a new issue prefilled with the incident summary */
+export async function incidentReportUrl(id: string): Promise<string> {
+  return invoke<string>("incident_report_url", { id });
+}
+
+export async function onIncidentCreated(
+  callback: (incident: IncidentSummary) => void,
+): Promise<UnlistenFn> {
+  return listen<IncidentSummary>("incident:created", (event) =>
+    callback(event.payload),
+  );
+}
+
+/** The incident notification was clicked */
+export async function onIncidentOpen(
+  callback: (id: string) => void,
+): Promise<UnlistenFn> {
+  return listen<string>("incident:open", (event) => callback(event.payload));
+}
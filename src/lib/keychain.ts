@@ -67,6 +67,21 @@ export async function setGatewayToken(token: string): Promise<boolean> {
   }
 }
 
+/**
+ * Re-read the gateway token after the backend replaced it in the keychain
+ * (token rotation), bringing the localStorage fallback up to date
+ */
+export async function reloadGatewayToken(): Promise<string> {
+  const token = await invoke<string>("keychain_get", {
+    service: SERVICE_NAME,
+    key: "gateway_token",
+  });
+  if (token) {
+    localStorage.setItem(FALLBACK_KEY, token);
+  }
+  return token;
+}
+
 /**
  * Delete the gateway token from keychain and localStorage
  */
@@ -81,3 +96,34 @@ export async function deleteGatewayToken(): Promise<void> {
     // Ignore keychain errors
   }
 }
+
+export interface ConnectResult {
+  success: boolean;
+  used_url: string;
+  protocol_switched: boolean;
+}
+
+/**
+ * Connect with new credentials, storing the token only once the Gateway
+ * accepts it (a live connection is kept if the new credentials fail).
+ * An empty token connects without auth and clears the stored one.
+ */
+export async function connectAndSaveGatewayToken(
+  url: string,
+  token: string,
+  profile = "default",
+): Promise<ConnectResult> {
+  const result = await invoke<ConnectResult>("connect_and_save", {
+    profile,
+    url,
+    token,
+  });
+  if (profile === "default") {
+    if (token) {
+      localStorage.setItem(FALLBACK_KEY, token);
+    } else {
+      localStorage.removeItem(FALLBACK_KEY);
+    }
+  }
+  return result;
+}
@@ -0,0 +1,41 @@
+/**
+ * Completed messages kept by the backend
+ * The backend assembles the final message of each run and stores it, so the
+ * full reply can be fetched even if this window missed streamed deltas
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type { TokenUsage } from "../stores/store";
+
+export interface CompletedMessage {
+  id: string;
+  runId: string | null;
+  sessionKey: string | null;
+  content: string;
+  usage: TokenUsage | null;
+  stopReason: string | null;
+  /** ISO timestamp */
+  completedAt: string;
+}
+
+/** Payload of `gateway:complete` */
+export interface RunComplete {
+  usage?: TokenUsage;
+  stopReason?: string;
+  runId?: string | null;
+  /** ID of the stored final message */
+  messageId?: string | null;
+}
+
+export async function getCompletedMessage(
+  id: string,
+): Promise<CompletedMessage | null> {
+  return invoke<CompletedMessage | null>("get_completed_message", { id });
+}
+
+/** Oldest first; only the most recent messages are kept */
+export async function listCompletedMessages(
+  sessionKey: string,
+): Promise<CompletedMessage[]> {
+  return invoke<CompletedMessage[]>("list_completed_messages", { sessionKey });
+}
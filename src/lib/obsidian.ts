@@ -0,0 +1,75 @@
+/**
+ * Obsidian vault export
+ * Conversations are written as interlinked notes by the backend; re-exporting
+ * a conversation updates its existing note
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-dialog";
+import { Conversation } from "../stores/store";
+
+export interface ObsidianConfig {
+  vaultPath: string | null;
+  folder: string | null;
+}
+
+export interface ObsidianExportResult {
+  folder: string;
+  /** Note names keyed by conversation ID */
+  notes: Record<string, string>;
+}
+
+/**
+ * Get the configured vault (vaultPath is null when not configured)
+ */
+export async function getObsidianConfig(): Promise<ObsidianConfig> {
+  return invoke<ObsidianConfig>("get_obsidian_config");
+}
+
+/**
+ * Ask the user to pick a vault folder and save it
+ * Returns false if the picker was cancelled
+ */
+export async function chooseObsidianVault(): Promise<boolean> {
+  const selected = await open({
+    directory: true,
+    title: "Choose your Obsidian vault",
+  });
+  if (typeof selected !== "string") return false;
+
+  const current = await getObsidianConfig();
+  await invoke("set_obsidian_config", {
+    config: { ...current, vaultPath: selected },
+  });
+  return true;
+}
+
+/**
+ * Export conversations into the configured vault, asking for the vault first
+ * if none is configured. Returns null if the user cancelled.
+ */
+export async function exportToObsidian(
+  conversations: Conversation[],
+): Promise<ObsidianExportResult | null> {
+  const config = await getObsidianConfig();
+  if (!config.vaultPath && !(await chooseObsidianVault())) {
+    return null;
+  }
+
+  return invoke<ObsidianExportResult>("export_to_obsidian", {
+    conversations: conversations.map((c) => ({
+      id: c.id,
+      title: c.title,
+      createdAt: c.createdAt,
+      updatedAt: c.updatedAt,
+      model: c.model,
+      isPinned: c.isPinned,
+      messages: c.messages.map((m) => ({
+        role: m.role,
+        content: m.content,
+        timestamp: m.timestamp,
+        modelUsed: m.modelUsed,
+      })),
+    })),
+  });
+}
@@ -0,0 +1,65 @@
+/**
+ * Command palette data
+ * The backend indexes and ranks the palette's entries (menu and tray actions,
+ * presets, recent conversations, models and settings) with fuzzy matching.
+ * Conversation titles only exist in the frontend database, so the palette
+ * hands over an index of them when it opens.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type PaletteKind =
+  | "action"
+  | "preset"
+  | "conversation"
+  | "model"
+  | "setting";
+
+export interface PaletteMatch {
+  kind: PaletteKind;
+  id: string;
+  label: string;
+  detail: string | null;
+  /** Accelerator, e.g. "CmdOrCtrl+N" */
+  shortcut: string | null;
+  score: number;
+  /** Positions (in code points) of the label characters that matched */
+  positions: number[];
+}
+
+export interface PaletteConversation {
+  id: string;
+  title: string;
+  /** ISO timestamp */
+  updatedAt: string;
+  messageCount: number;
+}
+
+/** Entries matching `text`, best first (all entries, in order, when empty) */
+export async function paletteQuery(text: string): Promise<PaletteMatch[]> {
+  return invoke<PaletteMatch[]>("palette_query", { text });
+}
+
+export async function setPaletteConversations(
+  conversations: PaletteConversation[],
+): Promise<void> {
+  return invoke("set_palette_conversations", { conversations });
+}
+
+/** Run an `action` entry through the menu and tray handlers */
+export async function runPaletteAction(id: string): Promise<void> {
+  return invoke("run_palette_action", { id });
+}
+
+/** "CmdOrCtrl+Shift+E" as shown on this platform */
+export function formatShortcut(accelerator: string): string {
+  const mac = navigator.platform.toLowerCase().includes("mac");
+  return accelerator
+    .split("+")
+    .map((key) => {
+      if (key === "CmdOrCtrl") return mac ? "⌘" : "Ctrl+";
+      if (key === "Shift") return mac ? "⇧" : "Shift+";
+      return key;
+    })
+    .join("");
+}
@@ -7,10 +7,12 @@
  * - Automatic sync between Zustand store and database
  *
  * All conversation data is encrypted at rest with zero user friction.
+ * In guest mode nothing is written and conversations stay in memory.
  */
 
 import { type DBMessage, type DBConversation } from "./db";
 import { encrypt, decrypt } from "./encryption";
+import { getGuestStatus, guestModeActive } from "./guest";
 import type { Conversation, Message } from "../stores/store";
 
 // Lazy-load the Dexie database (~95 kB) on first access.
@@ -25,6 +27,79 @@ async function getDb() {
   return _db;
 }
 
+/**
+ * Decrypt a conversation's rows into a store conversation
+ */
+async function decryptConversation(
+  dbConv: DBConversation,
+  dbMessages: DBMessage[],
+): Promise<Conversation> {
+  // Decrypt messages
+  const messages: Message[] = [];
+  for (const dbMsg of dbMessages) {
+    try {
+      const decrypted = await decrypt(dbMsg.content);
+      messages.push({
+        id: dbMsg.id,
+        role: dbMsg.role,
+        content: decrypted,
+        timestamp: dbMsg.timestamp,
+        modelUsed: dbMsg.modelUsed,
+        thinkingContent: dbMsg.thinkingContent
+          ? await decrypt(dbMsg.thinkingContent)
+          : undefined,
+      });
+    } catch (err) {
+      console.error(`Failed to decrypt message ${dbMsg.id}:`, err);
+      // Show placeholder for corrupted messages instead of skipping
+      messages.push({
+        id: dbMsg.id,
+        role: dbMsg.role,
+        content: "⚠️ *This message could not be decrypted. It may have been created with a different encryption key.*",
+        timestamp: dbMsg.timestamp,
+        modelUsed: dbMsg.modelUsed,
+      });
+    }
+  }
+
+  // Decrypt conversation title
+  let title = dbConv.title;
+  try {
+    title = await decrypt(dbConv.title);
+  } catch {
+    // If decryption fails, it might be unencrypted (migration case)
+    console.warn(
+      `Could not decrypt conversation title ${dbConv.id}, using as-is`,
+    );
+  }
+  
+  // Sanity check: if title looks like encoded data (base64, session key, etc.), use default
+  // Signs of corrupted/encoded title: very long, contains +/, no spaces in long string
+  const looksCorrupted = (t: string) => {
+    if (!t || t.length < 3) return true;
+    if (t.length > 50 && !t.includes(' ')) return true;  // Long string with no spaces
+    if (/^[A-Za-z0-9+/=]{20,}$/.test(t)) return true;   // Looks like base64
+    if (/^[a-f0-9-]{32,}$/.test(t)) return true;        // Looks like UUID/hash
+    return false;
+  };
+  
+  if (looksCorrupted(title)) {
+    console.warn(`Conversation ${dbConv.id} has corrupted title, using default`);
+    title = "Conversation";
+  }
+
+  return {
+    id: dbConv.id,
+    title,
+    messages,
+    createdAt: dbConv.createdAt,
+    updatedAt: dbConv.updatedAt,
+    model: dbConv.model,
+    thinkingEnabled: dbConv.thinkingEnabled,
+    isPinned: dbConv.isPinned,
+  };
+}
+
 /**
  * Load all conversations and messages from IndexedDB
  * Automatically decrypts all data
@@ -32,6 +107,11 @@ async function getDb() {
 export async function loadPersistedData(): Promise<{
   conversations: Conversation[];
 }> {
+  // A reloaded window in guest mode starts from an empty history
+  const guest = await getGuestStatus()
+    .then((status) => status.active)
+    .catch(() => false);
+  if (guest) return { conversations: [] };
   try {
     const db = await getDb();
 
@@ -50,70 +130,7 @@ export async function loadPersistedData(): Promise<{
         .equals(dbConv.id)
         .sortBy("timestamp");
 
-      // Decrypt messages
-      const messages: Message[] = [];
-      for (const dbMsg of dbMessages) {
-        try {
-          const decrypted = await decrypt(dbMsg.content);
-          messages.push({
-            id: dbMsg.id,
-            role: dbMsg.role,
-            content: decrypted,
-            timestamp: dbMsg.timestamp,
-            modelUsed: dbMsg.modelUsed,
-            thinkingContent: dbMsg.thinkingContent
-              ? await decrypt(dbMsg.thinkingContent)
-              : undefined,
-          });
-        } catch (err) {
-          console.error(`Failed to decrypt message ${dbMsg.id}:`, err);
-          // Show placeholder for corrupted messages instead of skipping
-          messages.push({
-            id: dbMsg.id,
-            role: dbMsg.role,
-            content: "⚠️ *This message could not be decrypted. It may have been created with a different encryption key.*",
-            timestamp: dbMsg.timestamp,
-            modelUsed: dbMsg.modelUsed,
-          });
-        }
-      }
-
-      // Decrypt conversation title
-      let title = dbConv.title;
-      try {
-        title = await decrypt(dbConv.title);
-      } catch {
-        // If decryption fails, it might be unencrypted (migration case)
-        console.warn(
-          `Could not decrypt conversation title ${dbConv.id}, using as-is`,
-        );
-      }
-      
-      // Sanity check: if title looks like encoded data (base64, session key, etc.), use default
-      // Signs of corrupted/encoded title: very long, contains +/, no spaces in long string
-      const looksCorrupted = (t: string) => {
-        if (!t || t.length < 3) return true;
-        if (t.length > 50 && !t.includes(' ')) return true;  // Long string with no spaces
-        if (/^[A-Za-z0-9+/=]{20,}$/.test(t)) return true;   // Looks like base64
-        if (/^[a-f0-9-]{32,}$/.test(t)) return true;        // Looks like UUID/hash
-        return false;
-      };
-      
-      if (looksCorrupted(title)) {
-        console.warn(`Conversation ${dbConv.id} has corrupted title, using default`);
-        title = "Conversation";
-      }
-
-      conversations.push({
-        id: dbConv.id,
-        title,
-        messages,
-        createdAt: dbConv.createdAt,
-        updatedAt: dbConv.updatedAt,
-        model: dbConv.model,
-        thinkingEnabled: dbConv.thinkingEnabled,
-        isPinned: dbConv.isPinned,
-      });
+      conversations.push(await decryptConversation(dbConv, dbMessages));
     }
 
     return { conversations };
@@ -131,6 +148,7 @@ export async function loadPersistedData(): Promise<{
 export async function persistConversation(
   conversation: Conversation,
 ): Promise<void> {
+  if (guestModeActive()) return;
   try {
     const db = await getDb();
 
@@ -196,6 +214,7 @@ export async function persistConversation(
 export async function deletePersistedConversation(
   conversationId: string,
 ): Promise<void> {
+  if (guestModeActive()) return;
   try {
     const db = await getDb();
 
@@ -209,6 +228,63 @@ export async function deletePersistedConversation(
   }
 }
 
+/**
+ * A conversation's rows as stored (still encrypted), for archiving
+ */
+export interface ConversationRows {
+  conversation: DBConversation;
+  messages: DBMessage[];
+}
+
+/**
+ * Read a conversation's rows without decrypting them
+ * Returns null if the conversation is not in the database
+ */
+export async function exportConversationRows(
+  conversationId: string,
+): Promise<ConversationRows | null> {
+  const db = await getDb();
+  const conversation = await db.conversations.get(conversationId);
+  if (!conversation) return null;
+  const messages = await db.messages
+    .where("conversationId")
+    .equals(conversationId)
+    .sortBy("timestamp");
+  // searchWords is derived on insert
+  return {
+    conversation,
+    messages: messages.map(({ searchWords: _, ...msg }) => msg),
+  };
+}
+
+/**
+ * Write back rows read by exportConversationRows (e.g. from an archive)
+ * and return the decrypted conversation
+ */
+export async function importConversationRows(
+  rows: ConversationRows,
+): Promise<Conversation> {
+  if (guestModeActive()) {
+    throw new Error("Restoring archives is off in guest mode");
+  }
+  const db = await getDb();
+  // Dates come back as ISO strings after a round trip through JSON
+  const conversation: DBConversation = {
+    ...rows.conversation,
+    createdAt: new Date(rows.conversation.createdAt),
+    updatedAt: new Date(rows.conversation.updatedAt),
+  };
+  const messages: DBMessage[] = rows.messages
+    .map((msg) => ({ ...msg, timestamp: new Date(msg.timestamp) }))
+    .sort((a, b) => a.timestamp.getTime() - b.timestamp.getTime());
+
+  await db.transaction("rw", db.conversations, db.messages, async () => {
+    await db.conversations.put(conversation);
+    await db.messages.bulkPut(messages);
+  });
+  return decryptConversation(conversation, messages);
+}
+
 /**
  * Update a single message in the database
  * Used for streaming updates to avoid full conversation sync
@@ -217,6 +293,7 @@ export async function persistMessage(
   conversationId: string,
   message: Message,
 ): Promise<void> {
+  if (guestModeActive()) return;
   try {
     const db = await getDb();
 
@@ -252,6 +329,7 @@ export async function persistMessage(
 export async function updatePersistedConversation(
   conversation: Conversation,
 ): Promise<void> {
+  if (guestModeActive()) return;
   try {
     const db = await getDb();
 
@@ -284,6 +362,8 @@ export async function searchPersistedMessages(
 ): Promise<
   Array<Message & { conversationId: string; conversationTitle: string }>
 > {
+  // Stored conversations are off limits in guest mode
+  if (guestModeActive()) return [];
   try {
     const db = await getDb();
     const searchWords = query.toLowerCase().split(/\s+/);
@@ -342,6 +422,7 @@ export async function searchPersistedMessages(
  * Delete a single message from IndexedDB
  */
 export async function deletePersistedMessage(messageId: string): Promise<void> {
+  if (guestModeActive()) return;
   try {
     const db = await getDb();
     await db.messages.delete(messageId);
@@ -357,6 +438,7 @@ export async function deletePersistedMessage(messageId: string): Promise<void> {
 export async function deletePersistedMessages(
   messageIds: string[],
 ): Promise<void> {
+  if (guestModeActive()) return;
   try {
     const db = await getDb();
     await db.messages.bulkDelete(messageIds);
@@ -371,6 +453,7 @@ export async function deletePersistedMessages(
  * WARNING: This will delete all conversations and messages
  */
 export async function clearAllData(): Promise<void> {
+  if (guestModeActive()) return;
   try {
     const db = await getDb();
 
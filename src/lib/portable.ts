@@ -0,0 +1,18 @@
+/**
+ * Portable mode
+ * Started with `--portable` or a `portable` file next to the executable,
+ * the app keeps its data in a folder beside the executable and its secrets
+ * in an encrypted file there instead of the OS keychain
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface PortableStatus {
+  enabled: boolean;
+  /** Folder holding the app's data when portable */
+  root: string | null;
+}
+
+export async function getPortableStatus(): Promise<PortableStatus> {
+  return invoke<PortableStatus>("get_portable_status");
+}
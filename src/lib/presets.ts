@@ -0,0 +1,83 @@
+/**
+ * Conversation presets
+ * Stored by the backend; applying one patches the Gateway session with its
+ * system prompt, model, thinking level and sampling settings
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { useStore } from "../stores/store";
+
+export type ThinkingLevel = "off" | "minimal" | "low" | "medium" | "high";
+
+export const THINKING_LEVELS: ThinkingLevel[] = [
+  "off",
+  "minimal",
+  "low",
+  "medium",
+  "high",
+];
+
+export interface Preset {
+  /** Empty for a preset that has not been saved yet */
+  id: string;
+  name: string;
+  systemPrompt: string | null;
+  model: string | null;
+  thinking: ThinkingLevel | null;
+  temperature: number | null;
+  maxTokens: number | null;
+}
+
+export async function listPresets(): Promise<Preset[]> {
+  return invoke<Preset[]>("list_presets");
+}
+
+/**
+ * Create (empty ID) or update a preset
+ */
+export async function savePreset(preset: Preset): Promise<Preset> {
+  return invoke<Preset>("save_preset", { preset });
+}
+
+export async function deletePreset(id: string): Promise<void> {
+  await invoke("delete_preset", { id });
+}
+
+/**
+ * Export presets (all, or the given IDs) to a preset file
+ */
+export async function exportPresets(
+  path: string,
+  ids?: string[],
+): Promise<number> {
+  return invoke<number>("export_presets", { path, ids: ids ?? null });
+}
+
+/**
+ * Import presets from a preset file; returns the added presets
+ */
+export async function importPresets(path: string): Promise<Preset[]> {
+  return invoke<Preset[]>("import_presets", { path });
+}
+
+/**
+ * Start a new conversation set up from a preset
+ * (conversation IDs double as session keys)
+ */
+export async function createConversationFromPreset(
+  preset: Preset,
+): Promise<void> {
+  const { createConversation, updateConversation } = useStore.getState();
+  const conversation = createConversation();
+  updateConversation(conversation.id, {
+    title: preset.name,
+    ...(preset.model ? { model: preset.model } : {}),
+    ...(preset.thinking
+      ? { thinkingEnabled: preset.thinking !== "off" }
+      : {}),
+  });
+  await invoke("apply_preset", {
+    sessionKey: conversation.id,
+    presetId: preset.id,
+  });
+}
@@ -0,0 +1,52 @@
+/**
+ * Quick Ask prompt history
+ * Prompts sent from Quick Ask are remembered locally, encrypted, for recall
+ * and autocomplete. They never leave the machine and are not part of
+ * diagnostics or settings files
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface PromptHistorySettings {
+  enabled: boolean;
+  /** Prompts kept; the least recently used are dropped first */
+  maxEntries: number;
+}
+
+export interface PromptHistoryStatus extends PromptHistorySettings {
+  /** Prompts remembered; null until the history is first used */
+  entries: number | null;
+}
+
+export interface PromptSuggestion {
+  prompt: string;
+  count: number;
+  /** ISO timestamp */
+  lastUsed: string;
+}
+
+/** Remembered prompts starting with `prefix`, best first */
+export async function getPromptSuggestions(
+  prefix: string,
+  limit?: number,
+): Promise<PromptSuggestion[]> {
+  return invoke<PromptSuggestion[]>("get_prompt_suggestions", {
+    prefix,
+    limit,
+  });
+}
+
+export async function getPromptHistory(): Promise<PromptHistoryStatus> {
+  return invoke<PromptHistoryStatus>("get_prompt_history");
+}
+
+/** Turning the history off also clears it */
+export async function setPromptHistory(
+  settings: PromptHistorySettings,
+): Promise<PromptHistoryStatus> {
+  return invoke<PromptHistoryStatus>("set_prompt_history", { settings });
+}
+
+export async function clearPromptHistory(): Promise<PromptHistoryStatus> {
+  return invoke<PromptHistoryStatus>("clear_prompt_history");
+}
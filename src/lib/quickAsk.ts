@@ -0,0 +1,59 @@
+/**
+ * Quick Ask queue
+ * Questions from the Quick Ask window run in the background, several at a
+ * time, each on its own session. Progress arrives as `quickask:updated`;
+ * `quickask:open` asks the main window to open a question as a conversation
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export type QuickAskStatus = "queued" | "running" | "done" | "failed";
+
+export interface QuickAsk {
+  id: string;
+  message: string;
+  /** Session the question runs on; also the conversation id when opened */
+  sessionKey: string;
+  status: QuickAskStatus;
+  answer: string | null;
+  error: string | null;
+  /** ISO timestamp */
+  createdAt: string;
+  /** ISO timestamp */
+  finishedAt: string | null;
+  /** Answered from the local response cache */
+  cached: boolean;
+}
+
+/**
+ * Queue a question. Comes back already answered when the response cache has
+ * a fresh answer, unless `bypassCache` is set
+ */
+export async function enqueueQuickAsk(
+  message: string,
+  bypassCache = false,
+): Promise<QuickAsk> {
+  return invoke<QuickAsk>("enqueue_quick_ask", { message, bypassCache });
+}
+
+/** Oldest first */
+export async function listQuickAsks(): Promise<QuickAsk[]> {
+  return invoke<QuickAsk[]>("list_quick_asks");
+}
+
+export async function dismissQuickAsk(id: string): Promise<void> {
+  return invoke("dismiss_quick_ask", { id });
+}
+
+export async function openQuickAsk(id: string): Promise<void> {
+  return invoke("open_quick_ask", { id });
+}
+
+export async function onQuickAskUpdated(
+  callback: (ask: QuickAsk) => void,
+): Promise<UnlistenFn> {
+  return listen<QuickAsk>("quickask:updated", (event) =>
+    callback(event.payload),
+  );
+}
@@ -0,0 +1,45 @@
+/**
+ * Usage quota status and limit warnings
+ * Limits are reported by the Gateway; consumption between fetches is
+ * tracked by the backend, which emits `gateway:quota_warning`
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface QuotaLimit {
+  id: string;
+  name: string;
+  /** "tokens", "requests" or a currency */
+  unit: string;
+  used: number;
+  limit: number;
+  /** ISO timestamp */
+  resetsAt: string | null;
+}
+
+export interface LocalUsage {
+  day: string;
+  dayTokens: number;
+  dayRequests: number;
+  month: string;
+  monthTokens: number;
+  monthRequests: number;
+}
+
+export interface QuotaStatus {
+  limits: QuotaLimit[];
+  local: LocalUsage;
+  /** When the limits were last fetched from the Gateway */
+  updatedAt: string | null;
+}
+
+/** Payload of `gateway:quota_warning` */
+export interface QuotaWarning {
+  limit: QuotaLimit;
+  /** Threshold crossed, in percent */
+  threshold: number;
+}
+
+export async function getQuotaStatus(): Promise<QuotaStatus> {
+  return invoke<QuotaStatus>("get_quota_status");
+}
@@ -0,0 +1,37 @@
+/**
+ * OS recent-documents integration
+ * Lists recently used files for the attachment picker and registers exported
+ * files in the OS recents list
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type RecentFileKind = "image" | "document" | "code";
+
+export interface RecentFile {
+  path: string;
+  name: string;
+  mimeType: string;
+  kind: RecentFileKind;
+  size: number;
+}
+
+/**
+ * Get recently used files that can be attached, newest first
+ */
+export async function getRecentFiles(
+  filter?: RecentFileKind,
+): Promise<RecentFile[]> {
+  return invoke<RecentFile[]>("get_recent_files", { filter: filter ?? null });
+}
+
+/**
+ * Register a file written by the app in the OS recents list (best-effort)
+ */
+export async function addRecentFile(path: string): Promise<void> {
+  try {
+    await invoke("add_recent_file", { path });
+  } catch (err) {
+    console.warn("Failed to add to recent files:", err);
+  }
+}
@@ -0,0 +1,29 @@
+/**
+ * Backend reconnection progress
+ * While the backend waits out its reconnection backoff it ticks
+ * `gateway:reconnect_countdown` every second; `reconnectNow` skips the rest
+ * of the wait
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export interface ReconnectCountdown {
+  attempt: number;
+  maxAttempts: number;
+  /** Time left before the next attempt (0 when it starts) */
+  remainingMs: number;
+}
+
+/** Retry immediately; false when no reconnection is waiting */
+export async function reconnectNow(): Promise<boolean> {
+  return invoke<boolean>("reconnect_now");
+}
+
+export async function onReconnectCountdown(
+  callback: (countdown: ReconnectCountdown) => void,
+): Promise<UnlistenFn> {
+  return listen<ReconnectCountdown>("gateway:reconnect_countdown", (event) =>
+    callback(event.payload),
+  );
+}
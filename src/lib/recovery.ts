@@ -0,0 +1,46 @@
+/**
+ * Recovery after a crash during streaming
+ * The backend journals sends and runs as they happen; on launch it reports
+ * what the previous session left in flight so it can be resumed or retried
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type { SendOutcome } from "./sendGuard";
+
+export interface PendingSend {
+  requestId: string;
+  idempotencyKey: string;
+  sessionKey: string | null;
+  message: string;
+  /** ISO timestamp */
+  sentAt: string;
+}
+
+export interface ActiveRunRecord {
+  runId: string;
+  sessionKey: string | null;
+  /** ISO timestamp */
+  startedAt: string;
+}
+
+export interface InFlight {
+  /** Messages no reply had started for */
+  unsent: PendingSend[];
+  /** Replies that were still streaming */
+  runs: ActiveRunRecord[];
+}
+
+export async function getInterruptedState(): Promise<InFlight> {
+  return invoke<InFlight>("get_interrupted_state");
+}
+
+/** Resend with the original idempotency key (a duplicate is dropped) */
+export async function retryInterruptedSend(
+  requestId: string,
+): Promise<SendOutcome> {
+  return invoke<SendOutcome>("retry_interrupted_send", { requestId });
+}
+
+export async function dismissInterruptedState(): Promise<void> {
+  await invoke("dismiss_interrupted_state");
+}
@@ -0,0 +1,61 @@
+/**
+ * Follow-up reminders on messages
+ * Stored and fired by the backend; clicking the notification emits
+ * `reminder:open` to bring the conversation back up
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { openConversationAt } from "./deepLink";
+
+export interface Reminder {
+  id: string;
+  sessionKey: string;
+  messageId: string;
+  /** ISO timestamp */
+  when: string;
+  note: string | null;
+  createdAt: string;
+}
+
+/** Payload of `reminder:due` and `reminder:open` */
+export interface ReminderTarget {
+  id: string;
+  sessionKey: string;
+  messageId: string;
+  note: string | null;
+}
+
+/** Default reminder time: tomorrow at 9:00 local time */
+export function tomorrowMorning(now: Date = new Date()): Date {
+  const when = new Date(now);
+  when.setDate(when.getDate() + 1);
+  when.setHours(9, 0, 0, 0);
+  return when;
+}
+
+export async function setReminder(
+  sessionKey: string,
+  messageId: string,
+  when: Date,
+  note?: string,
+): Promise<Reminder> {
+  return invoke<Reminder>("set_reminder", {
+    sessionKey,
+    messageId,
+    when: when.toISOString(),
+    note: note ?? null,
+  });
+}
+
+export async function listReminders(): Promise<Reminder[]> {
+  return invoke<Reminder[]>("list_reminders");
+}
+
+export async function cancelReminder(id: string): Promise<void> {
+  await invoke("cancel_reminder", { id });
+}
+
+/** Open a reminder's conversation and scroll to its message */
+export function openReminderTarget(target: ReminderTarget): void {
+  openConversationAt(target.sessionKey, target.messageId);
+}
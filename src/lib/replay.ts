@@ -0,0 +1,29 @@
+/**
+ * Replay of recent run events for windows that open mid-stream
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface ReplayedEvent {
+  /** Increasing per buffer; use to skip events already seen live */
+  seq: number;
+  /** Frontend event name, e.g. `gateway:stream` */
+  event: string;
+  payload: unknown;
+  /** ISO 8601 timestamp */
+  at: string;
+}
+
+/**
+ * Recent run events for a session, oldest first.
+ * Pass `afterSeq` (the last `seq` already seen) to get only newer events.
+ */
+export async function replayRecentEvents(
+  sessionKey: string,
+  afterSeq?: number,
+): Promise<ReplayedEvent[]> {
+  return invoke<ReplayedEvent[]>("replay_recent_events", {
+    sessionKey,
+    afterSeq: afterSeq ?? null,
+  });
+}
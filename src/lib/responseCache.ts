@@ -0,0 +1,33 @@
+/**
+ * Local response cache
+ * Opt-in: repeated identical Quick Ask questions get a recent answer back
+ * instantly instead of a new run
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface ResponseCacheSettings {
+  enabled: boolean;
+  /** How long answers are reused */
+  ttlHours: number;
+}
+
+export interface ResponseCacheStatus extends ResponseCacheSettings {
+  /** Answers currently cached */
+  entries: number;
+}
+
+export async function getResponseCache(): Promise<ResponseCacheStatus> {
+  return invoke<ResponseCacheStatus>("get_response_cache");
+}
+
+/** Turning the cache off also clears it */
+export async function setResponseCache(
+  settings: ResponseCacheSettings,
+): Promise<ResponseCacheStatus> {
+  return invoke<ResponseCacheStatus>("set_response_cache", { settings });
+}
+
+export async function clearResponseCache(): Promise<ResponseCacheStatus> {
+  return invoke<ResponseCacheStatus>("clear_response_cache");
+}
@@ -0,0 +1,110 @@
+/**
+ * Scheduled prompts
+ * Recurring messages stored and sent by the backend; results arrive as a
+ * notification and a `scheduler:run_complete` event
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface Recurrence {
+  /** Local time of day, HH:MM */
+  time: string;
+  /** ISO weekdays (1 = Monday ... 7 = Sunday); empty = every day */
+  days: number[];
+}
+
+export interface ScheduledPrompt {
+  id: string;
+  name: string;
+  message: string;
+  sessionKey: string;
+  recurrence: Recurrence;
+  /** IANA timezone; null = system timezone */
+  timezone: string | null;
+  gatewayUrl: string | null;
+  enabled: boolean;
+  nextRun: string | null;
+  lastResult: { ok: boolean; at: string; excerpt: string } | null;
+}
+
+export interface ScheduledPromptInput {
+  name: string;
+  message: string;
+  sessionKey?: string | null;
+  recurrence: Recurrence;
+  timezone?: string | null;
+  gatewayUrl?: string | null;
+  enabled: boolean;
+}
+
+export interface ScheduledRunComplete {
+  id: string;
+  name: string;
+  sessionKey: string;
+  ok: boolean;
+  /** Full reply, or the error */
+  text: string;
+}
+
+export const WEEKDAYS = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+export async function listScheduledPrompts(): Promise<ScheduledPrompt[]> {
+  return invoke<ScheduledPrompt[]>("list_scheduled_prompts");
+}
+
+export async function createScheduledPrompt(
+  input: ScheduledPromptInput,
+): Promise<ScheduledPrompt> {
+  return invoke<ScheduledPrompt>("create_scheduled_prompt", { input });
+}
+
+export async function updateScheduledPrompt(
+  id: string,
+  input: ScheduledPromptInput,
+): Promise<ScheduledPrompt> {
+  return invoke<ScheduledPrompt>("update_scheduled_prompt", { id, input });
+}
+
+export async function deleteScheduledPrompt(id: string): Promise<void> {
+  await invoke("delete_scheduled_prompt", { id });
+}
+
+/**
+ * Run a schedule now; the result arrives as `scheduler:run_complete`
+ */
+export async function runScheduledPromptNow(id: string): Promise<void> {
+  await invoke("run_scheduled_prompt_now", { id });
+}
+
+/**
+ * "Weekdays at 09:00", "Every day at 18:30", "Mon, Thu at 07:15"
+ */
+export function describeRecurrence(recurrence: Recurrence): string {
+  const days = [...recurrence.days].sort((a, b) => a - b);
+  let label: string;
+  if (days.length === 0 || days.length === 7) {
+    label = "Every day";
+  } else if (days.join() === "1,2,3,4,5") {
+    label = "Weekdays";
+  } else if (days.join() === "6,7") {
+    label = "Weekends";
+  } else {
+    label = days.map((d) => WEEKDAYS[d - 1]).join(", ");
+  }
+  return `${label} at ${recurrence.time}`;
+}
+
+/**
+ * Convert a schedule back to the input shape (for updates)
+ */
+export function toInput(prompt: ScheduledPrompt): ScheduledPromptInput {
+  return {
+    name: prompt.name,
+    message: prompt.message,
+    sessionKey: prompt.sessionKey,
+    recurrence: prompt.recurrence,
+    timezone: prompt.timezone,
+    gatewayUrl: prompt.gatewayUrl,
+    enabled: prompt.enabled,
+  };
+}
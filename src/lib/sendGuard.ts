@@ -0,0 +1,43 @@
+/**
+ * Health-aware send guard
+ * `send_message` reports how the backend handled each message, judged from
+ * the connection state and quality, so the composer can tell the user
+ * instead of failing with "Not connected"
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type SendStatus =
+  | "sent"
+  | "degraded"
+  | "queued_offline"
+  | "queued_reconnecting"
+  | "blocked_needs_reauth"
+  | "not_connected";
+
+/** Result of `send_message` */
+export interface SendOutcome {
+  requestId: string;
+  status: SendStatus;
+}
+
+/** Notice shown for statuses the user should know about */
+export const SEND_STATUS_NOTICES: Partial<Record<SendStatus, string>> = {
+  degraded: "Connection is poor - the reply may take a while",
+  queued_offline:
+    "Offline mode is on - message will be sent when you go back online",
+  queued_reconnecting: "Reconnecting - message will be sent once connected",
+  blocked_needs_reauth:
+    "Not sent - the Gateway rejected your token. Update it in Settings and try again",
+  not_connected: "Not connected - message will be sent when reconnected",
+};
+
+/** How a message sent now would be handled */
+export async function getSendStatus(): Promise<SendStatus> {
+  return invoke<SendStatus>("get_send_status");
+}
+
+/** The message was not sent and will not be until the user signs in again */
+export function isBlocked(outcome: SendOutcome): boolean {
+  return outcome.status === "blocked_needs_reauth";
+}
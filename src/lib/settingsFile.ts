@@ -0,0 +1,25 @@
+/**
+ * Shareable settings file
+ * Exports and imports the backend-managed settings (data saver, response
+ * cache, discovery hosts, DNS) so a team can share a known-good
+ * configuration.
+ * Secrets such as the Gateway token are never included.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+/** Sections a settings file can contain */
+export type SettingsSection =
+  | "dataSaver"
+  | "responseCache"
+  | "discoveryHosts"
+  | "dns";
+
+export async function exportSettings(path: string): Promise<void> {
+  return invoke("export_settings", { path });
+}
+
+/** Apply a settings file; returns the sections it replaced */
+export async function importSettings(path: string): Promise<SettingsSection[]> {
+  return invoke<SettingsSection[]>("import_settings", { path });
+}
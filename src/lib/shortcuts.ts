@@ -0,0 +1,46 @@
+/**
+ * Global shortcut registration status
+ * The backend tracks each global shortcut (`shortcuts:status`); on Wayland
+ * it binds them through the desktop portal, which then also handles the
+ * Quick Ask toggle the frontend registers elsewhere
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type ShortcutId = "ask_selection" | "quick_ask";
+
+export interface ShortcutStatus {
+  id: ShortcutId;
+  accelerator: string;
+  backend: "native" | "portal";
+  registered: boolean;
+  /** Keys the user picked in the portal dialog */
+  trigger: string | null;
+  /** Why the shortcut failed or may not fire */
+  warning: string | null;
+  /** Another app holds the keys */
+  conflict: boolean;
+}
+
+/** Display names of the shortcuts */
+export const SHORTCUT_LABELS: Record<ShortcutId, string> = {
+  ask_selection: "Ask about selection",
+  quick_ask: "Quick Ask",
+};
+
+export async function getShortcutStatus(): Promise<ShortcutStatus[]> {
+  return invoke<ShortcutStatus[]>("get_shortcut_status");
+}
+
+/** Report the outcome of registering a frontend shortcut */
+export async function reportShortcutStatus(
+  id: ShortcutId,
+  registered: boolean,
+  error?: string,
+): Promise<void> {
+  await invoke("report_shortcut_status", {
+    id,
+    registered,
+    error: error ?? null,
+  });
+}
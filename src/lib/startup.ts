@@ -0,0 +1,54 @@
+/**
+ * Startup health check
+ * The backend checks keychain access, stored credentials, discovery and the
+ * last Gateway once on launch and emits `startup:health` with the action to
+ * take
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export type StartupAction = "onboarding" | "reconnect" | "fix_it";
+
+export type StartupIssue = "keychain_unavailable" | "gateway_unreachable";
+
+export interface StartupHealth {
+  /** ISO timestamp */
+  checkedAt: string;
+  keychainAccessible: boolean;
+  credentialsStored: boolean;
+  discovery: {
+    found: number;
+    reachable: number;
+    /** Fastest first */
+    reachableUrls: string[];
+  };
+  lastGateway: {
+    url: string;
+    /** ISO timestamp */
+    connectedAt: string;
+    reachable: boolean;
+    responseTimeMs: number | null;
+  } | null;
+  action: StartupAction;
+  issues: StartupIssue[];
+}
+
+/**
+ * Call with the startup health report once it is available, whether the
+ * check finished before or after this window loaded
+ */
+export async function onStartupHealth(
+  callback: (health: StartupHealth) => void,
+): Promise<UnlistenFn> {
+  const unlisten = await listen<StartupHealth>("startup:health", (event) =>
+    callback(event.payload),
+  );
+  const health = await invoke<StartupHealth | null>("get_startup_health");
+  if (health) {
+    unlisten();
+    callback(health);
+    return () => {};
+  }
+  return unlisten;
+}
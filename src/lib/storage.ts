@@ -0,0 +1,59 @@
+/**
+ * Log and cache size management
+ * The backend keeps logs and caches under configurable caps, deleting the
+ * oldest files first
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type StorageCategory = "logs" | "cache";
+
+export interface StorageSettings {
+  logLimitMb: number;
+  cacheLimitMb: number;
+}
+
+export interface CategoryUsage {
+  category: StorageCategory;
+  bytes: number;
+  files: number;
+  limitBytes: number;
+}
+
+export interface MaintenanceReport {
+  /** ISO timestamp */
+  ranAt: string;
+  filesRemoved: number;
+  reclaimedBytes: number;
+}
+
+export interface StorageUsage {
+  categories: CategoryUsage[];
+  /** Latest run since the app started */
+  lastMaintenance: MaintenanceReport | null;
+}
+
+export const STORAGE_CATEGORY_LABELS: Record<StorageCategory, string> = {
+  logs: "Logs",
+  cache: "Caches",
+};
+
+export async function getStorageUsage(): Promise<StorageUsage> {
+  return invoke<StorageUsage>("get_storage_usage");
+}
+
+export async function getStorageSettings(): Promise<StorageSettings> {
+  return invoke<StorageSettings>("get_storage_settings");
+}
+
+/** Saving prunes down to the new caps in the background */
+export async function setStorageSettings(
+  settings: StorageSettings,
+): Promise<void> {
+  await invoke("set_storage_settings", { settings });
+}
+
+/** Prune now instead of waiting for the next scheduled run */
+export async function runStorageMaintenance(): Promise<MaintenanceReport> {
+  return invoke<MaintenanceReport>("run_storage_maintenance");
+}
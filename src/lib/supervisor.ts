@@ -0,0 +1,39 @@
+/**
+ * Background task supervision
+ * The backend restarts monitors that panic or stop making progress and
+ * reports each restart as `internal:task_restarted`; the task list is also
+ * part of the diagnostics bundle
+ */
+
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export type TaskFailure = "panicked" | "stalled";
+
+export interface TaskRestarted {
+  task: string;
+  reason: TaskFailure;
+  /** Panic message, credentials scrubbed */
+  message: string | null;
+  /** Restarts in a row, this one included */
+  restarts: number;
+  /** Wait before the task starts again */
+  delayMs: number;
+}
+
+export interface TaskStatus {
+  name: string;
+  running: boolean;
+  restarts: number;
+  lastFailure: TaskFailure | null;
+  /** Restarted too many times in a row; stays stopped */
+  gaveUp: boolean;
+  secsSinceHeartbeat: number;
+}
+
+export async function onTaskRestarted(
+  callback: (event: TaskRestarted) => void,
+): Promise<UnlistenFn> {
+  return listen<TaskRestarted>("internal:task_restarted", (event) =>
+    callback(event.payload),
+  );
+}
@@ -0,0 +1,60 @@
+/**
+ * Gateway system events: scheduled job results and channel messages
+ * The backend turns `cron.*` / `channel.*` Gateway events into
+ * `system:cron` / `system:channel_message` and posts desktop notifications
+ * for them according to these rules
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export type CronNotify = "all" | "failures" | "off";
+
+export interface SystemNotificationSettings {
+  cronResults: CronNotify;
+  channelMessages: boolean;
+  /** Also notify while the main window has focus */
+  whenFocused: boolean;
+}
+
+export interface CronResult {
+  jobId: string | null;
+  name: string | null;
+  ok: boolean;
+  /** The job's output, or its error when it failed */
+  summary: string | null;
+  sessionKey: string | null;
+}
+
+export interface ChannelMessage {
+  channel: string;
+  sender: string | null;
+  text: string;
+  sessionKey: string | null;
+}
+
+export async function getSystemNotificationSettings(): Promise<SystemNotificationSettings> {
+  return invoke<SystemNotificationSettings>(
+    "get_system_notification_settings",
+  );
+}
+
+export async function setSystemNotificationSettings(
+  settings: SystemNotificationSettings,
+): Promise<void> {
+  await invoke("set_system_notification_settings", { settings });
+}
+
+export async function onCronResult(
+  callback: (result: CronResult) => void,
+): Promise<UnlistenFn> {
+  return listen<CronResult>("system:cron", (event) => callback(event.payload));
+}
+
+export async function onChannelMessage(
+  callback: (message: ChannelMessage) => void,
+): Promise<UnlistenFn> {
+  return listen<ChannelMessage>("system:channel_message", (event) =>
+    callback(event.payload),
+  );
+}
@@ -0,0 +1,19 @@
+/**
+ * Tray icon and global shortcut recovery
+ * After a crash and quick restart, the tray icon or shortcuts may fail to
+ * register; the backend retries on its own, and this redoes both on demand
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type { ShortcutStatus } from "./shortcuts";
+import type { TrayStatus } from "./tray";
+
+export interface SystemIntegrationStatus {
+  tray: TrayStatus;
+  /** Quick Ask reports back later through `shortcuts:status` */
+  shortcuts: ShortcutStatus[];
+}
+
+export async function reinitializeSystemIntegration(): Promise<SystemIntegrationStatus> {
+  return invoke<SystemIntegrationStatus>("reinitialize_system_integration");
+}
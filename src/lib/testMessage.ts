@@ -0,0 +1,48 @@
+/**
+ * End-to-end test message
+ * The backend sends a short canned prompt on a throwaway session and reports
+ * how far it got: connect, send, stream, complete
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type TestStage = "connect" | "send" | "stream" | "complete";
+
+export interface TestStageResult {
+  stage: TestStage;
+  ok: boolean;
+  /** Time since the previous stage */
+  durationMs: number;
+  detail: string | null;
+}
+
+export interface TestMessageReport {
+  ok: boolean;
+  /** Stages reached, in order */
+  stages: TestStageResult[];
+  /** Plain text excerpt of the reply */
+  reply: string | null;
+}
+
+export const TEST_STAGE_LABELS: Record<TestStage, string> = {
+  connect: "Connection",
+  send: "Sending",
+  stream: "Streaming",
+  complete: "Reply",
+};
+
+/** Connects to `gatewayUrl` first if not connected yet */
+export async function sendTestMessage(
+  gatewayUrl?: string,
+): Promise<TestMessageReport> {
+  return invoke<TestMessageReport>("send_test_message", {
+    gatewayUrl: gatewayUrl ?? null,
+  });
+}
+
+/** The stage that failed, if any */
+export function failedStage(
+  report: TestMessageReport,
+): TestStageResult | undefined {
+  return report.stages.find((stage) => !stage.ok);
+}
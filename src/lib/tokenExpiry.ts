@@ -0,0 +1,49 @@
+/**
+ * Gateway token expiry and re-pairing
+ * The backend warns (`gateway:token_expiring`) when the token is about to
+ * expire; re-pairing uses a device code shown as a QR code, and reports the
+ * outcome with `gateway:repaired` or `gateway:repair_failed`
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface TokenExpiryStatus {
+  /** ISO timestamp; null when the token doesn't expire */
+  expiresAt: string | null;
+  /** Days left while within the warning window, otherwise null */
+  expiringInDays: number | null;
+  warnDays: number;
+}
+
+/** Payload of `gateway:token_expiring` */
+export interface TokenExpiring {
+  expiresAt: string;
+  daysLeft: number;
+}
+
+export interface PairingCode {
+  userCode: string;
+  verificationUrl: string;
+  /** QR code of the verification URL (SVG document) */
+  qrSvg: string;
+  /** ISO timestamp */
+  expiresAt: string;
+}
+
+export async function getTokenExpiry(): Promise<TokenExpiryStatus> {
+  return invoke<TokenExpiryStatus>("get_token_expiry");
+}
+
+export async function setTokenExpiryWarning(
+  days: number,
+): Promise<TokenExpiryStatus> {
+  return invoke<TokenExpiryStatus>("set_token_expiry_warning", { days });
+}
+
+export async function startRepair(): Promise<PairingCode> {
+  return invoke<PairingCode>("start_repair");
+}
+
+export async function cancelRepair(): Promise<void> {
+  return invoke("cancel_repair");
+}
@@ -0,0 +1,41 @@
+/**
+ * System tray availability
+ * On desktops without a tray (e.g. GNOME without the AppIndicator
+ * extension) the backend emits `tray:unavailable` and then `tray:status`
+ * whenever the status the tooltip would show changes; the main window shows
+ * that status and the tray actions itself until `tray:available` says a
+ * retry created the icon after all
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type TrayProtocol = "native" | "status_notifier";
+
+/** Tray menu actions that can be run from the window fallback */
+export type TrayAction =
+  | "tray_show_hide"
+  | "tray_new_conversation"
+  | "tray_quick_ask"
+  | "tray_repair"
+  | "tray_quit";
+
+/**
+ * Payload of `tray:unavailable` and `tray:available`, and result of
+ * `getTrayStatus`
+ */
+export interface TrayStatus {
+  available: boolean;
+  protocol: TrayProtocol | null;
+  /** Why there is no tray */
+  reason: string | null;
+  /** What the tooltip shows (connection, runs, unread, token expiry) */
+  statusText: string;
+}
+
+export async function getTrayStatus(): Promise<TrayStatus> {
+  return invoke<TrayStatus>("get_tray_status");
+}
+
+export async function runTrayAction(id: TrayAction): Promise<void> {
+  await invoke("run_tray_action", { id });
+}
@@ -0,0 +1,87 @@
+/**
+ * Agent workspace files on the Gateway
+ * Lists, previews and downloads artifacts left by agent runs; downloads
+ * report progress with `files:download_progress`
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export interface WorkspaceEntry {
+  name: string;
+  /** Path relative to the workspace root */
+  path: string;
+  isDir: boolean;
+  size: number | null;
+  /** ISO timestamp */
+  modifiedAt: string | null;
+}
+
+export interface WorkspaceFile {
+  path: string;
+  size: number;
+  /** UTF-8 contents; null for binary files */
+  text: string | null;
+  /** Base64 contents of binary files */
+  data: string | null;
+}
+
+export interface DownloadProgress {
+  downloadId: string;
+  path: string;
+  received: number;
+  /** Null when the Gateway doesn't report the size */
+  total: number | null;
+}
+
+export interface DownloadedFile {
+  /** Local path the file was saved to */
+  destination: string;
+  size: number;
+}
+
+export async function listWorkspaceFiles(
+  sessionKey: string,
+  path?: string,
+): Promise<WorkspaceEntry[]> {
+  return invoke<WorkspaceEntry[]>("list_workspace_files", { sessionKey, path });
+}
+
+export async function readWorkspaceFile(
+  sessionKey: string,
+  path: string,
+): Promise<WorkspaceFile> {
+  return invoke<WorkspaceFile>("read_workspace_file", { sessionKey, path });
+}
+
+/**
+ * Download a workspace file (to the Downloads folder unless `destinationDir`
+ * is given). Never overwrites: an existing name gets a " (1)" suffix.
+ */
+export async function downloadWorkspaceFile(
+  downloadId: string,
+  sessionKey: string,
+  path: string,
+  destinationDir?: string,
+): Promise<DownloadedFile> {
+  return invoke<DownloadedFile>("download_workspace_file", {
+    downloadId,
+    sessionKey,
+    path,
+    destinationDir,
+  });
+}
+
+export async function cancelWorkspaceDownload(
+  downloadId: string,
+): Promise<void> {
+  return invoke("cancel_workspace_download", { downloadId });
+}
+
+export async function onDownloadProgress(
+  callback: (progress: DownloadProgress) => void,
+): Promise<UnlistenFn> {
+  return listen<DownloadProgress>("files:download_progress", (event) =>
+    callback(event.payload),
+  );
+}
@@ -24,6 +24,15 @@ describe("Store", () => {
       expect(state.currentConversationId).toBe(conversation.id);
     });
 
+    it("should create a conversation with a given id", () => {
+      const conversation = useStore
+        .getState()
+        .createConversation("moltz:quick-ask");
+
+      expect(conversation.id).toBe("moltz:quick-ask");
+      expect(useStore.getState().currentConversationId).toBe("moltz:quick-ask");
+    });
+
     it("should select a conversation", () => {
       const store = useStore.getState();
       const conv1 = store.createConversation();
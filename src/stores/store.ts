@@ -20,7 +20,15 @@ import {
   deletePersistedMessage,
   deletePersistedMessages,
 } from "../lib/persistence";
-import { tryGetGatewayToken, setGatewayToken } from "../lib/keychain";
+import { guestModeActive } from "../lib/guest";
+import {
+  tryGetGatewayToken,
+  setGatewayToken,
+  reloadGatewayToken,
+  connectAndSaveGatewayToken,
+  type ConnectResult,
+} from "../lib/keychain";
+import { isBlocked, type SendOutcome } from "../lib/sendGuard";
 
 /**
  * Token usage statistics for a message
@@ -151,7 +159,8 @@ interface Store {
   currentConversation: Conversation | null;
 
   setConversationsLoading: (loading: boolean) => void;
-  createConversation: () => Conversation;
+  /** Create and select a conversation; `id` doubles as its session key */
+  createConversation: (id?: string) => Conversation;
   selectConversation: (id: string) => void;
   deleteConversation: (id: string) => void;
   updateConversation: (id: string, updates: Partial<Conversation>) => void;
@@ -181,13 +190,21 @@ interface Store {
   appendToCurrentMessage: (content: string) => void;
   /** PERF: Flush any buffered streaming content immediately */
   flushStreamingBuffer: () => void;
-  completeCurrentMessage: (usage?: TokenUsage) => void;
+  /** `finalContent` replaces the streamed content when given */
+  completeCurrentMessage: (usage?: TokenUsage, finalContent?: string) => void;
   currentStreamingMessageId: string | null;
 
   // Settings
   settings: Settings;
   updateSettings: (settings: Partial<Settings>) => Promise<void>;
   loadSettings: () => Promise<void>;
+  /** Pick up a token the backend stored (e.g. after rotation) */
+  reloadGatewayToken: () => Promise<void>;
+  /**
+   * Connect with new Gateway credentials and save them only once the
+   * Gateway accepts them; nothing is saved if the connection fails
+   */
+  connectAndSaveGateway: (url: string, token: string) => Promise<ConnectResult>;
 }
 
 const generateId = () => crypto.randomUUID();
@@ -320,9 +337,9 @@ export const useStore = create<Store>()((set, get) => ({
 
   setConversationsLoading: (loading) => set({ conversationsLoading: loading }),
 
-  createConversation: () => {
+  createConversation: (id) => {
     const conversation: Conversation = {
-      id: generateId(),
+      id: id ?? generateId(),
       title: "New Chat",
       messages: [],
       createdAt: new Date(),
@@ -624,12 +641,12 @@ export const useStore = create<Store>()((set, get) => ({
 
           // Retry sending (using the same invoke as ChatView)
           const { invoke } = await import("@tauri-apps/api/core");
-          await invoke("send_message", {
+          const outcome = await invoke<SendOutcome>("send_message", {
             params: {
               message: msg.content,
               session_key: conv.id,
               model: conv.model || get().settings.defaultModel,
-              thinking: conv.thinkingEnabled ? "low" : null,
+              thinking: conv.thinkingEnabled ? "low" : "off",
               attachments:
                 msg.attachments?.map((a) => ({
                   id: a.id,
@@ -640,6 +657,12 @@ export const useStore = create<Store>()((set, get) => ({
             },
           });
 
+          if (isBlocked(outcome)) {
+            // Retrying won't help until the token is updated
+            get().markMessageFailed(conv.id, msg.id, "Gateway token rejected");
+            continue;
+          }
+
           // Success - mark as sent
           get().markMessageSent(conv.id, msg.id);
         } catch (err) {
@@ -759,7 +782,7 @@ export const useStore = create<Store>()((set, get) => ({
     streamingBuffer.flush();
   },
 
-  completeCurrentMessage: (usage?: TokenUsage) => {
+  completeCurrentMessage: (usage?: TokenUsage, finalContent?: string) => {
     const { currentConversationId, currentStreamingMessageId } = get();
     if (!currentConversationId || !currentStreamingMessageId) return;
 
@@ -774,7 +797,12 @@ export const useStore = create<Store>()((set, get) => ({
               ...c,
               messages: c.messages.map((m) =>
                 m.id === currentStreamingMessageId
-                  ? { ...m, isStreaming: false, usage }
+                  ? {
+                      ...m,
+                      content: finalContent || m.content,
+                      isStreaming: false,
+                      usage,
+                    }
                   : m,
               ),
             }
@@ -829,6 +857,9 @@ export const useStore = create<Store>()((set, get) => ({
         await setGatewayToken(updates.gatewayToken);
       }
 
+      // Guest mode keeps settings in memory only
+      if (guestModeActive()) return;
+
       // Save other settings to localStorage (token excluded)
       const { gatewayToken: _gatewayToken, ...settingsWithoutToken } =
         settingsToSave;
@@ -880,4 +911,24 @@ export const useStore = create<Store>()((set, get) => ({
       console.error("Failed to load settings:", err);
     }
   },
+
+  reloadGatewayToken: async () => {
+    const token = await reloadGatewayToken();
+    if (!token) return;
+    set((state) => ({
+      settings: { ...state.settings, gatewayToken: token },
+    }));
+  },
+
+  connectAndSaveGateway: async (url, token) => {
+    // The backend stores the token, so it only goes into memory here
+    const result = await connectAndSaveGatewayToken(url, token);
+    set((state) => ({
+      settings: { ...state.settings, gatewayToken: token },
+    }));
+    await get().updateSettings({
+      gatewayUrl: result.protocol_switched ? result.used_url : url,
+    });
+    return result;
+  },
 }));